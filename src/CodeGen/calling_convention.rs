@@ -1,7 +1,30 @@
-use crate::{Target::{x64::X64Reg, Arch, CallConv}, IR::TypeMetadata};
+use crate::{Target::{x64::X64Reg, Arch, CallConv, Environment, ObjFormat, Triple, Vendor, OS}, IR::{TypeMetadata, VerifyError}};
 
 use super::Reg;
 
+/// The class of an argument or return value which describes how it is passed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArgClass {
+    /// Passed in a general purpose register
+    Integer,
+    /// Passed in a sse (xmm) register
+    Sse,
+    /// Passed over memory (sysv: copied onto the stack, windows: over a pointer to a copy). 
+    /// If it's the return value the caller passes a hidden pointer to the memory which receives it
+    Memory,
+}
+
+impl ArgClass {
+    /// The type which the eightbyte is moved as (the adress of the memory for `ArgClass::Memory`)
+    pub fn ty(&self) -> TypeMetadata {
+        match self {
+            ArgClass::Integer => TypeMetadata::i64,
+            ArgClass::Sse => TypeMetadata::f64,
+            ArgClass::Memory => TypeMetadata::ptr,
+        }
+    }
+}
+
 /// A more machine specifc calling convention
 /// (Just a wrapper around the normal calling convention but with some pretty handy functions)
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -35,6 +58,36 @@ impl MachineCallingConvention {
             CallConv::WasmBasicCAbi => Reg::wasm(0, TypeMetadata::i32),
         }
     }
+
+    /// returns the return registers for the values of a tuple (`elems`)
+    /// 
+    /// Intengers are returned in rax:rdx and floats in xmm0:xmm1 (like the eightbytes of a sysv struct)
+    pub fn return_regs(&self, arch: Arch, elems: &[TypeMetadata]) -> Vec<Reg> {
+        if arch != Arch::X86_64 {
+            todo!("multiple return values are only supported on x64 and wasm");
+        }
+
+        if self.call_conv == CallConv::WindowsFastCall {
+            panic!("tuples can't be returned with the windows calling convention");
+        }
+
+        let mut gprs = vec![X64Reg::Rax, X64Reg::Rdx].into_iter();
+        let mut fprs = vec![X64Reg::Xmm0, X64Reg::Xmm1].into_iter();
+
+        let mut regs = Vec::new();
+
+        for elem in elems {
+            let reg = if elem.float() {
+                fprs.next()
+            } else {
+                gprs.next().map(|reg| reg.sub_ty(*elem))
+            };
+
+            regs.push(Reg::x64(reg.expect("too many values in the tuple (only two intengers and two floats can be returned)")));
+        }
+
+        regs
+    }
     
     /// returns the args for the specifc architecture
     pub fn arg(&self, arch: Arch, ty: TypeMetadata, idx: usize) -> Option<Reg> {
@@ -89,7 +142,7 @@ impl MachineCallingConvention {
                     Arch::X86_64 => {
                         let args = vec![
                             Reg::x64(X64Reg::Rdi.sub_ty(ty)), Reg::x64(X64Reg::Rsi.sub_ty(ty)), 
-                            Reg::x64(X64Reg::Rcx.sub_ty(ty)), Reg::x64(X64Reg::Rdx.sub_ty(ty)), 
+                            Reg::x64(X64Reg::Rdx.sub_ty(ty)), Reg::x64(X64Reg::Rcx.sub_ty(ty)), 
                             Reg::x64(X64Reg::R8.sub_ty(ty)), Reg::x64(X64Reg::R9.sub_ty(ty))
                         ];
                         let arg = args.get(idx).cloned();
@@ -143,7 +196,7 @@ impl MachineCallingConvention {
                 match arch {
                     Arch::X86_64 => vec![
                         Reg::x64(X64Reg::Rdi), Reg::x64(X64Reg::Rsi), 
-                        Reg::x64(X64Reg::Rcx), Reg::x64(X64Reg::Rdx), 
+                        Reg::x64(X64Reg::Rdx), Reg::x64(X64Reg::Rcx), 
                         Reg::x64(X64Reg::R8), Reg::x64(X64Reg::R9)
                     ],
                     _ => todo!()
@@ -187,7 +240,8 @@ impl MachineCallingConvention {
     /// Returns the fp registers which are saved by the caller
     pub fn caller_saved_fps(&self, arch: Arch) -> Vec<Reg> {
         match (arch, self.call_conv) {
-            (Arch::X86_64, CallConv::SystemV) => Vec::new(),
+            // all xmm registers are caller saved in the system v abi
+            (Arch::X86_64, CallConv::SystemV) => vec![Reg::x64(X64Reg::Xmm0), Reg::x64(X64Reg::Xmm1), Reg::x64(X64Reg::Xmm2), Reg::x64(X64Reg::Xmm3), Reg::x64(X64Reg::Xmm4), Reg::x64(X64Reg::Xmm5), Reg::x64(X64Reg::Xmm6), Reg::x64(X64Reg::Xmm7), Reg::x64(X64Reg::Xmm8), Reg::x64(X64Reg::Xmm9), Reg::x64(X64Reg::Xmm10), Reg::x64(X64Reg::Xmm11), Reg::x64(X64Reg::Xmm12), Reg::x64(X64Reg::Xmm13), Reg::x64(X64Reg::Xmm14), Reg::x64(X64Reg::Xmm15)],
             (Arch::X86_64, CallConv::WindowsFastCall) => vec![Reg::x64(X64Reg::Xmm0), Reg::x64(X64Reg::Xmm1), Reg::x64(X64Reg::Xmm2), Reg::x64(X64Reg::Xmm3), Reg::x64(X64Reg::Xmm4), Reg::x64(X64Reg::Xmm5)],
             _ => Vec::new(),
         }
@@ -201,4 +255,86 @@ impl MachineCallingConvention {
             _ => Vec::new(),
         }
     }
-}
\ No newline at end of file
+
+    /// Classifies the type for passing it as an argument or return value.
+    /// 
+    /// Returns one class for every eightbyte of the type 
+    /// (or a single `ArgClass::Memory` if it's passed over memory).
+    /// Aggregates can only be classified for the x86-64 calling conventions
+    pub fn classify(&self, arch: Arch, ty: TypeMetadata) -> Result<Vec<ArgClass>, VerifyError> {
+        if !ty.aggregate() {
+            return Ok(vec![if ty.float() { ArgClass::Sse } else { ArgClass::Integer }]);
+        }
+
+        if arch != Arch::X86_64 {
+            Err(VerifyError::AggregateArgUnsupported(self.call_conv))?
+        }
+
+        let triple = self.layout_triple(arch);
+        let size = ty.size(&triple);
+
+        match self.call_conv {
+            CallConv::WindowsFastCall => {
+                // aggregates which have the size of an intenger are passed like one
+                // (even if they only contain floats)
+                Ok(match size {
+                    1 | 2 | 4 | 8 => vec![ArgClass::Integer],
+                    _ => vec![ArgClass::Memory],
+                })
+            },
+            CallConv::SystemV => {
+                if size > 16 {
+                    return Ok(vec![ArgClass::Memory]);
+                }
+
+                let mut classes = vec![ArgClass::Sse; size.div_ceil(8)];
+
+                for (offset, scalar) in ty.scalars(&triple) {
+                    if offset % scalar.align(&triple).max(1) != 0 {
+                        return Ok(vec![ArgClass::Memory]); // unaligned fields
+                    }
+
+                    if !scalar.float() {
+                        classes[offset / 8] = ArgClass::Integer;
+                    }
+                }
+
+                Ok(classes)
+            },
+            call_conv => Err(VerifyError::AggregateArgUnsupported(call_conv)),
+        }
+    }
+
+    /// Returns if the type is returned over memory
+    /// (then the caller passes the adress for it as an hidden first argument)
+    pub fn return_in_memory(&self, arch: Arch, ty: TypeMetadata) -> Result<bool, VerifyError> {
+        Ok(self.classify(arch, ty)?.contains(&ArgClass::Memory))
+    }
+
+    /// Returns the registers which the eightbytes of an aggregate that is returned in registers are returned in
+    pub fn aggregate_return_regs(&self, arch: Arch, classes: &[ArgClass]) -> Vec<Reg> {
+        let elems = classes.iter().map(|class| class.ty()).collect::<Vec<_>>();
+
+        if self.call_conv == CallConv::WindowsFastCall {
+            // only aggregates with the size of an intenger are returned in registers
+            return vec![self.return_reg(arch, TypeMetadata::i64)];
+        }
+
+        self.return_regs(arch, &elems)
+    }
+
+    /// The triple which the types are layed out for
+    pub(crate) fn layout_triple(&self, arch: Arch) -> Triple {
+        Triple {
+            arch: arch,
+            vendor: Vendor::Unknown,
+            os: match self.call_conv {
+                CallConv::WindowsFastCall => OS::Win32,
+                CallConv::AppleAarch64 => OS::MacOS,
+                _ => OS::Linux,
+            },
+            env: Environment::Unknown,
+            bin: ObjFormat::Default,
+        }
+    }
+}
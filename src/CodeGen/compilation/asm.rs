@@ -0,0 +1,205 @@
+use super::{CompilationHelper, VarLocation};
+use crate::{prelude::*, CodeGen::*, Target::{x64::X64Reg, Arch}};
+
+impl CompilationHelper {
+    #[allow(missing_docs)]
+    pub fn compile_asm(&mut self, node: &InlineAsm, mc_sink: &mut Vec<MachineInstr>, _: &Block, _: &mut crate::prelude::Module) {
+        // the registers which the assembly overwrites or which need to hold a specific value
+        let mut fixed = Vec::new();
+
+        for constraint in &node.constraints {
+            match constraint {
+                AsmConstraint::OutFixed(reg) | AsmConstraint::InFixed(reg) | AsmConstraint::Clobber(reg) => fixed.push(self.asm_reg(reg, TypeMetadata::i64)),
+                _ => {},
+            }
+        }
+
+        let in_constraints = node.in_constraints();
+
+        // inputs which already are in a register (which isn't overwritten) are used directly
+        let mut direct = Vec::new();
+        for (constraint, arg) in in_constraints.iter().zip(&node.args) {
+            direct.push(match constraint {
+                AsmConstraint::InReg => self.asm_direct_reg(arg, &fixed),
+                _ => None,
+            });
+        }
+
+        let mut used = fixed.clone();
+        used.extend(direct.iter().flatten());
+
+        let mut scratches = Vec::new();
+        let mut operands = Vec::new();
+        let mut moves = Vec::new();
+
+        let mut out_move = None;
+
+        if let (Some(out), Some(constraint)) = (&node.out, node.out_constraint()) {
+            let loc = *self.vars.get(&out.name).expect("expected valid variable");
+
+            let reg = match (constraint, loc) {
+                (AsmConstraint::OutReg, VarLocation::Reg(reg)) if !Reg::contains_reg(reg, &fixed) => reg,
+                (AsmConstraint::OutReg, _) => {
+                    let reg = self.asm_scratch(out.ty, &used);
+                    used.push(reg);
+                    scratches.push(reg);
+                    reg
+                },
+                (AsmConstraint::OutFixed(reg), _) => self.asm_reg(&reg, out.ty),
+                _ => unreachable!(),
+            };
+
+            if VarLocation::Reg(reg) != loc {
+                out_move = Some((reg, loc));
+            }
+
+            operands.push(MachineOperand::Reg(reg));
+        }
+
+        for ((constraint, arg), direct) in in_constraints.iter().zip(&node.args).zip(direct) {
+            let ty = arg.get_ty();
+
+            let reg = match (constraint, direct) {
+                (AsmConstraint::InImm, _) => {
+                    operands.push(arg.into_mi(self));
+                    continue;
+                },
+                (AsmConstraint::InReg, Some(reg)) => {
+                    operands.push(MachineOperand::Reg(reg));
+                    continue;
+                },
+                (AsmConstraint::InReg, None) => {
+                    let reg = self.asm_scratch(ty, &used);
+                    used.push(reg);
+                    scratches.push(reg);
+                    reg
+                },
+                (AsmConstraint::InFixed(reg), _) => self.asm_reg(reg, ty),
+                _ => unreachable!(),
+            };
+
+            moves.push((reg, arg));
+            operands.push(MachineOperand::Reg(reg));
+        }
+
+        // the variables which are stored in the used registers need to survive the assembly
+        let mut overwritten = fixed;
+        overwritten.extend(scratches);
+
+        let saved = self.save_vars_in(self.get_vars_to_save_for_call(node.clone_box()), &overwritten, mc_sink);
+
+        for (reg, arg) in moves {
+            let mut instr = if let IROperand::Var(var) = arg {
+                if self.allocated_vars.contains(&var.name) {
+                    let mut instr = MachineInstr::new(MachineMnemonic::AdrMove);
+                    instr.add_operand((*self.vars.get(&var.name).expect("expected valid variable")).into());
+                    instr
+                } else {
+                    let mut instr = MachineInstr::new(MachineMnemonic::Move);
+
+                    // the register of the variable could already be overwritten by another input
+                    if let Some((save, _)) = saved.get(&var.name) {
+                        instr.add_operand(MachineOperand::Stack(save.0, save.1));
+                    } else {
+                        instr.add_operand(arg.into_mi(self));
+                    }
+
+                    instr
+                }
+            } else {
+                let mut instr = MachineInstr::new(MachineMnemonic::Move);
+                instr.add_operand(arg.into_mi(self));
+                instr
+            };
+
+            instr.set_out(MachineOperand::Reg(reg));
+            instr.meta = arg.get_ty();
+
+            mc_sink.push(instr);
+        }
+
+        let mut instr = MachineInstr::new(MachineMnemonic::InlineAsm(node.asm.to_owned()));
+
+        for operand in operands {
+            instr.add_operand(operand);
+        }
+
+        instr.meta = if let Some(out) = &node.out { out.ty } else { TypeMetadata::Void };
+
+        mc_sink.push(instr);
+
+        if let Some((reg, loc)) = out_move {
+            let mut instr = MachineInstr::new(MachineMnemonic::Move);
+
+            instr.set_out(loc.into());
+            instr.add_operand(MachineOperand::Reg(reg));
+            instr.meta = node.out.as_ref().unwrap().ty;
+
+            mc_sink.push(instr);
+        }
+
+        self.restore_caller_saved(saved, mc_sink);
+
+        if let Some(out) = &node.out {
+            if let Some(phi_loc) = self.phi_vars.get(&out.name) {
+                let loc = *self.vars.get(&out.name).unwrap();
+
+                let mut instr = MachineInstr::new(MachineMnemonic::Move);
+                instr.set_out((*phi_loc).into());
+                instr.add_operand(loc.into());
+                instr.meta = out.ty;
+                mc_sink.push(instr);
+            }
+        }
+    }
+
+    /// Parses the name of a register used in an inline assembly constraint
+    fn asm_reg(&self, name: &str, ty: TypeMetadata) -> Reg {
+        match self.arch {
+            Arch::X86_64 => {
+                let reg = X64Reg::parse(name.to_string()).unwrap_or_else(|| panic!("unknown register in inline assembly constraint: {}", name));
+
+                // the constraint only selects the register (its size is the one of the operand)
+                Reg::x64(reg.sub_ty(ty))
+            },
+            _ => todo!("inline assembly is only supported on x64"),
+        }
+    }
+
+    /// Returns the register of the operand if it can be passed to the assembly directly
+    fn asm_direct_reg(&self, arg: &IROperand, fixed: &Vec<Reg>) -> Option<Reg> {
+        let IROperand::Var(var) = arg else { return None };
+
+        if self.allocated_vars.contains(&var.name) {
+            return None;
+        }
+
+        match self.vars.get(&var.name) {
+            Some(VarLocation::Reg(reg)) if !Reg::contains_reg(*reg, fixed) => Some(*reg),
+            _ => None,
+        }
+    }
+
+    /// Selects a caller saved register which isn't used by the assembly
+    /// (registers which don't hold any variable are prefered)
+    fn asm_scratch(&self, ty: TypeMetadata, used: &Vec<Reg>) -> Reg {
+        let (free, candidates) = if ty.float() {
+            (&self.alloc.ffpregs, self.call.caller_saved_fps(self.arch))
+        } else {
+            (&self.alloc.fregs, self.call.caller_saved_grs(self.arch))
+        };
+
+        let mut candidates = candidates.into_iter()
+            .filter(|reg| !reg.is(&self.tmp_reg) && !Reg::contains_reg(*reg, used))
+            .collect::<Vec<Reg>>();
+
+        candidates.sort_by_key(|reg| !Reg::contains_reg(*reg, free));
+
+        let reg = *candidates.first().expect("no register is left for the inline assembly operand");
+
+        match reg {
+            Reg::x64(x64) => Reg::x64(x64.sub_ty(ty)),
+            Reg::wasm(..) => reg,
+        }
+    }
+}
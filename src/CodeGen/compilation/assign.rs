@@ -1,5 +1,5 @@
 use crate::prelude::Assign;
-use crate::IR::{Block, Const, Type, Var};
+use crate::IR::{Block, Const, FuncId, Global, Type, Var};
 use super::CompilationHelper;
 use crate::CodeGen::{MachineInstr, MachineMnemonic, MachineOperand};
 
@@ -11,7 +11,7 @@ impl CompilationHelper {
 
         instr.set_out(out.into());
 
-        instr.add_operand(MachineOperand::Imm(node.inner2.val()));
+        instr.add_operand(MachineOperand::constant(node.inner2));
 
         instr.meta = node.inner1.ty;
 
@@ -73,4 +73,48 @@ impl CompilationHelper {
             mc_sink.push(instr);
         }
     }
-}
\ No newline at end of file
+
+    #[allow(missing_docs)]
+    pub fn compile_assign_var_func(&mut self, node: &Assign<Var, FuncId>, mc_sink: &mut Vec<MachineInstr>, _: &Block, _: &mut crate::prelude::Module) {
+        let out = *self.vars.get(&node.inner1.name).unwrap();
+
+        let mut instr = MachineInstr::new(
+            MachineMnemonic::AdressLoad(node.inner2.name.to_string())
+        );
+
+        instr.set_out(out.into());
+
+        instr.meta = node.inner1.ty;
+
+        mc_sink.push( instr );
+
+        if let Some(phi_loc) = self.phi_vars.get(&node.inner1.name) {
+            let mut instr = MachineInstr::new(MachineMnemonic::Move);
+            instr.set_out((*phi_loc).into());
+            instr.add_operand(out.into());
+            mc_sink.push(instr);
+        }
+    }
+
+    #[allow(missing_docs)]
+    pub fn compile_assign_var_global(&mut self, node: &Assign<Var, Global>, mc_sink: &mut Vec<MachineInstr>, _: &Block, _: &mut crate::prelude::Module) {
+        let out = *self.vars.get(&node.inner1.name).unwrap();
+
+        let mut instr = MachineInstr::new(
+            MachineMnemonic::AdressLoad(node.inner2.name.to_string())
+        );
+
+        instr.set_out(out.into());
+
+        instr.meta = node.inner1.ty;
+
+        mc_sink.push( instr );
+
+        if let Some(phi_loc) = self.phi_vars.get(&node.inner1.name) {
+            let mut instr = MachineInstr::new(MachineMnemonic::Move);
+            instr.set_out((*phi_loc).into());
+            instr.add_operand(out.into());
+            mc_sink.push(instr);
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use super::CompilationHelper;
+use crate::{prelude::*, CodeGen::*};
+
+impl CompilationHelper {
+    #[allow(missing_docs)]
+    pub fn compile_atomic_load(&mut self, node: &AtomicLoad, mc_sink: &mut Vec<MachineInstr>, _: &Block, _: &mut crate::prelude::Module) {
+        let ptr = self.mem_ptr_operand(&node.ptr, mc_sink);
+
+        let mut instr = MachineInstr::new(MachineMnemonic::AtomicLoad(node.order));
+
+        instr.add_operand(ptr);
+
+        instr.meta = node.out.ty;
+
+        self.compile_atomic_out(instr, &node.out, mc_sink);
+    }
+
+    #[allow(missing_docs)]
+    pub fn compile_atomic_store(&mut self, node: &AtomicStore, mc_sink: &mut Vec<MachineInstr>, _: &Block, _: &mut crate::prelude::Module) {
+        let ptr = self.mem_ptr_operand(&node.ptr, mc_sink);
+
+        let mut instr = MachineInstr::new(MachineMnemonic::AtomicStore(node.order));
+
+        instr.add_operand(ptr);
+        instr.add_operand(node.val.into_mi(self));
+
+        instr.meta = node.val.get_ty();
+
+        mc_sink.push(instr);
+    }
+
+    #[allow(missing_docs)]
+    pub fn compile_atomic_rmw(&mut self, node: &AtomicRmw, mc_sink: &mut Vec<MachineInstr>, _: &Block, _: &mut crate::prelude::Module) {
+        let ptr = self.mem_ptr_operand(&node.ptr, mc_sink);
+
+        let mut instr = MachineInstr::new(MachineMnemonic::AtomicRmw(node.op, node.order, node.label()));
+
+        instr.add_operand(ptr);
+        instr.add_operand(node.val.into_mi(self));
+
+        instr.meta = node.out.ty;
+
+        self.compile_atomic_out(instr, &node.out, mc_sink);
+    }
+
+    #[allow(missing_docs)]
+    pub fn compile_cmpxchg(&mut self, node: &CmpXchg, mc_sink: &mut Vec<MachineInstr>, _: &Block, _: &mut crate::prelude::Module) {
+        let ptr = self.mem_ptr_operand(&node.ptr, mc_sink);
+
+        let mut instr = MachineInstr::new(MachineMnemonic::CmpXchg(node.order));
+
+        instr.add_operand(ptr);
+        instr.add_operand(node.cmp.into_mi(self));
+        instr.add_operand(node.new.into_mi(self));
+
+        instr.meta = node.out.ty;
+
+        self.compile_atomic_out(instr, &node.out, mc_sink);
+    }
+
+    #[allow(missing_docs)]
+    pub fn compile_fence(&mut self, node: &Fence, mc_sink: &mut Vec<MachineInstr>, _: &Block, _: &mut crate::prelude::Module) {
+        mc_sink.push(MachineInstr::new(MachineMnemonic::Fence(node.order)));
+    }
+
+    /// Pushes the atomic instruction with `out` as its output (and moves it into the phi location if needed)
+    fn compile_atomic_out(&mut self, mut instr: MachineInstr, out: &Var, mc_sink: &mut Vec<MachineInstr>) {
+        let out_loc: MachineOperand = (*self.vars.get(&out.name).expect("expected valid variable")).into();
+
+        instr.set_out(out_loc);
+
+        mc_sink.push(instr);
+
+        if let Some(phi_loc) = self.phi_vars.get(&out.name) {
+            let mut instr = MachineInstr::new(MachineMnemonic::Move);
+            instr.set_out((*phi_loc).into());
+            instr.add_operand(out_loc);
+            instr.meta = out.ty;
+            mc_sink.push(instr);
+        }
+    }
+}
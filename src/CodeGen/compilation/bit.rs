@@ -0,0 +1,53 @@
+use crate::CodeGen::{MachineInstr, MachineMnemonic};
+use crate::IR::{Block, ir::{BitOp, BitOpKind}};
+use crate::Target::{Arch, TargetFeature};
+
+use super::CompilationHelper;
+
+impl CompilationHelper {
+    #[allow(missing_docs)]
+    pub fn compile_bitop(&mut self, node: &BitOp, mc_sink: &mut Vec<MachineInstr>, _: &Block, _: &mut crate::prelude::Module) {
+        let op = node.inner1.into_mi(self);
+        let out = *self.vars.get(&node.inner2.name).expect("expected valid variable");
+
+        let mut instr = MachineInstr::new(match node.kind {
+            kind if self.bitop_fallback(kind) => MachineMnemonic::BitOpFallback(kind),
+            BitOpKind::Popcnt => MachineMnemonic::Popcnt,
+            BitOpKind::Clz => MachineMnemonic::Clz,
+            BitOpKind::Ctz => MachineMnemonic::Ctz,
+            BitOpKind::Bswap => MachineMnemonic::Bswap,
+        });
+
+        instr.set_out( out.into() );
+        instr.add_operand( op.into() );
+
+        instr.meta = node.inner2.ty;
+
+        mc_sink.push( instr );
+
+        if let Some(phi_loc) = self.phi_vars.get(&node.inner2.name) {
+            let mut instr = MachineInstr::new(MachineMnemonic::Move);
+            instr.set_out((*phi_loc).into());
+            instr.add_operand(out.into());
+
+            instr.meta = node.inner2.ty;
+            
+            mc_sink.push(instr);
+        }
+    }
+
+    /// Returns if the target has no instruction for the kind, so it gets computed without it
+    fn bitop_fallback(&self, kind: BitOpKind) -> bool {
+        match self.arch {
+            // wasm has all of them
+            Arch::Wasm32 | Arch::Wasm64 => false,
+            // popcnt, lzcnt and tzcnt are only available with their extensions
+            _ => match kind {
+                BitOpKind::Popcnt => !self.features.contains(&TargetFeature::Popcnt),
+                BitOpKind::Clz => !self.features.contains(&TargetFeature::Lzcnt),
+                BitOpKind::Ctz => !self.features.contains(&TargetFeature::Bmi1),
+                BitOpKind::Bswap => false,
+            },
+        }
+    }
+}
@@ -1,5 +1,7 @@
 use crate::prelude::{Block, Br, BrCond};
 use crate::CodeGen::{MachineInstr, MachineMnemonic, MachineOperand};
+use crate::Target::Arch;
+use crate::IR::MetadataValue;
 
 use super::CompilationHelper;
 
@@ -24,8 +26,18 @@ impl CompilationHelper {
         //     goto node.inner3;
         //}
         
-        let iftrue = node.inner2.name.to_owned();
-        let iffalse = node.inner3.name.to_owned();
+        let mut iftrue = node.inner2.name.to_owned();
+        let mut iffalse = node.inner3.name.to_owned();
+
+        let mut value = 0.0;
+
+        // the conditional jump is predicted as not taken, so if the true block is the likely one
+        // the jump should go to the false block (the condition is an i1, so comparing
+        // it with 1 instead of 0 jumps to the false block)
+        if self.arch == Arch::X86_64 && matches!(self.branch_weights(), Some((iftrue, iffalse)) if iftrue > iffalse) {
+            std::mem::swap(&mut iftrue, &mut iffalse);
+            value = 1.0;
+        }
 
         let src = *self.vars.get(&node.inner1.name).expect("expected valid variable");
         let src = src.into();
@@ -34,10 +46,21 @@ impl CompilationHelper {
             MachineMnemonic::BrCond(iftrue, iffalse)
         );
         cmp.add_operand(src);
-        cmp.add_operand(MachineOperand::Imm(0.0));
+        cmp.add_operand(MachineOperand::Imm(value));
         
         cmp.meta = node.inner1.ty;
 
         mc_sink.push( cmp );
     }
+
+    /// Returns the weights of the true and the false block out of the `!branch_weights [true, false]`
+    /// metadata of the node which currently gets compiled
+    fn branch_weights(&self) -> Option<(i64, i64)> {
+        let weights = self.metadata.get("branch_weights")?;
+
+        match weights.values() {
+            [MetadataValue::Int(iftrue), MetadataValue::Int(iffalse)] => Some((*iftrue, *iffalse)),
+            _ => None,
+        }
+    }
 }
\ No newline at end of file
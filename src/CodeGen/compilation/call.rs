@@ -1,59 +1,284 @@
 use std::collections::HashMap;
 
-use crate::{prelude::{Call, IROperand}, CodeGen::{MachineMnemonic, MachineOperand, Reg}, Target::Arch, IR::TypeMetadata};
+use crate::{prelude::{Call, CallIndirect, IROperand, Ir}, CodeGen::{ArgClass, MachineMnemonic, MachineOperand, Reg}, Target::{x64::X64Reg, Arch, CallConv}, IR::{TypeMetadata, Var}};
 use crate::IR::Block;
 use super::{CompilationHelper, VarLocation};
 use crate::CodeGen::MachineInstr;
 
 impl CompilationHelper {
     #[allow(missing_docs)]
-    pub fn compile_call(&mut self, node: &Call, mc_sink: &mut Vec<MachineInstr>, _: &Block, _: &mut crate::prelude::Module) {
+    pub fn compile_call(&mut self, node: &Call, mc_sink: &mut Vec<MachineInstr>, _: &Block, module: &mut crate::prelude::Module) {
+        let call = MachineInstr::new(
+            MachineMnemonic::Call(node.func.name.to_string())
+        );
+
+        // the arguments are passed (and the registers saved) like the called function expects it
+        let caller = self.call;
+
+        if let Some(call_conv) = node.func.ty.call_conv.or(module.getFunc(&node.func.name).and_then(|func| func.ty.call_conv)) {
+            self.call.call_conv = call_conv;
+        }
+
+        if node.tail {
+            self.compile_tail_call(node, mc_sink);
+        } else if node.is_noreturn(module) {
+            // the call never comes back, so nothing needs to be saved or restored
+            // (only the memory for a returned aggregate still needs to be passed)
+            let ret = if node.func.ty.ret.aggregate() { Some((&node.out, node.func.ty.ret)) } else { None };
+
+            self.compile_call_with(call, &node.args, HashMap::new(), ret, mc_sink);
+        } else {
+            let saved = self.save_caller_saved(self.get_vars_to_save_for_call(node.clone_box()), mc_sink);
+
+            self.compile_call_with(call, &node.args, saved, Some((&node.out, node.func.ty.ret)), mc_sink);
+        }
+
+        self.call = caller;
+    }
+
+    #[allow(missing_docs)]
+    pub fn compile_call_indirect(&mut self, node: &CallIndirect, mc_sink: &mut Vec<MachineInstr>, _: &Block, _: &mut crate::prelude::Module) {
+        let caller = self.call;
+
+        if let Some(call_conv) = node.ty.call_conv {
+            self.call.call_conv = call_conv;
+        }
+
+        let saved = self.save_caller_saved(self.get_vars_to_save_for_call(node.clone_box()), mc_sink);
+
+        // the adress could be stored in an argument register which gets overwritten
+        // while preparing the arguments, so we call through the saved copy
+        let target = if let Some((save, _)) = saved.get(&node.ptr.name) {
+            MachineOperand::Stack(save.0, save.1)
+        } else {
+            (*self.vars.get(&node.ptr.name).expect(&format!("expected valid variable: {}", node.ptr.name))).into()
+        };
+
+        let mut call = MachineInstr::new(
+            MachineMnemonic::CallIndirect(node.ty.to_owned())
+        );
+
+        call.add_operand(target);
+        call.meta = TypeMetadata::ptr;
+
+        self.compile_call_with(call, &node.args, saved, Some((&node.out, node.ty.ret)), mc_sink);
+
+        self.call = caller;
+    }
+
+    /// Moves the arguments of the tail call into their registers and jumps to the called function
+    /// 
+    /// The frame gets torn down before the jump, so nothing can be saved onto the stack.
+    /// Instead the moves between registers are ordered so that no argument gets overwritten before it is read
+    fn compile_tail_call(&mut self, node: &Call, mc_sink: &mut Vec<MachineInstr>) {
+        if self.arch != Arch::X86_64 {
+            todo!("tail calls are only supported on x64");
+        }
+
+        let arg_regs = self.call.args(self.arch, TypeMetadata::i64);
+        let fp_args = self.call.args(self.arch, TypeMetadata::f64);
+
         let mut reg_args = 0;
         let mut fp_reg_args = 0;
 
-        let caller_grs = self.call.caller_saved_grs(self.arch);
-        let caller_fps = self.call.caller_saved_fps(self.arch);
+        // (destination, source, type)
+        let mut reg_moves = Vec::new();
+        // moves which read from the stack or an immediate are done after all register moves
+        let mut other_moves = Vec::new();
 
-        let mut saved = HashMap::new();
-        
-        for (name, loc) in self.get_vars_to_save_for_call(node) {
-            let typ = *self.var_types.get(&name).unwrap();
+        for arg in &node.args {
+            let ty = arg.get_ty();
 
-            match loc {
-                VarLocation::Reg(reg) => {
-                    if Reg::contains_reg(reg, &caller_grs) || Reg::contains_reg(reg, &caller_fps) {
-                        // SAVE IT ONTO THE STACK
-                        let mut save = MachineInstr::new( MachineMnemonic::Move );
-            
-                        let off = match self.alloc_stack(typ) {
-                            VarLocation::Mem(off, ty) => (off, ty),
-                            _ => unreachable!(),
-                        };
-                        saved.insert(name.to_owned(), (off, loc));
+            let reg = if ty.float() { fp_args.get(fp_reg_args) } else { arg_regs.get(reg_args) };
 
-                        save.set_out(MachineOperand::Stack(off.0, off.1));
-                        save.add_operand(loc.into());
+            let reg = match reg.expect("the arguments of a tail call need to be passed in registers") {
+                Reg::x64(x64) => Reg::x64(x64.sub_ty(ty)),
+                Reg::wasm(i, t) => Reg::wasm(*i, *t),
+            };
 
-                        mc_sink.push(save);
-                    }
+            if self.call.call_conv == CallConv::WindowsFastCall {
+                // in the windows abi the position of the argument selects its register (no matter its type)
+                fp_reg_args += 1;
+                reg_args += 1;
+            } else if ty.float() {
+                fp_reg_args += 1;
+            } else { 
+                reg_args += 1;
+            }
+
+            let mut instr = match arg {
+                IROperand::Var(var) if self.allocated_vars.contains(&var.name) => {
+                    let mut instr = MachineInstr::new(MachineMnemonic::AdrMove);
+                    instr.add_operand(arg.into_mi(self));
+                    instr
                 },
-                VarLocation::Mem(_, _) => {},
+                IROperand::Var(var) => match *self.vars.get(&var.name).expect(&format!("expected valid variable: {}", var.name)) {
+                    VarLocation::Reg(src) if src.is(&reg) => continue, // the argument already is in its register
+                    VarLocation::Reg(src) => {
+                        reg_moves.push((reg, src, ty));
+                        continue;
+                    },
+                    VarLocation::Mem(..) => {
+                        let mut instr = MachineInstr::new(MachineMnemonic::Move);
+                        instr.add_operand(arg.into_mi(self));
+                        instr
+                    },
+                },
+                IROperand::Type(_) => {
+                    let mut instr = MachineInstr::new(MachineMnemonic::Move);
+                    instr.add_operand(arg.into_mi(self));
+                    instr
+                },
+            };
+
+            instr.set_out(MachineOperand::Reg(reg));
+            instr.meta = ty;
+
+            other_moves.push(instr);
+        }
+
+        while !reg_moves.is_empty() {
+            // a move can be done if no other move still needs to read its destination
+            let ready = reg_moves.iter().position(|(dst, _, _)| !reg_moves.iter().any(|(_, src, _)| src.is(dst)));
+
+            if let Some(index) = ready {
+                let (dst, src, ty) = reg_moves.remove(index);
+
+                let mut instr = MachineInstr::new(MachineMnemonic::Move);
+                instr.set_out(MachineOperand::Reg(dst));
+                instr.add_operand(MachineOperand::Reg(src));
+                instr.meta = ty;
+
+                mc_sink.push(instr);
+            } else {
+                // only cycles are left (e.g: two swapped arguments), which get broken
+                // up by moving one register into a temporary one
+                let (Reg::x64(dst), _, ty) = reg_moves[0] else { unreachable!() };
+
+                let (tmp, full) = if ty.float() {
+                    (X64Reg::Xmm15, TypeMetadata::f64)
+                } else {
+                    let Reg::x64(tmp) = self.tmp_reg else { unreachable!() };
+                    (tmp.sub64(), TypeMetadata::i64)
+                };
+
+                let mut instr = MachineInstr::new(MachineMnemonic::Move);
+                instr.set_out(MachineOperand::Reg(Reg::x64(tmp)));
+                instr.add_operand(MachineOperand::Reg(Reg::x64(dst.sub_ty(full))));
+                instr.meta = full;
+
+                mc_sink.push(instr);
+
+                for (_, src, ty) in &mut reg_moves {
+                    if src.is(&Reg::x64(dst)) {
+                        *src = Reg::x64(tmp.sub_ty(*ty));
+                    }
+                }
             }
         }
 
-        let mut pushes = Vec::new();
+        mc_sink.extend(other_moves);
 
-        let args = self.call.args(Arch::X86_64, TypeMetadata::i64);
+        if self.call.call_conv == CallConv::SystemV {
+            // variadic functions expect the number of used vector registers in al
+            let mut instr = MachineInstr::new(MachineMnemonic::Move);
+            instr.set_out(MachineOperand::Reg(Reg::x64(X64Reg::Eax)));
+            instr.add_operand(MachineOperand::Imm(fp_reg_args.min(fp_args.len()) as f64));
+            instr.meta = TypeMetadata::i32;
+            mc_sink.push(instr);
+        }
+
+        mc_sink.push(MachineInstr::new(MachineMnemonic::TailCall(node.func.name.to_string())));
+    }
+
+    /// Moves the arguments into their locations, emits the `call`, moves the returned value into `ret` and restores the saved variables
+    /// 
+    /// Arguments which don't fit into the argument registers are pushed onto the stack (before the
+    /// registers are written, so the pushes can still read them) in reverse order, so that the first
+    /// one ends up at the top of the stack. Every one of them takes a quadword, 128bit intengers take two
+    /// which are 16 byte aligned
+    pub(crate) fn compile_call_with(&mut self, call: MachineInstr, args: &Vec<IROperand>, saved: HashMap<String, ((i64, TypeMetadata), VarLocation)>, ret: Option<(&Var, TypeMetadata)>, mc_sink: &mut Vec<MachineInstr>) {
+        let mut reg_args = 0;
+        let mut fp_reg_args = 0;
+
+        // the instructions which push the quadwords of the stack arguments
+        let mut stack_args: Vec<Vec<MachineInstr>> = Vec::new();
+
+        let arg_regs = self.call.args(Arch::X86_64, TypeMetadata::i64);
         let fp_args = self.call.args(Arch::X86_64, TypeMetadata::f64);
 
-        for arg in &node.args {
+        let reg_moves_start = mc_sink.len();
+
+        if let Some((out, ret)) = ret {
+            if self.call.return_in_memory(Arch::X86_64, ret).expect("the calling convention is verified before compiling") {
+                // the adress of the memory for the returned aggregate is passed as a hidden first argument
+                let mut instr = MachineInstr::new(MachineMnemonic::AdrMove);
+                instr.set_out(MachineOperand::Reg(arg_regs[0]));
+                instr.add_operand((*self.vars.get(&out.name).expect(&format!("expected valid variable: {}", out.name))).into());
+                instr.meta = TypeMetadata::ptr;
+                mc_sink.push(instr);
+
+                reg_args += 1;
+
+                if self.call.call_conv == CallConv::WindowsFastCall {
+                    fp_reg_args += 1;
+                }
+            }
+        }
+
+        for arg in args {
+            if arg.get_ty().aggregate() {
+                self.compile_agg_arg(arg, (&mut reg_args, &mut fp_reg_args), &mut stack_args, mc_sink);
+                continue;
+            }
+
+            if arg.get_ty().wide() {
+                // sysv passes 128bit intengers in two general purpose registers (low, high)
+                if self.call.call_conv != CallConv::SystemV {
+                    panic!("128bit intengers can only be passed as arguments in the system v calling convention");
+                }
+
+                let (lo, hi) = arg.into_mi(self).halves(arg.get_ty());
+
+                let (Some(lo_reg), Some(hi_reg)) = (arg_regs.get(reg_args), arg_regs.get(reg_args + 1)) else {
+                    // if the pair doesn't fit into the remaining registers, it is spilled onto the stack
+                    if stack_args.len() % 2 == 1 {
+                        stack_args.push(vec![Self::call_stack_space(MachineMnemonic::CallStackPrepare, 8)]);
+                    }
+
+                    for half in [lo, hi] {
+                        let mut instr = MachineInstr::new(MachineMnemonic::Push);
+                        instr.add_operand(half);
+                        instr.meta = TypeMetadata::i64;
+                        stack_args.push(vec![instr]);
+                    }
+
+                    continue;
+                };
+
+                for (reg, half) in [(*lo_reg, lo), (*hi_reg, hi)] {
+                    let mut instr = MachineInstr::new(MachineMnemonic::Move);
+                    instr.set_out(MachineOperand::Reg(reg));
+                    instr.add_operand(half);
+                    instr.meta = TypeMetadata::i64;
+                    mc_sink.push(instr);
+                }
+
+                reg_args += 2;
+                continue;
+            }
+
+            if arg.get_ty().vector() {
+                unreachable!("vector arguments are rejected during verification");
+            }
+
             if let IROperand::Var(arg) = arg {
                 let src = self.vars.get(&arg.name).expect(&format!("expected valid variable: {}", arg.name));
     
                 let arg_reg = if TypeMetadata::f32 == arg.ty || TypeMetadata::f64 == arg.ty {
                     fp_args.get(fp_reg_args)
                 } else { 
-                    args.get(reg_args)
+                    arg_regs.get(reg_args)
                 };
     
                 let mut arg_reg = arg_reg.cloned();
@@ -64,17 +289,17 @@ impl CompilationHelper {
                         Reg::wasm(i, t) => Reg::wasm(i, t),
                     })
                 }
+
+                let mut op = src.into();
+
+                if let Some((save, _)) = saved.get(&arg.name) {
+                    op = MachineOperand::Stack(save.0, save.1);
+                }
                 
                 if let Some(reg) = arg_reg {
                     if !self.allocated_vars.contains(&arg.name) {
                         let mut instr = MachineInstr::new(MachineMnemonic::Move);
         
-                        let mut op = src.into();
-        
-                        if let Some((save, _)) = saved.get(&arg.name) {
-                            op = MachineOperand::Stack(save.0, save.1);
-                        }
-        
                         instr.set_out(MachineOperand::Reg(reg));
                         instr.add_operand(op);
     
@@ -94,27 +319,27 @@ impl CompilationHelper {
                 } else {
                     if !self.allocated_vars.contains(&arg.name) {
                         let mut instr = MachineInstr::new(MachineMnemonic::Push);
-                        instr.add_operand(src.into());
-                        pushes.push(arg.ty);
-                        mc_sink.push( instr );
+                        instr.add_operand(op);
+                        instr.meta = arg.ty;
+                        stack_args.push(vec![instr]);
                     } else {
-                        let mut instr = MachineInstr::new(MachineMnemonic::AdrMove);
+                        let mut adr = MachineInstr::new(MachineMnemonic::AdrMove);
     
-                        instr.set_out(MachineOperand::Reg(self.tmp_reg));
-                        instr.add_operand(src.into());
-                        mc_sink.push( instr );
+                        adr.set_out(MachineOperand::Reg(self.tmp_reg));
+                        adr.add_operand(src.into());
     
                         let mut instr = MachineInstr::new(MachineMnemonic::Push);
                         instr.add_operand(MachineOperand::Reg(self.tmp_reg));
-                        pushes.push(arg.ty);
-                        mc_sink.push( instr );
+                        instr.meta = TypeMetadata::ptr;
+
+                        stack_args.push(vec![adr, instr]);
                     }
                 }
             } else {
                 let arg_reg = if arg.get_ty().float() {
                     fp_args.get(fp_reg_args)
                 } else { 
-                    args.get(reg_args)
+                    arg_regs.get(reg_args)
                 };
 
                 let mut arg_reg = arg_reg.cloned();
@@ -134,67 +359,306 @@ impl CompilationHelper {
                 } else {
                     let mut instr = MachineInstr::new(MachineMnemonic::Push);
                     instr.add_operand(arg.into_mi(self));
-                    pushes.push(arg.get_ty());
-                    mc_sink.push( instr );
+                    instr.meta = arg.get_ty();
+                    stack_args.push(vec![instr]);
                 }
             }
     
-            if arg.get_ty().float() {
+            if self.call.call_conv == CallConv::WindowsFastCall {
+                // in the windows abi the position of the argument selects its register (no matter its type)
+                fp_reg_args += 1;
+                reg_args += 1;
+            } else if arg.get_ty().float() {
                 fp_reg_args += 1;
             } else { 
                 reg_args += 1;
             }
         }
 
-        if !pushes.is_empty() {
-            mc_sink.push(MachineInstr::new(MachineMnemonic::CallStackPrepare));
+        // the stack needs to stay 16 byte aligned
+        if stack_args.len() % 2 == 1 {
+            stack_args.push(vec![Self::call_stack_space(MachineMnemonic::CallStackPrepare, 8)]);
         }
 
-        mc_sink.push(MachineInstr::new(
-            MachineMnemonic::Call(node.func.name.to_string())
-        ));
+        let mut stack_size = stack_args.len() as i64 * 8;
+
+        let pushes = stack_args.into_iter().rev().flatten().collect::<Vec<_>>();
+        mc_sink.splice(reg_moves_start..reg_moves_start, pushes);
+
+        if self.arch == Arch::X86_64 && self.call.call_conv == CallConv::WindowsFastCall {
+            // the called function can spill its argument registers into the 32 bytes (shadow space) above the stack arguments
+            mc_sink.push(Self::call_stack_space(MachineMnemonic::CallStackPrepare, 32));
+            stack_size += 32;
+        }
+
+        if self.arch == Arch::X86_64 && self.call.call_conv == CallConv::SystemV {
+            // variadic functions expect the number of used vector registers in al
+            let mut instr = MachineInstr::new(MachineMnemonic::Move);
+            instr.set_out(MachineOperand::Reg(Reg::x64(X64Reg::Eax)));
+            instr.add_operand(MachineOperand::Imm(fp_reg_args.min(fp_args.len()) as f64));
+            instr.meta = TypeMetadata::i32;
+            mc_sink.push(instr);
+        }
+
+        mc_sink.push(call);
         
-        if !pushes.is_empty() {
-            mc_sink.push(MachineInstr::new(MachineMnemonic::CallStackRedo));
+        if stack_size > 0 {
+            mc_sink.push(Self::call_stack_space(MachineMnemonic::CallStackRedo, stack_size));
         }
 
-        for (_, (stack, original)) in saved {
-            let mut restore = MachineInstr::new( MachineMnemonic::Move);
+        // the return register could be the one of a saved variable (e.g: xmm0),
+        // so the result needs to be moved out of it before restoring
+        if let Some((out, ret)) = ret {
+            self.move_call_result(out, ret, mc_sink);
+        }
 
-            restore.set_out(original.into());
-            restore.add_operand(MachineOperand::Stack(stack.0, stack.1));
+        self.restore_caller_saved(saved, mc_sink);
 
-            mc_sink.push( restore );
+        if let Some((out, _)) = ret {
+            if let Some(phi_loc) = self.phi_vars.get(&out.name) {
+                let loc = *self.vars.get(&out.name).unwrap();
+
+                let mut instr = MachineInstr::new(MachineMnemonic::Move);
+                instr.set_out((*phi_loc).into());
+                instr.add_operand(loc.into());
+                mc_sink.push(instr);
+            }
+        }
+
+    }
+
+    /// Passes the aggregate argument like it was classified by the calling convention (the counters are
+    /// the used general purpose and sse registers). Arguments which are passed on the stack are added to `stack_args`
+    fn compile_agg_arg(&mut self, arg: &IROperand, counters: (&mut usize, &mut usize), stack_args: &mut Vec<Vec<MachineInstr>>, mc_sink: &mut Vec<MachineInstr>) {
+        let (reg_args, fp_reg_args) = counters;
+
+        let ty = arg.get_ty();
+
+        let MachineOperand::Stack(off, _) = arg.into_mi(self) else { panic!("aggregates live on the stack") };
+
+        let classes = self.call.classify(Arch::X86_64, ty).expect("the calling convention is verified before compiling");
+
+        let arg_regs = self.call.args(Arch::X86_64, TypeMetadata::i64);
+        let fp_args = self.call.args(Arch::X86_64, TypeMetadata::f64);
+
+        if self.call.call_conv == CallConv::WindowsFastCall {
+            // the aggregate (or the adress of a copy of it) is passed like an intenger
+            let (mnemonic, value) = if classes[0] == ArgClass::Memory {
+                // the called function owns the copy, so it is allowed to modify it
+                let VarLocation::Mem(copy, _) = self.alloc_stack(ty) else { unreachable!() };
+
+                for index in 0..ty.byteSize().div_ceil(8) as i64 {
+                    let mut instr = MachineInstr::new(MachineMnemonic::Move);
+                    instr.set_out(MachineOperand::Stack(copy - 8 * index, TypeMetadata::i64));
+                    instr.add_operand(MachineOperand::Stack(off - 8 * index, TypeMetadata::i64));
+                    instr.meta = TypeMetadata::i64;
+                    mc_sink.push(instr);
+                }
+
+                (MachineMnemonic::AdrMove, MachineOperand::Stack(copy, ty))
+            } else {
+                (MachineMnemonic::Move, MachineOperand::Stack(off, TypeMetadata::i64))
+            };
+
+            let mut instr = MachineInstr::new(mnemonic.to_owned());
+            instr.add_operand(value);
+            instr.meta = TypeMetadata::i64;
+
+            if let Some(reg) = arg_regs.get(*reg_args) {
+                instr.set_out(MachineOperand::Reg(*reg));
+                mc_sink.push(instr);
+            } else if mnemonic == MachineMnemonic::AdrMove {
+                instr.set_out(MachineOperand::Reg(self.tmp_reg));
+
+                let mut push = MachineInstr::new(MachineMnemonic::Push);
+                push.add_operand(MachineOperand::Reg(self.tmp_reg));
+                push.meta = TypeMetadata::ptr;
+
+                stack_args.push(vec![instr, push]);
+            } else {
+                instr.mnemonic = MachineMnemonic::Push;
+                stack_args.push(vec![instr]);
+            }
+
+            *reg_args += 1;
+            *fp_reg_args += 1;
+            return;
         }
 
-        for push_type in pushes {
-            let mut instr = MachineInstr::new( MachineMnemonic::PushCleanup );
-            instr.meta = push_type;
+        let ints = classes.iter().filter(|class| **class == ArgClass::Integer).count();
+        let sses = classes.iter().filter(|class| **class == ArgClass::Sse).count();
+
+        if !classes.contains(&ArgClass::Memory) && *reg_args + ints <= arg_regs.len() && *fp_reg_args + sses <= fp_args.len() {
+            // every eightbyte is passed in the next register of its class
+            for (index, class) in classes.iter().enumerate() {
+                let reg = if *class == ArgClass::Sse {
+                    *fp_reg_args += 1;
+                    fp_args[*fp_reg_args - 1]
+                } else {
+                    *reg_args += 1;
+                    arg_regs[*reg_args - 1]
+                };
+
+                let mut instr = MachineInstr::new(MachineMnemonic::Move);
+                instr.set_out(MachineOperand::Reg(reg));
+                instr.add_operand(MachineOperand::Stack(off - 8 * index as i64, class.ty()));
+                instr.meta = class.ty();
+                mc_sink.push(instr);
+            }
 
-            mc_sink.push( instr );
+            return;
         }
 
-        let mut instr = MachineInstr::new(MachineMnemonic::Move);
+        // if it doesn't fit into the remaining registers, it is copied onto the stack
+        if ty.align(&self.call.layout_triple(Arch::X86_64)) > 8 && stack_args.len() % 2 == 1 {
+            stack_args.push(vec![Self::call_stack_space(MachineMnemonic::CallStackPrepare, 8)]);
+        }
 
-        let loc = *self.vars.get(&node.out.name).unwrap();
+        for index in 0..ty.byteSize().div_ceil(8) as i64 {
+            let mut instr = MachineInstr::new(MachineMnemonic::Push);
+            instr.add_operand(MachineOperand::Stack(off - 8 * index, TypeMetadata::i64));
+            instr.meta = TypeMetadata::i64;
+            stack_args.push(vec![instr]);
+        }
+    }
 
-        instr.add_operand(
-            MachineOperand::Reg(
-                self.call.return_reg(self.arch, node.func.ty.ret)
-            )
-        );
+    /// Reserves (`CallStackPrepare`) or frees (`CallStackRedo`) `size` bytes of stack for a call
+    fn call_stack_space(mnemonic: MachineMnemonic, size: i64) -> MachineInstr {
+        let mut instr = MachineInstr::new(mnemonic);
+        instr.add_operand(MachineOperand::Imm(size as f64));
+        instr
+    }
+
+    /// Moves the return value of a call into the output variable
+    fn move_call_result(&mut self, out: &Var, ret: TypeMetadata, mc_sink: &mut Vec<MachineInstr>) {
+        let loc = *self.vars.get(&out.name).unwrap();
+
+        if ret.wide() {
+            self.move_wide_return(loc.into(), mc_sink);
+        } else if ret.aggregate() {
+            let classes = self.call.classify(self.arch, ret).expect("the calling convention is verified before compiling");
+
+            // aggregates which are returned over memory were already written into the slot by the called function
+            if classes.contains(&ArgClass::Memory) {
+                return;
+            }
 
-        instr.meta = node.func.ty.ret;
+            let VarLocation::Mem(off, _) = loc else { unreachable!("aggregates live on the stack") };
 
-        instr.set_out(loc.into());
+            for (index, (class, reg)) in classes.iter().zip(self.call.aggregate_return_regs(self.arch, &classes)).enumerate() {
+                let mut instr = MachineInstr::new(MachineMnemonic::Move);
 
-        mc_sink.push(instr);
+                instr.set_out(MachineOperand::Stack(off - 8 * index as i64, class.ty()));
+                instr.add_operand(MachineOperand::Reg(reg));
+                instr.meta = class.ty();
 
-        if let Some(phi_loc) = self.phi_vars.get(&node.func.name) {
+                mc_sink.push(instr);
+            }
+        } else if let Some(elems) = ret.tupleElems() {
+            if self.arch != Arch::X86_64 {
+                todo!("the results of calls which return tuples are only supported on x64");
+            }
+
+            let out: MachineOperand = loc.into();
+
+            for (index, reg) in self.call.return_regs(self.arch, &elems).into_iter().enumerate() {
+                let mut instr = MachineInstr::new(MachineMnemonic::Move);
+
+                instr.set_out(out.field(ret, index));
+                instr.add_operand(MachineOperand::Reg(reg));
+                instr.meta = elems[index];
+
+                mc_sink.push(instr);
+            }
+        } else {
             let mut instr = MachineInstr::new(MachineMnemonic::Move);
-            instr.set_out((*phi_loc).into());
-            instr.add_operand(loc.into());
+
+            instr.add_operand(
+                MachineOperand::Reg(
+                    self.call.return_reg(self.arch, ret)
+                )
+            );
+
+            instr.meta = ret;
+
+            instr.set_out(loc.into());
+
+            mc_sink.push(instr);
+        }
+    }
+
+    /// Saves all variables which are stored in caller saved registers onto the stack
+    /// 
+    /// Returns the stack slots and the original locations of the saved variables
+    pub(crate) fn save_caller_saved(&mut self, vars: Vec<(String, VarLocation)>, mc_sink: &mut Vec<MachineInstr>) -> HashMap<String, ((i64, TypeMetadata), VarLocation)> {
+        let mut regs = self.call.caller_saved_grs(self.arch);
+        regs.extend(self.call.caller_saved_fps(self.arch));
+
+        self.save_vars_in(vars, &regs, mc_sink)
+    }
+
+    /// Saves all variables which are stored in one of the registers `regs` onto the stack
+    /// 
+    /// Returns the stack slots and the original locations of the saved variables
+    pub(crate) fn save_vars_in(&mut self, vars: Vec<(String, VarLocation)>, regs: &Vec<Reg>, mc_sink: &mut Vec<MachineInstr>) -> HashMap<String, ((i64, TypeMetadata), VarLocation)> {
+        let mut saved = HashMap::new();
+        
+        for (name, loc) in vars {
+            let typ = *self.var_types.get(&name).unwrap();
+
+            match loc {
+                VarLocation::Reg(reg) => {
+                    if Reg::contains_reg(reg, regs) {
+                        // SAVE IT ONTO THE STACK
+                        let mut save = MachineInstr::new( MachineMnemonic::Move );
+            
+                        let off = match self.alloc_stack(typ) {
+                            VarLocation::Mem(off, ty) => (off, ty),
+                            _ => unreachable!(),
+                        };
+                        saved.insert(name.to_owned(), (off, loc));
+
+                        save.set_out(MachineOperand::Stack(off.0, off.1));
+                        save.add_operand(loc.into());
+
+                        save.meta = typ;
+
+                        mc_sink.push(save);
+                    }
+                },
+                VarLocation::Mem(_, _) => {},
+            }
+        }
+
+        saved
+    }
+
+    /// Moves the variables which were saved using `save_caller_saved` back into their registers
+    pub(crate) fn restore_caller_saved(&mut self, saved: HashMap<String, ((i64, TypeMetadata), VarLocation)>, mc_sink: &mut Vec<MachineInstr>) {
+        for (_, (stack, original)) in saved {
+            let mut restore = MachineInstr::new( MachineMnemonic::Move);
+
+            restore.set_out(original.into());
+            restore.add_operand(MachineOperand::Stack(stack.0, stack.1));
+
+            restore.meta = stack.1;
+
+            mc_sink.push( restore );
+        }
+    }
+
+    /// Moves a returned 128bit intenger (which is returned in rdx:rax) into `out`
+    pub(crate) fn move_wide_return(&mut self, out: MachineOperand, mc_sink: &mut Vec<MachineInstr>) {
+        let (lo, hi) = out.halves(TypeMetadata::i128);
+
+        for (half, reg) in [(lo, X64Reg::Rax), (hi, X64Reg::Rdx)] {
+            let mut instr = MachineInstr::new(MachineMnemonic::Move);
+
+            instr.set_out(half);
+            instr.add_operand(MachineOperand::Reg(Reg::x64(reg)));
+            instr.meta = TypeMetadata::i64;
+
             mc_sink.push(instr);
         }
     }
-}
\ No newline at end of file
+}
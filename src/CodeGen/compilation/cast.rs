@@ -1,4 +1,4 @@
-use crate::prelude::Cast;
+use crate::prelude::{Cast, CastKind};
 use crate::IR::Block;
 use super::CompilationHelper;
 use crate::CodeGen::{MachineInstr, MachineMnemonic};
@@ -8,17 +8,28 @@ impl CompilationHelper {
     pub fn compile_cast(&mut self, node: &Cast, mc_sink: &mut Vec<MachineInstr>, _: &Block, _: &mut crate::prelude::Module) {
         let out = *self.vars.get(&node.inner3.name).unwrap();
 
-        let op = {
-            if node.inner1.get_ty().float() || node.inner2.float() {
-                MachineMnemonic::FCast
-            } else if node.inner1.get_ty().bitSize() < node.inner2.bitSize() {
-                MachineMnemonic::Zext
-            } else if node.inner1.get_ty().bitSize() > node.inner2.bitSize(){
-                MachineMnemonic::Downcast
-            } else {
-                return;
-            }  
-        }(node.inner1.get_ty());
+        let from = node.inner1.get_ty();
+
+        let op = match node.kind {
+            CastKind::Zext => MachineMnemonic::Zext(from),
+            CastKind::Sext => MachineMnemonic::Sext(from),
+            CastKind::Trunc => MachineMnemonic::Downcast(from),
+            CastKind::Bitcast => MachineMnemonic::Bitcast(from),
+
+            // pointers are just 64bit intengers
+            CastKind::PtrToInt | CastKind::IntToPtr => {
+                if from.bitSize() < node.inner2.bitSize() {
+                    MachineMnemonic::Zext(from)
+                } else if from.bitSize() > node.inner2.bitSize() {
+                    MachineMnemonic::Downcast(from)
+                } else {
+                    MachineMnemonic::Bitcast(from)
+                }
+            },
+
+            CastKind::FpTrunc | CastKind::FpExt | CastKind::FpToSi | 
+            CastKind::FpToUi | CastKind::SiToFp | CastKind::UiToFp => MachineMnemonic::FCast(node.kind, from),
+        };
 
         let mut instr = MachineInstr::new(op);
 
@@ -37,4 +48,4 @@ impl CompilationHelper {
             mc_sink.push(instr);
         }
     }
-}
\ No newline at end of file
+}
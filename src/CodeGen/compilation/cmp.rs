@@ -1,4 +1,4 @@
-use crate::{prelude::Cmp, CodeGen::{MachineInstr, MachineMnemonic}, IR::{Block, TypeMetadata}};
+use crate::{prelude::Cmp, CodeGen::{MachineInstr, MachineMnemonic}, IR::Block};
 
 use super::CompilationHelper;
 
@@ -14,7 +14,7 @@ impl CompilationHelper {
 
         let out =  out.into();
 
-        let mut cmp = MachineInstr::new(MachineMnemonic::Compare(node.mode) );
+        let mut cmp = MachineInstr::new(MachineMnemonic::Compare(node.mode.resolve(node.ls.get_ty())) );
         
         cmp.add_operand(ls);
         cmp.add_operand(rs);
@@ -30,7 +30,7 @@ impl CompilationHelper {
             instr.set_out((*phi_loc).into());
             instr.add_operand(out.into());
 
-            instr.meta = TypeMetadata::u8;
+            instr.meta = node.out.ty;
 
             mc_sink.push(instr);
         }
@@ -0,0 +1,64 @@
+use crate::CodeGen::{MachineInstr, MachineMnemonic};
+use crate::IR::{Block, FnTy, ir::{FloatOp, FloatOpKind}};
+use crate::prelude::Ir;
+use crate::Target::{Arch, TargetFeature};
+
+use super::CompilationHelper;
+
+impl CompilationHelper {
+    #[allow(missing_docs)]
+    pub fn compile_floatop(&mut self, node: &FloatOp, mc_sink: &mut Vec<MachineInstr>, _: &Block, module: &mut crate::prelude::Module) {
+        if self.floatop_libcall(node.kind) {
+            let libm = node.kind.libm(node.out.ty);
+
+            if module.getFunc(&libm).is_none() {
+                module.add(&libm, &FnTy(vec![node.out.ty; node.args.len()], node.out.ty)).import();
+            }
+
+            let saved = self.save_caller_saved(self.get_vars_to_save_for_call(node.clone_box()), mc_sink);
+
+            let call = MachineInstr::new(MachineMnemonic::Call(libm));
+
+            self.compile_call_with(call, &node.args, saved, Some((&node.out, node.out.ty)), mc_sink);
+            return;
+        }
+
+        let out = *self.vars.get(&node.out.name).expect("expected valid variable");
+
+        let mut instr = MachineInstr::new(MachineMnemonic::FloatOp(node.kind));
+
+        instr.set_out( out.into() );
+
+        for arg in &node.args {
+            instr.add_operand( arg.into_mi(self) );
+        }
+
+        instr.meta = node.out.ty;
+
+        mc_sink.push( instr );
+
+        if let Some(phi_loc) = self.phi_vars.get(&node.out.name) {
+            let mut instr = MachineInstr::new(MachineMnemonic::Move);
+            instr.set_out((*phi_loc).into());
+            instr.add_operand(out.into());
+
+            instr.meta = node.out.ty;
+
+            mc_sink.push(instr);
+        }
+    }
+
+    /// Returns if the target has no instructions for the kind, so it gets computed by a call into libm
+    fn floatop_libcall(&self, kind: FloatOpKind) -> bool {
+        match self.arch {
+            // wasm has all of them except fma
+            Arch::Wasm32 | Arch::Wasm64 => kind == FloatOpKind::Fma,
+            // roundsd/roundss are only available with SSE4.1 and fma needs the FMA extension
+            _ => match kind {
+                FloatOpKind::Floor | FloatOpKind::Ceil | FloatOpKind::Trunc | FloatOpKind::Round => !self.features.contains(&TargetFeature::Sse4_1),
+                FloatOpKind::Fma => true,
+                _ => false,
+            },
+        }
+    }
+}
@@ -26,7 +26,7 @@ impl CompilationHelper {
 
         mc_sink.push( add_instr );
 
-        let mut mov_instr = MachineInstr::new( MachineMnemonic::Load );
+        let mut mov_instr = MachineInstr::new( MachineMnemonic::Load(None) );
         mov_instr.set_out(out);
         mov_instr.add_operand(out);
 
@@ -0,0 +1,81 @@
+use crate::prelude::{Block, Invoke, Ir, LandingPad};
+use crate::CodeGen::{MachineInstr, MachineMnemonic, MachineOperand, Reg};
+use crate::Target::{x64::X64Reg, Arch};
+use crate::IR::Var;
+
+use super::CompilationHelper;
+
+impl CompilationHelper {
+    #[allow(missing_docs)]
+    pub fn compile_invoke(&mut self, node: &Invoke, mc_sink: &mut Vec<MachineInstr>, _: &Block, module: &mut crate::prelude::Module) {
+        // COMPILES TO:
+        // begin:
+        //     call func
+        // end:
+        //     jmp normal
+        // pad:                 <- the unwinder jumps here (exception pointer in rax and selector in rdx)
+        //     mov out, rax
+        //     jmp unwind
+
+        if self.arch != Arch::X86_64 {
+            todo!("exception handling is only supported on x64");
+        }
+
+        let caller = self.call;
+
+        if let Some(call_conv) = node.func.ty.call_conv.or(module.getFunc(&node.func.name).and_then(|func| func.ty.call_conv)) {
+            self.call.call_conv = call_conv;
+        }
+
+        let saved = self.save_caller_saved(self.get_vars_to_save_for_call(node.clone_box()), mc_sink);
+
+        mc_sink.push(MachineInstr::new(MachineMnemonic::Label(node.label("begin"))));
+
+        let call = MachineInstr::new(MachineMnemonic::Call(node.func.name.to_string()));
+        self.compile_call_with(call, &node.args, saved.clone(), Some((&node.out, node.func.ty.ret)), mc_sink);
+
+        mc_sink.push(MachineInstr::new(MachineMnemonic::Label(node.label("end"))));
+        mc_sink.push(MachineInstr::new(MachineMnemonic::Br(node.normal.name.to_owned())));
+
+        mc_sink.push(MachineInstr::new(MachineMnemonic::Label(node.label("pad"))));
+
+        let pad = self.landing_pads.get(&node.unwind.name).expect("the unwind block of an invoke needs to start with a landingpad").to_owned();
+
+        // rdx first since the exception pointer could be moved into it
+        if let Some(selector) = &pad.selector {
+            self.move_landing_pad_reg(selector, X64Reg::Rdx, mc_sink);
+        }
+
+        self.move_landing_pad_reg(&pad.out, X64Reg::Rax, mc_sink);
+
+        self.restore_caller_saved(saved, mc_sink);
+
+        mc_sink.push(MachineInstr::new(MachineMnemonic::Br(node.unwind.name.to_owned())));
+
+        self.call = caller;
+    }
+
+    #[allow(missing_docs)]
+    pub fn compile_landingpad(&mut self, _: &LandingPad, _: &mut Vec<MachineInstr>, _: &Block, _: &mut crate::prelude::Module) {
+        // the values are moved into the variables by the invokes which unwind to the landing pad
+    }
+
+    /// Moves the register which the unwinder sets into the variable
+    fn move_landing_pad_reg(&mut self, var: &Var, reg: X64Reg, mc_sink: &mut Vec<MachineInstr>) {
+        let loc = *self.vars.get(&var.name).expect(&format!("expected valid variable: {}", var.name));
+
+        let mut instr = MachineInstr::new(MachineMnemonic::Move);
+        instr.set_out(loc.into());
+        instr.add_operand(MachineOperand::Reg(Reg::x64(reg.sub_ty(var.ty))));
+        instr.meta = var.ty;
+        mc_sink.push(instr);
+
+        if let Some(phi_loc) = self.phi_vars.get(&var.name) {
+            let mut instr = MachineInstr::new(MachineMnemonic::Move);
+            instr.set_out((*phi_loc).into());
+            instr.add_operand(loc.into());
+            instr.meta = var.ty;
+            mc_sink.push(instr);
+        }
+    }
+}
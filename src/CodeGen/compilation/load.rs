@@ -11,7 +11,7 @@ impl CompilationHelper {
         let out = *self.vars.get(&node.inner1.name).unwrap();
         let out = out.into();
 
-        let mut instr = MachineInstr::new(MachineMnemonic::Load);
+        let mut instr = MachineInstr::new(MachineMnemonic::Load(node.align));
 
         instr.set_out( out );
         instr.add_operand(ptr);
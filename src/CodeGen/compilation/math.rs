@@ -1,32 +1,42 @@
-use crate::CodeGen::instr::{MachineMnemonic, MachineInstr};
+use crate::CodeGen::instr::{MachineMnemonic, MachineInstr, MachineOperand};
+use crate::CodeGen::Reg;
 use crate::IR::ir::*;
-use crate::prelude::Block;
+use crate::prelude::{Block, FnTy, Module};
+use crate::Target::{x64::X64Reg, Arch, CallConv, TargetFeature};
+use crate::IR::TypeMetadata;
 use super::CompilationHelper;
 
 macro_rules! MathImpls {
     ($func:ident, $node:ident, $mnemonic:expr) => {
         impl CompilationHelper {
             #[allow(missing_docs)]
-            pub(crate) fn $func(&mut self, node: &$node, mc_sink: &mut Vec<MachineInstr>, _: &Block, _: &mut crate::prelude::Module) {
+            pub(crate) fn $func(&mut self, node: &$node, mc_sink: &mut Vec<MachineInstr>, _: &Block, module: &mut crate::prelude::Module) {
                 let src1 = node.inner1.into_mi(self);
                 let src2 = node.inner2.into_mi(self);
         
                 let out = *self.vars.get(&node.inner3.name).unwrap();
         
-                let mut instr = MachineInstr::new($mnemonic);
-        
-                instr.add_operand(src1.into());
-                instr.add_operand(src2.into());
-                instr.set_out(out.into());
-        
-                instr.meta = node.inner3.ty;
-        
-                mc_sink.push(instr);
+                if let Some(func) = wide_runtime_func(&$mnemonic, node.inner3.ty) {
+                    self.compile_wide_runtime_call(func, Box::new(node.clone()), src1, src2, out.into(), node.inner3.ty, mc_sink, module);
+                } else if self.vector_scalarized(&$mnemonic, node.inner3.ty) {
+                    self.compile_scalarized(&$mnemonic, src1, src2, out.into(), node.inner3.ty, mc_sink);
+                } else {
+                    let mut instr = MachineInstr::new($mnemonic);
+            
+                    instr.add_operand(src1.into());
+                    instr.add_operand(src2.into());
+                    instr.set_out(out.into());
+            
+                    instr.meta = node.inner3.ty;
+            
+                    mc_sink.push(instr);
+                }
 
                 if let Some(phi_loc) = self.phi_vars.get(&node.inner3.name) {
                     let mut instr = MachineInstr::new(MachineMnemonic::Move);
                     instr.set_out((*phi_loc).into());
                     instr.add_operand(out.into());
+                    instr.meta = node.inner3.ty;
                     mc_sink.push(instr);
                 }
             }
@@ -44,4 +54,94 @@ MathImpls!(compile_sub, Sub, MachineMnemonic::Sub);
 MathImpls!(compile_xor, Xor, MachineMnemonic::Xor);
 MathImpls!(compile_rem, Rem, MachineMnemonic::Rem);
 MathImpls!(compile_shl, Shl, MachineMnemonic::Shl);
-MathImpls!(compile_shr, Shr, MachineMnemonic::Shr);
\ No newline at end of file
+MathImpls!(compile_shr, Shr, MachineMnemonic::Shr);
+MathImpls!(compile_rotl, Rotl, MachineMnemonic::Rotl);
+MathImpls!(compile_rotr, Rotr, MachineMnemonic::Rotr);
+
+/// Returns the runtime function (from libgcc/compiler-rt) which implements the 128bit operation
+/// (or none if the operation is lowered directly)
+fn wide_runtime_func(mnemonic: &MachineMnemonic, ty: TypeMetadata) -> Option<&'static str> {
+    if !ty.wide() {
+        return None;
+    }
+
+    match (mnemonic, ty.signed()) {
+        (MachineMnemonic::Mul, _) => Some("__multi3"),
+        (MachineMnemonic::Div, true) => Some("__divti3"),
+        (MachineMnemonic::Div, false) => Some("__udivti3"),
+        (MachineMnemonic::Rem, true) => Some("__modti3"),
+        (MachineMnemonic::Rem, false) => Some("__umodti3"),
+        _ => None,
+    }
+}
+
+impl CompilationHelper {
+    /// Returns if the target has no packed instruction for the vector operation, so it gets computed elementwise
+    fn vector_scalarized(&self, mnemonic: &MachineMnemonic, ty: TypeMetadata) -> bool {
+        let Some((elem, _)) = ty.vectorElems() else {
+            return false;
+        };
+
+        match self.arch {
+            // pmulld is only available with SSE4.1
+            Arch::X86_64 => *mnemonic == MachineMnemonic::Mul && !elem.float() && elem.byteSize() == 4 && !self.features.contains(&TargetFeature::Sse4_1),
+            _ => false,
+        }
+    }
+
+    /// Compiles the vector operation as one scalar operation per element
+    fn compile_scalarized(&mut self, mnemonic: &MachineMnemonic, src1: MachineOperand, src2: MachineOperand, out: MachineOperand, ty: TypeMetadata, mc_sink: &mut Vec<MachineInstr>) {
+        let (elem, len) = ty.vectorElems().expect("expected a vector type");
+
+        for index in 0..len {
+            let mut instr = MachineInstr::new(mnemonic.to_owned());
+
+            instr.add_operand(src1.element(ty, index));
+            instr.add_operand(src2.element(ty, index));
+            instr.set_out(out.element(ty, index));
+
+            instr.meta = elem;
+
+            mc_sink.push(instr);
+        }
+    }
+
+    /// Compiles a 128bit operation into a call to the runtime function `func`
+    fn compile_wide_runtime_call(&mut self, func: &str, node: Box<dyn Ir>, src1: MachineOperand, src2: MachineOperand, out: MachineOperand, ty: TypeMetadata, mc_sink: &mut Vec<MachineInstr>, module: &mut Module) {
+        if self.call.call_conv != CallConv::SystemV {
+            todo!("128bit runtime calls are currently only supported for the system v calling convention");
+        }
+
+        if module.getFunc(func).is_none() {
+            module.add(func, &FnTy(vec![ty, ty], ty)).import();
+        }
+
+        let vars = self.scoped_vars_before_node(node)
+            .into_iter()
+            .map(|(var, location)| (var.name, location))
+            .collect();
+
+        let saved = self.save_caller_saved(vars, mc_sink);
+
+        // the operands are passed in rsi:rdi and rcx:rdx
+        let (src1_lo, src1_hi) = src1.halves(ty);
+        let (src2_lo, src2_hi) = src2.halves(ty);
+
+        for (half, reg) in [(src1_lo, X64Reg::Rdi), (src1_hi, X64Reg::Rsi), (src2_lo, X64Reg::Rdx), (src2_hi, X64Reg::Rcx)] {
+            let mut instr = MachineInstr::new(MachineMnemonic::Move);
+
+            instr.set_out(MachineOperand::Reg(Reg::x64(reg)));
+            instr.add_operand(half);
+            instr.meta = TypeMetadata::i64;
+
+            mc_sink.push(instr);
+        }
+
+        mc_sink.push(MachineInstr::new(MachineMnemonic::Call(func.to_string())));
+
+        // the result needs to be moved out of rdx:rax before the registers are restored
+        self.move_wide_return(out, mc_sink);
+
+        self.restore_caller_saved(saved, mc_sink);
+    }
+}
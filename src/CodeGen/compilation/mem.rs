@@ -0,0 +1,88 @@
+use super::{CompilationHelper, VarLocation};
+use crate::{prelude::*, CodeGen::*, Target::Arch};
+
+/// memcpy and memset with a constant size up to this many bytes are expanded inline
+pub(crate) const MEM_INLINE_LIMIT: f64 = 64.0;
+
+impl CompilationHelper {
+    #[allow(missing_docs)]
+    pub fn compile_memcpy(&mut self, node: &MemCpy, mc_sink: &mut Vec<MachineInstr>, _: &Block, module: &mut crate::prelude::Module) {
+        let inline = matches!(node.size, IROperand::Type(size) if size.val() <= MEM_INLINE_LIMIT);
+
+        self.compile_mem_intrinsic(node.clone_box(), MachineMnemonic::MemCpy, "memcpy", inline, &node.dst, IROperand::Var(node.src.to_owned()), &node.size, mc_sink, module);
+    }
+
+    #[allow(missing_docs)]
+    pub fn compile_memmove(&mut self, node: &MemMove, mc_sink: &mut Vec<MachineInstr>, _: &Block, module: &mut crate::prelude::Module) {
+        // the inline expansion doesn't buffer the data, so it is only overlap safe
+        // if it's done with a single load and store
+        let inline = matches!(node.size, IROperand::Type(size) if [1.0, 2.0, 4.0, 8.0].contains(&size.val()));
+
+        self.compile_mem_intrinsic(node.clone_box(), MachineMnemonic::MemMove, "memmove", inline, &node.dst, IROperand::Var(node.src.to_owned()), &node.size, mc_sink, module);
+    }
+
+    #[allow(missing_docs)]
+    pub fn compile_memset(&mut self, node: &MemSet, mc_sink: &mut Vec<MachineInstr>, _: &Block, module: &mut crate::prelude::Module) {
+        let inline = matches!(node.size, IROperand::Type(size) if size.val() <= MEM_INLINE_LIMIT);
+
+        self.compile_mem_intrinsic(node.clone_box(), MachineMnemonic::MemSet, "memset", inline, &node.dst, node.val.to_owned(), &node.size, mc_sink, module);
+    }
+
+    /// Emits the memory intrinsic as a machine instruction if it can be expanded inline (or the target has
+    /// its own instructions for it like wasm), else it is compiled into a call to the libc function
+    fn compile_mem_intrinsic(&mut self, node: Box<dyn Ir>, mnemonic: MachineMnemonic, libc: &str, inline: bool, dst: &Var, src: IROperand, size: &IROperand, mc_sink: &mut Vec<MachineInstr>, module: &mut Module) {
+        if inline || matches!(self.arch, Arch::Wasm32 | Arch::Wasm64) {
+            let dst = self.mem_ptr_operand(dst, mc_sink);
+
+            let src_op = match &src {
+                IROperand::Var(var) if var.ty == TypeMetadata::ptr => self.mem_ptr_operand(var, mc_sink),
+                _ => src.into_mi(self),
+            };
+
+            let mut instr = MachineInstr::new(mnemonic);
+
+            instr.add_operand(dst);
+            instr.add_operand(src_op);
+            instr.add_operand(size.into_mi(self));
+
+            instr.meta = src.get_ty();
+
+            mc_sink.push(instr);
+            return;
+        }
+
+        if module.getFunc(libc).is_none() {
+            module.add(libc, &FnTy(vec![TypeMetadata::ptr, src.get_ty(), TypeMetadata::i64], TypeMetadata::ptr)).import();
+        }
+
+        let saved = self.save_caller_saved(self.get_vars_to_save_for_call(node), mc_sink);
+
+        let call = MachineInstr::new(MachineMnemonic::Call(libc.to_string()));
+
+        self.compile_call_with(call, &vec![IROperand::Var(dst.to_owned()), src, size.to_owned()], saved, None, mc_sink);
+    }
+
+    /// Returns the operand which holds the pointer
+    /// 
+    /// Allocas are stored directly on the stack, so their adress is moved into a new stack slot
+    pub(super) fn mem_ptr_operand(&mut self, var: &Var, mc_sink: &mut Vec<MachineInstr>) -> MachineOperand {
+        let loc = *self.vars.get(&var.name).expect("expected valid variable");
+
+        if !self.allocated_vars.contains(&var.name) {
+            return loc.into();
+        }
+
+        let VarLocation::Mem(off, ty) = self.alloc_stack(TypeMetadata::ptr) else { unreachable!() };
+        let slot = MachineOperand::Stack(off, ty);
+
+        let mut instr = MachineInstr::new(MachineMnemonic::AdrMove);
+
+        instr.set_out(slot);
+        instr.add_operand(loc.into());
+        instr.meta = TypeMetadata::ptr;
+
+        mc_sink.push(instr);
+
+        slot
+    }
+}
@@ -1,8 +1,11 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use crate::prelude::Ir;
-use crate::Target::{Arch, CallConv};
-use crate::IR::{Function, TypeMetadata, Var};
+use gimli::write::CallFrameInstruction;
+
+use crate::prelude::{Ir, LandingPad};
+use crate::Target::{Arch, CallConv, TargetFeature};
+use crate::IR::{FnAttr, Function, Metadata, TypeMetadata, Var};
+use crate::Obj::StackMapLocation;
 
 use super::MachineOperand;
 use super::{calling_convention::MachineCallingConvention, reg::Reg, MCInstr, MachineInstr};
@@ -25,9 +28,21 @@ mod load;
 mod switch;
 
 mod neg;
+mod bit;
+mod overflow;
+mod float;
 
 mod select;
 mod getelemptr;
+mod vector;
+mod unreachable;
+mod mem;
+mod atomic;
+mod va;
+mod asm;
+mod invoke;
+mod statepoint;
+mod tuple;
 
 /// handeles how constant imms are handeled (wether creating a const or just an instr op)
 /// 
@@ -64,12 +79,67 @@ pub struct Allocator {
     pub(crate) call: MachineCallingConvention,
 
     pub(crate) callee_save_registers: Vec<Reg>,
+
+    /// where the variadic arguments are stored (only set for variadic functions)
+    pub(crate) va: Option<VaLayout>,
+
+    /// arguments which are passed in registers but live on the stack (e.g: 128bit intengers),
+    /// the registers are stored into their slots after the prolog
+    pub(crate) arg_stores: Vec<(Reg, VarLocation)>,
+
+    /// arguments which are passed over the stack (the offset is from the first stack argument),
+    /// they are copied into their slots after the prolog
+    pub(crate) arg_loads: Vec<(i64, VarLocation)>,
+
+    /// aggregates which are passed over a pointer to a copy (windows), the first location
+    /// is the slot which the pointer is stored in. They are copied into their slots after the prolog
+    pub(crate) arg_copies: Vec<(VarLocation, VarLocation)>,
+
+    /// the slot which the adress of the returned aggregate is saved in (if it is returned over memory)
+    pub(crate) ret_ptr: Option<VarLocation>,
+
+    /// the instruction set extensions which can be used (set from the helper before allocating)
+    pub(crate) features: HashSet<TargetFeature>,
+}
+
+/// Describes where the arguments of a variadic function are stored
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct VaLayout {
+    /// the stack slot which the argument registers (and the adress of the stack arguments) are saved in
+    pub(crate) save_area: i64,
+    /// how many named arguments were passed in general purpose registers
+    pub(crate) named_grs: usize,
+    /// how many named arguments were passed in floating point registers
+    pub(crate) named_fps: usize,
+}
+
+/// The target functions which describe the frame that the lowered prolog sets up
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct FrameInfo {
+    /// describes how the lowered prolog changes the frame (for unwinding)
+    pub(crate) cfi: fn(&[Box<dyn MCInstr>]) -> Vec<(u32, CallFrameInstruction)>,
+    /// returns the size of the frame which the lowered prolog sets up (without the return adress)
+    pub(crate) size: fn(&[Box<dyn MCInstr>]) -> u64,
 }
+
+// the frame description only depends on the target (every helper of a target uses the same functions),
+// so it doesn't make two helpers different. The function pointers themselves aren't compared because
+// the same function can have different adresses (e.g: in different codegen units)
+impl PartialEq for FrameInfo {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for FrameInfo {}
+
 /// helps with compilation
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CompilationHelper {
     pub(crate) arch: Arch,
     pub(crate) lower: Option<fn(CallConv, Vec<MachineInstr>) -> Vec<Box<dyn MCInstr>>>,
+    /// describes the frame which the lowered prolog sets up (for unwinding)
+    pub(crate) frame: Option<FrameInfo>,
     
     pub(crate) alloc: Allocator,
 
@@ -81,6 +151,15 @@ pub struct CompilationHelper {
     pub(crate) scopes: HashMap<String, Vec<(Var, VarLocation)>>,
     pub(crate) phi_vars: HashMap<String, VarLocation>,
     pub(crate) epilog: bool,
+    pub(crate) naked: bool,
+    /// the landing pads which start the blocks (stored as: block name, landing pad)
+    pub(crate) landing_pads: HashMap<String, LandingPad>,
+    /// the locations of the gc roots at the statepoints (stored as: label after the call, (id, locations))
+    pub(crate) stack_maps: HashMap<String, (u64, Vec<StackMapLocation>)>,
+    /// the metadata which is attached to the node which currently gets compiled
+    pub(crate) metadata: Metadata,
+    /// the instruction set extensions which can be used
+    pub(crate) features: HashSet<TargetFeature>,
 
     pub(crate) tmp_reg: Reg,
 
@@ -96,17 +175,27 @@ impl CompilationHelper {
             var_types: HashMap::new(),
             call: call,
             lower: None,
+            frame: None,
             tmp_reg: tmp,
             fp_imm: ConstImmRules::InstrOp,
             alloc: alloc,
             epilog: false,
+            naked: false,
             scopes: HashMap::new(),
             phi_vars: HashMap::new(),
+            landing_pads: HashMap::new(),
+            stack_maps: HashMap::new(),
+            metadata: Metadata::new(),
+            features: HashSet::new(),
         }
     }
 
     /// runs the register allocator
     pub fn run_alloc(&mut self, func: &Function) {
+        let stack_off = self.alloc.stack_off;
+
+        self.alloc.features = self.features.to_owned();
+
         if let Some(alloc) = self.alloc.alloc {
             alloc(&mut self.alloc, func);
         } else { panic!("no registered allocator for {:?}", self.arch) }
@@ -120,9 +209,20 @@ impl CompilationHelper {
         self.allocated_vars = self.alloc.allocated_vars.to_owned();
         self.var_types      = self.alloc.var_types.to_owned();
         self.epilog         = self.alloc.epilog.to_owned();
+        self.naked          = func.has_attr(FnAttr::Naked);
+
+        if self.naked && self.alloc.stack_off != stack_off {
+            panic!("the naked function {} needs stack slots (it has no frame to spill its variables into)", func.name);
+        }
+
+        self.landing_pads   = func.blocks.iter().filter_map(|block| Some((block.name.to_owned(), func.landing_pad(&block.name)?.to_owned()))).collect();
     }
 
     pub(crate) fn alloc_stack(&mut self, ty: TypeMetadata) -> VarLocation {
+        if self.naked {
+            panic!("naked functions can't allocate stack slots (they have no frame)");
+        }
+
         let out = if let Some(alloc_stack) = self.alloc.alloc_stack {
             alloc_stack(&mut self.alloc, ty)
         } else { panic!("no registered stack allocation function for {:?}", self.arch) };
@@ -156,8 +256,8 @@ impl CompilationHelper {
         got.to_owned()
     }
 
-    fn get_vars_to_save_for_call(&self, node: &crate::prelude::Call) -> Vec<(String, VarLocation)> {
-        let vars = self.scoped_vars_before_node(Box::new( node.clone() ));
+    fn get_vars_to_save_for_call(&self, node: Box<dyn Ir>) -> Vec<(String, VarLocation)> {
+        let vars = self.scoped_vars_before_node(node);
     
         let mut with_name = vec![];
         for (var, location) in vars {
@@ -177,7 +277,7 @@ pub(crate) enum VarLocation {
 impl crate::IR::ir::IROperand {
     fn into_mi(&self, compiler: &mut CompilationHelper) -> MachineOperand {
         match self {
-            crate::prelude::IROperand::Type(ty) => MachineOperand::Imm(ty.val()),
+            crate::prelude::IROperand::Type(ty) => MachineOperand::constant(*ty),
             crate::prelude::IROperand::Var(var) => (*compiler.vars.get(&var.name).expect(&format!("unknown variable: {}", var.name))).into(),
         }
     }
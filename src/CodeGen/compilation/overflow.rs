@@ -0,0 +1,38 @@
+use crate::CodeGen::{MachineInstr, MachineMnemonic};
+use crate::IR::{Block, ir::OverflowOp};
+
+use super::CompilationHelper;
+
+impl CompilationHelper {
+    #[allow(missing_docs)]
+    pub fn compile_overflowop(&mut self, node: &OverflowOp, mc_sink: &mut Vec<MachineInstr>, _: &Block, _: &mut crate::prelude::Module) {
+        let ls = node.inner1.into_mi(self);
+        let rs = node.inner2.into_mi(self);
+
+        let out = *self.vars.get(&node.inner3.name).expect("expected valid variable");
+        let overflow = *self.vars.get(&node.overflow.name).expect("expected valid variable");
+
+        let mut instr = MachineInstr::new(MachineMnemonic::Overflow(node.kind));
+
+        instr.set_out( out.into() );
+        instr.add_operand( ls );
+        instr.add_operand( rs );
+        instr.add_operand( overflow.into() );
+
+        instr.meta = node.inner3.ty;
+
+        mc_sink.push( instr );
+
+        for (var, loc) in [(&node.inner3, out), (&node.overflow, overflow)] {
+            if let Some(phi_loc) = self.phi_vars.get(&var.name) {
+                let mut instr = MachineInstr::new(MachineMnemonic::Move);
+                instr.set_out((*phi_loc).into());
+                instr.add_operand(loc.into());
+
+                instr.meta = var.ty;
+
+                mc_sink.push(instr);
+            }
+        }
+    }
+}
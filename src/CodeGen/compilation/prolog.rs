@@ -1,10 +1,16 @@
-use crate::CodeGen::{MachineInstr, MachineMnemonic, MachineOperand};
+use crate::CodeGen::{MachineInstr, MachineMnemonic, MachineOperand, Reg};
+use crate::Target::x64::X64Reg;
+use crate::IR::TypeMetadata;
 
-use super::CompilationHelper;
+use super::{CompilationHelper, VarLocation};
 
 impl CompilationHelper {
     #[allow(missing_docs)]
     pub fn compile_prolog(&mut self, sink: &mut Vec<MachineInstr>) {
+        if self.naked {
+            return;
+        }
+
         let mut instr = MachineInstr::new( MachineMnemonic::Prolog );
         instr.add_operand( MachineOperand::Imm(self.alloc.stack_off as f64) );
 
@@ -13,10 +19,77 @@ impl CompilationHelper {
         }
 
         sink.push( instr );
+
+        // the stack arguments start after the return adress, the saved registers and the saved frame pointer
+        let stack_args = 16 + self.alloc.callee_save_registers.iter()
+            .map(|save| if matches!(save, Reg::x64(reg) if reg.is_xmm()) { 16 } else { 8 })
+            .sum::<usize>();
+
+        if let Some(va) = self.alloc.va {
+            let mut instr = MachineInstr::new( MachineMnemonic::VaSave );
+            instr.add_operand( MachineOperand::Stack(va.save_area, TypeMetadata::ptr) );
+            instr.add_operand( MachineOperand::Imm(stack_args as f64) );
+
+            sink.push( instr );
+        }
+
+        for (reg, slot) in &self.alloc.arg_stores {
+            // eightbytes of aggregates can also be passed in sse registers
+            let float = matches!(slot, VarLocation::Mem(_, TypeMetadata::f64));
+
+            let mut instr = MachineInstr::new( if float { MachineMnemonic::FMove } else { MachineMnemonic::Move } );
+            instr.set_out( (*slot).into() );
+            instr.add_operand( MachineOperand::Reg(*reg) );
+            instr.meta = if float { TypeMetadata::f64 } else { TypeMetadata::i64 };
+
+            sink.push( instr );
+        }
+
+        for (off, slot) in &self.alloc.arg_loads {
+            let VarLocation::Mem(slot_off, ty) = slot else { unreachable!("stack arguments are loaded into stack slots") };
+
+            // floats are copied as intengers of the same size (they don't need to go over a xmm register)
+            let ty = match *ty {
+                TypeMetadata::f32 => TypeMetadata::i32,
+                TypeMetadata::f64 => TypeMetadata::i64,
+                ty => ty,
+            };
+
+            // negative stack offsets are above the frame pointer
+            let mut instr = MachineInstr::new( MachineMnemonic::Move );
+            instr.set_out( MachineOperand::Stack(*slot_off, ty) );
+            instr.add_operand( MachineOperand::Stack(-(stack_args as i64 + off), ty) );
+            instr.meta = ty;
+
+            sink.push( instr );
+        }
+
+        for (ptr, slot) in &self.alloc.arg_copies {
+            let VarLocation::Mem(_, ty) = slot else { unreachable!("aggregates are copied into stack slots") };
+
+            // the copy uses rax as its temporary, so the pointer is loaded into r11
+            let mut instr = MachineInstr::new( MachineMnemonic::Move );
+            instr.set_out( MachineOperand::Reg(Reg::x64(X64Reg::R11)) );
+            instr.add_operand( (*ptr).into() );
+            instr.meta = TypeMetadata::ptr;
+
+            sink.push( instr );
+
+            let mut instr = MachineInstr::new( MachineMnemonic::Load(None) );
+            instr.set_out( (*slot).into() );
+            instr.add_operand( MachineOperand::Reg(Reg::x64(X64Reg::R11)) );
+            instr.meta = *ty;
+
+            sink.push( instr );
+        }
     }
 
     #[allow(missing_docs)]
     pub fn compile_epilog(&mut self, sink: &mut Vec<MachineInstr>) {
+        if self.naked {
+            return;
+        }
+
         let mut instr = MachineInstr::new( MachineMnemonic::Epilog );
         instr.add_operand( MachineOperand::Imm(self.alloc.stack_off as f64) );
 
@@ -1,15 +1,122 @@
-use crate::prelude::{Return, Block};
-use crate::CodeGen::{MachineInstr, MachineMnemonic};
+use crate::prelude::{Return, Block, IROperand};
+use crate::Target::Arch;
+use crate::CodeGen::{ArgClass, MachineInstr, MachineMnemonic, MachineOperand, Reg};
+use crate::Target::x64::X64Reg;
+use crate::IR::TypeMetadata;
 
 use super::CompilationHelper;
 
 impl CompilationHelper {
     #[allow(missing_docs)]
     pub fn compile_ret(&mut self, node: &Return, mc_sink: &mut Vec<MachineInstr>, _: &Block, _: &mut crate::prelude::Module) {
+        let ty = node.inner1.get_ty();
+
+        if ty.wide() {
+            // 128bit intengers are returned in rdx:rax
+            // they need to be loaded before the epilog restores the stack frame
+            let (lo, hi) = node.inner1.into_mi(self).halves(ty);
+
+            for (half, reg) in [(lo, X64Reg::Rax), (hi, X64Reg::Rdx)] {
+                let mut instr = MachineInstr::new(MachineMnemonic::Move);
+
+                instr.add_operand(half);
+                instr.set_out(MachineOperand::Reg(Reg::x64(reg)));
+                instr.meta = TypeMetadata::i64;
+
+                mc_sink.push(instr);
+            }
+        }
+
+        let mut value = node.inner1.into_mi(self);
+
+        if self.arch == Arch::X86_64 && ty.aggregate() {
+            value = self.compile_agg_ret(value, ty, mc_sink);
+        } else if let (Arch::X86_64, Some(elems)) = (self.arch, ty.tupleElems()) {
+            // every value of the tuple gets its own return register (rax:rdx, xmm0:xmm1)
+            let regs = self.call.return_regs(self.arch, &elems);
+
+            for (index, reg) in regs.iter().enumerate() {
+                let mut instr = MachineInstr::new(MachineMnemonic::Move);
+
+                instr.add_operand(value.field(ty, index));
+                instr.set_out(MachineOperand::Reg(*reg));
+                instr.meta = elems[index];
+
+                mc_sink.push(instr);
+            }
+
+            value = MachineOperand::Reg(regs[0]);
+        } else if self.arch == Arch::X86_64 && !ty.wide() && ty != TypeMetadata::Void {
+            // the value also needs to be moved into the return register before the epilog
+            // restores the stack frame and the callee saved registers (which could hold it)
+            let reg = MachineOperand::Reg(self.call.return_reg(self.arch, ty));
+
+            let allocated = matches!(&node.inner1, IROperand::Var(var) if self.allocated_vars.contains(&var.name));
+
+            let mut instr = MachineInstr::new(if allocated { MachineMnemonic::AdrMove } else { MachineMnemonic::Move });
+            instr.add_operand(value);
+            instr.set_out(reg);
+            instr.meta = ty;
+
+            mc_sink.push(instr);
+
+            value = reg;
+        }
+
         let mut instr = MachineInstr::new(MachineMnemonic::Return);
-        instr.add_operand(node.inner1.into_mi(self));
-        instr.meta = node.inner1.get_ty();
+        instr.add_operand(value);
+        instr.meta = ty;
 
         mc_sink.push( instr );
     }
-}
\ No newline at end of file
+
+    /// Moves the eightbytes of the returned aggregate into their return registers or copies it
+    /// into the memory of the caller (then its adress is returned). Returns the first return register
+    fn compile_agg_ret(&mut self, value: MachineOperand, ty: TypeMetadata, mc_sink: &mut Vec<MachineInstr>) -> MachineOperand {
+        let MachineOperand::Stack(off, _) = value else { panic!("aggregates live on the stack") };
+
+        let classes = self.call.classify(self.arch, ty).expect("the calling convention is verified before compiling");
+
+        if classes.contains(&ArgClass::Memory) {
+            let ptr = self.alloc.ret_ptr.expect("expected the adress of the returned aggregate");
+
+            // the copy uses rax as its temporary
+            let r11 = MachineOperand::Reg(Reg::x64(X64Reg::R11));
+            let rax = MachineOperand::Reg(Reg::x64(X64Reg::Rax));
+
+            let mut instr = MachineInstr::new(MachineMnemonic::Move);
+            instr.add_operand(ptr.into());
+            instr.set_out(r11);
+            instr.meta = TypeMetadata::ptr;
+            mc_sink.push(instr);
+
+            let mut instr = MachineInstr::new(MachineMnemonic::Store(None));
+            instr.add_operand(value);
+            instr.set_out(r11);
+            instr.meta = ty;
+            mc_sink.push(instr);
+
+            let mut instr = MachineInstr::new(MachineMnemonic::Move);
+            instr.add_operand(r11);
+            instr.set_out(rax);
+            instr.meta = TypeMetadata::ptr;
+            mc_sink.push(instr);
+
+            return rax;
+        }
+
+        let regs = self.call.aggregate_return_regs(self.arch, &classes);
+
+        for (index, (class, reg)) in classes.iter().zip(&regs).enumerate() {
+            let mut instr = MachineInstr::new(MachineMnemonic::Move);
+
+            instr.add_operand(MachineOperand::Stack(off - 8 * index as i64, class.ty()));
+            instr.set_out(MachineOperand::Reg(*reg));
+            instr.meta = class.ty();
+
+            mc_sink.push(instr);
+        }
+
+        MachineOperand::Reg(regs[0])
+    }
+}
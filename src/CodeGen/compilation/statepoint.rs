@@ -0,0 +1,83 @@
+use crate::prelude::{Block, Ir, Statepoint};
+use crate::CodeGen::{MachineInstr, MachineMnemonic, VarLocation};
+use crate::Obj::StackMapLocation;
+use crate::Target::{x64::X64Reg, Arch};
+
+use super::CompilationHelper;
+
+impl CompilationHelper {
+    #[allow(missing_docs)]
+    pub fn compile_statepoint(&mut self, node: &Statepoint, mc_sink: &mut Vec<MachineInstr>, _: &Block, module: &mut crate::prelude::Module) {
+        // COMPILES TO:
+        //     mov [rbp - x], root      <- the roots are spilled, so the gc can update them
+        //     call func
+        // label:                       <- the return adress which is recorded in the stack map
+        //     mov root, [rbp - x]      <- reloads the (maybe moved) roots
+
+        if self.arch != Arch::X86_64 {
+            todo!("statepoints are only supported on x64");
+        }
+
+        let caller = self.call;
+
+        if let Some(call_conv) = node.func.ty.call_conv.or(module.getFunc(&node.func.name).and_then(|func| func.ty.call_conv)) {
+            self.call.call_conv = call_conv;
+        }
+
+        let mut roots = Vec::new();
+        let mut root_regs = Vec::new();
+
+        for root in &node.gc {
+            if roots.iter().any(|(name, _)| name == &root.name) {
+                continue;
+            }
+
+            let loc = *self.vars.get(&root.name).expect(&format!("expected valid variable: {}", root.name));
+
+            if let VarLocation::Reg(reg) = loc {
+                root_regs.push(reg);
+            }
+
+            roots.push((root.name.to_owned(), loc));
+        }
+
+        let vars = self.get_vars_to_save_for_call(node.clone_box()).into_iter()
+            .filter(|(name, _)| !roots.iter().any(|(root, _)| root == name))
+            .collect();
+
+        let mut saved = self.save_caller_saved(vars, mc_sink);
+
+        // the roots also get spilled out of the callee saved registers
+        let saved_roots = self.save_vars_in(roots.to_owned(), &root_regs, mc_sink);
+
+        let mut locations = Vec::new();
+
+        for (name, loc) in &roots {
+            let off = match (saved_roots.get(name), loc) {
+                (Some(((off, _), _)), _) => *off,
+                (None, VarLocation::Mem(off, _)) => *off,
+                (None, VarLocation::Reg(_)) => unreachable!("the gc root {} wasn't spilled", name),
+            };
+
+            locations.push(StackMapLocation::Indirect(X64Reg::Rbp.dwarf(), -(off as i32)));
+        }
+
+        saved.extend(saved_roots);
+
+        let start = mc_sink.len();
+
+        let call = MachineInstr::new(MachineMnemonic::Call(node.func.name.to_string()));
+        self.compile_call_with(call, &node.args, saved, Some((&node.out, node.func.ty.ret)), mc_sink);
+
+        let ret_adr = mc_sink[start..].iter()
+            .position(|instr| matches!(instr.mnemonic, MachineMnemonic::Call(_)))
+            .expect("expected the call of the statepoint") + start + 1;
+
+        mc_sink.insert(ret_adr, MachineInstr::new(MachineMnemonic::Label(node.label())));
+
+        self.stack_maps.insert(node.label(), (node.id, locations));
+
+        self.call = caller;
+    }
+}
+
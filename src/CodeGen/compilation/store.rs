@@ -11,7 +11,7 @@ impl CompilationHelper {
         let ptr = self.vars.get(&node.inner1.name).expect("expected valid variable");
         let ptr = (*ptr).into();
 
-        let mut instr = MachineInstr::new(MachineMnemonic::Store);
+        let mut instr = MachineInstr::new(MachineMnemonic::Store(node.align));
 
         instr.set_out( ptr );
         instr.add_operand(in_var);
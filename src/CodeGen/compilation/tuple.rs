@@ -0,0 +1,39 @@
+use super::CompilationHelper;
+use crate::{prelude::*, CodeGen::*};
+
+impl CompilationHelper {
+    #[allow(missing_docs)]
+    pub fn compile_tuple(&mut self, node: &MakeTuple, mc_sink: &mut Vec<MachineInstr>, _: &Block, _: &mut crate::prelude::Module) {
+        let out: MachineOperand = (*self.vars.get(&node.out.name).expect("expected valid variable")).into();
+
+        for (index, elem) in node.elems.iter().enumerate() {
+            let mut instr = MachineInstr::new(MachineMnemonic::Move);
+            instr.set_out(out.field(node.out.ty, index));
+            instr.add_operand(elem.into_mi(self));
+            instr.meta = elem.get_ty();
+
+            mc_sink.push(instr);
+        }
+    }
+
+    #[allow(missing_docs)]
+    pub fn compile_extractvalue(&mut self, node: &ExtractValue, mc_sink: &mut Vec<MachineInstr>, _: &Block, _: &mut crate::prelude::Module) {
+        let tuple: MachineOperand = (*self.vars.get(&node.tuple.name).expect("expected valid variable")).into();
+        let out = (*self.vars.get(&node.out.name).expect("expected valid variable")).into();
+
+        let mut instr = MachineInstr::new(MachineMnemonic::Move);
+        instr.set_out(out);
+        instr.add_operand(tuple.field(node.tuple.ty, node.index));
+        instr.meta = node.out.ty;
+
+        mc_sink.push(instr);
+
+        if let Some(phi_loc) = self.phi_vars.get(&node.out.name) {
+            let mut instr = MachineInstr::new(MachineMnemonic::Move);
+            instr.set_out((*phi_loc).into());
+            instr.add_operand(out);
+            instr.meta = node.out.ty;
+            mc_sink.push(instr);
+        }
+    }
+}
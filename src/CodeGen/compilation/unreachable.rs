@@ -0,0 +1,14 @@
+use super::CompilationHelper;
+use crate::{prelude::*, CodeGen::*};
+
+impl CompilationHelper {
+    #[allow(missing_docs)]
+    pub fn compile_unreachable(&mut self, _: &Unreachable, mc_sink: &mut Vec<MachineInstr>, _: &Block, _: &mut crate::prelude::Module) {
+        mc_sink.push( MachineInstr::new(MachineMnemonic::Unreachable) );
+    }
+
+    #[allow(missing_docs)]
+    pub fn compile_trap(&mut self, _: &Trap, mc_sink: &mut Vec<MachineInstr>, _: &Block, _: &mut crate::prelude::Module) {
+        mc_sink.push( MachineInstr::new(MachineMnemonic::Trap) );
+    }
+}
@@ -0,0 +1,66 @@
+use super::CompilationHelper;
+use crate::{prelude::*, CodeGen::*};
+
+impl CompilationHelper {
+    #[allow(missing_docs)]
+    pub fn compile_va_start(&mut self, node: &VaStart, mc_sink: &mut Vec<MachineInstr>, _: &Block, _: &mut crate::prelude::Module) {
+        let va = self.alloc.va.expect("va_start can only be used in variadic functions");
+
+        let list = self.mem_ptr_operand(&node.list, mc_sink);
+
+        let mut instr = MachineInstr::new(MachineMnemonic::VaStart);
+
+        instr.add_operand(list);
+        instr.add_operand(MachineOperand::Stack(va.save_area, TypeMetadata::ptr));
+        instr.add_operand(MachineOperand::Imm(va.named_grs as f64));
+        instr.add_operand(MachineOperand::Imm(va.named_fps as f64));
+
+        instr.meta = TypeMetadata::ptr;
+
+        mc_sink.push(instr);
+    }
+
+    #[allow(missing_docs)]
+    pub fn compile_va_arg(&mut self, node: &VaArg, mc_sink: &mut Vec<MachineInstr>, _: &Block, _: &mut crate::prelude::Module) {
+        let list = self.mem_ptr_operand(&node.list, mc_sink);
+
+        let out: MachineOperand = (*self.vars.get(&node.out.name).expect("expected valid variable")).into();
+
+        let mut instr = MachineInstr::new(MachineMnemonic::VaArg(node.label("stack"), node.label("load")));
+
+        instr.set_out(out);
+        instr.add_operand(list);
+
+        instr.meta = node.out.ty;
+
+        mc_sink.push(instr);
+
+        if let Some(phi_loc) = self.phi_vars.get(&node.out.name) {
+            let mut instr = MachineInstr::new(MachineMnemonic::Move);
+            instr.set_out((*phi_loc).into());
+            instr.add_operand(out);
+            instr.meta = node.out.ty;
+            mc_sink.push(instr);
+        }
+    }
+
+    #[allow(missing_docs)]
+    pub fn compile_va_end(&mut self, _: &VaEnd, _: &mut Vec<MachineInstr>, _: &Block, _: &mut crate::prelude::Module) {
+        // the va_list doesn't own anything, so there is nothing to clean up
+    }
+
+    #[allow(missing_docs)]
+    pub fn compile_va_copy(&mut self, node: &VaCopy, mc_sink: &mut Vec<MachineInstr>, _: &Block, _: &mut crate::prelude::Module) {
+        let dst = self.mem_ptr_operand(&node.dst, mc_sink);
+        let src = self.mem_ptr_operand(&node.src, mc_sink);
+
+        let mut instr = MachineInstr::new(MachineMnemonic::VaCopy);
+
+        instr.add_operand(dst);
+        instr.add_operand(src);
+
+        instr.meta = TypeMetadata::ptr;
+
+        mc_sink.push(instr);
+    }
+}
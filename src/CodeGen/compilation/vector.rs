@@ -0,0 +1,71 @@
+use super::CompilationHelper;
+use crate::{prelude::*, CodeGen::*};
+
+impl CompilationHelper {
+    #[allow(missing_docs)]
+    pub fn compile_extractelement(&mut self, node: &ExtractElement, mc_sink: &mut Vec<MachineInstr>, _: &Block, _: &mut crate::prelude::Module) {
+        let vec: MachineOperand = (*self.vars.get(&node.vec.name).expect("expected valid variable")).into();
+        let out = (*self.vars.get(&node.out.name).expect("expected valid variable")).into();
+
+        let mut instr = MachineInstr::new(MachineMnemonic::Move);
+        instr.set_out(out);
+        instr.add_operand(vec.element(node.vec.ty, node.index));
+        instr.meta = node.out.ty;
+
+        mc_sink.push(instr);
+
+        if let Some(phi_loc) = self.phi_vars.get(&node.out.name) {
+            let mut instr = MachineInstr::new(MachineMnemonic::Move);
+            instr.set_out((*phi_loc).into());
+            instr.add_operand(out);
+            instr.meta = node.out.ty;
+            mc_sink.push(instr);
+        }
+    }
+
+    #[allow(missing_docs)]
+    pub fn compile_insertelement(&mut self, node: &InsertElement, mc_sink: &mut Vec<MachineInstr>, _: &Block, _: &mut crate::prelude::Module) {
+        let vec = (*self.vars.get(&node.vec.name).expect("expected valid variable")).into();
+        let out: MachineOperand = (*self.vars.get(&node.out.name).expect("expected valid variable")).into();
+
+        let elem = node.elem.into_mi(self);
+
+        let mut copy = MachineInstr::new(MachineMnemonic::Move);
+        copy.set_out(out);
+        copy.add_operand(vec);
+        copy.meta = node.vec.ty;
+
+        mc_sink.push(copy);
+
+        let mut instr = MachineInstr::new(MachineMnemonic::Move);
+        instr.set_out(out.element(node.out.ty, node.index));
+        instr.add_operand(elem);
+        instr.meta = node.elem.get_ty();
+
+        mc_sink.push(instr);
+    }
+
+    #[allow(missing_docs)]
+    pub fn compile_shufflevector(&mut self, node: &ShuffleVector, mc_sink: &mut Vec<MachineInstr>, _: &Block, _: &mut crate::prelude::Module) {
+        let ls: MachineOperand = (*self.vars.get(&node.ls.name).expect("expected valid variable")).into();
+        let rs: MachineOperand = (*self.vars.get(&node.rs.name).expect("expected valid variable")).into();
+        let out: MachineOperand = (*self.vars.get(&node.out.name).expect("expected valid variable")).into();
+
+        let (elem, len) = node.ls.ty.vectorElems().expect("shufflevector expects vectors");
+
+        for (index, selected) in node.mask.iter().enumerate() {
+            let src = if *selected < len {
+                ls.element(node.ls.ty, *selected)
+            } else {
+                rs.element(node.rs.ty, *selected - len)
+            };
+
+            let mut instr = MachineInstr::new(MachineMnemonic::Move);
+            instr.set_out(out.element(node.out.ty, index));
+            instr.add_operand(src);
+            instr.meta = elem;
+
+            mc_sink.push(instr);
+        }
+    }
+}
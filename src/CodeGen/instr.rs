@@ -1,9 +1,9 @@
 use std::error::Error;
 use std::fmt::{Debug, Display};
 use std::any::Any;
-use crate::prelude::CmpMode;
+use crate::prelude::{AtomicOrdering, AtomicRmwOp, BitOpKind, CastKind, CmpMode, FloatOpKind, OverflowKind};
 use crate::Obj::Link;
-use crate::IR::{BlockId, Type, TypeMetadata};
+use crate::IR::{BlockId, FunctionType, Type, TypeMetadata};
 
 use super::reg::Reg;
 use super::{CompilationHelper, VarLocation};
@@ -68,7 +68,8 @@ impl MachineInstr {
     /// 
     /// Returns the fixed machine instr (maybe some got added so)
     pub fn fix_const_imm(&mut self, helper: &mut CompilationHelper, module: &mut crate::prelude::Module) -> Vec<MachineInstr> {
-        if !self.meta.float() {
+        // casts know the type of their input and handle constants themselves
+        if !self.meta.float() || matches!(self.mnemonic, MachineMnemonic::FCast(..) | MachineMnemonic::Bitcast(_)) {
             return vec![self.to_owned()];
         }
 
@@ -106,7 +107,7 @@ impl MachineInstr {
 
                 let float = helper.alloc_rv(self.meta);
 
-                let mut load = MachineInstr::new(MachineMnemonic::Load);
+                let mut load = MachineInstr::new(MachineMnemonic::Load(None));
                 load.set_out(float.into());
                 load.add_operand(location.into());
 
@@ -162,16 +163,76 @@ impl Display for MachineInstr {
 pub enum MachineOperand {
     /// a number
     Imm(f64),
+    /// an exact intenger (128bit constants don't fit into the f64 of `Imm`)
+    Int(i128),
     /// a register
     Reg(Reg),
     /// stack offset
     Stack(i64, TypeMetadata),
 }
 
+impl MachineOperand {
+    /// Returns the immediate for the constant (128bit intengers are kept exact)
+    pub fn constant(value: Type) -> MachineOperand {
+        match value {
+            Type::i128(value) => MachineOperand::Int(value),
+            Type::u128(value) => MachineOperand::Int(value as i128),
+            _ => MachineOperand::Imm(value.val()),
+        }
+    }
+
+    /// Splits the operand of a 128bit intenger (`ty`) into its low and high quadword
+    /// 
+    /// Stack operands point to the low quadword, so the high one is 8 bytes above it
+    pub fn halves(&self, ty: TypeMetadata) -> (MachineOperand, MachineOperand) {
+        match self {
+            MachineOperand::Imm(imm) => {
+                let value = if ty.signed() { *imm as i128 } else { *imm as u128 as i128 };
+
+                MachineOperand::Int(value).halves(ty)
+            },
+            MachineOperand::Int(value) => (MachineOperand::Int(*value as i64 as i128), MachineOperand::Int((*value >> 64) as i64 as i128)),
+            MachineOperand::Stack(off, _) => (MachineOperand::Stack(*off, TypeMetadata::i64), MachineOperand::Stack(*off - 8, TypeMetadata::i64)),
+            MachineOperand::Reg(_) => panic!("128bit intengers can't be stored in a single register"),
+        }
+    }
+
+    /// Returns the operand of the element at `index` of the vector (`ty`)
+    /// 
+    /// Vectors always live on the stack, so the element is `index * elem size` bytes above the vector
+    pub fn element(&self, ty: TypeMetadata, index: usize) -> MachineOperand {
+        let (elem, len) = ty.vectorElems().expect("expected a vector type");
+        let elem_size = ty.byteSize() / len;
+
+        match self {
+            MachineOperand::Stack(off, _) => MachineOperand::Stack(*off - (index * elem_size) as i64, elem),
+            _ => panic!("vectors can only be stored on the stack (got: {})", self),
+        }
+    }
+
+    /// Returns the operand of the value at `index` of the tuple (`ty`)
+    /// 
+    /// On the stack every value has its own eightbyte, in wasm every value has its own local
+    pub fn field(&self, ty: TypeMetadata, index: usize) -> MachineOperand {
+        let elems = ty.tupleElems().expect("expected a tuple type");
+        let elem = *elems.get(index).expect("the index is out of the bounds of the tuple");
+
+        match self {
+            MachineOperand::Stack(off, _) => {
+                let field = ty.fieldOffset(index, &TypeMetadata::defaultLayout()).expect("expected valid field");
+                MachineOperand::Stack(*off - field as i64, elem)
+            },
+            MachineOperand::Reg(Reg::wasm(idx, _)) => MachineOperand::Reg(Reg::wasm(*idx + index as i32, elem)),
+            _ => panic!("tuples can only be stored on the stack or in wasm locals (got: {})", self),
+        }
+    }
+}
+
 impl PartialEq for MachineOperand {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
             (Self::Imm(l0), Self::Imm(r0)) => l0 == r0,
+            (Self::Int(l0), Self::Int(r0)) => l0 == r0,
             (Self::Reg(l0), Self::Reg(r0)) => l0 == r0,
             (Self::Stack(l0, l1), Self::Stack(r0, r1)) => l0 == r0 && l1 == r1,
             _ => false,
@@ -191,6 +252,7 @@ impl Display for MachineOperand {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", match self {
             MachineOperand::Imm(imm) => format!("{:#x?}", imm),
+            MachineOperand::Int(int) => format!("{:#x?}", int),
             MachineOperand::Reg(reg) => format!("{:?}", reg),
             MachineOperand::Stack(off, size) => format!("{size} sp - {:#x?}", off),
         })
@@ -229,6 +291,26 @@ pub enum MachineMnemonic {
     Neg,
     Shl,
     Shr,
+    Rotl,
+    Rotr,
+
+    /// counts the set bits
+    Popcnt,
+    /// counts the leading zeros (the bit size for zero)
+    Clz,
+    /// counts the trailing zeros (the bit size for zero)
+    Ctz,
+    /// reverses the order of the bytes
+    Bswap,
+    /// popcnt, clz or ctz without the instruction for it (the target doesn't have the extension),
+    /// so it is computed in software (popcnt) or with bsr/bsf (clz/ctz)
+    BitOpFallback(BitOpKind),
+
+    /// out: (wrapped) result
+    /// op0: left operand
+    /// op1: right operand
+    /// op2: overflow flag
+    Overflow(OverflowKind),
 
     FMove,
     FAdd,
@@ -243,34 +325,111 @@ pub enum MachineMnemonic {
     FShl,
     FShr,
     FCompare(CmpMode),
-    FCast(/*from type*/TypeMetadata),
+    /// conversions between floats and from/to intengers
+    FCast(CastKind, /*from type*/TypeMetadata),
+    /// float math functions (sqrt, floor, min, ...) the target has instructions for
+    /// 
+    /// out: result
+    /// ops: the operands
+    FloatOp(FloatOpKind),
 
     BrCond(/*if yes*/String, /*if no*/String),
     Compare(CmpMode),
 
     Zext(/*from type*/TypeMetadata),
+    Sext(/*from type*/TypeMetadata),
     Downcast(/*from type*/TypeMetadata),
+    /// reinterprets the bits of op0 as the type of out
+    Bitcast(/*from type*/TypeMetadata),
 
     Call(String),
+    /// op0: the adress of the called function
+    CallIndirect(FunctionType),
+    /// jumps to the function (the frame of the caller is already torn down)
+    TailCall(String),
     Br(String),
     Return,
+    /// the control flow never reaches this point
+    Unreachable,
+    /// aborts the execution
+    Trap,
+
+    /// op0: destination pointer
+    /// op1: source pointer
+    /// op2: size
+    MemCpy,
+    /// op0: destination pointer
+    /// op1: source pointer
+    /// op2: size
+    MemMove,
+    /// op0: destination pointer
+    /// op1: byte value
+    /// op2: size
+    MemSet,
+
+    /// out: loaded value
+    /// op0: pointer
+    AtomicLoad(AtomicOrdering),
+    /// op0: pointer
+    /// op1: value
+    AtomicStore(AtomicOrdering),
+    /// out: old value
+    /// op0: pointer
+    /// op1: value
+    /// the string is the label of the retry loop
+    AtomicRmw(AtomicRmwOp, AtomicOrdering, String),
+    /// out: old value
+    /// op0: pointer
+    /// op1: expected value
+    /// op2: new value
+    CmpXchg(AtomicOrdering),
+    Fence(AtomicOrdering),
+
+    /// saves the argument registers of a variadic function (emitted after the prolog)
+    /// op0: the stack slot of the register save area
+    /// op1: the offset of the arguments passed over the stack from the frame pointer
+    VaSave,
+    /// op0: pointer to the va_list
+    /// op1: the stack slot of the register save area
+    /// op2: how many named arguments were passed in general purpose registers
+    /// op3: how many named arguments were passed in floating point registers
+    VaStart,
+    /// out: the argument
+    /// op0: pointer to the va_list
+    /// the strings are the labels of the stack and the load branch
+    VaArg(String, String),
+    /// op0: pointer to the destination va_list
+    /// op1: pointer to the source va_list
+    VaCopy,
+
+    /// the assembly with `$0`, `$1`, ... replaced by the operands
+    /// op*: the operands (registers or immediates) in the order of the constraints
+    InlineAsm(String),
 
     AdressLoad(String),
     StackAlloc,
 
-    Store,
-    Load,
+    /// marks its position in the function (e.g: the call site of an invoke for the unwind tables)
+    Label(String),
+
+    /// out: pointer
+    /// op0: value
+    /// (the explicit alignment of the pointer in bytes)
+    Store(Option<usize>),
+    /// out: loaded value
+    /// op0: pointer
+    /// (the explicit alignment of the pointer in bytes)
+    Load(Option<usize>),
 
     Prolog,
     Epilog,
 
-    /// stack arg
+    /// stack arg (a quadword)
     Push,
 
-    /// stack arg cleanup
-    PushCleanup,
-
+    /// reserves stack space for a call (the size is the first operand)
     CallStackPrepare,
+    /// frees the stack space of a call (the size is the first operand)
     CallStackRedo,
 
     AdrMove,
@@ -302,9 +461,28 @@ impl MachineMnemonic {
             MachineMnemonic::Xor =>                 "xor",
             MachineMnemonic::Rem =>                 "rem",
             MachineMnemonic::Zext(_) =>                "zext",
+            MachineMnemonic::Sext(_) =>                "sext",
             MachineMnemonic::Downcast(_) =>            "dwcast",
+            MachineMnemonic::Bitcast(_) =>             "bitcast",
             MachineMnemonic::Call(_) =>             "call",
+            MachineMnemonic::CallIndirect(_) =>     "icall",
+            MachineMnemonic::TailCall(_) =>         "tailcall",
             MachineMnemonic::Return =>              "return",
+            MachineMnemonic::Unreachable =>         "unreachable",
+            MachineMnemonic::Trap =>                "trap",
+            MachineMnemonic::MemCpy =>              "memcpy",
+            MachineMnemonic::MemMove =>             "memmove",
+            MachineMnemonic::MemSet =>              "memset",
+            MachineMnemonic::AtomicLoad(_) =>       "atomic_load",
+            MachineMnemonic::AtomicStore(_) =>      "atomic_store",
+            MachineMnemonic::AtomicRmw(..) =>       "atomicrmw",
+            MachineMnemonic::CmpXchg(_) =>          "cmpxchg",
+            MachineMnemonic::Fence(_) =>            "fence",
+            MachineMnemonic::VaSave =>              "va_save",
+            MachineMnemonic::VaStart =>             "va_start",
+            MachineMnemonic::VaArg(..) =>          "va_arg",
+            MachineMnemonic::VaCopy =>              "va_copy",
+            MachineMnemonic::InlineAsm(_) =>        "asm",
             MachineMnemonic::AdressLoad(_) =>       "adrload",
             MachineMnemonic::Br(_) =>               "br",
             MachineMnemonic::BrCond(_, _) =>        "comparebr",
@@ -312,10 +490,10 @@ impl MachineMnemonic {
             MachineMnemonic::Prolog =>              "prolog",
             MachineMnemonic::Epilog =>              "epilog",
             MachineMnemonic::StackAlloc =>          "salloc",
-            MachineMnemonic::Store =>               "store",
-            MachineMnemonic::Load =>                "load",
+            MachineMnemonic::Label(_) =>            "label",
+            MachineMnemonic::Store(_) =>            "store",
+            MachineMnemonic::Load(_) =>             "load",
             MachineMnemonic::Push =>                "push",
-            MachineMnemonic::PushCleanup =>         "clean_push",
             MachineMnemonic::CallStackPrepare =>    "callsprep",
             MachineMnemonic::CallStackRedo =>       "callspred",
             MachineMnemonic::AdrMove =>             "adrmov",    
@@ -325,6 +503,14 @@ impl MachineMnemonic {
             MachineMnemonic::MovIfNotZero =>        "cmovnz",
             MachineMnemonic::Shl =>                 "shl",
             MachineMnemonic::Shr =>                 "shr",
+            MachineMnemonic::Rotl =>                "rotl",
+            MachineMnemonic::Rotr =>                "rotr",
+            MachineMnemonic::Popcnt =>              "popcnt",
+            MachineMnemonic::Clz =>                 "clz",
+            MachineMnemonic::Ctz =>                 "ctz",
+            MachineMnemonic::Bswap =>               "bswap",
+            MachineMnemonic::BitOpFallback(_) =>    "bitop",
+            MachineMnemonic::Overflow(_) =>         "overflow",
             MachineMnemonic::FMove =>               "fmove",
             MachineMnemonic::FAdd =>                "fadd",
             MachineMnemonic::FAnd =>                "fand",
@@ -338,7 +524,8 @@ impl MachineMnemonic {
             MachineMnemonic::FShl =>                "fshl",
             MachineMnemonic::FShr =>                "fshr",
             MachineMnemonic::FCompare(_) =>         "fcompare",
-            MachineMnemonic::FCast(_) =>            "fcast",
+            MachineMnemonic::FCast(..) =>           "fcast",
+            MachineMnemonic::FloatOp(_) =>          "floatop",
         }
     }
 }
@@ -346,8 +533,19 @@ impl MachineMnemonic {
 impl Display for MachineMnemonic {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", match self {
-            MachineMnemonic::Call(target) => format!("{} {}", self.name(), target),
+            MachineMnemonic::Call(target) | MachineMnemonic::TailCall(target) => format!("{} {}", self.name(), target),
             MachineMnemonic::AdressLoad(adr) => format!("{} {}", self.name(), adr),
+            MachineMnemonic::Label(label) => format!("{} {}", self.name(), label),
+            MachineMnemonic::AtomicLoad(order) => format!("{} {}", self.name(), order),
+            MachineMnemonic::AtomicStore(order) => format!("{} {}", self.name(), order),
+            MachineMnemonic::AtomicRmw(op, order, _) => format!("{} {} {}", self.name(), op, order),
+            MachineMnemonic::CmpXchg(order) => format!("{} {}", self.name(), order),
+            MachineMnemonic::Overflow(kind) => format!("{} {}", self.name(), kind),
+            MachineMnemonic::Fence(order) => format!("{} {}", self.name(), order),
+            MachineMnemonic::InlineAsm(asm) => format!("{} {:?}", self.name(), asm),
+            MachineMnemonic::FCast(kind, _) => format!("{} {}", self.name(), kind),
+            MachineMnemonic::FloatOp(kind) => format!("{} {}", self.name(), kind),
+            MachineMnemonic::Store(Some(align)) | MachineMnemonic::Load(Some(align)) => format!("{} align {}", self.name(), align),
             _ => self.name().to_string()
         })
     }
@@ -2,7 +2,7 @@ use crate::debug::DebugLocation;
 use crate::prelude::{DebugNode, Ir};
 use crate::CodeGen::MachineInstr;
 
-use crate::IR::{ir, Block, Const, Type, Var};
+use crate::IR::{ir, Block, Const, FuncId, Global, Type, Var};
 
 use super::CompilationHelper;
 
@@ -135,6 +135,16 @@ impl IrCodeGenHelper {
         "Loweres the shr node", 
         ir::Shr
     );
+    ir_codegen_wrap!(
+        compile_rotl, 
+        "Loweres the rotl node", 
+        ir::Rotl
+    );
+    ir_codegen_wrap!(
+        compile_rotr, 
+        "Loweres the rotr node", 
+        ir::Rotr
+    );
     ir_codegen_wrap!(
         compile_alloca, 
         "Loweres the alloca node", 
@@ -155,6 +165,16 @@ impl IrCodeGenHelper {
         "Loweres the assign node", 
         ir::Assign<Var, Const>
     );
+    ir_codegen_wrap!(
+        compile_assign_var_func, 
+        "Loweres the assign node", 
+        ir::Assign<Var, FuncId>
+    );
+    ir_codegen_wrap!(
+        compile_assign_var_global, 
+        "Loweres the assign node", 
+        ir::Assign<Var, Global>
+    );
     ir_codegen_wrap!(
         compile_br, 
         "Loweres the xor node", 
@@ -170,6 +190,11 @@ impl IrCodeGenHelper {
         "Loweres the call node", 
         ir::Call
     );
+    ir_codegen_wrap!(
+        compile_call_indirect, 
+        "Loweres the indirect call node", 
+        ir::CallIndirect
+    );
     ir_codegen_wrap!(
         compile_cast, 
         "Loweres the cast node", 
@@ -200,11 +225,116 @@ impl IrCodeGenHelper {
         "Loweres the switch node", 
         ir::Switch
     );
+    ir_codegen_wrap!(
+        compile_memcpy, 
+        "Loweres the memcpy node", 
+        ir::MemCpy
+    );
+    ir_codegen_wrap!(
+        compile_memmove, 
+        "Loweres the memmove node", 
+        ir::MemMove
+    );
+    ir_codegen_wrap!(
+        compile_memset, 
+        "Loweres the memset node", 
+        ir::MemSet
+    );
+    ir_codegen_wrap!(
+        compile_atomic_load, 
+        "Loweres the atomic load node", 
+        ir::AtomicLoad
+    );
+    ir_codegen_wrap!(
+        compile_atomic_store, 
+        "Loweres the atomic store node", 
+        ir::AtomicStore
+    );
+    ir_codegen_wrap!(
+        compile_atomic_rmw, 
+        "Loweres the atomicrmw node", 
+        ir::AtomicRmw
+    );
+    ir_codegen_wrap!(
+        compile_cmpxchg, 
+        "Loweres the cmpxchg node", 
+        ir::CmpXchg
+    );
+    ir_codegen_wrap!(
+        compile_fence, 
+        "Loweres the fence node", 
+        ir::Fence
+    );
+    ir_codegen_wrap!(
+        compile_unreachable, 
+        "Loweres the unreachable node", 
+        ir::Unreachable
+    );
+    ir_codegen_wrap!(
+        compile_trap, 
+        "Loweres the trap node", 
+        ir::Trap
+    );
     ir_codegen_wrap!(
         compile_neg, 
         "Loweres the neg node", 
         ir::Neg
     );
+    ir_codegen_wrap!(
+        compile_bitop, 
+        "Loweres the popcnt/clz/ctz/bswap node", 
+        ir::BitOp
+    );
+    ir_codegen_wrap!(
+        compile_overflowop, 
+        "Loweres the overflow checked arithmetic node", 
+        ir::OverflowOp
+    );
+    ir_codegen_wrap!(
+        compile_floatop, 
+        "Loweres the float math function node", 
+        ir::FloatOp
+    );
+    ir_codegen_wrap!(
+        compile_va_start, 
+        "Loweres the va_start node", 
+        ir::VaStart
+    );
+    ir_codegen_wrap!(
+        compile_va_arg, 
+        "Loweres the va_arg node", 
+        ir::VaArg
+    );
+    ir_codegen_wrap!(
+        compile_va_end, 
+        "Loweres the va_end node", 
+        ir::VaEnd
+    );
+    ir_codegen_wrap!(
+        compile_va_copy, 
+        "Loweres the va_copy node", 
+        ir::VaCopy
+    );
+    ir_codegen_wrap!(
+        compile_invoke, 
+        "Loweres the invoke node", 
+        ir::Invoke
+    );
+    ir_codegen_wrap!(
+        compile_landingpad, 
+        "Loweres the landingpad node", 
+        ir::LandingPad
+    );
+    ir_codegen_wrap!(
+        compile_statepoint, 
+        "Loweres the statepoint node", 
+        ir::Statepoint
+    );
+    ir_codegen_wrap!(
+        compile_asm, 
+        "Loweres the inline assembly node", 
+        ir::InlineAsm
+    );
     ir_codegen_wrap!(
         compile_select, 
         "Loweres the select ty ty node", 
@@ -215,6 +345,31 @@ impl IrCodeGenHelper {
         "Loweres the getelemptr node", 
         ir::GetElemPtr
     );
+    ir_codegen_wrap!(
+        compile_extractelement, 
+        "Loweres the extractelement node", 
+        ir::ExtractElement
+    );
+    ir_codegen_wrap!(
+        compile_insertelement, 
+        "Loweres the insertelement node", 
+        ir::InsertElement
+    );
+    ir_codegen_wrap!(
+        compile_tuple, 
+        "Loweres the tuple node", 
+        ir::MakeTuple
+    );
+    ir_codegen_wrap!(
+        compile_extractvalue, 
+        "Loweres the extractvalue node", 
+        ir::ExtractValue
+    );
+    ir_codegen_wrap!(
+        compile_shufflevector, 
+        "Loweres the shufflevector node", 
+        ir::ShuffleVector
+    );
 }
 
 impl Into<Vec<MachineInstr>> for IrCodeGenHelper {
@@ -0,0 +1,94 @@
+use std::fmt::Display;
+
+/// An attribute which describes a property of a function (or a call site)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum FnAttr {
+    /// The function should never be inlined
+    NoInline,
+    /// The function should always be inlined
+    AlwaysInline,
+    /// The function never returns to its caller
+    NoReturn,
+    /// The function is rarely called
+    Cold,
+    /// The function gets no prolog and no epilog
+    Naked,
+    /// The function doesn't read or write memory (so it only depends on its arguments)
+    ReadNone,
+}
+
+impl FnAttr {
+    /// Parses the attribute out of its ir name (e.g: `noinline`)
+    pub fn parse(string: &str) -> Option<Self> {
+        match string {
+            "noinline" => Some(FnAttr::NoInline),
+            "alwaysinline" => Some(FnAttr::AlwaysInline),
+            "noreturn" => Some(FnAttr::NoReturn),
+            "cold" => Some(FnAttr::Cold),
+            "naked" => Some(FnAttr::Naked),
+            "readnone" => Some(FnAttr::ReadNone),
+            _ => None,
+        }
+    }
+}
+
+impl Display for FnAttr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", match self {
+            FnAttr::NoInline => "noinline",
+            FnAttr::AlwaysInline => "alwaysinline",
+            FnAttr::NoReturn => "noreturn",
+            FnAttr::Cold => "cold",
+            FnAttr::Naked => "naked",
+            FnAttr::ReadNone => "readnone",
+        })
+    }
+}
+
+/// A set of function attributes
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct FnAttrs {
+    attrs: Vec<FnAttr>,
+}
+
+impl FnAttrs {
+    /// Creates an empty attribute set
+    pub fn new() -> Self {
+        Self {
+            attrs: Vec::new(),
+        }
+    }
+
+    /// Adds the attribute (if it isn't already in the set)
+    pub fn add(&mut self, attr: FnAttr) {
+        if let Err(index) = self.attrs.binary_search(&attr) {
+            self.attrs.insert(index, attr);
+        }
+    }
+
+    /// Removes the attribute from the set
+    pub fn remove(&mut self, attr: FnAttr) {
+        self.attrs.retain(|a| *a != attr);
+    }
+
+    /// Returns if the set contains the attribute
+    pub fn has(&self, attr: FnAttr) -> bool {
+        self.attrs.contains(&attr)
+    }
+
+    /// Returns if the set has no attributes
+    pub fn is_empty(&self) -> bool {
+        self.attrs.is_empty()
+    }
+
+    /// Returns an iterator over all attributes of the set
+    pub fn iter(&self) -> impl Iterator<Item = &FnAttr> {
+        self.attrs.iter()
+    }
+}
+
+impl Display for FnAttrs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.attrs.iter().map(|attr| attr.to_string()).collect::<Vec<_>>().join(" "))
+    }
+}
@@ -1,6 +1,8 @@
+use std::collections::BTreeMap;
+
 use crate::Support::{ColorProfile, Colorize};
 
-use super::{ir::Ir, Function, Var, VerifyError};
+use super::{ir::{Call, Ir}, Function, Metadata, MetadataNode, Var, VerifyError};
 
 /// A basic block: stores ir of a specific area of a function
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -9,6 +11,8 @@ pub struct Block {
     pub(crate) name: String,
     pub(crate) nodes: Vec<Box<dyn Ir>>,
     pub(crate) varCount: usize,
+    /// the metadata which is attached to the nodes (stored as: node index, metadata)
+    pub(crate) metadata: BTreeMap<usize, Metadata>,
 }
 
 impl Block {
@@ -19,6 +23,7 @@ impl Block {
             name: name.to_string(),
             nodes: vec![],
             varCount: func.ty.args.len(),
+            metadata: BTreeMap::new(),
         }
     }
 
@@ -28,8 +33,11 @@ impl Block {
 
         dump += &format!("  {}:\n", self.name);
 
-        for node in &self.nodes {
-            dump += &format!("\t{}\n", node.dump());
+        for (index, node) in self.nodes.iter().enumerate() {
+            match self.metadata.get(&index) {
+                Some(metadata) => dump += &format!("\t{} {}\n", node.dump(), metadata),
+                None => dump += &format!("\t{}\n", node.dump()),
+            }
         }
 
         dump
@@ -39,14 +47,92 @@ impl Block {
         self.nodes.push( ir );
     }
 
+    /// Attaches the metadata node to the node at `index`
+    pub fn attach(&mut self, index: usize, node: MetadataNode) {
+        self.metadata.entry(index).or_default().add(node);
+    }
+
+    /// Returns the metadata which is attached to the node at `index`
+    pub fn metadata(&self, index: usize) -> Option<&Metadata> {
+        self.metadata.get(&index)
+    }
+
+    /// Removes the node at `index` (its metadata is also removed)
+    pub(crate) fn remove_node(&mut self, index: usize) -> Box<dyn Ir> {
+        self.splice_nodes(index, 1, Vec::new()).remove(0)
+    }
+
+    /// Inserts the node at `index` (the metadata of the following nodes moves with them)
+    pub(crate) fn insert_node(&mut self, index: usize, node: Box<dyn Ir>) {
+        self.splice_nodes(index, 0, vec![node]);
+    }
+
+    /// Removes all nodes after the first `len` nodes (and their metadata)
+    pub(crate) fn truncate_nodes(&mut self, len: usize) {
+        self.nodes.truncate(len);
+        self.metadata.retain(|index, _| *index < len);
+    }
+
+    /// Replaces the node at `index` with the nodes (they keep the metadata of the replaced node)
+    pub(crate) fn replace_node(&mut self, index: usize, nodes: Vec<Box<dyn Ir>>) {
+        let metadata = self.metadata.remove(&index);
+        let len = nodes.len();
+
+        self.splice_nodes(index, 1, nodes);
+
+        if let Some(metadata) = metadata {
+            for index in index..index + len {
+                self.metadata.insert(index, metadata.to_owned());
+                self.drop_stale_metadata(index);
+            }
+        }
+    }
+
+    /// Removes the `!branch_weights` of the node at `index` if it isn't a conditional branch (anymore)
+    pub(crate) fn drop_stale_metadata(&mut self, index: usize) {
+        if self.nodes[index].is_brcond() {
+            return;
+        }
+
+        if let Some(metadata) = self.metadata.get_mut(&index) {
+            metadata.remove("branch_weights");
+
+            if metadata.is_empty() {
+                self.metadata.remove(&index);
+            }
+        }
+    }
+
+    /// Replaces `count` nodes at `index` with the nodes and moves the metadata of the following nodes
+    fn splice_nodes(&mut self, index: usize, count: usize, nodes: Vec<Box<dyn Ir>>) -> Vec<Box<dyn Ir>> {
+        let inserted = nodes.len();
+
+        let removed = self.nodes.splice(index..index + count, nodes).collect();
+
+        let metadata = std::mem::take(&mut self.metadata);
+
+        for (node, metadata) in metadata {
+            if node < index {
+                self.metadata.insert(node, metadata);
+            } else if node >= index + count {
+                self.metadata.insert(node - count + inserted, metadata);
+            }
+        }
+
+        removed
+    }
+
     /// Emits the ir of the block into one colored string
     pub fn dumpColored(&self, profile: ColorProfile) -> String {
         let mut dump = String::new();
 
         dump += &format!("{}:\n", self.name.cyan());
 
-        for node in &self.nodes {
-            dump += &format!("    {}\n", node.dumpColored(profile));
+        for (index, node) in self.nodes.iter().enumerate() {
+            match self.metadata.get(&index) {
+                Some(metadata) => dump += &format!("    {} {}\n", node.dumpColored(profile), metadata.to_string().magenta()),
+                None => dump += &format!("    {}\n", node.dumpColored(profile)),
+            }
         }
 
         dump
@@ -63,11 +149,27 @@ impl Block {
 
     /// Verifys if the block and all of its ir nodes are correct:
     ///  * Checks if the return type is the actual specified return type of the function
+    ///  * Checks that the block ends with a terminator
     pub fn verify(&self, func: &Function) -> Result<(), VerifyError> {
         for node in &self.nodes {
             node.verify(func.ty.clone())?;
         }
 
+        // `unreachable` and `trap` end the block like `ret` or `br` do
+        if !self.nodes.iter().rev().find(|node| !node.is_debug()).is_some_and(|last| last.is_terminator()) {
+            Err(VerifyError::MissingTerminator)?
+        }
+
+        let mut nodes = self.nodes.iter().filter(|node| !node.is_debug()).peekable();
+
+        while let Some(node) = nodes.next() {
+            if let Some(call) = node.as_any().downcast_ref::<Call>() {
+                if call.tail && !call.returned_by(nodes.peek().copied()) {
+                    Err(VerifyError::TailCallNotInTailPosition)?
+                }
+            }
+        }
+
         Ok(())
     }
 
@@ -7,6 +7,19 @@ pub struct Const {
     pub(crate) name: String,
     pub(crate) data: Vec<u8>,
     pub(crate) linkage: Linkage,
+    /// The symbol adresses which get written into the data
+    pub(crate) adrs: Vec<ConstAdr>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// The adress of a symbol (+ addend) which is placed into the data of a constant
+pub struct ConstAdr {
+    /// The offset of the adress in the data
+    pub at: usize,
+    /// The symbol name (e.g: a function, constant or global)
+    pub symbol: String,
+    /// The addend to add onto the adress
+    pub addend: i64,
 }
 
 impl Const {
@@ -16,12 +29,37 @@ impl Const {
             name: name,
             data: vec![],
             linkage: Linkage::Internal,
+            adrs: vec![],
         }
     }
 
-    /// sets the data of the constant
+    /// sets the data of the constant (this removes all symbol adresses)
     pub fn set(&mut self, data: Vec<u8>) {
         self.data = data;
+        self.adrs.clear();
+    }
+
+    /// appends the bytes to the data of the constant
+    pub fn addBytes(&mut self, bytes: &[u8]) {
+        self.data.extend_from_slice(bytes);
+    }
+
+    /// appends the 64bit adress of the symbol (+ the addend) to the data of the constant.
+    /// 
+    /// The adress gets filled in by the linker
+    pub fn addAdr(&mut self, symbol: &str, addend: i64) {
+        self.adrs.push(ConstAdr {
+            at: self.data.len(),
+            symbol: symbol.to_string(),
+            addend: addend,
+        });
+
+        self.data.extend_from_slice(&[0; 8]);
+    }
+
+    /// Returns the symbol adresses of the constant
+    pub fn getAdrs(&self) -> &[ConstAdr] {
+        &self.adrs
     }
 
     /// gets the data of the constant
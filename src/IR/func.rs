@@ -2,16 +2,24 @@ use std::collections::VecDeque;
 
 use super::block::BlockId;
 use super::Block;
+use super::FnAttr;
+use super::FnAttrs;
+use super::Metadata;
+use super::MetadataNode;
 use super::TypeMetadata;
 use super::Var;
 use super::VerifyError;
+use super::Module;
+use crate::prelude::{Call, CallIndirect, Invoke};
 use crate::prelude::PassManager;
 use crate::Obj::Linkage;
+use crate::CodeGen::MachineCallingConvention;
+use crate::Target::{Arch, CallConv};
 use crate::Support::ColorClass;
 use crate::Support::ColorProfile;
 
 /// Stores the function type
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct FunctionType {
     /// The function arguments (stored as: num, type)
     pub args: Vec<(String, TypeMetadata)>,
@@ -19,6 +27,8 @@ pub struct FunctionType {
     pub ret: TypeMetadata,
     /// After the given arguments any argument type can be supplied (like the printf function - is in c ...)
     pub any_args: bool,
+    /// The calling convention of the function (if it's `None` the one of the target is used)
+    pub call_conv: Option<CallConv>,
 }
 
 impl FunctionType {
@@ -28,9 +38,15 @@ impl FunctionType {
             args: args,
             ret: ret,
             any_args: false,
+            call_conv: None,
         }
     }
 
+    /// Sets the calling convention of the function (instead of the one of the target)
+    pub fn set_call_conv(&mut self, call_conv: CallConv) {
+        self.call_conv = Some(call_conv);
+    }
+
     /// Activates dynamic arguments
     /// Makes that you can supply any argument after the fixed given arguments
     pub fn activate_dynamic_arguments(&mut self) {
@@ -51,6 +67,45 @@ impl FunctionType {
 
         panic!("the func has {} args but args {} is accesed", self.args.len(), num)
     }
+
+    /// Checks that the arguments and the return type can be passed with the calling convention
+    /// 
+    /// Tuples are returned in two intenger and two float registers,
+    /// the windows calling convention only has one return register so it can't return them.
+    /// It also passes 128bit intengers over a pointer, which isn't supported yet.
+    /// Vectors can't be passed or returned in any calling convention yet
+    pub(crate) fn verify_call_conv(&self) -> Result<(), VerifyError> {
+        self.verify_call_conv_or(None)
+    }
+
+    /// Like `verify_call_conv` but uses `default` if the function doesn't specify a calling convention
+    pub(crate) fn verify_call_conv_or(&self, default: Option<CallConv>) -> Result<(), VerifyError> {
+        let call_conv = self.call_conv.or(default);
+
+        if let Some(ty) = self.args.iter().map(|(_, ty)| *ty).chain([self.ret]).find(|ty| ty.vector()) {
+            Err(VerifyError::VectorArgUnsupported(ty))?
+        }
+
+        if call_conv == Some(CallConv::WindowsFastCall) && self.args.iter().any(|(_, ty)| ty.wide()) {
+            Err(VerifyError::WideArgUnsupported(CallConv::WindowsFastCall))?
+        }
+
+        let Some(elems) = self.ret.tupleElems() else {
+            return Ok(());
+        };
+
+        if call_conv == Some(CallConv::WindowsFastCall) {
+            Err(VerifyError::TupleRetUnsupported(CallConv::WindowsFastCall))?
+        }
+
+        let floats = elems.iter().filter(|elem| elem.float()).count();
+
+        if floats > 2 || elems.len() - floats > 2 {
+            Err(VerifyError::TupleTooLargeToReturn(self.ret))?
+        }
+
+        Ok(())
+    }
 }
 
 /// A ir function with a known variable and arg size and count
@@ -63,6 +118,13 @@ pub struct Function {
     
     pub(crate) linkage: Linkage,
     pub(crate) blocks: VecDeque<Block>,
+
+    pub(crate) attrs: FnAttrs,
+
+    /// The personality function which is used to unwind through the function
+    pub(crate) personality: Option<String>,
+
+    pub(crate) metadata: Metadata,
 }
 
 impl Function {
@@ -76,6 +138,12 @@ impl Function {
             name: name,
 
             linkage: Linkage::Internal,
+
+            attrs: FnAttrs::new(),
+
+            personality: None,
+
+            metadata: Metadata::new(),
         }
     }
 
@@ -94,6 +162,54 @@ impl Function {
         self.linkage = Linkage::Internal;
     }
 
+    /// Adds the attribute to the function (e.g: `noinline`)
+    pub fn add_attr(&mut self, attr: FnAttr) {
+        self.attrs.add(attr);
+    }
+
+    /// Returns if the function has the attribute
+    pub fn has_attr(&self, attr: FnAttr) -> bool {
+        self.attrs.has(attr)
+    }
+
+    /// Returns all attributes of the function
+    pub fn attrs(&self) -> &FnAttrs {
+        &self.attrs
+    }
+
+    /// Sets the personality function which decides which landing pad
+    /// of the function is entered by an exception (e.g: `__gxx_personality_v0`)
+    pub fn set_personality(&mut self, name: &str) {
+        self.personality = Some(name.to_owned());
+    }
+
+    /// Returns the personality function of the function
+    pub fn personality(&self) -> Option<&String> {
+        self.personality.as_ref()
+    }
+
+    /// Attaches the metadata node to the function (e.g: `!span ["main.c", 12, 0]`)
+    pub fn add_metadata(&mut self, node: MetadataNode) {
+        self.metadata.add(node);
+    }
+
+    /// Returns the metadata which is attached to the function
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    /// Attaches the metadata node to the last node of the current block
+    /// ```no-run
+    /// br cond %0 then, else !branch_weights [90, 10]
+    /// ```
+    pub fn attach(&mut self, node: MetadataNode) {
+        let block = self.blocks.back_mut().expect("expected current block");
+
+        let index = block.nodes.len().checked_sub(1).expect("expected a node which the metadata is attached to");
+
+        block.attach(index, node);
+    }
+
     /// Adds a new block to the function
     pub fn addBlock(&mut self, name: &str) -> BlockId {
         self.blocks.push_back(Block::new(name, &self));
@@ -102,8 +218,14 @@ impl Function {
 
     /// Emits the Ir of the function into a string
     pub fn dump(&self) -> String {
+        let attrs = if self.attrs.is_empty() { String::new() } else { format!(" {}", self.attrs) };
+        let conv = if let Some(conv) = self.ty.call_conv { format!("{} ", conv) } else { String::new() };
+        let personality = if let Some(personality) = &self.personality { format!(" personality @{}", personality) } else { String::new() };
+        let metadata = if self.metadata.is_empty() { String::new() } else { format!(" {}", self.metadata) };
+
         if self.linkage == Linkage::Extern {
-            let string = format!("declare {} @{}({})\n",
+            let string = format!("declare {}{} @{}({}){}{}\n",
+                conv,
                 self.ty.ret,
                 self.name, {
                     let mut fmt = String::new();
@@ -113,19 +235,23 @@ impl Function {
                         fmt += &format!("{} {}, ", arg.ty, arg.name);
                     }
 
-                    if self.ty.args.len() > 0 {
+                    if self.ty.any_args {
+                        fmt += "..., ";
+                    }
+
+                    if !fmt.is_empty() {
                         fmt.remove(fmt.chars().count() - 1); // The last space
                         fmt.remove(fmt.chars().count() - 1); // The last comma
                     }
         
                     fmt
-                });
+                }, attrs, metadata);
             return string;
         }
 
         let mut string = String::new();
 
-        string += &format!("define {} {} @{}({}) {{\n", self.linkage, self.ty.ret, self.name, {
+        string += &format!("define {} {}{} @{}({}){}{}{} {{\n", self.linkage, conv, self.ty.ret, self.name, {
             let mut fmt = String::new();
 
             for index in 0..self.ty.args.len() {
@@ -133,13 +259,17 @@ impl Function {
                 fmt += &format!("{} {}, ", arg.ty, arg.name);
             }
 
-            if self.ty.args.len() > 0 {
+            if self.ty.any_args {
+                fmt += "..., ";
+            }
+
+            if !fmt.is_empty() {
                 fmt.remove(fmt.chars().count() - 1); // The last space
                 fmt.remove(fmt.chars().count() - 1); // The last comma
             }
 
             fmt
-        });
+        }, attrs, personality, metadata);
 
         for block in &self.blocks {
             string += &format!("{}\n", block.dump());
@@ -152,10 +282,18 @@ impl Function {
 
     /// Emits the Ir of the function into an colored string
     pub fn dumpColored(&self, profile: ColorProfile) -> String {
+        let attrs = if self.attrs.is_empty() { String::new() } else { format!(" {}", profile.markup(&self.attrs.to_string(), ColorClass::Instr)) };
+        let conv = if let Some(conv) = self.ty.call_conv { format!("{} ", profile.markup(&conv.to_string(), ColorClass::Instr)) } else { String::new() };
+        let personality = if let Some(personality) = &self.personality {
+            format!(" {} @{}", profile.markup("personality", ColorClass::Instr), profile.markup(personality, ColorClass::Name))
+        } else { String::new() };
+        let metadata = if self.metadata.is_empty() { String::new() } else { format!(" {}", profile.markup(&self.metadata.to_string(), ColorClass::Name)) };
+
         if self.linkage == Linkage::Extern {
-            let string = format!("{} {} {} @{}( {})\n",
+            let string = format!("{} {} {}{} @{}( {}){}{}\n",
                 profile.markup("declare", ColorClass::Instr),
                 profile.markup(&format!("{}", self.linkage), ColorClass::Ty),
+                conv,
                 profile.markup(&self.ty.ret.to_string(), ColorClass::Ty),
                 profile.markup(&self.name, ColorClass::Name), {
                     let mut fmt = String::new();
@@ -168,19 +306,24 @@ impl Function {
                             );
                     }
 
-                    if self.ty.args.len() != 0 {
+                    if self.ty.any_args {
+                        fmt += "..., ";
+                    }
+
+                    if !fmt.is_empty() {
                         fmt.remove(fmt.len() - 2); // The last comma
                     }
         
                     fmt
-                });
+                }, attrs, metadata);
             return string;
         }
 
         let mut string = String::new();
 
-        string += &format!("{} {} @{}({}) {{\n", 
+        string += &format!("{} {}{} @{}({}){}{}{} {{\n", 
                         profile.markup("define", ColorClass::Instr),
+                        conv,
                         profile.markup(&self.ty.ret.to_string(), ColorClass::Ty), 
                         profile.markup(&self.name, ColorClass::Name), {
             let mut fmt = String::new();
@@ -192,13 +335,17 @@ impl Function {
                         profile.markup(&format!("{}", arg.name), ColorClass::Var)
                     );
             }
+
+            if self.ty.any_args {
+                fmt += "..., ";
+            }
             
-            if self.ty.args.len() != 0 {
+            if !fmt.is_empty() {
                 fmt.remove(fmt.len() - 2); // The last comma
             }
 
             fmt
-        });
+        }, attrs, personality, metadata);
 
         for block in &self.blocks {
             string += &format!(" {}\n", block.dumpColored(profile));
@@ -212,14 +359,93 @@ impl Function {
     /// Verifys if the function and all of its blocks are correct:
     ///  * Checks if the return type is the actual specified return type of the function
     ///  * Checks all ir nodes
+    ///  * Checks that invokes unwind to landing pads
+    ///  * Checks that naked functions don't need a frame
     pub fn verify(&self) -> Result<(), VerifyError> {
+        self.ty.verify_call_conv()?;
+
         for block in &self.blocks {
             block.verify(self)?
         }
 
+        self.verify_unwind()?;
+
+        if self.has_attr(FnAttr::Naked) {
+            self.verify_naked()?;
+        }
+
         Ok(())
     }
     
+    /// Checks that the naked function doesn't need a frame:
+    ///  * it doesn't allocate stack memory (also no values which live on the stack)
+    ///  * it doesn't call (the live values would need to be saved onto the stack)
+    fn verify_naked(&self) -> Result<(), VerifyError> {
+        for block in &self.blocks {
+            for node in &block.nodes {
+                if node.is_alloca() || node.outputs().iter().any(|out| out.ty.aggregate() || out.ty.wide() || out.ty.vector() || out.ty.tuple()) {
+                    Err(VerifyError::NakedFuncWithAlloca)?
+                }
+
+                // tail calls reuse the frame of the caller, so they don't need one
+                let tail = matches!(node.as_any().downcast_ref::<Call>(), Some(call) if call.is_tail());
+
+                if (node.is_call() && !tail) || node.is_callindirect() || node.is_invoke() || node.is_statepoint() || node.is_memcpy() || node.is_memmove() || node.is_memset() {
+                    Err(VerifyError::NakedFuncWithCall)?
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks the calling conventions of the function and of all functions it calls
+    /// with the calling convention of the target (`target`) for the ones which don't specify their own.
+    /// Also checks that the aggregates which are passed or returned by value can be classified.
+    /// 
+    /// Calls are resolved like the backend does it: the convention of the call, then the one
+    /// of the declared function and then the one of the caller
+    pub(crate) fn verify_call_convs(&self, module: &Module, arch: Arch, target: CallConv) -> Result<(), VerifyError> {
+        let verify = |ty: &FunctionType, call_conv: CallConv| -> Result<(), VerifyError> {
+            let call_conv = ty.call_conv.unwrap_or(call_conv);
+
+            ty.verify_call_conv_or(Some(call_conv))?;
+
+            let call = MachineCallingConvention { call_conv: call_conv };
+
+            for ty in ty.args.iter().map(|(_, ty)| ty).chain([&ty.ret]) {
+                call.classify(arch, *ty)?;
+            }
+
+            Ok(())
+        };
+
+        let caller = self.ty.call_conv.unwrap_or(target);
+
+        verify(&self.ty, caller)?;
+
+        for block in &self.blocks {
+            for node in &block.nodes {
+                let ty = if let Some(call) = node.as_any().downcast_ref::<Call>() {
+                    &call.func
+                } else if let Some(invoke) = node.as_any().downcast_ref::<Invoke>() {
+                    &invoke.func
+                } else if let Some(call) = node.as_any().downcast_ref::<CallIndirect>() {
+                    verify(&call.ty, caller)?;
+                    continue;
+                } else {
+                    continue;
+                };
+
+                let declared = module.getFunc(&ty.name).and_then(|func| func.ty.call_conv);
+
+                verify(&ty.ty, declared.unwrap_or(caller))?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Runs the pass manager over all blocks
     pub fn runPassMngr(&mut self, mngr: &PassManager) {
         for block in &mut self.blocks {
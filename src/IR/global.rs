@@ -0,0 +1,76 @@
+use crate::{prelude::Triple, Obj::Linkage};
+
+use super::TypeMetadata;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A mutable global variable
+pub struct Global {
+    pub(crate) name: String,
+    pub(crate) ty: TypeMetadata,
+    /// The initial data (`None` means zero initialized)
+    pub(crate) init: Option<Vec<u8>>,
+    pub(crate) linkage: Linkage,
+}
+
+impl Global {
+    /// creates a new zero initialized global
+    pub fn new(name: String, ty: TypeMetadata) -> Self {
+        Self {
+            name: name,
+            ty: ty,
+            init: None,
+            linkage: Linkage::Internal,
+        }
+    }
+
+    /// sets the initial data of the global
+    pub fn set(&mut self, data: Vec<u8>) {
+        self.init = Some(data);
+    }
+
+    /// gets the initial data of the global (`None` if it is zero initialized)
+    pub fn get(&self) -> Option<Vec<u8>> {
+        self.init.clone()
+    }
+
+    /// Returns the type of the global
+    pub fn getType(&self) -> TypeMetadata {
+        self.ty
+    }
+
+    /// Returns true if the global is zero initialized
+    pub fn isZeroInit(&self) -> bool {
+        match &self.init {
+            Some(data) => data.iter().all(|byte| *byte == 0),
+            None => true,
+        }
+    }
+
+    /// Sets that the global is externally visible
+    pub fn extrn(&mut self) {
+        self.linkage = Linkage::External;
+    }
+
+    /// Sets that the global is imported from another object file
+    pub fn import(&mut self) {
+        self.linkage = Linkage::Extern;
+    }
+
+    /// Sets that the global is only internally visible
+    pub fn private(&mut self) {
+        self.linkage = Linkage::Internal;
+    }
+
+    /// Returns the data of the global padded to the size of its type
+    pub(crate) fn data(&self, triple: &Triple) -> Vec<u8> {
+        let mut data = self.init.clone().unwrap_or_default();
+
+        let size = self.ty.size(triple);
+
+        if data.len() < size {
+            data.resize(size, 0);
+        }
+
+        data
+    }
+}
@@ -0,0 +1,119 @@
+use std::fmt::Display;
+
+/// A value which is stored in a metadata node
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MetadataValue {
+    /// An intenger (e.g: a branch weight)
+    Int(i64),
+    /// A string (e.g: the file of a source span)
+    Str(String),
+    /// A reference to another named metadata node (e.g: `!scope_a`)
+    Ref(String),
+}
+
+impl Display for MetadataValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MetadataValue::Int(int) => write!(f, "{}", int),
+            MetadataValue::Str(string) => write!(f, "{:?}", string),
+            MetadataValue::Ref(name) => write!(f, "!{}", name),
+        }
+    }
+}
+
+/// A named metadata node (e.g: `!branch_weights [90, 10]`)
+///
+/// Except for `!branch_weights` (which decide where the x64 backend places the conditional jump)
+/// the values aren't interpreted by ygen, it's up to the passes (or tools) which read them
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MetadataNode {
+    pub(crate) name: String,
+    pub(crate) values: Vec<MetadataValue>,
+}
+
+impl MetadataNode {
+    /// Creates a new metadata node
+    pub fn new(name: &str, values: Vec<MetadataValue>) -> Self {
+        Self {
+            name: name.to_owned(),
+            values: values,
+        }
+    }
+
+    /// Returns the name of the node
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the values of the node
+    pub fn values(&self) -> &[MetadataValue] {
+        &self.values
+    }
+}
+
+impl Display for MetadataNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "!{}", self.name)?;
+
+        if !self.values.is_empty() {
+            write!(f, " [{}]", self.values.iter().map(|value| value.to_string()).collect::<Vec<_>>().join(", "))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The metadata nodes which are attached to an ir node, a function or a module
+/// (every name is only attached once)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Metadata {
+    nodes: Vec<MetadataNode>,
+}
+
+impl Metadata {
+    /// Creates an empty metadata set
+    pub fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+        }
+    }
+
+    /// Adds the node (it replaces the node with the same name)
+    pub fn add(&mut self, node: MetadataNode) {
+        match self.nodes.binary_search_by(|other| other.name.cmp(&node.name)) {
+            Ok(index) => self.nodes[index] = node,
+            Err(index) => self.nodes.insert(index, node),
+        }
+    }
+
+    /// Removes the node with the name
+    pub fn remove(&mut self, name: &str) {
+        self.nodes.retain(|node| node.name != name);
+    }
+
+    /// Returns the node with the name
+    pub fn get(&self, name: &str) -> Option<&MetadataNode> {
+        self.nodes.iter().find(|node| node.name == name)
+    }
+
+    /// Returns if a node with the name is attached
+    pub fn has(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// Returns if no nodes are attached
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns an iterator over all attached nodes
+    pub fn iter(&self) -> impl Iterator<Item = &MetadataNode> {
+        self.nodes.iter()
+    }
+}
+
+impl Display for Metadata {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.nodes.iter().map(|node| node.to_string()).collect::<Vec<_>>().join(" "))
+    }
+}
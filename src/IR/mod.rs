@@ -4,6 +4,9 @@ mod typ;
 mod block;
 mod var;
 mod constant;
+mod global;
+mod attrs;
+mod metadata;
 mod nodes;
 pub mod parser;
 
@@ -14,12 +17,17 @@ pub mod ir {
 
 use std::error::Error;
 use std::fmt::Display;
+use crate::Target::CallConv;
 
 pub use module::Module;
 pub use func::{Function, FunctionType, FnTy, Func, FuncId};
-pub use constant::Const;
+pub use constant::{Const, ConstAdr};
+pub use global::Global;
+pub use attrs::{FnAttr, FnAttrs};
+pub use metadata::{Metadata, MetadataNode, MetadataValue};
 pub use typ::Type;
 pub use typ::TypeMetadata;
+pub use typ::{AggregateId, AggregateType};
 pub use block::{Block, BlockId};
 pub use var::Var;
 
@@ -34,6 +42,71 @@ pub enum VerifyError {
     InvalidArgumentTypeFound,
     /// To many arguments were supplyed
     ToManyArgumentsWereSupplyed,
+    /// The element index is out of the bounds of the vector
+    IndexOutOfBounds(TypeMetadata, usize),
+    /// The block doesn't end with a terminator (`ret`, `br`, `switch`, `unreachable`, `trap`)
+    MissingTerminator,
+    /// The type can't be accessed atomically (only intengers and pointers can)
+    InvalidAtomicType(TypeMetadata),
+    /// The memory ordering isn't allowed for the atomic operation (e.g: a `release` load)
+    InvalidAtomicOrdering(ir::AtomicOrdering),
+    /// The alignment of a memory access needs to be a power of two
+    InvalidAlignment(usize),
+    /// The cast kind can't convert between the two types (e.g: a `zext` from i64 to i32)
+    InvalidCast(ir::CastKind, TypeMetadata, TypeMetadata),
+    /// The compare mode can't compare values of the type (e.g: a `slt` for f32)
+    InvalidCmpMode(ir::CmpMode, TypeMetadata),
+    /// The bit manipulation can't be done on the type (e.g: a `bswap` of an i8)
+    InvalidBitOp(ir::BitOpKind, TypeMetadata),
+    /// The type can't be rotated (128bit intengers can't)
+    InvalidRotate(TypeMetadata),
+    /// The overflow checked arithmetic can't be done on the type (e.g: an `sadd_overflow` of f32)
+    InvalidOverflowOp(ir::OverflowKind, TypeMetadata),
+    /// The float math function can't be done on the type (e.g: a `sqrt` of an i32)
+    InvalidFloatOp(ir::FloatOpKind, TypeMetadata),
+    /// The float math function got the wrong amount of operands (e.g: an `fma` with two)
+    InvalidFloatOpArgs(ir::FloatOpKind, usize),
+    /// `va_start` was used in a function which isn't variadic
+    VaStartInNonVariadic,
+    /// The type can't be read as a variadic argument (e.g: an aggregate)
+    InvalidVaArgType(TypeMetadata),
+    /// The inline assembly constraint (at the index) can't be used like this (e.g: an `i` for a variable)
+    InvalidAsmConstraint(usize),
+    /// The inline assembly has a different amount of operand constraints than operands
+    AsmOperandsNoMatch(usize, usize),
+    /// The tail call isn't directly followed by a return of its result
+    TailCallNotInTailPosition,
+    /// The tail called function doesn't return the same type using the same calling convention
+    /// as the caller or not all arguments are passed in registers
+    IncompatibleTailCall,
+    /// The landing pad has neither a `catch` nor a `cleanup` clause
+    LandingPadWithoutClause,
+    /// The landing pad isn't the first node of its block
+    LandingPadNotFirst,
+    /// The unwind block of an invoke doesn't start with a landing pad
+    UnwindBlockWithoutLandingPad,
+    /// The function uses an invoke or a landing pad but has no personality function
+    MissingPersonality,
+    /// The gc root of a statepoint isn't a pointer
+    GcRootNotPtr(TypeMetadata),
+    /// The value of a tuple doesn't fit into a register
+    InvalidTupleElem(TypeMetadata),
+    /// The condition of a `br cond` or `select` isn't an `i1`
+    ConditionNotI1(TypeMetadata),
+    /// A naked function allocates stack memory (it doesn't get a frame)
+    NakedFuncWithAlloca,
+    /// A naked function calls (the live values would need to be saved in a frame)
+    NakedFuncWithCall,
+    /// The returned tuple has more values than return registers
+    TupleTooLargeToReturn(TypeMetadata),
+    /// The calling convention can't return tuples
+    TupleRetUnsupported(CallConv),
+    /// The calling convention can't pass 128bit intengers as arguments
+    WideArgUnsupported(CallConv),
+    /// The calling convention can't pass or return aggregates by value
+    AggregateArgUnsupported(CallConv),
+    /// Vectors can't be passed as arguments or returned yet
+    VectorArgUnsupported(TypeMetadata),
 }
 
 impl Display for VerifyError {
@@ -61,6 +134,166 @@ impl Display for VerifyError {
                 format!(
                     "too many arguments were supplyed"
                 )
+            },
+            VerifyError::IndexOutOfBounds(ty, index) => {
+                format!(
+                    "the index {} is out of the bounds of the vector {}", index, ty
+                )
+            },
+            VerifyError::MissingTerminator => {
+                format!(
+                    "the block needs to end with a terminator (ret, br, switch, unreachable or trap)"
+                )
+            },
+            VerifyError::InvalidAtomicType(ty) => {
+                format!(
+                    "the type {} can't be accessed atomically (only intengers and pointers can)", ty
+                )
+            },
+            VerifyError::InvalidAtomicOrdering(order) => {
+                format!(
+                    "the memory ordering {} isn't allowed for this atomic operation", order
+                )
+            },
+            VerifyError::InvalidAlignment(align) => {
+                format!(
+                    "the alignment {} needs to be a power of two", align
+                )
+            },
+            VerifyError::InvalidCast(kind, from, to) => {
+                format!(
+                    "{} can't cast from {} to {}", kind, from, to
+                )
+            },
+            VerifyError::InvalidCmpMode(mode, ty) => {
+                format!(
+                    "the compare mode {} can't compare values of the type {}", mode, ty
+                )
+            },
+            VerifyError::InvalidBitOp(kind, ty) => {
+                format!(
+                    "{} can't be done on values of the type {}", kind, ty
+                )
+            },
+            VerifyError::InvalidRotate(ty) => {
+                format!(
+                    "values of the type {} can't be rotated", ty
+                )
+            },
+            VerifyError::InvalidOverflowOp(kind, ty) => {
+                format!(
+                    "{} can't be done on values of the type {}", kind, ty
+                )
+            },
+            VerifyError::InvalidFloatOp(kind, ty) => {
+                format!(
+                    "{} can't be done on values of the type {}", kind, ty
+                )
+            },
+            VerifyError::InvalidFloatOpArgs(kind, args) => {
+                format!(
+                    "{} takes {} operands but {} were supplyed", kind, kind.arity(), args
+                )
+            },
+            VerifyError::VaStartInNonVariadic => {
+                format!(
+                    "va_start can only be used in variadic functions"
+                )
+            },
+            VerifyError::InvalidVaArgType(ty) => {
+                format!(
+                    "the type {} can't be read as a variadic argument", ty
+                )
+            },
+            VerifyError::InvalidAsmConstraint(index) => {
+                format!(
+                    "the {}. inline assembly constraint is invalid for its operand", index + 1
+                )
+            },
+            VerifyError::AsmOperandsNoMatch(constraints, operands) => {
+                format!(
+                    "the inline assembly has {} operand constraints but {} operands", constraints, operands
+                )
+            },
+            VerifyError::TailCallNotInTailPosition => {
+                format!(
+                    "a tail call needs to be directly followed by a return of its result"
+                )
+            },
+            VerifyError::IncompatibleTailCall => {
+                format!(
+                    "the tail called function needs to return the same type with the same calling convention and take all arguments in registers"
+                )
+            },
+            VerifyError::LandingPadWithoutClause => {
+                format!(
+                    "a landingpad needs at least one catch or a cleanup clause"
+                )
+            },
+            VerifyError::LandingPadNotFirst => {
+                format!(
+                    "a landingpad needs to be the first node of its block"
+                )
+            },
+            VerifyError::UnwindBlockWithoutLandingPad => {
+                format!(
+                    "the unwind block of an invoke needs to start with a landingpad"
+                )
+            },
+            VerifyError::MissingPersonality => {
+                format!(
+                    "functions which use invoke or landingpad need a personality function"
+                )
+            },
+            VerifyError::GcRootNotPtr(ty) => {
+                format!(
+                    "the gc roots of a statepoint need to be pointers but found {}", ty
+                )
+            },
+            VerifyError::InvalidTupleElem(ty) => {
+                format!(
+                    "the values of a tuple need to fit into a register but found {}", ty
+                )
+            },
+            VerifyError::ConditionNotI1(ty) => {
+                format!(
+                    "the condition needs to be an i1 but found {}", ty
+                )
+            },
+            VerifyError::NakedFuncWithAlloca => {
+                format!(
+                    "naked functions can't allocate stack memory (they don't get a frame)"
+                )
+            },
+            VerifyError::NakedFuncWithCall => {
+                format!(
+                    "naked functions can't call other functions (they don't get a frame to save their values in)"
+                )
+            },
+            VerifyError::TupleTooLargeToReturn(ty) => {
+                format!(
+                    "the tuple {} can't be returned (only two intengers and two floats can be returned)", ty
+                )
+            },
+            VerifyError::TupleRetUnsupported(call_conv) => {
+                format!(
+                    "the calling convention {} can't return tuples", call_conv
+                )
+            },
+            VerifyError::WideArgUnsupported(call_conv) => {
+                format!(
+                    "the calling convention {} can't pass 128bit intengers as arguments", call_conv
+                )
+            },
+            VerifyError::AggregateArgUnsupported(call_conv) => {
+                format!(
+                    "the calling convention {} can't pass or return aggregates by value", call_conv
+                )
+            },
+            VerifyError::VectorArgUnsupported(ty) => {
+                format!(
+                    "vectors ({}) can't be passed as arguments or returned yet", ty
+                )
             }
         })
     }
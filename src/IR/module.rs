@@ -1,8 +1,9 @@
 use gimli::DwLang;
+use object::RelocationEncoding;
 
-use crate::{debug::{DebugLocation, DebugRegistry}, prelude::Triple, CodeGen::MachineInstr, Obj::{Decl, Link, Linkage, ObjectBuilder}, Optimizations::PassManager, Support::{ColorClass, ColorProfile}, Target::{Arch, TargetRegistry}};
+use crate::{debug::{DebugLocation, DebugRegistry}, prelude::Triple, CodeGen::MachineInstr, Obj::{CallSite, Decl, Link, Linkage, ObjectBuilder, StackMapRecord, UnwindInfo}, Optimizations::PassManager, Support::{ColorClass, ColorProfile}, Target::{Arch, TargetRegistry}};
 
-use super::{func::FunctionType, Const, Function, VerifyError};
+use super::{func::FunctionType, ir::Invoke, Const, ConstAdr, Function, Global, Metadata, MetadataNode, TypeMetadata, VerifyError};
 use std::{collections::HashMap, error::Error, fmt::Debug, fs::OpenOptions, io::Write, path::Path};
 
 /// ## The Module
@@ -11,12 +12,15 @@ use std::{collections::HashMap, error::Error, fmt::Debug, fs::OpenOptions, io::W
 pub struct Module {
     pub(crate) funcs: HashMap<String, Function>,
     pub(crate) consts: HashMap<String, Const>,
+    pub(crate) globals: HashMap<String, Global>,
     pub(crate) dbg_registry: Option<DebugRegistry>,
 
     /// The number of current constants
     pub(crate) const_index: usize,
 
     pub(crate) debug_passes: bool,
+
+    pub(crate) metadata: Metadata,
 }
 
 impl Module {
@@ -25,9 +29,11 @@ impl Module {
         Self {
             funcs: HashMap::new(),
             consts: HashMap::new(),
+            globals: HashMap::new(),
             dbg_registry: None,
             const_index: 0,
             debug_passes: false,
+            metadata: Metadata::new(),
         }
     }
 
@@ -41,6 +47,16 @@ impl Module {
         self.debug_passes = true;
     }
 
+    /// Adds the named metadata node to the module (e.g: `!producer = ["my compiler"]`)
+    pub fn add_metadata(&mut self, node: MetadataNode) {
+        self.metadata.add(node);
+    }
+
+    /// Returns the named metadata of the module
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
     /// Adds a new function to the module
     pub fn add(&mut self, name: &str, ty: &FunctionType) -> &mut Function {
         self.funcs
@@ -65,6 +81,24 @@ impl Module {
         self.consts.insert(constant.name.to_string(), constant);
     }
 
+    /// Adds a new mutable global variable to the module.
+    /// 
+    /// If no initializer is given, the global is zero initialized
+    pub fn addGlobal(&mut self, name: &str, ty: TypeMetadata, init: Option<Vec<u8>>, linkage: Linkage) -> &mut Global {
+        let mut global = Global::new(name.to_string(), ty);
+
+        global.init = init;
+        global.linkage = linkage;
+
+        self.globals.insert(name.to_string(), global);
+        self.globals.get_mut(name).unwrap()
+    }
+
+    /// Adds an already defined global to the module
+    pub fn add_raw_global(&mut self, global: Global) {
+        self.globals.insert(global.name.to_string(), global);
+    }
+
     #[allow(dead_code)]
     /// Returns a read only reference to the given function name
     /// ### Used for passes
@@ -84,19 +118,12 @@ impl Module {
     pub fn dump(&self) -> String {
         let mut string = String::new();
 
-        for (_, consta) in &self.consts {
-            let mut bytes = String::from("[ ");
-
-            for byte in &consta.data { 
-                bytes.push_str(&format!("{}, ", *byte));
-            }
-
-            if consta.data.len() > 0 {
-                bytes.remove(bytes.chars().count() - 1);
-                bytes.remove(bytes.chars().count() - 2);
-            }
+        for node in self.metadata.iter() {
+            string += &format!("{}\n", dump_metadata(node));
+        }
 
-            bytes.push(']');
+        for (_, consta) in &self.consts {
+            let bytes = dump_bytes(&consta.data, &consta.adrs);
 
             string += &format!("{} const {} = {}\n", match consta.linkage {
                 Linkage::Extern => "import",
@@ -105,6 +132,17 @@ impl Module {
             }, consta.name,  bytes);
         }
 
+        for (_, global) in &self.globals {
+            string += &format!("global {} {} {}{}\n", match global.linkage {
+                Linkage::Extern => "import",
+                Linkage::External => "extern",
+                Linkage::Internal => "intern",
+            }, global.ty, global.name, match &global.init {
+                Some(data) => format!(" = {}", dump_bytes(data, &[])),
+                None => String::new(),
+            });
+        }
+
         for (_, func) in &self.funcs {
             string += &format!("{}\n", func.dump());
         }
@@ -117,19 +155,12 @@ impl Module {
     pub fn dumpColored(&self, profile: ColorProfile) -> String {
         let mut string = String::new();
 
-        for (_, consta) in &self.consts {
-            let mut bytes = String::from("[ ");
-
-            for byte in &consta.data { 
-                bytes.push_str(&format!("{}, ", *byte));
-            }
-
-            if consta.data.len() > 0 {
-                bytes.remove(bytes.chars().count() - 1);
-                bytes.remove(bytes.chars().count() - 2);
-            }
+        for node in self.metadata.iter() {
+            string += &format!("{}\n", profile.markup(&dump_metadata(node), ColorClass::Name));
+        }
 
-            bytes.push(']');
+        for (_, consta) in &self.consts {
+            let bytes = dump_bytes(&consta.data, &consta.adrs);
 
             string += &format!("{} {} {} = {}\n", 
                 profile.markup(&match consta.linkage {
@@ -143,6 +174,23 @@ impl Module {
             );
         }
 
+        for (_, global) in &self.globals {
+            string += &format!("{} {} {} {}{}\n", 
+                profile.markup("global", ColorClass::Instr), 
+                profile.markup(&match global.linkage {
+                    Linkage::Extern => "import",
+                    Linkage::External => "extern",
+                    Linkage::Internal => "intern",
+                }, ColorClass::Instr), 
+                profile.markup(&global.ty.to_string(), ColorClass::Ty), 
+                profile.markup(&global.name, ColorClass::Name), 
+                match &global.init {
+                    Some(data) => format!(" = {}", profile.markup(&dump_bytes(data, &[]), ColorClass::Value)),
+                    None => String::new(),
+                }
+            );
+        }
+
         for (_, func) in &self.funcs {
             string += &format!("{}\n", func.dumpColored(profile));
         }
@@ -168,13 +216,7 @@ impl Module {
                 eprintln!("Running pass: {}", pass.name());
             }
 
-            for (_, func) in &mut self.funcs {
-                pass.run_func(func);
-    
-                for block in &mut func.blocks {
-                    pass.run(block);
-                }
-            }
+            pass.run_mod(self);
         }
     }
 
@@ -187,7 +229,9 @@ impl Module {
 
         let mut obj = ObjectBuilder::new(triple);
 
-        for (name, func) in self.funcs.clone() {
+        let funcs = self.funcs.clone();
+
+        for (name, func) in funcs.clone() {
             obj.decl( (&name, Decl::Function, func.linkage));
 
             let mut blocks: Vec<(String, (Vec<u8>, Vec<Link>))> = Vec::new();
@@ -196,20 +240,21 @@ impl Module {
 
             let mut comp = vec![];
 
+            // how the prolog changes the frame
+            let mut cfi = vec![];
+
+            let mut requires_prolog = false;
+
+            // the size of the frame (for the stack maps)
+            let mut frame_size = 0;
+
+            let mut debug_infos = vec![];
+
             for block in &func.blocks {
                 let (compiled, links) = registry.buildMachineCodeForTarget(triple.arch, &block, &func, self)?;
                 
                 if registry.requires_prolog(&func) && index == 0 {
-                    let mut helper = registry.getBackendForFuncOrFork(triple.arch, &func).helper.expect("expected valid helper");
-                    
-                    let mut prolog = vec![];
-
-                    helper.compile_prolog(&mut prolog);
-
-                    let mc_instrs = helper.lower.unwrap()(triple.getCallConv()?, prolog);
-                    for instr in mc_instrs {
-                        comp.extend_from_slice(&instr.encode()?.0);
-                    }
+                    requires_prolog = true;
 
                     if debug {
                         if let Some(reg) = self.dbg_registry.as_mut() {
@@ -227,17 +272,7 @@ impl Module {
                 }
 
                 if debug {
-                    let mut debug_info = registry.buildDebugInfo(triple.arch, &block, &func, self)?;
-
-                    for dbg in &mut debug_info {
-                        dbg.adr += comp.len() as u64 + 1;
-
-                        if let Some(reg) = self.dbg_registry.as_mut() {
-                            reg.add_location(&func.name, *dbg);
-                        } else {
-                            panic!("you need to initialize debugging information for the registry in order to use debugging information")
-                        }
-                    }
+                    debug_infos.extend(registry.buildDebugInfo(triple.arch, &block, &func, self)?);
                 }
 
                 blocks.push((block.name.to_owned(), (compiled, links)));
@@ -245,10 +280,45 @@ impl Module {
                 index += 1;
             }
 
+            // the prolog is compiled after all blocks, so the frame also
+            // contains the stack slots which are allocated in later blocks
+            if requires_prolog {
+                let mut helper = registry.getBackendForFuncOrFork(triple.arch, &func).helper.expect("expected valid helper");
+                
+                let mut prolog = vec![];
+
+                helper.compile_prolog(&mut prolog);
+
+                let call_conv = if let Some(call_conv) = func.ty.call_conv { call_conv } else { triple.getCallConv()? };
+
+                let mc_instrs = helper.lower.unwrap()(call_conv, prolog);
+
+                if let Some(frame) = helper.frame {
+                    cfi = (frame.cfi)(&mc_instrs);
+                    frame_size = (frame.size)(&mc_instrs);
+                }
+
+                for instr in mc_instrs {
+                    comp.extend_from_slice(&instr.encode()?.0);
+                }
+            }
+
+            for mut dbg in debug_infos {
+                dbg.adr += comp.len() as u64 + 1;
+
+                if let Some(reg) = self.dbg_registry.as_mut() {
+                    reg.add_location(&func.name, dbg);
+                } else {
+                    panic!("you need to initialize debugging information for the registry in order to use debugging information")
+                }
+            }
+
             let mut block_links: Vec<((i64, i64, i64, i64), String, String, i64)> = vec![];
 
             let mut block_adrs = HashMap::new();
 
+            let mut labels = HashMap::new();
+
             for (name, (data, links)) in blocks {
                 let prev_len = comp.len();
 
@@ -257,7 +327,9 @@ impl Module {
                 comp.extend_from_slice(&data);
 
                 for link in links {
-                    if link.special { // block to block link
+                    if link.label {
+                        labels.insert(link.to, (link.at + prev_len) as u64);
+                    } else if link.special { // block to block link
                         let adr = |idx| {
                             link.at as i64 + (prev_len as i64) + link.addend + idx
                         };
@@ -272,13 +344,18 @@ impl Module {
                             addend: link.addend,
                             special: false,
                             kind: link.kind,
+                            label: false,
                         });
                     }
                 }
             }
 
             for (idx, target, source, off) in block_links {
-                let mut target_adr = *block_adrs.get(&target).expect("hmm i made a programming error") as i64;
+                // local labels (like the retry loop of an atomicrmw) are jumped to like blocks
+                let mut target_adr = match block_adrs.get(&target) {
+                    Some(adr) => *adr as i64,
+                    None => *labels.get(&target).expect("hmm i made a programming error") as i64,
+                };
                 let source_adr = *block_adrs.get(&source).expect("hmm i made a programming error") as i64 + 5;
 
                 target_adr -= source_adr + off;
@@ -295,14 +372,83 @@ impl Module {
                 set_byte(idx.3, bytes[4]);
             }
 
+            let mut call_sites = vec![];
+
+            for block in &func.blocks {
+                for node in &block.nodes {
+                    let Some(invoke) = node.as_any().downcast_ref::<Invoke>() else { continue };
+
+                    let label = |what| *labels.get(&invoke.label(what)).expect("expected the labels of the invoke");
+
+                    let pad = func.landing_pad(&invoke.unwind.name).expect("the unwind block of an invoke needs to start with a landingpad");
+
+                    call_sites.push(CallSite {
+                        start: label("begin"),
+                        len: label("end") - label("begin"),
+                        landing_pad: label("pad"),
+                        cleanup: pad.cleanup,
+                        catches: pad.catches.to_owned(),
+                    });
+                }
+            }
+
+            let stack_maps = registry.getBackendForFuncOrFork(triple.arch, &func).helper.map(|helper| helper.stack_maps).unwrap_or_default();
+
+            let mut records = stack_maps.into_iter().map(|(label, (id, locations))| StackMapRecord {
+                id: id,
+                offset: *labels.get(&label).expect("expected the label of the statepoint") as u32,
+                locations: locations,
+            }).collect::<Vec<_>>();
+
+            if !records.is_empty() {
+                records.sort_by_key(|record| record.offset);
+
+                obj.stack_map(&name, frame_size, records);
+            }
+
+            obj.unwind(&name, UnwindInfo {
+                cfi: cfi,
+                personality: func.personality.to_owned(),
+                call_sites: call_sites,
+            });
+
             obj.define(&name, comp);
         }
 
+        // runtime functions (like __multi3) get declared while compiling
+        for (name, func) in &self.funcs {
+            if !funcs.contains_key(name) {
+                obj.decl( (name, Decl::Function, func.linkage));
+            }
+        }
+
         // NOT CHANGE THE ORDER CUZ FOR SOME ARCHS (LIKE X86) FPs ARE MADE USING CONSTS
         // WHICH WOULD LEED TO A PANIC
         for (_, consta) in &self.consts {
             obj.decl((consta.name.as_str(), Decl::Constant, consta.linkage));
             obj.define(&consta.name, consta.data.clone());
+
+            for adr in &consta.adrs {
+                obj.link(Link {
+                    from: consta.name.to_owned(),
+                    to: adr.symbol.to_owned(),
+                    at: adr.at,
+                    addend: adr.addend,
+                    special: false,
+                    kind: RelocationEncoding::Generic,
+                    label: false,
+                });
+            }
+        }
+
+        for (_, global) in &self.globals {
+            let decl = if global.isZeroInit() { Decl::ZeroData } else { Decl::Data };
+
+            obj.decl((global.name.as_str(), decl, global.linkage));
+
+            if global.linkage != Linkage::Extern {
+                obj.define(&global.name, global.data(&triple));
+            }
         }
 
         Ok((obj, self.dbg_registry.to_owned()))
@@ -370,6 +516,35 @@ impl Module {
     }
 }
 
+/// module level metadata is written as: `!name = [values]` (so it isn't attached to the next function)
+fn dump_metadata(node: &MetadataNode) -> String {
+    format!("!{} = [{}]", node.name, node.values.iter().map(|value| value.to_string()).collect::<Vec<_>>().join(", "))
+}
+
+/// Dumps the data as `[1, 2, symbol + 4, 3]`
+fn dump_bytes(data: &[u8], adrs: &[ConstAdr]) -> String {
+    let mut entries = vec![];
+
+    let mut index = 0;
+
+    while index < data.len() {
+        if let Some(adr) = adrs.iter().find(|adr| adr.at == index) {
+            entries.push(match adr.addend {
+                0 => adr.symbol.to_owned(),
+                addend => format!("{} + {}", adr.symbol, addend),
+            });
+
+            index += 8;
+            continue;
+        }
+
+        entries.push(data[index].to_string());
+        index += 1;
+    }
+
+    format!("[{}]", entries.join(", "))
+}
+
 /// Creates a new module
 pub fn Module() -> Module {
     Module::new()
@@ -0,0 +1,276 @@
+use std::fmt::Display;
+
+use super::*;
+
+/// A constraint of an inline assembly operand (or a clobbered register)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AsmConstraint {
+    /// `=r`: the output is written into a register selected by the register allocator
+    OutReg,
+    /// `={reg}`: the output is written into the given register
+    OutFixed(String),
+    /// `r`: the input is read from a register selected by the register allocator
+    InReg,
+    /// `i`: the input is a constant which is inserted as an immediate
+    InImm,
+    /// `{reg}`: the input is read from the given register
+    InFixed(String),
+    /// `~{reg}`: the assembly overwrites the given register
+    Clobber(String),
+}
+
+impl AsmConstraint {
+    /// Parses a single constraint (e.g: `={eax}`)
+    pub fn parse(string: &str) -> Option<Self> {
+        fn fixed(string: &str) -> Option<String> {
+            let reg = string.strip_prefix('{')?.strip_suffix('}')?;
+
+            if reg.is_empty() { None } else { Some(reg.to_ascii_lowercase()) }
+        }
+
+        match string.trim() {
+            "=r" => Some(AsmConstraint::OutReg),
+            "r" => Some(AsmConstraint::InReg),
+            "i" => Some(AsmConstraint::InImm),
+            string if string.starts_with("=") => Some(AsmConstraint::OutFixed(fixed(&string[1..])?)),
+            string if string.starts_with("~") => Some(AsmConstraint::Clobber(fixed(&string[1..])?)),
+            string => Some(AsmConstraint::InFixed(fixed(string)?)),
+        }
+    }
+
+    /// Returns if the constraint describes the output
+    pub fn is_out(&self) -> bool {
+        matches!(self, AsmConstraint::OutReg | AsmConstraint::OutFixed(_))
+    }
+
+    /// Returns if the constraint describes an input
+    pub fn is_in(&self) -> bool {
+        matches!(self, AsmConstraint::InReg | AsmConstraint::InImm | AsmConstraint::InFixed(_))
+    }
+}
+
+impl Display for AsmConstraint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AsmConstraint::OutReg => write!(f, "=r"),
+            AsmConstraint::OutFixed(reg) => write!(f, "={{{}}}", reg),
+            AsmConstraint::InReg => write!(f, "r"),
+            AsmConstraint::InImm => write!(f, "i"),
+            AsmConstraint::InFixed(reg) => write!(f, "{{{}}}", reg),
+            AsmConstraint::Clobber(reg) => write!(f, "~{{{}}}", reg),
+        }
+    }
+}
+
+/// Inline assembly which is spliced into the machine code of the function
+///
+/// The operands are refered to as `$0`, `$1`, ... in the assembly (the output comes first).
+/// Multiple instructions are seperated by newlines or `;`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InlineAsm {
+    pub(crate) out: Option<Var>,
+    pub(crate) asm: String,
+    pub(crate) constraints: Vec<AsmConstraint>,
+    pub(crate) args: Vec<IROperand>,
+}
+
+impl InlineAsm {
+    /// Returns the constraints of the inputs (in the order of the arguments)
+    pub(crate) fn in_constraints(&self) -> Vec<AsmConstraint> {
+        self.constraints.iter().filter(|constraint| constraint.is_in()).cloned().collect()
+    }
+
+    /// Returns the constraint of the output
+    pub(crate) fn out_constraint(&self) -> Option<AsmConstraint> {
+        self.constraints.iter().find(|constraint| constraint.is_out()).cloned()
+    }
+
+    fn constraint_string(&self) -> String {
+        self.constraints.iter().map(|constraint| constraint.to_string()).collect::<Vec<_>>().join(",")
+    }
+}
+
+/// Parses the comma seperated constraints of inline assembly (e.g: `=r,{eax},~{rdx}`)
+pub fn parse_asm_constraints(string: &str) -> Option<Vec<AsmConstraint>> {
+    if string.trim().is_empty() {
+        return Some(Vec::new());
+    }
+
+    let mut constraints = Vec::new();
+
+    for constraint in string.split(',') {
+        constraints.push(AsmConstraint::parse(constraint)?);
+    }
+
+    Some(constraints)
+}
+
+impl Ir for InlineAsm {
+    fn dump(&self) -> String {
+        let mut fmt = String::new();
+
+        for arg in &self.args {
+            fmt.push_str(&format!(" {} {}", arg.get_ty(), arg));
+        }
+
+        if let Some(out) = &self.out {
+            format!("{} = asm {} {:?} {:?}{}", out.name, out.ty, self.asm, self.constraint_string(), fmt)
+        } else {
+            format!("asm {:?} {:?}{}", self.asm, self.constraint_string(), fmt)
+        }
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        let mut fmt = String::new();
+
+        for arg in &self.args {
+            fmt.push_str(&format!(" {} {}",
+                profile.markup(&arg.get_ty().to_string(), ColorClass::Ty),
+                profile.markup(&arg.to_string(), ColorClass::Var)
+            ));
+        }
+
+        let asm = format!("{} {}",
+            profile.markup(&format!("{:?}", self.asm), ColorClass::Value),
+            profile.markup(&format!("{:?}", self.constraint_string()), ColorClass::Value),
+        );
+
+        if let Some(out) = &self.out {
+            format!("{} = {} {} {}{}",
+                profile.markup(&out.name, ColorClass::Var),
+                profile.markup("asm", ColorClass::Instr),
+                profile.markup(&out.ty.to_string(), ColorClass::Ty),
+                asm, fmt
+            )
+        } else {
+            format!("{} {}{}", profile.markup("asm", ColorClass::Instr), asm, fmt)
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        let outs = self.constraints.iter().filter(|constraint| constraint.is_out()).count();
+        let ins = self.in_constraints();
+
+        if outs != self.out.is_some() as usize || ins.len() != self.args.len() {
+            Err(VerifyError::AsmOperandsNoMatch(outs + ins.len(), self.out.is_some() as usize + self.args.len()))?
+        }
+
+        // the output needs to come first (it is `$0`)
+        if let Some(pos) = self.constraints.iter().position(|constraint| constraint.is_out()) {
+            if self.constraints[..pos].iter().any(|constraint| constraint.is_in()) {
+                Err(VerifyError::InvalidAsmConstraint(pos))?
+            }
+        }
+
+        let mut args = self.args.iter();
+
+        for (index, constraint) in self.constraints.iter().enumerate() {
+            let ty = if constraint.is_out() {
+                self.out.as_ref().unwrap().ty
+            } else if constraint.is_in() {
+                let arg = args.next().unwrap();
+
+                if *constraint == AsmConstraint::InImm && !arg.is_type() {
+                    Err(VerifyError::InvalidAsmConstraint(index))?
+                }
+
+                arg.get_ty()
+            } else { continue };
+
+            // the operands need to fit into a single register
+            if ty.aggregate() || ty.wide() || ty.vector() || ty == TypeMetadata::Void {
+                Err(VerifyError::InvalidAsmConstraint(index))?
+            }
+        }
+
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::new( self.clone() )
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr, module: &mut crate::prelude::Module) {
+        registry.compile_asm(self, module)
+    }
+
+    fn compile_dir(&self, compiler: &mut crate::CodeGen::IrCodeGenHelper, block: &crate::prelude::Block, module: &mut crate::prelude::Module) {
+        compiler.compile_asm(self, block, module)
+    }
+
+    fn inputs(&self) -> Vec<Var> {
+        let mut inputs = Vec::new();
+        for arg in &self.args {
+            if let IROperand::Var(arg) = arg { inputs.push(arg.to_owned()); }
+        }
+        inputs
+    }
+
+    fn inputs_mut(&mut self) -> Vec<&mut Var> {
+        let mut inputs = Vec::new();
+        for arg in &mut self.args {
+            if let IROperand::Var(arg) = arg { inputs.push(arg); }
+        }
+        inputs
+    }
+
+    fn output(&self) -> Option<Var> {
+        self.out.to_owned()
+    }
+}
+
+impl EvalOptVisitor for InlineAsm {
+    fn maybe_inline(&self, const_values: &HashMap<String, Type>) -> Option<Box<dyn Ir>> {
+        let mut inlined = self.clone();
+        let mut changed = false;
+
+        for arg in &mut inlined.args {
+            if let IROperand::Var(var) = arg {
+                if let Some(value) = const_values.get(&var.name) {
+                    *arg = IROperand::Type(*value);
+                    changed = true;
+                }
+            }
+        }
+
+        if changed { Some(Box::new(inlined)) } else { None }
+    }
+
+    fn eval(&self) -> Option<Box<dyn Ir>> {
+        None
+    }
+}
+
+impl IsNode for InlineAsm {
+    fn is_asm(&self) -> bool {
+        true
+    }
+}
+
+impl Function {
+    /// Builds inline assembly with the comma seperated `constraints`.
+    /// Returns the output if the type isn't void
+    /// ```no-run
+    /// %1 = asm i32 "mov $0, $1; add $0, 1" "=r,r" i32 %0
+    /// ```
+    pub fn BuildAsm(&mut self, ty: TypeMetadata, asm: &str, constraints: &str, args: Vec<IROperand>) -> Option<Var> {
+        let block = self.blocks.back_mut().expect("the IRBuilder needs to have an current block\nConsider creating one");
+
+        let constraints = parse_asm_constraints(constraints).expect("invalid inline assembly constraints");
+
+        let out = if ty != TypeMetadata::Void { Some(Var::new(block, ty)) } else { None };
+
+        block.push_ir(Box::new( InlineAsm {
+            out: out.to_owned(),
+            asm: asm.to_string(),
+            constraints: constraints,
+            args: args
+        } ));
+
+        out
+    }
+}
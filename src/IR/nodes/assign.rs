@@ -206,6 +206,136 @@ impl EvalOptVisitor for Assign<Var, Const> {
     }
 }
 
+impl Ir for Assign<Var, FuncId> {
+    fn dump(&self) -> String {
+        format!("{} = ptr {}", self.inner1.name, self.inner2.name)
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        format!("{} = {} {}", 
+            profile.markup(&self.inner1.name, ColorClass::Var), 
+            profile.markup("ptr", ColorClass::Ty), 
+            profile.markup(&self.inner2.name, ColorClass::Name),
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        if self.inner1.ty != TypeMetadata::ptr {
+            Err(VerifyError::Op0Op1TyNoMatch(self.inner1.ty, TypeMetadata::ptr))?
+        }
+
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::new(self.clone())
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr, module: &mut crate::prelude::Module) {
+        registry.compile_assign_var_func(&self, module)
+    }
+
+    fn uses(&self, var: &Var) -> bool {
+        if *var == self.inner1 { true }
+        else { false }
+    }
+    
+    fn compile_dir(&self, compiler: &mut crate::CodeGen::IrCodeGenHelper, block: &crate::prelude::Block, module: &mut crate::prelude::Module) {
+        compiler.compile_assign_var_func(&self, &block, module)
+    }
+    
+    fn inputs(&self) -> Vec<Var> {
+        vec![]
+    }
+    
+    fn inputs_mut(&mut self) -> Vec<&mut Var> {
+        vec![]
+    }
+    
+    fn output(&self) -> Option<Var> {
+        Some(self.inner1.to_owned())
+    }
+}
+
+impl EvalOptVisitor for Assign<Var, FuncId> {
+    fn maybe_inline(&self, _: &HashMap<String, Type>) -> Option<Box<dyn Ir>> {
+        None
+    }
+    
+    fn eval(&self) -> Option<Box<dyn Ir>> {
+        None
+    }
+}
+
+impl Ir for Assign<Var, Global> {
+    fn dump(&self) -> String {
+        format!("{} = ptr {}", self.inner1.name, self.inner2.name)
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        format!("{} = {} {}", 
+            profile.markup(&self.inner1.name, ColorClass::Var), 
+            profile.markup("ptr", ColorClass::Ty), 
+            profile.markup(&self.inner2.name.to_string(), ColorClass::Value),
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        if self.inner1.ty != TypeMetadata::ptr {
+            Err(VerifyError::Op0Op1TyNoMatch(self.inner1.ty, TypeMetadata::ptr))?
+        }
+
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::new(self.clone())
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr, module: &mut crate::prelude::Module) {
+        registry.compile_assign_var_global(&self, module)
+    }
+
+    fn uses(&self, var: &Var) -> bool {
+        if *var == self.inner1 { true }
+        else { false }
+    }
+    
+    fn compile_dir(&self, compiler: &mut crate::CodeGen::IrCodeGenHelper, block: &crate::prelude::Block, module: &mut crate::prelude::Module) {
+        compiler.compile_assign_var_global(&self, &block, module)
+    }
+    
+    fn inputs(&self) -> Vec<Var> {
+        vec![]
+    }
+    
+    fn inputs_mut(&mut self) -> Vec<&mut Var> {
+        vec![]
+    }
+    
+    fn output(&self) -> Option<Var> {
+        Some(self.inner1.to_owned())
+    }
+}
+
+impl EvalOptVisitor for Assign<Var, Global> {
+    fn maybe_inline(&self, _: &HashMap<String, Type>) -> Option<Box<dyn Ir>> {
+        None
+    }
+    
+    fn eval(&self) -> Option<Box<dyn Ir>> {
+        None
+    }
+}
+
 impl<U> Assign<Var, U> where 
     U: AsAny + Debug + Clone + PartialEq + Eq + 'static
 {
@@ -238,6 +368,26 @@ impl<U> Assign<Var, U> where
     pub fn getOpConstAdr(&self) -> Const {
         self.inner2.as_any().downcast_ref::<Const>().unwrap().clone()
     }
+
+    /// Returns if the operand is the adress of a function
+    pub fn isOpFuncAdr(&self) -> bool {
+        self.inner2.type_id() == TypeId::of::<FuncId>()
+    }
+
+    /// Returns the operand as a function adr
+    pub fn getOpFuncAdr(&self) -> FuncId {
+        self.inner2.as_any().downcast_ref::<FuncId>().unwrap().clone()
+    }
+
+    /// Returns if the operand is the adress of a global variable
+    pub fn isOpGlobalAdr(&self) -> bool {
+        self.inner2.type_id() == TypeId::of::<Global>()
+    }
+
+    /// Returns the operand as a global adr
+    pub fn getOpGlobalAdr(&self) -> Global {
+        self.inner2.as_any().downcast_ref::<Global>().unwrap().clone()
+    }
 }
 
 /// Trait used for overloading the BuildAssign function
@@ -280,3 +430,27 @@ impl BuildAssign<&Const> for Function {
         out
     }
 }
+
+impl BuildAssign<&FuncId> for Function {
+    fn BuildAssign(&mut self, value: &FuncId) -> Var {
+        let block = self.blocks.back_mut().expect("the IRBuilder needs to have an current block\nConsider creating one");
+        
+        let out = Var::new(block, TypeMetadata::ptr);
+
+        block.push_ir(Assign::new(out.clone(), value.clone()));
+
+        out
+    }
+}
+
+impl BuildAssign<&Global> for Function {
+    fn BuildAssign(&mut self, value: &Global) -> Var {
+        let block = self.blocks.back_mut().expect("the IRBuilder needs to have an current block\nConsider creating one");
+        
+        let out = Var::new(block, TypeMetadata::ptr);
+
+        block.push_ir(Assign::new(out.clone(), value.clone()));
+
+        out
+    }
+}
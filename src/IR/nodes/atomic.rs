@@ -0,0 +1,620 @@
+use std::fmt::Display;
+
+use crate::prelude::*;
+use super::*;
+
+/// The memory ordering of an atomic operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AtomicOrdering {
+    /// Only the operation itself is atomic
+    Relaxed,
+    /// No later memory access can be moved before the operation
+    Acquire,
+    /// No earlier memory access can be moved after the operation
+    Release,
+    /// Acquire and release
+    AcqRel,
+    /// Acquire and release with a single total order of all `seq_cst` operations
+    SeqCst,
+}
+
+impl AtomicOrdering {
+    /// Parses the ordering out of its ir name (e.g: `seq_cst`)
+    pub fn parse(string: &str) -> Option<Self> {
+        match string {
+            "relaxed" => Some(AtomicOrdering::Relaxed),
+            "acquire" => Some(AtomicOrdering::Acquire),
+            "release" => Some(AtomicOrdering::Release),
+            "acq_rel" => Some(AtomicOrdering::AcqRel),
+            "seq_cst" => Some(AtomicOrdering::SeqCst),
+            _ => None,
+        }
+    }
+}
+
+impl Display for AtomicOrdering {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", match self {
+            AtomicOrdering::Relaxed => "relaxed",
+            AtomicOrdering::Acquire => "acquire",
+            AtomicOrdering::Release => "release",
+            AtomicOrdering::AcqRel => "acq_rel",
+            AtomicOrdering::SeqCst => "seq_cst",
+        })
+    }
+}
+
+/// The operation of an `atomicrmw` node
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AtomicRmwOp {
+    /// *ptr = *ptr + val
+    Add,
+    /// *ptr = val
+    Xchg,
+    /// *ptr = *ptr & val
+    And,
+    /// *ptr = *ptr | val
+    Or,
+}
+
+impl AtomicRmwOp {
+    /// Parses the operation out of its ir name (e.g: `add`)
+    pub fn parse(string: &str) -> Option<Self> {
+        match string {
+            "add" => Some(AtomicRmwOp::Add),
+            "xchg" => Some(AtomicRmwOp::Xchg),
+            "and" => Some(AtomicRmwOp::And),
+            "or" => Some(AtomicRmwOp::Or),
+            _ => None,
+        }
+    }
+}
+
+impl Display for AtomicRmwOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", match self {
+            AtomicRmwOp::Add => "add",
+            AtomicRmwOp::Xchg => "xchg",
+            AtomicRmwOp::And => "and",
+            AtomicRmwOp::Or => "or",
+        })
+    }
+}
+
+/// Atomically loads the value behind `ptr`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AtomicLoad {
+    pub(crate) out: Var,
+    pub(crate) ptr: Var,
+    pub(crate) order: AtomicOrdering,
+}
+
+/// Atomically stores `val` into `ptr`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AtomicStore {
+    pub(crate) ptr: Var,
+    pub(crate) val: IROperand,
+    pub(crate) order: AtomicOrdering,
+}
+
+/// Atomically modifies the value behind `ptr` and returns the old value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AtomicRmw {
+    pub(crate) out: Var,
+    pub(crate) op: AtomicRmwOp,
+    pub(crate) ptr: Var,
+    pub(crate) val: IROperand,
+    pub(crate) order: AtomicOrdering,
+}
+
+/// Atomically stores `new` into `ptr` if it holds `cmp` and returns the old value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CmpXchg {
+    pub(crate) out: Var,
+    pub(crate) ptr: Var,
+    pub(crate) cmp: IROperand,
+    pub(crate) new: IROperand,
+    pub(crate) order: AtomicOrdering,
+}
+
+/// Orders the memory accesses before and after it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fence {
+    pub(crate) order: AtomicOrdering,
+}
+
+/// Checks that the type fits into a single register so it can be accessed atomically
+fn verify_atomic_ty(ty: TypeMetadata) -> Result<(), VerifyError> {
+    if ty.float() || ty.wide() || ty.aggregate() || ty.vector() || ty == TypeMetadata::Void {
+        Err(VerifyError::InvalidAtomicType(ty))?
+    }
+
+    Ok(())
+}
+
+/// Inlines a constant variable into the operand
+fn inline_atomic_operand(op: &IROperand, const_values: &HashMap<String, Type>) -> Option<IROperand> {
+    if let IROperand::Var(var) = op {
+        if let Some(value) = const_values.get(&var.name) {
+            return Some(IROperand::Type(*value));
+        }
+    }
+
+    None
+}
+
+impl Ir for AtomicLoad {
+    fn dump(&self) -> String {
+        format!("{} = load atomic {} {} {}", self.out.name, self.out.ty, self.ptr.name, self.order)
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        format!("{} = {} {} {} {}",
+            profile.markup(&self.out.name, ColorClass::Var),
+            profile.markup("load atomic", ColorClass::Instr),
+            profile.markup(&self.out.ty.to_string(), ColorClass::Ty),
+            profile.markup(&self.ptr.name, ColorClass::Var),
+            profile.markup(&self.order.to_string(), ColorClass::Instr),
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        if matches!(self.order, AtomicOrdering::Release | AtomicOrdering::AcqRel) {
+            Err(VerifyError::InvalidAtomicOrdering(self.order))?
+        }
+
+        verify_atomic_ty(self.out.ty)
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::new( self.clone() )
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr, module: &mut crate::prelude::Module) {
+        registry.compile_atomic_load(self, module)
+    }
+
+    fn compile_dir(&self, compiler: &mut crate::CodeGen::IrCodeGenHelper, block: &crate::prelude::Block, module: &mut crate::prelude::Module) {
+        compiler.compile_atomic_load(self, block, module)
+    }
+
+    fn inputs(&self) -> Vec<Var> {
+        vec![self.ptr.to_owned()]
+    }
+
+    fn inputs_mut(&mut self) -> Vec<&mut Var> {
+        vec![&mut self.ptr]
+    }
+
+    fn output(&self) -> Option<Var> {
+        Some(self.out.to_owned())
+    }
+}
+
+impl Ir for AtomicStore {
+    fn dump(&self) -> String {
+        format!("store atomic {} {}, {} {}", self.val.get_ty(), self.val, self.ptr.name, self.order)
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        format!("{} {} {}, {} {}",
+            profile.markup("store atomic", ColorClass::Instr),
+            profile.markup(&self.val.get_ty().to_string(), ColorClass::Ty),
+            profile.markup(&self.val.to_string(), ColorClass::Value),
+            profile.markup(&self.ptr.name, ColorClass::Var),
+            profile.markup(&self.order.to_string(), ColorClass::Instr),
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        if matches!(self.order, AtomicOrdering::Acquire | AtomicOrdering::AcqRel) {
+            Err(VerifyError::InvalidAtomicOrdering(self.order))?
+        }
+
+        verify_atomic_ty(self.val.get_ty())
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::new( self.clone() )
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr, module: &mut crate::prelude::Module) {
+        registry.compile_atomic_store(self, module)
+    }
+
+    fn compile_dir(&self, compiler: &mut crate::CodeGen::IrCodeGenHelper, block: &crate::prelude::Block, module: &mut crate::prelude::Module) {
+        compiler.compile_atomic_store(self, block, module)
+    }
+
+    fn inputs(&self) -> Vec<Var> {
+        let mut inputs = vec![self.ptr.to_owned()];
+
+        if let IROperand::Var(val) = &self.val { inputs.push(val.to_owned()); }
+
+        inputs
+    }
+
+    fn inputs_mut(&mut self) -> Vec<&mut Var> {
+        let mut inputs = vec![&mut self.ptr];
+
+        if let IROperand::Var(val) = &mut self.val { inputs.push(val); }
+
+        inputs
+    }
+
+    fn output(&self) -> Option<Var> {
+        None
+    }
+}
+
+impl AtomicRmw {
+    /// The label of the retry loop (if the target needs one)
+    pub(crate) fn label(&self) -> String {
+        format!(".atomicrmw_{}", self.out.name.trim_start_matches('%'))
+    }
+}
+
+impl Ir for AtomicRmw {
+    fn dump(&self) -> String {
+        format!("{} = atomicrmw {} {}, {} {} {}", self.out.name, self.op, self.ptr.name, self.val.get_ty(), self.val, self.order)
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        format!("{} = {} {} {}, {} {} {}",
+            profile.markup(&self.out.name, ColorClass::Var),
+            profile.markup("atomicrmw", ColorClass::Instr),
+            profile.markup(&self.op.to_string(), ColorClass::Instr),
+            profile.markup(&self.ptr.name, ColorClass::Var),
+            profile.markup(&self.val.get_ty().to_string(), ColorClass::Ty),
+            profile.markup(&self.val.to_string(), ColorClass::Value),
+            profile.markup(&self.order.to_string(), ColorClass::Instr),
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        let ty = self.val.get_ty();
+
+        if self.out.ty != ty {
+            Err(VerifyError::Op0Op1TyNoMatch(self.out.ty, ty))?
+        }
+
+        verify_atomic_ty(ty)
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::new( self.clone() )
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr, module: &mut crate::prelude::Module) {
+        registry.compile_atomic_rmw(self, module)
+    }
+
+    fn compile_dir(&self, compiler: &mut crate::CodeGen::IrCodeGenHelper, block: &crate::prelude::Block, module: &mut crate::prelude::Module) {
+        compiler.compile_atomic_rmw(self, block, module)
+    }
+
+    fn inputs(&self) -> Vec<Var> {
+        let mut inputs = vec![self.ptr.to_owned()];
+
+        if let IROperand::Var(val) = &self.val { inputs.push(val.to_owned()); }
+
+        inputs
+    }
+
+    fn inputs_mut(&mut self) -> Vec<&mut Var> {
+        let mut inputs = vec![&mut self.ptr];
+
+        if let IROperand::Var(val) = &mut self.val { inputs.push(val); }
+
+        inputs
+    }
+
+    fn output(&self) -> Option<Var> {
+        Some(self.out.to_owned())
+    }
+}
+
+impl Ir for CmpXchg {
+    fn dump(&self) -> String {
+        format!("{} = cmpxchg {}, {} {}, {} {} {}",
+            self.out.name,
+            self.ptr.name,
+            self.cmp.get_ty(),
+            self.cmp,
+            self.new.get_ty(),
+            self.new,
+            self.order
+        )
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        format!("{} = {} {}, {} {}, {} {} {}",
+            profile.markup(&self.out.name, ColorClass::Var),
+            profile.markup("cmpxchg", ColorClass::Instr),
+            profile.markup(&self.ptr.name, ColorClass::Var),
+            profile.markup(&self.cmp.get_ty().to_string(), ColorClass::Ty),
+            profile.markup(&self.cmp.to_string(), ColorClass::Value),
+            profile.markup(&self.new.get_ty().to_string(), ColorClass::Ty),
+            profile.markup(&self.new.to_string(), ColorClass::Value),
+            profile.markup(&self.order.to_string(), ColorClass::Instr),
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        let ty = self.cmp.get_ty();
+
+        if self.new.get_ty() != ty {
+            Err(VerifyError::Op0Op1TyNoMatch(ty, self.new.get_ty()))?
+        }
+
+        if self.out.ty != ty {
+            Err(VerifyError::Op0Op1TyNoMatch(self.out.ty, ty))?
+        }
+
+        verify_atomic_ty(ty)
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::new( self.clone() )
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr, module: &mut crate::prelude::Module) {
+        registry.compile_cmpxchg(self, module)
+    }
+
+    fn compile_dir(&self, compiler: &mut crate::CodeGen::IrCodeGenHelper, block: &crate::prelude::Block, module: &mut crate::prelude::Module) {
+        compiler.compile_cmpxchg(self, block, module)
+    }
+
+    fn inputs(&self) -> Vec<Var> {
+        let mut inputs = vec![self.ptr.to_owned()];
+
+        if let IROperand::Var(cmp) = &self.cmp { inputs.push(cmp.to_owned()); }
+        if let IROperand::Var(new) = &self.new { inputs.push(new.to_owned()); }
+
+        inputs
+    }
+
+    fn inputs_mut(&mut self) -> Vec<&mut Var> {
+        let mut inputs = vec![&mut self.ptr];
+
+        if let IROperand::Var(cmp) = &mut self.cmp { inputs.push(cmp); }
+        if let IROperand::Var(new) = &mut self.new { inputs.push(new); }
+
+        inputs
+    }
+
+    fn output(&self) -> Option<Var> {
+        Some(self.out.to_owned())
+    }
+}
+
+impl Ir for Fence {
+    fn dump(&self) -> String {
+        format!("fence {}", self.order)
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        format!("{} {}",
+            profile.markup("fence", ColorClass::Instr),
+            profile.markup(&self.order.to_string(), ColorClass::Instr),
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        if self.order == AtomicOrdering::Relaxed {
+            Err(VerifyError::InvalidAtomicOrdering(self.order))?
+        }
+
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::new( self.clone() )
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr, module: &mut crate::prelude::Module) {
+        registry.compile_fence(self, module)
+    }
+
+    fn compile_dir(&self, compiler: &mut crate::CodeGen::IrCodeGenHelper, block: &crate::prelude::Block, module: &mut crate::prelude::Module) {
+        compiler.compile_fence(self, block, module)
+    }
+
+    fn inputs(&self) -> Vec<Var> {
+        vec![]
+    }
+
+    fn inputs_mut(&mut self) -> Vec<&mut Var> {
+        vec![]
+    }
+
+    fn output(&self) -> Option<Var> {
+        None
+    }
+}
+
+impl EvalOptVisitor for AtomicLoad {
+    fn maybe_inline(&self, _: &HashMap<String, Type>) -> Option<Box<dyn Ir>> {
+        None
+    }
+
+    fn eval(&self) -> Option<Box<dyn Ir>> {
+        None
+    }
+}
+
+impl EvalOptVisitor for AtomicStore {
+    fn maybe_inline(&self, const_values: &HashMap<String, Type>) -> Option<Box<dyn Ir>> {
+        let val = inline_atomic_operand(&self.val, const_values)?;
+
+        let mut inlined = self.clone();
+        inlined.val = val;
+
+        Some(Box::new(inlined))
+    }
+
+    fn eval(&self) -> Option<Box<dyn Ir>> {
+        None
+    }
+}
+
+impl EvalOptVisitor for AtomicRmw {
+    fn maybe_inline(&self, const_values: &HashMap<String, Type>) -> Option<Box<dyn Ir>> {
+        let val = inline_atomic_operand(&self.val, const_values)?;
+
+        let mut inlined = self.clone();
+        inlined.val = val;
+
+        Some(Box::new(inlined))
+    }
+
+    fn eval(&self) -> Option<Box<dyn Ir>> {
+        None
+    }
+}
+
+impl EvalOptVisitor for CmpXchg {
+    fn maybe_inline(&self, const_values: &HashMap<String, Type>) -> Option<Box<dyn Ir>> {
+        let cmp = inline_atomic_operand(&self.cmp, const_values);
+        let new = inline_atomic_operand(&self.new, const_values);
+
+        if cmp.is_none() && new.is_none() {
+            return None;
+        }
+
+        let mut inlined = self.clone();
+
+        if let Some(cmp) = cmp { inlined.cmp = cmp; }
+        if let Some(new) = new { inlined.new = new; }
+
+        Some(Box::new(inlined))
+    }
+
+    fn eval(&self) -> Option<Box<dyn Ir>> {
+        None
+    }
+}
+
+impl EvalOptVisitor for Fence {
+    fn maybe_inline(&self, _: &HashMap<String, Type>) -> Option<Box<dyn Ir>> {
+        None
+    }
+
+    fn eval(&self) -> Option<Box<dyn Ir>> {
+        None
+    }
+}
+
+impl IsNode for AtomicLoad {
+    fn is_atomicload(&self) -> bool {
+        true
+    }
+}
+
+impl IsNode for AtomicStore {
+    fn is_atomicstore(&self) -> bool {
+        true
+    }
+}
+
+impl IsNode for AtomicRmw {
+    fn is_atomicrmw(&self) -> bool {
+        true
+    }
+}
+
+impl IsNode for CmpXchg {
+    fn is_cmpxchg(&self) -> bool {
+        true
+    }
+}
+
+impl IsNode for Fence {
+    fn is_fence(&self) -> bool {
+        true
+    }
+}
+
+impl Function {
+    /// Builds the `load atomic` node which atomically loads a value of the type `ty` from `ptr`
+    /// ```no-run
+    /// %1 = load atomic i32 %0 seq_cst
+    /// ```
+    pub fn BuildAtomicLoad(&mut self, ptr: Var, ty: TypeMetadata, order: AtomicOrdering) -> Var {
+        let block = self.blocks.back_mut().expect("the IRBuilder needs to have an current block\nConsider creating one");
+
+        let out = Var::new(block, ty);
+
+        block.push_ir(Box::new( AtomicLoad { out: out.to_owned(), ptr: ptr, order: order } ));
+
+        out
+    }
+
+    /// Builds the `store atomic` node which atomically stores `val` into `ptr`
+    /// ```no-run
+    /// store atomic i32 %1, %0 seq_cst
+    /// ```
+    pub fn BuildAtomicStore(&mut self, ptr: Var, val: IROperand, order: AtomicOrdering) {
+        self.blocks.back_mut().expect("the IRBuilder needs to have an current block\nConsider creating one")
+            .push_ir(Box::new( AtomicStore { ptr: ptr, val: val, order: order } ))
+    }
+
+    /// Builds the `atomicrmw` node which atomically modifies the value behind `ptr` and returns the old value
+    /// ```no-run
+    /// %2 = atomicrmw add %0, i32 %1 seq_cst
+    /// ```
+    pub fn BuildAtomicRmw(&mut self, op: AtomicRmwOp, ptr: Var, val: IROperand, order: AtomicOrdering) -> Var {
+        let block = self.blocks.back_mut().expect("the IRBuilder needs to have an current block\nConsider creating one");
+
+        let out = Var::new(block, val.get_ty());
+
+        block.push_ir(Box::new( AtomicRmw { out: out.to_owned(), op: op, ptr: ptr, val: val, order: order } ));
+
+        out
+    }
+
+    /// Builds the `cmpxchg` node which atomically replaces the value behind `ptr` with `new` if it is `cmp`.
+    /// It returns the old value, so the exchange succeeded if it is the same as `cmp`
+    /// ```no-run
+    /// %3 = cmpxchg %0, i32 %1, i32 %2 seq_cst
+    /// ```
+    pub fn BuildCmpXchg(&mut self, ptr: Var, cmp: IROperand, new: IROperand, order: AtomicOrdering) -> Var {
+        let block = self.blocks.back_mut().expect("the IRBuilder needs to have an current block\nConsider creating one");
+
+        let out = Var::new(block, cmp.get_ty());
+
+        block.push_ir(Box::new( CmpXchg { out: out.to_owned(), ptr: ptr, cmp: cmp, new: new, order: order } ));
+
+        out
+    }
+
+    /// Builds the `fence` node which orders the memory accesses before and after it
+    /// ```no-run
+    /// fence seq_cst
+    /// ```
+    pub fn BuildFence(&mut self, order: AtomicOrdering) {
+        self.blocks.back_mut().expect("the IRBuilder needs to have an current block\nConsider creating one")
+            .push_ir(Box::new( Fence { order: order } ))
+    }
+}
@@ -0,0 +1,211 @@
+use std::fmt::Display;
+
+use super::*;
+use super::cast::{cast_bits, cast_from_bits, cast_int};
+
+/// How a bit node manipulates its value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BitOpKind {
+    /// Counts the set bits
+    Popcnt,
+    /// Counts the leading zeros (the bit size for zero)
+    Clz,
+    /// Counts the trailing zeros (the bit size for zero)
+    Ctz,
+    /// Reverses the order of the bytes
+    Bswap,
+}
+
+impl BitOpKind {
+    /// Parses the kind out of its ir name (e.g: `popcnt`)
+    pub fn parse(string: &str) -> Option<Self> {
+        match string {
+            "popcnt" => Some(BitOpKind::Popcnt),
+            "clz" => Some(BitOpKind::Clz),
+            "ctz" => Some(BitOpKind::Ctz),
+            "bswap" => Some(BitOpKind::Bswap),
+            _ => None,
+        }
+    }
+
+    /// Returns if the kind can manipulate values of the type `ty` (128bit intengers can't be manipulated)
+    pub fn legal(&self, ty: TypeMetadata) -> bool {
+        if ty.wide() {
+            return false;
+        }
+
+        match self {
+            BitOpKind::Popcnt | BitOpKind::Clz | BitOpKind::Ctz => cast_int(ty),
+            BitOpKind::Bswap => cast_int(ty) && ty.bitSize() % 16 == 0,
+        }
+    }
+
+    /// Manipulates the constant `value`
+    pub(crate) fn fold(&self, value: Type) -> Option<Type> {
+        let ty: TypeMetadata = value.into();
+        let bits = cast_bits(value);
+        let width = ty.bitSize() as u32;
+
+        let result = match self {
+            BitOpKind::Popcnt => bits.count_ones() as u128,
+            BitOpKind::Clz => (bits.leading_zeros() - (128 - width)) as u128,
+            BitOpKind::Ctz => bits.trailing_zeros().min(width) as u128,
+            BitOpKind::Bswap => bits.swap_bytes() >> (128 - width),
+        };
+
+        cast_from_bits(ty, result)
+    }
+}
+
+impl Display for BitOpKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", match self {
+            BitOpKind::Popcnt => "popcnt",
+            BitOpKind::Clz => "clz",
+            BitOpKind::Ctz => "ctz",
+            BitOpKind::Bswap => "bswap",
+        })
+    }
+}
+
+/// Rotates the constant `value` by `amount` bits (used to fold `rotl` and `rotr`)
+pub(super) fn bit_rotate(value: Type, amount: Type, left: bool) -> Option<Type> {
+    let ty: TypeMetadata = value.into();
+    let bits = cast_bits(value);
+    let width = ty.bitSize() as u32;
+
+    let amount = (cast_bits(amount) % width as u128) as u32;
+
+    if amount == 0 {
+        return Some(value);
+    }
+
+    let amount = if left { amount } else { width - amount };
+
+    cast_from_bits(ty, (bits << amount) | (bits >> (width - amount)))
+}
+
+impl Ir for BitOp {
+    fn dump(&self) -> String {
+        format!("{} = {} {} {}", self.inner2.name, self.kind, self.inner1.get_ty(), self.inner1)
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        format!("{} = {} {} {}", 
+            profile.markup(&self.inner2.name, ColorClass::Var), 
+            profile.markup(&self.kind.to_string(), ColorClass::Instr),
+            profile.markup(&self.inner1.get_ty().to_string(), ColorClass::Ty), 
+            profile.markup(&self.inner1.to_string(), ColorClass::Var),
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        let ty = self.inner1.get_ty();
+
+        if self.inner2.ty != ty {
+            Err(VerifyError::Op0Op1TyNoMatch(self.inner2.ty, ty))?
+        }
+
+        if !self.kind.legal(ty) {
+            Err(VerifyError::InvalidBitOp(self.kind, ty))?
+        }
+
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::new( self.clone() )
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr, module: &mut crate::prelude::Module) {
+        registry.compile_bitop(self, module)
+    }
+
+    fn compile_dir(&self, compiler: &mut crate::CodeGen::IrCodeGenHelper, block: &crate::prelude::Block, module: &mut crate::prelude::Module) {
+        compiler.compile_bitop(self, block, module)
+    }
+
+    fn inputs(&self) -> Vec<Var> {
+        let mut inputs = Vec::new();
+        if let IROperand::Var(value) = &self.inner1 { inputs.push(value.to_owned()); }
+        inputs
+    }
+
+    fn inputs_mut(&mut self) -> Vec<&mut Var> {
+        let mut inputs = Vec::new();
+        if let IROperand::Var(value) = &mut self.inner1 { inputs.push( value); }
+        inputs
+    }
+
+    fn output(&self) -> Option<Var> {
+        Some(self.inner2.to_owned())
+    }
+}
+
+impl IsNode for BitOp {
+    fn is_bitop(&self) -> bool {
+        true
+    }
+}
+
+impl EvalOptVisitor for BitOp {
+    fn maybe_inline(&self, const_values: &HashMap<String, Type>) -> Option<Box<dyn Ir>> {
+        if let IROperand::Var(var) = &self.inner1 {
+            if let Some(value) = const_values.get(&var.name) {
+                let mut inlined = self.clone();
+                inlined.inner1 = IROperand::Type(*value);
+
+                return Some(Box::new(inlined));
+            }
+        }
+
+        None
+    }
+
+    fn eval(&self) -> Option<Box<dyn Ir>> {
+        if let IROperand::Type(value) = &self.inner1 {
+            if self.kind.legal(self.inner1.get_ty()) {
+                if let Some(folded) = self.kind.fold(*value) {
+                    return Some(Assign::new(self.inner2.to_owned(), folded));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Trait for the bit manipulation nodes
+/// Used for overloading the BuildBitOp function
+pub trait BuildBitOp<T> {
+    /// builds a bit manipulation (e.g: a `popcnt`) of the value
+    fn BuildBitOp(&mut self, kind: BitOpKind, value: T) -> Var;
+}
+
+impl BuildBitOp<Var> for Function {
+    fn BuildBitOp(&mut self, kind: BitOpKind, var: Var) -> Var {
+        let block = self.blocks.back_mut().expect("the IRBuilder needs to have an current block\nConsider creating one");
+
+        let out = Var::new(block, var.ty);
+
+        block.push_ir(BitOp::new(kind, IROperand::Var(var), out.clone()));
+
+        out
+    }
+}
+
+impl BuildBitOp<Type> for Function {
+    fn BuildBitOp(&mut self, kind: BitOpKind, value: Type) -> Var {
+        let block = self.blocks.back_mut().expect("the IRBuilder needs to have an current block\nConsider creating one");
+
+        let out = Var::new(block, value.into());
+
+        block.push_ir(BitOp::new(kind, IROperand::Type(value), out.clone()));
+
+        out
+    }
+}
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use crate::Support::ColorClass;
 use crate::IR::block::BlockId;
-use crate::IR::{Function, Type, Var};
+use crate::IR::{Function, Type, TypeMetadata, Var};
 
 use super::{Br, BrCond, EvalOptVisitor, Ir};
 
@@ -100,6 +100,10 @@ impl Ir for BrCond {
 
     fn verify(&self, _: crate::prelude::FunctionType) -> Result<(), crate::prelude::VerifyError> {
         // TODO: Check if the blocks and the var exits
+
+        if self.inner1.ty != TypeMetadata::i1 {
+            Err(crate::prelude::VerifyError::ConditionNotI1(self.inner1.ty))?
+        }
         
         Ok(())
     }
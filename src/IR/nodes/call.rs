@@ -1,3 +1,5 @@
+use crate::CodeGen::MachineCallingConvention;
+use crate::Target::{Arch, CallConv};
 use crate::IR::func::FuncId;
 
 use super::*;
@@ -10,7 +12,11 @@ impl Ir for Call {
             fmt.push_str(&format!("{} ", arg))
         }
 
-        format!("{} = call {} {} {}", self.out.name, self.func.ty.ret, self.func.name, fmt)
+        let conv = if let Some(conv) = self.func.ty.call_conv { format!("{} ", conv) } else { String::new() };
+
+        let tail = if self.tail { "tail " } else { "" };
+
+        format!("{} = {}call {}{} {} {}{}", self.out.name, tail, conv, self.func.ty.ret, self.func.name, fmt, self.attrs)
     }
 
     fn dumpColored(&self, profile: ColorProfile) -> String {
@@ -21,12 +27,16 @@ impl Ir for Call {
             fmt.push(' ');
         }
 
-        format!("{} = {} {} {} {}", 
+        let conv = if let Some(conv) = self.func.ty.call_conv { format!("{} ", profile.markup(&conv.to_string(), ColorClass::Instr)) } else { String::new() };
+
+        format!("{} = {} {}{} {} {}{}", 
             profile.markup(&self.out.name, ColorClass::Var),
-            profile.markup("call", ColorClass::Instr),
+            profile.markup(if self.tail { "tail call" } else { "call" }, ColorClass::Instr),
+            conv,
             profile.markup(&self.func.ty.ret.to_string(), ColorClass::Ty),
             profile.markup(&self.func.name, ColorClass::Name),
-            fmt
+            fmt,
+            profile.markup(&self.attrs.to_string(), ColorClass::Instr)
         )
     }
 
@@ -34,11 +44,17 @@ impl Ir for Call {
         self
     }
 
-    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+    fn verify(&self, caller: FunctionType) -> Result<(), VerifyError> {
         if self.out.ty != self.func.ty.ret {
             Err(VerifyError::Op0Op1TyNoMatch(self.out.ty, self.func.ty.ret))?
         }
 
+        self.func.ty.verify_call_conv()?;
+
+        if self.tail {
+            self.verify_tail(&caller, &self.func.ty)?;
+        }
+
         let mut index = 0;
         let args = &self.func.ty.args;
         for arg in &self.args {
@@ -50,6 +66,10 @@ impl Ir for Call {
                 if !self.func.ty.any_args {
                     Err(VerifyError::ToManyArgumentsWereSupplyed)?
                 }
+
+                if arg.get_ty().vector() {
+                    Err(VerifyError::VectorArgUnsupported(arg.get_ty()))?
+                }
             }
             
             index += 1;
@@ -113,10 +133,132 @@ impl Ir for Call {
     }
 }
 
+impl Ir for CallIndirect {
+    fn dump(&self) -> String {
+        let mut fmt = String::new();
+        
+        for arg in &self.args {
+            fmt.push_str(&format!("{} {} ", arg.get_ty(), arg))
+        }
+
+        let conv = if let Some(conv) = self.ty.call_conv { format!("{} ", conv) } else { String::new() };
+
+        format!("{} = call {}{} {} {}", self.out.name, conv, self.ty.ret, self.ptr.name, fmt)
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        let mut fmt = String::new();
+        
+        for arg in &self.args {
+            fmt.push_str(&profile.markup(&arg.get_ty().to_string(), ColorClass::Ty));
+            fmt.push(' ');
+            fmt.push_str(&profile.markup(&arg.to_string(), ColorClass::Var));
+            fmt.push(' ');
+        }
+
+        let conv = if let Some(conv) = self.ty.call_conv { format!("{} ", profile.markup(&conv.to_string(), ColorClass::Instr)) } else { String::new() };
+
+        format!("{} = {} {}{} {} {}", 
+            profile.markup(&self.out.name, ColorClass::Var),
+            profile.markup("call", ColorClass::Instr),
+            conv,
+            profile.markup(&self.ty.ret.to_string(), ColorClass::Ty),
+            profile.markup(&self.ptr.name, ColorClass::Var),
+            fmt
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        if self.ptr.ty != TypeMetadata::ptr {
+            Err(VerifyError::Op0Op1TyNoMatch(self.ptr.ty, TypeMetadata::ptr))?
+        }
+
+        if self.out.ty != self.ty.ret {
+            Err(VerifyError::Op0Op1TyNoMatch(self.out.ty, self.ty.ret))?
+        }
+
+        self.ty.verify_call_conv()?;
+
+        let args = &self.ty.args;
+        for (index, arg) in self.args.iter().enumerate() {
+            if let Some((_, argty)) = args.get(index) {
+                if *argty != arg.get_ty() {
+                    Err(VerifyError::InvalidArgumentTypeFound)?
+                }
+            } else if !self.ty.any_args {
+                Err(VerifyError::ToManyArgumentsWereSupplyed)?
+            } else if arg.get_ty().vector() {
+                Err(VerifyError::VectorArgUnsupported(arg.get_ty()))?
+            }
+        }
+
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::from( self.clone() )
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr, module: &mut crate::prelude::Module) {
+        registry.compile_call_indirect(&self, module)
+    }
+
+    fn uses(&self, var: &Var) -> bool {
+        self.inputs().iter().any(|input| input.name == var.name)
+    }
+    
+    fn compile_dir(&self, compiler: &mut crate::CodeGen::IrCodeGenHelper, block: &crate::prelude::Block, module: &mut crate::prelude::Module) {
+        compiler.compile_call_indirect(&self, &block, module)
+    }
+    
+    fn inputs(&self) -> Vec<Var> {
+        let mut inputs = vec![self.ptr.to_owned()];
+
+        for arg in &self.args {
+            if let IROperand::Var(arg) = &arg {
+                inputs.push(arg.to_owned());
+            }
+        }
+
+        inputs
+    }
+    
+    fn inputs_mut(&mut self) -> Vec<&mut Var> {
+        let mut inputs = vec![&mut self.ptr];
+
+        for arg in &mut self.args {
+            if let IROperand::Var(arg) = arg {
+                inputs.push(arg);
+            }
+        }
+
+        inputs
+    }
+    
+    fn output(&self) -> Option<Var> {
+        Some(self.out.to_owned())
+    }
+}
+
 impl IsNode for Call {
     fn is_call(&self) -> bool {
         true
     }
+
+    fn has_side_effects(&self) -> bool {
+        // a `readnone` call only depends on its arguments, so it can be removed if its output is unused
+        !self.attrs.has(FnAttr::ReadNone)
+    }
+}
+
+impl IsNode for CallIndirect {
+    fn is_callindirect(&self) -> bool {
+        true
+    }
 }
 
 impl Call {
@@ -134,6 +276,109 @@ impl Call {
     pub fn getOutputVar(&self) -> Var {
         self.out.to_owned()
     }
+
+    /// Adds the attribute to the call site (e.g: `noreturn`)
+    pub fn add_attr(&mut self, attr: FnAttr) {
+        self.attrs.add(attr);
+    }
+
+    /// Returns if the call site has the attribute
+    pub fn has_attr(&self, attr: FnAttr) -> bool {
+        self.attrs.has(attr)
+    }
+
+    /// Returns the attributes of the call site
+    pub fn attrs(&self) -> &FnAttrs {
+        &self.attrs
+    }
+
+    /// Returns if the call is compiled into a jump
+    pub fn is_tail(&self) -> bool {
+        self.tail
+    }
+
+    /// Sets if the call is compiled into a jump.
+    /// The call needs to be directly followed by a return of its result
+    pub fn set_tail(&mut self, tail: bool) {
+        self.tail = tail;
+    }
+
+    /// Checks if the tail call can replace the frame of the caller:
+    ///  * the called function returns the same type using the same calling convention
+    ///  * all arguments are passed in registers (the stack arguments of the caller can't be reused)
+    pub(crate) fn verify_tail(&self, caller: &FunctionType, callee: &FunctionType) -> Result<(), VerifyError> {
+        if caller.ret != callee.ret || caller.call_conv != callee.call_conv {
+            Err(VerifyError::IncompatibleTailCall)?
+        }
+
+        // without a calling convention the target decides, so the arguments need to fit into the registers of both
+        let in_regs = match callee.call_conv {
+            Some(call_conv) => self.args_in_regs(call_conv),
+            None => self.args_in_regs(CallConv::SystemV) && self.args_in_regs(CallConv::WindowsFastCall),
+        };
+
+        if !in_regs {
+            Err(VerifyError::IncompatibleTailCall)?
+        }
+
+        Ok(())
+    }
+
+    /// Returns if all arguments are passed in registers using the calling convention
+    fn args_in_regs(&self, call_conv: CallConv) -> bool {
+        let conv = MachineCallingConvention { call_conv: call_conv };
+
+        let fp_args = self.args.iter().filter(|arg| arg.get_ty().float()).count();
+        let gr_args = self.args.len() - fp_args;
+
+        if call_conv == CallConv::WindowsFastCall {
+            // in the windows abi the position of the argument selects its register
+            self.args.len() <= conv.args(Arch::X86_64, TypeMetadata::i64).len()
+        } else {
+            gr_args <= conv.args(Arch::X86_64, TypeMetadata::i64).len() && fp_args <= conv.args(Arch::X86_64, TypeMetadata::f64).len()
+        }
+    }
+
+    /// Returns if the node is a return of the result of the call
+    pub(crate) fn returned_by(&self, node: Option<&Box<dyn Ir>>) -> bool {
+        let Some(ret) = node.and_then(|node| node.as_any().downcast_ref::<Return>()) else {
+            return false;
+        };
+
+        if self.out.ty == TypeMetadata::Void {
+            return true;
+        }
+
+        matches!(&ret.inner1, IROperand::Var(var) if var.name == self.out.name)
+    }
+
+    /// Returns if the called function never returns
+    /// (either the call site or the called function has the `noreturn` attribute)
+    pub(crate) fn is_noreturn(&self, module: &crate::prelude::Module) -> bool {
+        self.attrs.has(FnAttr::NoReturn) || matches!(module.getFunc(&self.func.name), Some(func) if func.has_attr(FnAttr::NoReturn))
+    }
+}
+
+impl CallIndirect {
+    /// Returns the variable which holds the adress of the called function
+    pub fn getCallTarget(&self) -> Var {
+        self.ptr.to_owned()
+    }
+
+    /// Returns the type of the called function
+    pub fn getFuncTy(&self) -> FunctionType {
+        self.ty.to_owned()
+    }
+
+    /// Returns the arguments
+    pub fn getArgs(&self) -> Vec<IROperand> {
+        self.args.to_owned()
+    }
+
+    /// Returns the variable which stores the result of the call
+    pub fn getOutputVar(&self) -> Var {
+        self.out.to_owned()
+    }
 }
 
 impl EvalOptVisitor for Call {
@@ -162,6 +407,61 @@ impl BuildCall<&FuncId, Vec<IROperand>> for Function {
             out: out.to_owned(),
             func: func.clone(),
             args: args,
+            attrs: FnAttrs::new(),
+            tail: false,
+        }));
+
+        out 
+    }
+}
+
+impl Function {
+    /// Builds a tail call to the function which gets compiled into a jump.
+    /// Its result needs to be returned directly afterwards
+    /// ```no-run
+    /// %1 = tail call i32 func i32 %0
+    /// ret i32 %1
+    /// ```
+    pub fn BuildTailCall(&mut self, func: &FuncId, args: Vec<IROperand>) -> Var {
+        let block = self.blocks.back_mut().expect("the IRBuilder needs to have an current block\nConsider creating one");
+        
+        let out = Var::new(block, func.ty.ret);
+
+        block.push_ir(Box::new(Call {
+            out: out.to_owned(),
+            func: func.clone(),
+            args: args,
+            attrs: FnAttrs::new(),
+            tail: true,
+        }));
+
+        out 
+    }
+}
+
+impl EvalOptVisitor for CallIndirect {
+    fn maybe_inline(&self, _: &HashMap<String, Type>) -> Option<Box<dyn Ir>> {
+        None
+    }
+    
+    fn eval(&self) -> Option<Box<dyn Ir>> {
+        None
+    }
+}
+
+impl Function {
+    /// Builds a call to the function the pointer `ptr` points to.
+    /// `ty` is the type of the called function
+    pub fn BuildCallIndirect(&mut self, ptr: Var, ty: &FunctionType, args: Vec<IROperand>) -> Var {
+        let block = self.blocks.back_mut().expect("the IRBuilder needs to have an current block\nConsider creating one");
+        
+        let out = Var::new(block, ty.ret);
+
+        block.push_ir(Box::new(CallIndirect {
+            out: out.to_owned(),
+            ptr: ptr,
+            ty: ty.to_owned(),
+            args: args,
         }));
 
         out 
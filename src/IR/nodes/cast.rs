@@ -1,14 +1,193 @@
+use std::fmt::Display;
+
 use super::*;
 
+/// How a cast converts its value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CastKind {
+    /// Cuts the upper bits of an intenger away
+    Trunc,
+    /// Extends an intenger with zeros
+    Zext,
+    /// Extends an intenger with its sign bit
+    Sext,
+    /// Converts a float into a smaller float
+    FpTrunc,
+    /// Converts a float into a bigger float
+    FpExt,
+    /// Converts a float into a signed intenger (rounds towards zero)
+    FpToSi,
+    /// Converts a float into an unsigned intenger (rounds towards zero)
+    FpToUi,
+    /// Converts a signed intenger into a float
+    SiToFp,
+    /// Converts an unsigned intenger into a float
+    UiToFp,
+    /// Converts a pointer into an intenger
+    PtrToInt,
+    /// Converts an intenger into a pointer
+    IntToPtr,
+    /// Reinterprets the bits of the value as another type of the same size
+    Bitcast,
+}
+
+impl CastKind {
+    /// Parses the cast kind out of its ir name (e.g: `zext`)
+    pub fn parse(string: &str) -> Option<Self> {
+        match string {
+            "trunc" => Some(CastKind::Trunc),
+            "zext" => Some(CastKind::Zext),
+            "sext" => Some(CastKind::Sext),
+            "fptrunc" => Some(CastKind::FpTrunc),
+            "fpext" => Some(CastKind::FpExt),
+            "fptosi" => Some(CastKind::FpToSi),
+            "fptoui" => Some(CastKind::FpToUi),
+            "sitofp" => Some(CastKind::SiToFp),
+            "uitofp" => Some(CastKind::UiToFp),
+            "ptrtoint" => Some(CastKind::PtrToInt),
+            "inttoptr" => Some(CastKind::IntToPtr),
+            "bitcast" => Some(CastKind::Bitcast),
+            _ => None,
+        }
+    }
+
+    /// Infers the kind of the cast out of the types (the signedness of `from` decides between sign and zero extension)
+    pub fn infer(from: TypeMetadata, to: TypeMetadata) -> Self {
+        if from == to {
+            return CastKind::Bitcast;
+        }
+
+        match (from.float(), to.float()) {
+            (true, true) if from.bitSize() < to.bitSize() => CastKind::FpExt,
+            (true, true) if from.bitSize() > to.bitSize() => CastKind::FpTrunc,
+            (true, false) if to.signed() => CastKind::FpToSi,
+            (true, false) => CastKind::FpToUi,
+            (false, true) if from.signed() => CastKind::SiToFp,
+            (false, true) => CastKind::UiToFp,
+            _ if from == TypeMetadata::ptr && cast_int(to) => CastKind::PtrToInt,
+            _ if to == TypeMetadata::ptr && cast_int(from) => CastKind::IntToPtr,
+            _ if from.bitSize() < to.bitSize() && from.signed() => CastKind::Sext,
+            _ if from.bitSize() < to.bitSize() => CastKind::Zext,
+            _ if from.bitSize() > to.bitSize() => CastKind::Trunc,
+            _ => CastKind::Bitcast,
+        }
+    }
+
+    /// Returns if the cast kind can convert from `from` to `to`
+    pub fn legal(&self, from: TypeMetadata, to: TypeMetadata) -> bool {
+        match self {
+            CastKind::Trunc => cast_int(from) && cast_int(to) && from.bitSize() > to.bitSize(),
+            CastKind::Zext | CastKind::Sext => cast_int(from) && cast_int(to) && from.bitSize() < to.bitSize(),
+            CastKind::FpTrunc => from.float() && to.float() && from.bitSize() > to.bitSize(),
+            CastKind::FpExt => from.float() && to.float() && from.bitSize() < to.bitSize(),
+            CastKind::FpToSi | CastKind::FpToUi => from.float() && cast_int(to),
+            CastKind::SiToFp | CastKind::UiToFp => cast_int(from) && to.float(),
+            CastKind::PtrToInt => from == TypeMetadata::ptr && cast_int(to),
+            CastKind::IntToPtr => cast_int(from) && to == TypeMetadata::ptr,
+            CastKind::Bitcast => {
+                !from.aggregate() && !to.aggregate() && from != TypeMetadata::Void && to != TypeMetadata::Void &&
+                from.bitSize() == to.bitSize()
+            },
+        }
+    }
+
+    /// Casts the constant `value` into `to`
+    pub(crate) fn fold(&self, value: Type, to: TypeMetadata) -> Option<Type> {
+        let from: TypeMetadata = value.into();
+        let bits = cast_bits(value);
+
+        match self {
+            CastKind::Trunc | CastKind::Zext | CastKind::PtrToInt | CastKind::IntToPtr | CastKind::Bitcast => cast_from_bits(to, bits),
+            CastKind::Sext => cast_from_bits(to, cast_sext(bits, from) as u128),
+            CastKind::FpTrunc | CastKind::FpExt | CastKind::FpToSi | CastKind::FpToUi => Some(Type::from_int(to, value.val())),
+            CastKind::SiToFp => Some(Type::from_int(to, cast_sext(bits, from) as f64)),
+            CastKind::UiToFp => Some(Type::from_int(to, bits as f64)),
+        }
+    }
+}
+
+impl Display for CastKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", match self {
+            CastKind::Trunc => "trunc",
+            CastKind::Zext => "zext",
+            CastKind::Sext => "sext",
+            CastKind::FpTrunc => "fptrunc",
+            CastKind::FpExt => "fpext",
+            CastKind::FpToSi => "fptosi",
+            CastKind::FpToUi => "fptoui",
+            CastKind::SiToFp => "sitofp",
+            CastKind::UiToFp => "uitofp",
+            CastKind::PtrToInt => "ptrtoint",
+            CastKind::IntToPtr => "inttoptr",
+            CastKind::Bitcast => "bitcast",
+        })
+    }
+}
+
+/// Returns if the type is a scalar intenger
+pub(super) fn cast_int(ty: TypeMetadata) -> bool {
+    !ty.float() && !ty.aggregate() && !ty.vector() && ty != TypeMetadata::ptr && ty != TypeMetadata::Void
+}
+
+/// Returns the raw bits of the constant (zero extended)
+pub(super) fn cast_bits(value: Type) -> u128 {
+    match value {
+        Type::i1(val) => val as u128,
+        Type::u8(val) => val as u128,
+        Type::u16(val) => val as u128,
+        Type::u32(val) => val as u128,
+        Type::u64(val) => val as u128,
+        Type::u128(val) => val,
+        Type::i8(val) => val as u8 as u128,
+        Type::i16(val) => val as u16 as u128,
+        Type::i32(val) => val as u32 as u128,
+        Type::i64(val) => val as u64 as u128,
+        Type::i128(val) => val as u128,
+        Type::ptr(adr) => adr as u64 as u128,
+        Type::Void => 0,
+        Type::f32(val) => val.to_bits() as u128,
+        Type::f64(val) => val.to_bits() as u128,
+    }
+}
+
+/// Sign extends the bits of a value of the type `from`
+pub(super) fn cast_sext(bits: u128, from: TypeMetadata) -> i128 {
+    let shift = 128 - from.bitSize() as u32;
+
+    ((bits << shift) as i128) >> shift
+}
+
+/// Creates a constant of the type `ty` out of the raw bits (the upper bits are cut away)
+pub(super) fn cast_from_bits(ty: TypeMetadata, bits: u128) -> Option<Type> {
+    Some(match ty {
+        TypeMetadata::i1 => Type::i1(bits & 1 == 1),
+        TypeMetadata::u8 => Type::u8(bits as u8),
+        TypeMetadata::u16 => Type::u16(bits as u16),
+        TypeMetadata::u32 => Type::u32(bits as u32),
+        TypeMetadata::u64 => Type::u64(bits as u64),
+        TypeMetadata::u128 => Type::u128(bits),
+        TypeMetadata::i8 => Type::i8(bits as i8),
+        TypeMetadata::i16 => Type::i16(bits as i16),
+        TypeMetadata::i32 => Type::i32(bits as i32),
+        TypeMetadata::i64 => Type::i64(bits as i64),
+        TypeMetadata::i128 => Type::i128(bits as i128),
+        TypeMetadata::ptr => Type::ptr(bits as i64),
+        TypeMetadata::f32 => Type::f32(f32::from_bits(bits as u32)),
+        TypeMetadata::f64 => Type::f64(f64::from_bits(bits as u64)),
+        _ => return None,
+    })
+}
+
 impl Ir for Cast {
     fn dump(&self) -> String {
-        format!("{} = cast {} {} to {}", self.inner3.name, self.inner1.get_ty(), self.inner1, self.inner2)
+        format!("{} = {} {} {} to {}", self.inner3.name, self.kind, self.inner1.get_ty(), self.inner1, self.inner2)
     }
 
     fn dumpColored(&self, profile: ColorProfile) -> String {
         format!("{} = {} {} {} {} {}", 
             profile.markup(&self.inner3.name, ColorClass::Var), 
-            profile.markup(&"cast", ColorClass::Instr),
+            profile.markup(&self.kind.to_string(), ColorClass::Instr),
             profile.markup(&self.inner1.get_ty().to_string(), ColorClass::Ty), 
             profile.markup(&self.inner1.to_string(), ColorClass::Var), 
             profile.markup(&"to", ColorClass::Instr),
@@ -24,6 +203,11 @@ impl Ir for Cast {
         if self.inner3.ty != self.inner2 {
             Err(VerifyError::Op0Op1TyNoMatch(self.inner3.ty, self.inner2))?
         }
+
+        if !self.kind.legal(self.inner1.get_ty(), self.inner2) {
+            Err(VerifyError::InvalidCast(self.kind, self.inner1.get_ty(), self.inner2))?
+        }
+
         Ok(())
     }
 
@@ -77,6 +261,12 @@ impl Ir for Cast {
     }
 }
 
+impl IsNode for Cast {
+    fn is_cast(&self) -> bool {
+        true
+    }
+}
+
 impl Cast {
     /// Returns the input as a variable
     pub fn getInputVar(&self) -> Var {
@@ -112,13 +302,21 @@ impl Cast {
     pub fn getFromType(&self) -> TypeMetadata {
         self.inner1.get_ty()
     }
+
+    /// Returns how the value gets converted
+    pub fn getCastKind(&self) -> CastKind {
+        self.kind
+    }
 }
 
 impl EvalOptVisitor for Cast {
     fn maybe_inline(&self, vars: &HashMap<String, Type>) -> Option<Box<dyn Ir>> {
         if let IROperand::Var(value) = &self.inner1 {
             if let Some(var) = vars.get(&value.name) {
-                return Some(Assign::new(self.inner3.to_owned(), *var));
+                let mut inlined = self.clone();
+                inlined.inner1 = IROperand::Type(*var);
+
+                return Some(Box::new(inlined));
             } 
         }
         
@@ -127,28 +325,44 @@ impl EvalOptVisitor for Cast {
     
     fn eval(&self) -> Option<Box<dyn Ir>> {
         if self.inner2 == self.inner1.get_ty() {
-            match &self.inner1 {
+            return match &self.inner1 {
                 IROperand::Type(ty) => Some(Assign::new(self.inner3.to_owned(), ty.to_owned())),
                 IROperand::Var(var) => Some(Assign::new(self.inner3.to_owned(), var.to_owned())),
+            };
+        }
+
+        if let IROperand::Type(value) = &self.inner1 {
+            if self.kind.legal(self.inner1.get_ty(), self.inner2) {
+                if let Some(casted) = self.kind.fold(*value, self.inner2) {
+                    return Some(Assign::new(self.inner3.to_owned(), casted));
+                }
             }
-        } else { None }
+        }
+
+        None
     }
 }
 
 /// Trait for the cast instruction
 /// Used for overloading the BuildCast function
 pub trait BuildCast<T, U> {
-    /// builds an cast to form one variable into another type
+    /// builds an cast to form one variable into another type (the kind of the cast is infered out of the types)
     fn BuildCast(&mut self, value: T, ty: U) -> Var;
+    /// builds an cast with an explicit kind (e.g: a `bitcast` from f64 to u64)
+    fn BuildCastOp(&mut self, kind: CastKind, value: T, ty: U) -> Var;
 }
 
 impl BuildCast<Var, TypeMetadata> for Function {
     fn BuildCast(&mut self, var: Var, ty: TypeMetadata) -> Var {
+        self.BuildCastOp(CastKind::infer(var.ty, ty), var, ty)
+    }
+
+    fn BuildCastOp(&mut self, kind: CastKind, var: Var, ty: TypeMetadata) -> Var {
         let block = self.blocks.back_mut().expect("the IRBuilder needs to have an current block\nConsider creating one");
-        
+
         let out = Var::new(block, ty);
 
-        block.push_ir(Cast::new(IROperand::Var(var), ty, out.clone()));
+        block.push_ir(Cast::new(kind, IROperand::Var(var), ty, out.clone()));
 
         out
     }
@@ -156,11 +370,15 @@ impl BuildCast<Var, TypeMetadata> for Function {
 
 impl BuildCast<Type, TypeMetadata> for Function {
     fn BuildCast(&mut self, value: Type, ty: TypeMetadata) -> Var {
+        self.BuildCastOp(CastKind::infer(value.into(), ty), value, ty)
+    }
+
+    fn BuildCastOp(&mut self, kind: CastKind, value: Type, ty: TypeMetadata) -> Var {
         let block = self.blocks.back_mut().expect("the IRBuilder needs to have an current block\nConsider creating one");
-        
+
         let out = Var::new(block, ty);
 
-        block.push_ir(Cast::new(IROperand::Type(value), ty, out.clone()));
+        block.push_ir(Cast::new(kind, IROperand::Type(value), ty, out.clone()));
 
         out
     }
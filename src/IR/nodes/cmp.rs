@@ -3,9 +3,13 @@ use std::fmt::Display;
 use crate::Support::ColorClass;
 use crate::IR::{Function, Type, TypeMetadata, Var, VerifyError};
 
+use super::cast::{cast_bits, cast_sext};
 use super::{Assign, Cmp, EvalOptVisitor, IROperand, Ir, IsNode};
 
 /// The "compare mode" (e.g: ls is equal to rs)
+/// 
+/// The modes without a prefix take the signedness out of the type
+/// and compare floats ordered (except `ne` which is unordered)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum CmpMode {
     /// ls == rs
@@ -20,6 +24,225 @@ pub enum CmpMode {
     GreaterThanOrEqual,
     /// ls <= rs
     LessThanOrEqual,
+
+    /// ls > rs (signed integers)
+    SignedGreaterThan,
+    /// ls < rs (signed integers)
+    SignedLessThan,
+    /// ls >= rs (signed integers)
+    SignedGreaterThanOrEqual,
+    /// ls <= rs (signed integers)
+    SignedLessThanOrEqual,
+
+    /// ls > rs (unsigned integers)
+    UnsignedGreaterThan,
+    /// ls < rs (unsigned integers)
+    UnsignedLessThan,
+    /// ls >= rs (unsigned integers)
+    UnsignedGreaterThanOrEqual,
+    /// ls <= rs (unsigned integers)
+    UnsignedLessThanOrEqual,
+
+    /// neither ls nor rs is NaN
+    Ordered,
+    /// ls or rs is NaN
+    Unordered,
+
+    /// ls == rs and neither is NaN
+    OrderedEqal,
+    /// ls != rs and neither is NaN
+    OrderedNotEqal,
+    /// ls > rs and neither is NaN
+    OrderedGreaterThan,
+    /// ls < rs and neither is NaN
+    OrderedLessThan,
+    /// ls >= rs and neither is NaN
+    OrderedGreaterThanOrEqual,
+    /// ls <= rs and neither is NaN
+    OrderedLessThanOrEqual,
+
+    /// ls == rs or one is NaN
+    UnorderedEqal,
+    /// ls != rs or one is NaN
+    UnorderedNotEqal,
+    /// ls > rs or one is NaN
+    UnorderedGreaterThan,
+    /// ls < rs or one is NaN
+    UnorderedLessThan,
+    /// ls >= rs or one is NaN
+    UnorderedGreaterThanOrEqual,
+    /// ls <= rs or one is NaN
+    UnorderedLessThanOrEqual,
+}
+
+impl CmpMode {
+    /// Parses the compare mode for operands of the type `ty`
+    /// (`ugt`, `ult`, `uge` and `ule` are unsigned for integers and unordered for floats)
+    pub fn parse(mode: &str, ty: TypeMetadata) -> Option<CmpMode> {
+        Some(match mode {
+            "eq" => CmpMode::Eqal,
+            "ne" => CmpMode::NotEqal,
+            "ge" => CmpMode::GreaterThan,
+            "le" => CmpMode::LessThan,
+            "gte" => CmpMode::GreaterThanOrEqual,
+            "lte" => CmpMode::LessThanOrEqual,
+
+            "sgt" => CmpMode::SignedGreaterThan,
+            "slt" => CmpMode::SignedLessThan,
+            "sge" => CmpMode::SignedGreaterThanOrEqual,
+            "sle" => CmpMode::SignedLessThanOrEqual,
+
+            "ugt" if ty.float() => CmpMode::UnorderedGreaterThan,
+            "ult" if ty.float() => CmpMode::UnorderedLessThan,
+            "uge" if ty.float() => CmpMode::UnorderedGreaterThanOrEqual,
+            "ule" if ty.float() => CmpMode::UnorderedLessThanOrEqual,
+
+            "ugt" => CmpMode::UnsignedGreaterThan,
+            "ult" => CmpMode::UnsignedLessThan,
+            "uge" => CmpMode::UnsignedGreaterThanOrEqual,
+            "ule" => CmpMode::UnsignedLessThanOrEqual,
+
+            "ord" => CmpMode::Ordered,
+            "uno" => CmpMode::Unordered,
+
+            "oeq" => CmpMode::OrderedEqal,
+            "one" => CmpMode::OrderedNotEqal,
+            "ogt" => CmpMode::OrderedGreaterThan,
+            "olt" => CmpMode::OrderedLessThan,
+            "oge" => CmpMode::OrderedGreaterThanOrEqual,
+            "ole" => CmpMode::OrderedLessThanOrEqual,
+
+            "ueq" => CmpMode::UnorderedEqal,
+            "une" => CmpMode::UnorderedNotEqal,
+
+            _ => return None,
+        })
+    }
+
+    /// Replaces the type driven modes (like `ge`) with the explicit mode for operands of the type `ty`
+    pub fn resolve(self, ty: TypeMetadata) -> CmpMode {
+        let unsigned = matches!(ty, 
+            TypeMetadata::u8 | TypeMetadata::u16 | TypeMetadata::u32 | 
+            TypeMetadata::u64 | TypeMetadata::u128 | TypeMetadata::ptr
+        );
+
+        match self {
+            CmpMode::Eqal if ty.float() => CmpMode::OrderedEqal,
+            CmpMode::NotEqal if ty.float() => CmpMode::UnorderedNotEqal,
+            CmpMode::GreaterThan if ty.float() => CmpMode::OrderedGreaterThan,
+            CmpMode::LessThan if ty.float() => CmpMode::OrderedLessThan,
+            CmpMode::GreaterThanOrEqual if ty.float() => CmpMode::OrderedGreaterThanOrEqual,
+            CmpMode::LessThanOrEqual if ty.float() => CmpMode::OrderedLessThanOrEqual,
+
+            CmpMode::GreaterThan if unsigned => CmpMode::UnsignedGreaterThan,
+            CmpMode::LessThan if unsigned => CmpMode::UnsignedLessThan,
+            CmpMode::GreaterThanOrEqual if unsigned => CmpMode::UnsignedGreaterThanOrEqual,
+            CmpMode::LessThanOrEqual if unsigned => CmpMode::UnsignedLessThanOrEqual,
+
+            CmpMode::GreaterThan => CmpMode::SignedGreaterThan,
+            CmpMode::LessThan => CmpMode::SignedLessThan,
+            CmpMode::GreaterThanOrEqual => CmpMode::SignedGreaterThanOrEqual,
+            CmpMode::LessThanOrEqual => CmpMode::SignedLessThanOrEqual,
+
+            mode => mode,
+        }
+    }
+
+    /// Returns the mode which gives the same result if ls and rs are swapped
+    pub fn swapped(self) -> CmpMode {
+        match self {
+            CmpMode::GreaterThan => CmpMode::LessThan,
+            CmpMode::LessThan => CmpMode::GreaterThan,
+            CmpMode::GreaterThanOrEqual => CmpMode::LessThanOrEqual,
+            CmpMode::LessThanOrEqual => CmpMode::GreaterThanOrEqual,
+            CmpMode::SignedGreaterThan => CmpMode::SignedLessThan,
+            CmpMode::SignedLessThan => CmpMode::SignedGreaterThan,
+            CmpMode::SignedGreaterThanOrEqual => CmpMode::SignedLessThanOrEqual,
+            CmpMode::SignedLessThanOrEqual => CmpMode::SignedGreaterThanOrEqual,
+            CmpMode::UnsignedGreaterThan => CmpMode::UnsignedLessThan,
+            CmpMode::UnsignedLessThan => CmpMode::UnsignedGreaterThan,
+            CmpMode::UnsignedGreaterThanOrEqual => CmpMode::UnsignedLessThanOrEqual,
+            CmpMode::UnsignedLessThanOrEqual => CmpMode::UnsignedGreaterThanOrEqual,
+            CmpMode::OrderedGreaterThan => CmpMode::OrderedLessThan,
+            CmpMode::OrderedLessThan => CmpMode::OrderedGreaterThan,
+            CmpMode::OrderedGreaterThanOrEqual => CmpMode::OrderedLessThanOrEqual,
+            CmpMode::OrderedLessThanOrEqual => CmpMode::OrderedGreaterThanOrEqual,
+            CmpMode::UnorderedGreaterThan => CmpMode::UnorderedLessThan,
+            CmpMode::UnorderedLessThan => CmpMode::UnorderedGreaterThan,
+            CmpMode::UnorderedGreaterThanOrEqual => CmpMode::UnorderedLessThanOrEqual,
+            CmpMode::UnorderedLessThanOrEqual => CmpMode::UnorderedGreaterThanOrEqual,
+            mode => mode,
+        }
+    }
+
+    /// Returns if the mode is only defined for floats
+    pub fn float(&self) -> bool {
+        matches!(self, 
+            CmpMode::Ordered | CmpMode::Unordered |
+            CmpMode::OrderedEqal | CmpMode::OrderedNotEqal | CmpMode::OrderedGreaterThan | 
+            CmpMode::OrderedLessThan | CmpMode::OrderedGreaterThanOrEqual | CmpMode::OrderedLessThanOrEqual |
+            CmpMode::UnorderedEqal | CmpMode::UnorderedNotEqal | CmpMode::UnorderedGreaterThan | 
+            CmpMode::UnorderedLessThan | CmpMode::UnorderedGreaterThanOrEqual | CmpMode::UnorderedLessThanOrEqual
+        )
+    }
+
+    /// Returns if the mode is only defined for integers
+    pub fn integer(&self) -> bool {
+        matches!(self,
+            CmpMode::SignedGreaterThan | CmpMode::SignedLessThan | 
+            CmpMode::SignedGreaterThanOrEqual | CmpMode::SignedLessThanOrEqual |
+            CmpMode::UnsignedGreaterThan | CmpMode::UnsignedLessThan | 
+            CmpMode::UnsignedGreaterThanOrEqual | CmpMode::UnsignedLessThanOrEqual
+        )
+    }
+
+    /// Compares the two constants
+    pub(crate) fn fold(&self, ls: &Type, rs: &Type) -> bool {
+        let ty: TypeMetadata = (*ls).into();
+
+        if ty.float() {
+            let (ls, rs) = (ls.val(), rs.val());
+            let unordered = ls.is_nan() || rs.is_nan();
+
+            // rust's float compares are already ordered (except !=)
+            return match self.resolve(ty) {
+                CmpMode::Ordered => !unordered,
+                CmpMode::Unordered => unordered,
+                CmpMode::OrderedEqal => ls == rs,
+                CmpMode::OrderedNotEqal => !unordered && ls != rs,
+                CmpMode::OrderedGreaterThan => ls > rs,
+                CmpMode::OrderedLessThan => ls < rs,
+                CmpMode::OrderedGreaterThanOrEqual => ls >= rs,
+                CmpMode::OrderedLessThanOrEqual => ls <= rs,
+                CmpMode::UnorderedEqal => unordered || ls == rs,
+                CmpMode::UnorderedNotEqal => ls != rs,
+                CmpMode::UnorderedGreaterThan => unordered || ls > rs,
+                CmpMode::UnorderedLessThan => unordered || ls < rs,
+                CmpMode::UnorderedGreaterThanOrEqual => unordered || ls >= rs,
+                CmpMode::UnorderedLessThanOrEqual => unordered || ls <= rs,
+                // integer modes aren't legal for floats
+                _ => false,
+            };
+        }
+
+        let (ls, rs) = (cast_bits(*ls), cast_bits(*rs));
+        let (sls, srs) = (cast_sext(ls, ty), cast_sext(rs, ty));
+
+        match self.resolve(ty) {
+            CmpMode::Eqal => ls == rs,
+            CmpMode::NotEqal => ls != rs,
+            CmpMode::SignedGreaterThan => sls > srs,
+            CmpMode::SignedLessThan => sls < srs,
+            CmpMode::SignedGreaterThanOrEqual => sls >= srs,
+            CmpMode::SignedLessThanOrEqual => sls <= srs,
+            CmpMode::UnsignedGreaterThan => ls > rs,
+            CmpMode::UnsignedLessThan => ls < rs,
+            CmpMode::UnsignedGreaterThanOrEqual => ls >= rs,
+            CmpMode::UnsignedLessThanOrEqual => ls <= rs,
+            // float modes aren't legal for integers
+            _ => false,
+        }
+    }
 }
 
 impl Display for CmpMode {
@@ -31,6 +254,28 @@ impl Display for CmpMode {
             CmpMode::LessThan => "le",
             CmpMode::GreaterThanOrEqual => "gte",
             CmpMode::LessThanOrEqual => "lte",
+            CmpMode::SignedGreaterThan => "sgt",
+            CmpMode::SignedLessThan => "slt",
+            CmpMode::SignedGreaterThanOrEqual => "sge",
+            CmpMode::SignedLessThanOrEqual => "sle",
+            CmpMode::UnsignedGreaterThan => "ugt",
+            CmpMode::UnsignedLessThan => "ult",
+            CmpMode::UnsignedGreaterThanOrEqual => "uge",
+            CmpMode::UnsignedLessThanOrEqual => "ule",
+            CmpMode::Ordered => "ord",
+            CmpMode::Unordered => "uno",
+            CmpMode::OrderedEqal => "oeq",
+            CmpMode::OrderedNotEqal => "one",
+            CmpMode::OrderedGreaterThan => "ogt",
+            CmpMode::OrderedLessThan => "olt",
+            CmpMode::OrderedGreaterThanOrEqual => "oge",
+            CmpMode::OrderedLessThanOrEqual => "ole",
+            CmpMode::UnorderedEqal => "ueq",
+            CmpMode::UnorderedNotEqal => "une",
+            CmpMode::UnorderedGreaterThan => "ugt",
+            CmpMode::UnorderedLessThan => "ult",
+            CmpMode::UnorderedGreaterThanOrEqual => "uge",
+            CmpMode::UnorderedLessThanOrEqual => "ule",
         })
     }
 }
@@ -60,6 +305,12 @@ impl Ir for Cmp {
             Err(VerifyError::Op0Op1TyNoMatch(self.ls.get_ty(), self.rs.get_ty()))?
         }
 
+        let ty = self.ls.get_ty();
+
+        if (self.mode.float() && !ty.float()) || (self.mode.integer() && ty.float()) {
+            Err(VerifyError::InvalidCmpMode(self.mode, ty))?
+        }
+
         Ok(())
     }
 
@@ -118,40 +369,32 @@ impl Ir for Cmp {
 }
 
 fn calc_based_on_mode(mode: &CmpMode, ls: &Type, rs: &Type, out: Var) -> Option<Box<dyn Ir>> {
-    let condition_met = match mode {
-        CmpMode::Eqal => ls.val() == rs.val(),
-        CmpMode::NotEqal => ls.val() != rs.val(),
-        CmpMode::GreaterThan => ls.val() > rs.val(),
-        CmpMode::LessThan => ls.val() < rs.val(),
-        CmpMode::GreaterThanOrEqual => ls.val() >= rs.val(),
-        CmpMode::LessThanOrEqual => ls.val() <= rs.val(),
-    };
-
-    Some(Assign::new(out, Type::from_int(TypeMetadata::u8, condition_met as i8 as f64)))
+    let condition_met = mode.fold(ls, rs);
+
+    Some(Assign::new(out.to_owned(), Type::from_int(out.ty, condition_met as i8 as f64)))
 }
 
 impl EvalOptVisitor for Cmp {
     fn maybe_inline(&self, consts: &std::collections::HashMap<String, crate::prelude::Type>) -> Option<Box<dyn Ir>> {
-        match (&self.ls, &self.rs) {
-            (IROperand::Var(ls), IROperand::Var(rs)) => {
-                match (consts.get(&ls.name), consts.get(&rs.name)) {
-                    (Option::Some(ls), Option::Some(rs)) => calc_based_on_mode(&self.mode, ls, rs, self.out.to_owned()),
-                    _ => None,
-                }
-            },
-            _ => None
+        let constant = |op: &IROperand| match op {
+            IROperand::Var(var) => consts.get(&var.name).copied(),
+            IROperand::Type(ty) => Some(*ty),
+        };
+
+        match (constant(&self.ls), constant(&self.rs)) {
+            (Option::Some(ls), Option::Some(rs)) => calc_based_on_mode(&self.mode, &ls, &rs, self.out.to_owned()),
+            _ => None,
         }
     }
     
     fn eval(&self) -> Option<Box<dyn Ir>> {
-        if self.ls == self.rs {
-            let yes = match self.mode {
+        // a float variable could be NaN which isn't equal to itself
+        if self.ls == self.rs && !self.ls.get_ty().float() {
+            let yes = match self.mode.resolve(self.ls.get_ty()) {
                 CmpMode::Eqal => 1,
-                CmpMode::NotEqal => 0,
-                CmpMode::GreaterThan => 0,
-                CmpMode::LessThan => 0,
-                CmpMode::GreaterThanOrEqual => 1,
-                CmpMode::LessThanOrEqual => 1,
+                CmpMode::SignedGreaterThanOrEqual | CmpMode::SignedLessThanOrEqual => 1,
+                CmpMode::UnsignedGreaterThanOrEqual | CmpMode::UnsignedLessThanOrEqual => 1,
+                _ => 0,
             };
 
             Some(Assign::new(self.out.to_owned(), Type::from_int(
@@ -220,7 +463,7 @@ impl BuildCmp<Var, Var> for Function {
     fn BuildCmp(&mut self, mode: CmpMode, ls: Var, rs: Var) -> Var {
         let block = self.blocks.back_mut().expect("the IRBuilder needs to have an current block\nConsider creating one");
         
-        let out = Var::new(block, TypeMetadata::u8);
+        let out = Var::new(block, TypeMetadata::i1);
 
         block.push_ir( Cmp::new(mode, IROperand::Var(ls), IROperand::Var(rs), out.to_owned()) );
 
@@ -232,7 +475,7 @@ impl BuildCmp<Var, Type> for Function {
     fn BuildCmp(&mut self, mode: CmpMode, ls: Var, rs: Type) -> Var {
         let block = self.blocks.back_mut().expect("the IRBuilder needs to have an current block\nConsider creating one");
         
-        let out = Var::new(block, TypeMetadata::u8);
+        let out = Var::new(block, TypeMetadata::i1);
 
         block.push_ir( Cmp::new(mode, IROperand::Var(ls), IROperand::Type(rs), out.to_owned()) );
 
@@ -244,7 +487,7 @@ impl BuildCmp<Type, Var> for Function {
     fn BuildCmp(&mut self, mode: CmpMode, ls: Type, rs: Var) -> Var {
         let block = self.blocks.back_mut().expect("the IRBuilder needs to have an current block\nConsider creating one");
         
-        let out = Var::new(block, TypeMetadata::u8);
+        let out = Var::new(block, TypeMetadata::i1);
 
         block.push_ir( Cmp::new(mode, IROperand::Type(ls), IROperand::Var(rs), out.to_owned()) );
 
@@ -256,7 +499,7 @@ impl BuildCmp<Type, Type> for Function {
     fn BuildCmp(&mut self, mode: CmpMode, ls: Type, rs: Type) -> Var {
         let block = self.blocks.back_mut().expect("the IRBuilder needs to have an current block\nConsider creating one");
         
-        let out = Var::new(block, TypeMetadata::u8);
+        let out = Var::new(block, TypeMetadata::i1);
 
         block.push_ir( Cmp::new(mode, IROperand::Type(ls), IROperand::Type(rs), out.to_owned()) );
 
@@ -0,0 +1,283 @@
+use std::fmt::Display;
+
+use super::*;
+
+/// Which math function a float node computes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FloatOpKind {
+    /// The square root
+    Sqrt,
+    /// The absolute value
+    Fabs,
+    /// The magnitude of the first operand with the sign of the second one
+    Copysign,
+    /// Rounds towards negative infinity
+    Floor,
+    /// Rounds towards positive infinity
+    Ceil,
+    /// Rounds towards zero
+    Trunc,
+    /// Rounds to the nearest intenger (halfway cases away from zero)
+    Round,
+    /// Multiplies the first two operands and adds the third one (with only one rounding)
+    Fma,
+    /// The smaller operand (if one operand is NaN the other one)
+    Minnum,
+    /// The bigger operand (if one operand is NaN the other one)
+    Maxnum,
+}
+
+impl FloatOpKind {
+    /// Parses the kind out of its ir name (e.g: `sqrt`)
+    /// 
+    /// Trunc is called `ftrunc` in the ir, so it doesn't clash with the intenger truncation
+    pub fn parse(string: &str) -> Option<Self> {
+        match string {
+            "sqrt" => Some(FloatOpKind::Sqrt),
+            "fabs" => Some(FloatOpKind::Fabs),
+            "copysign" => Some(FloatOpKind::Copysign),
+            "floor" => Some(FloatOpKind::Floor),
+            "ceil" => Some(FloatOpKind::Ceil),
+            "ftrunc" => Some(FloatOpKind::Trunc),
+            "round" => Some(FloatOpKind::Round),
+            "fma" => Some(FloatOpKind::Fma),
+            "minnum" => Some(FloatOpKind::Minnum),
+            "maxnum" => Some(FloatOpKind::Maxnum),
+            _ => None,
+        }
+    }
+
+    /// Returns how many operands the kind takes
+    pub fn arity(&self) -> usize {
+        match self {
+            FloatOpKind::Copysign | FloatOpKind::Minnum | FloatOpKind::Maxnum => 2,
+            FloatOpKind::Fma => 3,
+            _ => 1,
+        }
+    }
+
+    /// Returns the name of the libm function which computes the kind for values of the type `ty`
+    pub fn libm(&self, ty: TypeMetadata) -> String {
+        let name = match self {
+            FloatOpKind::Sqrt => "sqrt",
+            FloatOpKind::Fabs => "fabs",
+            FloatOpKind::Copysign => "copysign",
+            FloatOpKind::Floor => "floor",
+            FloatOpKind::Ceil => "ceil",
+            FloatOpKind::Trunc => "trunc",
+            FloatOpKind::Round => "round",
+            FloatOpKind::Fma => "fma",
+            FloatOpKind::Minnum => "fmin",
+            FloatOpKind::Maxnum => "fmax",
+        };
+
+        if ty == TypeMetadata::f32 { format!("{}f", name) } else { name.to_string() }
+    }
+
+    /// Computes the kind on the constant operands
+    pub(crate) fn fold(&self, args: &[Type]) -> Option<Type> {
+        match args {
+            [Type::f32(x)] => Some(Type::f32(match self {
+                FloatOpKind::Sqrt => x.sqrt(),
+                FloatOpKind::Fabs => x.abs(),
+                FloatOpKind::Floor => x.floor(),
+                FloatOpKind::Ceil => x.ceil(),
+                FloatOpKind::Trunc => x.trunc(),
+                FloatOpKind::Round => x.round(),
+                _ => None?,
+            })),
+            [Type::f64(x)] => Some(Type::f64(match self {
+                FloatOpKind::Sqrt => x.sqrt(),
+                FloatOpKind::Fabs => x.abs(),
+                FloatOpKind::Floor => x.floor(),
+                FloatOpKind::Ceil => x.ceil(),
+                FloatOpKind::Trunc => x.trunc(),
+                FloatOpKind::Round => x.round(),
+                _ => None?,
+            })),
+            [Type::f32(x), Type::f32(y)] => Some(Type::f32(match self {
+                FloatOpKind::Copysign => x.copysign(*y),
+                FloatOpKind::Minnum => x.min(*y),
+                FloatOpKind::Maxnum => x.max(*y),
+                _ => None?,
+            })),
+            [Type::f64(x), Type::f64(y)] => Some(Type::f64(match self {
+                FloatOpKind::Copysign => x.copysign(*y),
+                FloatOpKind::Minnum => x.min(*y),
+                FloatOpKind::Maxnum => x.max(*y),
+                _ => None?,
+            })),
+            [Type::f32(x), Type::f32(y), Type::f32(z)] if *self == FloatOpKind::Fma => Some(Type::f32(x.mul_add(*y, *z))),
+            [Type::f64(x), Type::f64(y), Type::f64(z)] if *self == FloatOpKind::Fma => Some(Type::f64(x.mul_add(*y, *z))),
+            _ => None,
+        }
+    }
+}
+
+impl Display for FloatOpKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", match self {
+            FloatOpKind::Sqrt => "sqrt",
+            FloatOpKind::Fabs => "fabs",
+            FloatOpKind::Copysign => "copysign",
+            FloatOpKind::Floor => "floor",
+            FloatOpKind::Ceil => "ceil",
+            FloatOpKind::Trunc => "ftrunc",
+            FloatOpKind::Round => "round",
+            FloatOpKind::Fma => "fma",
+            FloatOpKind::Minnum => "minnum",
+            FloatOpKind::Maxnum => "maxnum",
+        })
+    }
+}
+
+impl Ir for FloatOp {
+    fn dump(&self) -> String {
+        let args = self.args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>().join(", ");
+
+        format!("{} = {} {} {}", self.out.name, self.kind, self.out.ty, args)
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        let args = self.args.iter().map(|arg| profile.markup(&arg.to_string(), ColorClass::Value)).collect::<Vec<_>>().join(", ");
+
+        format!("{} = {} {} {}",
+            profile.markup(&self.out.name, ColorClass::Var),
+            profile.markup(&self.kind.to_string(), ColorClass::Instr),
+            profile.markup(&self.out.ty.to_string(), ColorClass::Ty),
+            args,
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        let ty = self.out.ty;
+
+        if self.args.len() != self.kind.arity() {
+            Err(VerifyError::InvalidFloatOpArgs(self.kind, self.args.len()))?
+        }
+
+        for arg in &self.args {
+            if arg.get_ty() != ty {
+                Err(VerifyError::Op0Op1TyNoMatch(arg.get_ty(), ty))?
+            }
+        }
+
+        if !ty.float() {
+            Err(VerifyError::InvalidFloatOp(self.kind, ty))?
+        }
+
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::new( self.clone() )
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr, module: &mut crate::prelude::Module) {
+        registry.compile_floatop(self, module)
+    }
+
+    fn compile_dir(&self, compiler: &mut crate::CodeGen::IrCodeGenHelper, block: &crate::prelude::Block, module: &mut crate::prelude::Module) {
+        compiler.compile_floatop(self, block, module)
+    }
+
+    fn inputs(&self) -> Vec<Var> {
+        let mut inputs = Vec::new();
+        for arg in &self.args {
+            if let IROperand::Var(arg) = arg { inputs.push(arg.to_owned()); }
+        }
+        inputs
+    }
+
+    fn inputs_mut(&mut self) -> Vec<&mut Var> {
+        let mut inputs = Vec::new();
+        for arg in &mut self.args {
+            if let IROperand::Var(arg) = arg { inputs.push(arg); }
+        }
+        inputs
+    }
+
+    fn output(&self) -> Option<Var> {
+        Some(self.out.to_owned())
+    }
+}
+
+impl IsNode for FloatOp {
+    fn is_floatop(&self) -> bool {
+        true
+    }
+}
+
+impl EvalOptVisitor for FloatOp {
+    fn maybe_inline(&self, const_values: &HashMap<String, Type>) -> Option<Box<dyn Ir>> {
+        let mut inlined = self.clone();
+        let mut changed = false;
+
+        for arg in &mut inlined.args {
+            if let IROperand::Var(var) = arg {
+                if let Some(value) = const_values.get(&var.name) {
+                    *arg = IROperand::Type(*value);
+                    changed = true;
+                }
+            }
+        }
+
+        if changed { Some(Box::new(inlined)) } else { None }
+    }
+
+    fn eval(&self) -> Option<Box<dyn Ir>> {
+        let mut args = Vec::new();
+
+        for arg in &self.args {
+            let IROperand::Type(arg) = arg else { return None };
+            args.push(*arg);
+        }
+
+        let folded = self.kind.fold(&args)?;
+
+        if TypeMetadata::from(folded) != self.out.ty {
+            return None;
+        }
+
+        Some(Assign::new(self.out.to_owned(), folded))
+    }
+}
+
+/// Trait for the float math nodes
+/// Used for overloading the BuildFloatOp function
+pub trait BuildFloatOp<T> {
+    /// computes the math function (e.g: a `sqrt`) on the operands
+    fn BuildFloatOp(&mut self, kind: FloatOpKind, args: Vec<T>) -> Var;
+}
+
+impl BuildFloatOp<Var> for Function {
+    fn BuildFloatOp(&mut self, kind: FloatOpKind, args: Vec<Var>) -> Var {
+        let block = self.blocks.back_mut().expect("the IRBuilder needs to have an current block\nConsider creating one");
+
+        let ty = args.first().expect("float nodes need at least one operand").ty;
+
+        let out = Var::new(block, ty);
+
+        block.push_ir(FloatOp::new(kind, args.into_iter().map(|arg| IROperand::Var(arg)).collect(), out.clone()));
+
+        out
+    }
+}
+
+impl BuildFloatOp<IROperand> for Function {
+    fn BuildFloatOp(&mut self, kind: FloatOpKind, args: Vec<IROperand>) -> Var {
+        let block = self.blocks.back_mut().expect("the IRBuilder needs to have an current block\nConsider creating one");
+
+        let ty = args.first().expect("float nodes need at least one operand").get_ty();
+
+        let out = Var::new(block, ty);
+
+        block.push_ir(FloatOp::new(kind, args, out.clone()));
+
+        out
+    }
+}
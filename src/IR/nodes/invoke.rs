@@ -0,0 +1,431 @@
+use crate::IR::func::FuncId;
+
+use super::*;
+
+/// Calls the function and branches to `normal` if it returns or to `unwind`
+/// if an exception is thrown through it (the `unwind` block needs to start with a `landingpad`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Invoke {
+    pub(crate) out: Var,
+    pub(crate) func: FuncId,
+    pub(crate) args: Vec<IROperand>,
+    pub(crate) normal: BlockId,
+    pub(crate) unwind: BlockId,
+}
+
+/// Recives the exception at the start of the block an `invoke` unwinds to
+///
+/// The personality function of the function selects the landing pad using the clauses:
+///  * `catch @typeinfo`: catches exceptions of the type (`catch null` catches all exceptions)
+///  * `cleanup`: the landing pad is also entered for exceptions which aren't catched (they need to be resumed)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LandingPad {
+    pub(crate) out: Var,
+    /// which clause was selected: the index (starting at 1) of the catched type in the type table of the function
+    /// (which lists the catched types of all landing pads in the order they first appear in) or `0` for a cleanup
+    pub(crate) selector: Option<Var>,
+    pub(crate) cleanup: bool,
+    /// the type infos of the catched exceptions (`None` catches all)
+    pub(crate) catches: Vec<Option<String>>,
+}
+
+impl Invoke {
+    /// Returns the called function
+    pub fn getCallTarget(&self) -> FuncId {
+        self.func.to_owned()
+    }
+
+    /// Returns the arguments
+    pub fn getArgs(&self) -> Vec<IROperand> {
+        self.args.to_owned()
+    }
+
+    /// Returns the variable which stores the result of the call
+    pub fn getOutputVar(&self) -> Var {
+        self.out.to_owned()
+    }
+
+    /// Returns the block which is executed if the called function returns
+    pub fn getNormalBlock(&self) -> BlockId {
+        self.normal.to_owned()
+    }
+
+    /// Returns the block which is executed if an exception is thrown
+    pub fn getUnwindBlock(&self) -> BlockId {
+        self.unwind.to_owned()
+    }
+
+    /// The name of the label which marks a position in the compiled invoke
+    /// (`begin` and `end` surround the call and `pad` is the start of the code which enters the landing pad)
+    pub(crate) fn label(&self, what: &str) -> String {
+        format!("{}.invoke_{}", self.out.name, what)
+    }
+}
+
+impl LandingPad {
+    /// Returns the variable which holds the adress of the exception
+    pub fn getOutputVar(&self) -> Var {
+        self.out.to_owned()
+    }
+
+    /// Returns the variable which holds the selector of the catched exception
+    pub fn getSelector(&self) -> Option<Var> {
+        self.selector.to_owned()
+    }
+
+    /// Returns if the landing pad is also entered for exceptions which aren't catched
+    pub fn is_cleanup(&self) -> bool {
+        self.cleanup
+    }
+
+    /// Returns the type infos of the catched exceptions (`None` catches all)
+    pub fn getCatches(&self) -> Vec<Option<String>> {
+        self.catches.to_owned()
+    }
+
+    fn clauses(&self) -> String {
+        let mut fmt = String::new();
+
+        if self.cleanup {
+            fmt.push_str(" cleanup");
+        }
+
+        for catch in &self.catches {
+            match catch {
+                Some(typeinfo) => fmt.push_str(&format!(" catch @{}", typeinfo)),
+                None => fmt.push_str(" catch null"),
+            }
+        }
+
+        fmt
+    }
+}
+
+impl Ir for Invoke {
+    fn dump(&self) -> String {
+        let mut fmt = String::new();
+
+        for arg in &self.args {
+            fmt.push_str(&format!("{} {} ", arg.get_ty(), arg))
+        }
+
+        let conv = if let Some(conv) = self.func.ty.call_conv { format!("{} ", conv) } else { String::new() };
+
+        format!("{} = invoke {}{} {} {}to {} unwind {}", self.out.name, conv, self.func.ty.ret, self.func.name, fmt, self.normal.name, self.unwind.name)
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        let mut fmt = String::new();
+
+        for arg in &self.args {
+            fmt.push_str(&profile.markup(&arg.get_ty().to_string(), ColorClass::Ty));
+            fmt.push(' ');
+            fmt.push_str(&profile.markup(&arg.to_string(), ColorClass::Var));
+            fmt.push(' ');
+        }
+
+        let conv = if let Some(conv) = self.func.ty.call_conv { format!("{} ", profile.markup(&conv.to_string(), ColorClass::Instr)) } else { String::new() };
+
+        format!("{} = {} {}{} {} {}{} {} {} {}",
+            profile.markup(&self.out.name, ColorClass::Var),
+            profile.markup("invoke", ColorClass::Instr),
+            conv,
+            profile.markup(&self.func.ty.ret.to_string(), ColorClass::Ty),
+            profile.markup(&self.func.name, ColorClass::Name),
+            fmt,
+            profile.markup("to", ColorClass::Instr),
+            profile.markup(&self.normal.name, ColorClass::Name),
+            profile.markup("unwind", ColorClass::Instr),
+            profile.markup(&self.unwind.name, ColorClass::Name),
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        if self.out.ty != self.func.ty.ret {
+            Err(VerifyError::Op0Op1TyNoMatch(self.out.ty, self.func.ty.ret))?
+        }
+
+        self.func.ty.verify_call_conv()?;
+
+        let args = &self.func.ty.args;
+        for (index, arg) in self.args.iter().enumerate() {
+            if let Some((_, argty)) = args.get(index) {
+                if *argty != arg.get_ty() {
+                    Err(VerifyError::InvalidArgumentTypeFound)?
+                }
+            } else if !self.func.ty.any_args {
+                Err(VerifyError::ToManyArgumentsWereSupplyed)?
+            } else if arg.get_ty().vector() {
+                Err(VerifyError::VectorArgUnsupported(arg.get_ty()))?
+            }
+        }
+
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::from( self.clone() )
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr, module: &mut crate::prelude::Module) {
+        registry.compile_invoke(&self, module)
+    }
+
+    fn uses(&self, var: &Var) -> bool {
+        self.inputs().iter().any(|input| input.name == var.name)
+    }
+
+    fn compile_dir(&self, compiler: &mut crate::CodeGen::IrCodeGenHelper, block: &crate::prelude::Block, module: &mut crate::prelude::Module) {
+        compiler.compile_invoke(&self, &block, module)
+    }
+
+    fn inputs(&self) -> Vec<Var> {
+        let mut inputs = Vec::new();
+
+        for arg in &self.args {
+            if let IROperand::Var(arg) = &arg {
+                inputs.push(arg.to_owned());
+            }
+        }
+
+        inputs
+    }
+
+    fn inputs_mut(&mut self) -> Vec<&mut Var> {
+        let mut inputs = Vec::new();
+
+        for arg in &mut self.args {
+            if let IROperand::Var(arg) = arg {
+                inputs.push(arg);
+            }
+        }
+
+        inputs
+    }
+
+    fn output(&self) -> Option<Var> {
+        Some(self.out.to_owned())
+    }
+}
+
+impl Ir for LandingPad {
+    fn dump(&self) -> String {
+        let outs = if let Some(selector) = &self.selector { format!("{}, {}", self.out.name, selector.name) } else { self.out.name.to_owned() };
+
+        format!("{} = landingpad {}{}", outs, self.out.ty, self.clauses())
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        let outs = if let Some(selector) = &self.selector {
+            format!("{}, {}", profile.markup(&self.out.name, ColorClass::Var), profile.markup(&selector.name, ColorClass::Var))
+        } else {
+            profile.markup(&self.out.name, ColorClass::Var)
+        };
+
+        format!("{} = {} {}{}",
+            outs,
+            profile.markup("landingpad", ColorClass::Instr),
+            profile.markup(&self.out.ty.to_string(), ColorClass::Ty),
+            profile.markup(&self.clauses(), ColorClass::Instr),
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        if self.out.ty != TypeMetadata::ptr {
+            Err(VerifyError::Op0Op1TyNoMatch(self.out.ty, TypeMetadata::ptr))?
+        }
+
+        if let Some(selector) = &self.selector {
+            if selector.ty != TypeMetadata::i32 {
+                Err(VerifyError::Op0Op1TyNoMatch(selector.ty, TypeMetadata::i32))?
+            }
+        }
+
+        if !self.cleanup && self.catches.is_empty() {
+            Err(VerifyError::LandingPadWithoutClause)?
+        }
+
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::from( self.clone() )
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr, module: &mut crate::prelude::Module) {
+        registry.compile_landingpad(&self, module)
+    }
+
+    fn compile_dir(&self, compiler: &mut crate::CodeGen::IrCodeGenHelper, block: &crate::prelude::Block, module: &mut crate::prelude::Module) {
+        compiler.compile_landingpad(&self, &block, module)
+    }
+
+    fn inputs(&self) -> Vec<Var> {
+        vec![]
+    }
+
+    fn inputs_mut(&mut self) -> Vec<&mut Var> {
+        vec![]
+    }
+
+    fn output(&self) -> Option<Var> {
+        Some(self.out.to_owned())
+    }
+
+    fn outputs(&self) -> Vec<Var> {
+        let mut outs = vec![self.out.to_owned()];
+        outs.extend(self.selector.to_owned());
+        outs
+    }
+}
+
+impl EvalOptVisitor for Invoke {
+    fn maybe_inline(&self, const_values: &HashMap<String, Type>) -> Option<Box<dyn Ir>> {
+        let mut inlined = self.clone();
+        let mut changed = false;
+
+        for arg in &mut inlined.args {
+            if let IROperand::Var(var) = arg {
+                if let Some(value) = const_values.get(&var.name) {
+                    *arg = IROperand::Type(*value);
+                    changed = true;
+                }
+            }
+        }
+
+        if changed { Some(Box::new(inlined)) } else { None }
+    }
+
+    fn eval(&self) -> Option<Box<dyn Ir>> {
+        None
+    }
+}
+
+impl EvalOptVisitor for LandingPad {
+    fn maybe_inline(&self, _: &HashMap<String, Type>) -> Option<Box<dyn Ir>> {
+        None
+    }
+
+    fn eval(&self) -> Option<Box<dyn Ir>> {
+        None
+    }
+}
+
+impl IsNode for Invoke {
+    fn is_invoke(&self) -> bool {
+        true
+    }
+}
+
+impl IsNode for LandingPad {
+    fn is_landingpad(&self) -> bool {
+        true
+    }
+}
+
+impl Function {
+    /// Builds a call to the function which continues in `normal` if it returns
+    /// or in `unwind` if an exception is thrown
+    /// ```no-run
+    /// %1 = invoke i32 func i32 %0 to ok unwind lpad
+    /// ```
+    pub fn BuildInvoke(&mut self, func: &FuncId, args: Vec<IROperand>, normal: &BlockId, unwind: &BlockId) -> Var {
+        let block = self.blocks.back_mut().expect("the IRBuilder needs to have an current block\nConsider creating one");
+
+        let out = Var::new(block, func.ty.ret);
+
+        block.push_ir(Box::new(Invoke {
+            out: out.to_owned(),
+            func: func.clone(),
+            args: args,
+            normal: normal.to_owned(),
+            unwind: unwind.to_owned(),
+        }));
+
+        out
+    }
+
+    /// Builds a landing pad which recives the adress of the exception.
+    /// `catches` are the type infos of the catched exceptions (`None` catches all)
+    /// ```no-run
+    /// %1 = landingpad ptr cleanup catch @_ZTIi
+    /// ```
+    pub fn BuildLandingPad(&mut self, cleanup: bool, catches: Vec<Option<&str>>) -> Var {
+        let block = self.blocks.back_mut().expect("the IRBuilder needs to have an current block\nConsider creating one");
+
+        let out = Var::new(block, TypeMetadata::ptr);
+
+        block.push_ir(Box::new(LandingPad {
+            out: out.to_owned(),
+            selector: None,
+            cleanup: cleanup,
+            catches: catches.into_iter().map(|catch| catch.map(str::to_owned)).collect(),
+        }));
+
+        out
+    }
+
+    /// Builds a landing pad which also returns the selector of the catched exception
+    /// (the index of its type in the type table or `0` for a cleanup)
+    /// ```no-run
+    /// %1, %2 = landingpad ptr catch @_ZTIi catch @_ZTIl
+    /// ```
+    pub fn BuildLandingPadWithSelector(&mut self, cleanup: bool, catches: Vec<Option<&str>>) -> (Var, Var) {
+        let block = self.blocks.back_mut().expect("the IRBuilder needs to have an current block\nConsider creating one");
+
+        let out = Var::new(block, TypeMetadata::ptr);
+        let selector = Var::new(block, TypeMetadata::i32);
+
+        block.push_ir(Box::new(LandingPad {
+            out: out.to_owned(),
+            selector: Some(selector.to_owned()),
+            cleanup: cleanup,
+            catches: catches.into_iter().map(|catch| catch.map(str::to_owned)).collect(),
+        }));
+
+        (out, selector)
+    }
+
+    /// Returns the landing pad which starts the block
+    pub(crate) fn landing_pad(&self, block: &str) -> Option<&LandingPad> {
+        let block = self.blocks.iter().find(|b| b.name == block)?;
+
+        block.nodes.iter().find(|node| !node.is_debug())?.as_any().downcast_ref::<LandingPad>()
+    }
+
+    /// Checks that the unwind blocks of invokes start with a landing pad
+    /// (and that landing pads are only used at the start of a block of a function with a personality)
+    pub(crate) fn verify_unwind(&self) -> Result<(), VerifyError> {
+        for block in &self.blocks {
+            for (index, node) in block.nodes.iter().filter(|node| !node.is_debug()).enumerate() {
+                if let Some(invoke) = node.as_any().downcast_ref::<Invoke>() {
+                    let Some(unwind) = self.blocks.iter().find(|block| block.name == invoke.unwind.name) else { continue };
+
+                    if !unwind.nodes.iter().find(|node| !node.is_debug()).is_some_and(|node| node.is_landingpad()) {
+                        Err(VerifyError::UnwindBlockWithoutLandingPad)?
+                    }
+                } else if node.is_landingpad() {
+                    if index != 0 {
+                        Err(VerifyError::LandingPadNotFirst)?
+                    }
+                } else {
+                    continue;
+                }
+
+                if self.personality.is_none() {
+                    Err(VerifyError::MissingPersonality)?
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -3,19 +3,27 @@ use std::collections::HashMap;
 use crate::Support::ColorClass;
 use crate::IR::{Function, Type, TypeMetadata, Var};
 
-use super::{EvalOptVisitor, IROperand, Ir, Load};
+use super::{EvalOptVisitor, IROperand, Ir, IsNode, Load};
 
 impl Ir for Load {
     fn dump(&self) -> String {
-        format!("{} = load {} {}", self.inner1.name, self.inner2, self.inner3)
+        format!("{} = load {}{} {}{}", 
+            self.inner1.name, 
+            if self.volatile { "volatile " } else { "" },
+            self.inner2, 
+            self.inner3,
+            if let Some(align) = self.align { format!(", align {}", align) } else { String::new() },
+        )
     }
 
     fn dumpColored(&self, profile: crate::Support::ColorProfile) -> String {
-        format!("{} = {} {} {}", 
+        format!("{} = {}{} {} {}{}", 
             profile.markup(&self.inner1.name, ColorClass::Var), 
             profile.markup("load", ColorClass::Instr), 
+            if self.volatile { format!(" {}", profile.markup("volatile", ColorClass::Instr)) } else { String::new() },
             profile.markup(&self.inner2.to_string(), ColorClass::Ty),
             profile.markup(&self.inner3.to_string(), ColorClass::Var),
+            if let Some(align) = self.align { format!(", {} {}", profile.markup("align", ColorClass::Instr), profile.markup(&align.to_string(), ColorClass::Value)) } else { String::new() },
         )
     }
 
@@ -24,6 +32,12 @@ impl Ir for Load {
     }
 
     fn verify(&self, _: crate::prelude::FunctionType) -> Result<(), crate::prelude::VerifyError> {
+        if let Some(align) = self.align {
+            if !align.is_power_of_two() {
+                Err(crate::prelude::VerifyError::InvalidAlignment(align))?
+            }
+        }
+
         Ok(())
     }
 
@@ -65,6 +79,16 @@ impl Ir for Load {
     }
 }
 
+impl IsNode for Load {
+    fn is_load(&self) -> bool {
+        true
+    }
+
+    fn is_volatile(&self) -> bool {
+        self.volatile
+    }
+}
+
 impl EvalOptVisitor for Load {
     fn maybe_inline(&self, _: &HashMap<String, Type>) -> Option<Box<dyn Ir>> {
         None
@@ -86,4 +110,19 @@ impl Function {
 
         out
     }
+
+    /// Builds a load which can be volatile and has an explicit alignment (in bytes)
+    pub fn BuildLoadWithFlags(&mut self, ptr: Var, ty: TypeMetadata, volatile: bool, align: Option<usize>) -> Var {
+        let block = self.blocks.back_mut().expect("the IRBuilder needs to have an current block\nConsider creating one");
+        
+        let out = Var::new(block, ty);
+
+        let mut node = Load::new(out.clone(), ty, IROperand::Var(ptr));
+        node.volatile = volatile;
+        node.align = align;
+
+        block.push_ir( node );
+
+        out
+    }
 }
\ No newline at end of file
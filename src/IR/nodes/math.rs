@@ -1,4 +1,13 @@
 use super::*;
+use super::cast::{cast_bits, cast_from_bits, cast_sext};
+
+/// Folds the intenger operation on the bits of the constants, so it is exact for every width
+/// (the result wraps around like at runtime)
+/// 
+/// Returns none if the operation can't be folded (e.g: a division by zero)
+fn fold_int(ls: &Type, rs: &Type, ty: TypeMetadata, op: impl Fn(u128, u128) -> Option<u128>) -> Option<Type> {
+    cast_from_bits(ty, op(cast_bits(*ls), cast_bits(*rs))?)
+}
 
 macro_rules! MathIrNode {
     ($name:ident, $compile_func:ident, $build_trait:ident, $build_func:ident, $dump:expr) => {
@@ -101,6 +110,10 @@ macro_rules! MathIrNode {
                         Err(VerifyError::Op0Op1TyNoMatch(op0Ty, op2Ty))?
                     } else { todo!("unknown error variant (debug: ty0 {} ty1 {} ty2 {})", op0Ty, op1Ty, op2Ty) }
                 }
+
+                if (self.is_rotl() || self.is_rotr()) && op0Ty.wide() {
+                    Err(VerifyError::InvalidRotate(op0Ty))?
+                }
         
                 Ok(())
             }
@@ -160,6 +173,8 @@ MathIrNode!(Div,    compile_div,  BuildDiv, BuildDiv, "div");
 MathIrNode!(Rem,    compile_rem,  BuildRem, BuildRem, "rem");
 MathIrNode!(Shl,    compile_shl,  BuildShl, BuildShl, "shl");
 MathIrNode!(Shr,    compile_shr,  BuildShr, BuildShr, "shr");
+MathIrNode!(Rotl,   compile_rotl, BuildRotl, BuildRotl, "rotl");
+MathIrNode!(Rotr,   compile_rotr, BuildRotr, BuildRotr, "rotr");
 
 impl EvalOptVisitor for Add {
     fn maybe_inline(&self, const_values: &HashMap<String, Type>) -> Option<Box<dyn Ir>> {
@@ -204,8 +219,11 @@ impl EvalOptVisitor for Add {
 
         if let IROperand::Type(ls) = &self.inner1 {
             if let IROperand::Type(rs) = &self.inner2 {
-                let calc = ls.val() + rs.val();
-                let calc = Type::from_int(self.inner3.ty, calc);
+                let calc = if self.inner3.ty.float() {
+                    Type::from_int(self.inner3.ty, ls.val() + rs.val())
+                } else {
+                    fold_int(ls, rs, self.inner3.ty, |ls, rs| Some(ls.wrapping_add(rs)))?
+                };
                 return Some(Assign::new(self.inner3.to_owned(), calc));
             }
         }
@@ -262,8 +280,11 @@ impl EvalOptVisitor for Sub {
 
         if let IROperand::Type(ls) = &self.inner1 {
             if let IROperand::Type(rs) = &self.inner2 {
-                let calc = ls.val() - rs.val();
-                let calc = Type::from_int(self.inner3.ty, calc);
+                let calc = if self.inner3.ty.float() {
+                    Type::from_int(self.inner3.ty, ls.val() - rs.val())
+                } else {
+                    fold_int(ls, rs, self.inner3.ty, |ls, rs| Some(ls.wrapping_sub(rs)))?
+                };
                 return Some(Assign::new(self.inner3.to_owned(), calc));
             }
         }
@@ -301,8 +322,7 @@ impl EvalOptVisitor for Xor {
 
         if let IROperand::Type(ls) = &self.inner1 {
             if let IROperand::Type(rs) = &self.inner2 {
-                let calc = (ls.val() as i64) ^ (rs.val() as i64);
-                let calc = Type::from_int(self.inner3.ty, calc as f64);
+                let calc = fold_int(ls, rs, self.inner3.ty, |ls, rs| Some(ls ^ rs))?;
                 return Some(Assign::new(self.inner3.to_owned(), calc));
             }
         }
@@ -337,8 +357,7 @@ impl EvalOptVisitor for Or {
     fn eval(&self) -> Option<Box<dyn Ir>> {
         if let IROperand::Type(ls) = &self.inner1 {
             if let IROperand::Type(rs) = &self.inner2 {
-                let calc = (ls.val() as i64) | (rs.val() as i64);
-                let calc = Type::from_int(self.inner3.ty, calc as f64);
+                let calc = fold_int(ls, rs, self.inner3.ty, |ls, rs| Some(ls | rs))?;
                 return Some(Assign::new(self.inner3.to_owned(), calc));
             }
         }
@@ -373,8 +392,7 @@ impl EvalOptVisitor for And {
     fn eval(&self) -> Option<Box<dyn Ir>> {
         if let IROperand::Type(ls) = &self.inner1 {
             if let IROperand::Type(rs) = &self.inner2 {
-                let calc = (ls.val() as i64) & (rs.val() as i64);
-                let calc = Type::from_int(self.inner3.ty, calc as f64);
+                let calc = fold_int(ls, rs, self.inner3.ty, |ls, rs| Some(ls & rs))?;
                 return Some(Assign::new(self.inner3.to_owned(), calc));
             }
         }
@@ -424,8 +442,11 @@ impl EvalOptVisitor for Mul {
 
         if let IROperand::Type(ls) = &self.inner1 {
             if let IROperand::Type(rs) = &self.inner2 {
-                let calc = ls.val() * rs.val();
-                let calc = Type::from_int(self.inner3.ty, calc);
+                let calc = if self.inner3.ty.float() {
+                    Type::from_int(self.inner3.ty, ls.val() * rs.val())
+                } else {
+                    fold_int(ls, rs, self.inner3.ty, |ls, rs| Some(ls.wrapping_mul(rs)))?
+                };
                 return Some(Assign::new(self.inner3.to_owned(), calc));
             }
         }
@@ -469,8 +490,15 @@ impl EvalOptVisitor for Div {
 
         if let IROperand::Type(ls) = &self.inner1 {
             if let IROperand::Type(rs) = &self.inner2 {
-                let calc = ls.val() / rs.val();
-                let calc = Type::from_int(self.inner3.ty, calc);
+                let calc = if self.inner3.ty.float() {
+                    Type::from_int(self.inner3.ty, ls.val() / rs.val())
+                } else {
+                    fold_int(ls, rs, self.inner3.ty, |ls, rs| if self.inner3.ty.signed() {
+                        Some(cast_sext(ls, self.inner3.ty).checked_div(cast_sext(rs, self.inner3.ty))? as u128)
+                    } else {
+                        ls.checked_div(rs)
+                    })?
+                };
                 return Some(Assign::new(self.inner3.to_owned(), calc));
             }
         }
@@ -514,8 +542,15 @@ impl EvalOptVisitor for Rem {
 
         if let IROperand::Type(ls) = &self.inner1 {
             if let IROperand::Type(rs) = &self.inner2 {
-                let calc = ls.val() % rs.val();
-                let calc = Type::from_int(self.inner3.ty, calc);
+                let calc = if self.inner3.ty.float() {
+                    Type::from_int(self.inner3.ty, ls.val() % rs.val())
+                } else {
+                    fold_int(ls, rs, self.inner3.ty, |ls, rs| if self.inner3.ty.signed() {
+                        Some(cast_sext(ls, self.inner3.ty).checked_rem(cast_sext(rs, self.inner3.ty))? as u128)
+                    } else {
+                        ls.checked_rem(rs)
+                    })?
+                };
                 return Some(Assign::new(self.inner3.to_owned(), calc));
             }
         }
@@ -558,8 +593,11 @@ impl EvalOptVisitor for Shl {
 
         if let IROperand::Type(ls) = &self.inner1 {
             if let IROperand::Type(rs) = &self.inner2 {
-                let calc = (ls.val() as i64) << (rs.val() as i64);
-                let calc = Type::from_int(self.inner3.ty, calc as f64);
+                // shifting by the bit width or more isn't defined, so it's left for the runtime
+                let calc = fold_int(ls, rs, self.inner3.ty, |ls, rs| {
+                    let amount = u32::try_from(rs).ok().filter(|amount| *amount < self.inner3.ty.bitSize() as u32)?;
+                    ls.checked_shl(amount)
+                })?;
                 return Some(Assign::new(self.inner3.to_owned(), calc));
             }
         }
@@ -606,11 +644,68 @@ impl EvalOptVisitor for Shr {
 
         if let IROperand::Type(ls) = &self.inner1 {
             if let IROperand::Type(rs) = &self.inner2 {
-                let calc = (ls.val() as i64) >> (rs.val() as i64);
-                let calc = Type::from_int(self.inner3.ty, calc as f64);
+                // shifting by the bit width or more isn't defined, so it's left for the runtime
+                let calc = fold_int(ls, rs, self.inner3.ty, |ls, rs| {
+                    let amount = u32::try_from(rs).ok().filter(|amount| *amount < self.inner3.ty.bitSize() as u32)?;
+
+                    if self.inner3.ty.signed() {
+                        Some((cast_sext(ls, self.inner3.ty) >> amount) as u128)
+                    } else {
+                        ls.checked_shr(amount)
+                    }
+                })?;
                 return Some(Assign::new(self.inner3.to_owned(), calc));
             }
         }
         None
     }
-}
\ No newline at end of file
+}
+macro_rules! RotateEvalOpt {
+    ($name:ident, $left:expr) => {
+        impl EvalOptVisitor for $name {
+            fn maybe_inline(&self, const_values: &HashMap<String, Type>) -> Option<Box<dyn Ir>> {
+                let mut new_ls = self.inner1.to_owned();
+                let mut new_rs = self.inner2.to_owned();
+
+                if let IROperand::Var(ls) = &self.inner1 {
+                    if let Some(ls) = const_values.get(&ls.name) {
+                        new_ls = IROperand::Type(*ls);
+                    }
+                }
+                if let IROperand::Var(rs) = &self.inner2 {
+                    if let Some(rs) = const_values.get(&rs.name) {
+                        new_rs = IROperand::Type(*rs);
+                    }
+                }
+
+                Some(Box::new($name {
+                    inner1: new_ls,
+                    inner2: new_rs,
+                    inner3: self.inner3.to_owned(),
+                }))
+            }
+
+            fn eval(&self) -> Option<Box<dyn Ir>> {
+                if let IROperand::Type(ty) = &self.inner2 {
+                    if ty.val() == 0.0 {
+                        return Some(match &self.inner1 {
+                            IROperand::Type(ty) => Assign::new(self.inner3.to_owned(), *ty),
+                            IROperand::Var(var) => Assign::new(self.inner3.to_owned(), var.to_owned()),
+                        });
+                    }
+                }
+
+                if let IROperand::Type(ls) = &self.inner1 {
+                    if let IROperand::Type(rs) = &self.inner2 {
+                        let calc = super::bit::bit_rotate(*ls, *rs, $left)?;
+                        return Some(Assign::new(self.inner3.to_owned(), calc));
+                    }
+                }
+                None
+            }
+        }
+    };
+}
+
+RotateEvalOpt!(Rotl, true);
+RotateEvalOpt!(Rotr, false);
@@ -0,0 +1,278 @@
+use crate::prelude::*;
+use super::*;
+
+/// Copies `size` bytes from `src` to `dst` (the regions are not allowed to overlap)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemCpy {
+    pub(crate) dst: Var,
+    pub(crate) src: Var,
+    pub(crate) size: IROperand,
+}
+
+/// Copies `size` bytes from `src` to `dst` (the regions are allowed to overlap)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemMove {
+    pub(crate) dst: Var,
+    pub(crate) src: Var,
+    pub(crate) size: IROperand,
+}
+
+/// Sets `size` bytes starting at `dst` to the byte `val`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemSet {
+    pub(crate) dst: Var,
+    pub(crate) val: IROperand,
+    pub(crate) size: IROperand,
+}
+
+/// Checks that the size operand of a memory intrinsic is pointer sized
+fn verify_mem_size(size: &IROperand) -> Result<(), VerifyError> {
+    let ty = size.get_ty();
+
+    if ty != TypeMetadata::i64 && ty != TypeMetadata::u64 {
+        Err(VerifyError::Op0Op1TyNoMatch(ty, TypeMetadata::i64))?
+    }
+
+    Ok(())
+}
+
+/// Inlines a constant variable into the operand
+fn inline_mem_operand(op: &IROperand, const_values: &HashMap<String, Type>) -> Option<IROperand> {
+    if let IROperand::Var(var) = op {
+        if let Some(value) = const_values.get(&var.name) {
+            return Some(IROperand::Type(*value));
+        }
+    }
+
+    None
+}
+
+macro_rules! MemTransferImpl {
+    ($name:ident, $mnemonic:expr, $compile:ident, $is_func:ident) => {
+        impl Ir for $name {
+            fn dump(&self) -> String {
+                format!("{} {}, {}, {} {}",
+                    $mnemonic,
+                    self.dst.name,
+                    self.src.name,
+                    self.size.get_ty(),
+                    self.size
+                )
+            }
+
+            fn dumpColored(&self, profile: ColorProfile) -> String {
+                format!("{} {}, {}, {} {}",
+                    profile.markup($mnemonic, ColorClass::Instr),
+                    profile.markup(&self.dst.name, ColorClass::Var),
+                    profile.markup(&self.src.name, ColorClass::Var),
+                    profile.markup(&self.size.get_ty().to_string(), ColorClass::Ty),
+                    profile.markup(&self.size.to_string(), ColorClass::Value),
+                )
+            }
+
+            fn as_any(&self) -> &dyn Any {
+                self
+            }
+
+            fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+                if self.dst.ty != TypeMetadata::ptr {
+                    Err(VerifyError::Op0Op1TyNoMatch(self.dst.ty, TypeMetadata::ptr))?
+                }
+
+                if self.src.ty != TypeMetadata::ptr {
+                    Err(VerifyError::Op0Op1TyNoMatch(self.src.ty, TypeMetadata::ptr))?
+                }
+
+                verify_mem_size(&self.size)
+            }
+
+            fn clone_box(&self) -> Box<dyn Ir> {
+                Box::new( self.clone() )
+            }
+
+            fn compile(&self, registry: &mut TargetBackendDescr, module: &mut crate::prelude::Module) {
+                registry.$compile(self, module)
+            }
+
+            fn compile_dir(&self, compiler: &mut crate::CodeGen::IrCodeGenHelper, block: &crate::prelude::Block, module: &mut crate::prelude::Module) {
+                compiler.$compile(self, block, module)
+            }
+
+            fn inputs(&self) -> Vec<Var> {
+                let mut inputs = vec![self.dst.to_owned(), self.src.to_owned()];
+
+                if let IROperand::Var(size) = &self.size { inputs.push(size.to_owned()); }
+
+                inputs
+            }
+
+            fn inputs_mut(&mut self) -> Vec<&mut Var> {
+                let mut inputs = vec![&mut self.dst, &mut self.src];
+
+                if let IROperand::Var(size) = &mut self.size { inputs.push(size); }
+
+                inputs
+            }
+
+            fn output(&self) -> Option<Var> {
+                None
+            }
+        }
+
+        impl EvalOptVisitor for $name {
+            fn maybe_inline(&self, const_values: &HashMap<String, Type>) -> Option<Box<dyn Ir>> {
+                let size = inline_mem_operand(&self.size, const_values)?;
+
+                let mut inlined = self.clone();
+                inlined.size = size;
+
+                Some(Box::new(inlined))
+            }
+
+            fn eval(&self) -> Option<Box<dyn Ir>> {
+                None
+            }
+        }
+
+        impl IsNode for $name {
+            fn $is_func(&self) -> bool {
+                true
+            }
+        }
+    };
+}
+
+MemTransferImpl!(MemCpy, "memcpy", compile_memcpy, is_memcpy);
+MemTransferImpl!(MemMove, "memmove", compile_memmove, is_memmove);
+
+impl Ir for MemSet {
+    fn dump(&self) -> String {
+        format!("memset {}, {} {}, {} {}",
+            self.dst.name,
+            self.val.get_ty(),
+            self.val,
+            self.size.get_ty(),
+            self.size
+        )
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        format!("{} {}, {} {}, {} {}",
+            profile.markup("memset", ColorClass::Instr),
+            profile.markup(&self.dst.name, ColorClass::Var),
+            profile.markup(&self.val.get_ty().to_string(), ColorClass::Ty),
+            profile.markup(&self.val.to_string(), ColorClass::Value),
+            profile.markup(&self.size.get_ty().to_string(), ColorClass::Ty),
+            profile.markup(&self.size.to_string(), ColorClass::Value),
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        if self.dst.ty != TypeMetadata::ptr {
+            Err(VerifyError::Op0Op1TyNoMatch(self.dst.ty, TypeMetadata::ptr))?
+        }
+
+        let val = self.val.get_ty();
+
+        if val != TypeMetadata::u8 && val != TypeMetadata::i8 {
+            Err(VerifyError::Op0Op1TyNoMatch(val, TypeMetadata::u8))?
+        }
+
+        verify_mem_size(&self.size)
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::new( self.clone() )
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr, module: &mut crate::prelude::Module) {
+        registry.compile_memset(self, module)
+    }
+
+    fn compile_dir(&self, compiler: &mut crate::CodeGen::IrCodeGenHelper, block: &crate::prelude::Block, module: &mut crate::prelude::Module) {
+        compiler.compile_memset(self, block, module)
+    }
+
+    fn inputs(&self) -> Vec<Var> {
+        let mut inputs = vec![self.dst.to_owned()];
+
+        if let IROperand::Var(val) = &self.val { inputs.push(val.to_owned()); }
+        if let IROperand::Var(size) = &self.size { inputs.push(size.to_owned()); }
+
+        inputs
+    }
+
+    fn inputs_mut(&mut self) -> Vec<&mut Var> {
+        let mut inputs = vec![&mut self.dst];
+
+        if let IROperand::Var(val) = &mut self.val { inputs.push(val); }
+        if let IROperand::Var(size) = &mut self.size { inputs.push(size); }
+
+        inputs
+    }
+
+    fn output(&self) -> Option<Var> {
+        None
+    }
+}
+
+impl EvalOptVisitor for MemSet {
+    fn maybe_inline(&self, const_values: &HashMap<String, Type>) -> Option<Box<dyn Ir>> {
+        let val = inline_mem_operand(&self.val, const_values);
+        let size = inline_mem_operand(&self.size, const_values);
+
+        if val.is_none() && size.is_none() {
+            return None;
+        }
+
+        let mut inlined = self.clone();
+
+        if let Some(val) = val { inlined.val = val; }
+        if let Some(size) = size { inlined.size = size; }
+
+        Some(Box::new(inlined))
+    }
+
+    fn eval(&self) -> Option<Box<dyn Ir>> {
+        None
+    }
+}
+
+impl IsNode for MemSet {
+    fn is_memset(&self) -> bool {
+        true
+    }
+}
+
+impl Function {
+    /// Builds the `memcpy` node which copies `size` bytes from `src` to `dst`
+    /// ```no-run
+    /// memcpy %0, %1, i64 16
+    /// ```
+    pub fn BuildMemCpy(&mut self, dst: Var, src: Var, size: IROperand) {
+        self.blocks.back_mut().expect("the IRBuilder needs to have an current block\nConsider creating one")
+            .push_ir(Box::new( MemCpy { dst: dst, src: src, size: size } ))
+    }
+
+    /// Builds the `memmove` node which copies `size` bytes from `src` to `dst` (the regions can overlap)
+    /// ```no-run
+    /// memmove %0, %1, i64 %2
+    /// ```
+    pub fn BuildMemMove(&mut self, dst: Var, src: Var, size: IROperand) {
+        self.blocks.back_mut().expect("the IRBuilder needs to have an current block\nConsider creating one")
+            .push_ir(Box::new( MemMove { dst: dst, src: src, size: size } ))
+    }
+
+    /// Builds the `memset` node which sets `size` bytes starting at `dst` to `val`
+    /// ```no-run
+    /// memset %0, u8 0, i64 16
+    /// ```
+    pub fn BuildMemSet(&mut self, dst: Var, val: IROperand, size: IROperand) {
+        self.blocks.back_mut().expect("the IRBuilder needs to have an current block\nConsider creating one")
+            .push_ir(Box::new( MemSet { dst: dst, val: val, size: size } ))
+    }
+}
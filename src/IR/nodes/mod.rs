@@ -1,6 +1,6 @@
 use std::{any::Any, fmt::Debug, hash::Hash};
 use std::collections::HashMap;
-use super::{Block, BlockId, Const, FuncId, Function, FunctionType, Type, TypeMetadata, Var, VerifyError};
+use super::{Block, BlockId, Const, FnAttr, FnAttrs, FuncId, Function, FunctionType, Global, Type, TypeMetadata, Var, VerifyError};
 use crate::Target::TargetBackendDescr;
 
 mod assign;
@@ -19,6 +19,18 @@ mod switch;
 mod neg;
 mod select;
 mod getelemptr;
+mod vector;
+mod unreachable;
+mod mem;
+mod atomic;
+mod bit;
+mod overflow;
+mod float;
+mod va;
+mod asm;
+mod invoke;
+mod statepoint;
+mod tuple;
 
 pub use assign::*;
 pub use call::*;
@@ -32,6 +44,18 @@ pub use debug::*;
 pub use switch::*;
 pub use select::*;
 pub use getelemptr::*;
+pub use vector::*;
+pub use unreachable::*;
+pub use mem::*;
+pub use atomic::*;
+pub use bit::*;
+pub use overflow::*;
+pub use float::*;
+pub use va::*;
+pub use asm::*;
+pub use invoke::*;
+pub use statepoint::*;
+pub use tuple::*;
 
 macro_rules! IrTypeWith3 {
     ($name:tt, $param1:tt, $param2:tt, $param3:tt, $is_func:ident) => {
@@ -166,7 +190,34 @@ impl<T, U> IsNode for Assign<T, U> where
 
 IrTypeWith1!(Return, IROperand, is_ret);
 
-IrTypeWith3!(Cast, IROperand, TypeMetadata, Var, is_cast);
+
+/// The cast node converts a value into another type
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Cast {
+    /// the value which gets casted
+    pub inner1: IROperand,
+    /// the type to cast into
+    pub inner2: TypeMetadata,
+    /// the output variable
+    pub inner3: Var,
+    /// how the value gets converted
+    pub kind: CastKind,
+}
+
+impl Cast {
+    /// Creates a new instance
+    #[allow(dead_code)]
+    pub fn new(kind: CastKind, value: IROperand, ty: TypeMetadata, out: Var) -> Box<Self> {
+        Box::from(
+            Self {
+                inner1: value,
+                inner2: ty,
+                inner3: out,
+                kind: kind,
+            }
+        )
+    }
+}
 
 IrTypeWith3!(Add, IROperand, IROperand, Var, is_add);
 IrTypeWith3!(Sub, IROperand, IROperand, Var, is_sub);
@@ -178,16 +229,156 @@ IrTypeWith3!(Div, IROperand, IROperand, Var, is_div);
 IrTypeWith3!(Rem, IROperand, IROperand, Var, is_rem);
 IrTypeWith3!(Shl, IROperand, IROperand, Var, is_shl);
 IrTypeWith3!(Shr, IROperand, IROperand, Var, is_shr);
+IrTypeWith3!(Rotl, IROperand, IROperand, Var, is_rotl);
+IrTypeWith3!(Rotr, IROperand, IROperand, Var, is_rotr);
 
 IrTypeWith1!(Br, BlockId, is_br);
 IrTypeWith3!(BrCond, Var, BlockId, BlockId, is_brcond);
 
 IrTypeWith2!(Alloca, Var, TypeMetadata, is_alloca);
-IrTypeWith2!(Store, Var, IROperand, is_store);
-IrTypeWith3!(Load, Var, TypeMetadata, IROperand, is_load);
+
+/// The store node stores a value into memory
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Store {
+    /// the pointer to store into
+    pub inner1: Var,
+    /// the value which gets stored
+    pub inner2: IROperand,
+    /// volatile stores are never removed or merged
+    pub volatile: bool,
+    /// the explicit alignment of the pointer (in bytes)
+    pub align: Option<usize>,
+}
+
+impl Store {
+    /// Creates a new instance
+    #[allow(dead_code)]
+    pub fn new(ptr: Var, value: IROperand) -> Box<Self> {
+        Box::from(
+            Self {
+                inner1: ptr,
+                inner2: value,
+                volatile: false,
+                align: None,
+            }
+        )
+    }
+}
+
+/// The load node loads a value from memory
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Load {
+    /// the output variable
+    pub inner1: Var,
+    /// the type which gets loaded
+    pub inner2: TypeMetadata,
+    /// the pointer to load from
+    pub inner3: IROperand,
+    /// volatile loads are never removed or folded
+    pub volatile: bool,
+    /// the explicit alignment of the pointer (in bytes)
+    pub align: Option<usize>,
+}
+
+impl Load {
+    /// Creates a new instance
+    #[allow(dead_code)]
+    pub fn new(out: Var, ty: TypeMetadata, ptr: IROperand) -> Box<Self> {
+        Box::from(
+            Self {
+                inner1: out,
+                inner2: ty,
+                inner3: ptr,
+                volatile: false,
+                align: None,
+            }
+        )
+    }
+}
 
 IrTypeWith2!(Neg, IROperand, Var, is_neg);
 
+/// The bit node manipulates the bits of an intenger (e.g: counts the set bits)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitOp {
+    /// the value which gets manipulated
+    pub inner1: IROperand,
+    /// the output variable
+    pub inner2: Var,
+    /// how the bits get manipulated
+    pub kind: BitOpKind,
+}
+
+impl BitOp {
+    /// Creates a new instance
+    #[allow(dead_code)]
+    pub fn new(kind: BitOpKind, value: IROperand, out: Var) -> Box<Self> {
+        Box::from(
+            Self {
+                inner1: value,
+                inner2: out,
+                kind: kind,
+            }
+        )
+    }
+}
+
+/// The overflow node does intenger arithmetic and also returns if the result overflowed
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OverflowOp {
+    /// the left operand
+    pub inner1: IROperand,
+    /// the right operand
+    pub inner2: IROperand,
+    /// the output variable for the (wrapped) result
+    pub inner3: Var,
+    /// the output variable for the overflow flag (an i1)
+    pub overflow: Var,
+    /// which arithmetic is done
+    pub kind: OverflowKind,
+}
+
+impl OverflowOp {
+    /// Creates a new instance
+    #[allow(dead_code)]
+    pub fn new(kind: OverflowKind, ls: IROperand, rs: IROperand, out: Var, overflow: Var) -> Box<Self> {
+        Box::from(
+            Self {
+                inner1: ls,
+                inner2: rs,
+                inner3: out,
+                overflow: overflow,
+                kind: kind,
+            }
+        )
+    }
+}
+
+/// The float node computes a math function on floats (e.g: a square root)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FloatOp {
+    /// the operands (two for `copysign`, `minnum` and `maxnum`, three for `fma`)
+    pub args: Vec<IROperand>,
+    /// the output variable
+    pub out: Var,
+    /// which function is computed
+    pub kind: FloatOpKind,
+}
+
+impl FloatOp {
+    /// Creates a new instance
+    #[allow(dead_code)]
+    pub fn new(kind: FloatOpKind, args: Vec<IROperand>, out: Var) -> Box<Self> {
+        Box::from(
+            Self {
+                args: args,
+                out: out,
+                kind: kind,
+            }
+        )
+    }
+}
+
 /// The cmp node is used to compare values
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Cmp {
@@ -253,6 +444,18 @@ pub struct Call {
     pub(crate) out: Var,
     pub(crate) func: FuncId,
     pub(crate) args: Vec<IROperand>,
+    pub(crate) attrs: FnAttrs,
+    /// the call is compiled into a jump (`tail call`)
+    pub(crate) tail: bool,
+}
+
+/// Calls the function the pointer points to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallIndirect {
+    pub(crate) out: Var,
+    pub(crate) ptr: Var,
+    pub(crate) ty: FunctionType,
+    pub(crate) args: Vec<IROperand>,
 }
 
 /// checks if the node is another node
@@ -260,14 +463,27 @@ pub struct Call {
 pub trait IsNode {
     fn is_alloca(&self) -> bool { false }
     fn is_assign(&self) -> bool { false }
+    fn is_atomicload(&self) -> bool { false }
+    fn is_atomicrmw(&self) -> bool { false }
+    fn is_atomicstore(&self) -> bool { false }
+    fn is_bitop(&self) -> bool { false }
     fn is_cast(&self) -> bool { false }
     fn is_br(&self) -> bool { false }
     fn is_brcond(&self) -> bool { false }
     fn is_call(&self) -> bool { false }
+    fn is_callindirect(&self) -> bool { false }
     fn is_cmp(&self) -> bool { false }
+    fn is_cmpxchg(&self) -> bool { false }
     fn is_debug(&self) -> bool { false }
+    fn is_extractelement(&self) -> bool { false }
+    fn is_fence(&self) -> bool { false }
+    fn is_floatop(&self) -> bool { false }
     fn is_getelemptr(&self) -> bool { false }
+    fn is_insertelement(&self) -> bool { false }
     fn is_load(&self) -> bool { false }
+    fn is_memcpy(&self) -> bool { false }
+    fn is_memmove(&self) -> bool { false }
+    fn is_memset(&self) -> bool { false }
     fn is_add(&self) -> bool { false }
     fn is_sub(&self) -> bool { false }
     fn is_xor(&self) -> bool { false }
@@ -278,12 +494,52 @@ pub trait IsNode {
     fn is_rem(&self) -> bool { false }
     fn is_shl(&self) -> bool { false }
     fn is_shr(&self) -> bool { false }
+    fn is_rotl(&self) -> bool { false }
+    fn is_rotr(&self) -> bool { false }
+    fn is_shufflevector(&self) -> bool { false }
     fn is_neg(&self) -> bool { false }
+    fn is_overflowop(&self) -> bool { false }
     fn is_phi(&self) -> bool { false }
     fn is_ret(&self) -> bool { false }
     fn is_select(&self) -> bool { false }
     fn is_store(&self) -> bool { false }
     fn is_switch(&self) -> bool { false }
+    fn is_trap(&self) -> bool { false }
+    fn is_unreachable(&self) -> bool { false }
+    fn is_vaarg(&self) -> bool { false }
+    fn is_vacopy(&self) -> bool { false }
+    fn is_vaend(&self) -> bool { false }
+    fn is_vastart(&self) -> bool { false }
+    fn is_asm(&self) -> bool { false }
+    fn is_invoke(&self) -> bool { false }
+    fn is_landingpad(&self) -> bool { false }
+    fn is_statepoint(&self) -> bool { false }
+    fn is_tuple(&self) -> bool { false }
+    fn is_extractvalue(&self) -> bool { false }
+
+    /// Returns if the node is a volatile memory access (which is never removed or folded)
+    fn is_volatile(&self) -> bool { false }
+
+    /// Returns if the node ends a block
+    fn is_terminator(&self) -> bool {
+        self.is_ret() || self.is_br() || self.is_brcond() || self.is_switch() || self.is_unreachable() || self.is_trap() || self.is_invoke()
+    }
+
+    /// Returns if the node has side effects (so it can't be removed even if its output is unused)
+    fn has_side_effects(&self) -> bool {
+        self.is_call() || self.is_callindirect() || self.is_store() || self.is_memcpy() || self.is_memmove() || self.is_memset() || self.is_atomic() || self.is_volatile() || self.is_va() || self.is_asm() || self.is_invoke() || self.is_landingpad() || self.is_statepoint()
+    }
+
+    /// Returns if the node is an atomic operation or a fence
+    /// (memory accesses are not allowed to be moved across it and it can't be removed)
+    fn is_atomic(&self) -> bool {
+        self.is_atomicload() || self.is_atomicstore() || self.is_atomicrmw() || self.is_cmpxchg() || self.is_fence()
+    }
+
+    /// Returns if the node uses a `va_list` (they all modify the list, so they can't be removed)
+    fn is_va(&self) -> bool {
+        self.is_vastart() || self.is_vaarg() || self.is_vaend() || self.is_vacopy()
+    }
 }
 
 use crate::Support::{AsAny, ColorClass, ColorProfile};
@@ -328,6 +584,11 @@ pub trait Ir: Debug + Any + EvalOptVisitor + IsNode {
 
     /// returns the output var
     fn output(&self) -> Option<Var>;
+
+    /// returns all output vars (nodes like the overflow node have more than one)
+    fn outputs(&self) -> Vec<Var> {
+        self.output().into_iter().collect()
+    }
 }
 
 /// A trait used for constant propagination
@@ -337,6 +598,11 @@ pub trait EvalOptVisitor {
 
     /// evaluteas the node
     fn eval(&self) -> Option<Box<dyn Ir>>;
+
+    /// evaluates the node into multiple nodes (used for nodes with more than one output)
+    fn eval_split(&self) -> Option<Vec<Box<dyn Ir>>> {
+        None
+    }
 }
 
 impl PartialEq for Box<dyn Ir> {
@@ -419,15 +685,19 @@ impl std::fmt::Display for IROperand {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", match self {
             IROperand::Type(ty) => match ty {
+                Type::i1(b) => format!("{}", *b as u8),
+
                 Type::u8(i) => format!("{i}"),
                 Type::u16(i) => format!("{i}"),
                 Type::u32(i) => format!("{i}"),
                 Type::u64(i) => format!("{i}"),
+                Type::u128(i) => format!("{i}"),
     
                 Type::i8(i) => format!("{i}"),
                 Type::i16(i) => format!("{i}"),
                 Type::i32(i) => format!("{i}"),
                 Type::i64(i) => format!("{i}"),
+                Type::i128(i) => format!("{i}"),
     
                 Type::ptr(adr) => format!("{adr:#04x}"),
                 Type::Void => format!("0"),
@@ -0,0 +1,252 @@
+use std::fmt::Display;
+
+use super::*;
+use super::cast::{cast_bits, cast_from_bits, cast_int, cast_sext};
+
+/// Which arithmetic an overflow node does and if the operands are treated as signed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OverflowKind {
+    /// Signed addition
+    SAdd,
+    /// Unsigned addition
+    UAdd,
+    /// Signed subtraction
+    SSub,
+    /// Unsigned subtraction
+    USub,
+    /// Signed multiplication
+    SMul,
+    /// Unsigned multiplication
+    UMul,
+}
+
+impl OverflowKind {
+    /// Parses the kind out of its ir name (e.g: `sadd_overflow`)
+    pub fn parse(string: &str) -> Option<Self> {
+        match string {
+            "sadd_overflow" => Some(OverflowKind::SAdd),
+            "uadd_overflow" => Some(OverflowKind::UAdd),
+            "ssub_overflow" => Some(OverflowKind::SSub),
+            "usub_overflow" => Some(OverflowKind::USub),
+            "smul_overflow" => Some(OverflowKind::SMul),
+            "umul_overflow" => Some(OverflowKind::UMul),
+            _ => None,
+        }
+    }
+
+    /// Returns if the operands are treated as signed intengers
+    pub fn signed(&self) -> bool {
+        matches!(self, OverflowKind::SAdd | OverflowKind::SSub | OverflowKind::SMul)
+    }
+
+    /// Returns if the arithmetic can be done on values of the type `ty`
+    pub fn legal(&self, ty: TypeMetadata) -> bool {
+        cast_int(ty) && ty != TypeMetadata::i1
+    }
+
+    /// Does the arithmetic on the constants and returns the (wrapped) result and the overflow flag
+    pub(crate) fn fold(&self, ls: Type, rs: Type) -> Option<(Type, Type)> {
+        let ty: TypeMetadata = ls.into();
+        let width = ty.bitSize() as u32;
+
+        let (result, overflow) = if self.signed() {
+            let ls = cast_sext(cast_bits(ls), ty);
+            let rs = cast_sext(cast_bits(rs), ty);
+
+            let (result, overflow) = match self {
+                OverflowKind::SAdd => ls.overflowing_add(rs),
+                OverflowKind::SSub => ls.overflowing_sub(rs),
+                _ => ls.overflowing_mul(rs),
+            };
+
+            // the result needs to be representable in the width of the type
+            (result as u128, overflow || cast_sext(result as u128, ty) != result)
+        } else {
+            let ls = cast_bits(ls);
+            let rs = cast_bits(rs);
+
+            let (result, overflow) = match self {
+                OverflowKind::UAdd => ls.overflowing_add(rs),
+                OverflowKind::USub => ls.overflowing_sub(rs),
+                _ => ls.overflowing_mul(rs),
+            };
+
+            (result, overflow || (width < 128 && (result >> width) != 0))
+        };
+
+        Some((cast_from_bits(ty, result)?, Type::i1(overflow)))
+    }
+}
+
+impl Display for OverflowKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", match self {
+            OverflowKind::SAdd => "sadd_overflow",
+            OverflowKind::UAdd => "uadd_overflow",
+            OverflowKind::SSub => "ssub_overflow",
+            OverflowKind::USub => "usub_overflow",
+            OverflowKind::SMul => "smul_overflow",
+            OverflowKind::UMul => "umul_overflow",
+        })
+    }
+}
+
+impl Ir for OverflowOp {
+    fn dump(&self) -> String {
+        format!("{}, {} = {} {} {}, {}", self.inner3.name, self.overflow.name, self.kind, self.inner3.ty, self.inner1, self.inner2)
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        format!("{}, {} = {} {} {}, {}",
+            profile.markup(&self.inner3.name, ColorClass::Var),
+            profile.markup(&self.overflow.name, ColorClass::Var),
+            profile.markup(&self.kind.to_string(), ColorClass::Instr),
+            profile.markup(&self.inner3.ty.to_string(), ColorClass::Ty),
+            profile.markup(&self.inner1.to_string(), ColorClass::Value),
+            profile.markup(&self.inner2.to_string(), ColorClass::Value),
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        let ty = self.inner3.ty;
+
+        if self.inner1.get_ty() != ty {
+            Err(VerifyError::Op0Op1TyNoMatch(self.inner1.get_ty(), ty))?
+        }
+
+        if self.inner2.get_ty() != ty {
+            Err(VerifyError::Op0Op1TyNoMatch(self.inner2.get_ty(), ty))?
+        }
+
+        if self.overflow.ty != TypeMetadata::i1 {
+            Err(VerifyError::Op0Op1TyNoMatch(self.overflow.ty, TypeMetadata::i1))?
+        }
+
+        if !self.kind.legal(ty) {
+            Err(VerifyError::InvalidOverflowOp(self.kind, ty))?
+        }
+
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::new( self.clone() )
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr, module: &mut crate::prelude::Module) {
+        registry.compile_overflowop(self, module)
+    }
+
+    fn compile_dir(&self, compiler: &mut crate::CodeGen::IrCodeGenHelper, block: &crate::prelude::Block, module: &mut crate::prelude::Module) {
+        compiler.compile_overflowop(self, block, module)
+    }
+
+    fn uses(&self, var: &Var) -> bool {
+        *var == self.inner3 || *var == self.overflow
+    }
+
+    fn inputs(&self) -> Vec<Var> {
+        let mut inputs = Vec::new();
+        if let IROperand::Var(ls) = &self.inner1 { inputs.push(ls.to_owned()); }
+        if let IROperand::Var(rs) = &self.inner2 { inputs.push(rs.to_owned()); }
+        inputs
+    }
+
+    fn inputs_mut(&mut self) -> Vec<&mut Var> {
+        let mut inputs = Vec::new();
+        if let IROperand::Var(ls) = &mut self.inner1 { inputs.push(ls); }
+        if let IROperand::Var(rs) = &mut self.inner2 { inputs.push(rs); }
+        inputs
+    }
+
+    fn output(&self) -> Option<Var> {
+        Some(self.inner3.to_owned())
+    }
+
+    fn outputs(&self) -> Vec<Var> {
+        vec![self.inner3.to_owned(), self.overflow.to_owned()]
+    }
+}
+
+impl IsNode for OverflowOp {
+    fn is_overflowop(&self) -> bool {
+        true
+    }
+}
+
+impl EvalOptVisitor for OverflowOp {
+    fn maybe_inline(&self, const_values: &HashMap<String, Type>) -> Option<Box<dyn Ir>> {
+        let mut inlined = self.clone();
+        let mut changed = false;
+
+        if let IROperand::Var(var) = &self.inner1 {
+            if let Some(value) = const_values.get(&var.name) {
+                inlined.inner1 = IROperand::Type(*value);
+                changed = true;
+            }
+        }
+
+        if let IROperand::Var(var) = &self.inner2 {
+            if let Some(value) = const_values.get(&var.name) {
+                inlined.inner2 = IROperand::Type(*value);
+                changed = true;
+            }
+        }
+
+        if changed { Some(Box::new(inlined)) } else { None }
+    }
+
+    fn eval(&self) -> Option<Box<dyn Ir>> {
+        None
+    }
+
+    fn eval_split(&self) -> Option<Vec<Box<dyn Ir>>> {
+        if let (IROperand::Type(ls), IROperand::Type(rs)) = (&self.inner1, &self.inner2) {
+            if self.kind.legal(self.inner3.ty) {
+                if let Some((result, overflow)) = self.kind.fold(*ls, *rs) {
+                    return Some(vec![
+                        Assign::new(self.inner3.to_owned(), result),
+                        Assign::new(self.overflow.to_owned(), overflow),
+                    ]);
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Trait for the overflow checked arithmetic nodes
+/// Used for overloading the BuildOverflowOp function
+pub trait BuildOverflowOp<T, U> {
+    /// does the arithmetic on the values and returns the result and the overflow flag (an i1)
+    fn BuildOverflowOp(&mut self, kind: OverflowKind, ls: T, rs: U) -> (Var, Var);
+}
+
+macro_rules! BuildOverflowOpImpl {
+    ($ls:ty, $ls_variant:ident, $rs:ty, $rs_variant:ident, $ty:expr) => {
+        impl BuildOverflowOp<$ls, $rs> for Function {
+            fn BuildOverflowOp(&mut self, kind: OverflowKind, ls: $ls, rs: $rs) -> (Var, Var) {
+                let block = self.blocks.back_mut().expect("the IRBuilder needs to have an current block\nConsider creating one");
+
+                let ty: TypeMetadata = $ty(&ls);
+
+                let out = Var::new(block, ty);
+                let overflow = Var::new(block, TypeMetadata::i1);
+
+                block.push_ir(OverflowOp::new(kind, IROperand::$ls_variant(ls), IROperand::$rs_variant(rs), out.clone(), overflow.clone()));
+
+                (out, overflow)
+            }
+        }
+    };
+}
+
+BuildOverflowOpImpl!(Var, Var, Var, Var, |ls: &Var| ls.ty);
+BuildOverflowOpImpl!(Var, Var, Type, Type, |ls: &Var| ls.ty);
+BuildOverflowOpImpl!(Type, Type, Var, Var, |ls: &Type| (*ls).into());
+BuildOverflowOpImpl!(Type, Type, Type, Type, |ls: &Type| (*ls).into());
@@ -48,7 +48,8 @@ impl Ir for Return {
     }
     
     fn inputs_mut(&mut self) -> Vec<&mut Var> {
-        vec![]
+        if let IROperand::Var(ret) = &mut self.inner1 { vec![ret] }
+        else { vec![] }
     }
     
     fn output(&self) -> Option<Var> {
@@ -35,6 +35,10 @@ impl Ir for Select {
     }
 
     fn verify(&self, _: crate::prelude::FunctionType) -> Result<(), crate::prelude::VerifyError> {
+        if self.cond.ty != TypeMetadata::i1 {
+            Err(crate::prelude::VerifyError::ConditionNotI1(self.cond.ty))?
+        }
+
         Ok(())
     }
 
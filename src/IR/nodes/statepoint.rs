@@ -0,0 +1,221 @@
+use crate::IR::func::FuncId;
+
+use super::*;
+
+/// Calls the function at a safepoint of the garbage collector
+///
+/// The `gc` pointers are the live gc references. Where they are stored while the
+/// called function runs is recorded in the stack map of the object file (using the `id`),
+/// so the garbage collector can find (and update) them while walking the stack
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Statepoint {
+    pub(crate) out: Var,
+    pub(crate) id: u64,
+    pub(crate) func: FuncId,
+    pub(crate) args: Vec<IROperand>,
+    pub(crate) gc: Vec<Var>,
+}
+
+impl Statepoint {
+    /// Returns the id of the stack map record
+    pub fn getId(&self) -> u64 {
+        self.id
+    }
+
+    /// Returns the called function
+    pub fn getCallTarget(&self) -> FuncId {
+        self.func.to_owned()
+    }
+
+    /// Returns the arguments of the call
+    pub fn getArgs(&self) -> Vec<IROperand> {
+        self.args.to_owned()
+    }
+
+    /// Returns the live gc references
+    pub fn getGcRoots(&self) -> Vec<Var> {
+        self.gc.to_owned()
+    }
+
+    /// Returns the output variable
+    pub fn getOutputVar(&self) -> Var {
+        self.out.to_owned()
+    }
+
+    /// The label which marks the return adress of the call
+    pub(crate) fn label(&self) -> String {
+        format!(".statepoint_{}_{}", self.out.name.trim_start_matches('%'), self.id)
+    }
+}
+
+impl Ir for Statepoint {
+    fn dump(&self) -> String {
+        let mut fmt = String::new();
+
+        for arg in &self.args {
+            fmt.push_str(&format!("{} {} ", arg.get_ty(), arg))
+        }
+
+        let gc = self.gc.iter().map(|root| root.name.to_owned()).collect::<Vec<_>>().join(", ");
+
+        let conv = if let Some(conv) = self.func.ty.call_conv { format!("{} ", conv) } else { String::new() };
+
+        format!("{} = statepoint {} {}{} {} {}gc [{}]", self.out.name, self.id, conv, self.func.ty.ret, self.func.name, fmt, gc)
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        let mut fmt = String::new();
+
+        for arg in &self.args {
+            fmt.push_str(&profile.markup(&arg.get_ty().to_string(), ColorClass::Ty));
+            fmt.push(' ');
+            fmt.push_str(&profile.markup(&arg.to_string(), ColorClass::Var));
+            fmt.push(' ');
+        }
+
+        let gc = self.gc.iter().map(|root| profile.markup(&root.name, ColorClass::Var)).collect::<Vec<_>>().join(", ");
+
+        let conv = if let Some(conv) = self.func.ty.call_conv { format!("{} ", profile.markup(&conv.to_string(), ColorClass::Instr)) } else { String::new() };
+
+        format!("{} = {} {} {}{} {} {}{} [{}]",
+            profile.markup(&self.out.name, ColorClass::Var),
+            profile.markup("statepoint", ColorClass::Instr),
+            profile.markup(&self.id.to_string(), ColorClass::Value),
+            conv,
+            profile.markup(&self.func.ty.ret.to_string(), ColorClass::Ty),
+            profile.markup(&self.func.name, ColorClass::Name),
+            fmt,
+            profile.markup("gc", ColorClass::Instr),
+            gc,
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        if self.out.ty != self.func.ty.ret {
+            Err(VerifyError::Op0Op1TyNoMatch(self.out.ty, self.func.ty.ret))?
+        }
+
+        let args = &self.func.ty.args;
+        for (index, arg) in self.args.iter().enumerate() {
+            if let Some((_, argty)) = args.get(index) {
+                if *argty != arg.get_ty() {
+                    Err(VerifyError::InvalidArgumentTypeFound)?
+                }
+            } else if !self.func.ty.any_args {
+                Err(VerifyError::ToManyArgumentsWereSupplyed)?
+            }
+        }
+
+        for root in &self.gc {
+            if root.ty != TypeMetadata::ptr {
+                Err(VerifyError::GcRootNotPtr(root.ty))?
+            }
+        }
+
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::from( self.clone() )
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr, module: &mut crate::prelude::Module) {
+        registry.compile_statepoint(&self, module)
+    }
+
+    fn uses(&self, var: &Var) -> bool {
+        self.inputs().iter().any(|input| input.name == var.name)
+    }
+
+    fn compile_dir(&self, compiler: &mut crate::CodeGen::IrCodeGenHelper, block: &crate::prelude::Block, module: &mut crate::prelude::Module) {
+        compiler.compile_statepoint(&self, &block, module)
+    }
+
+    fn inputs(&self) -> Vec<Var> {
+        let mut inputs = Vec::new();
+
+        for arg in &self.args {
+            if let IROperand::Var(arg) = &arg {
+                inputs.push(arg.to_owned());
+            }
+        }
+
+        inputs.extend_from_slice(&self.gc);
+
+        inputs
+    }
+
+    fn inputs_mut(&mut self) -> Vec<&mut Var> {
+        let mut inputs = Vec::new();
+
+        for arg in &mut self.args {
+            if let IROperand::Var(arg) = arg {
+                inputs.push(arg);
+            }
+        }
+
+        inputs.extend(self.gc.iter_mut());
+
+        inputs
+    }
+
+    fn output(&self) -> Option<Var> {
+        Some(self.out.to_owned())
+    }
+}
+
+impl EvalOptVisitor for Statepoint {
+    fn maybe_inline(&self, const_values: &HashMap<String, Type>) -> Option<Box<dyn Ir>> {
+        // the gc roots need to stay variables (the garbage collector could move them)
+        let mut inlined = self.clone();
+        let mut changed = false;
+
+        for arg in &mut inlined.args {
+            if let IROperand::Var(var) = arg {
+                if let Some(value) = const_values.get(&var.name) {
+                    *arg = IROperand::Type(*value);
+                    changed = true;
+                }
+            }
+        }
+
+        if changed { Some(Box::new(inlined)) } else { None }
+    }
+
+    fn eval(&self) -> Option<Box<dyn Ir>> {
+        None
+    }
+}
+
+impl IsNode for Statepoint {
+    fn is_statepoint(&self) -> bool {
+        true
+    }
+}
+
+impl Function {
+    /// Builds a call to the function at a safepoint of the garbage collector.
+    /// `gc` are the live gc references whose locations are recorded in the stack map record `id`
+    /// ```no-run
+    /// %2 = statepoint 1 i32 func i32 %0 gc [%1]
+    /// ```
+    pub fn BuildStatepoint(&mut self, id: u64, func: &FuncId, args: Vec<IROperand>, gc: Vec<Var>) -> Var {
+        let block = self.blocks.back_mut().expect("the IRBuilder needs to have an current block\nConsider creating one");
+
+        let out = Var::new(block, func.ty.ret);
+
+        block.push_ir(Box::new(Statepoint {
+            out: out.to_owned(),
+            id: id,
+            func: func.clone(),
+            args: args,
+            gc: gc,
+        }));
+
+        out
+    }
+}
@@ -3,19 +3,27 @@ use std::collections::HashMap;
 use crate::IR::{Function, Type, Var};
 use crate::Support::ColorClass;
 
-use super::{EvalOptVisitor, IROperand, Ir, Store};
+use super::{EvalOptVisitor, IROperand, Ir, IsNode, Store};
 
 impl Ir for Store {
     fn dump(&self) -> String {
-        format!("store {} {}, {}", self.inner2.get_ty(), self.inner2, self.inner1.name)
+        format!("store {}{} {}, {}{}", 
+            if self.volatile { "volatile " } else { "" },
+            self.inner2.get_ty(), 
+            self.inner2, 
+            self.inner1.name,
+            if let Some(align) = self.align { format!(", align {}", align) } else { String::new() },
+        )
     }
 
     fn dumpColored(&self, profile: crate::Support::ColorProfile) -> String {
-        format!("{} {} {} {}",
+        format!("{}{} {} {}, {}{}",
             profile.markup("store", ColorClass::Instr),
+            if self.volatile { format!(" {}", profile.markup("volatile", ColorClass::Instr)) } else { String::new() },
             profile.markup(&self.inner2.get_ty().to_string(), ColorClass::Ty),
             profile.markup(&self.inner2.to_string(), ColorClass::Var),
             profile.markup(&self.inner1.name, ColorClass::Var),
+            if let Some(align) = self.align { format!(", {} {}", profile.markup("align", ColorClass::Instr), profile.markup(&align.to_string(), ColorClass::Value)) } else { String::new() },
         )
     }
 
@@ -24,6 +32,12 @@ impl Ir for Store {
     }
 
     fn verify(&self, _: crate::prelude::FunctionType) -> Result<(), crate::prelude::VerifyError> {
+        if let Some(align) = self.align {
+            if !align.is_power_of_two() {
+                Err(crate::prelude::VerifyError::InvalidAlignment(align))?
+            }
+        }
+
         Ok(())
     }
 
@@ -78,11 +92,24 @@ impl Ir for Store {
     }
 }
 
+impl IsNode for Store {
+    fn is_store(&self) -> bool {
+        true
+    }
+
+    fn is_volatile(&self) -> bool {
+        self.volatile
+    }
+}
+
 impl EvalOptVisitor for Store {
     fn maybe_inline(&self, const_values: &HashMap<String, Type>) -> Option<Box<dyn Ir>> {
         if let IROperand::Var(value) = &self.inner2 {
             if let Some(constant) = const_values.get(&value.name) {
-                return Some( Store::new(self.inner1.to_owned(), IROperand::Type(*constant)) );
+                let mut inlined = self.clone();
+                inlined.inner2 = IROperand::Type(*constant);
+
+                return Some( Box::new(inlined) );
             } 
         }
         
@@ -95,6 +122,19 @@ impl EvalOptVisitor for Store {
     
 }
 
+impl Function {
+    /// Builds a store which can be volatile and has an explicit alignment (in bytes)
+    pub fn BuildStoreWithFlags(&mut self, target: Var, value: IROperand, volatile: bool, align: Option<usize>) {
+        let block = self.blocks.back_mut().expect("the IRBuilder needs to have an current block\nConsider creating one");
+
+        let mut node = Store::new(target, value);
+        node.volatile = volatile;
+        node.align = align;
+
+        block.push_ir( node );
+    }
+}
+
 /// The `BuildStore` trait is used for overloading the `BuildStore` method
 pub trait BuildStore<T, U> {
     /// the `store` node, stores a value into a allocted pointer
@@ -5,6 +5,9 @@ use crate::{Support::ColorClass, IR::{BlockId, Function, Type, TypeMetadata, Var
 use super::{Br, EvalOptVisitor, Ir, IsNode};
 
 /// The switch node is used to switch
+/// 
+/// The switched value can be an intenger of any size (it isn't a condition, so unlike
+/// the ones of `br cond` and `select` it doesn't need to be an `i1`)
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Switch {
     pub(crate) to_switch: Var,
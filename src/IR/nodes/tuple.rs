@@ -0,0 +1,285 @@
+use crate::prelude::*;
+use super::*;
+
+/// builds a tuple out of the values (so they can be returned together)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MakeTuple {
+    pub(crate) out: Var,
+    pub(crate) elems: Vec<IROperand>,
+}
+
+/// extracts a value out of a tuple (e.g: one of the return values of a call)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtractValue {
+    pub(crate) out: Var,
+    pub(crate) tuple: Var,
+    pub(crate) index: usize,
+}
+
+impl MakeTuple {
+    /// Returns the values of the tuple
+    pub fn getElems(&self) -> Vec<IROperand> {
+        self.elems.to_owned()
+    }
+
+    /// Returns the output variable
+    pub fn getOutputVar(&self) -> Var {
+        self.out.to_owned()
+    }
+}
+
+impl ExtractValue {
+    /// Returns the tuple
+    pub fn getTuple(&self) -> Var {
+        self.tuple.to_owned()
+    }
+
+    /// Returns the index of the extracted value
+    pub fn getIndex(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the output variable
+    pub fn getOutputVar(&self) -> Var {
+        self.out.to_owned()
+    }
+}
+
+impl Ir for MakeTuple {
+    fn dump(&self) -> String {
+        let elems = self.elems.iter()
+            .map(|elem| format!("{} {}", elem.get_ty(), elem))
+            .collect::<Vec<String>>();
+
+        format!("{} = tuple {}", self.out.name, elems.join(", "))
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        let elems = self.elems.iter()
+            .map(|elem| format!("{} {}",
+                profile.markup(&elem.get_ty().to_string(), ColorClass::Ty),
+                profile.markup(&elem.to_string(), ColorClass::Value)
+            ))
+            .collect::<Vec<String>>();
+
+        format!("{} = {} {}",
+            profile.markup(&self.out.name, ColorClass::Var),
+            profile.markup("tuple", ColorClass::Instr),
+            elems.join(", "),
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        let tys = self.elems.iter().map(|elem| elem.get_ty()).collect::<Vec<_>>();
+
+        for ty in &tys {
+            // every value needs to fit into a single register
+            if ty.aggregate() || ty.vector() || ty.wide() || ty.tuple() || *ty == TypeMetadata::Void {
+                Err(VerifyError::InvalidTupleElem(*ty))?
+            }
+        }
+
+        let ty = TypeMetadata::newTuple(&tys);
+
+        if ty != self.out.ty {
+            Err(VerifyError::Op0Op1TyNoMatch(ty, self.out.ty))?
+        }
+
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::new( self.clone() )
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr, module: &mut crate::prelude::Module) {
+        registry.compile_tuple(self, module)
+    }
+
+    fn compile_dir(&self, compiler: &mut crate::CodeGen::IrCodeGenHelper, block: &crate::prelude::Block, module: &mut crate::prelude::Module) {
+        compiler.compile_tuple(self, block, module)
+    }
+
+    fn inputs(&self) -> Vec<Var> {
+        let mut inputs = Vec::new();
+
+        for elem in &self.elems {
+            if let IROperand::Var(var) = elem {
+                inputs.push(var.to_owned());
+            }
+        }
+
+        inputs
+    }
+
+    fn inputs_mut(&mut self) -> Vec<&mut Var> {
+        let mut inputs = Vec::new();
+
+        for elem in &mut self.elems {
+            if let IROperand::Var(var) = elem {
+                inputs.push(var);
+            }
+        }
+
+        inputs
+    }
+
+    fn output(&self) -> Option<Var> {
+        Some(self.out.to_owned())
+    }
+}
+
+impl Ir for ExtractValue {
+    fn dump(&self) -> String {
+        format!("{} = extractvalue {} {}, {}",
+            self.out.name,
+            self.tuple.ty,
+            self.tuple.name,
+            self.index
+        )
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        format!("{} = {} {} {}, {}",
+            profile.markup(&self.out.name, ColorClass::Var),
+            profile.markup("extractvalue", ColorClass::Instr),
+            profile.markup(&self.tuple.ty.to_string(), ColorClass::Ty),
+            profile.markup(&self.tuple.name, ColorClass::Var),
+            profile.markup(&self.index.to_string(), ColorClass::Value),
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        let Some(elems) = self.tuple.ty.tupleElems() else {
+            Err(VerifyError::Op0Op1TyNoMatch(self.tuple.ty, self.out.ty))?
+        };
+
+        let Some(elem) = elems.get(self.index) else {
+            Err(VerifyError::IndexOutOfBounds(self.tuple.ty, self.index))?
+        };
+
+        if *elem != self.out.ty {
+            Err(VerifyError::Op0Op1TyNoMatch(*elem, self.out.ty))?
+        }
+
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::new( self.clone() )
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr, module: &mut crate::prelude::Module) {
+        registry.compile_extractvalue(self, module)
+    }
+
+    fn compile_dir(&self, compiler: &mut crate::CodeGen::IrCodeGenHelper, block: &crate::prelude::Block, module: &mut crate::prelude::Module) {
+        compiler.compile_extractvalue(self, block, module)
+    }
+
+    fn inputs(&self) -> Vec<Var> {
+        vec![self.tuple.to_owned()]
+    }
+
+    fn inputs_mut(&mut self) -> Vec<&mut Var> {
+        vec![&mut self.tuple]
+    }
+
+    fn output(&self) -> Option<Var> {
+        Some(self.out.to_owned())
+    }
+}
+
+impl EvalOptVisitor for MakeTuple {
+    fn maybe_inline(&self, const_values: &HashMap<String, Type>) -> Option<Box<dyn Ir>> {
+        let mut inlined = self.clone();
+        let mut changed = false;
+
+        for elem in &mut inlined.elems {
+            if let IROperand::Var(var) = elem {
+                if let Some(value) = const_values.get(&var.name) {
+                    *elem = IROperand::Type(*value);
+                    changed = true;
+                }
+            }
+        }
+
+        if changed { Some(Box::new(inlined)) } else { None }
+    }
+
+    fn eval(&self) -> Option<Box<dyn Ir>> {
+        None
+    }
+}
+
+impl EvalOptVisitor for ExtractValue {
+    fn maybe_inline(&self, _: &HashMap<String, Type>) -> Option<Box<dyn Ir>> {
+        None
+    }
+
+    fn eval(&self) -> Option<Box<dyn Ir>> {
+        None
+    }
+}
+
+impl IsNode for MakeTuple {
+    fn is_tuple(&self) -> bool {
+        true
+    }
+}
+
+impl IsNode for ExtractValue {
+    fn is_extractvalue(&self) -> bool {
+        true
+    }
+}
+
+impl Function {
+    /// Builds the `tuple` node which combines the values (e.g: to return them together)
+    /// ```no-run
+    /// %2 = tuple i64 %0, i32 %1
+    /// ret (i64, i32) %2
+    /// ```
+    pub fn BuildTuple(&mut self, elems: Vec<IROperand>) -> Var {
+        let block = self.blocks.back_mut().expect("expected current block");
+
+        let tys = elems.iter().map(|elem| elem.get_ty()).collect::<Vec<_>>();
+
+        let out = Var::new(block, TypeMetadata::newTuple(&tys));
+
+        block.push_ir(Box::new(MakeTuple {
+            out: out.clone(),
+            elems: elems,
+        }));
+
+        out
+    }
+
+    /// Builds the `extractvalue` node which reads the value at the index out of the tuple
+    /// ```no-run
+    /// %1 = extractvalue (i64, i32) %0, 1
+    /// ```
+    pub fn BuildExtractValue(&mut self, tuple: Var, index: usize) -> Var {
+        let block = self.blocks.back_mut().expect("expected current block");
+
+        let elems = tuple.ty.tupleElems().expect("extractvalue expects a tuple");
+
+        let out = Var::new(block, *elems.get(index).expect("the index is out of the bounds of the tuple"));
+
+        block.push_ir(Box::new(ExtractValue {
+            out: out.clone(),
+            tuple: tuple,
+            index: index,
+        }));
+
+        out
+    }
+}
@@ -0,0 +1,147 @@
+use crate::prelude::*;
+use super::*;
+
+/// Marks that the control flow never reaches this point (e.g: after a call to `abort`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unreachable {}
+
+/// Aborts the execution of the program (e.g: after a failed bounds check)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trap {}
+
+impl Ir for Unreachable {
+    fn dump(&self) -> String {
+        "unreachable".to_string()
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        profile.markup("unreachable", ColorClass::Instr)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::new( self.clone() )
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr, module: &mut crate::prelude::Module) {
+        registry.compile_unreachable(self, module)
+    }
+
+    fn compile_dir(&self, compiler: &mut crate::CodeGen::IrCodeGenHelper, block: &crate::prelude::Block, module: &mut crate::prelude::Module) {
+        compiler.compile_unreachable(self, block, module)
+    }
+
+    fn inputs(&self) -> Vec<Var> {
+        vec![]
+    }
+
+    fn inputs_mut(&mut self) -> Vec<&mut Var> {
+        vec![]
+    }
+
+    fn output(&self) -> Option<Var> {
+        None
+    }
+}
+
+impl Ir for Trap {
+    fn dump(&self) -> String {
+        "trap".to_string()
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        profile.markup("trap", ColorClass::Instr)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::new( self.clone() )
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr, module: &mut crate::prelude::Module) {
+        registry.compile_trap(self, module)
+    }
+
+    fn compile_dir(&self, compiler: &mut crate::CodeGen::IrCodeGenHelper, block: &crate::prelude::Block, module: &mut crate::prelude::Module) {
+        compiler.compile_trap(self, block, module)
+    }
+
+    fn inputs(&self) -> Vec<Var> {
+        vec![]
+    }
+
+    fn inputs_mut(&mut self) -> Vec<&mut Var> {
+        vec![]
+    }
+
+    fn output(&self) -> Option<Var> {
+        None
+    }
+}
+
+impl EvalOptVisitor for Unreachable {
+    fn maybe_inline(&self, _: &HashMap<String, Type>) -> Option<Box<dyn Ir>> {
+        None
+    }
+
+    fn eval(&self) -> Option<Box<dyn Ir>> {
+        None
+    }
+}
+
+impl EvalOptVisitor for Trap {
+    fn maybe_inline(&self, _: &HashMap<String, Type>) -> Option<Box<dyn Ir>> {
+        None
+    }
+
+    fn eval(&self) -> Option<Box<dyn Ir>> {
+        None
+    }
+}
+
+impl IsNode for Unreachable {
+    fn is_unreachable(&self) -> bool {
+        true
+    }
+}
+
+impl IsNode for Trap {
+    fn is_trap(&self) -> bool {
+        true
+    }
+}
+
+impl Function {
+    /// Builds the `unreachable` node which marks that the control flow never reaches this point
+    /// ```no-run
+    /// call void abort
+    /// unreachable
+    /// ```
+    pub fn BuildUnreachable(&mut self) {
+        self.blocks.back_mut().expect("the IRBuilder needs to have an current block\nConsider creating one")
+            .push_ir(Box::new( Unreachable {} ))
+    }
+
+    /// Builds the `trap` node which aborts the execution of the program
+    /// ```no-run
+    /// trap
+    /// ```
+    pub fn BuildTrap(&mut self) {
+        self.blocks.back_mut().expect("the IRBuilder needs to have an current block\nConsider creating one")
+            .push_ir(Box::new( Trap {} ))
+    }
+}
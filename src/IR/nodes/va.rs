@@ -0,0 +1,295 @@
+use crate::prelude::*;
+use super::*;
+
+// The nodes take a pointer to the `va_list` which needs to be allocated by the function.
+// It needs 24 bytes on system v (e.g: `alloca [3 x i64]`) and 8 bytes on windows
+
+/// Initializes the `va_list` so that it points to the first variadic argument
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VaStart {
+    pub(crate) list: Var,
+}
+
+/// Reads the next variadic argument as a value of the type of `out` and advances the `va_list`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VaArg {
+    pub(crate) out: Var,
+    pub(crate) list: Var,
+}
+
+/// Ends the use of the `va_list`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VaEnd {
+    pub(crate) list: Var,
+}
+
+/// Copies the state of the `va_list` `src` into `dst`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VaCopy {
+    pub(crate) dst: Var,
+    pub(crate) src: Var,
+}
+
+fn verify_va_list(list: &Var) -> Result<(), VerifyError> {
+    if list.ty != TypeMetadata::ptr {
+        Err(VerifyError::Op0Op1TyNoMatch(list.ty, TypeMetadata::ptr))?
+    }
+
+    Ok(())
+}
+
+macro_rules! VaListImpl {
+    ($name:ident, $mnemonic:expr, $compile:ident, $is_func:ident, $variadic_only:expr) => {
+        impl Ir for $name {
+            fn dump(&self) -> String {
+                format!("{} {}", $mnemonic, self.list.name)
+            }
+
+            fn dumpColored(&self, profile: ColorProfile) -> String {
+                format!("{} {}",
+                    profile.markup($mnemonic, ColorClass::Instr),
+                    profile.markup(&self.list.name, ColorClass::Var),
+                )
+            }
+
+            fn as_any(&self) -> &dyn Any {
+                self
+            }
+
+            fn verify(&self, func: FunctionType) -> Result<(), VerifyError> {
+                if $variadic_only && !func.any_args {
+                    Err(VerifyError::VaStartInNonVariadic)?
+                }
+
+                verify_va_list(&self.list)
+            }
+
+            fn clone_box(&self) -> Box<dyn Ir> {
+                Box::new( self.clone() )
+            }
+
+            fn compile(&self, registry: &mut TargetBackendDescr, module: &mut crate::prelude::Module) {
+                registry.$compile(self, module)
+            }
+
+            fn compile_dir(&self, compiler: &mut crate::CodeGen::IrCodeGenHelper, block: &crate::prelude::Block, module: &mut crate::prelude::Module) {
+                compiler.$compile(self, block, module)
+            }
+
+            fn inputs(&self) -> Vec<Var> {
+                vec![self.list.to_owned()]
+            }
+
+            fn inputs_mut(&mut self) -> Vec<&mut Var> {
+                vec![&mut self.list]
+            }
+
+            fn output(&self) -> Option<Var> {
+                None
+            }
+        }
+
+        impl EvalOptVisitor for $name {
+            fn maybe_inline(&self, _: &HashMap<String, Type>) -> Option<Box<dyn Ir>> {
+                None
+            }
+
+            fn eval(&self) -> Option<Box<dyn Ir>> {
+                None
+            }
+        }
+
+        impl IsNode for $name {
+            fn $is_func(&self) -> bool {
+                true
+            }
+        }
+    };
+}
+
+// only variadic functions can start a va_list (but every function can end one)
+VaListImpl!(VaStart, "va_start", compile_va_start, is_vastart, true);
+VaListImpl!(VaEnd, "va_end", compile_va_end, is_vaend, false);
+
+impl VaArg {
+    /// The label of the given branch of the va_arg (if the target needs one)
+    pub(crate) fn label(&self, what: &str) -> String {
+        format!(".va_arg_{}_{}", self.out.name.trim_start_matches('%'), what)
+    }
+}
+
+impl Ir for VaArg {
+    fn dump(&self) -> String {
+        format!("{} = va_arg {} {}", self.out.name, self.out.ty, self.list.name)
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        format!("{} = {} {} {}",
+            profile.markup(&self.out.name, ColorClass::Var),
+            profile.markup("va_arg", ColorClass::Instr),
+            profile.markup(&self.out.ty.to_string(), ColorClass::Ty),
+            profile.markup(&self.list.name, ColorClass::Var),
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        let ty = self.out.ty;
+
+        // variadic arguments are passed in a single register (or stack slot)
+        if ty.aggregate() || ty.wide() || ty.vector() || ty == TypeMetadata::Void {
+            Err(VerifyError::InvalidVaArgType(ty))?
+        }
+
+        verify_va_list(&self.list)
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::new( self.clone() )
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr, module: &mut crate::prelude::Module) {
+        registry.compile_va_arg(self, module)
+    }
+
+    fn compile_dir(&self, compiler: &mut crate::CodeGen::IrCodeGenHelper, block: &crate::prelude::Block, module: &mut crate::prelude::Module) {
+        compiler.compile_va_arg(self, block, module)
+    }
+
+    fn inputs(&self) -> Vec<Var> {
+        vec![self.list.to_owned()]
+    }
+
+    fn inputs_mut(&mut self) -> Vec<&mut Var> {
+        vec![&mut self.list]
+    }
+
+    fn output(&self) -> Option<Var> {
+        Some(self.out.to_owned())
+    }
+}
+
+impl EvalOptVisitor for VaArg {
+    fn maybe_inline(&self, _: &HashMap<String, Type>) -> Option<Box<dyn Ir>> {
+        None
+    }
+
+    fn eval(&self) -> Option<Box<dyn Ir>> {
+        None
+    }
+}
+
+impl IsNode for VaArg {
+    fn is_vaarg(&self) -> bool {
+        true
+    }
+}
+
+impl Ir for VaCopy {
+    fn dump(&self) -> String {
+        format!("va_copy {}, {}", self.dst.name, self.src.name)
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        format!("{} {}, {}",
+            profile.markup("va_copy", ColorClass::Instr),
+            profile.markup(&self.dst.name, ColorClass::Var),
+            profile.markup(&self.src.name, ColorClass::Var),
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        verify_va_list(&self.dst)?;
+        verify_va_list(&self.src)
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::new( self.clone() )
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr, module: &mut crate::prelude::Module) {
+        registry.compile_va_copy(self, module)
+    }
+
+    fn compile_dir(&self, compiler: &mut crate::CodeGen::IrCodeGenHelper, block: &crate::prelude::Block, module: &mut crate::prelude::Module) {
+        compiler.compile_va_copy(self, block, module)
+    }
+
+    fn inputs(&self) -> Vec<Var> {
+        vec![self.dst.to_owned(), self.src.to_owned()]
+    }
+
+    fn inputs_mut(&mut self) -> Vec<&mut Var> {
+        vec![&mut self.dst, &mut self.src]
+    }
+
+    fn output(&self) -> Option<Var> {
+        None
+    }
+}
+
+impl EvalOptVisitor for VaCopy {
+    fn maybe_inline(&self, _: &HashMap<String, Type>) -> Option<Box<dyn Ir>> {
+        None
+    }
+
+    fn eval(&self) -> Option<Box<dyn Ir>> {
+        None
+    }
+}
+
+impl IsNode for VaCopy {
+    fn is_vacopy(&self) -> bool {
+        true
+    }
+}
+
+impl Function {
+    /// Builds the `va_start` node which initializes the `va_list` behind `list`
+    /// ```no-run
+    /// va_start %0
+    /// ```
+    pub fn BuildVaStart(&mut self, list: Var) {
+        self.blocks.back_mut().expect("the IRBuilder needs to have an current block\nConsider creating one")
+            .push_ir(Box::new( VaStart { list: list } ))
+    }
+
+    /// Builds the `va_arg` node which reads the next variadic argument as a value of the type `ty`
+    /// ```no-run
+    /// %1 = va_arg i32 %0
+    /// ```
+    pub fn BuildVaArg(&mut self, list: Var, ty: TypeMetadata) -> Var {
+        let block = self.blocks.back_mut().expect("the IRBuilder needs to have an current block\nConsider creating one");
+
+        let out = Var::new(block, ty);
+
+        block.push_ir(Box::new( VaArg { out: out.to_owned(), list: list } ));
+
+        out
+    }
+
+    /// Builds the `va_end` node which ends the use of the `va_list` behind `list`
+    /// ```no-run
+    /// va_end %0
+    /// ```
+    pub fn BuildVaEnd(&mut self, list: Var) {
+        self.blocks.back_mut().expect("the IRBuilder needs to have an current block\nConsider creating one")
+            .push_ir(Box::new( VaEnd { list: list } ))
+    }
+
+    /// Builds the `va_copy` node which copies the `va_list` behind `src` into `dst`
+    /// ```no-run
+    /// va_copy %1, %0
+    /// ```
+    pub fn BuildVaCopy(&mut self, dst: Var, src: Var) {
+        self.blocks.back_mut().expect("the IRBuilder needs to have an current block\nConsider creating one")
+            .push_ir(Box::new( VaCopy { dst: dst, src: src } ))
+    }
+}
@@ -0,0 +1,400 @@
+use crate::prelude::*;
+use super::*;
+use std::fmt::Debug;
+
+/// extracts an element out of a vector
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtractElement {
+    pub(crate) out: Var,
+    pub(crate) vec: Var,
+    pub(crate) index: usize,
+}
+
+/// inserts an element into a copy of a vector
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InsertElement {
+    pub(crate) out: Var,
+    pub(crate) vec: Var,
+    pub(crate) elem: IROperand,
+    pub(crate) index: usize,
+}
+
+/// builds a new vector out of the elements of two vectors.
+///
+/// The mask entry `i` selects the element `i` of the first vector
+/// if `i < len` else the element `i - len` of the second vector
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShuffleVector {
+    pub(crate) out: Var,
+    pub(crate) ls: Var,
+    pub(crate) rs: Var,
+    pub(crate) mask: Vec<usize>,
+}
+
+impl Ir for ExtractElement {
+    fn dump(&self) -> String {
+        format!("{} = extractelement {} {}, {}",
+            self.out.name,
+            self.vec.ty,
+            self.vec.name,
+            self.index
+        )
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        format!("{} = {} {} {}, {}",
+            profile.markup(&self.out.name, ColorClass::Var),
+            profile.markup("extractelement", ColorClass::Instr),
+            profile.markup(&self.vec.ty.to_string(), ColorClass::Ty),
+            profile.markup(&self.vec.name, ColorClass::Var),
+            profile.markup(&self.index.to_string(), ColorClass::Value),
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        let Some((elem, len)) = self.vec.ty.vectorElems() else {
+            Err(VerifyError::Op0Op1TyNoMatch(self.vec.ty, self.out.ty))?
+        };
+
+        if elem != self.out.ty {
+            Err(VerifyError::Op0Op1TyNoMatch(elem, self.out.ty))?
+        }
+
+        if self.index >= len {
+            Err(VerifyError::IndexOutOfBounds(self.vec.ty, self.index))?
+        }
+
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::new( self.clone() )
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr, module: &mut crate::prelude::Module) {
+        registry.compile_extractelement(self, module)
+    }
+
+    fn compile_dir(&self, compiler: &mut crate::CodeGen::IrCodeGenHelper, block: &crate::prelude::Block, module: &mut crate::prelude::Module) {
+        compiler.compile_extractelement(self, block, module)
+    }
+
+    fn inputs(&self) -> Vec<Var> {
+        vec![self.vec.to_owned()]
+    }
+
+    fn inputs_mut(&mut self) -> Vec<&mut Var> {
+        vec![&mut self.vec]
+    }
+
+    fn output(&self) -> Option<Var> {
+        Some(self.out.to_owned())
+    }
+}
+
+impl Ir for InsertElement {
+    fn dump(&self) -> String {
+        format!("{} = insertelement {} {}, {} {}, {}",
+            self.out.name,
+            self.vec.ty,
+            self.vec.name,
+            self.elem.get_ty(),
+            self.elem,
+            self.index
+        )
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        format!("{} = {} {} {}, {} {}, {}",
+            profile.markup(&self.out.name, ColorClass::Var),
+            profile.markup("insertelement", ColorClass::Instr),
+            profile.markup(&self.vec.ty.to_string(), ColorClass::Ty),
+            profile.markup(&self.vec.name, ColorClass::Var),
+            profile.markup(&self.elem.get_ty().to_string(), ColorClass::Ty),
+            profile.markup(&self.elem.to_string(), ColorClass::Value),
+            profile.markup(&self.index.to_string(), ColorClass::Value),
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        let Some((elem, len)) = self.vec.ty.vectorElems() else {
+            Err(VerifyError::Op0Op1TyNoMatch(self.vec.ty, self.elem.get_ty()))?
+        };
+
+        if elem != self.elem.get_ty() {
+            Err(VerifyError::Op0Op1TyNoMatch(elem, self.elem.get_ty()))?
+        }
+
+        if self.index >= len {
+            Err(VerifyError::IndexOutOfBounds(self.vec.ty, self.index))?
+        }
+
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::new( self.clone() )
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr, module: &mut crate::prelude::Module) {
+        registry.compile_insertelement(self, module)
+    }
+
+    fn compile_dir(&self, compiler: &mut crate::CodeGen::IrCodeGenHelper, block: &crate::prelude::Block, module: &mut crate::prelude::Module) {
+        compiler.compile_insertelement(self, block, module)
+    }
+
+    fn inputs(&self) -> Vec<Var> {
+        let mut inputs = vec![self.vec.to_owned()];
+
+        if let IROperand::Var(elem) = &self.elem { inputs.push(elem.to_owned()); }
+
+        inputs
+    }
+
+    fn inputs_mut(&mut self) -> Vec<&mut Var> {
+        let mut inputs = vec![&mut self.vec];
+
+        if let IROperand::Var(elem) = &mut self.elem { inputs.push(elem); }
+
+        inputs
+    }
+
+    fn output(&self) -> Option<Var> {
+        Some(self.out.to_owned())
+    }
+}
+
+impl Ir for ShuffleVector {
+    fn dump(&self) -> String {
+        let mask = self.mask.iter()
+            .map(|index| index.to_string())
+            .collect::<Vec<String>>();
+
+        format!("{} = shufflevector {} {}, {}, [{}]",
+            self.out.name,
+            self.ls.ty,
+            self.ls.name,
+            self.rs.name,
+            mask.join(", ")
+        )
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        let mask = self.mask.iter()
+            .map(|index| profile.markup(&index.to_string(), ColorClass::Value))
+            .collect::<Vec<String>>();
+
+        format!("{} = {} {} {}, {}, [{}]",
+            profile.markup(&self.out.name, ColorClass::Var),
+            profile.markup("shufflevector", ColorClass::Instr),
+            profile.markup(&self.ls.ty.to_string(), ColorClass::Ty),
+            profile.markup(&self.ls.name, ColorClass::Var),
+            profile.markup(&self.rs.name, ColorClass::Var),
+            mask.join(", ")
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        if self.ls.ty != self.rs.ty {
+            Err(VerifyError::Op0Op1TyNoMatch(self.ls.ty, self.rs.ty))?
+        }
+
+        let Some((elem, len)) = self.ls.ty.vectorElems() else {
+            Err(VerifyError::Op0Op1TyNoMatch(self.ls.ty, self.out.ty))?
+        };
+
+        // the output has the element type of the inputs and one element for each index of the mask
+        let out_ty = TypeMetadata::newVector(elem, self.mask.len());
+
+        if self.out.ty != out_ty {
+            Err(VerifyError::Op0Op1TyNoMatch(out_ty, self.out.ty))?
+        }
+
+        if let Some(index) = self.mask.iter().find(|index| **index >= len * 2) {
+            Err(VerifyError::IndexOutOfBounds(self.ls.ty, *index))?
+        }
+
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::new( self.clone() )
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr, module: &mut crate::prelude::Module) {
+        registry.compile_shufflevector(self, module)
+    }
+
+    fn compile_dir(&self, compiler: &mut crate::CodeGen::IrCodeGenHelper, block: &crate::prelude::Block, module: &mut crate::prelude::Module) {
+        compiler.compile_shufflevector(self, block, module)
+    }
+
+    fn inputs(&self) -> Vec<Var> {
+        vec![self.ls.to_owned(), self.rs.to_owned()]
+    }
+
+    fn inputs_mut(&mut self) -> Vec<&mut Var> {
+        vec![&mut self.ls, &mut self.rs]
+    }
+
+    fn output(&self) -> Option<Var> {
+        Some(self.out.to_owned())
+    }
+}
+
+impl EvalOptVisitor for ExtractElement {
+    fn maybe_inline(&self, _: &HashMap<String, Type>) -> Option<Box<dyn Ir>> {
+        None
+    }
+
+    fn eval(&self) -> Option<Box<dyn Ir>> {
+        None
+    }
+}
+
+impl EvalOptVisitor for InsertElement {
+    fn maybe_inline(&self, const_values: &HashMap<String, Type>) -> Option<Box<dyn Ir>> {
+        if let IROperand::Var(elem) = &self.elem {
+            if let Some(elem) = const_values.get(&elem.name) {
+                let mut inlined = self.clone();
+                inlined.elem = IROperand::Type(*elem);
+
+                return Some(Box::new(inlined));
+            }
+        }
+
+        None
+    }
+
+    fn eval(&self) -> Option<Box<dyn Ir>> {
+        None
+    }
+}
+
+impl EvalOptVisitor for ShuffleVector {
+    fn maybe_inline(&self, _: &HashMap<String, Type>) -> Option<Box<dyn Ir>> {
+        None
+    }
+
+    fn eval(&self) -> Option<Box<dyn Ir>> {
+        None
+    }
+}
+
+impl IsNode for ExtractElement {
+    fn is_extractelement(&self) -> bool {
+        true
+    }
+}
+
+impl IsNode for InsertElement {
+    fn is_insertelement(&self) -> bool {
+        true
+    }
+}
+
+impl IsNode for ShuffleVector {
+    fn is_shufflevector(&self) -> bool {
+        true
+    }
+}
+
+impl Function {
+    /// Builds the `extractelement` node which reads the element at the index out of the vector
+    /// ```no-run
+    /// %1 = extractelement <4 x f32> %0, 2
+    /// ```
+    pub fn BuildExtractElement(&mut self, vec: Var, index: usize) -> Var {
+        let block = self.blocks.back_mut().expect("expected current block");
+
+        let (elem, _) = vec.ty.vectorElems().expect("extractelement expects a vector");
+
+        let out = Var::new(block, elem);
+
+        block.push_ir(Box::new(ExtractElement {
+            out: out.clone(),
+            vec: vec,
+            index: index,
+        }));
+
+        out
+    }
+
+    /// Builds the `shufflevector` node which builds a new vector out of the elements of `ls` and `rs`
+    /// ```no-run
+    /// %2 = shufflevector <4 x i32> %0, %1, [0, 5, 2, 7]
+    /// ```
+    pub fn BuildShuffleVector(&mut self, ls: Var, rs: Var, mask: &[usize]) -> Var {
+        let block = self.blocks.back_mut().expect("expected current block");
+
+        let (elem, _) = ls.ty.vectorElems().expect("shufflevector expects vectors");
+
+        let out = Var::new(block, TypeMetadata::newVector(elem, mask.len()));
+
+        block.push_ir(Box::new(ShuffleVector {
+            out: out.clone(),
+            ls: ls,
+            rs: rs,
+            mask: mask.to_vec(),
+        }));
+
+        out
+    }
+}
+
+/// This trait is used to build the insertelement node
+pub trait BuildInsertElement<T> {
+    /// Builds the `insertelement` node which returns a copy of the vector
+    /// with the element at the index replaced
+    /// ```no-run
+    /// %1 = insertelement <4 x f32> %0, f32 1.5, 3
+    /// ```
+    fn BuildInsertElement(&mut self, vec: Var, elem: T, index: usize) -> Var;
+}
+
+impl BuildInsertElement<Type> for Function {
+    fn BuildInsertElement(&mut self, vec: Var, elem: Type, index: usize) -> Var {
+        let block = self.blocks.back_mut().expect("expected current block");
+
+        let out = Var::new(block, vec.ty);
+
+        block.push_ir(Box::new(InsertElement {
+            out: out.clone(),
+            vec: vec,
+            elem: IROperand::Type(elem),
+            index: index,
+        }));
+
+        out
+    }
+}
+
+impl BuildInsertElement<Var> for Function {
+    fn BuildInsertElement(&mut self, vec: Var, elem: Var, index: usize) -> Var {
+        let block = self.blocks.back_mut().expect("expected current block");
+
+        let out = Var::new(block, vec.ty);
+
+        block.push_ir(Box::new(InsertElement {
+            out: out.clone(),
+            vec: vec,
+            elem: IROperand::Var(elem),
+            index: index,
+        }));
+
+        out
+    }
+}
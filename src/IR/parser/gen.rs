@@ -1,6 +1,6 @@
 use std::collections::{BTreeMap, VecDeque};
 
-use crate::{Obj::Linkage, IR::{Block, Const, Function, FunctionType, Module, TypeMetadata}};
+use crate::{Obj::Linkage, Target::CallConv, IR::{Block, Const, ConstAdr, FnAttrs, Function, FunctionType, Global, Metadata, MetadataNode, Module, TypeMetadata}};
 
 use super::parser::{IrBlock, IrInstr, IrStmt};
 
@@ -12,6 +12,8 @@ pub struct IrGen {
 
     funcs: Vec<Function>,
     consts: Vec<Const>,
+    globals: Vec<Global>,
+    metadata: Metadata,
 }
 
 impl IrGen {
@@ -22,21 +24,31 @@ impl IrGen {
 
             funcs: vec![],
             consts: vec![],
+            globals: vec![],
+            metadata: Metadata::new(),
         }
     }
 
-    fn gen_func(&mut self, name: String, ret: TypeMetadata, args:  (BTreeMap<String, TypeMetadata>, bool), body: Vec<(String, IrBlock)>, scope: Linkage) {
+    fn gen_func(&mut self, name: String, ret: TypeMetadata, args:  (BTreeMap<String, TypeMetadata>, bool), body: Vec<(String, IrBlock)>, scope: Linkage, attrs: FnAttrs, call_conv: Option<CallConv>, personality: Option<String>, metadata: Metadata) {
         let mut ty = FunctionType::new(Vec::new(), ret);
+        ty.call_conv = call_conv;
 
         for (name, arg) in &args.0 {
             ty.args.push( (name.to_owned(), *arg) );
         }
 
+        if args.1 {
+            ty.activate_dynamic_arguments();
+        }
+
         let mut raw = Function {
             ty: ty,
             name: name,
             linkage: scope,
             blocks: VecDeque::new(),
+            attrs: attrs,
+            personality: personality,
+            metadata: metadata,
         };
 
         for (name, block) in body {
@@ -44,6 +56,7 @@ impl IrGen {
                 name: name.to_owned(),
                 nodes: vec![],
                 varCount: 0,
+                metadata: BTreeMap::new(),
             };
 
             for node in block.body {
@@ -60,28 +73,50 @@ impl IrGen {
         block.nodes.push( 
             node.inst
          );
+
+        for metadata in node.metadata.iter() {
+            block.attach(block.nodes.len() - 1, metadata.to_owned());
+        }
     }
 
     /// lowers the incoming statemants into ygen ir
     pub fn gen(&mut self) {
         for stmt in self.input.clone() {
             match stmt {
-                IrStmt::Func { name, ret, args, body, scope, location: _ } => self.gen_func(name, ret, args, body, scope),
-                IrStmt::Const { name, data, location: _, scope } => self.gen_const(name, data, scope),
+                IrStmt::Func { name, ret, args, body, scope, attrs, call_conv, personality, metadata, location: _ } => self.gen_func(name, ret, args, body, scope, attrs, call_conv, personality, metadata),
+                IrStmt::Const { name, data, adrs, location: _, scope } => self.gen_const(name, data, adrs, scope),
+                IrStmt::Global { name, ty, data, location: _, scope } => self.gen_global(name, ty, data, scope),
+                IrStmt::Metadata { node, location: _ } => self.gen_metadata(node),
             }
         }
     }
 
-    fn gen_const(&mut self, name: String, data: Vec<u8>, scope: Linkage) {
+    fn gen_const(&mut self, name: String, data: Vec<u8>, adrs: Vec<ConstAdr>, scope: Linkage) {
         let raw = Const {
             name: name,
             data: data,
             linkage: scope,
+            adrs: adrs,
         };
 
         self.consts.push( raw );
     }
 
+    fn gen_global(&mut self, name: String, ty: TypeMetadata, data: Option<Vec<u8>>, scope: Linkage) {
+        let raw = Global {
+            name: name,
+            ty: ty,
+            init: data,
+            linkage: scope,
+        };
+
+        self.globals.push( raw );
+    }
+
+    fn gen_metadata(&mut self, node: MetadataNode) {
+        self.metadata.add(node);
+    }
+
     /// emits the generated functions, constants, .. into a usable module
     pub fn module(&self) -> Module {
         let mut module = Module();
@@ -94,6 +129,14 @@ impl IrGen {
             module.add_raw_const( constant.to_owned() );
         }
 
+        for global in &self.globals {
+            module.add_raw_global( global.to_owned() );
+        }
+
+        for node in self.metadata.iter() {
+            module.add_metadata( node.to_owned() );
+        }
+
         module
     }
 }
\ No newline at end of file
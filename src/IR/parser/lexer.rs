@@ -28,9 +28,15 @@ pub enum TokenType {
     /// const
     Const,
 
+    /// global
+    Global,
+
     /// ,
     Comma,
 
+    /// +
+    Plus,
+
     /// %name
     Var(String),
 
@@ -55,6 +61,12 @@ pub enum TokenType {
     /// ]
     RSquare,
 
+    /// <
+    LAngle,
+
+    /// >
+    RAngle,
+
     /// a-zA-Z.
     Ident(String),
 
@@ -108,7 +120,9 @@ impl TokenType {
         match self {
             TokenType::Dot => ":",
             TokenType::Const => "const",
+            TokenType::Global => "global",
             TokenType::Comma => ",",
+            TokenType::Plus => "+",
             TokenType::Var(_) => "var",
             TokenType::Equal => "=",
             TokenType::LParam => "(",
@@ -117,6 +131,8 @@ impl TokenType {
             TokenType::RBracket => "}",
             TokenType::LSquare => "[",
             TokenType::RSquare => "]",
+            TokenType::LAngle => "<",
+            TokenType::RAngle => ">",
             TokenType::Ident(_) => "ident",
             TokenType::String(_) => "string",
             TokenType::Int(_) => "int",
@@ -174,6 +190,7 @@ impl IrLexer {
         keys.insert("declare".into(), TokenType::Declare);
         keys.insert("define".into(), TokenType::Define);
         keys.insert("const".into(), TokenType::Const);
+        keys.insert("global".into(), TokenType::Global);
         keys.insert("cond".into(), TokenType::Cond);
 
         let input = format!("{}\n", input);
@@ -297,13 +314,16 @@ impl IrLexer {
             '(' => ty = Some(TokenType::LParam),
             '{' => ty = Some(TokenType::LBracket),
             '[' => ty = Some(TokenType::LSquare),
+            '<' => ty = Some(TokenType::LAngle),
 
             ')' => ty = Some(TokenType::RParam),
             '}' => ty = Some(TokenType::RBracket),
             ']' => ty = Some(TokenType::RSquare),
+            '>' => ty = Some(TokenType::RAngle),
 
             ':' => ty = Some(TokenType::Dot),
             ',' => ty = Some(TokenType::Comma),
+            '+' => ty = Some(TokenType::Plus),
 
             '=' => ty = Some(TokenType::Equal),
 
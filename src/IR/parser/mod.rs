@@ -112,6 +112,14 @@ pub enum IrError {
         loc: Loc,
     },
 
+    /// an imported global has an initializer
+    ExternGlobalWithInit {
+        /// the name of the global
+        name: String,
+        /// the location
+        loc: Loc,
+    },
+
     /// found the wrong argument
     WrongArgument {
         /// where
@@ -297,6 +305,17 @@ impl Display for IrError {
 
                 fab.to_string() 
             }
+
+            IrError::ExternGlobalWithInit { name, loc } => {
+                let mut fab = Support::Error::new("imported global has an initializer", "", "", "");
+
+                fab.deactivateLocationDisplay();
+
+                fab.setCodeLine(loc.line_string.to_owned());
+                fab.addWhere(format!("the global {name} is imported and has an initializer which isn't allowed"), loc.coloumn, loc.length);
+
+                fab.to_string() 
+            }
         
             IrError::WrongArgument { loc, index, expected, found } => {
                 let mut fab = Support::Error::new("found the wrong argument", "", "", "");
@@ -1,10 +1,11 @@
 use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::path::PathBuf;
 
-use crate::prelude::{Alloca, Cmp, CmpMode, DebugNode, GetElemPtr, IROperand, Ir, Load, Neg, Phi, Select, Store, Switch};
+use crate::prelude::{Alloca, Cmp, CmpMode, DebugNode, ExtractElement, ExtractValue, GetElemPtr, IROperand, InsertElement, Ir, Load, MakeTuple, Neg, Phi, Select, ShuffleVector, Store, Switch};
 use crate::Obj::Linkage;
+use crate::Target::CallConv;
 use crate::IR::block::BlockId;
-use crate::IR::{ir, Block, Const, FnTy, Type, TypeMetadata, Var};
+use crate::IR::{ir, Block, Const, ConstAdr, FnAttr, FnAttrs, FnTy, Metadata, MetadataNode, MetadataValue, Type, TypeMetadata, Var};
 
 use super::lexer::{Loc, Token, TokenType};
 use super::IrError;
@@ -15,11 +16,12 @@ use super::IrError;
 pub struct IrInstr {
     pub loc: Loc,
     pub inst: Box<dyn Ir>,
+    pub metadata: Metadata,
 }
 
 impl PartialEq for IrInstr {
     fn eq(&self, other: &Self) -> bool {
-        self.loc == other.loc && &self.inst == &other.inst
+        self.loc == other.loc && &self.inst == &other.inst && self.metadata == other.metadata
     }
 }
 
@@ -42,6 +44,10 @@ pub enum IrStmt {
         args: (BTreeMap<String, TypeMetadata>, /*unlim args*/bool), 
         body: Vec<(String, IrBlock)>,
         scope: Linkage,
+        attrs: FnAttrs,
+        call_conv: Option<CallConv>,
+        personality: Option<String>,
+        metadata: Metadata,
 
         location: Loc,
     },
@@ -49,9 +55,23 @@ pub enum IrStmt {
     Const{
         name: String,
         data: Vec<u8>, 
+        adrs: Vec<ConstAdr>,
         location: Loc,
         scope: Linkage,
     },
+    /// a mutable global variable (`data` is `None` if it is zero initialized)
+    Global{
+        name: String,
+        ty: TypeMetadata,
+        data: Option<Vec<u8>>,
+        location: Loc,
+        scope: Linkage,
+    },
+    /// named metadata of the module (`!name = [values]`)
+    Metadata{
+        node: MetadataNode,
+        location: Loc,
+    },
 }
 
 /// Parses ir tokens into ir statements with location data
@@ -93,6 +113,8 @@ impl IrParser {
             TokenType::Declare => self.parse_declare(),
             TokenType::Define => self.parse_define(),
             TokenType::Const => self.parse_const(),
+            TokenType::Global => self.parse_global(),
+            TokenType::ExclamationMark => self.parse_module_metadata(),
 
             _ => Err(IrError::UnexpectedToken(tok.clone())),
         }
@@ -105,6 +127,8 @@ impl IrParser {
         self.expect( TokenType::Declare )?;
         self.input.pop_front(); // advance over declare
 
+        let call_conv = self.parse_call_conv();
+
         let ret = self.parse_type()?;
         self.input.pop_front();
 
@@ -164,12 +188,20 @@ impl IrParser {
         self.expect(TokenType::RParam)?;
         self.input.pop_front(); // the closing param )
 
+        let attrs = self.parse_fn_attrs();
+
+        let metadata = self.parse_metadata()?;
+
         Ok(IrStmt::Func { 
             name: name, 
             body: Vec::new(),
             scope: Linkage::Extern,
             args: (args, unlim),
             ret: ret,
+            attrs: attrs,
+            call_conv: call_conv,
+            personality: None,
+            metadata: metadata,
 
             location: loc,
         })
@@ -185,6 +217,8 @@ impl IrParser {
         self.expect( TokenType::Define )?;
         self.input.pop_front(); // advance over define
 
+        let call_conv = self.parse_call_conv();
+
         let ret = self.parse_type()?;
         self.input.pop_front();
 
@@ -215,6 +249,8 @@ impl IrParser {
 
         self.input.pop_front();
 
+        let mut unlim = false;
+
         loop {
             let current = self.current_token()?;
 
@@ -226,6 +262,12 @@ impl IrParser {
                 self.input.pop_front();
             }
 
+            if TokenType::TripleDot == self.current_token()?.typ {
+                self.input.pop_front();
+                unlim = true;
+                break;
+            }
+
             let var_type = self.parse_type()?;
             self.input.pop_front();
 
@@ -244,8 +286,24 @@ impl IrParser {
             args.insert(var_name, var_type );
         }
 
+        self.expect(TokenType::RParam)?;
         self.input.pop_front(); // the closing param )
 
+        let attrs = self.parse_fn_attrs();
+
+        let mut personality = None;
+
+        if TokenType::Ident("personality".to_owned()) == self.current_token()?.typ {
+            self.input.pop_front(); // personality
+
+            self.expect(TokenType::Func(String::new()))?;
+            if let TokenType::Func(func) = &self.current_token()?.typ {
+                personality = Some(func.to_owned());
+            } else { unreachable!() }
+            self.input.pop_front();
+        }
+
+        let metadata = self.parse_metadata()?;
         
         self.expect(TokenType::LBracket)?;
         self.input.pop_front();
@@ -267,9 +325,13 @@ impl IrParser {
         Ok(IrStmt::Func { 
             name: name, 
             body: body,
-            args: (args, false),
+            args: (args, unlim),
             scope: link,
             ret: ret,
+            attrs: attrs,
+            call_conv: call_conv,
+            personality: personality,
+            metadata: metadata,
 
             location: loc,
         })
@@ -335,10 +397,11 @@ impl IrParser {
         let current = self.current_token()?;
 
         let mut data = vec![];
+        let mut adrs = vec![];
 
         match &current.typ {
             TokenType::String(x) => data = x.as_bytes().to_vec(),
-            TokenType::LSquare => data = self.parse_data_array()?, 
+            TokenType::LSquare => (data, adrs) = self.parse_data_array()?, 
 
             _=> Err(IrError::ExpectedTokenButFoundAnUnexpectedOne { 
                 found: current.clone(), 
@@ -360,6 +423,109 @@ impl IrParser {
         Ok(IrStmt::Const { 
             name: name, 
             data: data,
+            adrs: adrs,
+            location: location,
+            scope: scope,
+        })
+    }
+
+    fn parse_global(&mut self) -> Result<IrStmt, IrError> {
+        self.expect(TokenType::Global)?;
+
+        self.input.pop_front();
+
+        /* 
+        PARSE SCOPE
+        */
+
+        let mut scope = Linkage::Internal;
+
+        if let TokenType::Ident(ident) = &self.current_token()?.typ {
+            let parsed_scope = match ident.as_str() {
+                "local" | "internal" | "intern" | "private" => Some(Linkage::Internal),
+                "public" | "external" | "extern" => Some(Linkage::External),
+                "import" => Some(Linkage::Extern),
+                _ => None,
+            };
+
+            if let Some(parsed_scope) = parsed_scope {
+                scope = parsed_scope;
+                self.input.pop_front();
+            }
+        }
+
+        /* 
+        PARSE TYPE AND NAME
+        */
+
+        let ty = self.parse_type()?;
+        self.input.pop_front();
+
+        self.expect(TokenType::Ident(String::new()))?;
+
+        let tok = self.current_token()?;
+
+        let name = if let TokenType::Ident(ident) = &tok.typ {
+            ident.to_owned()
+        } else { unreachable!() };
+
+        let mut location = tok.loc.clone();
+
+        self.input.pop_front();
+
+        // without an initializer the global is zero initialized
+        let Some(Token { typ: TokenType::Equal, .. }) = self.input.front() else {
+            return Ok(IrStmt::Global { 
+                name: name, 
+                ty: ty,
+                data: None,
+                location: location,
+                scope: scope,
+            });
+        };
+
+        self.input.pop_front();
+
+        let current = self.current_token()?;
+
+        let data = match &current.typ {
+            TokenType::String(x) => x.as_bytes().to_vec(),
+            TokenType::LSquare => {
+                let loc = current.loc.clone();
+
+                let (data, adrs) = self.parse_data_array()?;
+
+                if !adrs.is_empty() {
+                    Err(IrError::UndeterminedTokenSequence { 
+                        loc: loc, 
+                        expected: "bytes - globals can't contain symbol adresses".to_owned() 
+                    })?
+                }
+
+                data
+            }, 
+
+            _=> Err(IrError::ExpectedTokenButFoundAnUnexpectedOne { 
+                found: current.clone(), 
+                expected: Token { 
+                    typ: TokenType::LSquare, 
+                    loc: current.loc.clone() 
+                } 
+            })?
+        };
+
+        let current = &self.current_token()?.loc;
+
+        if location.line == current.line {
+            location.length = current.coloumn - location.coloumn;
+        }
+
+        self.input.pop_front();
+
+        Ok(IrStmt::Global { 
+            name: name, 
+            ty: ty,
+            data: Some(data),
             location: location,
             scope: scope,
         })
@@ -417,21 +583,39 @@ impl IrParser {
                     name
                 } else { unreachable!() };
 
+                // nodes with two outputs (like the overflow node) name both of them
+                let mut second = None;
+
+                if TokenType::Comma == self.current_token()?.typ {
+                    let comma = self.input.pop_front().expect("unreachable"); // ,
+
+                    self.expect(TokenType::Var(String::new()))?;
+                    if let TokenType::Var(name) = &self.current_token()?.typ {
+                        second = Some((comma, name.to_owned()));
+                    } else { unreachable!() }
+                    self.input.pop_front();
+                }
+
                 self.expect(TokenType::Equal)?;
                 self.input.pop_front(); // =
 
                 self.expect(TokenType::Ident(String::new()))?; // node
-                if let TokenType::Ident(instrinc) = &self.current_token()?.typ {
+                let node = if let TokenType::Ident(instrinc) = &self.current_token()?.typ {
                     match instrinc.as_str() {
                         "sub" => self.parse_sub(name)?,
                         "add" => self.parse_add(name)?,
                         "mul" => self.parse_mul(name)?,
-                        "cast" => self.parse_cast(name)?,
+                        "cast" => self.parse_cast(name, None)?,
                         "xor" => self.parse_xor(name)?,
                         "or" => self.parse_or(name)?,
                         "and" => self.parse_and(name)?,
                         "div" => self.parse_div(name)?,
-                        "call" => self.parse_call(name)?,
+                        "call" => self.parse_call(name, false)?,
+                        "tail" => {
+                            self.input.pop_front(); // tail
+                            self.expect_ident("call".to_owned())?;
+                            self.parse_call(name, true)?
+                        },
                         "cmp" => self.parse_cmp(name)?,
                         "alloca" => self.parse_alloca(name)?,
                         "load" => self.parse_load(name)?,
@@ -441,20 +625,54 @@ impl IrParser {
                         "rem" => self.parse_rem(name)?,
                         "shl" => self.parse_shl(name)?,
                         "shr" => self.parse_shr(name)?,
+                        "rotl" => self.parse_rotl(name)?,
+                        "rotr" => self.parse_rotr(name)?,
                         "getelemptr" => self.parse_getelemptr(name)?,
+                        "extractelement" => self.parse_extractelement(name)?,
+                        "insertelement" => self.parse_insertelement(name)?,
+                        "shufflevector" => self.parse_shufflevector(name)?,
+                        "atomicrmw" => self.parse_atomicrmw(name)?,
+                        "cmpxchg" => self.parse_cmpxchg(name)?,
+                        "va_arg" => self.parse_va_arg(name)?,
+                        "asm" => self.parse_asm(Some(name))?,
+                        "invoke" => self.parse_invoke(name)?,
+                        "landingpad" => self.parse_landingpad(name, second.take().map(|(_, name)| name))?,
+                        "statepoint" => self.parse_statepoint(name)?,
+                        "tuple" => self.parse_tuple(name)?,
+                        "extractvalue" => self.parse_extractvalue(name)?,
+                        kind if ir::CastKind::parse(kind).is_some() => self.parse_cast(name, ir::CastKind::parse(kind))?,
+                        kind if ir::BitOpKind::parse(kind).is_some() => self.parse_bitop(name, ir::BitOpKind::parse(kind).unwrap())?,
+                        kind if ir::FloatOpKind::parse(kind).is_some() => self.parse_floatop(name, ir::FloatOpKind::parse(kind).unwrap())?,
+                        kind if ir::OverflowKind::parse(kind).is_some() => self.parse_overflowop(name, second.take().map(|(_, name)| name), ir::OverflowKind::parse(kind).unwrap())?,
                         _ => {
                             let ty = self.parse_type()?;
                             self.input.pop_front(); // the type
                             self.parse_const_assing(name, ty)?
                         }
                     }
-                } else { unreachable!() }
+                } else { unreachable!() };
+
+                if let Some((comma, _)) = second {
+                    Err(IrError::UnexpectedToken(comma))?
+                }
+
+                node
             } else if let TokenType::Ident(instrinc) = curr.typ {
                 match instrinc.as_str() {
                     "ret" => self.parse_ret()?,
                     "br" => self.parse_br()?,
                     "store" => self.parse_store()?,
                     "switch" => self.parse_switch()?,
+                    "unreachable" => self.parse_unreachable()?,
+                    "trap" => self.parse_trap()?,
+                    "memcpy" => self.parse_memcpy()?,
+                    "memmove" => self.parse_memmove()?,
+                    "memset" => self.parse_memset()?,
+                    "fence" => self.parse_fence()?,
+                    "va_start" => self.parse_va_start()?,
+                    "va_end" => self.parse_va_end()?,
+                    "va_copy" => self.parse_va_copy()?,
+                    "asm" => self.parse_asm(None)?,
                     _ => Err(IrError::UnkownInstrinc{loc: curr.loc.clone(), found: instrinc })?,
                 }
             } else if let TokenType::ExclamationMark = curr.typ {
@@ -498,190 +716,885 @@ impl IrParser {
 
         let loc = curr.loc;
 
+        // the metadata which is attached to the node (e.g: `!branch_weights [90, 10]`)
+        let metadata = self.parse_metadata()?;
+
         Ok(IrInstr { 
             loc: loc, 
-            inst: node
+            inst: node,
+            metadata: metadata,
         })
 
     }
 
-    fn parse_ret(&mut self) -> Result<Box<dyn Ir>, IrError> {
-        self.input.pop_front(); // ret
-
-        let out_ty = self.parse_type()?;
-        self.input.pop_front();
-
-        let ret_op = self.parse_operand(out_ty)?;
+    /// parses the metadata nodes which are attached to a node or a function (`!name [values] !other ...`)
+    fn parse_metadata(&mut self) -> Result<Metadata, IrError> {
+        let mut metadata = Metadata::new();
 
-        self.input.pop_front();
-
-        Ok(ir::Return::new(ret_op))
-    }
+        loop {
+            let Some(Token { typ: TokenType::ExclamationMark, .. }) = self.input.get(0) else { break };
 
-    fn parse_operand(&mut self, ty: TypeMetadata) -> Result<IROperand, IrError> {
-        let curr = self.current_token()?;
+            // `!dbg` starts a debug node and `!name =` is metadata of the module
+            match (self.input.get(1), self.input.get(2)) {
+                (Some(Token { typ: TokenType::Ident(name), .. }), _) if name == "dbg" => break,
+                (_, Some(Token { typ: TokenType::Equal, .. })) => break,
+                _ => {},
+            }
 
-        if let TokenType::Int(numeral) = &curr.typ {
-            Ok(IROperand::Type(Type::from_int(ty, *numeral)))
-        } else if let TokenType::Var(var) = &curr.typ {
-            Ok(IROperand::Var(Var {
-                name: var.to_owned(),
-                ty: ty,
-            }))
-        } else {
-            Err(IrError::UndeterminedTokenSequence { 
-                loc: curr.loc.clone(), 
-                expected: "intenger, variable names - for valid constant assignments".to_owned() 
-            })
+            metadata.add( self.parse_metadata_node()? );
         }
-    }
 
-    fn parse_const_assing(&mut self, var: String, ty: TypeMetadata) -> Result<Box<dyn Ir>, IrError> {
-        let out = Var {
-            name: var,
-            ty: ty,
-        };
+        Ok(metadata)
+    }
 
-        let curr = self.current_token()?;
+    /// parses `!name = [values]`
+    fn parse_module_metadata(&mut self) -> Result<IrStmt, IrError> {
+        let location = self.current_token()?.loc.clone();
 
-        let out:  Result<Box<dyn Ir>, IrError>  = if let TokenType::Int(numeral) = &curr.typ {
-            Ok(ir::Assign::new(out, Type::from_int(ty, *numeral)))
-        } else if let TokenType::Var(var) = &curr.typ {
-            Ok(ir::Assign::new(out, Var { 
-                name: var.to_owned(),
-                ty: ty,
-            }))
-        } else if let TokenType::Ident(cons) = &curr.typ {
-            Ok(ir::Assign::new(out, Const::new(cons.to_string())))
-        } else {
-            Err(IrError::UndeterminedTokenSequence { 
-                loc: curr.loc.clone(), 
-                expected: "intenger, variable names - for valid constant assignments".to_owned() 
-            })
-        };
+        let name = self.parse_metadata_name()?;
 
+        self.expect(TokenType::Equal)?;
         self.input.pop_front();
 
-        out
+        let values = self.parse_metadata_values()?;
+
+        Ok(IrStmt::Metadata { 
+            node: MetadataNode::new(&name, values),
+            location: location,
+        })
     }
 
-    fn parse_call(&mut self, var: String) -> Result<Box<dyn Ir>, IrError> {
-        self.input.pop_front(); // call
+    /// parses `!name`
+    fn parse_metadata_name(&mut self) -> Result<String, IrError> {
+        self.expect(TokenType::ExclamationMark)?;
+        self.input.pop_front();
 
-        let func_ty = self.parse_type()?;
+        self.expect(TokenType::Ident(String::new()))?;
+        let name = if let TokenType::Ident(name) = &self.current_token()?.typ {
+            name.to_owned()
+        } else { unreachable!() };
         self.input.pop_front();
 
-        self.expect(TokenType::Ident(String::from("func name")))?;
+        Ok(name)
+    }
 
-        let target = &self.current_token()?;
+    /// parses `!name [values]` (the values are optional)
+    fn parse_metadata_node(&mut self) -> Result<MetadataNode, IrError> {
+        let name = self.parse_metadata_name()?;
 
-        let target = if let TokenType::Ident(ident) = &target.typ {
-            ident.to_owned()
-        } else { unreachable!() };
+        let mut values = Vec::new();
 
-        self.input.pop_front(); // function name
+        if let Some(Token { typ: TokenType::LSquare, .. }) = self.input.front() {
+            values = self.parse_metadata_values()?;
+        }
 
-        let out = Var {
-            name: var,
-            ty: func_ty
-        };
+        Ok(MetadataNode::new(&name, values))
+    }
 
-        let mut args = vec![];
+    /// parses `[1, "abc", !other]`
+    fn parse_metadata_values(&mut self) -> Result<Vec<MetadataValue>, IrError> {
+        self.expect(TokenType::LSquare)?;
+        self.input.pop_front();
+
+        let mut values = Vec::new();
 
         loop {
-            let ty = if let TokenType::Ident(ty) = &self.current_token()?.typ {
-                if let Some(ty) = TypeMetadata::parse(ty.to_owned()) {
-                    ty
-                } else {
-                    break
-                }
-            } else { break; };
+            let current = self.current_token()?.clone();
 
-            self.input.pop_front();
+            match current.typ {
+                TokenType::RSquare => break,
+                TokenType::Comma => {},
+                TokenType::Int(int) => values.push(MetadataValue::Int(int as i64)),
+                TokenType::String(string) => values.push(MetadataValue::Str(string)),
+                TokenType::ExclamationMark => {
+                    values.push(MetadataValue::Ref(self.parse_metadata_name()?));
+                    continue;
+                },
+                _ => Err(IrError::UnexpectedToken(current))?,
+            }
 
-            let arg = self.parse_operand(ty)?;
             self.input.pop_front();
-
-            args.push(arg);
         }
 
-        Ok(Box::new(ir::Call {
-            func: crate::IR::FuncId {
-                ty: FnTy(vec![], func_ty),
-                name: target,
-            }, args, out
-        }))
-    }
-
-    fn parse_br(&mut self) -> Result<Box<dyn Ir>, IrError> {
-        self.input.pop_front(); // br
+        self.input.pop_front(); // ]
 
-        let current = self.current_token()?;
+        Ok(values)
+    }
 
-        match &current.typ {
-            TokenType::Ident(block) => {
-                let block = block.to_owned();
+    fn parse_ret(&mut self) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // ret
 
-                self.input.pop_front();
+        let out_ty = self.parse_type()?;
+        self.input.pop_front();
 
-                Ok(ir::Br::new( BlockId(block) ))
-            },
-            TokenType::Cond => {
-                self.input.pop_front(); // cond
+        let ret_op = self.parse_operand(out_ty)?;
 
-                let var = self.expect(TokenType::Var(String::new()))?;
+        self.input.pop_front();
 
-                let var = if let TokenType::Var(var) = var.typ 
-                                                { var } else { unreachable!() };
+        Ok(ir::Return::new(ret_op))
+    }
 
-                self.input.pop_front();
+    fn parse_unreachable(&mut self) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // unreachable
 
-                self.expect(TokenType::Ident(String::new()))?;
+        Ok(Box::new( ir::Unreachable {} ))
+    }
 
-                let iftrue;
+    fn parse_trap(&mut self) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // trap
 
-                if let TokenType::Ident(ident) = &self.current_token()?.typ {
-                    iftrue = ident.to_owned();
-                } else { unreachable!() }
+        Ok(Box::new( ir::Trap {} ))
+    }
 
-                self.input.pop_front();
+    fn parse_memcpy(&mut self) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // memcpy
 
-                self.expect(TokenType::Comma)?;
-                self.input.pop_front();
+        let (dst, src, size) = self.parse_mem_transfer()?;
 
-                self.expect(TokenType::Ident(String::new()))?;
+        Ok(Box::new( ir::MemCpy { dst: dst, src: src, size: size } ))
+    }
 
-                let iffalse;
+    fn parse_memmove(&mut self) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // memmove
 
-                if let TokenType::Ident(ident) = &self.current_token()?.typ {
-                    iffalse = ident.to_owned();
-                } else { unreachable!() }
-                self.input.pop_front();
+        let (dst, src, size) = self.parse_mem_transfer()?;
 
-                Ok(ir::BrCond::new(Var {
-                    name: var,
-                    ty: TypeMetadata::Void,
-                }, 
-                BlockId(iftrue), 
-                BlockId(iffalse)
-                ))
-            },
-            _ => Err(IrError::UnexpectedToken(self.current_token()?.clone())),
-        }
+        Ok(Box::new( ir::MemMove { dst: dst, src: src, size: size } ))
     }
 
-    fn parse_cast(&mut self, var: String) -> Result<Box<dyn Ir>, IrError> {
-        self.input.pop_front();
+    /// parses `%dst, %src, ty size`
+    fn parse_mem_transfer(&mut self) -> Result<(Var, Var, IROperand), IrError> {
+        let dst = self.parse_ptr_var()?;
 
-        let ty = self.parse_type()?;
+        self.expect(TokenType::Comma)?;
         self.input.pop_front();
 
-        self.expect(TokenType::Var(String::new()))?;
+        let src = self.parse_ptr_var()?;
 
-        let in_var = self.parse_operand(ty)?;
+        self.expect(TokenType::Comma)?;
         self.input.pop_front();
 
-        self.expect_ident("to".into())?;
+        let size = self.parse_typed_operand()?;
+
+        Ok((dst, src, size))
+    }
+
+    fn parse_memset(&mut self) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // memset
+
+        let dst = self.parse_ptr_var()?;
+
+        self.expect(TokenType::Comma)?;
+        self.input.pop_front();
+
+        let val = self.parse_typed_operand()?;
+
+        self.expect(TokenType::Comma)?;
+        self.input.pop_front();
+
+        let size = self.parse_typed_operand()?;
+
+        Ok(Box::new( ir::MemSet { dst: dst, val: val, size: size } ))
+    }
+
+    fn parse_atomic_load(&mut self, var: String) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // atomic
+
+        let ty = self.parse_type()?;
+        self.input.pop_front();
+
+        let ptr = self.parse_ptr_var()?;
+
+        let order = self.parse_ordering()?;
+
+        Ok(Box::new( ir::AtomicLoad { out: Var { name: var, ty: ty }, ptr: ptr, order: order } ))
+    }
+
+    fn parse_atomic_store(&mut self) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // atomic
+
+        let val = self.parse_typed_operand()?;
+
+        self.expect(TokenType::Comma)?;
+        self.input.pop_front();
+
+        let ptr = self.parse_ptr_var()?;
+
+        let order = self.parse_ordering()?;
+
+        Ok(Box::new( ir::AtomicStore { ptr: ptr, val: val, order: order } ))
+    }
+
+    fn parse_atomicrmw(&mut self, var: String) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // atomicrmw
+
+        self.expect(TokenType::Ident(String::new()))?;
+
+        let curr = self.current_token()?;
+        let ident = if let TokenType::Ident(ident) = &curr.typ {
+            ident.to_owned()
+        } else { unreachable!() };
+
+        let Some(op) = ir::AtomicRmwOp::parse(&ident) else {
+            Err(IrError::Unkown { 
+                what: "atomicrmw operation".to_owned(), 
+                name: ident, 
+                loc: curr.loc.to_owned()
+            })?
+        };
+        self.input.pop_front();
+
+        let ptr = self.parse_ptr_var()?;
+
+        self.expect(TokenType::Comma)?;
+        self.input.pop_front();
+
+        let val = self.parse_typed_operand()?;
+
+        let order = self.parse_ordering()?;
+
+        let out = Var {
+            name: var,
+            ty: val.get_ty(),
+        };
+
+        Ok(Box::new( ir::AtomicRmw { out: out, op: op, ptr: ptr, val: val, order: order } ))
+    }
+
+    fn parse_cmpxchg(&mut self, var: String) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // cmpxchg
+
+        let ptr = self.parse_ptr_var()?;
+
+        self.expect(TokenType::Comma)?;
+        self.input.pop_front();
+
+        let cmp = self.parse_typed_operand()?;
+
+        self.expect(TokenType::Comma)?;
+        self.input.pop_front();
+
+        let new = self.parse_typed_operand()?;
+
+        let order = self.parse_ordering()?;
+
+        let out = Var {
+            name: var,
+            ty: cmp.get_ty(),
+        };
+
+        Ok(Box::new( ir::CmpXchg { out: out, ptr: ptr, cmp: cmp, new: new, order: order } ))
+    }
+
+    fn parse_fence(&mut self) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // fence
+
+        let order = self.parse_ordering()?;
+
+        Ok(Box::new( ir::Fence { order: order } ))
+    }
+
+    fn parse_va_start(&mut self) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // va_start
+
+        let list = self.parse_ptr_var()?;
+
+        Ok(Box::new( ir::VaStart { list: list } ))
+    }
+
+    fn parse_va_arg(&mut self, var: String) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // va_arg
+
+        let ty = self.parse_type()?;
+        self.input.pop_front();
+
+        let list = self.parse_ptr_var()?;
+
+        Ok(Box::new( ir::VaArg { out: Var { name: var, ty: ty }, list: list } ))
+    }
+
+    fn parse_va_end(&mut self) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // va_end
+
+        let list = self.parse_ptr_var()?;
+
+        Ok(Box::new( ir::VaEnd { list: list } ))
+    }
+
+    fn parse_va_copy(&mut self) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // va_copy
+
+        let dst = self.parse_ptr_var()?;
+
+        self.expect(TokenType::Comma)?;
+        self.input.pop_front();
+
+        let src = self.parse_ptr_var()?;
+
+        Ok(Box::new( ir::VaCopy { dst: dst, src: src } ))
+    }
+
+    fn parse_asm(&mut self, var: Option<String>) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // asm
+
+        let out = if let Some(name) = var {
+            let ty = self.parse_type()?;
+            self.input.pop_front();
+
+            Some(Var { name: name, ty: ty })
+        } else { None };
+
+        self.expect(TokenType::String(String::new()))?;
+        let asm = if let TokenType::String(asm) = &self.current_token()?.typ {
+            asm.to_owned()
+        } else { unreachable!() };
+        self.input.pop_front();
+
+        let constraints = self.expect(TokenType::String(String::new()))?;
+        let constraints = if let TokenType::String(string) = &constraints.typ {
+            match ir::parse_asm_constraints(string) {
+                Some(parsed) => parsed,
+                None => Err(IrError::UndeterminedTokenSequence { 
+                    loc: constraints.loc.clone(), 
+                    expected: "inline assembly constraints (=r, ={reg}, r, i, {reg} or ~{reg}) seperated by commas".to_owned() 
+                })?,
+            }
+        } else { unreachable!() };
+        self.input.pop_front();
+
+        let mut args = vec![];
+
+        loop {
+            let ty = if let TokenType::Ident(ty) = &self.current_token()?.typ {
+                if let Some(ty) = TypeMetadata::parse(ty.to_owned()) {
+                    ty
+                } else {
+                    break
+                }
+            } else { break; };
+
+            self.input.pop_front();
+
+            let arg = self.parse_operand(ty)?;
+            self.input.pop_front();
+
+            args.push(arg);
+        }
+
+        Ok(Box::new( ir::InlineAsm { out: out, asm: asm, constraints: constraints, args: args } ))
+    }
+
+    /// parses the memory ordering of an atomic operation (e.g: `seq_cst`)
+    fn parse_ordering(&mut self) -> Result<ir::AtomicOrdering, IrError> {
+        self.expect(TokenType::Ident(String::new()))?;
+
+        let curr = self.current_token()?;
+        let ident = if let TokenType::Ident(ident) = &curr.typ {
+            ident.to_owned()
+        } else { unreachable!() };
+
+        let Some(order) = ir::AtomicOrdering::parse(&ident) else {
+            Err(IrError::Unkown { 
+                what: "memory ordering".to_owned(), 
+                name: ident, 
+                loc: curr.loc.to_owned()
+            })?
+        };
+        self.input.pop_front();
+
+        Ok(order)
+    }
+
+    /// parses the optional `volatile` keyword of a load or store
+    fn parse_volatile(&mut self) -> bool {
+        if let Some(Token { typ: TokenType::Ident(ident), .. }) = self.input.front() {
+            if ident == "volatile" {
+                self.input.pop_front();
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// parses the optional `, align 4` of a load or store
+    fn parse_align(&mut self) -> Result<Option<usize>, IrError> {
+        let Some(Token { typ: TokenType::Comma, .. }) = self.input.front() else {
+            return Ok(None);
+        };
+
+        self.input.pop_front(); // ,
+
+        self.expect_ident("align".to_owned())?;
+        self.input.pop_front();
+
+        self.expect(TokenType::Int(0.0))?;
+
+        let align = if let TokenType::Int(align) = &self.current_token()?.typ {
+            *align as usize
+        } else { unreachable!() };
+        self.input.pop_front();
+
+        Ok(Some(align))
+    }
+
+    fn parse_ptr_var(&mut self) -> Result<Var, IrError> {
+        self.expect(TokenType::Var(String::new()))?;
+
+        let name = if let TokenType::Var(var) = &self.current_token()?.typ {
+            var.to_owned()
+        } else { unreachable!() };
+        self.input.pop_front();
+
+        Ok(Var {
+            name: name,
+            ty: TypeMetadata::ptr,
+        })
+    }
+
+    /// parses `ty operand`
+    fn parse_typed_operand(&mut self) -> Result<IROperand, IrError> {
+        let ty = self.parse_type()?;
+        self.input.pop_front();
+
+        let op = self.parse_operand(ty)?;
+        self.input.pop_front();
+
+        Ok(op)
+    }
+
+    fn parse_operand(&mut self, ty: TypeMetadata) -> Result<IROperand, IrError> {
+        let curr = self.current_token()?;
+
+        if let TokenType::Int(numeral) = &curr.typ {
+            Ok(IROperand::Type(Type::from_int(ty, *numeral)))
+        } else if let TokenType::Var(var) = &curr.typ {
+            Ok(IROperand::Var(Var {
+                name: var.to_owned(),
+                ty: ty,
+            }))
+        } else {
+            Err(IrError::UndeterminedTokenSequence { 
+                loc: curr.loc.clone(), 
+                expected: "intenger, variable names - for valid constant assignments".to_owned() 
+            })
+        }
+    }
+
+    fn parse_const_assing(&mut self, var: String, ty: TypeMetadata) -> Result<Box<dyn Ir>, IrError> {
+        let out = Var {
+            name: var,
+            ty: ty,
+        };
+
+        let curr = self.current_token()?;
+
+        let out:  Result<Box<dyn Ir>, IrError>  = if let TokenType::Int(numeral) = &curr.typ {
+            Ok(ir::Assign::new(out, Type::from_int(ty, *numeral)))
+        } else if let TokenType::Var(var) = &curr.typ {
+            Ok(ir::Assign::new(out, Var { 
+                name: var.to_owned(),
+                ty: ty,
+            }))
+        } else if let TokenType::Ident(cons) = &curr.typ {
+            // the adresses of globals and functions are also taken by their name
+            // (they are resolved during the semantic analysis)
+            Ok(ir::Assign::new(out, Const::new(cons.to_string())))
+        } else {
+            Err(IrError::UndeterminedTokenSequence { 
+                loc: curr.loc.clone(), 
+                expected: "intenger, variable names - for valid constant assignments".to_owned() 
+            })
+        };
+
+        self.input.pop_front();
+
+        out
+    }
+
+    fn parse_call(&mut self, var: String, tail: bool) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // call
+
+        let call_conv = self.parse_call_conv();
+
+        let func_ty = self.parse_type()?;
+        self.input.pop_front();
+
+        // only direct calls can be tail calls
+        if let (TokenType::Var(ptr), false) = (&self.current_token()?.typ, tail) {
+            let ptr = ptr.to_owned();
+            self.input.pop_front(); // the adress
+
+            return self.parse_call_indirect(var, ptr, func_ty, call_conv);
+        }
+
+        self.expect(TokenType::Ident(String::from("func name")))?;
+
+        let target = &self.current_token()?;
+
+        let target = if let TokenType::Ident(ident) = &target.typ {
+            ident.to_owned()
+        } else { unreachable!() };
+
+        self.input.pop_front(); // function name
+
+        let out = Var {
+            name: var,
+            ty: func_ty
+        };
+
+        let mut args = vec![];
+
+        loop {
+            let ty = match &self.current_token()?.typ {
+                TokenType::Ident(ty) => if let Some(ty) = TypeMetadata::parse(ty.to_owned()) {
+                    ty
+                } else {
+                    break
+                },
+                // aggregates are passed by value
+                TokenType::LBracket | TokenType::LSquare => self.parse_type()?,
+                _ => break,
+            };
+
+            self.input.pop_front();
+
+            let arg = self.parse_operand(ty)?;
+            self.input.pop_front();
+
+            args.push(arg);
+        }
+
+        let attrs = self.parse_fn_attrs();
+
+        let mut ty = FnTy(vec![], func_ty);
+        ty.call_conv = call_conv;
+
+        Ok(Box::new(ir::Call {
+            func: crate::IR::FuncId {
+                ty: ty,
+                name: target,
+            }, args, out, attrs, tail
+        }))
+    }
+
+    fn parse_call_indirect(&mut self, var: String, ptr: String, ret: TypeMetadata, call_conv: Option<CallConv>) -> Result<Box<dyn Ir>, IrError> {
+        let out = Var {
+            name: var,
+            ty: ret
+        };
+
+        let mut args = vec![];
+
+        loop {
+            let ty = match &self.current_token()?.typ {
+                TokenType::Ident(ty) => if let Some(ty) = TypeMetadata::parse(ty.to_owned()) {
+                    ty
+                } else {
+                    break
+                },
+                // aggregates are passed by value
+                TokenType::LBracket | TokenType::LSquare => self.parse_type()?,
+                _ => break,
+            };
+
+            self.input.pop_front();
+
+            let arg = self.parse_operand(ty)?;
+            self.input.pop_front();
+
+            args.push(arg);
+        }
+
+        // the function type is described by the types of the supplied arguments
+        let mut ty = FnTy(args.iter().map(|arg| arg.get_ty()).collect(), ret);
+        ty.call_conv = call_conv;
+
+        Ok(Box::new(ir::CallIndirect {
+            out: out,
+            ptr: Var {
+                name: ptr,
+                ty: TypeMetadata::ptr,
+            },
+            ty: ty,
+            args: args,
+        }))
+    }
+
+    /// parses `invoke i32 func i32 %0 to normal unwind lpad`
+    fn parse_invoke(&mut self, var: String) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // invoke
+
+        let call_conv = self.parse_call_conv();
+
+        let func_ty = self.parse_type()?;
+        self.input.pop_front();
+
+        self.expect(TokenType::Ident(String::from("func name")))?;
+
+        let target = if let TokenType::Ident(ident) = &self.current_token()?.typ {
+            ident.to_owned()
+        } else { unreachable!() };
+
+        self.input.pop_front(); // function name
+
+        let mut args = vec![];
+
+        loop {
+            let ty = match &self.current_token()?.typ {
+                TokenType::Ident(ty) => if let Some(ty) = TypeMetadata::parse(ty.to_owned()) {
+                    ty
+                } else {
+                    break
+                },
+                // aggregates are passed by value
+                TokenType::LBracket | TokenType::LSquare => self.parse_type()?,
+                _ => break,
+            };
+
+            self.input.pop_front();
+
+            let arg = self.parse_operand(ty)?;
+            self.input.pop_front();
+
+            args.push(arg);
+        }
+
+        self.expect_ident("to".to_owned())?;
+        self.input.pop_front();
+
+        self.expect(TokenType::Ident(String::new()))?;
+        let normal = if let TokenType::Ident(block) = &self.current_token()?.typ {
+            block.to_owned()
+        } else { unreachable!() };
+        self.input.pop_front();
+
+        self.expect_ident("unwind".to_owned())?;
+        self.input.pop_front();
+
+        self.expect(TokenType::Ident(String::new()))?;
+        let unwind = if let TokenType::Ident(block) = &self.current_token()?.typ {
+            block.to_owned()
+        } else { unreachable!() };
+        self.input.pop_front();
+
+        let mut ty = FnTy(vec![], func_ty);
+        ty.call_conv = call_conv;
+
+        Ok(Box::new(ir::Invoke {
+            out: Var {
+                name: var,
+                ty: func_ty,
+            },
+            func: crate::IR::FuncId {
+                ty: ty,
+                name: target,
+            },
+            args: args,
+            normal: BlockId(normal),
+            unwind: BlockId(unwind),
+        }))
+    }
+
+    /// parses `landingpad ptr cleanup catch @_ZTIi catch null`
+    /// (the selector is the optional second output)
+    fn parse_landingpad(&mut self, var: String, selector: Option<String>) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // landingpad
+
+        let ty = self.parse_type()?;
+        self.input.pop_front();
+
+        let mut cleanup = false;
+        let mut catches = Vec::new();
+
+        loop {
+            match &self.current_token()?.typ {
+                TokenType::Ident(ident) if ident == "cleanup" => {
+                    self.input.pop_front();
+                    cleanup = true;
+                },
+                TokenType::Ident(ident) if ident == "catch" => {
+                    self.input.pop_front();
+
+                    match &self.current_token()?.typ {
+                        TokenType::Func(typeinfo) => catches.push(Some(typeinfo.to_owned())),
+                        TokenType::Ident(null) if null == "null" => catches.push(None),
+                        _ => Err(IrError::UnexpectedToken(self.current_token()?.clone()))?,
+                    }
+
+                    self.input.pop_front();
+                },
+                _ => break,
+            }
+        }
+
+        Ok(Box::new(ir::LandingPad {
+            out: Var {
+                name: var,
+                ty: ty,
+            },
+            selector: selector.map(|name| Var {
+                name: name,
+                ty: TypeMetadata::i32,
+            }),
+            cleanup: cleanup,
+            catches: catches,
+        }))
+    }
+
+    /// parses `statepoint 1 i32 func i32 %0 gc [%1, %2]`
+    fn parse_statepoint(&mut self, var: String) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // statepoint
+
+        self.expect(TokenType::Int(0.0))?;
+        let id = if let TokenType::Int(id) = &self.current_token()?.typ {
+            *id as u64
+        } else { unreachable!() };
+        self.input.pop_front();
+
+        let call_conv = self.parse_call_conv();
+
+        let func_ty = self.parse_type()?;
+        self.input.pop_front();
+
+        self.expect(TokenType::Ident(String::from("func name")))?;
+
+        let target = if let TokenType::Ident(ident) = &self.current_token()?.typ {
+            ident.to_owned()
+        } else { unreachable!() };
+
+        self.input.pop_front(); // function name
+
+        let mut args = vec![];
+
+        loop {
+            let ty = if let TokenType::Ident(ty) = &self.current_token()?.typ {
+                if let Some(ty) = TypeMetadata::parse(ty.to_owned()) {
+                    ty
+                } else {
+                    break
+                }
+            } else { break; };
+
+            self.input.pop_front();
+
+            let arg = self.parse_operand(ty)?;
+            self.input.pop_front();
+
+            args.push(arg);
+        }
+
+        self.expect_ident("gc".to_owned())?;
+        self.input.pop_front();
+
+        self.expect(TokenType::LSquare)?;
+        self.input.pop_front();
+
+        let mut gc = vec![];
+
+        while let TokenType::Var(root) = &self.current_token()?.typ {
+            // the type is set in the semantic analysis
+            gc.push(Var {
+                name: root.to_owned(),
+                ty: TypeMetadata::ptr,
+            });
+
+            self.input.pop_front();
+
+            if self.current_token()?.typ == TokenType::Comma {
+                self.input.pop_front();
+            }
+        }
+
+        self.expect(TokenType::RSquare)?;
+        self.input.pop_front();
+
+        let mut ty = FnTy(vec![], func_ty);
+        ty.call_conv = call_conv;
+
+        Ok(Box::new(ir::Statepoint {
+            out: Var {
+                name: var,
+                ty: func_ty,
+            },
+            id: id,
+            func: crate::IR::FuncId {
+                ty: ty,
+                name: target,
+            },
+            args: args,
+            gc: gc,
+        }))
+    }
+
+    fn parse_br(&mut self) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // br
+
+        let current = self.current_token()?;
+
+        match &current.typ {
+            TokenType::Ident(block) => {
+                let block = block.to_owned();
+
+                self.input.pop_front();
+
+                Ok(ir::Br::new( BlockId(block) ))
+            },
+            TokenType::Cond => {
+                self.input.pop_front(); // cond
+
+                let var = self.expect(TokenType::Var(String::new()))?;
+
+                let var = if let TokenType::Var(var) = var.typ 
+                                                { var } else { unreachable!() };
+
+                self.input.pop_front();
+
+                self.expect(TokenType::Ident(String::new()))?;
+
+                let iftrue;
+
+                if let TokenType::Ident(ident) = &self.current_token()?.typ {
+                    iftrue = ident.to_owned();
+                } else { unreachable!() }
+
+                self.input.pop_front();
+
+                self.expect(TokenType::Comma)?;
+                self.input.pop_front();
+
+                self.expect(TokenType::Ident(String::new()))?;
+
+                let iffalse;
+
+                if let TokenType::Ident(ident) = &self.current_token()?.typ {
+                    iffalse = ident.to_owned();
+                } else { unreachable!() }
+                self.input.pop_front();
+
+                Ok(ir::BrCond::new(Var {
+                    name: var,
+                    ty: TypeMetadata::Void,
+                }, 
+                BlockId(iftrue), 
+                BlockId(iffalse)
+                ))
+            },
+            _ => Err(IrError::UnexpectedToken(self.current_token()?.clone())),
+        }
+    }
+
+    /// parses `cast i32 %0 to i64` (the kind gets infered) or an explicit cast like `sext i32 %0 to i64`
+    fn parse_cast(&mut self, var: String, kind: Option<ir::CastKind>) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front();
+
+        let ty = self.parse_type()?;
+        self.input.pop_front();
+
+        let in_var = self.parse_operand(ty)?;
+        self.input.pop_front();
+
+        self.expect_ident("to".into())?;
         self.input.pop_front();
 
         let out_ty = self.parse_type()?;
@@ -693,7 +1606,9 @@ impl IrParser {
             ty: out_ty 
         };
 
-        Ok(ir::Cast::new(in_var, out_ty, out))
+        let kind = kind.unwrap_or(ir::CastKind::infer(ty, out_ty));
+
+        Ok(ir::Cast::new(kind, in_var, out_ty, out))
     }
 
     fn parse_alloca(&mut self, var: String) -> Result<Box<dyn Ir>, IrError> {
@@ -714,6 +1629,14 @@ impl IrParser {
     fn parse_store(&mut self) -> Result<Box<dyn Ir>, IrError> {
         self.input.pop_front();
 
+        if let TokenType::Ident(ident) = &self.current_token()?.typ {
+            if ident == "atomic" {
+                return self.parse_atomic_store();
+            }
+        }
+
+        let volatile = self.parse_volatile();
+
         let ty = self.parse_type()?;
         self.input.pop_front();
 
@@ -728,17 +1651,24 @@ impl IrParser {
         } else { unreachable!() };
         self.input.pop_front();
 
-        Ok(Store::new(Var {
+        let mut store = Store::new(Var {
             name: var,
             ty: TypeMetadata::ptr,
-        }, op))
+        }, op);
+
+        store.volatile = volatile;
+        store.align = self.parse_align()?;
+
+        Ok(store)
     }
 
-    fn parse_data_array(&mut self) -> Result<Vec<u8>, IrError> {
+    /// parses `[1, 2, symbol, func + 8, ...]` into the bytes and the symbol adresses
+    fn parse_data_array(&mut self) -> Result<(Vec<u8>, Vec<ConstAdr>), IrError> {
         self.expect(TokenType::LSquare)?;
         self.input.pop_front();
         
         let mut data = vec![];
+        let mut adrs = vec![];
 
         loop {
             let current = self.current_token()?;
@@ -746,6 +1676,28 @@ impl IrParser {
             match &current.typ {
                 TokenType::Int(int) => data.push(*int as u8),
 
+                TokenType::Ident(symbol) => {
+                    let mut adr = ConstAdr {
+                        at: data.len(),
+                        symbol: symbol.to_owned(),
+                        addend: 0,
+                    };
+
+                    if let Some(Token { typ: TokenType::Plus, .. }) = self.input.get(1) {
+                        self.input.pop_front(); // symbol
+                        self.input.pop_front(); // +
+
+                        self.expect(TokenType::Int(0.0))?;
+
+                        if let TokenType::Int(addend) = &self.current_token()?.typ {
+                            adr.addend = *addend as i64;
+                        } else { unreachable!() }
+                    }
+
+                    adrs.push(adr);
+                    data.extend_from_slice(&[0; 8]);
+                },
+
                 TokenType::RSquare => break,
                 _ => Err(IrError::UnexpectedToken(current.clone()))?,
             };
@@ -759,7 +1711,7 @@ impl IrParser {
             }
         }
 
-        Ok(data)
+        Ok((data, adrs))
     }
 
     fn current_token(&self) -> Result<&Token, IrError> {
@@ -805,31 +1757,159 @@ impl IrParser {
             })?
         }
 
-        Ok(())
+        Ok(())
+    }
+
+    /// Parses the calling convention (e.g: `win64`) if there is one
+    fn parse_call_conv(&mut self) -> Option<CallConv> {
+        let Some(Token { typ: TokenType::Ident(ident), .. }) = self.input.front() else { return None };
+
+        let call_conv = CallConv::parse(ident)?;
+        self.input.pop_front();
+
+        Some(call_conv)
+    }
+
+    /// Parses the function attributes (e.g: `noinline cold`) till the first token which isn't one
+    fn parse_fn_attrs(&mut self) -> FnAttrs {
+        let mut attrs = FnAttrs::new();
+
+        while let Some(Token { typ: TokenType::Ident(ident), .. }) = self.input.front() {
+            let Some(attr) = FnAttr::parse(ident) else { break };
+
+            attrs.add(attr);
+            self.input.pop_front();
+        }
+
+        attrs
+    }
+
+    fn parse_type(&mut self) -> Result<TypeMetadata, IrError> {
+        let token = self.current_token()?;
+
+        match &token.typ {
+            TokenType::LBracket => return self.parse_struct_type(),
+            TokenType::LSquare => return self.parse_array_type(),
+            TokenType::LAngle => return self.parse_vector_type(),
+            TokenType::LParam => return self.parse_tuple_type(),
+            _ => {},
+        }
+
+        let mut ident = String::new();
+
+        if let TokenType::Ident(text) = &token.typ {
+            ident = text.to_string();
+        } else {
+            Err(IrError::ExpectedTokenButFoundAnUnexpectedOne { 
+                found: token.clone(), 
+                expected: Token { 
+                    typ: TokenType::Ident("abc".to_string()), 
+                    loc: token.loc.clone()
+                } 
+            })?
+        }
+
+        if let Some(typ) = TypeMetadata::parse(ident) {
+            Ok(typ)
+        } else {
+            Err(IrError::UnkownType(token.clone()) )
+        }
+    }
+
+    /// parses `{ty, ty, ...}` (the closing bracket is left as the current token)
+    fn parse_struct_type(&mut self) -> Result<TypeMetadata, IrError> {
+        self.expect(TokenType::LBracket)?;
+        self.input.pop_front();
+
+        let mut fields = Vec::new();
+
+        loop {
+            let current = self.current_token()?;
+
+            if TokenType::RBracket == current.typ {
+                break;
+            }
+
+            if TokenType::Comma == current.typ {
+                self.input.pop_front();
+                continue;
+            }
+
+            fields.push( self.parse_type()? );
+            self.input.pop_front();
+        }
+
+        Ok(TypeMetadata::newStruct(&fields))
+    }
+
+    /// parses `[len x ty]` (the closing square is left as the current token)
+    fn parse_array_type(&mut self) -> Result<TypeMetadata, IrError> {
+        self.expect(TokenType::LSquare)?;
+        self.input.pop_front();
+
+        self.expect(TokenType::Int(0.0))?;
+        let len = if let TokenType::Int(int) = &self.current_token()?.typ {
+            *int as usize
+        } else { unreachable!() };
+        self.input.pop_front();
+
+        self.expect_ident("x".into())?;
+        self.input.pop_front();
+
+        let elem = self.parse_type()?;
+        self.input.pop_front();
+
+        self.expect(TokenType::RSquare)?;
+
+        Ok(TypeMetadata::newArray(elem, len))
+    }
+
+    /// parses `<len x ty>` (the closing angle is left as the current token)
+    fn parse_vector_type(&mut self) -> Result<TypeMetadata, IrError> {
+        self.expect(TokenType::LAngle)?;
+        self.input.pop_front();
+
+        self.expect(TokenType::Int(0.0))?;
+        let len = if let TokenType::Int(int) = &self.current_token()?.typ {
+            *int as usize
+        } else { unreachable!() };
+        self.input.pop_front();
+
+        self.expect_ident("x".into())?;
+        self.input.pop_front();
+
+        let elem = self.parse_type()?;
+        self.input.pop_front();
+
+        self.expect(TokenType::RAngle)?;
+
+        Ok(TypeMetadata::newVector(elem, len))
     }
 
-    fn parse_type(&mut self) -> Result<TypeMetadata, IrError> {
-        let token = self.current_token()?;
+    /// parses `(ty, ty, ...)` (the closing param is left as the current token)
+    fn parse_tuple_type(&mut self) -> Result<TypeMetadata, IrError> {
+        self.expect(TokenType::LParam)?;
+        self.input.pop_front();
 
-        let mut ident = String::new();
+        let mut elems = Vec::new();
 
-        if let TokenType::Ident(text) = &token.typ {
-            ident = text.to_string();
-        } else {
-            Err(IrError::ExpectedTokenButFoundAnUnexpectedOne { 
-                found: token.clone(), 
-                expected: Token { 
-                    typ: TokenType::Ident("abc".to_string()), 
-                    loc: token.loc.clone()
-                } 
-            })?
-        }
+        loop {
+            let current = self.current_token()?;
 
-        if let Some(typ) = TypeMetadata::parse(ident) {
-            Ok(typ)
-        } else {
-            Err(IrError::UnkownType(token.clone()) )
+            if TokenType::RParam == current.typ {
+                break;
+            }
+
+            if TokenType::Comma == current.typ {
+                self.input.pop_front();
+                continue;
+            }
+
+            elems.push( self.parse_type()? );
+            self.input.pop_front();
         }
+
+        Ok(TypeMetadata::newTuple(&elems))
     }
 
     fn parse_cmp(&mut self, var: String) -> Result<Box<dyn Ir>, IrError> {
@@ -842,25 +1922,22 @@ impl IrParser {
             ident.to_owned()
         } else { unreachable!() };
 
-        let mode = match ident.as_str() {
-            "eq" => CmpMode::Eqal,
-            "ne" => CmpMode::NotEqal,
-            "ge" => CmpMode::GreaterThan,
-            "le" => CmpMode::LessThan,
-            "gte" => CmpMode::GreaterThanOrEqual,
-            "lte" => CmpMode::LessThanOrEqual,
-            _=> Err(IrError::Unkown { 
-                what: "compare mode".to_owned(), 
-                name: ident, 
-                loc: curr.loc.to_owned()
-            })?
-        };
+        let loc = curr.loc.to_owned();
 
         self.input.pop_front();
 
         let ty = self.parse_type()?;
         self.input.pop_front();
 
+        // the type is needed to know if `ugt` etc. is unsigned or unordered
+        let Some(mode) = CmpMode::parse(&ident, ty) else {
+            Err(IrError::Unkown { 
+                what: "compare mode".to_owned(), 
+                name: ident, 
+                loc: loc
+            })?
+        };
+
         let ls = self.parse_operand(ty)?;
         self.input.pop_front();
 
@@ -874,13 +1951,21 @@ impl IrParser {
 
         Ok(Cmp::new(mode, ls, rs, Var {
             name: var,
-            ty: TypeMetadata::u8
+            ty: TypeMetadata::i1
         }))
     }
 
     fn parse_load(&mut self, var: String) -> Result<Box<dyn Ir>, IrError> {
         self.input.pop_front();
 
+        if let TokenType::Ident(ident) = &self.current_token()?.typ {
+            if ident == "atomic" {
+                return self.parse_atomic_load(var);
+            }
+        }
+
+        let volatile = self.parse_volatile();
+
         let ty = self.parse_type()?;
 
         let out = Var {
@@ -892,7 +1977,12 @@ impl IrParser {
         let var = self.parse_operand(TypeMetadata::ptr)?;
         self.input.pop_front();
 
-        Ok(Load::new(out, ty, var))
+        let mut load = Load::new(out, ty, var);
+
+        load.volatile = volatile;
+        load.align = self.parse_align()?;
+
+        Ok(load)
     }
 
     fn parse_phi(&mut self, var: String) -> Result<Box<dyn Ir>, IrError> {
@@ -929,6 +2019,7 @@ impl IrParser {
                         name: block,
                         nodes: Vec::new(),
                         varCount: 0,
+                        metadata: BTreeMap::new(),
                     }, Var {
                         name: var,
                         ty: out_ty,
@@ -1027,6 +2118,81 @@ impl IrParser {
         Ok( Neg::new(op, out) )
     }
 
+    fn parse_bitop(&mut self, var: String, kind: ir::BitOpKind) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // popcnt/clz/ctz/bswap
+
+        let typ = self.parse_type()?;
+        self.input.pop_front();
+
+        let out = Var {
+            name: var,
+            ty: typ
+        };
+
+        let op = self.parse_operand(typ)?;
+        self.input.pop_front();
+
+        Ok( ir::BitOp::new(kind, op, out) )
+    }
+
+    fn parse_floatop(&mut self, var: String, kind: ir::FloatOpKind) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // sqrt/fabs/copysign/...
+
+        let ty = self.parse_type()?;
+        self.input.pop_front(); // type
+
+        let out = Var {
+            name: var,
+            ty: ty,
+        };
+
+        let mut args = Vec::new();
+
+        for index in 0..kind.arity() {
+            if index != 0 {
+                self.expect(TokenType::Comma)?;
+                self.input.pop_front();
+            }
+
+            args.push(self.parse_operand(ty)?);
+            self.input.pop_front();
+        }
+
+        Ok( ir::FloatOp::new(kind, args, out) )
+    }
+
+    fn parse_overflowop(&mut self, var: String, overflow: Option<String>, kind: ir::OverflowKind) -> Result<Box<dyn Ir>, IrError> {
+        let Some(overflow) = overflow else {
+            Err(IrError::UnexpectedToken(self.current_token()?.to_owned()))?
+        };
+
+        self.input.pop_front(); // sadd_overflow/uadd_overflow/...
+
+        let ty = self.parse_type()?;
+        self.input.pop_front(); // type
+
+        let out = Var {
+            name: var,
+            ty: ty,
+        };
+
+        let overflow = Var {
+            name: overflow,
+            ty: TypeMetadata::i1,
+        };
+
+        let ls = self.parse_operand(ty)?;
+        self.input.pop_front();
+
+        self.expect(TokenType::Comma)?;
+        self.input.pop_front();
+
+        let rs = self.parse_operand(ty)?;
+        self.input.pop_front();
+
+        Ok( ir::OverflowOp::new(kind, ls, rs, out, overflow) )
+    }
+
     fn parse_select(&mut self, var: String) -> Result<Box<dyn Ir>, IrError> {
         self.input.pop_front(); // select
 
@@ -1116,6 +2282,227 @@ impl IrParser {
             index: index,
         }.clone_box())
     }
+
+    /// parses an element index (like the `2` in `extractelement <4 x f32> %0, 2`)
+    fn parse_index(&mut self) -> Result<usize, IrError> {
+        self.expect(TokenType::Int(0.0))?;
+        let index = if let TokenType::Int(int) = &self.current_token()?.typ {
+            *int as usize
+        } else { unreachable!() };
+        self.input.pop_front();
+
+        Ok(index)
+    }
+
+    fn parse_extractelement(&mut self, var: String) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // extractelement
+
+        let vec_ty = self.parse_type()?;
+        self.input.pop_front();
+
+        self.expect(TokenType::Var(String::new()))?;
+        let vec = if let TokenType::Var(vec) = &self.current_token()?.typ {
+            Var {
+                name: vec.to_owned(),
+                ty: vec_ty,
+            }
+        } else { unreachable!() };
+        self.input.pop_front();
+
+        self.expect(TokenType::Comma)?;
+        self.input.pop_front();
+
+        let index = self.parse_index()?;
+
+        let out_ty = if let Some((elem, _)) = vec_ty.vectorElems() {
+            elem
+        } else {
+            vec_ty // gets catched by the verifier
+        };
+
+        Ok(Box::new(ExtractElement {
+            out: Var {
+                name: var,
+                ty: out_ty,
+            },
+            vec: vec,
+            index: index,
+        }))
+    }
+
+    fn parse_tuple(&mut self, var: String) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // tuple
+
+        let mut elems = Vec::new();
+
+        loop {
+            let ty = self.parse_type()?;
+            self.input.pop_front();
+
+            elems.push( self.parse_operand(ty)? );
+            self.input.pop_front();
+
+            if TokenType::Comma != self.current_token()?.typ {
+                break;
+            }
+
+            self.input.pop_front();
+        }
+
+        let tys = elems.iter().map(|elem| elem.get_ty()).collect::<Vec<_>>();
+
+        Ok(Box::new(MakeTuple {
+            out: Var {
+                name: var,
+                ty: TypeMetadata::newTuple(&tys),
+            },
+            elems: elems,
+        }))
+    }
+
+    fn parse_extractvalue(&mut self, var: String) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // extractvalue
+
+        let tuple_ty = self.parse_type()?;
+        self.input.pop_front();
+
+        self.expect(TokenType::Var(String::new()))?;
+        let tuple = if let TokenType::Var(tuple) = &self.current_token()?.typ {
+            Var {
+                name: tuple.to_owned(),
+                ty: tuple_ty,
+            }
+        } else { unreachable!() };
+        self.input.pop_front();
+
+        self.expect(TokenType::Comma)?;
+        self.input.pop_front();
+
+        let index = self.parse_index()?;
+
+        let out_ty = match tuple_ty.tupleElems().and_then(|elems| elems.get(index).copied()) {
+            Some(elem) => elem,
+            None => tuple_ty, // gets catched by the verifier
+        };
+
+        Ok(Box::new(ExtractValue {
+            out: Var {
+                name: var,
+                ty: out_ty,
+            },
+            tuple: tuple,
+            index: index,
+        }))
+    }
+
+    fn parse_insertelement(&mut self, var: String) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // insertelement
+
+        let vec_ty = self.parse_type()?;
+        self.input.pop_front();
+
+        self.expect(TokenType::Var(String::new()))?;
+        let vec = if let TokenType::Var(vec) = &self.current_token()?.typ {
+            Var {
+                name: vec.to_owned(),
+                ty: vec_ty,
+            }
+        } else { unreachable!() };
+        self.input.pop_front();
+
+        self.expect(TokenType::Comma)?;
+        self.input.pop_front();
+
+        let elem_ty = self.parse_type()?;
+        self.input.pop_front();
+
+        let elem = self.parse_operand(elem_ty)?;
+        self.input.pop_front();
+
+        self.expect(TokenType::Comma)?;
+        self.input.pop_front();
+
+        let index = self.parse_index()?;
+
+        Ok(Box::new(InsertElement {
+            out: Var {
+                name: var,
+                ty: vec_ty,
+            },
+            vec: vec,
+            elem: elem,
+            index: index,
+        }))
+    }
+
+    fn parse_shufflevector(&mut self, var: String) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // shufflevector
+
+        let ty = self.parse_type()?;
+        self.input.pop_front();
+
+        self.expect(TokenType::Var(String::new()))?;
+        let ls = if let TokenType::Var(ls) = &self.current_token()?.typ {
+            Var {
+                name: ls.to_owned(),
+                ty: ty,
+            }
+        } else { unreachable!() };
+        self.input.pop_front();
+
+        self.expect(TokenType::Comma)?;
+        self.input.pop_front();
+
+        self.expect(TokenType::Var(String::new()))?;
+        let rs = if let TokenType::Var(rs) = &self.current_token()?.typ {
+            Var {
+                name: rs.to_owned(),
+                ty: ty,
+            }
+        } else { unreachable!() };
+        self.input.pop_front();
+
+        self.expect(TokenType::Comma)?;
+        self.input.pop_front();
+
+        self.expect(TokenType::LSquare)?;
+        self.input.pop_front();
+
+        let mut mask = Vec::new();
+
+        loop {
+            let current = self.current_token()?;
+
+            if TokenType::RSquare == current.typ {
+                break;
+            }
+
+            if TokenType::Comma == current.typ {
+                self.input.pop_front();
+                continue;
+            }
+
+            mask.push( self.parse_index()? );
+        }
+
+        self.input.pop_front(); // ]
+
+        let out_ty = if let Some((elem, _)) = ty.vectorElems() {
+            TypeMetadata::newVector(elem, mask.len())
+        } else {
+            ty // gets catched by the verifier
+        };
+
+        Ok(Box::new(ShuffleVector {
+            out: Var {
+                name: var,
+                ty: out_ty,
+            },
+            ls: ls,
+            rs: rs,
+            mask: mask,
+        }))
+    }
 }
 
 macro_rules! ParserImplParseMath {
@@ -1156,4 +2543,6 @@ ParserImplParseMath!(parse_mul, Mul);
 ParserImplParseMath!(parse_div, Div);
 ParserImplParseMath!(parse_rem, Rem);
 ParserImplParseMath!(parse_shl, Shl);
-ParserImplParseMath!(parse_shr, Shr);
\ No newline at end of file
+ParserImplParseMath!(parse_shr, Shr);
+ParserImplParseMath!(parse_rotl, Rotl);
+ParserImplParseMath!(parse_rotr, Rotr);
\ No newline at end of file
@@ -1,11 +1,12 @@
 use std::collections::{BTreeMap, HashMap};
 
 use crate::Obj::Linkage;
-use crate::IR::{BlockId, Const, FunctionType, TypeMetadata, Var};
+use crate::Target::CallConv;
+use crate::IR::{BlockId, Const, ConstAdr, FuncId, FunctionType, Global, TypeMetadata, Var, VerifyError};
 
 use crate::prelude::ir::*;
 
-use super::parser::{IrBlock, IrStmt};
+use super::parser::{IrBlock, IrInstr, IrStmt};
 use super::lexer::Loc;
 use super::IrError;
 
@@ -14,6 +15,7 @@ pub struct IrSemnatic<'a> {
     input: &'a mut Vec<IrStmt>,
 
     const_sigs: HashMap<String, Linkage>,
+    global_sigs: HashMap<String, (TypeMetadata, Linkage)>,
     func_sigs: HashMap<String, (FunctionType, Linkage, /*the blocks*/Vec<String>)>,
 }
 
@@ -24,6 +26,7 @@ impl<'a> IrSemnatic<'a> {
             input: exprs,
 
             const_sigs: HashMap::new(),
+            global_sigs: HashMap::new(),
             func_sigs: HashMap::new(),
         }
     }
@@ -32,16 +35,20 @@ impl<'a> IrSemnatic<'a> {
     pub fn verify(&mut self) -> Result<(), IrError> {
         for stmt in &self.input.clone() {
             match stmt {
-                IrStmt::Func { name, ret, args, body, scope, location } => self.add_func(name, *ret, args, scope, body, location)?,
-                IrStmt::Const { name, data: _, location, scope } => self.add_const(name, scope, location)?
+                IrStmt::Func { name, ret, args, body, scope, location, call_conv, .. } => self.add_func(name, *ret, args, scope, body, *call_conv, location)?,
+                IrStmt::Const { name, data: _, adrs: _, location, scope } => self.add_const(name, scope, location)?,
+                IrStmt::Global { name, ty, data: _, location, scope } => self.add_global(name, *ty, scope, location)?,
+                IrStmt::Metadata { .. } => {},
             }
         }
 
         let mut stmts = self.input.to_vec();
         for stmt in &mut stmts {
             match stmt {
-                IrStmt::Func { name, ret, args, body, scope, location } => self.analizye_func(name, *ret, args, body, *scope, location)?,
-                IrStmt::Const { name, data, location, scope } => self.analyize_const(name, data, location, *scope)?,
+                IrStmt::Func { name, ret, args, body, scope, location, personality, .. } => self.analizye_func(name, *ret, args, body, *scope, personality.is_some(), location)?,
+                IrStmt::Const { name, data, adrs, location, scope } => self.analyize_const(name, data, adrs, location, *scope)?,
+                IrStmt::Global { name, ty: _, data, location, scope } => self.analyize_global(name, data, location, *scope)?,
+                IrStmt::Metadata { .. } => {},
             }
         }
 
@@ -50,7 +57,7 @@ impl<'a> IrSemnatic<'a> {
         Ok(())
     }
 
-    fn add_func(&mut self, name: &String, ret: TypeMetadata, args: &(BTreeMap<String, TypeMetadata>, bool),  scope: &Linkage, body: &Vec<(String, IrBlock)>, loc: &Loc) -> Result<(), IrError> {
+    fn add_func(&mut self, name: &String, ret: TypeMetadata, args: &(BTreeMap<String, TypeMetadata>, bool),  scope: &Linkage, body: &Vec<(String, IrBlock)>, call_conv: Option<CallConv>, loc: &Loc) -> Result<(), IrError> {
         if self.func_sigs.contains_key(name) {
             Err(IrError::DefinedTwice {
                 loc: loc.clone(),
@@ -68,6 +75,7 @@ impl<'a> IrSemnatic<'a> {
             args: fun_args,
             ret: ret,
             any_args: false,
+            call_conv: call_conv,
         };
 
         if args.1 {
@@ -97,7 +105,19 @@ impl<'a> IrSemnatic<'a> {
         Ok(())
     }
 
-    fn analizye_func(&mut self, name: &String, ret: TypeMetadata, args: &(BTreeMap<String, TypeMetadata>, bool), body: &mut Vec<(String, IrBlock)>, scope: Linkage, loc: &Loc) -> Result<(), IrError> {
+    fn add_global(&mut self, name: &String, ty: TypeMetadata, scope: &Linkage, loc: &Loc) -> Result<(), IrError> {
+        if self.func_sigs.contains_key(name) || self.const_sigs.contains_key(name) || self.global_sigs.contains_key(name) {
+            Err(IrError::DefinedTwice {
+                loc: loc.clone(),
+                name: name.to_owned()
+            })?
+        }
+
+        self.global_sigs.insert(name.to_owned(), (ty, *scope));
+        Ok(())
+    }
+
+    fn analizye_func(&mut self, name: &String, ret: TypeMetadata, args: &(BTreeMap<String, TypeMetadata>, bool), body: &mut Vec<(String, IrBlock)>, scope: Linkage, personality: bool, loc: &Loc) -> Result<(), IrError> {
         let mut vars = HashMap::new();
 
         let mut blocks = vec![];
@@ -134,6 +154,9 @@ impl<'a> IrSemnatic<'a> {
                     for (_, case) in &switch.cases {
                         branches.push(case.to_owned());
                     }
+                } else if let Some(invoke) = node.inst.as_any().downcast_ref::<Invoke>() {
+                    branches.push(invoke.normal.to_owned());
+                    branches.push(invoke.unwind.to_owned());
                 }
 
                 branches_to.insert(name.to_owned(), branches);
@@ -142,7 +165,7 @@ impl<'a> IrSemnatic<'a> {
 
         for (_, block) in body.iter() {
             for node in &block.body {
-                if let Some(out) = node.inst.output() {
+                for out in node.inst.outputs() {
                     if vars.contains_key(&out.name) {
                         Err(IrError::DefinedTwice { 
                             loc: node.loc.to_owned(),
@@ -155,7 +178,7 @@ impl<'a> IrSemnatic<'a> {
             }
         }
 
-        for (name, block) in body {
+        for (name, block) in body.iter_mut() {
             if blocks.contains(name) {
                 Err(IrError::DefinedTwice {
                     loc: block.loc.to_owned(),
@@ -166,6 +189,8 @@ impl<'a> IrSemnatic<'a> {
             blocks.push(name.to_owned());
 
             for node in &mut block.body {
+                self.resolve_symbol_ref(node);
+
                 let instr = &node.inst;
                 let loc = node.loc.to_owned();
 
@@ -175,6 +200,8 @@ impl<'a> IrSemnatic<'a> {
                     self.analiyze_ret(&mut vars, node, ret, loc.to_owned())?;
                 } else if let Some(node) = any.downcast_ref::<Assign<Var, Const>>() {
                     self.analiyze_assign_const(&mut vars, node, loc.to_owned())?;
+                } else if let Some(node) = any.downcast_ref::<Assign<Var, FuncId>>() {
+                    self.analiyze_assign_func(&mut vars, node, loc.to_owned())?;
                 } else if let Some(node) = any.downcast_ref::<Call>() {
                     self.analyize_call(&mut vars, node, loc.to_owned())?;
                 } else if let Some(node) = any.downcast_ref::<Br>() {
@@ -219,6 +246,12 @@ impl<'a> IrSemnatic<'a> {
                     self.analyze_br(func, &mut vars, br, loc.to_owned())?;
                 } else if let Some(br) = any.downcast_ref::<BrCond>() {
                     self.analyze_brcond(func, &mut vars, br, loc.to_owned())?;
+                } else if let Some(select) = any.downcast_ref::<Select>() {
+                    self.analyze_cond(&mut vars, &select.cond, loc.to_owned())?;
+                } else if let Some(invoke) = any.downcast_ref::<Invoke>() {
+                    self.analyze_invoke(func, &mut vars, invoke, loc.to_owned())?;
+                } else if let Some(statepoint) = any.downcast_ref::<Statepoint>() {
+                    self.analyze_statepoint(&mut vars, statepoint, loc.to_owned())?;
                 }
 
                 for input in node.inst.inputs_mut() {
@@ -233,6 +266,81 @@ impl<'a> IrSemnatic<'a> {
                     input.ty = *ty;
                 }
             }
+
+            self.analyze_tail_calls(func, block)?;
+        }
+
+        self.analyze_unwind(body, personality)?;
+
+        Ok(())
+    }
+
+    /// Checks that the unwind blocks of invokes start with a landing pad
+    /// (and that landing pads are only used at the start of a block of a function with a personality)
+    fn analyze_unwind(&self, body: &Vec<(String, IrBlock)>, personality: bool) -> Result<(), IrError> {
+        for (_, block) in body {
+            for (index, node) in block.body.iter().filter(|node| !node.inst.is_debug()).enumerate() {
+                let err = if let Some(invoke) = node.inst.as_any().downcast_ref::<Invoke>() {
+                    let unwind = body.iter().find(|(name, _)| *name == invoke.unwind.name);
+
+                    if let Some((_, unwind)) = unwind {
+                        if !unwind.body.iter().find(|node| !node.inst.is_debug()).is_some_and(|node| node.inst.is_landingpad()) {
+                            Some(VerifyError::UnwindBlockWithoutLandingPad)
+                        } else if !personality {
+                            Some(VerifyError::MissingPersonality)
+                        } else { None }
+                    } else { None }
+                } else if node.inst.is_landingpad() {
+                    if index != 0 {
+                        Some(VerifyError::LandingPadNotFirst)
+                    } else if !personality {
+                        Some(VerifyError::MissingPersonality)
+                    } else { None }
+                } else { None };
+
+                if let Some(err) = err {
+                    Err(IrError::Boxed {
+                        loc: node.loc.to_owned(),
+                        err: Box::new(err),
+                    })?
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn analyze_tail_calls(&self, func: &String, block: &IrBlock) -> Result<(), IrError> {
+        let Some((caller, _, _)) = self.func_sigs.get(func) else { return Ok(()) };
+
+        let mut nodes = block.body.iter().filter(|node| !node.inst.is_debug()).peekable();
+
+        while let Some(node) = nodes.next() {
+            let Some(call) = node.inst.as_any().downcast_ref::<Call>() else { continue };
+
+            if !call.tail {
+                continue;
+            }
+
+            if !call.returned_by(nodes.peek().map(|next| &next.inst)) {
+                Err(IrError::Boxed {
+                    loc: node.loc.to_owned(),
+                    err: Box::new(VerifyError::TailCallNotInTailPosition),
+                })?
+            }
+
+            let Some((callee, _, _)) = self.func_sigs.get(&call.func.name) else { continue };
+
+            // the calling convention can also be given at the call
+            let mut callee = callee.to_owned();
+            callee.call_conv = call.func.ty.call_conv.or(callee.call_conv);
+
+            if let Err(err) = call.verify_tail(caller, &callee) {
+                Err(IrError::Boxed {
+                    loc: node.loc.to_owned(),
+                    err: Box::new(err),
+                })?
+            }
         }
 
         Ok(())
@@ -270,6 +378,32 @@ impl<'a> IrSemnatic<'a> {
         Ok(())
     }
 
+    /// The parser can't tell apart references to constants, globals and functions (all are `%x = ptr name`)
+    /// so the references to globals and functions are resolved here
+    fn resolve_symbol_ref(&self, node: &mut IrInstr) {
+        let Some(assign) = node.inst.as_any().downcast_ref::<Assign<Var, Const>>() else {
+            return;
+        };
+
+        if let Some((ty, _, _)) = self.func_sigs.get(&assign.inner2.name) {
+            let func = FuncId {
+                name: assign.inner2.name.to_owned(),
+                ty: ty.to_owned(),
+            };
+
+            node.inst = Assign::new(assign.inner1.to_owned(), func);
+        } else if let Some((ty, linkage)) = self.global_sigs.get(&assign.inner2.name) {
+            let global = Global {
+                name: assign.inner2.name.to_owned(),
+                ty: *ty,
+                init: None,
+                linkage: *linkage,
+            };
+
+            node.inst = Assign::new(assign.inner1.to_owned(), global);
+        }
+    }
+
     fn analiyze_assign_const(&mut self, _vars: &mut HashMap<String, TypeMetadata>, node: &Assign<Var, Const>, loc: Loc) -> Result<(), IrError> {
         let name = &node.inner2.name;
         if !self.const_sigs.contains_key(name) {
@@ -283,6 +417,19 @@ impl<'a> IrSemnatic<'a> {
         Ok(())
     }
 
+    fn analiyze_assign_func(&mut self, _vars: &mut HashMap<String, TypeMetadata>, node: &Assign<Var, FuncId>, loc: Loc) -> Result<(), IrError> {
+        let name = &node.inner2.name;
+        if !self.func_sigs.contains_key(name) {
+            Err(IrError::Unkown {
+                what: "function".to_owned(),
+                loc: loc.to_owned(),
+                name: name.to_owned(),
+            })?
+        }
+
+        Ok(())
+    }
+
     fn analyize_call(&mut self, vars: &mut HashMap<String, TypeMetadata>, node: &Call, loc: Loc) -> Result<(), IrError> {
         let name = &node.func.name;
         let mut sig = node.func.ty.to_owned();
@@ -345,6 +492,55 @@ impl<'a> IrSemnatic<'a> {
         Ok(())
     }
 
+    fn analyze_invoke(&mut self, func: &String, vars: &mut HashMap<String, TypeMetadata>, node: &Invoke, loc: Loc) -> Result<(), IrError> {
+        // the called function is checked like the one of a normal call
+        self.analyize_call(vars, &Call {
+            out: node.out.to_owned(),
+            func: node.func.to_owned(),
+            args: node.args.to_owned(),
+            attrs: crate::IR::FnAttrs::new(),
+            tail: false,
+        }, loc.to_owned())?;
+
+        let (_, _, blocks) = self.func_sigs.get(func).unwrap();
+
+        for block in [&node.normal, &node.unwind] {
+            if !blocks.contains(&block.name) {
+                Err(IrError::Unkown { 
+                    what: "block".to_owned(), 
+                    name: block.name.to_owned(), 
+                    loc: loc.to_owned()
+                })?
+            }
+        }
+
+        Ok(())
+    }
+
+    fn analyze_statepoint(&mut self, vars: &mut HashMap<String, TypeMetadata>, node: &Statepoint, loc: Loc) -> Result<(), IrError> {
+        // the called function is checked like the one of a normal call
+        self.analyize_call(vars, &Call {
+            out: node.out.to_owned(),
+            func: node.func.to_owned(),
+            args: node.args.to_owned(),
+            attrs: crate::IR::FnAttrs::new(),
+            tail: false,
+        }, loc.to_owned())?;
+
+        for root in &node.gc {
+            if let Some(ty) = vars.get(&root.name) {
+                if *ty != TypeMetadata::ptr {
+                    Err(IrError::Boxed {
+                        loc: loc.to_owned(),
+                        err: Box::new(VerifyError::GcRootNotPtr(*ty)),
+                    })?
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     fn analiyze_block(&mut self, func: &String, node: &Br, loc: Loc) -> Result<(), IrError> {
         let br_block = &node.inner1.name;
 
@@ -396,14 +592,51 @@ impl<'a> IrSemnatic<'a> {
             Err(IrError::Unkown { 
                 what: "variable".to_owned(), 
                 name: node.inner1.name.to_owned(), 
-                loc: loc
+                loc: loc.to_owned()
             })?
         }
 
+        self.analyze_cond(vars, &node.inner1, loc)
+    }
+
+    /// Checks that the condition (of a `br cond` or `select`) is an i1
+    fn analyze_cond(&mut self, vars: &mut HashMap<String, TypeMetadata>, cond: &Var, loc: Loc) -> Result<(), IrError> {
+        if let Some(ty) = vars.get(&cond.name) {
+            if *ty != TypeMetadata::i1 {
+                Err(IrError::Boxed {
+                    loc: loc,
+                    err: Box::new(VerifyError::ConditionNotI1(*ty)),
+                })?
+            }
+        }
+
         Ok(())
     }
-    fn analyize_const(&mut self, _: &String, _: &Vec<u8>, _: &Loc, _: Linkage) -> Result<(), IrError> {
-        Ok(()) // what can go wrong on constants?
+    fn analyize_const(&mut self, _: &String, _: &Vec<u8>, adrs: &Vec<ConstAdr>, loc: &Loc, _: Linkage) -> Result<(), IrError> {
+        for adr in adrs {
+            let name = &adr.symbol;
+
+            if !self.func_sigs.contains_key(name) && !self.const_sigs.contains_key(name) && !self.global_sigs.contains_key(name) {
+                Err(IrError::Unkown {
+                    what: "symbol".to_owned(),
+                    loc: loc.to_owned(),
+                    name: name.to_owned(),
+                })?
+            }
+        }
+
+        Ok(())
+    }
+
+    fn analyize_global(&mut self, name: &String, data: &Option<Vec<u8>>, loc: &Loc, scope: Linkage) -> Result<(), IrError> {
+        if scope == Linkage::Extern && data.is_some() {
+            Err(IrError::ExternGlobalWithInit {
+                name: name.to_owned(),
+                loc: loc.to_owned(),
+            })?
+        }
+
+        Ok(())
     }
 
     fn analyze_switch(&mut self, func: &String, vars: &mut HashMap<String, TypeMetadata>, node: &Switch, loc: Loc) -> Result<(), IrError> {
@@ -1,10 +1,15 @@
-use std::fmt::Display;
+use std::{collections::HashMap, fmt::Display, sync::{LazyLock, RwLock}};
+
+use crate::Target::{Arch, Environment, ObjFormat, Triple, Vendor, OS};
 
 /// Stores a type and a value of that type
 /// 
 /// If you want an empty Type consider using `TypeMetadata`
 #[derive(Debug, Clone, Copy)]
 pub enum Type {
+    /// A boolean (i1) with a value
+    i1(bool),
+
     /// Just an u8 with a value
     u8(u8),
     /// Just an u16 with a value
@@ -13,6 +18,8 @@ pub enum Type {
     u32(u32),
     /// Just an u64 with a value
     u64(u64),
+    /// Just an u128 with a value
+    u128(u128),
 
     /// Just an i8 with a value
     i8(i8),
@@ -22,6 +29,8 @@ pub enum Type {
     i32(i32),
     /// Just an i64 with a value
     i64(i64),
+    /// Just an i128 with a value
+    i128(i128),
     /// 64Bit pointer
     ptr(i64),
 
@@ -38,14 +47,17 @@ pub enum Type {
 impl PartialEq for Type {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
+            (Self::i1(l0), Self::i1(r0)) => l0 == r0,
             (Self::u8(l0), Self::u8(r0)) => l0 == r0,
             (Self::u16(l0), Self::u16(r0)) => l0 == r0,
             (Self::u32(l0), Self::u32(r0)) => l0 == r0,
             (Self::u64(l0), Self::u64(r0)) => l0 == r0,
+            (Self::u128(l0), Self::u128(r0)) => l0 == r0,
             (Self::i8(l0), Self::i8(r0)) => l0 == r0,
             (Self::i16(l0), Self::i16(r0)) => l0 == r0,
             (Self::i32(l0), Self::i32(r0)) => l0 == r0,
             (Self::i64(l0), Self::i64(r0)) => l0 == r0,
+            (Self::i128(l0), Self::i128(r0)) => l0 == r0,
             (Self::ptr(l0), Self::ptr(r0)) => l0 == r0,
             (Self::f32(l0), Self::f32(r0)) => l0 == r0,
             (Self::f64(l0), Self::f64(r0)) => l0 == r0,
@@ -65,6 +77,9 @@ impl std::hash::Hash for Type {
 /// Stores type metadata (just the type without data)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum TypeMetadata {
+    /// i1 (a boolean)
+    i1,
+
     /// u8
     u8,
     /// u16
@@ -73,6 +88,8 @@ pub enum TypeMetadata {
     u32,
     /// u64
     u64,
+    /// u128
+    u128,
 
     /// i8
     i8,
@@ -82,6 +99,8 @@ pub enum TypeMetadata {
     i32,
     /// i64
     i64,
+    /// i128
+    i128,
     /// ptr
     ptr,
 
@@ -93,6 +112,79 @@ pub enum TypeMetadata {
 
     /// f64
     f64,
+
+    /// A struct (the fields are stored in the aggregate table)
+    Struct(AggregateId),
+
+    /// An array (the element type and length are stored in the aggregate table)
+    Array(AggregateId),
+
+    /// A simd vector (the element type and length are stored in the aggregate table)
+    Vector(AggregateId),
+
+    /// Multiple values which are returned by a function (the types are stored in the aggregate table)
+    Tuple(AggregateId),
+}
+
+/// A handle to an aggregate type which is stored in the global aggregate table
+/// 
+/// Aggregates are interned, so two structurally equal aggregates always have the same id
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AggregateId(usize);
+
+/// The description of an aggregate type
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AggregateType {
+    /// A struct with the given field types
+    Struct(Vec<TypeMetadata>),
+    /// An array with `len` elements of the type `elem`
+    Array {
+        /// the type of the elements
+        elem: TypeMetadata,
+        /// the amount of elements
+        len: usize,
+    },
+    /// A simd vector with `len` elements of the type `elem`
+    Vector {
+        /// the type of the elements
+        elem: TypeMetadata,
+        /// the amount of elements
+        len: usize,
+    },
+    /// The values of a function with multiple return values
+    Tuple(Vec<TypeMetadata>),
+}
+
+/// The aggregate types (indexed by their id) and the ids of them (keyed by their description)
+#[derive(Default)]
+struct AggregateTable {
+    types: Vec<AggregateType>,
+    ids: HashMap<AggregateType, AggregateId>,
+}
+
+static AGGREGATES: LazyLock<RwLock<AggregateTable>> = LazyLock::new(|| RwLock::new(AggregateTable::default()));
+
+fn intern(ty: AggregateType) -> AggregateId {
+    let mut table = AGGREGATES.write().expect("the aggregate table is poisoned");
+
+    if let Some(id) = table.ids.get(&ty) {
+        return *id;
+    }
+
+    let id = AggregateId(table.types.len());
+
+    table.types.push(ty.to_owned());
+    table.ids.insert(ty, id);
+
+    id
+}
+
+fn align_to(value: usize, align: usize) -> usize {
+    if align == 0 {
+        return value;
+    }
+
+    value.div_ceil(align) * align
 }
 
 impl Type {
@@ -102,12 +194,15 @@ impl Type {
             Type::u16(val) => *val as f64,
             Type::u32(val) => *val as f64,
             Type::u64(val) => *val as f64,
+            Type::u128(val) => *val as f64,
             Type::i16(val) => *val as f64,
             Type::i32(val) => *val as f64,
             Type::i64(val) => *val as f64,
+            Type::i128(val) => *val as f64,
             Type::ptr(adr) => *adr as f64,
             Type::Void => 0.0,
             Type::u8(val) => *val as f64,
+            Type::i1(val) => *val as u8 as f64,
             Type::i8(val) => *val as f64,
             Type::f32(val) => *val as f64,
             Type::f64(val) => *val as f64,
@@ -120,15 +215,21 @@ impl Type {
             TypeMetadata::u16 => Type::u16(value as u16),
             TypeMetadata::u32 => Type::u32(value as u32),
             TypeMetadata::u64 => Type::u64(value as u64),
+            TypeMetadata::u128 => Type::u128(value as u128),
             TypeMetadata::i16 => Type::i16(value as i16),
             TypeMetadata::i32 => Type::i32(value as i32),
             TypeMetadata::i64 => Type::i64(value as i64),
+            TypeMetadata::i128 => Type::i128(value as i128),
             TypeMetadata::ptr => Type::ptr(value as i64),
             TypeMetadata::Void => Type::Void,
             TypeMetadata::u8 => Type::u8(value as u8),
+            TypeMetadata::i1 => Type::i1(value != 0.0),
             TypeMetadata::i8 => Type::i8(value as i8),
             TypeMetadata::f32 => Type::f32(value as f32),
             TypeMetadata::f64 => Type::f64(value as f64),
+            TypeMetadata::Struct(_) | TypeMetadata::Array(_) => panic!("aggregates ({}) can't be constructed from an intenger", ty),
+            TypeMetadata::Vector(_) => panic!("vectors ({}) can't be constructed from an intenger", ty),
+            TypeMetadata::Tuple(_) => panic!("tuples ({}) can't be constructed from an intenger", ty),
         }
     }
 }
@@ -138,22 +239,28 @@ impl TypeMetadata {
     #[inline]
     pub fn bitSize(&self) -> usize {
         match self {
+            TypeMetadata::i1 => 1,
             TypeMetadata::u8 | TypeMetadata::i8 => 8,
             TypeMetadata::u16 | TypeMetadata::i16 => 16,
             TypeMetadata::u32 | TypeMetadata::i32 => 32,
             TypeMetadata::u64 | TypeMetadata::i64 => 64,
+            TypeMetadata::u128 | TypeMetadata::i128 => 128,
             TypeMetadata::ptr => 64,
             TypeMetadata::Void => 0,
 
-            TypeMetadata::f32 => 4,
-            TypeMetadata::f64 => 8,
+            TypeMetadata::f32 => 32,
+            TypeMetadata::f64 => 64,
+
+            TypeMetadata::Struct(_) | TypeMetadata::Array(_) | TypeMetadata::Vector(_) | TypeMetadata::Tuple(_) => self.size(&Self::defaultLayout()) * 8,
         }
     }
 
     /// Returns the size of the type in bytes
     #[inline]
     pub fn byteSize(&self) -> usize {
-        if *self != TypeMetadata::Void {
+        if *self == TypeMetadata::i1 {
+            1 // booleans still need one byte
+        } else if *self != TypeMetadata::Void {
             self.bitSize() / 8
         } else {
             0
@@ -167,6 +274,7 @@ impl TypeMetadata {
             TypeMetadata::i16 => true,
             TypeMetadata::i32 => true,
             TypeMetadata::i64 => true,
+            TypeMetadata::i128 => true,
 
             _ => false,
         }
@@ -182,19 +290,231 @@ impl TypeMetadata {
         }
     }
 
+    /// Returns if it is a 128bit intenger (which doesn't fit into a single register)
+    #[inline]
+    pub fn wide(&self) -> bool {
+        matches!(self, TypeMetadata::u128 | TypeMetadata::i128)
+    }
+
+    /// Creates a new struct type out of the given fields
+    pub fn newStruct(fields: &[TypeMetadata]) -> Self {
+        TypeMetadata::Struct(intern(AggregateType::Struct(fields.to_vec())))
+    }
+
+    /// Creates a new array type with `len` elements of the type `elem`
+    pub fn newArray(elem: TypeMetadata, len: usize) -> Self {
+        TypeMetadata::Array(intern(AggregateType::Array { elem: elem, len: len }))
+    }
+
+    /// Creates a new vector type with `len` elements of the type `elem`
+    pub fn newVector(elem: TypeMetadata, len: usize) -> Self {
+        TypeMetadata::Vector(intern(AggregateType::Vector { elem: elem, len: len }))
+    }
+
+    /// Creates a new tuple type out of the given values
+    pub fn newTuple(elems: &[TypeMetadata]) -> Self {
+        TypeMetadata::Tuple(intern(AggregateType::Tuple(elems.to_vec())))
+    }
+
+    /// Returns if it is a struct or array type
+    #[inline]
+    pub fn aggregate(&self) -> bool {
+        matches!(self, TypeMetadata::Struct(_) | TypeMetadata::Array(_))
+    }
+
+    /// Returns if it is a vector type
+    #[inline]
+    pub fn vector(&self) -> bool {
+        matches!(self, TypeMetadata::Vector(_))
+    }
+
+    /// Returns if it is a tuple type
+    #[inline]
+    pub fn tuple(&self) -> bool {
+        matches!(self, TypeMetadata::Tuple(_))
+    }
+
+    /// Returns the types of the values of the tuple (or none if it isn't a tuple)
+    pub fn tupleElems(&self) -> Option<Vec<TypeMetadata>> {
+        match self.aggregateType()? {
+            AggregateType::Tuple(elems) => Some(elems),
+            _ => None,
+        }
+    }
+
+    /// Returns the element type and the amount of elements of the vector (or none if it isn't a vector)
+    pub fn vectorElems(&self) -> Option<(TypeMetadata, usize)> {
+        match self.aggregateType()? {
+            AggregateType::Vector { elem, len } => Some((elem, len)),
+            _ => None,
+        }
+    }
+
+    /// Returns the description of the aggregate type (or none if it isn't an aggregate)
+    pub fn aggregateType(&self) -> Option<AggregateType> {
+        let id = match self {
+            TypeMetadata::Struct(id) | TypeMetadata::Array(id) | TypeMetadata::Vector(id) | TypeMetadata::Tuple(id) => id,
+            _ => return None,
+        };
+
+        let table = AGGREGATES.read().expect("the aggregate table is poisoned");
+        table.types.get(id.0).cloned()
+    }
+
+    /// The layout which is used by target independent functions like `bitSize`
+    /// (it's the x86-64 layout)
+    pub(crate) fn defaultLayout() -> Triple {
+        Triple {
+            arch: Arch::X86_64,
+            vendor: Vendor::Unknown,
+            os: OS::Linux,
+            env: Environment::Gnu,
+            bin: ObjFormat::Elf,
+        }
+    }
+
+    /// Returns the size of the type in bytes for the given target
+    pub fn size(&self, triple: &Triple) -> usize {
+        match self {
+            TypeMetadata::i1 | TypeMetadata::u8 | TypeMetadata::i8 => 1,
+            TypeMetadata::u16 | TypeMetadata::i16 => 2,
+            TypeMetadata::u32 | TypeMetadata::i32 | TypeMetadata::f32 => 4,
+            TypeMetadata::u64 | TypeMetadata::i64 | TypeMetadata::f64 => 8,
+            TypeMetadata::u128 | TypeMetadata::i128 => 16,
+            TypeMetadata::ptr => triple.ptrSize(),
+            TypeMetadata::Void => 0,
+
+            TypeMetadata::Struct(_) => {
+                let Some(AggregateType::Struct(fields)) = self.aggregateType() else { unreachable!() };
+
+                let mut size = 0;
+
+                for field in &fields {
+                    size = align_to(size, field.align(triple)) + field.size(triple);
+                }
+
+                align_to(size, self.align(triple))
+            },
+            TypeMetadata::Array(_) => {
+                let Some(AggregateType::Array { elem, len }) = self.aggregateType() else { unreachable!() };
+                elem.size(triple) * len
+            },
+            TypeMetadata::Vector(_) => {
+                let Some(AggregateType::Vector { elem, len }) = self.aggregateType() else { unreachable!() };
+                elem.size(triple) * len
+            },
+            // every value of a tuple has its own eightbyte (like it has its own register)
+            TypeMetadata::Tuple(_) => {
+                let Some(AggregateType::Tuple(elems)) = self.aggregateType() else { unreachable!() };
+                elems.len() * 8
+            },
+        }
+    }
+
+    /// Returns the alignment of the type in bytes for the given target
+    pub fn align(&self, triple: &Triple) -> usize {
+        match self {
+            // i386 sysv only aligns 64bit values to 4 bytes
+            TypeMetadata::u64 | TypeMetadata::i64 | TypeMetadata::f64 
+                if triple.arch == Arch::X86 && triple.os != OS::Win32 => 4,
+
+            TypeMetadata::Void => 1,
+
+            TypeMetadata::Struct(_) => {
+                let Some(AggregateType::Struct(fields)) = self.aggregateType() else { unreachable!() };
+
+                fields.iter()
+                    .map(|field| field.align(triple))
+                    .max()
+                    .unwrap_or(1)
+            },
+            TypeMetadata::Array(_) => {
+                let Some(AggregateType::Array { elem, .. }) = self.aggregateType() else { unreachable!() };
+                elem.align(triple)
+            },
+            TypeMetadata::Tuple(_) => 8,
+
+            _ => self.size(triple),
+        }
+    }
+
+    /// Returns the offset in bytes of the field (or array element) with the given index
+    pub fn fieldOffset(&self, index: usize, triple: &Triple) -> Option<usize> {
+        match self.aggregateType()? {
+            AggregateType::Struct(fields) => {
+                let mut offset = 0;
+
+                for (field_index, field) in fields.iter().enumerate() {
+                    offset = align_to(offset, field.align(triple));
+
+                    if field_index == index {
+                        return Some(offset);
+                    }
+
+                    offset += field.size(triple);
+                }
+
+                None
+            },
+            AggregateType::Array { elem, len } | AggregateType::Vector { elem, len } => {
+                if index < len {
+                    Some(elem.size(triple) * index)
+                } else {
+                    None
+                }
+            },
+            AggregateType::Tuple(elems) => {
+                if index < elems.len() {
+                    Some(8 * index)
+                } else {
+                    None
+                }
+            },
+        }
+    }
+
+    /// Returns all scalar types which are contained in the type with their offsets
+    pub fn scalars(&self, triple: &Triple) -> Vec<(usize, TypeMetadata)> {
+        let Some(aggregate) = self.aggregateType() else {
+            return vec![(0, *self)];
+        };
+
+        let elems = match aggregate {
+            AggregateType::Struct(fields) => fields,
+            AggregateType::Array { elem, len } | AggregateType::Vector { elem, len } => vec![elem; len],
+            AggregateType::Tuple(elems) => elems,
+        };
+
+        let mut scalars = Vec::new();
+
+        for (index, elem) in elems.iter().enumerate() {
+            let offset = self.fieldOffset(index, triple).expect("the index should be valid");
+
+            for (inner, scalar) in elem.scalars(triple) {
+                scalars.push((offset + inner, scalar));
+            }
+        }
+
+        scalars
+    }
+
     /// returns the parsed typemetadata
     #[inline]
     pub fn parse(string: String) -> Option<Self> {
         match string.as_str() {
+            "i1" | "bool" => Some(TypeMetadata::i1),
+
             "u8" => Some(TypeMetadata::u8),
             "u16" => Some(TypeMetadata::u16),
             "u32" => Some(TypeMetadata::u32),
             "u64" => Some(TypeMetadata::u64),
+            "u128" => Some(TypeMetadata::u128),
 
             "i8" => Some(TypeMetadata::i8),
             "i16" => Some(TypeMetadata::i16),
             "i32" => Some(TypeMetadata::i32),
             "i64" => Some(TypeMetadata::i64),
+            "i128" => Some(TypeMetadata::i128),
 
             "ptr" => Some(TypeMetadata::ptr),
 
@@ -211,15 +531,19 @@ impl TypeMetadata {
 impl Display for Type {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", match &self {
+            Type::i1(b) => format!("i1 {}", *b as u8),
+
             Type::u8(i) => format!("u8 {}", i),
             Type::u16(i) => format!("u16 {}", i),
             Type::u32(i) => format!("u32 {}", i),
             Type::u64(i) => format!("u64 {}", i),
+            Type::u128(i) => format!("u128 {}", i),
 
             Type::i8(i) => format!("i8 {}", i),
             Type::i16(i) => format!("i16 {}", i),
             Type::i32(i) => format!("i32 {}", i),
             Type::i64(i) => format!("i64 {}", i),
+            Type::i128(i) => format!("i128 {}", i),
 
             Type::ptr(adr) => format!("ptr {:#04x}", adr),
             Type::Void => format!("void"),
@@ -239,21 +563,46 @@ impl crate::Support::AsAny for Type {
 impl Display for TypeMetadata {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", match &self {
+            TypeMetadata::i1 => "i1",
+
             TypeMetadata::u8 => "u8",
             TypeMetadata::u16 => "u16",
             TypeMetadata::u32 => "u32",
             TypeMetadata::u64 => "u64",
+            TypeMetadata::u128 => "u128",
 
             TypeMetadata::i8 => "i8",
             TypeMetadata::i16 => "i16",
             TypeMetadata::i32 => "i32",
             TypeMetadata::i64 => "i64",
+            TypeMetadata::i128 => "i128",
 
             TypeMetadata::ptr => "ptr",
             TypeMetadata::Void => "void",
 
             TypeMetadata::f32 => "f32",
             TypeMetadata::f64 => "f64",
+
+            TypeMetadata::Struct(_) | TypeMetadata::Array(_) | TypeMetadata::Vector(_) | TypeMetadata::Tuple(_) => {
+                return match self.aggregateType().expect("expected valid aggregate") {
+                    AggregateType::Struct(fields) => {
+                        let fields = fields.iter()
+                            .map(|field| field.to_string())
+                            .collect::<Vec<String>>();
+
+                        write!(f, "{{{}}}", fields.join(", "))
+                    },
+                    AggregateType::Array { elem, len } => write!(f, "[{} x {}]", len, elem),
+                    AggregateType::Vector { elem, len } => write!(f, "<{} x {}>", len, elem),
+                    AggregateType::Tuple(elems) => {
+                        let elems = elems.iter()
+                            .map(|elem| elem.to_string())
+                            .collect::<Vec<String>>();
+
+                        write!(f, "({})", elems.join(", "))
+                    },
+                };
+            }
         })
     }
 }
@@ -261,15 +610,19 @@ impl Display for TypeMetadata {
 impl From<Type> for TypeMetadata {
     fn from(value: Type) -> Self {
         match value {
+            Type::i1(_) => TypeMetadata::i1,
+
             Type::u8(_) => TypeMetadata::u8,
             Type::u16(_) => TypeMetadata::u16,
             Type::u32(_) => TypeMetadata::u32,
             Type::u64(_) => TypeMetadata::u64,
+            Type::u128(_) => TypeMetadata::u128,
 
             Type::i8(_) => TypeMetadata::i8,
             Type::i16(_) => TypeMetadata::i16,
             Type::i32(_) => TypeMetadata::i32,
             Type::i64(_) => TypeMetadata::i64,
+            Type::i128(_) => TypeMetadata::i128,
 
             Type::ptr(_) => TypeMetadata::ptr,
             Type::Void => TypeMetadata::Void,
@@ -283,21 +636,28 @@ impl From<Type> for TypeMetadata {
 impl From<TypeMetadata> for Type {
     fn from(value: TypeMetadata) -> Self {
         match value {
+            TypeMetadata::i1 => Type::i1(false),
+
             TypeMetadata::u8 => Type::u8(0),
             TypeMetadata::u16 => Type::u16(0),
             TypeMetadata::u32 => Type::u32(0),
             TypeMetadata::u64 => Type::u64(0),
+            TypeMetadata::u128 => Type::u128(0),
 
             TypeMetadata::i8 => Type::i8(0),
             TypeMetadata::i16 => Type::i16(0),
             TypeMetadata::i32 => Type::i32(0),
             TypeMetadata::i64 => Type::i64(0),
+            TypeMetadata::i128 => Type::i128(0),
 
             TypeMetadata::ptr => Type::ptr(0),
             TypeMetadata::Void => Type::Void,
 
             TypeMetadata::f32 => Type::f32(0.0),
             TypeMetadata::f64 => Type::f64(0.0),
+
+            // aggregates, vectors and tuples don't have an immediate representation
+            TypeMetadata::Struct(_) | TypeMetadata::Array(_) | TypeMetadata::Vector(_) | TypeMetadata::Tuple(_) => Type::Void,
         }
     }
 }
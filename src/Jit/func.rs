@@ -19,6 +19,9 @@ pub struct JitFunction<T> {
     pub code: Vec<u8>,
     tmp: Vec<T>,
     mem: *mut c_void,
+
+    /// the absolute adresses (offset, offset of the target + addend) which get resolved when allocating
+    pub(crate) abs_relocs: Vec<(usize, i64)>,
 }
 
 impl<T> JitFunction<T> {
@@ -28,6 +31,7 @@ impl<T> JitFunction<T> {
             code: code,
             tmp: vec![],
             mem: 0 as *mut c_void,
+            abs_relocs: vec![],
         }
     }
 
@@ -40,6 +44,12 @@ impl<T> JitFunction<T> {
 
         ptr::copy_nonoverlapping(self.code.as_ptr(), mem as *mut u8, self.code.len());
 
+        for (at, target) in &self.abs_relocs {
+            let adr = (mem as i64 + target) as u64;
+
+            ptr::copy_nonoverlapping(adr.to_le_bytes().as_ptr(), (mem as *mut u8).add(*at), 8);
+        }
+
         self.mem = mem;
 
         mem
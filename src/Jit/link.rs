@@ -10,6 +10,9 @@ pub struct JitLinker {
     
     pub(crate) reloc_with_custom_actions: Vec<(Link, Box<dyn Fn(Link, &mut Vec<u8>, usize)>)>,
     pub(crate) relocs: Vec<Link>,
+
+    /// (offset, offset of the target + addend) of the absolute adresses
+    abs_relocs: Vec<(usize, i64)>,
 }
 
 impl JitLinker {
@@ -21,6 +24,8 @@ impl JitLinker {
 
             reloc_with_custom_actions: Vec::new(),
             relocs: Vec::new(),
+
+            abs_relocs: Vec::new(),
         }
     }
 
@@ -83,10 +88,19 @@ impl JitLinker {
             action(reloc.to_owned(), &mut out, from);
         }
 
+        self.abs_relocs.clear();
+
         for reloc in &self.relocs {
             let from = *positions.get(reloc.from.as_str()).expect(&format!("Unkown symbol: {}", reloc.from));
             let mut to = *positions.get(&reloc.to.as_str()).expect(&format!("Unkown symbol: {}", reloc.to));
 
+            if self.labels.contains_key(&reloc.from) {
+                // links out of data are absolute adresses
+                // which are only known after the code was placed into memory
+                self.abs_relocs.push((from + reloc.at, to as i64 + reloc.addend));
+                continue;
+            }
+
             let offset = reloc.at + from;
             let offset = offset as i64 + reloc.addend;
 
@@ -110,7 +124,9 @@ impl JitLinker {
 
     /// Links the code and puts it into a page aligned `JitFunction`
     pub unsafe fn engine<T>(&mut self) -> JitFunction<T> {
-        let func: JitFunction<T> = JitFunction::new(self.link());
+        let mut func: JitFunction<T> = JitFunction::new(self.link());
+
+        func.abs_relocs = self.abs_relocs.clone();
 
         func
     }
@@ -45,7 +45,7 @@ impl Module {
 
             match typ {
                 Decl::Function => map.define_func(name, data.to_owned()),
-                Decl::Data => map.define_data(name, data.to_owned()),
+                Decl::Data | Decl::ZeroData => map.define_data(name, data.to_owned()),
                 Decl::Constant => map.define_data(name, data.to_owned()),
             }
         }
@@ -1,4 +1,6 @@
 mod wrapper;
+mod unwind;
+mod stackmap;
 mod dll;
 mod exe;
 
@@ -6,5 +8,7 @@ pub use wrapper::{
     ObjectBuilder,
     Decl, Link, Linkage,
 };
+pub use unwind::{UnwindInfo, CallSite};
+pub use stackmap::{StackMapLocation, StackMapRecord};
 pub use dll::*;
 pub use exe::*;
\ No newline at end of file
@@ -0,0 +1,137 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+
+use object::write::{Object, Relocation, SectionId, Symbol, SymbolId, SymbolSection};
+use object::{RelocationEncoding, RelocationFlags, RelocationKind, SectionKind, SymbolFlags, SymbolKind, SymbolScope};
+
+use super::{Decl, Linkage, ObjectBuilder};
+
+/// Where a gc root is stored while the function is suspended at a statepoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StackMapLocation {
+    /// The pointer is stored in the register
+    Register(gimli::Register),
+    /// The pointer is stored in memory at `[reg + offset]`
+    Indirect(gimli::Register, i32),
+}
+
+/// The gc roots which are live at a statepoint
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StackMapRecord {
+    /// The id of the statepoint
+    pub id: u64,
+    /// The offset of the return adress of the call (relative to the start of the function)
+    pub offset: u32,
+    /// Where the gc roots are stored
+    pub locations: Vec<StackMapLocation>,
+}
+
+impl ObjectBuilder {
+    /// Sets the stack maps of the function
+    ///
+    /// `stack_size` is the size of the frame (without the return adress)
+    pub fn stack_map(&mut self, func: &str, stack_size: u64, records: Vec<StackMapRecord>) {
+        self.stack_maps.insert(func.to_string(), (stack_size, records));
+    }
+
+    /// Writes the `.llvm_stackmaps` section (in the format of the llvm stack maps v3, so existing gc runtimes can parse it)
+    pub(crate) fn emit_stack_maps(&self, obj: &mut Object<'_>, syms: &BTreeMap<String, (Option<SectionId>, Option</*offsest*/u64>, SymbolId, Decl, u64, Linkage)>) -> Result<(), Box<dyn Error>> {
+        let funcs = self.stack_maps.iter()
+            .filter_map(|(name, map)| match syms.get(name) {
+                Some((_, Some(_), sym, Decl::Function, _, _)) => Some((*sym, map)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        let mut data = Vec::new();
+        // the function adresses (stored as: offset, function)
+        let mut relocs = Vec::new();
+
+        // header
+        data.push(3); // version
+        data.push(0);
+        data.extend_from_slice(&0u16.to_le_bytes());
+        data.extend_from_slice(&(funcs.len() as u32).to_le_bytes());
+        data.extend_from_slice(&0u32.to_le_bytes()); // constants
+        data.extend_from_slice(&(funcs.iter().map(|(_, (_, records))| records.len()).sum::<usize>() as u32).to_le_bytes());
+
+        for (sym, (stack_size, records)) in &funcs {
+            relocs.push((data.len() as u64, *sym));
+
+            data.extend_from_slice(&0u64.to_le_bytes());
+            data.extend_from_slice(&stack_size.to_le_bytes());
+            data.extend_from_slice(&(records.len() as u64).to_le_bytes());
+        }
+
+        for (_, (_, records)) in &funcs {
+            for record in records {
+                write_record(&mut data, record);
+            }
+        }
+
+        // writable, so the adresses of the functions don't create text relocations in position independent executables
+        let section = obj.add_section(vec![], ".llvm_stackmaps".as_bytes().to_vec(), SectionKind::Data);
+        obj.set_section_data(section, data, 8);
+
+        for (offset, sym) in relocs {
+            obj.add_relocation(section, Relocation {
+                offset: offset,
+                symbol: sym,
+                addend: 0,
+                flags: RelocationFlags::Generic {
+                    kind: RelocationKind::Absolute,
+                    encoding: RelocationEncoding::Generic,
+                    size: 64,
+                },
+            })?;
+        }
+
+        // the gc runtimes search for this symbol
+        obj.add_symbol(Symbol {
+            name: "__LLVM_StackMaps".as_bytes().to_vec(),
+            value: 0,
+            size: 0,
+            kind: SymbolKind::Data,
+            scope: SymbolScope::Compilation,
+            weak: false,
+            section: SymbolSection::Section(section),
+            flags: SymbolFlags::None,
+        });
+
+        Ok(())
+    }
+}
+
+fn write_record(data: &mut Vec<u8>, record: &StackMapRecord) {
+    data.extend_from_slice(&record.id.to_le_bytes());
+    data.extend_from_slice(&record.offset.to_le_bytes());
+    data.extend_from_slice(&0u16.to_le_bytes()); // flags
+    data.extend_from_slice(&(record.locations.len() as u16).to_le_bytes());
+
+    for location in &record.locations {
+        let (kind, reg, offset) = match location {
+            StackMapLocation::Register(reg) => (1u8, *reg, 0),
+            StackMapLocation::Indirect(reg, offset) => (3u8, *reg, *offset),
+        };
+
+        data.push(kind);
+        data.push(0);
+        data.extend_from_slice(&8u16.to_le_bytes()); // size
+        data.extend_from_slice(&reg.0.to_le_bytes());
+        data.extend_from_slice(&0u16.to_le_bytes());
+        data.extend_from_slice(&offset.to_le_bytes());
+    }
+
+    align(data);
+
+    data.extend_from_slice(&0u16.to_le_bytes());
+    data.extend_from_slice(&0u16.to_le_bytes()); // live outs
+
+    align(data);
+}
+
+fn align(data: &mut Vec<u8>) {
+    while data.len() % 8 != 0 {
+        data.push(0);
+    }
+}
@@ -0,0 +1,329 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+
+use gimli::write::{Address, CallFrameInstruction, CommonInformationEntry, EhFrame, FrameDescriptionEntry, FrameTable, RelocationTarget};
+use gimli::{constants, Encoding, Format, X86_64};
+use object::write::{Object, Relocation, SectionId, Symbol, SymbolId, SymbolSection};
+use object::{RelocationEncoding, RelocationFlags, RelocationKind, SectionKind, SymbolFlags, SymbolKind, SymbolScope};
+
+use super::wrapper::Section;
+use super::{Decl, Linkage, ObjectBuilder};
+
+/// Describes how the unwinder can unwind through a function (e.g: for exceptions)
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct UnwindInfo {
+    /// How the prolog changes the frame (stored as: code offset after which it applies, instruction)
+    pub cfi: Vec<(u32, CallFrameInstruction)>,
+    /// The personality function which selects the landing pads (e.g: `__gxx_personality_v0`)
+    pub personality: Option<String>,
+    /// The calls which unwind into a landing pad
+    pub call_sites: Vec<CallSite>,
+}
+
+/// A call which unwinds into a landing pad if an exception is thrown through it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallSite {
+    /// The offset of the first instruction of the call (relative to the start of the function)
+    pub start: u64,
+    /// The length of the instructions of the call
+    pub len: u64,
+    /// The offset of the landing pad (relative to the start of the function)
+    pub landing_pad: u64,
+    /// If the landing pad is also entered for exceptions which aren't catched
+    pub cleanup: bool,
+    /// The type infos of the catched exceptions (`None` catches all)
+    pub catches: Vec<Option<String>>,
+}
+
+impl ObjectBuilder {
+    /// Sets how the function can be unwound
+    pub fn unwind(&mut self, func: &str, info: UnwindInfo) {
+        self.unwind.insert(func.to_string(), info);
+    }
+
+    /// Writes the `.eh_frame` for all functions and the `.gcc_except_table` for the functions with landing pads
+    pub(crate) fn emit_unwind(&self, obj: &mut Object<'_>, syms: &BTreeMap<String, (Option<SectionId>, Option</*offsest*/u64>, SymbolId, Decl, u64, Linkage)>) -> Result<(), Box<dyn Error>> {
+        let mut except = Vec::new();
+        // the type infos in the type tables (stored as: offset, type info)
+        let mut except_relocs = Vec::new();
+        // where the lsdas of the functions start in the .gcc_except_table
+        let mut lsdas = BTreeMap::new();
+
+        for (name, info) in &self.unwind {
+            let Some((_, Some(_), _, Decl::Function, size, _)) = syms.get(name) else { continue };
+
+            if !info.call_sites.is_empty() {
+                lsdas.insert(name, except.len() as i64);
+                write_lsda(&mut except, &mut except_relocs, info, *size);
+            }
+        }
+
+        // the personality functions and type infos are referenced through the pointers `DW.ref.name`
+        let mut refs = BTreeMap::new();
+
+        let mut except_sym = None;
+
+        if !except.is_empty() {
+            let section = obj.add_section(vec![], ".gcc_except_table".as_bytes().to_vec(), SectionKind::ReadOnlyData);
+            obj.set_section_data(section, except, 4);
+
+            for (offset, ty) in except_relocs {
+                let symbol = dw_ref(obj, syms, &mut refs, &ty, SymbolKind::Data)?;
+
+                obj.add_relocation(section, Relocation {
+                    offset: offset,
+                    symbol: symbol,
+                    addend: 0,
+                    flags: RelocationFlags::Generic {
+                        kind: RelocationKind::Relative,
+                        encoding: RelocationEncoding::Generic,
+                        size: 32,
+                    },
+                })?;
+            }
+
+            except_sym = Some(obj.section_symbol(section));
+        }
+
+        let encoding = Encoding {
+            address_size: 8,
+            format: Format::Dwarf32,
+            version: 1,
+        };
+
+        let pcrel = constants::DW_EH_PE_pcrel | constants::DW_EH_PE_sdata4;
+
+        let mut cie = CommonInformationEntry::new(encoding, 1, -8, X86_64::RA);
+        cie.fde_address_encoding = pcrel;
+        // the call pushed the return adress
+        cie.add_instruction(CallFrameInstruction::Cfa(X86_64::RSP, 8));
+        cie.add_instruction(CallFrameInstruction::Offset(X86_64::RA, -8));
+
+        let mut frames = FrameTable::default();
+
+        let plain = frames.add_cie(cie.to_owned());
+
+        for (name, info) in &self.unwind {
+            let Some((_, Some(_), sym, Decl::Function, size, _)) = syms.get(name) else { continue };
+
+            let mut fde = FrameDescriptionEntry::new(symbol_adress(*sym, 0), *size as u32);
+
+            for (offset, instr) in &info.cfi {
+                fde.add_instruction(*offset, instr.to_owned());
+            }
+
+            let cie = match (lsdas.get(name), &info.personality, except_sym) {
+                (Some(lsda), Some(personality), Some(except)) => {
+                    fde.lsda = Some(symbol_adress(except, *lsda));
+
+                    let personality = dw_ref(obj, syms, &mut refs, personality, SymbolKind::Text)?;
+
+                    let mut cie = cie.to_owned();
+                    cie.personality = Some((constants::DW_EH_PE_indirect | pcrel, symbol_adress(personality, 0)));
+                    cie.lsda_encoding = Some(pcrel);
+
+                    frames.add_cie(cie)
+                },
+                _ => plain,
+            };
+
+            frames.add_fde(cie, fde);
+        }
+
+        let mut eh_frame = EhFrame(Section::new());
+        frames.write_eh_frame(&mut eh_frame)?;
+
+        let section = obj.add_section(vec![], ".eh_frame".as_bytes().to_vec(), SectionKind::ReadOnlyData);
+
+        for reloc in &eh_frame.0.relocations {
+            let RelocationTarget::Symbol(symbol) = reloc.target else { unreachable!("the frame table only references symbols") };
+
+            let symbol: SymbolId = unsafe {
+                std::mem::transmute(symbol)
+            };
+
+            obj.add_relocation(section, Relocation {
+                offset: reloc.offset as u64,
+                symbol: symbol,
+                addend: reloc.addend,
+                flags: RelocationFlags::Generic {
+                    kind: match reloc.eh_pe {
+                        Some(eh_pe) if eh_pe.application() == constants::DW_EH_PE_pcrel => RelocationKind::Relative,
+                        _ => RelocationKind::Absolute,
+                    },
+                    encoding: RelocationEncoding::Generic,
+                    size: reloc.size * 8,
+                },
+            })?;
+        }
+
+        obj.set_section_data(section, eh_frame.0.data.take(), 8);
+
+        Ok(())
+    }
+}
+
+fn symbol_adress(symbol: SymbolId, addend: i64) -> Address {
+    let symbol: usize = unsafe {
+        std::mem::transmute(symbol)
+    };
+
+    Address::Symbol { symbol: symbol, addend: addend }
+}
+
+/// Returns the symbol `DW.ref.name` which stores the adress of the symbol (and creates it if it doesn't exist yet)
+fn dw_ref(obj: &mut Object<'_>, syms: &BTreeMap<String, (Option<SectionId>, Option</*offsest*/u64>, SymbolId, Decl, u64, Linkage)>, refs: &mut BTreeMap<String, SymbolId>, name: &str, kind: SymbolKind) -> Result<SymbolId, Box<dyn Error>> {
+    if let Some(sym) = refs.get(name) {
+        return Ok(*sym);
+    }
+
+    let target = if let Some((_, _, sym, _, _, _)) = syms.get(name) {
+        *sym
+    } else {
+        obj.add_symbol(Symbol {
+            name: name.as_bytes().to_vec(),
+            value: 0,
+            size: 0,
+            kind: kind,
+            scope: SymbolScope::Dynamic,
+            weak: false,
+            section: SymbolSection::Undefined,
+            flags: SymbolFlags::None,
+        })
+    };
+
+    // every object file has its own copy, so it's weak and hidden
+    let section = obj.add_section(vec![], format!(".data.rel.local.DW.ref.{}", name).into_bytes(), SectionKind::Data);
+
+    let sym = obj.add_symbol(Symbol {
+        name: format!("DW.ref.{}", name).into_bytes(),
+        value: 0,
+        size: 8,
+        kind: SymbolKind::Data,
+        scope: SymbolScope::Linkage,
+        weak: true,
+        section: SymbolSection::Section(section),
+        flags: SymbolFlags::None,
+    });
+
+    let offset = obj.add_symbol_data(sym, section, &[0; 8], 8);
+
+    obj.add_relocation(section, Relocation {
+        offset: offset,
+        symbol: target,
+        addend: 0,
+        flags: RelocationFlags::Generic {
+            kind: RelocationKind::Absolute,
+            encoding: RelocationEncoding::Generic,
+            size: 64,
+        },
+    })?;
+
+    refs.insert(name.to_owned(), sym);
+
+    Ok(sym)
+}
+
+/// Writes the language specific data area of the function, which the personality
+/// function uses to find the landing pad (and the catched type) of a call
+fn write_lsda(out: &mut Vec<u8>, relocs: &mut Vec<(u64, String)>, info: &UnwindInfo, size: u64) {
+    // the catched types (the selector of a landing pad is the index of the catched type + 1)
+    let mut types: Vec<&Option<String>> = Vec::new();
+
+    for site in &info.call_sites {
+        for catch in &site.catches {
+            if !types.contains(&catch) {
+                types.push(catch);
+            }
+        }
+    }
+
+    let mut call_sites = info.call_sites.iter().collect::<Vec<_>>();
+    call_sites.sort_by_key(|site| site.start);
+
+    let mut table = Vec::new();
+    let mut actions = Vec::new();
+
+    // calls outside of the call sites need an entry without a landing pad,
+    // else the personality function terminates instead of unwinding through the function
+    let mut pos = 0;
+
+    for site in call_sites {
+        if site.start > pos {
+            write_call_site(&mut table, pos, site.start - pos, 0, 0);
+        }
+
+        let action = if site.catches.is_empty() { 0 } else {
+            let first = actions.len() as u64 + 1;
+
+            let mut filters = site.catches.iter()
+                .map(|catch| types.iter().position(|ty| *ty == catch).expect("expected known type") as i64 + 1)
+                .collect::<Vec<_>>();
+
+            if site.cleanup {
+                filters.push(0);
+            }
+
+            for (index, filter) in filters.iter().enumerate() {
+                sleb(&mut actions, *filter);
+                // the next action directly follows the offset to it
+                sleb(&mut actions, if index + 1 < filters.len() { 1 } else { 0 });
+            }
+
+            first
+        };
+
+        write_call_site(&mut table, site.start, site.len, site.landing_pad, action);
+
+        pos = site.start + site.len;
+    }
+
+    if pos < size {
+        write_call_site(&mut table, pos, size - pos, 0, 0);
+    }
+
+    let mut body = vec![constants::DW_EH_PE_uleb128.0];
+    uleb(&mut body, table.len() as u64);
+    body.extend_from_slice(&table);
+    body.extend_from_slice(&actions);
+
+    let types_start = body.len();
+    // the type table is indexed backwards from its end
+    body.extend(std::iter::repeat(0).take(types.len() * 4));
+
+    // the landing pads are relative to the start of the function
+    out.push(constants::DW_EH_PE_omit.0);
+
+    if types.is_empty() {
+        out.push(constants::DW_EH_PE_omit.0);
+    } else {
+        out.push((constants::DW_EH_PE_indirect | constants::DW_EH_PE_pcrel | constants::DW_EH_PE_sdata4).0);
+        uleb(out, body.len() as u64);
+    }
+
+    let body_start = out.len();
+
+    for (index, ty) in types.iter().rev().enumerate() {
+        // `catch null` (catches everything) is stored as zero
+        if let Some(ty) = ty {
+            relocs.push(((body_start + types_start + index * 4) as u64, ty.to_owned()));
+        }
+    }
+
+    out.extend_from_slice(&body);
+}
+
+fn write_call_site(table: &mut Vec<u8>, start: u64, len: u64, landing_pad: u64, action: u64) {
+    uleb(table, start);
+    uleb(table, len);
+    uleb(table, landing_pad);
+    uleb(table, action);
+}
+
+fn uleb(out: &mut Vec<u8>, value: u64) {
+    gimli::leb128::write::unsigned(out, value).expect("writing into a vec can't fail");
+}
+
+fn sleb(out: &mut Vec<u8>, value: i64) {
+    gimli::leb128::write::signed(out, value).expect("writing into a vec can't fail");
+}
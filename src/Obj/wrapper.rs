@@ -4,6 +4,7 @@ use object::write::{Object, Relocation, SectionId, Symbol, SymbolId, SymbolSecti
 use object::{Architecture, BinaryFormat, Endianness, FileFlags, RelocationEncoding, RelocationFlags, RelocationKind, SectionKind, SymbolFlags, SymbolKind, SymbolScope};
 
 use crate::debug::DebugRegistry;
+use super::{StackMapRecord, UnwindInfo};
 use crate::prelude::Triple;
 use crate::Target::{self, Arch};
 use std::collections::BTreeMap;
@@ -30,14 +31,14 @@ impl std::fmt::Display for ObjectError {
 impl std::error::Error for ObjectError {}
 
 #[derive(Clone)]
-struct Section {
-    data: EndianVec<LittleEndian>,
-    relocations: Vec<gimli::write::Relocation>,
+pub(super) struct Section {
+    pub(super) data: EndianVec<LittleEndian>,
+    pub(super) relocations: Vec<gimli::write::Relocation>,
     id: Option<object::write::SectionId>,
 }
 
 impl Section {
-    fn new() -> Self {
+    pub(super) fn new() -> Self {
         Self {
             data: EndianVec::new(LittleEndian),
             relocations: Vec::new(),
@@ -69,6 +70,8 @@ pub enum Decl {
     Function,
     /// A mutable data (E.g: a global variable)
     Data,
+    /// A zero initialized mutable data which is placed into the .bss section
+    ZeroData,
     /// A constant data
     Constant,
 }
@@ -81,6 +84,8 @@ pub struct Link {
     /// The link destination
     pub to: String,
     /// The binary offset of the start of the function
+    /// 
+    /// Links out of data (constants, globals) are absolute 64bit adresses which are written at this offset
     pub at: usize,
     /// The addend to use
     pub addend: i64,
@@ -88,6 +93,8 @@ pub struct Link {
     pub special: bool,
     /// the type
     pub kind: RelocationEncoding,
+    /// If it only marks the position of the label `to` (only internal usage)
+    pub label: bool,
 }
 
 /// The linkage of the target symbol
@@ -128,6 +135,11 @@ pub struct ObjectBuilder {
     pub debug: bool,
 
     pub(crate) just_write_bytes: Option<Vec<u8>>,
+
+    pub(crate) unwind: BTreeMap<String, UnwindInfo>,
+
+    /// the stack maps of the functions (stored as: function, (frame size, records))
+    pub(crate) stack_maps: BTreeMap<String, (u64, Vec<StackMapRecord>)>,
 }
 
 impl ObjectBuilder {
@@ -145,6 +157,9 @@ impl ObjectBuilder {
 
             debug: false,
             just_write_bytes: None,
+
+            unwind: BTreeMap::new(),
+            stack_maps: BTreeMap::new(),
         }
     }
 
@@ -241,6 +256,9 @@ impl ObjectBuilder {
         let secText = obj.add_section(vec![], ".text".as_bytes().to_vec(), SectionKind::Text);
         let secData = obj.add_section(vec![], ".data".as_bytes().to_vec(), SectionKind::Data);
         let secConsts = obj.add_section(vec![], ".rodata".as_bytes().to_vec(), SectionKind::ReadOnlyData);
+        let secBss = obj.add_section(vec![], ".bss".as_bytes().to_vec(), SectionKind::UninitializedData);
+        // constants which contain adresses need to be relocated at load time
+        let secRelConsts = obj.add_section(vec![], ".data.rel.ro".as_bytes().to_vec(), SectionKind::ReadOnlyDataWithRel);
 
         let mut syms: BTreeMap<String, (Option<SectionId>, Option</*offsest*/u64>, SymbolId, Decl, /*size*/u64, /*link*/Linkage)> = BTreeMap::new();
 
@@ -264,6 +282,8 @@ impl ObjectBuilder {
             let decl = decl.unwrap();
             let link = link.unwrap();
 
+            let secConsts = if self.links.iter().any(|link| link.from == name) { secRelConsts } else { secConsts };
+
             let sym = obj.add_symbol(Symbol {
                 name: name.clone().as_bytes().to_vec(),
                 value: 0,
@@ -277,7 +297,7 @@ impl ObjectBuilder {
                 kind: {
                     match decl {
                         Decl::Function => SymbolKind::Text,
-                        Decl::Data => SymbolKind::Data,
+                        Decl::Data | Decl::ZeroData => SymbolKind::Data,
                         Decl::Constant => SymbolKind::Data,
                     }
                 },
@@ -294,6 +314,7 @@ impl ObjectBuilder {
                         match decl {
                             Decl::Function => SymbolSection::Section(secText),
                             Decl::Data => SymbolSection::Section(secData),
+                            Decl::ZeroData => SymbolSection::Section(secBss),
                             Decl::Constant => SymbolSection::Section(secConsts),
                         }
                     } else {
@@ -314,6 +335,7 @@ impl ObjectBuilder {
                 let def_offset = match decl {
                     Decl::Function => obj.add_symbol_data(sym, secText, &data, align),
                     Decl::Data => obj.add_symbol_data(sym, secData, &data, align),
+                    Decl::ZeroData => obj.add_symbol_bss(sym, secBss, data.len() as u64, align),
                     Decl::Constant => obj.add_symbol_data(sym, secConsts, &data, align),
                 };
     
@@ -339,7 +361,7 @@ impl ObjectBuilder {
                 kind: {
                     match decl {
                         Decl::Function => SymbolKind::Text,
-                        Decl::Data => SymbolKind::Data,
+                        Decl::Data | Decl::ZeroData => SymbolKind::Data,
                         Decl::Constant => SymbolKind::Data,
                     }
                 },
@@ -353,9 +375,25 @@ impl ObjectBuilder {
         }
 
         for link in &self.links {
-            let (_, off, _, _, _, _) = syms.get(&link.from).expect("expectd valid link source");
+            let (_, off, _, from_decl, _, _) = syms.get(&link.from).expect("expectd valid link source");
             let (_, _, to_sym, _decl, _, _) = syms.get(&link.to).expect("expected valid link destination");
 
+            if *from_decl != Decl::Function {
+                // links out of data are absolute 64bit adresses
+                obj.add_relocation(if *from_decl == Decl::Data { secData } else { secRelConsts }, Relocation {
+                    offset: link.at as u64 + {if let Some(off) = off { *off } else { 0 }},
+                    symbol: to_sym.to_owned(),
+                    addend: link.addend,
+                    flags: RelocationFlags::Generic { 
+                        kind: RelocationKind::Absolute, 
+                        encoding: RelocationEncoding::Generic, 
+                        size: 64, 
+                    },
+                })?;
+
+                continue;
+            }
+
             let addend = 0;/*match decl {
                 Decl::Function => if self.triple.getCallConv()? == Target::CallConv::SystemV { 4 } else {0},
                 _ => 0,
@@ -374,6 +412,16 @@ impl ObjectBuilder {
             })?;
         }
 
+        // the unwinder needs the .eh_frame to unwind through the functions (e.g: for c++ exceptions)
+        if obj.format() == BinaryFormat::Elf && self.triple.arch == Arch::X86_64 && !self.unwind.is_empty() {
+            self.emit_unwind(&mut obj, &syms)?;
+        }
+
+        // the gc runtimes need the stack maps to find the gc roots in the frames
+        if obj.format() == BinaryFormat::Elf && self.triple.arch == Arch::X86_64 && !self.stack_maps.is_empty() {
+            self.emit_stack_maps(&mut obj, &syms)?;
+        }
+
         if let Some(debug) = debug {
             if self.debug {
                 self.emit_dwarf(&mut obj, &syms, debug)?;
@@ -35,6 +35,9 @@ impl<'a> BlockBranchAnalysis<'a> {
                     for (_, case) in &switch.cases {
                         brs.push(case);
                     }
+                } else if let Some(invoke) = node.as_any().downcast_ref::<Invoke>() {
+                    brs.push(&invoke.normal);
+                    brs.push(&invoke.unwind);
                 }
             }
 
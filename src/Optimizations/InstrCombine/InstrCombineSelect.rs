@@ -14,7 +14,9 @@ fn optimizeSelectToCast(SI: &Select) -> Option<Box<dyn Ir>> {
     }
 
 
-    Some(Cast::new(IROperand::Var(SI.getCondition()), SI.getSelType(), SI.getOut()))
+    let kind = CastKind::infer(SI.getCondition().ty, SI.getSelType());
+
+    Some(Cast::new(kind, IROperand::Var(SI.getCondition()), SI.getSelType(), SI.getOut()))
 }
 
 impl InstrCombinePass {
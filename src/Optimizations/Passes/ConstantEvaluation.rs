@@ -19,7 +19,17 @@ impl Pass for ConstantEvaluation {
     fn run(&self, block: &mut crate::prelude::Block) {
         let mut const_values = HashMap::new();
 
-        for node in block.nodes.iter_mut() {
+        let mut index = 0;
+
+        while index < block.nodes.len() {
+            let node = &mut block.nodes[index];
+
+            // volatile accesses need to stay exactly as they are written
+            if node.is_volatile() {
+                index += 1;
+                continue;
+            }
+
             if let Some(inlined) = node.maybe_inline(&const_values) {
                 node.replace( inlined )
             }
@@ -28,10 +38,28 @@ impl Pass for ConstantEvaluation {
                 node.replace( eval );
             }
 
-            if let Some(node) = node.as_any().downcast_ref::<Assign<Var, Type>>() {
-                const_values.insert(node.inner1.name.to_owned(), node.inner2);
+            // e.g: a `br cond` with a constant condition is folded into a `br`
+            block.drop_stale_metadata(index);
+
+            let node = &mut block.nodes[index];
+
+            // nodes with multiple outputs evaluate into one node per output
+            let evaluated = match node.eval_split() {
+                Some(split) => {
+                    let len = split.len();
+                    block.replace_node(index, split);
+                    len
+                },
+                None => 1,
+            };
+
+            for node in &block.nodes[index..index + evaluated] {
+                if let Some(node) = node.as_any().downcast_ref::<Assign<Var, Type>>() {
+                    const_values.insert(node.inner1.name.to_owned(), node.inner2);
+                }
+            }
 
-            }    
+            index += evaluated;
         }
     }
 }
\ No newline at end of file
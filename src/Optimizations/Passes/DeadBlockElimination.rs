@@ -1,8 +1,11 @@
 use crate::Optimizations::Pass;
-use crate::IR::{ir::Br, ir::BrCond};
+use crate::IR::{ir::Br, ir::BrCond, ir::Invoke, ir::Switch};
 
 /// ## Pass DeadBlockElimination <br>
 /// deletes unused blocks
+///
+/// Conditional branches into blocks which only contain an `unreachable`
+/// are replaced with a branch to the other block
 pub(crate) struct DeadBlockElimination {
 }
 
@@ -19,6 +22,30 @@ impl Pass for DeadBlockElimination {
     fn run_func(&self, func: &mut crate::prelude::Function) {
         let mut used_blocks = Vec::new();
 
+        // FOLD BRANCHES INTO UNREACHABLE BLOCKS
+
+        let unreachable_blocks = func.blocks.iter()
+            .filter(|block| matches!(block.nodes.iter().find(|node| !node.is_debug()), Some(node) if node.is_unreachable()))
+            .map(|block| block.name.to_owned())
+            .collect::<Vec<String>>();
+
+        for block in &mut func.blocks {
+            for index in 0..block.nodes.len() {
+                let Some(br) = block.nodes[index].as_any().downcast_ref::<BrCond>() else { continue; };
+
+                let folded = if unreachable_blocks.contains(&br.inner2.name) && !unreachable_blocks.contains(&br.inner3.name) {
+                    Br::new(br.inner3.to_owned())
+                } else if unreachable_blocks.contains(&br.inner3.name) && !unreachable_blocks.contains(&br.inner2.name) {
+                    Br::new(br.inner2.to_owned())
+                } else {
+                    continue;
+                };
+
+                block.nodes[index] = folded;
+                block.drop_stale_metadata(index);
+            }
+        }
+
         // CHECK FOR ALL USED BLOCKS
 
         for block in &func.blocks {
@@ -31,6 +58,23 @@ impl Pass for DeadBlockElimination {
                     used_blocks.push(br.inner2.name.to_owned());
                     used_blocks.push(br.inner3.name.to_owned());
                 }
+
+                if let Some(switch) = node.as_any().downcast_ref::<Switch>() {
+                    used_blocks.push(switch.default.name.to_owned());
+
+                    for (_, case) in &switch.cases {
+                        used_blocks.push(case.name.to_owned());
+                    }
+                }
+
+                if let Some(invoke) = node.as_any().downcast_ref::<Invoke>() {
+                    used_blocks.push(invoke.normal.name.to_owned());
+                    used_blocks.push(invoke.unwind.name.to_owned());
+                }
+
+                if node.is_terminator() { // the nodes after it are never executed
+                    break;
+                }
             }
         }
 
@@ -1,5 +1,6 @@
 use crate::Optimizations::Pass;
-use crate::IR::ir::Phi;
+use crate::IR::ir::{Call, Phi};
+use crate::IR::{FnAttr, Function, Module};
 
 /// ## Pass DeadNodeElimination <br>
 /// deletes unused nodes
@@ -15,7 +16,33 @@ impl Pass for DeadNodeElimination_ {
         "DeadNodeElimination"
     }
     
-    fn run_func(&self, func: &mut crate::prelude::Function) {
+    fn run_func(&self, func: &mut Function) {
+        self.eliminate(func, &[]);
+    }
+
+    fn run_mod(&self, module: &mut Module) {
+        let readnone = module.funcs.values()
+            .filter(|func| func.has_attr(FnAttr::ReadNone))
+            .map(|func| func.name.to_owned())
+            .collect::<Vec<String>>();
+
+        for (_, func) in &mut module.funcs {
+            self.eliminate(func, &readnone);
+        }
+    }
+}
+
+impl DeadNodeElimination_ {
+    /// Removes the unused nodes of the function (`readnone` are the functions which are declared `readnone`)
+    fn eliminate(&self, func: &mut Function, readnone: &[String]) {
+        // the nodes after a terminator (e.g: `unreachable`) are never executed
+
+        for block in &mut func.blocks {
+            if let Some(term) = block.nodes.iter().position(|node| node.is_terminator()) {
+                block.truncate_nodes(term + 1);
+            }
+        }
+
         for _ in 0..2 { // iterate two times, cuz then we can remove dependants with a dept of 1
             let mut used: Vec<String> = Vec::new();
 
@@ -43,7 +70,7 @@ impl Pass for DeadNodeElimination_ {
 
                 for node in iter {
                     let inputs =  node.inputs();
-                    let out = node.output();
+                    let outs = node.outputs();
         
                     for input in inputs {
                         if !used.contains(&input.name) {
@@ -51,12 +78,14 @@ impl Pass for DeadNodeElimination_ {
                         }
                     }
 
-                    if let Some(out) = out {
-                        if !used.contains(&out.name) {
-                            if !node.is_call() {
-                                // node isn't a call
-                                to_remove.push((block.name.clone(), index - 1));
-                            }
+                    // nodes with multiple outputs are only removed if none of them is used
+                    if !outs.is_empty() && outs.iter().all(|out| !used.contains(&out.name)) {
+                        // calls to functions which are declared `readnone` don't have side effects either
+                        let readnone_call = node.as_any().downcast_ref::<Call>().is_some_and(|call| readnone.contains(&call.func.name));
+
+                        if !node.has_side_effects() || readnone_call {
+                            // node doesn't write memory or call functions
+                            to_remove.push((block.name.clone(), index - 1));
                         }
                     }
 
@@ -69,7 +98,7 @@ impl Pass for DeadNodeElimination_ {
 
                 for (target_block, node) in &to_remove {
                     if target_block == &block.name {
-                        block.nodes.remove((*node - off) as usize);
+                        block.remove_node((*node - off) as usize);
 
                         //off += 1;
                     }
@@ -0,0 +1,138 @@
+use std::collections::BTreeMap;
+
+use crate::Optimizations::Pass;
+use crate::IR::{ir::*, Block, BlockId, Function, Var};
+
+/// ## Pass TailRecursionElimination <br>
+/// turns self recursive tail calls into a loop
+///
+/// A new entry block is inserted before the old one, which gets `phi`s for all arguments.
+/// The tail calls are replaced with branches back to the old entry block
+pub(crate) struct TailRecursionElimination {
+}
+
+/// Creates a new TailRecursionElimination pass which is heap allocated
+pub fn TailRecursionElimination() -> Box<dyn Pass> {
+    Box::from( TailRecursionElimination {} )
+}
+
+impl Pass for TailRecursionElimination {
+    fn name(&self) -> &'static str {
+        "TailRecursionElimination"
+    }
+
+    fn run_func(&self, func: &mut Function) {
+        // the variadic arguments can't be passed again in a loop
+        if func.ty.any_args {
+            return;
+        }
+
+        let Some(entry) = func.blocks.front() else { return };
+
+        // phis in the entry block would need to handle the new entry block
+        if entry.nodes.iter().any(|node| node.is_phi()) {
+            return;
+        }
+
+        let entry = entry.name.to_owned();
+
+        // the blocks (and the index of the call) which end with a self recursive tail call
+        let mut tail_calls = Vec::new();
+
+        for block in &func.blocks {
+            let mut nodes = block.nodes.iter().enumerate().filter(|(_, node)| !node.is_debug()).peekable();
+
+            while let Some((index, node)) = nodes.next() {
+                let Some(call) = node.as_any().downcast_ref::<Call>() else { continue };
+
+                if call.tail && call.func.name == func.name && call.args.len() == func.ty.args.len() && call.returned_by(nodes.peek().map(|(_, node)| *node)) {
+                    tail_calls.push((block.name.to_owned(), index));
+                }
+            }
+        }
+
+        if tail_calls.is_empty() {
+            return;
+        }
+
+        let mut header = block_ref(&format!("{}_preheader", entry));
+
+        // the arguments are replaced with phis in the old entry block
+        let mut phis = Vec::new();
+
+        for (name, ty) in &func.ty.args {
+            let phi = Var { name: format!("{}_loop", name), ty: *ty };
+
+            for block in &mut func.blocks {
+                for node in &mut block.nodes {
+                    for input in node.inputs_mut() {
+                        if input.name == *name {
+                            input.name = phi.name.to_owned();
+                        }
+                    }
+                }
+            }
+
+            // phis can only recive variables which are defined by a node
+            let recive = Var { name: format!("{}_entry", name), ty: *ty };
+
+            header.nodes.push(Box::new(Assign { inner1: recive.to_owned(), inner2: Var { name: name.to_owned(), ty: *ty } }));
+
+            phis.push((phi, vec![(block_ref(&header.name), recive)]));
+        }
+
+        header.nodes.push(Br::new(BlockId(entry.to_owned())));
+
+        for (block_name, index) in tail_calls {
+            let block = func.blocks.iter_mut().find(|block| block.name == block_name).expect("expected valid block");
+
+            let call = block.nodes[index].as_any().downcast_ref::<Call>().expect("expected a call").to_owned();
+
+            block.truncate_nodes(index);
+
+            // the arguments are first copied into temporarys, because
+            // the copy into a phi could overwrite an argument (e.g: two swapped arguments)
+            let mut tmps = Vec::new();
+
+            for (arg_index, arg) in call.args.iter().enumerate() {
+                let tmp = Var { name: format!("{}_arg{}", call.out.name, arg_index), ty: arg.get_ty() };
+
+                block.nodes.push(match arg {
+                    IROperand::Var(var) => Box::new(Assign { inner1: tmp.to_owned(), inner2: var.to_owned() }),
+                    IROperand::Type(ty) => Box::new(Assign { inner1: tmp.to_owned(), inner2: *ty }),
+                });
+
+                tmps.push(tmp);
+            }
+
+            for ((_, recives), (arg_index, tmp)) in phis.iter_mut().zip(tmps.into_iter().enumerate()) {
+                let recive = Var { name: format!("{}_next{}", call.out.name, arg_index), ty: tmp.ty };
+
+                block.nodes.push(Box::new(Assign { inner1: recive.to_owned(), inner2: tmp }));
+
+                recives.push((block_ref(&block.name), recive));
+            }
+
+            block.nodes.push(Br::new(BlockId(entry.to_owned())));
+        }
+
+        let old_entry = func.blocks.front_mut().expect("expected an entry block");
+
+        for (index, (out, recives)) in phis.into_iter().enumerate() {
+            let ty = out.ty;
+            old_entry.insert_node(index, Box::new(Phi::new(out, recives, ty)));
+        }
+
+        func.blocks.push_front(header);
+    }
+}
+
+/// Creates an empty block with the name (phis only use the name of the blocks)
+fn block_ref(name: &str) -> Block {
+    Block {
+        name: name.to_owned(),
+        nodes: Vec::new(),
+        varCount: 0,
+        metadata: BTreeMap::new(),
+    }
+}
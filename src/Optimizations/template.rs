@@ -1,4 +1,4 @@
-use crate::IR::{Block, Function};
+use crate::IR::{Block, Function, Module};
 
 /// The trait all Passes need to implement
 pub trait Pass {
@@ -8,6 +8,19 @@ pub trait Pass {
     /// Runs the pass on the entire function
     fn run_func(&self, _func: &mut Function) {}
 
+    /// Runs the pass on all functions of the module
+    /// 
+    /// Passes which need to know about other functions (e.g: their attributes) can overwrite it
+    fn run_mod(&self, module: &mut Module) {
+        for (_, func) in &mut module.funcs {
+            self.run_func(func);
+
+            for block in &mut func.blocks {
+                self.run(block);
+            }
+        }
+    }
+
     /// Returns the name of the pass
     fn name(&self) -> &'static str;
 }
\ No newline at end of file
@@ -147,6 +147,67 @@ pub enum CallConv {
     WasmBasicCAbi,
 }
 
+impl CallConv {
+    /// Parses the calling convention out of its ir name (e.g: `sysv`)
+    pub fn parse(string: &str) -> Option<Self> {
+        match string {
+            "win64" => Some(CallConv::WindowsFastCall),
+            "sysv" => Some(CallConv::SystemV),
+            "aarch64_apple" => Some(CallConv::AppleAarch64),
+            "wasm" => Some(CallConv::WasmBasicCAbi),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for CallConv {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", match self {
+            CallConv::WindowsFastCall => "win64",
+            CallConv::SystemV => "sysv",
+            CallConv::AppleAarch64 => "aarch64_apple",
+            CallConv::WasmBasicCAbi => "wasm",
+        })
+    }
+}
+
+/// An optional instruction set extension which the backend is allowed to use
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TargetFeature {
+    /// x86-64 SSE4.1 (e.g: `roundsd` for floor, ceil, trunc and round)
+    Sse4_1,
+    /// x86-64 `popcnt` instruction
+    Popcnt,
+    /// x86-64 `lzcnt` instruction (ABM)
+    Lzcnt,
+    /// x86-64 BMI1 (e.g: `tzcnt`)
+    Bmi1,
+}
+
+impl TargetFeature {
+    /// Parses the feature out of its name (e.g: `sse4.1`)
+    pub fn parse(string: &str) -> Option<Self> {
+        match string {
+            "sse4.1" | "sse4_1" => Some(TargetFeature::Sse4_1),
+            "popcnt" => Some(TargetFeature::Popcnt),
+            "lzcnt" => Some(TargetFeature::Lzcnt),
+            "bmi" | "bmi1" => Some(TargetFeature::Bmi1),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for TargetFeature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", match self {
+            TargetFeature::Sse4_1 => "sse4.1",
+            TargetFeature::Popcnt => "popcnt",
+            TargetFeature::Lzcnt => "lzcnt",
+            TargetFeature::Bmi1 => "bmi",
+        })
+    }
+}
+
 /// Vendor
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Vendor {
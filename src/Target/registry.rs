@@ -2,7 +2,7 @@ use std::{collections::HashMap, error::Error, fmt::Display};
 
 use crate::{debug::DebugLocation, prelude::Function, CodeGen::MachineInstr, Obj::Link, IR::{Block, Module}};
 
-use super::{Arch, CallConv, TargetBackendDescr, Triple};
+use super::{Arch, CallConv, TargetBackendDescr, TargetFeature, Triple};
 
 /// The target registry: manages different targets
 pub struct TargetRegistry {
@@ -11,7 +11,7 @@ pub struct TargetRegistry {
 
     pub(crate) epilogs: HashMap<String, bool>,
 
-    triple: Triple,
+    pub(crate) triple: Triple,
 }
 
 impl TargetRegistry {
@@ -38,6 +38,16 @@ impl TargetRegistry {
         }
     }
 
+    /// Allows the backend of the specified architecture to use the instruction set extension
+    /// If it isn't found the function does noting
+    pub fn enableFeatureForTarget(&mut self, arch: Arch, feature: TargetFeature) {
+        if let Some(target) = self.targets.get_mut(&arch) {
+            if let Some(helper) = &mut target.helper {
+                helper.features.insert(feature);
+            }
+        }
+    }
+
     /// returns the `TargetBackendDescr` for the arch (also it adjusts it's calling convention ...)
     pub fn getBasedOnArch(&mut self, arch: Arch) -> Result<&mut TargetBackendDescr, Box<dyn Error>> {
         if let Some(descr) = self.targets.get_mut(&arch) {
@@ -55,7 +65,21 @@ impl TargetRegistry {
 
             return backend;
         } else if let Some(to_fork) = self.targets.get(&arch) {
-            let to_fork = to_fork.to_owned();
+            let mut to_fork = to_fork.to_owned();
+
+            // functions with their own calling convention get a backend which uses it
+            if let (Some(call_conv), Some(init)) = (funct.ty.call_conv, to_fork.init) {
+                if call_conv != to_fork.call {
+                    let features = to_fork.helper.as_ref().map(|helper| helper.features.to_owned());
+
+                    to_fork = init(call_conv);
+
+                    if let (Some(helper), Some(features)) = (&mut to_fork.helper, features) {
+                        helper.features = features;
+                    }
+                }
+            }
+
             self.funcs.insert(funct.name.to_owned(), to_fork);
             self.getBackendForFuncOrFork(arch, funct)
         } else { panic!("the arch: {:?} wasn't initialized", arch); }
@@ -78,6 +102,8 @@ impl TargetRegistry {
         let mut backend = self.getBackendForFuncOrFork(arch, funct);
 
         if run_alloc {
+            funct.verify_call_convs(module, arch, triple.getCallConv()?)?;
+
             if let Some(helper) = &mut backend.helper {
                 helper.run_alloc(&funct);
             }
@@ -107,6 +133,8 @@ impl TargetRegistry {
         let mut backend = self.getBackendForFuncOrFork(arch, funct);
 
         if run_alloc {
+            funct.verify_call_convs(module, arch, triple.getCallConv()?)?;
+
             if let Some(helper) = &mut backend.helper {
                 helper.run_alloc(&funct);
             }
@@ -146,6 +174,8 @@ impl TargetRegistry {
         let mut backend = self.getBackendForFuncOrFork(arch, funct);
 
         if run_alloc {
+            funct.verify_call_convs(module, arch, triple.getCallConv()?)?;
+
             if let Some(helper) = &mut backend.helper {
                 helper.run_alloc(&funct);
             }
@@ -204,6 +234,8 @@ impl TargetRegistry {
         let mut backend = self.getBackendForFuncOrFork(arch, funct);
 
         if run_alloc {
+            funct.verify_call_convs(module, arch, triple.getCallConv()?)?;
+
             if let Some(helper) = &mut backend.helper {
                 helper.run_alloc(&funct);
             }
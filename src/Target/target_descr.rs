@@ -3,7 +3,7 @@ use crate::debug::DebugLocation;
 use crate::prelude::{ir::*, Block, Var};
 use crate::CodeGen::{IrCodeGenArea, IrCodeGenHelper, MCDocInstr, MCInstr};
 use crate::CodeGen::{compilation::CompilationHelper, MachineInstr};
-use crate::IR::{Const, Module, Type};
+use crate::IR::{Const, FuncId, Global, Module, Type};
 
 use super::{AsmPrinter, Triple, WhiteList};
 use super::{CallConv, Compiler, Lexer};
@@ -116,7 +116,13 @@ impl TargetBackendDescr {
 
         let mut ir_helper = IrCodeGenHelper::new(helper.to_owned());
 
-        for node in block.nodes.to_owned() {
+        // after a call to a noreturn function the block is never left through its return
+        let mut noreturn = false;
+        let mut tail = false;
+
+        for (index, node) in block.nodes.to_owned().into_iter().enumerate() {
+            ir_helper.helper.metadata = block.metadata(index).cloned().unwrap_or_default();
+
             if ir_helper.helper.alloc.epilog {
                 self.epilog = true;
             }
@@ -130,24 +136,57 @@ impl TargetBackendDescr {
             }
 
             if let Some(node) = node.as_any().downcast_ref::<Return>() {
+                if tail {
+                    // the tail call already left the function
+                    continue;
+                }
+
                 ir_helper.compile_ret(node, &block, module);
 
-                if self.epilog {
+                if self.epilog && !noreturn {
                     let mut epilog_instrs = vec![];
                     helper.compile_epilog(&mut epilog_instrs);
     
                     if let Some(last) = ir_helper.compiled.last_mut() {
-                        let backup = last.compiled.clone();
-                        last.compiled = epilog_instrs;
-                        last.compiled.extend_from_slice(&backup);
+                        // the epilog gets placed directly before the return
+                        let ret = last.compiled.pop().expect("expected a return instruction");
+                        last.compiled.extend_from_slice(&epilog_instrs);
+                        last.compiled.push(ret);
                     } else { unreachable!() }
 
                 }
+            } else if let Some(call) = node.as_any().downcast_ref::<Call>().filter(|call| call.tail) {
+                ir_helper.compile_call(call, &block, module);
+
+                if self.epilog {
+                    let mut epilog_instrs = vec![];
+                    helper.compile_epilog(&mut epilog_instrs);
+
+                    if let Some(last) = ir_helper.compiled.last_mut() {
+                        // the frame gets torn down directly before jumping to the called function
+                        let jmp = last.compiled.pop().expect("expected a tail call instruction");
+                        last.compiled.extend_from_slice(&epilog_instrs);
+                        last.compiled.push(jmp);
+                    } else { unreachable!() }
+                }
+
+                tail = true;
             } else {
+                if let Some(call) = node.as_any().downcast_ref::<Call>() {
+                    noreturn |= call.is_noreturn(module);
+                }
+
                 node.compile_dir(&mut ir_helper, &block, module);
             }
         }
 
+        ir_helper.helper.metadata = Default::default();
+
+        // the last node (e.g: an invoke) could also have allocated stack
+        if ir_helper.helper.alloc.epilog {
+            self.epilog = true;
+        }
+
         *helper = ir_helper.helper;
 
         ir_helper.compiled
@@ -248,16 +287,21 @@ compile_func!(compile_xor, compile_xor, Xor);
 compile_func!(compile_rem, compile_rem, Rem);
 compile_func!(compile_shl, compile_shl, Shl);
 compile_func!(compile_shr, compile_shr, Shr);
+compile_func!(compile_rotl, compile_rotl, Rotl);
+compile_func!(compile_rotr, compile_rotr, Rotr);
 
 compile_func!(compile_ret, compile_ret, Return);
 
 compile_func!(compile_cast_var, compile_cast, Cast);
 
 compile_func!(compile_call, compile_call, Call);
+compile_func!(compile_call_indirect, compile_call_indirect, CallIndirect);
 
 compile_func!(compile_assign_var_type, compile_assign_var_type, Assign<Var, Type>);
 compile_func!(compile_assign_var_var, compile_assign_var_var, Assign<Var, Var>);
 compile_func!(compile_assign_var_const, compile_assign_var_const, Assign<Var, Const>);
+compile_func!(compile_assign_var_func, compile_assign_var_func, Assign<Var, FuncId>);
+compile_func!(compile_assign_var_global, compile_assign_var_global, Assign<Var, Global>);
 
 compile_func!(compile_br, compile_br, Br);
 compile_func!(compile_br_cond, compile_br_cond, BrCond);
@@ -270,8 +314,41 @@ compile_func!(compile_load, compile_load, Load);
 
 compile_func!(compile_switch, compile_switch, Switch);
 
+compile_func!(compile_unreachable, compile_unreachable, Unreachable);
+compile_func!(compile_trap, compile_trap, Trap);
+
+compile_func!(compile_memcpy, compile_memcpy, MemCpy);
+compile_func!(compile_memmove, compile_memmove, MemMove);
+compile_func!(compile_memset, compile_memset, MemSet);
+
+compile_func!(compile_atomic_load, compile_atomic_load, AtomicLoad);
+compile_func!(compile_atomic_store, compile_atomic_store, AtomicStore);
+compile_func!(compile_atomic_rmw, compile_atomic_rmw, AtomicRmw);
+compile_func!(compile_cmpxchg, compile_cmpxchg, CmpXchg);
+compile_func!(compile_fence, compile_fence, Fence);
+
+compile_func!(compile_va_start, compile_va_start, VaStart);
+compile_func!(compile_va_arg, compile_va_arg, VaArg);
+compile_func!(compile_va_end, compile_va_end, VaEnd);
+compile_func!(compile_va_copy, compile_va_copy, VaCopy);
+
+compile_func!(compile_asm, compile_asm, InlineAsm);
+
+compile_func!(compile_invoke, compile_invoke, Invoke);
+compile_func!(compile_landingpad, compile_landingpad, LandingPad);
+compile_func!(compile_statepoint, compile_statepoint, Statepoint);
+
 compile_func!(compile_neg, compile_neg, Neg);
+compile_func!(compile_bitop, compile_bitop, BitOp);
+compile_func!(compile_overflowop, compile_overflowop, OverflowOp);
+compile_func!(compile_floatop, compile_floatop, FloatOp);
 
 compile_func!(compile_select, compile_select, Select);
 
-compile_func!(compile_getelemptr, compile_getelemptr, GetElemPtr);
\ No newline at end of file
+compile_func!(compile_getelemptr, compile_getelemptr, GetElemPtr);
+
+compile_func!(compile_extractelement, compile_extractelement, ExtractElement);
+compile_func!(compile_insertelement, compile_insertelement, InsertElement);
+compile_func!(compile_tuple, compile_tuple, MakeTuple);
+compile_func!(compile_extractvalue, compile_extractvalue, ExtractValue);
+compile_func!(compile_shufflevector, compile_shufflevector, ShuffleVector);
\ No newline at end of file
@@ -279,6 +279,21 @@ impl Triple {
         })
     }
 
+    /// Returns the size of a pointer in bytes
+    pub fn ptrSize(&self) -> usize {
+        match self.arch {
+            Arch::Avr | Arch::Msp420 => 2,
+
+            Arch::Aarch64 | Arch::Aarch64BE | Arch::Bpfel | Arch::Bpfeb |
+            Arch::Mips64 | Arch::Mips64EL | Arch::Ppc64 | Arch::Ppc64LE |
+            Arch::AmdGCN | Arch::Riscv64 | Arch::Sparcv9 | Arch::SystemZ |
+            Arch::X86_64 | Arch::Nvptx64 | Arch::Le64 | Arch::AmdIL64 |
+            Arch::Hsail64 | Arch::Spir64 | Arch::Wasm64 | Arch::Renderscript64 => 8,
+
+            _ => 4,
+        }
+    }
+
     /// Returns the host target triple
     pub fn host() -> Triple {
         Triple::parse(&getHostTargetTriple()).unwrap()
@@ -3,6 +3,7 @@ use std::fmt::Display;
 use wasm_encoder::{BlockType, ValType};
 
 use crate::CodeGen::MCInstr;
+use crate::IR::FunctionType;
 
 /// A wasm instruction
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -184,6 +185,7 @@ impl WasmMCInstr {
                 }]
             },
             WasmMnemonic::Return => encoded = vec![0x0f],
+            WasmMnemonic::Unreachable => encoded = vec![0x00],
             WasmMnemonic::Eq | WasmMnemonic::Ne | WasmMnemonic::Gt | 
             WasmMnemonic::Gts | WasmMnemonic::Gtu | WasmMnemonic::Lt | 
             WasmMnemonic::Lts | WasmMnemonic::Ltu | WasmMnemonic::Ge | 
@@ -307,6 +309,23 @@ impl WasmMCInstr {
                     _ => unreachable!()
                 }]
             },
+            WasmMnemonic::Rotl | WasmMnemonic::Rotr | WasmMnemonic::Popcnt | WasmMnemonic::Clz | WasmMnemonic::Ctz => {
+                let prefix = self.prefix.expect("rotl/rotr/popcnt/clz/ctz expect a prefix");
+
+                encoded = vec![match (&self.mnemonic, prefix) {
+                    (WasmMnemonic::Clz, WasmPrefix::i32) => 0x67,
+                    (WasmMnemonic::Ctz, WasmPrefix::i32) => 0x68,
+                    (WasmMnemonic::Popcnt, WasmPrefix::i32) => 0x69,
+                    (WasmMnemonic::Rotl, WasmPrefix::i32) => 0x77,
+                    (WasmMnemonic::Rotr, WasmPrefix::i32) => 0x78,
+                    (WasmMnemonic::Clz, WasmPrefix::i64) => 0x79,
+                    (WasmMnemonic::Ctz, WasmPrefix::i64) => 0x7a,
+                    (WasmMnemonic::Popcnt, WasmPrefix::i64) => 0x7b,
+                    (WasmMnemonic::Rotl, WasmPrefix::i64) => 0x89,
+                    (WasmMnemonic::Rotr, WasmPrefix::i64) => 0x8a,
+                    _ => panic!("rotl/rotr/popcnt/clz/ctz only work on i32/i64")
+                }];
+            },
             WasmMnemonic::Neg => {
                 let prefix = self.prefix.expect("neg expects prefix");
 
@@ -316,11 +335,56 @@ impl WasmMCInstr {
                     _ => panic!("neg only supports f32/f64")
                 }];
             },
+            WasmMnemonic::Abs | WasmMnemonic::Ceil | WasmMnemonic::Floor | WasmMnemonic::Trunc | WasmMnemonic::Sqrt | WasmMnemonic::Min | WasmMnemonic::Max | WasmMnemonic::Copysign => {
+                let prefix = self.prefix.expect("abs/ceil/floor/trunc/sqrt/min/max/copysign expect a prefix");
+
+                encoded = vec![match (&self.mnemonic, prefix) {
+                    (WasmMnemonic::Abs, WasmPrefix::f32) => 0x8b,
+                    (WasmMnemonic::Ceil, WasmPrefix::f32) => 0x8d,
+                    (WasmMnemonic::Floor, WasmPrefix::f32) => 0x8e,
+                    (WasmMnemonic::Trunc, WasmPrefix::f32) => 0x8f,
+                    (WasmMnemonic::Sqrt, WasmPrefix::f32) => 0x91,
+                    (WasmMnemonic::Min, WasmPrefix::f32) => 0x96,
+                    (WasmMnemonic::Max, WasmPrefix::f32) => 0x97,
+                    (WasmMnemonic::Copysign, WasmPrefix::f32) => 0x98,
+                    (WasmMnemonic::Abs, WasmPrefix::f64) => 0x99,
+                    (WasmMnemonic::Ceil, WasmPrefix::f64) => 0x9b,
+                    (WasmMnemonic::Floor, WasmPrefix::f64) => 0x9c,
+                    (WasmMnemonic::Trunc, WasmPrefix::f64) => 0x9d,
+                    (WasmMnemonic::Sqrt, WasmPrefix::f64) => 0x9f,
+                    (WasmMnemonic::Min, WasmPrefix::f64) => 0xa4,
+                    (WasmMnemonic::Max, WasmPrefix::f64) => 0xa5,
+                    (WasmMnemonic::Copysign, WasmPrefix::f64) => 0xa6,
+                    _ => panic!("abs/ceil/floor/trunc/sqrt/min/max/copysign only work on f32/f64")
+                }];
+            },
             WasmMnemonic::Extends => encoded = vec![0xac],
+            WasmMnemonic::Extend8s => {
+                let prefix = self.prefix.expect("extend8_s expects a prefix");
+
+                encoded = vec![match prefix {
+                    WasmPrefix::i32 => 0xc0,
+                    WasmPrefix::i64 => 0xc2,
+                    _ => panic!("illegal prefix for extend8_s: {}", prefix),
+                }];
+            },
+            WasmMnemonic::Extend16s => {
+                let prefix = self.prefix.expect("extend16_s expects a prefix");
+
+                encoded = vec![match prefix {
+                    WasmPrefix::i32 => 0xc1,
+                    WasmPrefix::i64 => 0xc3,
+                    _ => panic!("illegal prefix for extend16_s: {}", prefix),
+                }];
+            },
             WasmMnemonic::Extendu => encoded = vec![0xad],
             WasmMnemonic::Wrap => encoded = vec![0xa7],
             WasmMnemonic::Promote => encoded = vec![0xbb],
             WasmMnemonic::Demote => encoded = vec![0xb6],
+            WasmMnemonic::ReinterpretF32 => encoded = vec![0xbc],
+            WasmMnemonic::ReinterpretF64 => encoded = vec![0xbd],
+            WasmMnemonic::ReinterpretI32 => encoded = vec![0xbe],
+            WasmMnemonic::ReinterpretI64 => encoded = vec![0xbf],
             WasmMnemonic::ConvertI32s => {
                 let prefix = self.prefix.expect("convert_i32_s expects a prefix");
 
@@ -400,6 +464,56 @@ impl WasmMCInstr {
                     encoded = vec![0x0c, 0x00]
                 }
             },
+            WasmMnemonic::BrIf => {
+                if let Some(WasmOperand::Const(target)) = self.op1 {
+                    encoded = vec![0x0d, target as u8]
+                } else {
+                    encoded = vec![0x0d, 0x00]
+                }
+            },
+            WasmMnemonic::Select => encoded = vec![0x1b],
+            WasmMnemonic::Load => {
+                let Some(WasmOperand::MemArg(align)) = self.op1 else { panic!("...load expects a memarg") };
+
+                let op = match self.prefix.expect("...load expects an prefix") {
+                    WasmPrefix::i32 => 0x28,
+                    WasmPrefix::i64 => 0x29,
+                    WasmPrefix::f32 => 0x2a,
+                    WasmPrefix::f64 => 0x2b,
+                    prefix => panic!("illegal prefix for load: {}", prefix),
+                };
+
+                encoded = vec![op, align as u8, 0x00]
+            },
+            WasmMnemonic::Store => {
+                let Some(WasmOperand::MemArg(align)) = self.op1 else { panic!("...store expects a memarg") };
+
+                let op = match self.prefix.expect("...store expects an prefix") {
+                    WasmPrefix::i32 => 0x36,
+                    WasmPrefix::i64 => 0x37,
+                    WasmPrefix::f32 => 0x38,
+                    WasmPrefix::f64 => 0x39,
+                    prefix => panic!("illegal prefix for store: {}", prefix),
+                };
+
+                encoded = vec![op, align as u8, 0x00]
+            },
+            WasmMnemonic::MemoryCopy => encoded = vec![0xfc, 0x0a, 0x00, 0x00],
+            WasmMnemonic::MemoryFill => encoded = vec![0xfc, 0x0b, 0x00],
+            WasmMnemonic::Call => {
+                if let Some(WasmOperand::Const(func)) = self.op1 {
+                    encoded = vec![0x10, func as u8]
+                } else {
+                    encoded = vec![0x10, 0x00]
+                }
+            },
+            WasmMnemonic::CallIndirect => {
+                if let Some(WasmOperand::Const(ty)) = self.op1 {
+                    encoded = vec![0x11, ty as u8, 0x00]
+                } else {
+                    encoded = vec![0x11, 0x00, 0x00]
+                }
+            },
             WasmMnemonic::Block => encoded = vec![0x02, 0x40],
             WasmMnemonic::End => encoded = vec![0x0b],
         }
@@ -425,6 +539,7 @@ pub enum WasmMnemonic {
     Rems,
     Remu,
     Return,
+    Unreachable,
     Eq,
     Ne,
     Gt,
@@ -446,16 +561,38 @@ pub enum WasmMnemonic {
     Shl,
     Shrs,
     Shru,
+    Rotl,
+    Rotr,
+
+    Popcnt,
+    Clz,
+    Ctz,
 
     Neg,
 
+    Abs,
+    Ceil,
+    Floor,
+    Trunc,
+    Sqrt,
+    Min,
+    Max,
+    Copysign,
+
     Extends,
     Extendu,
+    Extend8s,
+    Extend16s,
 
     Wrap,
     Promote,
     Demote,
 
+    ReinterpretF32,
+    ReinterpretF64,
+    ReinterpretI32,
+    ReinterpretI64,
+
     ConvertI32s,
     ConvertI32u,
     ConvertI64s,
@@ -467,6 +604,18 @@ pub enum WasmMnemonic {
     TruncF64u,
 
     Br,
+    BrIf,
+
+    Select,
+
+    Load,
+    Store,
+
+    MemoryCopy,
+    MemoryFill,
+
+    Call,
+    CallIndirect,
 
     Block,
     End,
@@ -487,6 +636,7 @@ impl From<String> for WasmMnemonic {
             "rem_s" => WasmMnemonic::Rems,
             "rem_u" => WasmMnemonic::Remu,
             "return" => WasmMnemonic::Return,
+            "unreachable" => WasmMnemonic::Unreachable,
             "eq" => WasmMnemonic::Eq,
             "ne" => WasmMnemonic::Ne,
             "gt" => WasmMnemonic::Gt,
@@ -506,12 +656,31 @@ impl From<String> for WasmMnemonic {
             "shl" => WasmMnemonic::Shl,
             "shr_s" => WasmMnemonic::Shrs,
             "shr_u" => WasmMnemonic::Shru,
+            "rotl" => WasmMnemonic::Rotl,
+            "rotr" => WasmMnemonic::Rotr,
+            "popcnt" => WasmMnemonic::Popcnt,
+            "clz" => WasmMnemonic::Clz,
+            "ctz" => WasmMnemonic::Ctz,
             "neg" => WasmMnemonic::Neg,
+            "abs" => WasmMnemonic::Abs,
+            "ceil" => WasmMnemonic::Ceil,
+            "floor" => WasmMnemonic::Floor,
+            "trunc" => WasmMnemonic::Trunc,
+            "sqrt" => WasmMnemonic::Sqrt,
+            "min" => WasmMnemonic::Min,
+            "max" => WasmMnemonic::Max,
+            "copysign" => WasmMnemonic::Copysign,
             "extend_i32_s" => WasmMnemonic::Extends,
             "extend_i32_u" => WasmMnemonic::Extendu,
+            "extend8_s" => WasmMnemonic::Extend8s,
+            "extend16_s" => WasmMnemonic::Extend16s,
             "wrap_i64" => WasmMnemonic::Wrap,
             "promote_f32" => WasmMnemonic::Promote,
-            "demote_f32" => WasmMnemonic::Demote,
+            "demote_f64" => WasmMnemonic::Demote,
+            "reinterpret_f32" => WasmMnemonic::ReinterpretF32,
+            "reinterpret_f64" => WasmMnemonic::ReinterpretF64,
+            "reinterpret_i32" => WasmMnemonic::ReinterpretI32,
+            "reinterpret_i64" => WasmMnemonic::ReinterpretI64,
             "trunc_f32_s" => WasmMnemonic::TruncF32s,
             "trunc_f32_u" => WasmMnemonic::TruncF32u,
             "trunc_f64_s" => WasmMnemonic::TruncF64s,
@@ -521,6 +690,14 @@ impl From<String> for WasmMnemonic {
             "convert_i64_s" => WasmMnemonic::ConvertI64s,
             "convert_i64_u" => WasmMnemonic::ConvertI64u,
             "br" => WasmMnemonic::Br,
+            "br_if" => WasmMnemonic::BrIf,
+            "select" => WasmMnemonic::Select,
+            "load" => WasmMnemonic::Load,
+            "store" => WasmMnemonic::Store,
+            "memory.copy" => WasmMnemonic::MemoryCopy,
+            "memory.fill" => WasmMnemonic::MemoryFill,
+            "call" => WasmMnemonic::Call,
+            "call_indirect" => WasmMnemonic::CallIndirect,
             "block" => WasmMnemonic::Block,
             "end" => WasmMnemonic::End,
             _ => panic!("unkown wasm mnemonic: {value}"),
@@ -543,6 +720,7 @@ impl Display for WasmMnemonic {
             WasmMnemonic::Rems => "rem_s",
             WasmMnemonic::Remu => "rem_u",
             WasmMnemonic::Return => "return",
+            WasmMnemonic::Unreachable => "unreachable",
             WasmMnemonic::Eq => "eq",
             WasmMnemonic::Ne => "ne",
             WasmMnemonic::Gt => "gt",
@@ -563,12 +741,31 @@ impl Display for WasmMnemonic {
             WasmMnemonic::Shl => "shl",
             WasmMnemonic::Shrs => "shr_s",
             WasmMnemonic::Shru => "shr_u",
+            WasmMnemonic::Rotl => "rotl",
+            WasmMnemonic::Rotr => "rotr",
+            WasmMnemonic::Popcnt => "popcnt",
+            WasmMnemonic::Clz => "clz",
+            WasmMnemonic::Ctz => "ctz",
             WasmMnemonic::Neg => "neg",
+            WasmMnemonic::Abs => "abs",
+            WasmMnemonic::Ceil => "ceil",
+            WasmMnemonic::Floor => "floor",
+            WasmMnemonic::Trunc => "trunc",
+            WasmMnemonic::Sqrt => "sqrt",
+            WasmMnemonic::Min => "min",
+            WasmMnemonic::Max => "max",
+            WasmMnemonic::Copysign => "copysign",
             WasmMnemonic::Extends => "extend_i32_s",
             WasmMnemonic::Extendu => "extend_i32_u",
+            WasmMnemonic::Extend8s => "extend8_s",
+            WasmMnemonic::Extend16s => "extend16_s",
             WasmMnemonic::Wrap => "wrap_i64",
             WasmMnemonic::Promote => "promote_f32",
-            WasmMnemonic::Demote => "demote_f32",
+            WasmMnemonic::Demote => "demote_f64",
+            WasmMnemonic::ReinterpretF32 => "reinterpret_f32",
+            WasmMnemonic::ReinterpretF64 => "reinterpret_f64",
+            WasmMnemonic::ReinterpretI32 => "reinterpret_i32",
+            WasmMnemonic::ReinterpretI64 => "reinterpret_i64",
             WasmMnemonic::ConvertI32s => "convert_i32_s",
             WasmMnemonic::ConvertI32u => "convert_i32_u",
             WasmMnemonic::ConvertI64s => "convert_i64_s",
@@ -578,6 +775,14 @@ impl Display for WasmMnemonic {
             WasmMnemonic::TruncF64s => "trunc_f64_s",
             WasmMnemonic::TruncF64u => "trunc_f64_u",
             WasmMnemonic::Br => "br",
+            WasmMnemonic::BrIf => "br_if",
+            WasmMnemonic::Select => "select",
+            WasmMnemonic::Load => "load",
+            WasmMnemonic::Store => "store",
+            WasmMnemonic::MemoryCopy => "memory.copy",
+            WasmMnemonic::MemoryFill => "memory.fill",
+            WasmMnemonic::Call => "call",
+            WasmMnemonic::CallIndirect => "call_indirect",
             WasmMnemonic::Block => "block",
             WasmMnemonic::End => "end",
         })
@@ -632,6 +837,10 @@ pub enum WasmOperand {
     Var(i32),
     Const(f64),
     BlockLink(/*target*/String),
+    FuncLink(/*target*/String),
+    TypeLink(FunctionType),
+    /// the alignment of a memory access (as its log2)
+    MemArg(u32),
 }
 
 impl PartialEq for WasmOperand {
@@ -640,6 +849,9 @@ impl PartialEq for WasmOperand {
             (Self::Var(l0), Self::Var(r0)) => l0 == r0,
             (Self::Const(l0), Self::Const(r0)) => l0 == r0,
             (Self::BlockLink(l0), Self::BlockLink(r0)) => l0 == r0,
+            (Self::FuncLink(l0), Self::FuncLink(r0)) => l0 == r0,
+            (Self::TypeLink(l0), Self::TypeLink(r0)) => l0 == r0,
+            (Self::MemArg(l0), Self::MemArg(r0)) => l0 == r0,
             _ => false,
         }
     }
@@ -653,6 +865,9 @@ impl Display for WasmOperand {
             WasmOperand::Var(var) => format!("{var}"),
             WasmOperand::Const(imm) => format!("{:.5}", imm), 
             WasmOperand::BlockLink(target) => format!("{target}"),
+            WasmOperand::FuncLink(target) => format!("{target}"),
+            WasmOperand::TypeLink(ty) => format!("(type {} -> {})", ty.args.iter().map(|(_, ty)| ty.to_string()).collect::<Vec<String>>().join(", "), ty.ret),
+            WasmOperand::MemArg(align) => format!("align={}", 1 << align),
         })
     }
 }
@@ -765,6 +980,7 @@ impl<'a> Into<wasm_encoder::Instruction<'a>> for WasmMCInstr {
                 _ => panic!()
             }},
             WasmMnemonic::Return => Instruction::Return,
+            WasmMnemonic::Unreachable => Instruction::Unreachable,
             WasmMnemonic::Eq => { let Some(prefix) = self.prefix else { unreachable!()}; match prefix {
                 WasmPrefix::i32 => Instruction::I32Eq,
                 WasmPrefix::i64 => Instruction::I64Eq,
@@ -869,16 +1085,95 @@ impl<'a> Into<wasm_encoder::Instruction<'a>> for WasmMCInstr {
                 WasmPrefix::i64 => Instruction::I64ShrU,
                 _ => panic!()
             }},
+            WasmMnemonic::Rotl => { let Some(prefix) = self.prefix else { unreachable!()}; match prefix {
+                WasmPrefix::i32 => Instruction::I32Rotl,
+                WasmPrefix::i64 => Instruction::I64Rotl,
+                _ => panic!()
+            }},
+            WasmMnemonic::Rotr => { let Some(prefix) = self.prefix else { unreachable!()}; match prefix {
+                WasmPrefix::i32 => Instruction::I32Rotr,
+                WasmPrefix::i64 => Instruction::I64Rotr,
+                _ => panic!()
+            }},
+            WasmMnemonic::Popcnt => { let Some(prefix) = self.prefix else { unreachable!()}; match prefix {
+                WasmPrefix::i32 => Instruction::I32Popcnt,
+                WasmPrefix::i64 => Instruction::I64Popcnt,
+                _ => panic!()
+            }},
+            WasmMnemonic::Clz => { let Some(prefix) = self.prefix else { unreachable!()}; match prefix {
+                WasmPrefix::i32 => Instruction::I32Clz,
+                WasmPrefix::i64 => Instruction::I64Clz,
+                _ => panic!()
+            }},
+            WasmMnemonic::Ctz => { let Some(prefix) = self.prefix else { unreachable!()}; match prefix {
+                WasmPrefix::i32 => Instruction::I32Ctz,
+                WasmPrefix::i64 => Instruction::I64Ctz,
+                _ => panic!()
+            }},
             WasmMnemonic::Neg => { let Some(prefix) = self.prefix else { unreachable!()}; match prefix {
                 WasmPrefix::f32 => Instruction::F32Neg,
                 WasmPrefix::f64 => Instruction::F64Neg,
                 _ => panic!(),
             }},
-            WasmMnemonic::Extends => Instruction::I64Extend32S,
+            WasmMnemonic::Abs => { let Some(prefix) = self.prefix else { unreachable!()}; match prefix {
+                WasmPrefix::f32 => Instruction::F32Abs,
+                WasmPrefix::f64 => Instruction::F64Abs,
+                _ => panic!(),
+            }},
+            WasmMnemonic::Ceil => { let Some(prefix) = self.prefix else { unreachable!()}; match prefix {
+                WasmPrefix::f32 => Instruction::F32Ceil,
+                WasmPrefix::f64 => Instruction::F64Ceil,
+                _ => panic!(),
+            }},
+            WasmMnemonic::Floor => { let Some(prefix) = self.prefix else { unreachable!()}; match prefix {
+                WasmPrefix::f32 => Instruction::F32Floor,
+                WasmPrefix::f64 => Instruction::F64Floor,
+                _ => panic!(),
+            }},
+            WasmMnemonic::Trunc => { let Some(prefix) = self.prefix else { unreachable!()}; match prefix {
+                WasmPrefix::f32 => Instruction::F32Trunc,
+                WasmPrefix::f64 => Instruction::F64Trunc,
+                _ => panic!(),
+            }},
+            WasmMnemonic::Sqrt => { let Some(prefix) = self.prefix else { unreachable!()}; match prefix {
+                WasmPrefix::f32 => Instruction::F32Sqrt,
+                WasmPrefix::f64 => Instruction::F64Sqrt,
+                _ => panic!(),
+            }},
+            WasmMnemonic::Min => { let Some(prefix) = self.prefix else { unreachable!()}; match prefix {
+                WasmPrefix::f32 => Instruction::F32Min,
+                WasmPrefix::f64 => Instruction::F64Min,
+                _ => panic!(),
+            }},
+            WasmMnemonic::Max => { let Some(prefix) = self.prefix else { unreachable!()}; match prefix {
+                WasmPrefix::f32 => Instruction::F32Max,
+                WasmPrefix::f64 => Instruction::F64Max,
+                _ => panic!(),
+            }},
+            WasmMnemonic::Copysign => { let Some(prefix) = self.prefix else { unreachable!()}; match prefix {
+                WasmPrefix::f32 => Instruction::F32Copysign,
+                WasmPrefix::f64 => Instruction::F64Copysign,
+                _ => panic!(),
+            }},
+            WasmMnemonic::Extends => Instruction::I64ExtendI32S,
             WasmMnemonic::Extendu => Instruction::I64ExtendI32U,
+            WasmMnemonic::Extend8s => { let Some(prefix) = self.prefix else { unreachable!()}; match prefix {
+                WasmPrefix::i32 => Instruction::I32Extend8S,
+                WasmPrefix::i64 => Instruction::I64Extend8S,
+                _ => panic!(),
+            }},
+            WasmMnemonic::Extend16s => { let Some(prefix) = self.prefix else { unreachable!()}; match prefix {
+                WasmPrefix::i32 => Instruction::I32Extend16S,
+                WasmPrefix::i64 => Instruction::I64Extend16S,
+                _ => panic!(),
+            }},
             WasmMnemonic::Wrap => Instruction::I32WrapI64,
             WasmMnemonic::Promote => Instruction::F64PromoteF32,
             WasmMnemonic::Demote => Instruction::F32DemoteF64,
+            WasmMnemonic::ReinterpretF32 => Instruction::I32ReinterpretF32,
+            WasmMnemonic::ReinterpretF64 => Instruction::I64ReinterpretF64,
+            WasmMnemonic::ReinterpretI32 => Instruction::F32ReinterpretI32,
+            WasmMnemonic::ReinterpretI64 => Instruction::F64ReinterpretI64,
             WasmMnemonic::ConvertI32s => { let Some(prefix) = self.prefix else { unreachable!()}; match prefix {
                 WasmPrefix::f32 => Instruction::F32ConvertI32S,
                 WasmPrefix::f64 => Instruction::F64ConvertI32S,
@@ -920,6 +1215,36 @@ impl<'a> Into<wasm_encoder::Instruction<'a>> for WasmMCInstr {
                 _ => panic!(),
             }},
             WasmMnemonic::Br => { if let Some(WasmOperand::Const(target)) = &self.op1 { Instruction::Br(*target as u32) } else { unreachable!()} },
+            WasmMnemonic::BrIf => { if let Some(WasmOperand::Const(target)) = &self.op1 { Instruction::BrIf(*target as u32) } else { unreachable!()} },
+            WasmMnemonic::Select => Instruction::Select,
+            WasmMnemonic::Load => { 
+                let Some(WasmOperand::MemArg(align)) = self.op1 else { unreachable!() };
+                let memarg = wasm_encoder::MemArg { offset: 0, align: align, memory_index: 0 };
+                
+                let Some(prefix) = self.prefix else { unreachable!()}; match prefix {
+                    WasmPrefix::i32 => Instruction::I32Load(memarg),
+                    WasmPrefix::i64 => Instruction::I64Load(memarg),
+                    WasmPrefix::f32 => Instruction::F32Load(memarg),
+                    WasmPrefix::f64 => Instruction::F64Load(memarg),
+                    _ => panic!(),
+                }
+            },
+            WasmMnemonic::Store => { 
+                let Some(WasmOperand::MemArg(align)) = self.op1 else { unreachable!() };
+                let memarg = wasm_encoder::MemArg { offset: 0, align: align, memory_index: 0 };
+                
+                let Some(prefix) = self.prefix else { unreachable!()}; match prefix {
+                    WasmPrefix::i32 => Instruction::I32Store(memarg),
+                    WasmPrefix::i64 => Instruction::I64Store(memarg),
+                    WasmPrefix::f32 => Instruction::F32Store(memarg),
+                    WasmPrefix::f64 => Instruction::F64Store(memarg),
+                    _ => panic!(),
+                }
+            },
+            WasmMnemonic::MemoryCopy => Instruction::MemoryCopy { src_mem: 0, dst_mem: 0 },
+            WasmMnemonic::MemoryFill => Instruction::MemoryFill(0),
+            WasmMnemonic::Call => { if let Some(WasmOperand::Const(func)) = &self.op1 { Instruction::Call(*func as u32) } else { unreachable!()} },
+            WasmMnemonic::CallIndirect => { if let Some(WasmOperand::Const(ty)) = &self.op1 { Instruction::CallIndirect { type_index: *ty as u32, table_index: 0 } } else { unreachable!()} },
             WasmMnemonic::Block => Instruction::Block(BlockType::Empty),
             WasmMnemonic::End => Instruction::End,
         }
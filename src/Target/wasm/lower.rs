@@ -8,10 +8,14 @@ mod cmp;
 mod cast;
 mod call;
 mod switch;
+mod mem;
+mod bit;
+mod overflow;
+mod float;
 
 use std::collections::HashMap;
 
-use crate::{CodeGen::{MCInstr, MachineInstr, MachineOperand}, Optimizations::Optimize, Target::CallConv, IR::TypeMetadata};
+use crate::{CodeGen::{MCInstr, MachineInstr, MachineOperand}, Optimizations::Optimize, Target::CallConv, IR::{ir::CastKind, TypeMetadata}};
 
 use super::asm::{WasmOperand, WasmPrefix};
 
@@ -29,6 +33,14 @@ pub(crate) fn wasm_lower_instr(sink: &mut Vec<super::asm::WasmMCInstr>, instr: M
         crate::CodeGen::MachineMnemonic::Neg => math::wasm_lower_neg(sink, &instr),
         crate::CodeGen::MachineMnemonic::Shl => math::wasm_lower_shl(sink, &instr),
         crate::CodeGen::MachineMnemonic::Shr => math::wasm_lower_shr(sink, &instr),
+        crate::CodeGen::MachineMnemonic::Rotl => bit::wasm_lower_rotate(sink, &instr, true),
+        crate::CodeGen::MachineMnemonic::Rotr => bit::wasm_lower_rotate(sink, &instr, false),
+        crate::CodeGen::MachineMnemonic::Popcnt => bit::wasm_lower_bitop(sink, &instr),
+        crate::CodeGen::MachineMnemonic::Clz => bit::wasm_lower_bitop(sink, &instr),
+        crate::CodeGen::MachineMnemonic::Ctz => bit::wasm_lower_bitop(sink, &instr),
+        crate::CodeGen::MachineMnemonic::Bswap => bit::wasm_lower_bitop(sink, &instr),
+        crate::CodeGen::MachineMnemonic::BitOpFallback(_) => unreachable!("wasm has instructions for all bit manipulations"),
+        crate::CodeGen::MachineMnemonic::Overflow(kind) => overflow::wasm_lower_overflow(sink, &instr, &kind),
         crate::CodeGen::MachineMnemonic::FMove => mov::wasm_lower_mov(sink, &instr),
         crate::CodeGen::MachineMnemonic::FAdd => math::wasm_lower_add(sink, &instr),
         crate::CodeGen::MachineMnemonic::FAnd => math::wasm_lower_and(sink, &instr),
@@ -42,22 +54,42 @@ pub(crate) fn wasm_lower_instr(sink: &mut Vec<super::asm::WasmMCInstr>, instr: M
         crate::CodeGen::MachineMnemonic::FShl => math::wasm_lower_shl(sink, &instr),
         crate::CodeGen::MachineMnemonic::FShr => math::wasm_lower_shr(sink, &instr),
         crate::CodeGen::MachineMnemonic::FCompare(cmp_mode) => cmp::wasm_lower_cmp(sink, &instr, cmp_mode),
-        crate::CodeGen::MachineMnemonic::FCast(start_ty) => cast::wasm_lower_cast(sink, &instr, start_ty),
+        crate::CodeGen::MachineMnemonic::FloatOp(kind) => float::wasm_lower_floatop(sink, &instr, &kind),
+        crate::CodeGen::MachineMnemonic::FCast(kind, start_ty) => cast::wasm_lower_cast(sink, &instr, kind, start_ty),
         crate::CodeGen::MachineMnemonic::BrCond(iftrue, iffalse) => br::wasm_lower_brcond(sink, &instr, iftrue, iffalse),
         crate::CodeGen::MachineMnemonic::Compare(cmp_mode) => cmp::wasm_lower_cmp(sink, &instr, cmp_mode),
-        crate::CodeGen::MachineMnemonic::Zext(start_ty) => cast::wasm_lower_cast(sink, &instr, start_ty),
-        crate::CodeGen::MachineMnemonic::Downcast(start_ty) => cast::wasm_lower_cast(sink, &instr, start_ty),
+        crate::CodeGen::MachineMnemonic::Zext(start_ty) => cast::wasm_lower_cast(sink, &instr, CastKind::Zext, start_ty),
+        crate::CodeGen::MachineMnemonic::Sext(start_ty) => cast::wasm_lower_cast(sink, &instr, CastKind::Sext, start_ty),
+        crate::CodeGen::MachineMnemonic::Downcast(start_ty) => cast::wasm_lower_cast(sink, &instr, CastKind::Trunc, start_ty),
+        crate::CodeGen::MachineMnemonic::Bitcast(start_ty) => cast::wasm_lower_cast(sink, &instr, CastKind::Bitcast, start_ty),
         crate::CodeGen::MachineMnemonic::Call(func) => call::wasm_lower_call(sink, &instr, func),
+        crate::CodeGen::MachineMnemonic::CallIndirect(ty) => call::wasm_lower_call_indirect(sink, &instr, ty),
+        crate::CodeGen::MachineMnemonic::TailCall(_) => todo!("wasm doesn't support tail calls yet"),
         crate::CodeGen::MachineMnemonic::Br(block) => br::wasm_lower_br(sink, &instr, block),
         crate::CodeGen::MachineMnemonic::Return => ret::wasm_lower_return(sink, &instr),
+        crate::CodeGen::MachineMnemonic::Unreachable => ret::wasm_lower_unreachable(sink, &instr),
+        crate::CodeGen::MachineMnemonic::Trap => ret::wasm_lower_unreachable(sink, &instr),
+        crate::CodeGen::MachineMnemonic::MemCpy => mem::wasm_lower_memcpy(sink, &instr),
+        crate::CodeGen::MachineMnemonic::MemMove => mem::wasm_lower_memcpy(sink, &instr),
+        crate::CodeGen::MachineMnemonic::MemSet => mem::wasm_lower_memset(sink, &instr),
+        crate::CodeGen::MachineMnemonic::AtomicLoad(_) => todo!("atomics require the wasm threads proposal"),
+        crate::CodeGen::MachineMnemonic::AtomicStore(_) => todo!("atomics require the wasm threads proposal"),
+        crate::CodeGen::MachineMnemonic::AtomicRmw(..) => todo!("atomics require the wasm threads proposal"),
+        crate::CodeGen::MachineMnemonic::CmpXchg(_) => todo!("atomics require the wasm threads proposal"),
+        crate::CodeGen::MachineMnemonic::Fence(_) => {},
+        crate::CodeGen::MachineMnemonic::VaSave |
+        crate::CodeGen::MachineMnemonic::VaStart |
+        crate::CodeGen::MachineMnemonic::VaArg(..) |
+        crate::CodeGen::MachineMnemonic::VaCopy => todo!("wasm doesn't support variadic functions yet"),
+        crate::CodeGen::MachineMnemonic::InlineAsm(_) => todo!("wasm doesn't support inline assembly yet"),
+        crate::CodeGen::MachineMnemonic::Label(_) => todo!("wasm doesn't support exception handling yet"),
         crate::CodeGen::MachineMnemonic::AdressLoad(constant) => stack::wasm_lower_adress_load(sink, &instr, constant),
         crate::CodeGen::MachineMnemonic::StackAlloc => stack::wasm_lower_alloc(sink, &instr),
-        crate::CodeGen::MachineMnemonic::Store => stack::wasm_lower_store(sink, &instr),
-        crate::CodeGen::MachineMnemonic::Load => stack::wasm_lower_load(sink, &instr),
+        crate::CodeGen::MachineMnemonic::Store(align) => stack::wasm_lower_store(sink, &instr, align),
+        crate::CodeGen::MachineMnemonic::Load(align) => stack::wasm_lower_load(sink, &instr, align),
         crate::CodeGen::MachineMnemonic::Prolog => {},
         crate::CodeGen::MachineMnemonic::Epilog => {},
         crate::CodeGen::MachineMnemonic::Push => stack::wasm_lower_push(sink, &instr),
-        crate::CodeGen::MachineMnemonic::PushCleanup => {},
         crate::CodeGen::MachineMnemonic::CallStackPrepare => {},
         crate::CodeGen::MachineMnemonic::CallStackRedo => {},
         crate::CodeGen::MachineMnemonic::AdrMove => mov::wasm_lower_mov(sink, &instr),
@@ -94,8 +126,14 @@ pub(crate) fn wasm_construct_local_types(instrs: &Vec<MachineInstr>) -> HashMap<
     for instr in instrs {
         for operand in &instr.operands {
             match operand {
-                MachineOperand::Imm(_) => {},
+                MachineOperand::Imm(_) | MachineOperand::Int(_) => {},
                 MachineOperand::Reg(reg) => match reg {
+                    // every value of a tuple has its own local
+                    crate::CodeGen::Reg::wasm(num, type_metadata) if type_metadata.tuple() => {
+                        for (index, elem) in type_metadata.tupleElems().unwrap_or_default().into_iter().enumerate() {
+                            types.insert(*num + index as i32, elem);
+                        }
+                    },
                     crate::CodeGen::Reg::wasm(num, type_metadata) => if let Some(element) = types.get_mut(num) {
                         *element = *type_metadata;
                     } else {
@@ -115,6 +153,7 @@ impl Into<WasmOperand> for MachineOperand {
     fn into(self) -> WasmOperand {
         match self {
             MachineOperand::Imm(imm) => WasmOperand::Const(imm),
+            MachineOperand::Int(_) => todo!("128bit intengers are not supported by the wasm backend yet"),
             MachineOperand::Reg(var) => match var {
                 crate::CodeGen::Reg::wasm(var, _) => WasmOperand::Var(var),
                 _ => panic!("the wasm backend expects that only wasm registers are used"),
@@ -133,12 +172,13 @@ impl Into<WasmOperand> for &MachineOperand {
 impl Into<WasmPrefix> for TypeMetadata {
     fn into(self) -> WasmPrefix {
         match self {
+            TypeMetadata::i1 => WasmPrefix::i32,
             TypeMetadata::i32 => WasmPrefix::i32,
             TypeMetadata::i64 => WasmPrefix::i64,
             TypeMetadata::ptr => WasmPrefix::i64,
             TypeMetadata::f32 => WasmPrefix::f32,
             TypeMetadata::f64 => WasmPrefix::f64,
-            _ => panic!("wasm only supports i32/i64/f32/f64 and ptrs")
+            _ => panic!("wasm only supports i1/i32/i64/f32/f64 and ptrs")
         }
     }
 }
\ No newline at end of file
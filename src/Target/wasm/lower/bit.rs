@@ -0,0 +1,123 @@
+use super::super::asm::*;
+use super::cast::{wasm_mask, wasm_storage};
+use crate::CodeGen::{MachineInstr, MachineMnemonic};
+
+// i8/i16 values are stored in an i32 (with undefined upper bits), 
+// so they are masked before the native i32 instructions are used
+
+/// Pushes the operand onto the stack
+fn wasm_bit_operand(sink: &mut Vec<WasmMCInstr>, prefix: WasmPrefix, op: &WasmOperand) {
+    if let WasmOperand::Const(_) = op {
+        sink.push( WasmMCInstr::with1(Some(prefix), WasmMnemonic::Const, op.to_owned()));
+    } else {
+        sink.push( WasmMCInstr::with1(Some(WasmPrefix::Local), WasmMnemonic::Get, op.to_owned()));
+    }
+}
+
+fn wasm_bit_const(sink: &mut Vec<WasmMCInstr>, prefix: WasmPrefix, value: i64) {
+    sink.push(WasmMCInstr::with1(Some(prefix), WasmMnemonic::Const, WasmOperand::Const(value as f64)));
+}
+
+/// Lowers popcnt, clz, ctz and bswap
+pub(crate) fn wasm_lower_bitop(sink: &mut Vec<WasmMCInstr>, instr: &MachineInstr) {
+    let op: WasmOperand = (*instr.operands.get(0).expect("expected an operand for the bit manipulation")).into();
+    let out: WasmOperand = instr.out.expect("expected an output for the bit manipulation").into();
+
+    let prefix = wasm_storage(instr.meta);
+    let bits = instr.meta.bitSize() as i64;
+    let size = if prefix == WasmPrefix::i64 { 64 } else { 32 };
+
+    match &instr.mnemonic {
+        MachineMnemonic::Popcnt => {
+            wasm_bit_operand(sink, prefix, &op);
+            wasm_mask(sink, bits as usize, prefix);
+            sink.push(WasmMCInstr::with0(Some(prefix), WasmMnemonic::Popcnt));
+        },
+        MachineMnemonic::Clz => {
+            wasm_bit_operand(sink, prefix, &op);
+            wasm_mask(sink, bits as usize, prefix);
+            sink.push(WasmMCInstr::with0(Some(prefix), WasmMnemonic::Clz));
+
+            if bits < size {
+                wasm_bit_const(sink, prefix, size - bits);
+                sink.push(WasmMCInstr::with0(Some(prefix), WasmMnemonic::Sub));
+            }
+        },
+        MachineMnemonic::Ctz => {
+            wasm_bit_operand(sink, prefix, &op);
+
+            // the set bit above the value makes ctz(0) the bit size
+            if bits < size {
+                wasm_bit_const(sink, prefix, 1 << bits);
+                sink.push(WasmMCInstr::with0(Some(prefix), WasmMnemonic::Or));
+            }
+
+            sink.push(WasmMCInstr::with0(Some(prefix), WasmMnemonic::Ctz));
+        },
+        MachineMnemonic::Bswap => {
+            // every byte is shifted to its new place: ((x >> 8i) & 0xff) << 8(n - 1 - i)
+            let bytes = bits / 8;
+
+            for byte in 0..bytes {
+                wasm_bit_operand(sink, prefix, &op);
+                wasm_bit_const(sink, prefix, 8 * byte);
+                sink.push(WasmMCInstr::with0(Some(prefix), WasmMnemonic::Shru));
+                wasm_bit_const(sink, prefix, 0xff);
+                sink.push(WasmMCInstr::with0(Some(prefix), WasmMnemonic::And));
+                wasm_bit_const(sink, prefix, 8 * (bytes - 1 - byte));
+                sink.push(WasmMCInstr::with0(Some(prefix), WasmMnemonic::Shl));
+
+                if byte > 0 {
+                    sink.push(WasmMCInstr::with0(Some(prefix), WasmMnemonic::Or));
+                }
+            }
+        },
+        mnemonic => panic!("{} isn't a bit manipulation", mnemonic),
+    }
+
+    sink.push(WasmMCInstr::with1(Some(WasmPrefix::Local), WasmMnemonic::Set, out));
+}
+
+/// Lowers rotl and rotr
+pub(crate) fn wasm_lower_rotate(sink: &mut Vec<WasmMCInstr>, instr: &MachineInstr, left: bool) {
+    let ls: WasmOperand = (*instr.operands.get(0).expect("rotates expect ls operand")).into();
+    let rs: WasmOperand = (*instr.operands.get(1).expect("rotates expect rs operand")).into();
+    let out: WasmOperand = instr.out.expect("rotates expect out").into();
+
+    let prefix = wasm_storage(instr.meta);
+    let bits = instr.meta.bitSize() as i64;
+
+    if bits == 32 || bits == 64 {
+        wasm_bit_operand(sink, prefix, &ls);
+        wasm_bit_operand(sink, prefix, &rs);
+        sink.push(WasmMCInstr::with0(Some(prefix), if left { WasmMnemonic::Rotl } else { WasmMnemonic::Rotr }));
+    } else {
+        // (x << (n % bits)) | (x >> (bits - n % bits)) for rotl (the shifts are swapped for rotr)
+        let (first, second) = if left { (WasmMnemonic::Shl, WasmMnemonic::Shru) } else { (WasmMnemonic::Shru, WasmMnemonic::Shl) };
+
+        wasm_bit_operand(sink, prefix, &ls);
+        wasm_mask(sink, bits as usize, prefix);
+        wasm_bit_operand(sink, prefix, &rs);
+        wasm_bit_const(sink, prefix, bits - 1);
+        sink.extend_from_slice(&[
+            WasmMCInstr::with0(Some(prefix), WasmMnemonic::And),
+            WasmMCInstr::with0(Some(prefix), first),
+        ]);
+
+        wasm_bit_operand(sink, prefix, &ls);
+        wasm_mask(sink, bits as usize, prefix);
+        wasm_bit_const(sink, prefix, bits);
+        wasm_bit_operand(sink, prefix, &rs);
+        wasm_bit_const(sink, prefix, bits - 1);
+        sink.extend_from_slice(&[
+            WasmMCInstr::with0(Some(prefix), WasmMnemonic::And),
+            WasmMCInstr::with0(Some(prefix), WasmMnemonic::Sub),
+            WasmMCInstr::with0(Some(prefix), second),
+            WasmMCInstr::with0(Some(prefix), WasmMnemonic::Or),
+        ]);
+
+        wasm_mask(sink, bits as usize, prefix);
+    }
+
+    sink.push(WasmMCInstr::with1(Some(WasmPrefix::Local), WasmMnemonic::Set, out));
+}
@@ -6,5 +6,13 @@ pub(crate) fn wasm_lower_br(sink: &mut Vec<WasmMCInstr>, _: &MachineInstr, block
 }
 
 pub(crate) fn wasm_lower_brcond(sink: &mut Vec<WasmMCInstr>, instr: &MachineInstr, iftrue: String, iffalse: String) {
-    todo!()
+    let cond = instr.operands.get(0).expect("expected condition for conditional branches");
+    let cond: WasmOperand = (*cond).into();
+
+    // booleans are just i32s in wasm, so br_if can consume them directly
+    sink.extend_from_slice(&[
+        WasmMCInstr::with1(Some(WasmPrefix::Local), WasmMnemonic::Get, cond),
+        WasmMCInstr::with1(None, WasmMnemonic::BrIf, WasmOperand::BlockLink(iftrue)),
+        WasmMCInstr::with1(None, WasmMnemonic::Br, WasmOperand::BlockLink(iffalse)),
+    ]);
 }
\ No newline at end of file
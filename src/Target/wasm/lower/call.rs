@@ -1,6 +1,18 @@
 use super::super::asm::*;
 use crate::CodeGen::MachineInstr;
+use crate::IR::FunctionType;
 
-pub(crate) fn wasm_lower_call(sink: &mut Vec<WasmMCInstr>, instr: &MachineInstr, func: String) {
-    todo!()
-}
\ No newline at end of file
+pub(crate) fn wasm_lower_call(sink: &mut Vec<WasmMCInstr>, _: &MachineInstr, func: String) {
+    sink.push( WasmMCInstr::with1(None, WasmMnemonic::Call, WasmOperand::FuncLink(func)) );
+}
+
+pub(crate) fn wasm_lower_call_indirect(sink: &mut Vec<WasmMCInstr>, instr: &MachineInstr, ty: FunctionType) {
+    let target = instr.operands.get(0).expect("call_indirect expects the adress of the called function");
+
+    // function adresses are indices into the function table (see `wasm_emit_mccode`)
+    sink.extend_from_slice(&[
+        WasmMCInstr::with1(Some(WasmPrefix::Local), WasmMnemonic::Get, target.into()),
+        WasmMCInstr::with0(Some(WasmPrefix::i32), WasmMnemonic::Wrap),
+        WasmMCInstr::with1(None, WasmMnemonic::CallIndirect, WasmOperand::TypeLink(ty)),
+    ]);
+}
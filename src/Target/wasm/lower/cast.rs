@@ -1,48 +1,125 @@
 use super::super::asm::*;
 use crate::CodeGen::MachineInstr;
-use crate::IR::TypeMetadata;
-
-pub(crate) fn wasm_lower_cast(sink: &mut Vec<WasmMCInstr>, instr: &MachineInstr, start_ty: TypeMetadata) {
-    let (prefix, mnemonic) = match (start_ty, instr.meta) {
-        (TypeMetadata::i32, TypeMetadata::i64) => (None, WasmMnemonic::Extends),
-        (TypeMetadata::u32, TypeMetadata::u64) => (None, WasmMnemonic::Extendu),
-        (TypeMetadata::i64, TypeMetadata::i32) => (None, WasmMnemonic::Wrap),
-        (TypeMetadata::f32, TypeMetadata::f64) => (None, WasmMnemonic::Promote),
-        (TypeMetadata::f64, TypeMetadata::f32) => (None, WasmMnemonic::Demote),
-
-        (TypeMetadata::i32, TypeMetadata::f32) => (Some(WasmPrefix::f32), WasmMnemonic::ConvertI32s),
-        (TypeMetadata::i64, TypeMetadata::f32) => (Some(WasmPrefix::f32), WasmMnemonic::ConvertI64s),
-        (TypeMetadata::u32, TypeMetadata::f32) => (Some(WasmPrefix::f32), WasmMnemonic::ConvertI32u),
-        (TypeMetadata::u64, TypeMetadata::f32) => (Some(WasmPrefix::f32), WasmMnemonic::ConvertI32u),
-
-        (TypeMetadata::i32, TypeMetadata::f64) => (Some(WasmPrefix::f64), WasmMnemonic::ConvertI32s),
-        (TypeMetadata::i64, TypeMetadata::f64) => (Some(WasmPrefix::f64), WasmMnemonic::ConvertI64s),
-        (TypeMetadata::u32, TypeMetadata::f64) => (Some(WasmPrefix::f64), WasmMnemonic::ConvertI32u),
-        (TypeMetadata::u64, TypeMetadata::f64) => (Some(WasmPrefix::f64), WasmMnemonic::ConvertI32u),
-
-        (TypeMetadata::f32, TypeMetadata::i32) => (Some(WasmPrefix::i32), WasmMnemonic::TruncF32s),
-        (TypeMetadata::f32, TypeMetadata::i64) => (Some(WasmPrefix::i64), WasmMnemonic::TruncF32s),
-        (TypeMetadata::f32, TypeMetadata::u32) => (Some(WasmPrefix::i32), WasmMnemonic::TruncF32u),
-        (TypeMetadata::f32, TypeMetadata::u64) => (Some(WasmPrefix::i64), WasmMnemonic::TruncF32u),
-
-        (TypeMetadata::f64, TypeMetadata::i32) => (Some(WasmPrefix::i32), WasmMnemonic::TruncF64s),
-        (TypeMetadata::f64, TypeMetadata::i64) => (Some(WasmPrefix::i64), WasmMnemonic::TruncF64s),
-        (TypeMetadata::f64, TypeMetadata::u32) => (Some(WasmPrefix::i32), WasmMnemonic::TruncF64u),
-        (TypeMetadata::f64, TypeMetadata::u64) => (Some(WasmPrefix::i64), WasmMnemonic::TruncF64u),
-
-        _ => panic!("illegal cast combination for wasm: cast {} ... to {}", start_ty, instr.meta),
-    };
+use crate::IR::{ir::CastKind, TypeMetadata};
+
+/// Returns the wasm type in which values of `ty` are stored (i8/i16 live in an i32)
+pub(super) fn wasm_storage(ty: TypeMetadata) -> WasmPrefix {
+    match ty {
+        TypeMetadata::f32 => WasmPrefix::f32,
+        TypeMetadata::f64 => WasmPrefix::f64,
+        ty if ty.bitSize() > 32 => WasmPrefix::i64,
+        _ => WasmPrefix::i32,
+    }
+}
+
+/// Clears all bits above the `bits` lowest ones of the value ontop of the stack
+pub(super) fn wasm_mask(sink: &mut Vec<WasmMCInstr>, bits: usize, prefix: WasmPrefix) {
+    if bits >= 32 {
+        return;
+    }
+
+    sink.extend_from_slice(&[
+        WasmMCInstr::with1(Some(prefix), WasmMnemonic::Const, WasmOperand::Const(((1i64 << bits) - 1) as f64)),
+        WasmMCInstr::with0(Some(prefix), WasmMnemonic::And),
+    ]);
+}
+
+/// Sign extends the `bits` lowest bits of the value ontop of the stack
+pub(super) fn wasm_sext(sink: &mut Vec<WasmMCInstr>, bits: usize, prefix: WasmPrefix) {
+    match bits {
+        1 => sink.extend_from_slice(&[
+            WasmMCInstr::with1(Some(WasmPrefix::i32), WasmMnemonic::Const, WasmOperand::Const(31.0)),
+            WasmMCInstr::with0(Some(WasmPrefix::i32), WasmMnemonic::Shl),
+            WasmMCInstr::with1(Some(WasmPrefix::i32), WasmMnemonic::Const, WasmOperand::Const(31.0)),
+            WasmMCInstr::with0(Some(WasmPrefix::i32), WasmMnemonic::Shrs),
+        ]),
+        8 => sink.push(WasmMCInstr::with0(Some(prefix), WasmMnemonic::Extend8s)),
+        16 => sink.push(WasmMCInstr::with0(Some(prefix), WasmMnemonic::Extend16s)),
+        _ => {},
+    }
+}
+
+pub(crate) fn wasm_lower_cast(sink: &mut Vec<WasmMCInstr>, instr: &MachineInstr, kind: CastKind, from: TypeMetadata) {
+    let to = instr.meta;
+
+    let from_prefix = wasm_storage(from);
+    let to_prefix = wasm_storage(to);
 
     let op = (*instr.operands.get(0).expect("expected operand for cast")).into();
     let out = instr.out.expect("expected output for cast").into();
 
     if let WasmOperand::Const(_) = op {
-        sink.push( WasmMCInstr::with1(Some(instr.meta.into()), WasmMnemonic::Const, op));
+        sink.push( WasmMCInstr::with1(Some(from_prefix), WasmMnemonic::Const, op));
     } else {
         sink.push( WasmMCInstr::with1(Some(WasmPrefix::Local), WasmMnemonic::Get, op));
     }
-    sink.extend_from_slice(&[
-        WasmMCInstr::with0(prefix, mnemonic),
-        WasmMCInstr::with1(Some(WasmPrefix::Local), WasmMnemonic::Set, out)
-    ]);
-}
\ No newline at end of file
+
+    match kind {
+        CastKind::Zext => {
+            wasm_mask(sink, from.bitSize(), from_prefix);
+
+            if from_prefix != to_prefix {
+                sink.push(WasmMCInstr::with0(Some(WasmPrefix::i64), WasmMnemonic::Extendu));
+            }
+        },
+        CastKind::Sext => {
+            wasm_sext(sink, from.bitSize(), from_prefix);
+
+            if from_prefix != to_prefix {
+                sink.push(WasmMCInstr::with0(Some(WasmPrefix::i64), WasmMnemonic::Extends));
+            }
+        },
+        CastKind::Trunc => {
+            if from_prefix != to_prefix {
+                sink.push(WasmMCInstr::with0(Some(WasmPrefix::i32), WasmMnemonic::Wrap));
+            }
+
+            wasm_mask(sink, to.bitSize(), to_prefix);
+        },
+        CastKind::FpExt => sink.push(WasmMCInstr::with0(Some(WasmPrefix::f64), WasmMnemonic::Promote)),
+        CastKind::FpTrunc => sink.push(WasmMCInstr::with0(Some(WasmPrefix::f32), WasmMnemonic::Demote)),
+        CastKind::FpToSi | CastKind::FpToUi => {
+            let mnemonic = match (from, kind) {
+                (TypeMetadata::f32, CastKind::FpToSi) => WasmMnemonic::TruncF32s,
+                (TypeMetadata::f32, _) => WasmMnemonic::TruncF32u,
+                (_, CastKind::FpToSi) => WasmMnemonic::TruncF64s,
+                (_, _) => WasmMnemonic::TruncF64u,
+            };
+
+            sink.push(WasmMCInstr::with0(Some(to_prefix), mnemonic));
+            wasm_mask(sink, to.bitSize(), to_prefix);
+        },
+        CastKind::SiToFp | CastKind::UiToFp => {
+            if kind == CastKind::SiToFp {
+                wasm_sext(sink, from.bitSize(), from_prefix);
+            } else {
+                wasm_mask(sink, from.bitSize(), from_prefix);
+            }
+
+            let mnemonic = match (from_prefix, kind) {
+                (WasmPrefix::i32, CastKind::SiToFp) => WasmMnemonic::ConvertI32s,
+                (WasmPrefix::i32, _) => WasmMnemonic::ConvertI32u,
+                (_, CastKind::SiToFp) => WasmMnemonic::ConvertI64s,
+                (_, _) => WasmMnemonic::ConvertI64u,
+            };
+
+            sink.push(WasmMCInstr::with0(Some(to_prefix), mnemonic));
+        },
+        CastKind::Bitcast | CastKind::PtrToInt | CastKind::IntToPtr => {
+            // integers with the same storage don't need to be converted
+            let mnemonic = match (from_prefix, to_prefix) {
+                (WasmPrefix::f32, WasmPrefix::i32) => Some(WasmMnemonic::ReinterpretF32),
+                (WasmPrefix::f64, WasmPrefix::i64) => Some(WasmMnemonic::ReinterpretF64),
+                (WasmPrefix::i32, WasmPrefix::f32) => Some(WasmMnemonic::ReinterpretI32),
+                (WasmPrefix::i64, WasmPrefix::f64) => Some(WasmMnemonic::ReinterpretI64),
+                _ => None,
+            };
+
+            if let Some(mnemonic) = mnemonic {
+                sink.push(WasmMCInstr::with0(Some(to_prefix), mnemonic));
+            }
+        },
+    }
+
+    sink.push(WasmMCInstr::with1(Some(WasmPrefix::Local), WasmMnemonic::Set, out));
+}
@@ -1,10 +1,40 @@
 use super::super::asm::*;
 use crate::CodeGen::MachineInstr;
 
+fn wasm_push_value(sink: &mut Vec<WasmMCInstr>, instr: &MachineInstr, value: WasmOperand) {
+    if let WasmOperand::Const(_) = value {
+        sink.push( WasmMCInstr::with1(Some(instr.meta.into()), WasmMnemonic::Const, value));
+    } else {
+        sink.push( WasmMCInstr::with1(Some(WasmPrefix::Local), WasmMnemonic::Get, value));
+    }
+}
+
 pub(crate) fn wasm_lower_cmove(sink: &mut Vec<WasmMCInstr>, instr: &MachineInstr) {
-    todo!()
+    let cond: WasmOperand = (*instr.operands.get(0).expect("expected condition for valid cmov")).into();
+    let value: WasmOperand = (*instr.operands.get(1).expect("expected value for valid cmov")).into();
+    let out: WasmOperand = instr.out.expect("expected output for valid cmov").into();
+
+    // out = cond ? value : out
+    wasm_push_value(sink, instr, value);
+    sink.extend_from_slice(&[
+        WasmMCInstr::with1(Some(WasmPrefix::Local), WasmMnemonic::Get, out.clone()),
+        WasmMCInstr::with1(Some(WasmPrefix::Local), WasmMnemonic::Get, cond),
+        WasmMCInstr::with0(None, WasmMnemonic::Select),
+        WasmMCInstr::with1(Some(WasmPrefix::Local), WasmMnemonic::Set, out),
+    ]);
 }
 
 pub(crate) fn wasm_lower_cmovne(sink: &mut Vec<WasmMCInstr>, instr: &MachineInstr) {
-    todo!()
+    let cond: WasmOperand = (*instr.operands.get(0).expect("expected condition for valid cmov")).into();
+    let value: WasmOperand = (*instr.operands.get(1).expect("expected value for valid cmov")).into();
+    let out: WasmOperand = instr.out.expect("expected output for valid cmov").into();
+
+    // out = cond ? out : value
+    sink.push(WasmMCInstr::with1(Some(WasmPrefix::Local), WasmMnemonic::Get, out.clone()));
+    wasm_push_value(sink, instr, value);
+    sink.extend_from_slice(&[
+        WasmMCInstr::with1(Some(WasmPrefix::Local), WasmMnemonic::Get, cond),
+        WasmMCInstr::with0(None, WasmMnemonic::Select),
+        WasmMCInstr::with1(Some(WasmPrefix::Local), WasmMnemonic::Set, out),
+    ]);
 }
\ No newline at end of file
@@ -1,60 +1,96 @@
 use super::super::asm::*;
 use crate::{prelude::CmpMode, CodeGen::MachineInstr};
 
+/// Pushes the operand onto the stack
+fn wasm_cmp_operand(sink: &mut Vec<WasmMCInstr>, instr: &MachineInstr, op: &WasmOperand) {
+    if let WasmOperand::Const(_) = op {
+        sink.push( WasmMCInstr::with1(Some(instr.meta.into()), WasmMnemonic::Const, op.to_owned()));
+    } else {
+        sink.push( WasmMCInstr::with1(Some(WasmPrefix::Local), WasmMnemonic::Get, op.to_owned()));
+    }
+}
+
+/// Compares the two operands with the wasm compare instruction `mnemonic`
+fn wasm_cmp(sink: &mut Vec<WasmMCInstr>, instr: &MachineInstr, ls: &WasmOperand, rs: &WasmOperand, mnemonic: WasmMnemonic) {
+    wasm_cmp_operand(sink, instr, ls);
+    wasm_cmp_operand(sink, instr, rs);
+
+    sink.push(WasmMCInstr::with0(Some(instr.meta.into()), mnemonic));
+}
+
 pub(crate) fn wasm_lower_cmp(sink: &mut Vec<WasmMCInstr>, instr: &MachineInstr, mode: CmpMode) {
     let ls = instr.operands.get(0).expect("expected ls operand for cmps");
     let rs = instr.operands.get(1).expect("expected rs operand for cmps");
 
-    let ls = (*ls).into();
-    let rs = (*rs).into();
+    let ls: WasmOperand = (*ls).into();
+    let rs: WasmOperand = (*rs).into();
     let out: WasmOperand = instr.out.unwrap().into();
 
-    let mnemonic = match mode {
-        CmpMode::Eqal => WasmMnemonic::Eq,
-        CmpMode::NotEqal => WasmMnemonic::Ne,
-        CmpMode::GreaterThan => if instr.meta.float() {
-            WasmMnemonic::Gt
-        } else if instr.meta.signed() {
-            WasmMnemonic::Gts
-        } else {
-            WasmMnemonic::Gtu
+    // the float compares of wasm are ordered (except ne), so the unordered ones
+    // are the inverted ordered ones and NaNs are checked by comparing a value with itself
+    match mode.resolve(instr.meta) {
+        CmpMode::Ordered => {
+            wasm_cmp(sink, instr, &ls, &ls, WasmMnemonic::Eq);
+            wasm_cmp(sink, instr, &rs, &rs, WasmMnemonic::Eq);
+            sink.push(WasmMCInstr::with0(Some(WasmPrefix::i32), WasmMnemonic::And));
         },
-        CmpMode::LessThan => if instr.meta.float() {
-            WasmMnemonic::Lt
-        } else if instr.meta.signed() {
-            WasmMnemonic::Lts
-        } else {
-            WasmMnemonic::Ltu
+        CmpMode::Unordered => {
+            wasm_cmp(sink, instr, &ls, &ls, WasmMnemonic::Ne);
+            wasm_cmp(sink, instr, &rs, &rs, WasmMnemonic::Ne);
+            sink.push(WasmMCInstr::with0(Some(WasmPrefix::i32), WasmMnemonic::Or));
         },
-        CmpMode::GreaterThanOrEqual => if instr.meta.float() {
-            WasmMnemonic::Ge
-        } else if instr.meta.signed() {
-            WasmMnemonic::Ges
-        } else {
-            WasmMnemonic::Geu
+        CmpMode::OrderedNotEqal => {
+            wasm_cmp(sink, instr, &ls, &rs, WasmMnemonic::Lt);
+            wasm_cmp(sink, instr, &ls, &rs, WasmMnemonic::Gt);
+            sink.push(WasmMCInstr::with0(Some(WasmPrefix::i32), WasmMnemonic::Or));
         },
-        CmpMode::LessThanOrEqual => if instr.meta.float() {
-            WasmMnemonic::Le
-        } else if instr.meta.signed() {
-            WasmMnemonic::Les
-        } else {
-            WasmMnemonic::Leu
+        CmpMode::UnorderedEqal => {
+            // !(olt || ogt)
+            wasm_cmp(sink, instr, &ls, &rs, WasmMnemonic::Lt);
+            wasm_cmp(sink, instr, &ls, &rs, WasmMnemonic::Gt);
+            sink.extend_from_slice(&[
+                WasmMCInstr::with0(Some(WasmPrefix::i32), WasmMnemonic::Or),
+                WasmMCInstr::with1(Some(WasmPrefix::i32), WasmMnemonic::Const, WasmOperand::Const(1.0)),
+                WasmMCInstr::with0(Some(WasmPrefix::i32), WasmMnemonic::Xor),
+            ]);
         },
-    };
-    if let WasmOperand::Const(_) = ls {
-        sink.push( WasmMCInstr::with1(Some(instr.meta.into()), WasmMnemonic::Const, ls));
-    } else {
-        sink.push( WasmMCInstr::with1(Some(WasmPrefix::Local), WasmMnemonic::Get, ls));
-    }
+        mode @ (CmpMode::UnorderedGreaterThan | CmpMode::UnorderedLessThan | 
+        CmpMode::UnorderedGreaterThanOrEqual | CmpMode::UnorderedLessThanOrEqual) => {
+            let inverse = match mode {
+                CmpMode::UnorderedGreaterThan => WasmMnemonic::Le,
+                CmpMode::UnorderedLessThan => WasmMnemonic::Ge,
+                CmpMode::UnorderedGreaterThanOrEqual => WasmMnemonic::Lt,
+                _ => WasmMnemonic::Gt,
+            };
 
-    if let WasmOperand::Const(_) = rs {
-        sink.push( WasmMCInstr::with1(Some(instr.meta.into()), WasmMnemonic::Const, rs));
-    } else {
-        sink.push( WasmMCInstr::with1(Some(WasmPrefix::Local), WasmMnemonic::Get, rs));
+            wasm_cmp(sink, instr, &ls, &rs, inverse);
+            sink.extend_from_slice(&[
+                WasmMCInstr::with1(Some(WasmPrefix::i32), WasmMnemonic::Const, WasmOperand::Const(1.0)),
+                WasmMCInstr::with0(Some(WasmPrefix::i32), WasmMnemonic::Xor),
+            ]);
+        },
+        mode => {
+            let mnemonic = match mode {
+                CmpMode::Eqal | CmpMode::OrderedEqal => WasmMnemonic::Eq,
+                CmpMode::NotEqal | CmpMode::UnorderedNotEqal => WasmMnemonic::Ne,
+                CmpMode::OrderedGreaterThan => WasmMnemonic::Gt,
+                CmpMode::OrderedLessThan => WasmMnemonic::Lt,
+                CmpMode::OrderedGreaterThanOrEqual => WasmMnemonic::Ge,
+                CmpMode::OrderedLessThanOrEqual => WasmMnemonic::Le,
+                CmpMode::SignedGreaterThan => WasmMnemonic::Gts,
+                CmpMode::SignedLessThan => WasmMnemonic::Lts,
+                CmpMode::SignedGreaterThanOrEqual => WasmMnemonic::Ges,
+                CmpMode::SignedLessThanOrEqual => WasmMnemonic::Les,
+                CmpMode::UnsignedGreaterThan => WasmMnemonic::Gtu,
+                CmpMode::UnsignedLessThan => WasmMnemonic::Ltu,
+                CmpMode::UnsignedGreaterThanOrEqual => WasmMnemonic::Geu,
+                CmpMode::UnsignedLessThanOrEqual => WasmMnemonic::Leu,
+                mode => panic!("the compare mode {} can't be lowered to wasm", mode),
+            };
+
+            wasm_cmp(sink, instr, &ls, &rs, mnemonic);
+        },
     }
 
-    sink.extend_from_slice(&[
-        WasmMCInstr::with0(Some(instr.meta.into()), mnemonic),
-        WasmMCInstr::with1(Some(WasmPrefix::Local), WasmMnemonic::Set, out)
-    ]);
-}
\ No newline at end of file
+    sink.push(WasmMCInstr::with1(Some(WasmPrefix::Local), WasmMnemonic::Set, out));
+}
@@ -0,0 +1,89 @@
+use super::super::asm::*;
+use crate::prelude::FloatOpKind;
+use crate::CodeGen::MachineInstr;
+use crate::IR::TypeMetadata;
+
+// Everything except fma (which is a libm call) and round maps onto a native instruction.
+// round is done as trunc(x + copysign(0.49999..., x)), because `nearest` rounds halfway cases to even.
+// min and max return NaN if one operand is NaN, so selects pick the other operand in that case
+
+/// Pushes the operand onto the stack
+fn wasm_float_operand(sink: &mut Vec<WasmMCInstr>, prefix: WasmPrefix, op: &WasmOperand) {
+    if let WasmOperand::Const(_) = op {
+        sink.push( WasmMCInstr::with1(Some(prefix), WasmMnemonic::Const, op.to_owned()));
+    } else {
+        sink.push( WasmMCInstr::with1(Some(WasmPrefix::Local), WasmMnemonic::Get, op.to_owned()));
+    }
+}
+
+/// Pushes if the operand is NaN (NaN is the only value which isn't equal to itself)
+fn wasm_float_is_nan(sink: &mut Vec<WasmMCInstr>, prefix: WasmPrefix, op: &WasmOperand) {
+    wasm_float_operand(sink, prefix, op);
+    wasm_float_operand(sink, prefix, op);
+    sink.push(WasmMCInstr::with0(Some(prefix), WasmMnemonic::Ne));
+}
+
+/// Lowers the float math functions
+pub(crate) fn wasm_lower_floatop(sink: &mut Vec<WasmMCInstr>, instr: &MachineInstr, kind: &FloatOpKind) {
+    let out: WasmOperand = instr.out.expect("expected an output for the float operation").into();
+
+    let arg = |index: usize| -> WasmOperand { (*instr.operands.get(index).expect("expected an operand for the float operation")).into() };
+
+    let prefix: WasmPrefix = instr.meta.into();
+
+    match kind {
+        FloatOpKind::Sqrt | FloatOpKind::Fabs | FloatOpKind::Floor | FloatOpKind::Ceil | FloatOpKind::Trunc => {
+            wasm_float_operand(sink, prefix, &arg(0));
+
+            sink.push(WasmMCInstr::with0(Some(prefix), match kind {
+                FloatOpKind::Sqrt => WasmMnemonic::Sqrt,
+                FloatOpKind::Fabs => WasmMnemonic::Abs,
+                FloatOpKind::Floor => WasmMnemonic::Floor,
+                FloatOpKind::Ceil => WasmMnemonic::Ceil,
+                _ => WasmMnemonic::Trunc,
+            }));
+        },
+        FloatOpKind::Copysign => {
+            wasm_float_operand(sink, prefix, &arg(0));
+            wasm_float_operand(sink, prefix, &arg(1));
+            sink.push(WasmMCInstr::with0(Some(prefix), WasmMnemonic::Copysign));
+        },
+        FloatOpKind::Round => {
+            // the biggest value below 0.5, so that x + 0.5 can't round up to the next intenger
+            let half = if instr.meta == TypeMetadata::f32 { 0.49999997f32 as f64 } else { 0.49999999999999994 };
+
+            wasm_float_operand(sink, prefix, &arg(0));
+
+            sink.push(WasmMCInstr::with1(Some(prefix), WasmMnemonic::Const, WasmOperand::Const(half)));
+            wasm_float_operand(sink, prefix, &arg(0));
+
+            sink.extend_from_slice(&[
+                WasmMCInstr::with0(Some(prefix), WasmMnemonic::Copysign),
+                WasmMCInstr::with0(Some(prefix), WasmMnemonic::Add),
+                WasmMCInstr::with0(Some(prefix), WasmMnemonic::Trunc),
+            ]);
+        },
+        FloatOpKind::Minnum | FloatOpKind::Maxnum => {
+            let ls = arg(0);
+            let rs = arg(1);
+
+            let mnemonic = if *kind == FloatOpKind::Minnum { WasmMnemonic::Min } else { WasmMnemonic::Max };
+
+            // rs is NaN ? ls : (ls is NaN ? rs : min(ls, rs))
+            wasm_float_operand(sink, prefix, &ls);
+
+            wasm_float_operand(sink, prefix, &rs);
+            wasm_float_operand(sink, prefix, &ls);
+            wasm_float_operand(sink, prefix, &rs);
+            sink.push(WasmMCInstr::with0(Some(prefix), mnemonic));
+            wasm_float_is_nan(sink, prefix, &ls);
+            sink.push(WasmMCInstr::with0(None, WasmMnemonic::Select));
+
+            wasm_float_is_nan(sink, prefix, &rs);
+            sink.push(WasmMCInstr::with0(None, WasmMnemonic::Select));
+        },
+        FloatOpKind::Fma => panic!("wasm has no fma instruction (it is compiled into a call to libm)"),
+    }
+
+    sink.push(WasmMCInstr::with1(Some(WasmPrefix::Local), WasmMnemonic::Set, out));
+}
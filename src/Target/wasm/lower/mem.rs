@@ -0,0 +1,37 @@
+use super::super::asm::*;
+use crate::CodeGen::MachineInstr;
+
+// the memory intrinsics are mapped onto the bulk memory instructions
+// (memory.copy is overlap safe so it's used for memcpy and memmove)
+
+fn wasm_push_operand(sink: &mut Vec<WasmMCInstr>, op: WasmOperand, prefix: WasmPrefix) {
+    if let WasmOperand::Const(_) = op {
+        sink.push( WasmMCInstr::with1(Some(prefix), WasmMnemonic::Const, op) );
+    } else {
+        sink.push( WasmMCInstr::with1(Some(WasmPrefix::Local), WasmMnemonic::Get, op) );
+    }
+}
+
+pub(crate) fn wasm_lower_memcpy(sink: &mut Vec<WasmMCInstr>, instr: &MachineInstr) {
+    let dst = instr.operands.get(0).expect("memcpy expects a destination");
+    let src = instr.operands.get(1).expect("memcpy expects a source");
+    let size = instr.operands.get(2).expect("memcpy expects a size");
+
+    wasm_push_operand(sink, dst.into(), WasmPrefix::i64);
+    wasm_push_operand(sink, src.into(), WasmPrefix::i64);
+    wasm_push_operand(sink, size.into(), WasmPrefix::i64);
+
+    sink.push( WasmMCInstr::with0(None, WasmMnemonic::MemoryCopy) );
+}
+
+pub(crate) fn wasm_lower_memset(sink: &mut Vec<WasmMCInstr>, instr: &MachineInstr) {
+    let dst = instr.operands.get(0).expect("memset expects a destination");
+    let val = instr.operands.get(1).expect("memset expects a value");
+    let size = instr.operands.get(2).expect("memset expects a size");
+
+    wasm_push_operand(sink, dst.into(), WasmPrefix::i64);
+    wasm_push_operand(sink, val.into(), WasmPrefix::i32);
+    wasm_push_operand(sink, size.into(), WasmPrefix::i64);
+
+    sink.push( WasmMCInstr::with0(None, WasmMnemonic::MemoryFill) );
+}
@@ -0,0 +1,168 @@
+use super::super::asm::*;
+use super::cast::{wasm_mask, wasm_sext, wasm_storage};
+use crate::prelude::OverflowKind;
+use crate::CodeGen::MachineInstr;
+
+// Values up to 32 bits are recomputed exactly in an i64, which is then checked if it fits
+// into the type. 64 bit values check the wrapped result: add/sub compare it with the operands
+// and mul divides it by the left operand (signed MIN / -1 traps, so -1 is handled through a select)
+
+/// Pushes the operand onto the stack
+fn wasm_overflow_operand(sink: &mut Vec<WasmMCInstr>, prefix: WasmPrefix, op: &WasmOperand) {
+    if let WasmOperand::Const(_) = op {
+        sink.push( WasmMCInstr::with1(Some(prefix), WasmMnemonic::Const, op.to_owned()));
+    } else {
+        sink.push( WasmMCInstr::with1(Some(WasmPrefix::Local), WasmMnemonic::Get, op.to_owned()));
+    }
+}
+
+fn wasm_overflow_const(sink: &mut Vec<WasmMCInstr>, prefix: WasmPrefix, value: i64) {
+    sink.push(WasmMCInstr::with1(Some(prefix), WasmMnemonic::Const, WasmOperand::Const(value as f64)));
+}
+
+/// Lowers the overflow checked add, sub and mul
+pub(crate) fn wasm_lower_overflow(sink: &mut Vec<WasmMCInstr>, instr: &MachineInstr, kind: &OverflowKind) {
+    let ls: WasmOperand = (*instr.operands.get(0).expect("expected a left operand for the overflow checked arithmetic")).into();
+    let rs: WasmOperand = (*instr.operands.get(1).expect("expected a right operand for the overflow checked arithmetic")).into();
+    let overflow: WasmOperand = (*instr.operands.get(2).expect("expected an overflow flag for the overflow checked arithmetic")).into();
+    let out: WasmOperand = instr.out.expect("expected an output for the overflow checked arithmetic").into();
+
+    let prefix = wasm_storage(instr.meta);
+    let bits = instr.meta.bitSize();
+
+    let math = match kind {
+        OverflowKind::SAdd | OverflowKind::UAdd => WasmMnemonic::Add,
+        OverflowKind::SSub | OverflowKind::USub => WasmMnemonic::Sub,
+        OverflowKind::SMul | OverflowKind::UMul => WasmMnemonic::Mul,
+    };
+
+    // the wrapped result
+    wasm_overflow_operand(sink, prefix, &ls);
+    wasm_overflow_operand(sink, prefix, &rs);
+    sink.extend_from_slice(&[
+        WasmMCInstr::with0(Some(prefix), math.clone()),
+        WasmMCInstr::with1(Some(WasmPrefix::Local), WasmMnemonic::Set, out.clone()),
+    ]);
+
+    let wide = WasmPrefix::i64;
+
+    if bits < 64 {
+        for op in [&ls, &rs] {
+            wasm_overflow_operand(sink, prefix, op);
+
+            if kind.signed() {
+                wasm_sext(sink, bits, prefix);
+                sink.push(WasmMCInstr::with0(Some(wide), WasmMnemonic::Extends));
+            } else {
+                wasm_mask(sink, bits, prefix);
+                sink.push(WasmMCInstr::with0(Some(wide), WasmMnemonic::Extendu));
+            }
+        }
+
+        sink.push(WasmMCInstr::with0(Some(wide), math));
+
+        // signed results fit if they are inside of [-2^(bits - 1), 2^(bits - 1))
+        if kind.signed() {
+            wasm_overflow_const(sink, wide, 1 << (bits - 1));
+            sink.push(WasmMCInstr::with0(Some(wide), WasmMnemonic::Add));
+        }
+
+        wasm_overflow_const(sink, wide, bits as i64);
+        sink.push(WasmMCInstr::with0(Some(wide), WasmMnemonic::Shru));
+        wasm_overflow_const(sink, wide, 0);
+        sink.push(WasmMCInstr::with0(Some(wide), WasmMnemonic::Ne));
+    } else {
+        let op = |sink: &mut Vec<WasmMCInstr>, op: &WasmOperand| wasm_overflow_operand(sink, wide, op);
+        let emit = |sink: &mut Vec<WasmMCInstr>, prefix: WasmPrefix, mnemonic: WasmMnemonic| sink.push(WasmMCInstr::with0(Some(prefix), mnemonic));
+
+        match kind {
+            OverflowKind::UAdd => {
+                // the result wrapped around if it is smaller than an operand
+                op(sink, &out);
+                op(sink, &ls);
+                emit(sink, wide, WasmMnemonic::Ltu);
+            },
+            OverflowKind::USub => {
+                op(sink, &ls);
+                op(sink, &rs);
+                emit(sink, wide, WasmMnemonic::Ltu);
+            },
+            OverflowKind::SAdd => {
+                // ((ls ^ out) & (rs ^ out)) < 0
+                op(sink, &ls);
+                op(sink, &out);
+                emit(sink, wide, WasmMnemonic::Xor);
+                op(sink, &rs);
+                op(sink, &out);
+                emit(sink, wide, WasmMnemonic::Xor);
+                emit(sink, wide, WasmMnemonic::And);
+                wasm_overflow_const(sink, wide, 0);
+                emit(sink, wide, WasmMnemonic::Lts);
+            },
+            OverflowKind::SSub => {
+                // ((ls ^ rs) & (ls ^ out)) < 0
+                op(sink, &ls);
+                op(sink, &rs);
+                emit(sink, wide, WasmMnemonic::Xor);
+                op(sink, &ls);
+                op(sink, &out);
+                emit(sink, wide, WasmMnemonic::Xor);
+                emit(sink, wide, WasmMnemonic::And);
+                wasm_overflow_const(sink, wide, 0);
+                emit(sink, wide, WasmMnemonic::Lts);
+            },
+            OverflowKind::UMul => {
+                // ls != 0 && out / ls != rs (ls is replaced by 1 if it is 0)
+                op(sink, &out);
+                op(sink, &ls);
+                op(sink, &ls);
+                wasm_overflow_const(sink, wide, 0);
+                emit(sink, wide, WasmMnemonic::Eq);
+                emit(sink, wide, WasmMnemonic::Extendu);
+                emit(sink, wide, WasmMnemonic::Or);
+                emit(sink, wide, WasmMnemonic::Divu);
+                op(sink, &rs);
+                emit(sink, wide, WasmMnemonic::Ne);
+
+                op(sink, &ls);
+                wasm_overflow_const(sink, wide, 0);
+                emit(sink, wide, WasmMnemonic::Ne);
+                emit(sink, WasmPrefix::i32, WasmMnemonic::And);
+            },
+            OverflowKind::SMul => {
+                // ls != 0 && (ls == -1 ? rs == MIN : out / ls != rs)
+                op(sink, &rs);
+                wasm_overflow_const(sink, wide, i64::MIN);
+                emit(sink, wide, WasmMnemonic::Eq);
+
+                // the divisor is 1 if ls is 0 or -1
+                op(sink, &out);
+                wasm_overflow_const(sink, wide, 1);
+                op(sink, &ls);
+                op(sink, &ls);
+                wasm_overflow_const(sink, wide, 0);
+                emit(sink, wide, WasmMnemonic::Eq);
+                op(sink, &ls);
+                wasm_overflow_const(sink, wide, -1);
+                emit(sink, wide, WasmMnemonic::Eq);
+                emit(sink, WasmPrefix::i32, WasmMnemonic::Or);
+                sink.push(WasmMCInstr::with0(None, WasmMnemonic::Select));
+                emit(sink, wide, WasmMnemonic::Divs);
+                op(sink, &rs);
+                emit(sink, wide, WasmMnemonic::Ne);
+
+                op(sink, &ls);
+                wasm_overflow_const(sink, wide, -1);
+                emit(sink, wide, WasmMnemonic::Eq);
+                sink.push(WasmMCInstr::with0(None, WasmMnemonic::Select));
+
+                op(sink, &ls);
+                wasm_overflow_const(sink, wide, 0);
+                emit(sink, wide, WasmMnemonic::Ne);
+                emit(sink, WasmPrefix::i32, WasmMnemonic::And);
+            },
+        }
+    }
+
+    sink.push(WasmMCInstr::with1(Some(WasmPrefix::Local), WasmMnemonic::Set, overflow));
+}
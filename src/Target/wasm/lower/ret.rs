@@ -3,6 +3,17 @@ use crate::CodeGen::MachineInstr;
 
 pub(crate) fn wasm_lower_return(sink: &mut Vec<WasmMCInstr>, instr: &MachineInstr) {
     let op = instr.operands.get(0).expect("return expects operand");
+
+    if let Some(elems) = instr.meta.tupleElems() {
+        // the values are returned using the multi-value proposal
+        for index in 0..elems.len() {
+            sink.push( WasmMCInstr::with1(Some(WasmPrefix::Local), WasmMnemonic::Get, op.field(instr.meta, index).into()) );
+        }
+
+        sink.push( WasmMCInstr::with0(None, WasmMnemonic::Return) );
+        return;
+    }
+
     let op = op.into();
 
     if let WasmOperand::Const(_) = op {
@@ -12,4 +23,8 @@ pub(crate) fn wasm_lower_return(sink: &mut Vec<WasmMCInstr>, instr: &MachineInst
     }
 
     sink.push( WasmMCInstr::with0(None, WasmMnemonic::Return) );
-}
\ No newline at end of file
+}
+
+pub(crate) fn wasm_lower_unreachable(sink: &mut Vec<WasmMCInstr>, _: &MachineInstr) {
+    sink.push( WasmMCInstr::with0(None, WasmMnemonic::Unreachable) );
+}
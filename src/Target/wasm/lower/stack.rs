@@ -5,16 +5,55 @@ pub(crate) fn wasm_lower_alloc(sink: &mut Vec<WasmMCInstr>, instr: &MachineInstr
     todo!()
 }
 
-pub(crate) fn wasm_lower_store(sink: &mut Vec<WasmMCInstr>, instr: &MachineInstr) {
-    todo!()
+/// Returns the memarg alignment (log2) of a memory access
+/// (wasm doesn't allow alignments which are bigger than the natural one)
+fn wasm_memarg(instr: &MachineInstr, align: Option<usize>) -> WasmOperand {
+    let natural = instr.meta.byteSize();
+    
+    let align = match align {
+        Some(align) if align < natural => align,
+        _ => natural,
+    };
+
+    WasmOperand::MemArg(align.trailing_zeros())
 }
 
-pub(crate) fn wasm_lower_load(sink: &mut Vec<WasmMCInstr>, instr: &MachineInstr) {
-    todo!()
+pub(crate) fn wasm_lower_store(sink: &mut Vec<WasmMCInstr>, instr: &MachineInstr, align: Option<usize>) {
+    let ptr = instr.out.expect("stores need a pointer");
+    let value = instr.operands.get(0).expect("stores need a value");
+
+    let value: WasmOperand = (*value).into();
+
+    sink.push( WasmMCInstr::with1(Some(WasmPrefix::Local), WasmMnemonic::Get, ptr.into()) );
+
+    if let WasmOperand::Const(_) = value {
+        sink.push( WasmMCInstr::with1(Some(instr.meta.into()), WasmMnemonic::Const, value) );
+    } else {
+        sink.push( WasmMCInstr::with1(Some(WasmPrefix::Local), WasmMnemonic::Get, value) );
+    }
+
+    sink.push( WasmMCInstr::with1(Some(instr.meta.into()), WasmMnemonic::Store, wasm_memarg(instr, align)) );
+}
+
+pub(crate) fn wasm_lower_load(sink: &mut Vec<WasmMCInstr>, instr: &MachineInstr, align: Option<usize>) {
+    let out = instr.out.expect("loads need a output");
+    let ptr = instr.operands.get(0).expect("loads need a pointer");
+
+    sink.extend_from_slice(&[
+        WasmMCInstr::with1(Some(WasmPrefix::Local), WasmMnemonic::Get, (*ptr).into()),
+        WasmMCInstr::with1(Some(instr.meta.into()), WasmMnemonic::Load, wasm_memarg(instr, align)),
+        WasmMCInstr::with1(Some(WasmPrefix::Local), WasmMnemonic::Set, out.into()),
+    ]);
 }
 
 pub(crate) fn wasm_lower_adress_load(sink: &mut Vec<WasmMCInstr>, instr: &MachineInstr, constant: String) {
-    todo!()
+    let out = instr.out.expect("adress load expects out");
+
+    // currently only the adresses of functions (their index in the function table) are supported
+    sink.extend_from_slice(&[
+        WasmMCInstr::with1(Some(WasmPrefix::i64), WasmMnemonic::Const, WasmOperand::FuncLink(constant)),
+        WasmMCInstr::with1(Some(WasmPrefix::Local), WasmMnemonic::Set, out.into()),
+    ]);
 }
 
 pub(crate) fn wasm_lower_push(sink: &mut Vec<WasmMCInstr>, instr: &MachineInstr) {
@@ -24,6 +63,6 @@ pub(crate) fn wasm_lower_push(sink: &mut Vec<WasmMCInstr>, instr: &MachineInstr)
     if let WasmOperand::Const(_) = op {
         sink.push( WasmMCInstr::with1(Some(instr.meta.into()), WasmMnemonic::Const, op));
     } else {
-        sink.push( WasmMCInstr::with1(Some(instr.meta.into()), WasmMnemonic::Get, op));
+        sink.push( WasmMCInstr::with1(Some(WasmPrefix::Local), WasmMnemonic::Get, op));
     }
 }
\ No newline at end of file
@@ -7,7 +7,7 @@ pub(crate) mod obj;
 
 mod reg_alloc;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::CodeGen::{Allocator, CompilationHelper, ConstImmRules, MachineCallingConvention};
 
@@ -44,6 +44,12 @@ pub fn initializeWasmTarget(_: CallConv) -> TargetBackendDescr {
         ffpregs: Vec::new(),
         call: MachineCallingConvention { call_conv: CallConv::WasmBasicCAbi },
         callee_save_registers: Vec::new(),
+        va: None,
+        arg_stores: Vec::new(),
+        arg_loads: Vec::new(),
+        arg_copies: Vec::new(),
+        ret_ptr: None,
+        features: HashSet::new(),
     };
 
     let mut compiler = CompilationHelper::new(
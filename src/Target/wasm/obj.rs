@@ -1,5 +1,5 @@
 use std::{collections::{BTreeMap, HashMap}, error::Error};
-use crate::{debug::DebugRegistry, Obj::ObjectBuilder, Optimizations::Optimize, Target::*, IR::TypeMetadata};
+use crate::{debug::DebugRegistry, Obj::ObjectBuilder, Optimizations::Optimize, Target::*, IR::{FunctionType, TypeMetadata}};
 use wasm::{asm::{WasmMCInstr, WasmMnemonic, WasmOperand}, lower::wasm_construct_local_types};
 use wasm_encoder::*;
 
@@ -14,18 +14,29 @@ pub(crate) fn wasm_emit_mccode(registry: &mut TargetRegistry, debug: bool, modul
 
     let mut types = TypeSection::new();
     let mut functions = FunctionSection::new();
+    let mut tables = TableSection::new();
     let mut exports = ExportSection::new();
+    let mut elements = ElementSection::new();
     let mut codes = CodeSection::new();
 
+    // every function is also placed at its index into the function table
+    // so that `call_indirect` can use the index as the function adress
+    let funcs = module.funcs.clone().into_iter().collect::<Vec<_>>();
+
+    let mut func_indexes = HashMap::new();
+
+    for (index, (name, _)) in funcs.iter().enumerate() {
+        func_indexes.insert(name.to_owned(), index as u32);
+    }
+
+    // the types used by `call_indirect` are stored after the types of the functions
+    let mut indirect_types = Vec::new();
+
     let mut idx = 0;
-    for (name, func) in module.funcs.clone() {
+    for (name, func) in funcs {
         // type
         let mut params = Vec::new();
-        let mut ret = Vec::new();
-
-        if func.ty.ret != TypeMetadata::Void {
-            ret.push(func.ty.ret.into());
-        }
+        let ret = wasm_results(func.ty.ret);
 
         for (_, arg) in &func.ty.args {
             params.push((*arg).into());
@@ -44,7 +55,7 @@ pub(crate) fn wasm_emit_mccode(registry: &mut TargetRegistry, debug: bool, modul
 
         // code
 
-        let (instrs, locals) = wasm_build_instrs(&func, registry, module)?;
+        let (instrs, locals) = wasm_build_instrs(&func, registry, module, &func_indexes, &mut indirect_types)?;
 
         let mut func = Function::new(locals);
 
@@ -58,11 +69,32 @@ pub(crate) fn wasm_emit_mccode(registry: &mut TargetRegistry, debug: bool, modul
         idx += 1;
     }
 
+    for ty in &indirect_types {
+        let params = ty.args.iter().map(|(_, arg)| (*arg).into()).collect::<Vec<ValType>>();
+
+        let ret = wasm_results(ty.ret);
+
+        types.ty().function(params, ret);
+    }
+
+    tables.table(TableType {
+        element_type: RefType::FUNCREF,
+        table64: false,
+        minimum: idx as u64,
+        maximum: Some(idx as u64),
+        shared: false,
+    });
+
+    let table_funcs = (0..idx).collect::<Vec<u32>>();
+    elements.active(Some(0), &ConstExpr::i32_const(0), Elements::Functions(table_funcs.into()));
+
     // actuall code end
 
     wasm.section(&types);
     wasm.section(&functions);
+    wasm.section(&tables);
     wasm.section(&exports);
+    wasm.section(&elements);
     wasm.section(&codes);
 
     let encoded = wasm.finish();
@@ -80,14 +112,30 @@ pub(crate) fn wasm_emit_mccode(registry: &mut TargetRegistry, debug: bool, modul
     Ok((obj, None))
 }
 
+/// Returns the result types of a function with the return type `ret`
+/// 
+/// Tuples are returned as multiple results (using the multi-value proposal)
+fn wasm_results(ret: TypeMetadata) -> Vec<ValType> {
+    if let Some(elems) = ret.tupleElems() {
+        return elems.into_iter().map(|elem| elem.into()).collect();
+    }
+
+    if ret != TypeMetadata::Void {
+        vec![ret.into()]
+    } else {
+        vec![]
+    }
+}
+
 impl Into<ValType> for TypeMetadata {
     fn into(self) -> ValType {
         match self {
+            TypeMetadata::i1 => ValType::I32,
             TypeMetadata::u32 => ValType::I32,
             TypeMetadata::u64 => ValType::I64,
             TypeMetadata::i32 => ValType::I32,
             TypeMetadata::i64 => ValType::I64,
-            TypeMetadata::ptr => ValType::Ref(RefType::ANYREF),
+            TypeMetadata::ptr => ValType::I64,
             TypeMetadata::f32 => ValType::F32,
             TypeMetadata::f64 => ValType::F64,
             _ => panic!("unsupported type for wasm: {}", self),
@@ -95,7 +143,7 @@ impl Into<ValType> for TypeMetadata {
     }
 }
 
-fn wasm_build_instrs(func: &crate::IR::Function, registry: &mut TargetRegistry, module: &mut crate::IR::Module) -> Result<(Vec<WasmMCInstr>, Vec<(u32, ValType)>), Box<dyn Error>> {
+fn wasm_build_instrs(func: &crate::IR::Function, registry: &mut TargetRegistry, module: &mut crate::IR::Module, func_indexes: &HashMap<String, u32>, indirect_types: &mut Vec<FunctionType>) -> Result<(Vec<WasmMCInstr>, Vec<(u32, ValType)>), Box<dyn Error>> {
     let mut blocked_instrs: BTreeMap<&String, Vec<crate::CodeGen::MachineInstr>> = BTreeMap::new();
 
     for block in &func.blocks {
@@ -154,6 +202,23 @@ fn wasm_build_instrs(func: &crate::IR::Function, registry: &mut TargetRegistry,
 
             instr.op1 = Some(WasmOperand::Const(*index as f64));
         }
+
+        if let Some(WasmOperand::FuncLink(ref target)) = instr.op1 {
+            let Some(index) = func_indexes.get(target) else { panic!("unknown function: {} (wasm currently only supports the adresses of functions)", target) };
+
+            instr.op1 = Some(WasmOperand::Const(*index as f64));
+        }
+
+        if let Some(WasmOperand::TypeLink(ref ty)) = instr.op1 {
+            let index = if let Some(index) = indirect_types.iter().position(|indirect: &FunctionType| indirect.ret == ty.ret && indirect.args.iter().map(|(_, arg)| arg).eq(ty.args.iter().map(|(_, arg)| arg))) {
+                index
+            } else {
+                indirect_types.push(ty.to_owned());
+                indirect_types.len() - 1
+            };
+
+            instr.op1 = Some(WasmOperand::Const((module.funcs.len() + index) as f64));
+        }
     }
 
     lowered = lowered.optimize();
@@ -33,7 +33,12 @@ impl AsmPrinter for WasmAsmPrinter {
                 index += 1;
             }
 
-            fmt_ty.push_str(&format!(") -> ({})", func.ty.ret));
+            if let Some(elems) = func.ty.ret.tupleElems() {
+                let elems = elems.iter().map(|elem| elem.to_string()).collect::<Vec<String>>();
+                fmt_ty.push_str(&format!(") -> ({})", elems.join(", ")));
+            } else {
+                fmt_ty.push_str(&format!(") -> ({})", func.ty.ret));
+            }
 
 
             if func.linkage == Linkage::Extern {
@@ -67,7 +67,7 @@ fn node_prep(alloc: &mut Allocator, node: &Box<dyn Ir>) {
         return;
     }
 
-    if let Some(out) = node.output() {
+    for out in node.outputs() {
         let location = wasm_alloc_var(alloc, out.ty);
 
         alloc.vars.insert(out.name.to_owned(), location);
@@ -87,9 +87,12 @@ pub(crate) fn wasm_alloc_var(alloc: &mut Allocator, ty: TypeMetadata) -> VarLoca
         }
     }
 
-    alloc.stack_off += 1; // the stack off is in reality really only the amount of current variables
+    // every value of a tuple gets its own local
+    let locals = ty.tupleElems().map(|elems| elems.len()).unwrap_or(1);
 
-    VarLocation::Reg(Reg::wasm((alloc.stack_off - 1) as i32, ty))
+    alloc.stack_off += locals as i64; // the stack off is in reality really only the amount of current variables
+
+    VarLocation::Reg(Reg::wasm((alloc.stack_off - locals as i64) as i32, ty))
 }
 
 pub(crate) fn phi_prep(alloc: &mut Allocator, phi: &Phi) {
@@ -21,6 +21,8 @@ pub struct X64MCInstr {
 
     // for far calls
     pub(crate) far: bool,
+    // for atomic read-modify-write instructions
+    pub(crate) lock: bool,
 }
 
 impl X64MCInstr {
@@ -32,6 +34,7 @@ impl X64MCInstr {
             op2: None,
             op3: None,
             far: false,
+            lock: false,
         }
     }
 
@@ -43,6 +46,19 @@ impl X64MCInstr {
             op2: self.op2.to_owned(),
             op3: self.op3.to_owned(),
             far: true,
+            lock: self.lock,
+        }
+    }
+
+    /// Makes the instruction use the `lock` prefix (for atomic instructions like xadd)
+    pub fn make_lock(&self) -> Self {
+        Self {
+            mnemonic: self.mnemonic.to_owned(),
+            op1: self.op1.to_owned(),
+            op2: self.op2.to_owned(),
+            op3: self.op3.to_owned(),
+            far: self.far,
+            lock: true,
         }
     }
 
@@ -54,6 +70,7 @@ impl X64MCInstr {
             op2: None,
             op3: None,
             far: false,
+            lock: false,
         }
     }
 
@@ -65,6 +82,7 @@ impl X64MCInstr {
             op2: Some(op2),
             op3: None,
             far: false,
+            lock: false,
         }
     }
 
@@ -76,6 +94,7 @@ impl X64MCInstr {
             op2: Some(op2),
             op3: Some(op3),
             far: false,
+            lock: false,
         }
     }
 
@@ -92,6 +111,7 @@ impl X64MCInstr {
                     at: 0, addend: *addend, 
                     special: false,
                     kind: RelocationEncoding::X86Branch,
+                    label: false,
                 })));
             } else if let Some(Operand::BlockLinkDestination(dst, addend)) = &self.op1 {
                 return Ok((vec![], Some(Link { 
@@ -100,18 +120,34 @@ impl X64MCInstr {
                     at: 0, addend: *addend, 
                     special: true,
                     kind: RelocationEncoding::Generic,
+                    label: false,
                  })));
             } else {
                 return Ok((vec![], None));
             }
         }
 
+        if self.mnemonic == Mnemonic::Label {
+            if let Some(Operand::Debug(label)) = &self.op1 {
+                return Ok((vec![], Some(Link {
+                    from: "".into(),
+                    to: label.to_owned(),
+                    at: 0, addend: 0,
+                    special: false,
+                    kind: RelocationEncoding::Generic,
+                    label: true,
+                })));
+            } else {
+                return Ok((vec![], None));
+            }
+        }
+
         if Mnemonic::Debug == self.mnemonic || Mnemonic::StartOptimization == self.mnemonic || Mnemonic::EndOptimization == self.mnemonic {
             return Ok((vec![], None))
         }
 
-        let /*mut*/ instr = match self.mnemonic {
-            Mnemonic::Link | Mnemonic::Debug | Mnemonic::StartOptimization | Mnemonic::EndOptimization => unreachable!(),
+        let mut instr = match self.mnemonic {
+            Mnemonic::Link | Mnemonic::Label | Mnemonic::Debug | Mnemonic::StartOptimization | Mnemonic::EndOptimization => unreachable!(),
             Mnemonic::Add => {
                 if let Some(Operand::Reg(op1)) = &self.op1 {
                     if let Some(Operand::Reg(op2)) = &self.op2 {
@@ -206,6 +242,53 @@ impl X64MCInstr {
                     } else { todo!("{}", self) }
                 } else { todo!("{}", self) }
             },
+            Mnemonic::Sbb => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2)) = &self.op2 {
+                        if op1.is_gr8() {
+                            Instruction::with2::<Register, Register>(Code::Sbb_rm8_r8, (*op1).into(), (*op2).into())?
+                        } else if op1.is_gr16() {
+                            Instruction::with2::<Register, Register>(Code::Sbb_rm16_r16, (*op1).into(), (*op2).into())?
+                        } else if op1.is_gr32() {
+                            Instruction::with2::<Register, Register>(Code::Sbb_rm32_r32, (*op1).into(), (*op2).into())?
+                        } else if op1.is_gr64() {
+                            Instruction::with2::<Register, Register>(Code::Sbb_rm64_r64, (*op1).into(), (*op2).into())?
+                        } else { todo!("{}", self) }
+                    } else if let Some(Operand::Mem(op2)) = &self.op2 {
+                        if op1.is_gr8() {
+                            Instruction::with2::<Register, MemoryOperand>(Code::Sbb_r8_rm8, (*op1).into(), op2.into())?
+                        } else if op1.is_gr16() {
+                            Instruction::with2::<Register, MemoryOperand>(Code::Sbb_r16_rm16, (*op1).into(), op2.into())?
+                        } else if op1.is_gr32() {
+                            Instruction::with2::<Register, MemoryOperand>(Code::Sbb_r32_rm32, (*op1).into(), op2.into())?
+                        } else if op1.is_gr64() {
+                            Instruction::with2::<Register, MemoryOperand>(Code::Sbb_r64_rm64, (*op1).into(), op2.into())?
+                        } else { todo!("{}", self) }
+                    } else if let Some(Operand::Imm(imm)) = &self.op2 {
+                        if op1.is_gr8() {
+                            Instruction::with2::<Register, i32>(Code::Sbb_rm8_imm8, (*op1).into(), *imm as i32)?
+                        } else if op1.is_gr16() {
+                            Instruction::with2::<Register, i32>(Code::Sbb_rm16_imm16, (*op1).into(), *imm as i32)?
+                        } else if op1.is_gr32() {
+                            Instruction::with2::<Register, i32>(Code::Sbb_rm32_imm32, (*op1).into(), *imm as i32)?
+                        } else if op1.is_gr64() {
+                            Instruction::with2::<Register, i32>(Code::Sbb_rm64_imm32, (*op1).into(), *imm as i32)?
+                        } else { todo!("{}", self) }
+                    } else { todo!("{}", self) }
+                } else if let Some(Operand::Mem(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2)) = &self.op2 {
+                        if op2.is_gr8() {
+                            Instruction::with2::<MemoryOperand, Register>(Code::Sbb_rm8_r8, op1.into(), (*op2).into())?
+                        } else if op2.is_gr16() {
+                            Instruction::with2::<MemoryOperand, Register>(Code::Sbb_rm16_r16, op1.into(), (*op2).into())?
+                        } else if op2.is_gr32() {
+                            Instruction::with2::<MemoryOperand, Register>(Code::Sbb_rm32_r32, op1.into(), (*op2).into())?
+                        } else if op2.is_gr64() {
+                            Instruction::with2::<MemoryOperand, Register>(Code::Sbb_rm64_r64, op1.into(), (*op2).into())?
+                        } else { todo!("{}", self) }
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
             Mnemonic::And => {
                 if let Some(Operand::Reg(op1)) = &self.op1 {
                     if let Some(Operand::Reg(op2)) = &self.op2 {
@@ -501,7 +584,11 @@ impl X64MCInstr {
                         } else if op1.is_gr32() {
                             Instruction::with2::<Register, i32>(Code::Mov_rm32_imm32, (*op1).into(), *imm as i32)?
                         } else if op1.is_gr64() {
-                            Instruction::with2::<Register, i32>(Code::Mov_rm64_imm32, (*op1).into(), *imm as i32)?
+                            if i32::try_from(*imm).is_ok() {
+                                Instruction::with2::<Register, i32>(Code::Mov_rm64_imm32, (*op1).into(), *imm as i32)?
+                            } else {
+                                Instruction::with2::<Register, u64>(Code::Mov_r64_imm64, (*op1).into(), *imm as u64)?
+                            }
                         } else { todo!("{}", self) }
                     } else { todo!("{}", self) }
                 } else if let Some(Operand::Mem(op1)) = &self.op1 {
@@ -551,6 +638,15 @@ impl X64MCInstr {
                     } else { todo!("{}", self)}
                 } else { todo!("{}", self) }
             },
+            Mnemonic::Movsxd => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2)) = &self.op2 {
+                        Instruction::with2::<Register, Register>(Code::Movsxd_r64_rm32, (*op1).into(), (*op2).into())?
+                    } else if let Some(Operand::Mem(op2)) = &self.op2 {
+                        Instruction::with2::<Register, MemoryOperand>(Code::Movsxd_r64_rm32, (*op1).into(), op2.into())?
+                    } else { todo!("{}", self)}
+                } else { todo!("{}", self)}
+            },
             Mnemonic::Movsx => {
                 if let Some(Operand::Reg(op1)) = &self.op1 {
                     if let Some(Operand::Reg(op2)) = &self.op2 {
@@ -594,7 +690,7 @@ impl X64MCInstr {
                 } else if let Some(Operand::Mem(op1)) = &self.op1 {
                     Instruction::with1::<MemoryOperand>(Code::Push_rm64, op1.into())?
                 } else if let Some(Operand::Imm(imm)) = &self.op1 {
-                    Instruction::with1(Code::Pushd_imm32, *imm as i32)?
+                    Instruction::with1(Code::Pushq_imm32, *imm as i32)?
                 } else { todo!("{}", self) }
             },
             Mnemonic::Pop => {
@@ -803,6 +899,60 @@ impl X64MCInstr {
                     Instruction::with_branch(Code::Jg_rel32_64, 0)?
                 } else { todo!("{}", self) }
             },
+            Mnemonic::Ja => {
+                if let Some(Operand::Imm(op1)) = &self.op1 {
+                    Instruction::with_branch(Code::Ja_rel32_64, *op1 as u64)?
+                } else if let Some(Operand::LinkDestination(..)) = &self.op1 {
+                    Instruction::with_branch(Code::Ja_rel32_64, 0)?
+                } else if let Some(Operand::BlockLinkDestination(..)) = &self.op1 {
+                    Instruction::with_branch(Code::Ja_rel32_64, 0)?
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Jae => {
+                if let Some(Operand::Imm(op1)) = &self.op1 {
+                    Instruction::with_branch(Code::Jae_rel32_64, *op1 as u64)?
+                } else if let Some(Operand::LinkDestination(..)) = &self.op1 {
+                    Instruction::with_branch(Code::Jae_rel32_64, 0)?
+                } else if let Some(Operand::BlockLinkDestination(..)) = &self.op1 {
+                    Instruction::with_branch(Code::Jae_rel32_64, 0)?
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Jb => {
+                if let Some(Operand::Imm(op1)) = &self.op1 {
+                    Instruction::with_branch(Code::Jb_rel32_64, *op1 as u64)?
+                } else if let Some(Operand::LinkDestination(..)) = &self.op1 {
+                    Instruction::with_branch(Code::Jb_rel32_64, 0)?
+                } else if let Some(Operand::BlockLinkDestination(..)) = &self.op1 {
+                    Instruction::with_branch(Code::Jb_rel32_64, 0)?
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Jbe => {
+                if let Some(Operand::Imm(op1)) = &self.op1 {
+                    Instruction::with_branch(Code::Jbe_rel32_64, *op1 as u64)?
+                } else if let Some(Operand::LinkDestination(..)) = &self.op1 {
+                    Instruction::with_branch(Code::Jbe_rel32_64, 0)?
+                } else if let Some(Operand::BlockLinkDestination(..)) = &self.op1 {
+                    Instruction::with_branch(Code::Jbe_rel32_64, 0)?
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Jp => {
+                if let Some(Operand::Imm(op1)) = &self.op1 {
+                    Instruction::with_branch(Code::Jp_rel32_64, *op1 as u64)?
+                } else if let Some(Operand::LinkDestination(..)) = &self.op1 {
+                    Instruction::with_branch(Code::Jp_rel32_64, 0)?
+                } else if let Some(Operand::BlockLinkDestination(..)) = &self.op1 {
+                    Instruction::with_branch(Code::Jp_rel32_64, 0)?
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Jnp => {
+                if let Some(Operand::Imm(op1)) = &self.op1 {
+                    Instruction::with_branch(Code::Jnp_rel32_64, *op1 as u64)?
+                } else if let Some(Operand::LinkDestination(..)) = &self.op1 {
+                    Instruction::with_branch(Code::Jnp_rel32_64, 0)?
+                } else if let Some(Operand::BlockLinkDestination(..)) = &self.op1 {
+                    Instruction::with_branch(Code::Jnp_rel32_64, 0)?
+                } else { todo!("{}", self) }
+            },
             Mnemonic::Endbr64 => Instruction::with(Code::Endbr64),
             Mnemonic::Sete => {
                 if let Some(Operand::Reg(op1)) = &self.op1 {
@@ -846,6 +996,55 @@ impl X64MCInstr {
                     Instruction::with1::<MemoryOperand>(Code::Setle_rm8, op1.into())?
                 } else { todo!("{}", self) }
             },
+            Mnemonic::Seta => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    Instruction::with1::<Register>(Code::Seta_rm8, (*op1).into())?
+                } else if let Some(Operand::Mem(op1)) = &self.op1 {
+                    Instruction::with1::<MemoryOperand>(Code::Seta_rm8, op1.into())?
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Setae => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    Instruction::with1::<Register>(Code::Setae_rm8, (*op1).into())?
+                } else if let Some(Operand::Mem(op1)) = &self.op1 {
+                    Instruction::with1::<MemoryOperand>(Code::Setae_rm8, op1.into())?
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Setb => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    Instruction::with1::<Register>(Code::Setb_rm8, (*op1).into())?
+                } else if let Some(Operand::Mem(op1)) = &self.op1 {
+                    Instruction::with1::<MemoryOperand>(Code::Setb_rm8, op1.into())?
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Setbe => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    Instruction::with1::<Register>(Code::Setbe_rm8, (*op1).into())?
+                } else if let Some(Operand::Mem(op1)) = &self.op1 {
+                    Instruction::with1::<MemoryOperand>(Code::Setbe_rm8, op1.into())?
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Setp => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    Instruction::with1::<Register>(Code::Setp_rm8, (*op1).into())?
+                } else if let Some(Operand::Mem(op1)) = &self.op1 {
+                    Instruction::with1::<MemoryOperand>(Code::Setp_rm8, op1.into())?
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Setnp => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    Instruction::with1::<Register>(Code::Setnp_rm8, (*op1).into())?
+                } else if let Some(Operand::Mem(op1)) = &self.op1 {
+                    Instruction::with1::<MemoryOperand>(Code::Setnp_rm8, op1.into())?
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Seto => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    Instruction::with1::<Register>(Code::Seto_rm8, (*op1).into())?
+                } else if let Some(Operand::Mem(op1)) = &self.op1 {
+                    Instruction::with1::<MemoryOperand>(Code::Seto_rm8, op1.into())?
+                } else { todo!("{}", self) }
+            },
             Mnemonic::Cmove => {
                 if let Some(Operand::Reg(op1)) = &self.op1 {
                     if let Some(Operand::Reg(op2)) = &self.op2 {
@@ -888,8 +1087,39 @@ impl X64MCInstr {
                     } else { todo!("{}", self) }
                 } else { todo!("{}", self) }
             },
-            Mnemonic::Sal => {
+            Mnemonic::Cmovp => {
                 if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2)) = &self.op2 {
+                        if op1.is_gr16() {
+                            Instruction::with2::<Register, Register>(Code::Cmovp_r16_rm16, (*op1).into(), (*op2).into())?
+                        } else if op1.is_gr32() {
+                            Instruction::with2::<Register, Register>(Code::Cmovp_r32_rm32, (*op1).into(), (*op2).into())?
+                        } else if op1.is_gr64() {
+                            Instruction::with2::<Register, Register>(Code::Cmovp_r64_rm64, (*op1).into(), (*op2).into())?
+                        } else { todo!("{}", self) }
+                    } else if let Some(Operand::Mem(op2)) = &self.op2 {
+                        if op1.is_gr16() {
+                            Instruction::with2::<Register, MemoryOperand>(Code::Cmovp_r16_rm16, (*op1).into(), op2.into())?
+                        } else if op1.is_gr32() {
+                            Instruction::with2::<Register, MemoryOperand>(Code::Cmovp_r32_rm32, (*op1).into(), op2.into())?
+                        } else if op1.is_gr64() {
+                            Instruction::with2::<Register, MemoryOperand>(Code::Cmovp_r64_rm64, (*op1).into(), op2.into())?
+                        } else { todo!("{}", self) }
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Sal => {
+                if let (Some(Operand::Reg(op1)), Some(Operand::Imm(imm))) = (&self.op1, &self.op2) {
+                    if op1.is_gr8() {
+                        Instruction::with2::<Register, u32>(Code::Sal_rm8_imm8, (*op1).into(), *imm as u32)?
+                    } else if op1.is_gr16() {
+                        Instruction::with2::<Register, u32>(Code::Sal_rm16_imm8, (*op1).into(), *imm as u32)?
+                    } else if op1.is_gr32() {
+                        Instruction::with2::<Register, u32>(Code::Sal_rm32_imm8, (*op1).into(), *imm as u32)?
+                    } else if op1.is_gr64() {
+                        Instruction::with2::<Register, u32>(Code::Sal_rm64_imm8, (*op1).into(), *imm as u32)?
+                    } else { todo!("{}", self) }
+                } else if let Some(Operand::Reg(op1)) = &self.op1 {
                     if op1.is_gr8() {
                         Instruction::with2::<Register, Register>(Code::Sal_rm8_CL, (*op1).into(), Register::CL)?
                     } else if op1.is_gr16() {
@@ -904,7 +1134,17 @@ impl X64MCInstr {
                 } else { todo!("{}", self) }
             },
             Mnemonic::Shr => {
-                if let Some(Operand::Reg(op1)) = &self.op1 {
+                if let (Some(Operand::Reg(op1)), Some(Operand::Imm(imm))) = (&self.op1, &self.op2) {
+                    if op1.is_gr8() {
+                        Instruction::with2::<Register, u32>(Code::Shr_rm8_imm8, (*op1).into(), *imm as u32)?
+                    } else if op1.is_gr16() {
+                        Instruction::with2::<Register, u32>(Code::Shr_rm16_imm8, (*op1).into(), *imm as u32)?
+                    } else if op1.is_gr32() {
+                        Instruction::with2::<Register, u32>(Code::Shr_rm32_imm8, (*op1).into(), *imm as u32)?
+                    } else if op1.is_gr64() {
+                        Instruction::with2::<Register, u32>(Code::Shr_rm64_imm8, (*op1).into(), *imm as u32)?
+                    } else { todo!("{}", self) }
+                } else if let Some(Operand::Reg(op1)) = &self.op1 {
                     if op1.is_gr8() {
                         Instruction::with2::<Register, Register>(Code::Shr_rm8_CL, (*op1).into(), Register::CL)?
                     } else if op1.is_gr16() {
@@ -919,7 +1159,17 @@ impl X64MCInstr {
                 } else { todo!("{}", self) }
             },
             Mnemonic::Sar => {
-                if let Some(Operand::Reg(op1)) = &self.op1 {
+                if let (Some(Operand::Reg(op1)), Some(Operand::Imm(imm))) = (&self.op1, &self.op2) {
+                    if op1.is_gr8() {
+                        Instruction::with2::<Register, u32>(Code::Sar_rm8_imm8, (*op1).into(), *imm as u32)?
+                    } else if op1.is_gr16() {
+                        Instruction::with2::<Register, u32>(Code::Sar_rm16_imm8, (*op1).into(), *imm as u32)?
+                    } else if op1.is_gr32() {
+                        Instruction::with2::<Register, u32>(Code::Sar_rm32_imm8, (*op1).into(), *imm as u32)?
+                    } else if op1.is_gr64() {
+                        Instruction::with2::<Register, u32>(Code::Sar_rm64_imm8, (*op1).into(), *imm as u32)?
+                    } else { todo!("{}", self) }
+                } else if let Some(Operand::Reg(op1)) = &self.op1 {
                     if op1.is_gr8() {
                         Instruction::with2::<Register, Register>(Code::Sar_rm8_CL, (*op1).into(), Register::CL)?
                     } else if op1.is_gr16() {
@@ -933,6 +1183,183 @@ impl X64MCInstr {
                     Instruction::with2::<MemoryOperand, Register>(Code::Sar_rm64_CL, op1.into(), Register::CL)?
                 } else { todo!("{}", self) }
             },
+            Mnemonic::Rol => {
+                if let (Some(Operand::Reg(op1)), Some(Operand::Imm(imm))) = (&self.op1, &self.op2) {
+                    if op1.is_gr8() {
+                        Instruction::with2::<Register, u32>(Code::Rol_rm8_imm8, (*op1).into(), *imm as u32)?
+                    } else if op1.is_gr16() {
+                        Instruction::with2::<Register, u32>(Code::Rol_rm16_imm8, (*op1).into(), *imm as u32)?
+                    } else if op1.is_gr32() {
+                        Instruction::with2::<Register, u32>(Code::Rol_rm32_imm8, (*op1).into(), *imm as u32)?
+                    } else if op1.is_gr64() {
+                        Instruction::with2::<Register, u32>(Code::Rol_rm64_imm8, (*op1).into(), *imm as u32)?
+                    } else { todo!("{}", self) }
+                } else if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if op1.is_gr8() {
+                        Instruction::with2::<Register, Register>(Code::Rol_rm8_CL, (*op1).into(), Register::CL)?
+                    } else if op1.is_gr16() {
+                        Instruction::with2::<Register, Register>(Code::Rol_rm16_CL, (*op1).into(), Register::CL)?
+                    } else if op1.is_gr32() {
+                        Instruction::with2::<Register, Register>(Code::Rol_rm32_CL, (*op1).into(), Register::CL)?
+                    } else if op1.is_gr64() {
+                        Instruction::with2::<Register, Register>(Code::Rol_rm64_CL, (*op1).into(), Register::CL)?
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Ror => {
+                if let (Some(Operand::Reg(op1)), Some(Operand::Imm(imm))) = (&self.op1, &self.op2) {
+                    if op1.is_gr8() {
+                        Instruction::with2::<Register, u32>(Code::Ror_rm8_imm8, (*op1).into(), *imm as u32)?
+                    } else if op1.is_gr16() {
+                        Instruction::with2::<Register, u32>(Code::Ror_rm16_imm8, (*op1).into(), *imm as u32)?
+                    } else if op1.is_gr32() {
+                        Instruction::with2::<Register, u32>(Code::Ror_rm32_imm8, (*op1).into(), *imm as u32)?
+                    } else if op1.is_gr64() {
+                        Instruction::with2::<Register, u32>(Code::Ror_rm64_imm8, (*op1).into(), *imm as u32)?
+                    } else { todo!("{}", self) }
+                } else if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if op1.is_gr8() {
+                        Instruction::with2::<Register, Register>(Code::Ror_rm8_CL, (*op1).into(), Register::CL)?
+                    } else if op1.is_gr16() {
+                        Instruction::with2::<Register, Register>(Code::Ror_rm16_CL, (*op1).into(), Register::CL)?
+                    } else if op1.is_gr32() {
+                        Instruction::with2::<Register, Register>(Code::Ror_rm32_CL, (*op1).into(), Register::CL)?
+                    } else if op1.is_gr64() {
+                        Instruction::with2::<Register, Register>(Code::Ror_rm64_CL, (*op1).into(), Register::CL)?
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Popcnt => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2)) = &self.op2 {
+                        if op1.is_gr16() {
+                            Instruction::with2::<Register, Register>(Code::Popcnt_r16_rm16, (*op1).into(), (*op2).into())?
+                        } else if op1.is_gr32() {
+                            Instruction::with2::<Register, Register>(Code::Popcnt_r32_rm32, (*op1).into(), (*op2).into())?
+                        } else if op1.is_gr64() {
+                            Instruction::with2::<Register, Register>(Code::Popcnt_r64_rm64, (*op1).into(), (*op2).into())?
+                        } else { todo!("{}", self) }
+                    } else if let Some(Operand::Mem(op2)) = &self.op2 {
+                        if op1.is_gr16() {
+                            Instruction::with2::<Register, MemoryOperand>(Code::Popcnt_r16_rm16, (*op1).into(), op2.into())?
+                        } else if op1.is_gr32() {
+                            Instruction::with2::<Register, MemoryOperand>(Code::Popcnt_r32_rm32, (*op1).into(), op2.into())?
+                        } else if op1.is_gr64() {
+                            Instruction::with2::<Register, MemoryOperand>(Code::Popcnt_r64_rm64, (*op1).into(), op2.into())?
+                        } else { todo!("{}", self) }
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Bsr => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2)) = &self.op2 {
+                        if op1.is_gr16() {
+                            Instruction::with2::<Register, Register>(Code::Bsr_r16_rm16, (*op1).into(), (*op2).into())?
+                        } else if op1.is_gr32() {
+                            Instruction::with2::<Register, Register>(Code::Bsr_r32_rm32, (*op1).into(), (*op2).into())?
+                        } else if op1.is_gr64() {
+                            Instruction::with2::<Register, Register>(Code::Bsr_r64_rm64, (*op1).into(), (*op2).into())?
+                        } else { todo!("{}", self) }
+                    } else if let Some(Operand::Mem(op2)) = &self.op2 {
+                        if op1.is_gr16() {
+                            Instruction::with2::<Register, MemoryOperand>(Code::Bsr_r16_rm16, (*op1).into(), op2.into())?
+                        } else if op1.is_gr32() {
+                            Instruction::with2::<Register, MemoryOperand>(Code::Bsr_r32_rm32, (*op1).into(), op2.into())?
+                        } else if op1.is_gr64() {
+                            Instruction::with2::<Register, MemoryOperand>(Code::Bsr_r64_rm64, (*op1).into(), op2.into())?
+                        } else { todo!("{}", self) }
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Bsf => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2)) = &self.op2 {
+                        if op1.is_gr16() {
+                            Instruction::with2::<Register, Register>(Code::Bsf_r16_rm16, (*op1).into(), (*op2).into())?
+                        } else if op1.is_gr32() {
+                            Instruction::with2::<Register, Register>(Code::Bsf_r32_rm32, (*op1).into(), (*op2).into())?
+                        } else if op1.is_gr64() {
+                            Instruction::with2::<Register, Register>(Code::Bsf_r64_rm64, (*op1).into(), (*op2).into())?
+                        } else { todo!("{}", self) }
+                    } else if let Some(Operand::Mem(op2)) = &self.op2 {
+                        if op1.is_gr16() {
+                            Instruction::with2::<Register, MemoryOperand>(Code::Bsf_r16_rm16, (*op1).into(), op2.into())?
+                        } else if op1.is_gr32() {
+                            Instruction::with2::<Register, MemoryOperand>(Code::Bsf_r32_rm32, (*op1).into(), op2.into())?
+                        } else if op1.is_gr64() {
+                            Instruction::with2::<Register, MemoryOperand>(Code::Bsf_r64_rm64, (*op1).into(), op2.into())?
+                        } else { todo!("{}", self) }
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Lzcnt => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2)) = &self.op2 {
+                        if op1.is_gr16() {
+                            Instruction::with2::<Register, Register>(Code::Lzcnt_r16_rm16, (*op1).into(), (*op2).into())?
+                        } else if op1.is_gr32() {
+                            Instruction::with2::<Register, Register>(Code::Lzcnt_r32_rm32, (*op1).into(), (*op2).into())?
+                        } else if op1.is_gr64() {
+                            Instruction::with2::<Register, Register>(Code::Lzcnt_r64_rm64, (*op1).into(), (*op2).into())?
+                        } else { todo!("{}", self) }
+                    } else if let Some(Operand::Mem(op2)) = &self.op2 {
+                        if op1.is_gr16() {
+                            Instruction::with2::<Register, MemoryOperand>(Code::Lzcnt_r16_rm16, (*op1).into(), op2.into())?
+                        } else if op1.is_gr32() {
+                            Instruction::with2::<Register, MemoryOperand>(Code::Lzcnt_r32_rm32, (*op1).into(), op2.into())?
+                        } else if op1.is_gr64() {
+                            Instruction::with2::<Register, MemoryOperand>(Code::Lzcnt_r64_rm64, (*op1).into(), op2.into())?
+                        } else { todo!("{}", self) }
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Tzcnt => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2)) = &self.op2 {
+                        if op1.is_gr16() {
+                            Instruction::with2::<Register, Register>(Code::Tzcnt_r16_rm16, (*op1).into(), (*op2).into())?
+                        } else if op1.is_gr32() {
+                            Instruction::with2::<Register, Register>(Code::Tzcnt_r32_rm32, (*op1).into(), (*op2).into())?
+                        } else if op1.is_gr64() {
+                            Instruction::with2::<Register, Register>(Code::Tzcnt_r64_rm64, (*op1).into(), (*op2).into())?
+                        } else { todo!("{}", self) }
+                    } else if let Some(Operand::Mem(op2)) = &self.op2 {
+                        if op1.is_gr16() {
+                            Instruction::with2::<Register, MemoryOperand>(Code::Tzcnt_r16_rm16, (*op1).into(), op2.into())?
+                        } else if op1.is_gr32() {
+                            Instruction::with2::<Register, MemoryOperand>(Code::Tzcnt_r32_rm32, (*op1).into(), op2.into())?
+                        } else if op1.is_gr64() {
+                            Instruction::with2::<Register, MemoryOperand>(Code::Tzcnt_r64_rm64, (*op1).into(), op2.into())?
+                        } else { todo!("{}", self) }
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Bswap => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if op1.is_gr32() {
+                        Instruction::with1::<Register>(Code::Bswap_r32, (*op1).into())?
+                    } else if op1.is_gr64() {
+                        Instruction::with1::<Register>(Code::Bswap_r64, (*op1).into())?
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Shld | Mnemonic::Shrd => {
+                let shld = self.mnemonic == Mnemonic::Shld;
+
+                if let Some(Operand::Reg(op2)) = &self.op2 {
+                    if let Some(Operand::Reg(op1)) = &self.op1 {
+                        if op1.is_gr16() {
+                            Instruction::with3::<Register, Register, Register>(if shld { Code::Shld_rm16_r16_CL } else { Code::Shrd_rm16_r16_CL }, (*op1).into(), (*op2).into(), Register::CL)?
+                        } else if op1.is_gr32() {
+                            Instruction::with3::<Register, Register, Register>(if shld { Code::Shld_rm32_r32_CL } else { Code::Shrd_rm32_r32_CL }, (*op1).into(), (*op2).into(), Register::CL)?
+                        } else if op1.is_gr64() {
+                            Instruction::with3::<Register, Register, Register>(if shld { Code::Shld_rm64_r64_CL } else { Code::Shrd_rm64_r64_CL }, (*op1).into(), (*op2).into(), Register::CL)?
+                        } else { todo!("{}", self) }
+                    } else if let Some(Operand::Mem(op1)) = &self.op1 {
+                        Instruction::with3::<MemoryOperand, Register, Register>(if shld { Code::Shld_rm64_r64_CL } else { Code::Shrd_rm64_r64_CL }, op1.into(), (*op2).into(), Register::CL)?
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
             Mnemonic::Movq => {
                 if let Some(Operand::Reg(op1)) = &self.op1 {
                     if let Some(Operand::Mem(op2)) = &self.op2 {
@@ -1014,23 +1441,36 @@ impl X64MCInstr {
                     } else { todo!("{}", self) } 
                 } else { todo!("{}", self) }
             },
-            Mnemonic::Movupd => {
+            Mnemonic::Movaps => {
                 if let Some(Operand::Reg(op1)) = &self.op1 {
                     if let Some(Operand::Reg(op2))  = &self.op2 {
-                        Instruction::with2::<Register, Register>(Code::Movupd_xmm_xmmm128, (*op1).into(), (*op2).into())?
+                        Instruction::with2::<Register, Register>(Code::Movaps_xmm_xmmm128, (*op1).into(), (*op2).into())?
                     } else if let Some(Operand::Mem(op2))  = &self.op2 {
-                        Instruction::with2::<Register, MemoryOperand>(Code::Movupd_xmm_xmmm128, (*op1).into(), op2.into())?
+                        Instruction::with2::<Register, MemoryOperand>(Code::Movaps_xmm_xmmm128, (*op1).into(), op2.into())?
                     } else { todo!("{}", self) }
                 } else if let Some(Operand::Mem(op1)) = &self.op1 {
                     if let Some(Operand::Reg(op2))  = &self.op2 {
-                        Instruction::with2::<MemoryOperand, Register>(Code::Movupd_xmmm128_xmm, op1.into(), (*op2).into())?
+                        Instruction::with2::<MemoryOperand, Register>(Code::Movaps_xmmm128_xmm, op1.into(), (*op2).into())?
                     } else { todo!("{}", self) } 
                 } else { todo!("{}", self) }
             },
-            Mnemonic::Addss => {
+            Mnemonic::Movupd => {
                 if let Some(Operand::Reg(op1)) = &self.op1 {
                     if let Some(Operand::Reg(op2))  = &self.op2 {
-                        Instruction::with2::<Register, Register>(Code::Addss_xmm_xmmm32, (*op1).into(), (*op2).into())?
+                        Instruction::with2::<Register, Register>(Code::Movupd_xmm_xmmm128, (*op1).into(), (*op2).into())?
+                    } else if let Some(Operand::Mem(op2))  = &self.op2 {
+                        Instruction::with2::<Register, MemoryOperand>(Code::Movupd_xmm_xmmm128, (*op1).into(), op2.into())?
+                    } else { todo!("{}", self) }
+                } else if let Some(Operand::Mem(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2))  = &self.op2 {
+                        Instruction::with2::<MemoryOperand, Register>(Code::Movupd_xmmm128_xmm, op1.into(), (*op2).into())?
+                    } else { todo!("{}", self) } 
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Addss => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2))  = &self.op2 {
+                        Instruction::with2::<Register, Register>(Code::Addss_xmm_xmmm32, (*op1).into(), (*op2).into())?
                     } else if let Some(Operand::Mem(op2))  = &self.op2 {
                         Instruction::with2::<Register, MemoryOperand>(Code::Addss_xmm_xmmm32, (*op1).into(), op2.into())?
                     } else { todo!("{}", self) }
@@ -1099,6 +1539,294 @@ impl X64MCInstr {
                     } else { todo!("{}", self) }
                 } else { todo!("{}", self) }
             },
+            Mnemonic::Sqrtss => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2))  = &self.op2 {
+                        Instruction::with2::<Register, Register>(Code::Sqrtss_xmm_xmmm32, (*op1).into(), (*op2).into())?
+                    } else if let Some(Operand::Mem(op2))  = &self.op2 {
+                        Instruction::with2::<Register, MemoryOperand>(Code::Sqrtss_xmm_xmmm32, (*op1).into(), op2.into())?
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Sqrtsd => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2))  = &self.op2 {
+                        Instruction::with2::<Register, Register>(Code::Sqrtsd_xmm_xmmm64, (*op1).into(), (*op2).into())?
+                    } else if let Some(Operand::Mem(op2))  = &self.op2 {
+                        Instruction::with2::<Register, MemoryOperand>(Code::Sqrtsd_xmm_xmmm64, (*op1).into(), op2.into())?
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Roundss => {
+                if let (Some(Operand::Reg(op1)), Some(Operand::Imm(mode))) = (&self.op1, &self.op3) {
+                    if let Some(Operand::Reg(op2))  = &self.op2 {
+                        Instruction::with3::<Register, Register, i32>(Code::Roundss_xmm_xmmm32_imm8, (*op1).into(), (*op2).into(), *mode as i32)?
+                    } else if let Some(Operand::Mem(op2))  = &self.op2 {
+                        Instruction::with3::<Register, MemoryOperand, i32>(Code::Roundss_xmm_xmmm32_imm8, (*op1).into(), op2.into(), *mode as i32)?
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Roundsd => {
+                if let (Some(Operand::Reg(op1)), Some(Operand::Imm(mode))) = (&self.op1, &self.op3) {
+                    if let Some(Operand::Reg(op2))  = &self.op2 {
+                        Instruction::with3::<Register, Register, i32>(Code::Roundsd_xmm_xmmm64_imm8, (*op1).into(), (*op2).into(), *mode as i32)?
+                    } else if let Some(Operand::Mem(op2))  = &self.op2 {
+                        Instruction::with3::<Register, MemoryOperand, i32>(Code::Roundsd_xmm_xmmm64_imm8, (*op1).into(), op2.into(), *mode as i32)?
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Minss => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2))  = &self.op2 {
+                        Instruction::with2::<Register, Register>(Code::Minss_xmm_xmmm32, (*op1).into(), (*op2).into())?
+                    } else if let Some(Operand::Mem(op2))  = &self.op2 {
+                        Instruction::with2::<Register, MemoryOperand>(Code::Minss_xmm_xmmm32, (*op1).into(), op2.into())?
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Minsd => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2))  = &self.op2 {
+                        Instruction::with2::<Register, Register>(Code::Minsd_xmm_xmmm64, (*op1).into(), (*op2).into())?
+                    } else if let Some(Operand::Mem(op2))  = &self.op2 {
+                        Instruction::with2::<Register, MemoryOperand>(Code::Minsd_xmm_xmmm64, (*op1).into(), op2.into())?
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Maxss => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2))  = &self.op2 {
+                        Instruction::with2::<Register, Register>(Code::Maxss_xmm_xmmm32, (*op1).into(), (*op2).into())?
+                    } else if let Some(Operand::Mem(op2))  = &self.op2 {
+                        Instruction::with2::<Register, MemoryOperand>(Code::Maxss_xmm_xmmm32, (*op1).into(), op2.into())?
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Maxsd => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2))  = &self.op2 {
+                        Instruction::with2::<Register, Register>(Code::Maxsd_xmm_xmmm64, (*op1).into(), (*op2).into())?
+                    } else if let Some(Operand::Mem(op2))  = &self.op2 {
+                        Instruction::with2::<Register, MemoryOperand>(Code::Maxsd_xmm_xmmm64, (*op1).into(), op2.into())?
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Addps => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2))  = &self.op2 {
+                        Instruction::with2::<Register, Register>(Code::Addps_xmm_xmmm128, (*op1).into(), (*op2).into())?
+                    } else if let Some(Operand::Mem(op2))  = &self.op2 {
+                        Instruction::with2::<Register, MemoryOperand>(Code::Addps_xmm_xmmm128, (*op1).into(), op2.into())?
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Addpd => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2))  = &self.op2 {
+                        Instruction::with2::<Register, Register>(Code::Addpd_xmm_xmmm128, (*op1).into(), (*op2).into())?
+                    } else if let Some(Operand::Mem(op2))  = &self.op2 {
+                        Instruction::with2::<Register, MemoryOperand>(Code::Addpd_xmm_xmmm128, (*op1).into(), op2.into())?
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Subps => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2))  = &self.op2 {
+                        Instruction::with2::<Register, Register>(Code::Subps_xmm_xmmm128, (*op1).into(), (*op2).into())?
+                    } else if let Some(Operand::Mem(op2))  = &self.op2 {
+                        Instruction::with2::<Register, MemoryOperand>(Code::Subps_xmm_xmmm128, (*op1).into(), op2.into())?
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Subpd => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2))  = &self.op2 {
+                        Instruction::with2::<Register, Register>(Code::Subpd_xmm_xmmm128, (*op1).into(), (*op2).into())?
+                    } else if let Some(Operand::Mem(op2))  = &self.op2 {
+                        Instruction::with2::<Register, MemoryOperand>(Code::Subpd_xmm_xmmm128, (*op1).into(), op2.into())?
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Mulps => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2))  = &self.op2 {
+                        Instruction::with2::<Register, Register>(Code::Mulps_xmm_xmmm128, (*op1).into(), (*op2).into())?
+                    } else if let Some(Operand::Mem(op2))  = &self.op2 {
+                        Instruction::with2::<Register, MemoryOperand>(Code::Mulps_xmm_xmmm128, (*op1).into(), op2.into())?
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Mulpd => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2))  = &self.op2 {
+                        Instruction::with2::<Register, Register>(Code::Mulpd_xmm_xmmm128, (*op1).into(), (*op2).into())?
+                    } else if let Some(Operand::Mem(op2))  = &self.op2 {
+                        Instruction::with2::<Register, MemoryOperand>(Code::Mulpd_xmm_xmmm128, (*op1).into(), op2.into())?
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Divps => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2))  = &self.op2 {
+                        Instruction::with2::<Register, Register>(Code::Divps_xmm_xmmm128, (*op1).into(), (*op2).into())?
+                    } else if let Some(Operand::Mem(op2))  = &self.op2 {
+                        Instruction::with2::<Register, MemoryOperand>(Code::Divps_xmm_xmmm128, (*op1).into(), op2.into())?
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Divpd => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2))  = &self.op2 {
+                        Instruction::with2::<Register, Register>(Code::Divpd_xmm_xmmm128, (*op1).into(), (*op2).into())?
+                    } else if let Some(Operand::Mem(op2))  = &self.op2 {
+                        Instruction::with2::<Register, MemoryOperand>(Code::Divpd_xmm_xmmm128, (*op1).into(), op2.into())?
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Andpd => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2))  = &self.op2 {
+                        Instruction::with2::<Register, Register>(Code::Andpd_xmm_xmmm128, (*op1).into(), (*op2).into())?
+                    } else if let Some(Operand::Mem(op2))  = &self.op2 {
+                        Instruction::with2::<Register, MemoryOperand>(Code::Andpd_xmm_xmmm128, (*op1).into(), op2.into())?
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Andnpd => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2))  = &self.op2 {
+                        Instruction::with2::<Register, Register>(Code::Andnpd_xmm_xmmm128, (*op1).into(), (*op2).into())?
+                    } else if let Some(Operand::Mem(op2))  = &self.op2 {
+                        Instruction::with2::<Register, MemoryOperand>(Code::Andnpd_xmm_xmmm128, (*op1).into(), op2.into())?
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Orpd => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2))  = &self.op2 {
+                        Instruction::with2::<Register, Register>(Code::Orpd_xmm_xmmm128, (*op1).into(), (*op2).into())?
+                    } else if let Some(Operand::Mem(op2))  = &self.op2 {
+                        Instruction::with2::<Register, MemoryOperand>(Code::Orpd_xmm_xmmm128, (*op1).into(), op2.into())?
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Paddb => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2))  = &self.op2 {
+                        Instruction::with2::<Register, Register>(Code::Paddb_xmm_xmmm128, (*op1).into(), (*op2).into())?
+                    } else if let Some(Operand::Mem(op2))  = &self.op2 {
+                        Instruction::with2::<Register, MemoryOperand>(Code::Paddb_xmm_xmmm128, (*op1).into(), op2.into())?
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Paddw => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2))  = &self.op2 {
+                        Instruction::with2::<Register, Register>(Code::Paddw_xmm_xmmm128, (*op1).into(), (*op2).into())?
+                    } else if let Some(Operand::Mem(op2))  = &self.op2 {
+                        Instruction::with2::<Register, MemoryOperand>(Code::Paddw_xmm_xmmm128, (*op1).into(), op2.into())?
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Paddd => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2))  = &self.op2 {
+                        Instruction::with2::<Register, Register>(Code::Paddd_xmm_xmmm128, (*op1).into(), (*op2).into())?
+                    } else if let Some(Operand::Mem(op2))  = &self.op2 {
+                        Instruction::with2::<Register, MemoryOperand>(Code::Paddd_xmm_xmmm128, (*op1).into(), op2.into())?
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Paddq => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2))  = &self.op2 {
+                        Instruction::with2::<Register, Register>(Code::Paddq_xmm_xmmm128, (*op1).into(), (*op2).into())?
+                    } else if let Some(Operand::Mem(op2))  = &self.op2 {
+                        Instruction::with2::<Register, MemoryOperand>(Code::Paddq_xmm_xmmm128, (*op1).into(), op2.into())?
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Psubb => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2))  = &self.op2 {
+                        Instruction::with2::<Register, Register>(Code::Psubb_xmm_xmmm128, (*op1).into(), (*op2).into())?
+                    } else if let Some(Operand::Mem(op2))  = &self.op2 {
+                        Instruction::with2::<Register, MemoryOperand>(Code::Psubb_xmm_xmmm128, (*op1).into(), op2.into())?
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Psubw => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2))  = &self.op2 {
+                        Instruction::with2::<Register, Register>(Code::Psubw_xmm_xmmm128, (*op1).into(), (*op2).into())?
+                    } else if let Some(Operand::Mem(op2))  = &self.op2 {
+                        Instruction::with2::<Register, MemoryOperand>(Code::Psubw_xmm_xmmm128, (*op1).into(), op2.into())?
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Psubd => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2))  = &self.op2 {
+                        Instruction::with2::<Register, Register>(Code::Psubd_xmm_xmmm128, (*op1).into(), (*op2).into())?
+                    } else if let Some(Operand::Mem(op2))  = &self.op2 {
+                        Instruction::with2::<Register, MemoryOperand>(Code::Psubd_xmm_xmmm128, (*op1).into(), op2.into())?
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Psubq => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2))  = &self.op2 {
+                        Instruction::with2::<Register, Register>(Code::Psubq_xmm_xmmm128, (*op1).into(), (*op2).into())?
+                    } else if let Some(Operand::Mem(op2))  = &self.op2 {
+                        Instruction::with2::<Register, MemoryOperand>(Code::Psubq_xmm_xmmm128, (*op1).into(), op2.into())?
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Pmullw => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2))  = &self.op2 {
+                        Instruction::with2::<Register, Register>(Code::Pmullw_xmm_xmmm128, (*op1).into(), (*op2).into())?
+                    } else if let Some(Operand::Mem(op2))  = &self.op2 {
+                        Instruction::with2::<Register, MemoryOperand>(Code::Pmullw_xmm_xmmm128, (*op1).into(), op2.into())?
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Pmulld => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2))  = &self.op2 {
+                        Instruction::with2::<Register, Register>(Code::Pmulld_xmm_xmmm128, (*op1).into(), (*op2).into())?
+                    } else if let Some(Operand::Mem(op2))  = &self.op2 {
+                        Instruction::with2::<Register, MemoryOperand>(Code::Pmulld_xmm_xmmm128, (*op1).into(), op2.into())?
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Pand => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2))  = &self.op2 {
+                        Instruction::with2::<Register, Register>(Code::Pand_xmm_xmmm128, (*op1).into(), (*op2).into())?
+                    } else if let Some(Operand::Mem(op2))  = &self.op2 {
+                        Instruction::with2::<Register, MemoryOperand>(Code::Pand_xmm_xmmm128, (*op1).into(), op2.into())?
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Por => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2))  = &self.op2 {
+                        Instruction::with2::<Register, Register>(Code::Por_xmm_xmmm128, (*op1).into(), (*op2).into())?
+                    } else if let Some(Operand::Mem(op2))  = &self.op2 {
+                        Instruction::with2::<Register, MemoryOperand>(Code::Por_xmm_xmmm128, (*op1).into(), op2.into())?
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Pxor => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2))  = &self.op2 {
+                        Instruction::with2::<Register, Register>(Code::Pxor_xmm_xmmm128, (*op1).into(), (*op2).into())?
+                    } else if let Some(Operand::Mem(op2))  = &self.op2 {
+                        Instruction::with2::<Register, MemoryOperand>(Code::Pxor_xmm_xmmm128, (*op1).into(), op2.into())?
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
             Mnemonic::Ucomiss => {
                 if let Some(Operand::Reg(op1)) = &self.op1 {
                     if let Some(Operand::Reg(op2)) = &self.op2 {
@@ -1151,6 +1879,40 @@ impl X64MCInstr {
                     } else { todo!("{}", self) }
                 } else { todo!("{}", self) }
             },
+            Mnemonic::Cvttss2si => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2)) = &self.op2 {
+                        if op1.is_gr32() {
+                            Instruction::with2::<Register, Register>(Code::Cvttss2si_r32_xmmm32, (*op1).into(), (*op2).into())?
+                        } else if op1.is_gr64() {
+                            Instruction::with2::<Register, Register>(Code::Cvttss2si_r64_xmmm32, (*op1).into(), (*op2).into())?
+                        } else { todo!("{}", self) }
+                    } else if let Some(Operand::Mem(op2)) = &self.op2 {
+                        if op1.is_gr32() {
+                            Instruction::with2::<Register, MemoryOperand>(Code::Cvttss2si_r32_xmmm32, (*op1).into(), op2.into())?
+                        } else if op1.is_gr64() {
+                            Instruction::with2::<Register, MemoryOperand>(Code::Cvttss2si_r64_xmmm32, (*op1).into(), op2.into())?
+                        } else { todo!("{}", self) }
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Cvttsd2si => {
+                if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2)) = &self.op2 {
+                        if op1.is_gr32() {
+                            Instruction::with2::<Register, Register>(Code::Cvttsd2si_r32_xmmm64, (*op1).into(), (*op2).into())?
+                        } else if op1.is_gr64() {
+                            Instruction::with2::<Register, Register>(Code::Cvttsd2si_r64_xmmm64, (*op1).into(), (*op2).into())?
+                        } else { todo!("{}", self) }
+                    } else if let Some(Operand::Mem(op2)) = &self.op2 {
+                        if op1.is_gr32() {
+                            Instruction::with2::<Register, MemoryOperand>(Code::Cvttsd2si_r32_xmmm64, (*op1).into(), op2.into())?
+                        } else if op1.is_gr64() {
+                            Instruction::with2::<Register, MemoryOperand>(Code::Cvttsd2si_r64_xmmm64, (*op1).into(), op2.into())?
+                        } else { todo!("{}", self) }
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
             Mnemonic::Cvtss2sd => {
                 if let Some(Operand::Reg(op1)) = &self.op1 {
                     if let Some(Operand::Reg(op2)) = &self.op2 {
@@ -1180,7 +1942,9 @@ impl X64MCInstr {
             Mnemonic::Cvtsi2ss => {
                 if let Some(Operand::Reg(op1)) = &self.op1 {
                     if let Some(Operand::Reg(op2)) = &self.op2 {
-                        if op1.is_xmm() {
+                        if op1.is_xmm() && op2.is_gr64() {
+                            Instruction::with2::<Register, Register>(Code::Cvtsi2ss_xmm_rm64, (*op1).into(), (*op2).into())?
+                        } else if op1.is_xmm() {
                             Instruction::with2::<Register, Register>(Code::Cvtsi2ss_xmm_rm32, (*op1).into(), (*op2).into())?
                         } else { todo!("{}", self) }
                     } else if let Some(Operand::Mem(op2)) = &self.op2 {
@@ -1193,7 +1957,9 @@ impl X64MCInstr {
             Mnemonic::Cvtsi2sd => {
                 if let Some(Operand::Reg(op1)) = &self.op1 {
                     if let Some(Operand::Reg(op2)) = &self.op2 {
-                        if op1.is_xmm() {
+                        if op1.is_xmm() && op2.is_gr32() {
+                            Instruction::with2::<Register, Register>(Code::Cvtsi2sd_xmm_rm32, (*op1).into(), (*op2).into())?
+                        } else if op1.is_xmm() {
                             Instruction::with2::<Register, Register>(Code::Cvtsi2sd_xmm_rm64, (*op1).into(), (*op2).into())?
                         } else { todo!("{}", self) }
                     } else if let Some(Operand::Mem(op2)) = &self.op2 {
@@ -1207,7 +1973,98 @@ impl X64MCInstr {
             Mnemonic::Cwd => Instruction::with(Code::Cwd),
             Mnemonic::Cdq => Instruction::with(Code::Cdq),
             Mnemonic::Cqo => Instruction::with(Code::Cqo),
+            Mnemonic::Ud2 => Instruction::with(Code::Ud2),
+            Mnemonic::Xchg => {
+                if let Some(Operand::Mem(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2)) = &self.op2 {
+                        if op2.is_gr8() {
+                            Instruction::with2::<MemoryOperand, Register>(Code::Xchg_rm8_r8, op1.into(), (*op2).into())?
+                        } else if op2.is_gr16() {
+                            Instruction::with2::<MemoryOperand, Register>(Code::Xchg_rm16_r16, op1.into(), (*op2).into())?
+                        } else if op2.is_gr32() {
+                            Instruction::with2::<MemoryOperand, Register>(Code::Xchg_rm32_r32, op1.into(), (*op2).into())?
+                        } else if op2.is_gr64() {
+                            Instruction::with2::<MemoryOperand, Register>(Code::Xchg_rm64_r64, op1.into(), (*op2).into())?
+                        } else { todo!("{}", self) }
+                    } else { todo!("{}", self) }
+                } else if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2)) = &self.op2 {
+                        if op1.is_gr8() {
+                            Instruction::with2::<Register, Register>(Code::Xchg_rm8_r8, (*op1).into(), (*op2).into())?
+                        } else if op1.is_gr16() {
+                            Instruction::with2::<Register, Register>(Code::Xchg_rm16_r16, (*op1).into(), (*op2).into())?
+                        } else if op1.is_gr32() {
+                            Instruction::with2::<Register, Register>(Code::Xchg_rm32_r32, (*op1).into(), (*op2).into())?
+                        } else if op1.is_gr64() {
+                            Instruction::with2::<Register, Register>(Code::Xchg_rm64_r64, (*op1).into(), (*op2).into())?
+                        } else { todo!("{}", self) }
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Xadd => {
+                if let Some(Operand::Mem(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2)) = &self.op2 {
+                        if op2.is_gr8() {
+                            Instruction::with2::<MemoryOperand, Register>(Code::Xadd_rm8_r8, op1.into(), (*op2).into())?
+                        } else if op2.is_gr16() {
+                            Instruction::with2::<MemoryOperand, Register>(Code::Xadd_rm16_r16, op1.into(), (*op2).into())?
+                        } else if op2.is_gr32() {
+                            Instruction::with2::<MemoryOperand, Register>(Code::Xadd_rm32_r32, op1.into(), (*op2).into())?
+                        } else if op2.is_gr64() {
+                            Instruction::with2::<MemoryOperand, Register>(Code::Xadd_rm64_r64, op1.into(), (*op2).into())?
+                        } else { todo!("{}", self) }
+                    } else { todo!("{}", self) }
+                } else if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2)) = &self.op2 {
+                        if op1.is_gr8() {
+                            Instruction::with2::<Register, Register>(Code::Xadd_rm8_r8, (*op1).into(), (*op2).into())?
+                        } else if op1.is_gr16() {
+                            Instruction::with2::<Register, Register>(Code::Xadd_rm16_r16, (*op1).into(), (*op2).into())?
+                        } else if op1.is_gr32() {
+                            Instruction::with2::<Register, Register>(Code::Xadd_rm32_r32, (*op1).into(), (*op2).into())?
+                        } else if op1.is_gr64() {
+                            Instruction::with2::<Register, Register>(Code::Xadd_rm64_r64, (*op1).into(), (*op2).into())?
+                        } else { todo!("{}", self) }
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Cmpxchg => {
+                if let Some(Operand::Mem(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2)) = &self.op2 {
+                        if op2.is_gr8() {
+                            Instruction::with2::<MemoryOperand, Register>(Code::Cmpxchg_rm8_r8, op1.into(), (*op2).into())?
+                        } else if op2.is_gr16() {
+                            Instruction::with2::<MemoryOperand, Register>(Code::Cmpxchg_rm16_r16, op1.into(), (*op2).into())?
+                        } else if op2.is_gr32() {
+                            Instruction::with2::<MemoryOperand, Register>(Code::Cmpxchg_rm32_r32, op1.into(), (*op2).into())?
+                        } else if op2.is_gr64() {
+                            Instruction::with2::<MemoryOperand, Register>(Code::Cmpxchg_rm64_r64, op1.into(), (*op2).into())?
+                        } else { todo!("{}", self) }
+                    } else { todo!("{}", self) }
+                } else if let Some(Operand::Reg(op1)) = &self.op1 {
+                    if let Some(Operand::Reg(op2)) = &self.op2 {
+                        if op1.is_gr8() {
+                            Instruction::with2::<Register, Register>(Code::Cmpxchg_rm8_r8, (*op1).into(), (*op2).into())?
+                        } else if op1.is_gr16() {
+                            Instruction::with2::<Register, Register>(Code::Cmpxchg_rm16_r16, (*op1).into(), (*op2).into())?
+                        } else if op1.is_gr32() {
+                            Instruction::with2::<Register, Register>(Code::Cmpxchg_rm32_r32, (*op1).into(), (*op2).into())?
+                        } else if op1.is_gr64() {
+                            Instruction::with2::<Register, Register>(Code::Cmpxchg_rm64_r64, (*op1).into(), (*op2).into())?
+                        } else { todo!("{}", self) }
+                    } else { todo!("{}", self) }
+                } else { todo!("{}", self) }
+            },
+            Mnemonic::Mfence => Instruction::with(Code::Mfence),
+            Mnemonic::Cpuid => Instruction::with(Code::Cpuid),
+            Mnemonic::Rdtsc => Instruction::with(Code::Rdtsc),
+            Mnemonic::Syscall => Instruction::with(Code::Syscall),
+            Mnemonic::Nop => Instruction::with(Code::Nopd),
         };
+
+        if self.lock {
+            instr.set_has_lock_prefix(true);
+        }
         
         //instr.as_near_branch();
         //println!("instr: {}", instr);
@@ -1227,6 +2084,7 @@ impl X64MCInstr {
                 addend: *addend,
                 special: false,
                 kind: RelocationEncoding::X86Branch,
+                label: false,
             })
         } else if let Some(Operand::BlockLinkDestination(target, addend)) = &self.op1 {
             links = Some(Link {
@@ -1236,6 +2094,7 @@ impl X64MCInstr {
                 addend: *addend,
                 special: true,
                 kind: RelocationEncoding::Generic,
+                label: false,
             })
         } else if let Some(Operand::RipRelative(target)) = &self.op2 {
             links = Some(Link {
@@ -1245,6 +2104,7 @@ impl X64MCInstr {
                 addend: -4,
                 special: false,
                 kind: RelocationEncoding::X86RipRelative,
+                label: false,
             })
         }
 
@@ -1268,7 +2128,11 @@ impl X64MCInstr {
 
     /// emits the instruction as one colored string
     pub fn color(&self, profile: ColorProfile) -> String {
-        let mut string = profile.markup(&format!("{}", self.mnemonic), ColorClass::Instr);
+        let mut string = if self.lock {
+            profile.markup(&format!("lock {}", self.mnemonic), ColorClass::Instr)
+        } else {
+            profile.markup(&format!("{}", self.mnemonic), ColorClass::Instr)
+        };
 
         if let Some(op1) = &self.op1 {
             string.push_str(&format!(" {}", match op1 {
@@ -1328,7 +2192,15 @@ impl MCInstr for X64MCInstr {
 
 impl Display for X64MCInstr {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut string = format!("{}", self.mnemonic);
+        if let (Mnemonic::Label, Some(label)) = (self.mnemonic, &self.op1) {
+            return write!(f, "{}:", label);
+        }
+
+        let mut string = if self.lock {
+            format!("lock {}", self.mnemonic)
+        } else {
+            format!("{}", self.mnemonic)
+        };
 
         if let Some(op1) = &self.op1 {
             string.push_str(&format!(" {}", op1));
@@ -1368,6 +2240,7 @@ impl std::error::Error for InstrEncodingError {}
 pub enum Mnemonic {
     Add,
     Adc,
+    Sbb,
     And,
     Or,
     Xor,
@@ -1381,6 +2254,7 @@ pub enum Mnemonic {
     Mov,
     Movzx,
     Movsx,
+    Movsxd,
     Push,
     Pop,
     Ret,
@@ -1401,6 +2275,8 @@ pub enum Mnemonic {
 
     /// here's a link placed
     Link,
+    /// marks its position (the name is the debug operand)
+    Label,
     /// for debugging pourpusis
     Debug,
     /// start optimization again
@@ -1414,19 +2290,39 @@ pub enum Mnemonic {
     Setl,
     Setge,
     Setle,
+    Seta,
+    Setae,
+    Setb,
+    Setbe,
+    Setp,
+    Setnp,
+    Seto,
 
     Cmove,
     Cmovne,
+    Cmovp,
 
     Sal,
     Shr,
     Sar,
+    Shld,
+    Shrd,
+    Rol,
+    Ror,
+
+    Popcnt,
+    Bsr,
+    Bsf,
+    Lzcnt,
+    Tzcnt,
+    Bswap,
 
     Movq,
     Movd,
     Movss,
     Movsd,
     Movups,
+    Movaps,
     Movupd,
 
     Addss,
@@ -1437,12 +2333,47 @@ pub enum Mnemonic {
     Mulsd,
     Subss,
     Subsd,
+    Sqrtss,
+    Sqrtsd,
+    Roundss,
+    Roundsd,
+    Minss,
+    Minsd,
+    Maxss,
+    Maxsd,
+
+    Addps,
+    Addpd,
+    Subps,
+    Subpd,
+    Mulps,
+    Mulpd,
+    Divps,
+    Divpd,
+    Andpd,
+    Andnpd,
+    Orpd,
+    Paddb,
+    Paddw,
+    Paddd,
+    Paddq,
+    Psubb,
+    Psubw,
+    Psubd,
+    Psubq,
+    Pmullw,
+    Pmulld,
+    Pand,
+    Por,
+    Pxor,
 
     Ucomiss,
     Ucomisd,
 
     Cvtss2si,
     Cvtsd2si,
+    Cvttss2si,
+    Cvttsd2si,
     Cvtss2sd,
     Cvtsd2ss,
     Cvtsi2ss,
@@ -1452,11 +2383,29 @@ pub enum Mnemonic {
     Jl,
     Jge,
     Jle,
+    Ja,
+    Jae,
+    Jb,
+    Jbe,
+    Jp,
+    Jnp,
 
     Cbw,
     Cwd,
     Cdq,
     Cqo,
+
+    Ud2,
+
+    Xchg,
+    Xadd,
+    Cmpxchg,
+    Mfence,
+
+    Cpuid,
+    Rdtsc,
+    Syscall,
+    Nop,
 }
 
 impl FromStr for Mnemonic {
@@ -1466,6 +2415,7 @@ impl FromStr for Mnemonic {
         match s {
             "add" => Ok(Mnemonic::Add),
             "adc" => Ok(Mnemonic::Adc),
+            "sbb" => Ok(Mnemonic::Sbb),
             "and" => Ok(Mnemonic::And),
             "or" => Ok(Mnemonic::Or),
             "xor" => Ok(Mnemonic::Xor),
@@ -1487,22 +2437,41 @@ impl FromStr for Mnemonic {
             "sete" => Ok(Mnemonic::Sete),
             "setne" => Ok(Mnemonic::Setne),
             "setg" => Ok(Mnemonic::Setg),
+            "seta" => Ok(Mnemonic::Seta),
+            "setae" => Ok(Mnemonic::Setae),
+            "setb" => Ok(Mnemonic::Setb),
+            "setbe" => Ok(Mnemonic::Setbe),
+            "setp" => Ok(Mnemonic::Setp),
+            "setnp" => Ok(Mnemonic::Setnp),
+            "seto" => Ok(Mnemonic::Seto),
             "setl" => Ok(Mnemonic::Setl),
             "setge" => Ok(Mnemonic::Setge),
             "setle" => Ok(Mnemonic::Setle),
             "neg" => Ok(Mnemonic::Neg),
             "cmove" => Ok(Mnemonic::Cmove),
             "cmovne" => Ok(Mnemonic::Cmovne),
+            "cmovp" => Ok(Mnemonic::Cmovp),
             "div" => Ok(Mnemonic::Div),
             "idiv" => Ok(Mnemonic::Idiv),
             "sal" => Ok(Mnemonic::Sal),
             "shr" => Ok(Mnemonic::Shr),
             "sar" => Ok(Mnemonic::Sar),
+            "rol" => Ok(Mnemonic::Rol),
+            "ror" => Ok(Mnemonic::Ror),
+            "popcnt" => Ok(Mnemonic::Popcnt),
+            "bsr" => Ok(Mnemonic::Bsr),
+            "bsf" => Ok(Mnemonic::Bsf),
+            "lzcnt" => Ok(Mnemonic::Lzcnt),
+            "tzcnt" => Ok(Mnemonic::Tzcnt),
+            "bswap" => Ok(Mnemonic::Bswap),
+            "shld" => Ok(Mnemonic::Shld),
+            "shrd" => Ok(Mnemonic::Shrd),
             "movq" => Ok(Mnemonic::Movq),
             "movd" => Ok(Mnemonic::Movd),
             "movss" => Ok(Mnemonic::Movss),
             "movsd" => Ok(Mnemonic::Movsd),
             "movups" => Ok(Mnemonic::Movups),
+            "movaps" => Ok(Mnemonic::Movaps),
             "movupd" => Ok(Mnemonic::Movupd),
             "addss" => Ok(Mnemonic::Addss),
             "addsd" => Ok(Mnemonic::Addsd),
@@ -1512,15 +2481,55 @@ impl FromStr for Mnemonic {
             "mulsd" => Ok(Mnemonic::Mulsd),
             "subss" => Ok(Mnemonic::Subss),
             "subsd" => Ok(Mnemonic::Subsd),
+            "addps" => Ok(Mnemonic::Addps),
+            "addpd" => Ok(Mnemonic::Addpd),
+            "subps" => Ok(Mnemonic::Subps),
+            "subpd" => Ok(Mnemonic::Subpd),
+            "mulps" => Ok(Mnemonic::Mulps),
+            "mulpd" => Ok(Mnemonic::Mulpd),
+            "divps" => Ok(Mnemonic::Divps),
+            "divpd" => Ok(Mnemonic::Divpd),
+            "paddb" => Ok(Mnemonic::Paddb),
+            "paddw" => Ok(Mnemonic::Paddw),
+            "paddd" => Ok(Mnemonic::Paddd),
+            "paddq" => Ok(Mnemonic::Paddq),
+            "psubb" => Ok(Mnemonic::Psubb),
+            "psubw" => Ok(Mnemonic::Psubw),
+            "psubd" => Ok(Mnemonic::Psubd),
+            "psubq" => Ok(Mnemonic::Psubq),
+            "pmullw" => Ok(Mnemonic::Pmullw),
+            "pmulld" => Ok(Mnemonic::Pmulld),
+            "pand" => Ok(Mnemonic::Pand),
+            "por" => Ok(Mnemonic::Por),
+            "pxor" => Ok(Mnemonic::Pxor),
+            "sqrtss" => Ok(Mnemonic::Sqrtss),
+            "sqrtsd" => Ok(Mnemonic::Sqrtsd),
+            "roundss" => Ok(Mnemonic::Roundss),
+            "roundsd" => Ok(Mnemonic::Roundsd),
+            "minss" => Ok(Mnemonic::Minss),
+            "minsd" => Ok(Mnemonic::Minsd),
+            "maxss" => Ok(Mnemonic::Maxss),
+            "maxsd" => Ok(Mnemonic::Maxsd),
+            "andpd" => Ok(Mnemonic::Andpd),
+            "andnpd" => Ok(Mnemonic::Andnpd),
+            "orpd" => Ok(Mnemonic::Orpd),
             "ucomiss" => Ok(Mnemonic::Ucomiss),
             "ucomisd" => Ok(Mnemonic::Ucomisd),
             "cvtss2si"  => Ok(Mnemonic::Cvtss2si),
             "cvtsd2si"  => Ok(Mnemonic::Cvtsd2si),
+            "cvttss2si"  => Ok(Mnemonic::Cvttss2si),
+            "cvttsd2si"  => Ok(Mnemonic::Cvttsd2si),
             "cvtss2sd"  => Ok(Mnemonic::Cvtss2sd),
             "cvtsd2ss"  => Ok(Mnemonic::Cvtsd2ss),
             "cvtsi2ss" => Ok(Mnemonic::Cvtsi2ss),
             "cvtsi2sd" => Ok(Mnemonic::Cvtsi2sd),
             "jg" => Ok(Mnemonic::Jg),
+            "ja" => Ok(Mnemonic::Ja),
+            "jae" => Ok(Mnemonic::Jae),
+            "jb" => Ok(Mnemonic::Jb),
+            "jbe" => Ok(Mnemonic::Jbe),
+            "jp" => Ok(Mnemonic::Jp),
+            "jnp" => Ok(Mnemonic::Jnp),
             "jl" => Ok(Mnemonic::Jl),
             "jge" => Ok(Mnemonic::Jge),
             "jle" => Ok(Mnemonic::Jle),
@@ -1528,7 +2537,17 @@ impl FromStr for Mnemonic {
             "cwd" => Ok(Mnemonic::Cwd),
             "cdq" => Ok(Mnemonic::Cdq),
             "cqo" => Ok(Mnemonic::Cqo),
+            "ud2" => Ok(Mnemonic::Ud2),
+            "xchg" => Ok(Mnemonic::Xchg),
+            "xadd" => Ok(Mnemonic::Xadd),
+            "cmpxchg" => Ok(Mnemonic::Cmpxchg),
+            "mfence" => Ok(Mnemonic::Mfence),
+            "cpuid" => Ok(Mnemonic::Cpuid),
+            "rdtsc" => Ok(Mnemonic::Rdtsc),
+            "syscall" => Ok(Mnemonic::Syscall),
+            "nop" => Ok(Mnemonic::Nop),
             "movsx" => Ok(Mnemonic::Movsx),
+            "movsxd" => Ok(Mnemonic::Movsxd),
             _ => Err(()),
         }
     }
@@ -1539,6 +2558,7 @@ impl Display for Mnemonic {
         write!(f, "{}", match self {    
             Mnemonic::Add => "add",
             Mnemonic::Adc => "adc",
+            Mnemonic::Sbb => "sbb",
             Mnemonic::And => "and",
             Mnemonic::Or => "or",
             Mnemonic::Xor => "xor",
@@ -1555,6 +2575,7 @@ impl Display for Mnemonic {
             Mnemonic::Mul => "mul",
             Mnemonic::Imul => "imul",
             Mnemonic::Link => "",
+            Mnemonic::Label => "",
             Mnemonic::StartOptimization => "",
             Mnemonic::EndOptimization => "",
             Mnemonic::Debug => "#",
@@ -1563,6 +2584,13 @@ impl Display for Mnemonic {
             Mnemonic::Cmp => "cmp",
             Mnemonic::Sete => "sete",
             Mnemonic::Setg => "setg",
+            Mnemonic::Seta => "seta",
+            Mnemonic::Setae => "setae",
+            Mnemonic::Setb => "setb",
+            Mnemonic::Setbe => "setbe",
+            Mnemonic::Setp => "setp",
+            Mnemonic::Setnp => "setnp",
+            Mnemonic::Seto => "seto",
             Mnemonic::Setl => "setl",
             Mnemonic::Setge => "setge",
             Mnemonic::Setle => "setle",
@@ -1570,16 +2598,28 @@ impl Display for Mnemonic {
             Mnemonic::Neg => "neg",
             Mnemonic::Cmove => "cmove",
             Mnemonic::Cmovne => "cmovne",
+            Mnemonic::Cmovp => "cmovp",
             Mnemonic::Div => "div",
             Mnemonic::Idiv => "idiv",
             Mnemonic::Sal => "sal",
             Mnemonic::Shr => "shr",
             Mnemonic::Sar => "sar",
+            Mnemonic::Rol => "rol",
+            Mnemonic::Ror => "ror",
+            Mnemonic::Popcnt => "popcnt",
+            Mnemonic::Bsr => "bsr",
+            Mnemonic::Bsf => "bsf",
+            Mnemonic::Lzcnt => "lzcnt",
+            Mnemonic::Tzcnt => "tzcnt",
+            Mnemonic::Bswap => "bswap",
+            Mnemonic::Shld => "shld",
+            Mnemonic::Shrd => "shrd",
             Mnemonic::Movq => "movq",
             Mnemonic::Movd => "movd",
             Mnemonic::Movss=> "movss",
-            Mnemonic::Movsd=> "movss",
+            Mnemonic::Movsd=> "movsd",
             Mnemonic::Movups=> "movups",
+            Mnemonic::Movaps=> "movaps",
             Mnemonic::Movupd => "movupd",
             Mnemonic::Addss => "addss",
             Mnemonic::Addsd => "addsd",
@@ -1589,15 +2629,55 @@ impl Display for Mnemonic {
             Mnemonic::Mulsd => "mulsd",
             Mnemonic::Subss => "subss",
             Mnemonic::Subsd => "subsd",
+            Mnemonic::Addps => "addps",
+            Mnemonic::Addpd => "addpd",
+            Mnemonic::Subps => "subps",
+            Mnemonic::Subpd => "subpd",
+            Mnemonic::Mulps => "mulps",
+            Mnemonic::Mulpd => "mulpd",
+            Mnemonic::Divps => "divps",
+            Mnemonic::Divpd => "divpd",
+            Mnemonic::Paddb => "paddb",
+            Mnemonic::Paddw => "paddw",
+            Mnemonic::Paddd => "paddd",
+            Mnemonic::Paddq => "paddq",
+            Mnemonic::Psubb => "psubb",
+            Mnemonic::Psubw => "psubw",
+            Mnemonic::Psubd => "psubd",
+            Mnemonic::Psubq => "psubq",
+            Mnemonic::Pmullw => "pmullw",
+            Mnemonic::Pmulld => "pmulld",
+            Mnemonic::Pand => "pand",
+            Mnemonic::Por => "por",
+            Mnemonic::Pxor => "pxor",
+            Mnemonic::Sqrtss => "sqrtss",
+            Mnemonic::Sqrtsd => "sqrtsd",
+            Mnemonic::Roundss => "roundss",
+            Mnemonic::Roundsd => "roundsd",
+            Mnemonic::Minss => "minss",
+            Mnemonic::Minsd => "minsd",
+            Mnemonic::Maxss => "maxss",
+            Mnemonic::Maxsd => "maxsd",
+            Mnemonic::Andpd => "andpd",
+            Mnemonic::Andnpd => "andnpd",
+            Mnemonic::Orpd => "orpd",
             Mnemonic::Ucomiss => "ucomiss",
             Mnemonic::Ucomisd => "ucomisd",
             Mnemonic::Cvtss2si => "cvtss2si",
             Mnemonic::Cvtsd2si => "cvtsd2si",
+            Mnemonic::Cvttss2si => "cvttss2si",
+            Mnemonic::Cvttsd2si => "cvttsd2si",
             Mnemonic::Cvtss2sd => "cvtss2sd",
             Mnemonic::Cvtsd2ss => "cvtsd2ss",
             Mnemonic::Cvtsi2ss => "cvtsi2ss",
             Mnemonic::Cvtsi2sd => "cvtsi2sd",
             Mnemonic::Jg => "jg",
+            Mnemonic::Ja => "ja",
+            Mnemonic::Jae => "jae",
+            Mnemonic::Jb => "jb",
+            Mnemonic::Jbe => "jbe",
+            Mnemonic::Jp => "jp",
+            Mnemonic::Jnp => "jnp",
             Mnemonic::Jl => "jl",
             Mnemonic::Jge => "jge",
             Mnemonic::Jle => "jle",
@@ -1605,7 +2685,17 @@ impl Display for Mnemonic {
             Mnemonic::Cwd => "cwd",
             Mnemonic::Cdq => "cdq",
             Mnemonic::Cqo => "cqo",
-            Mnemonic::Movsx => "movxz",
+            Mnemonic::Ud2 => "ud2",
+            Mnemonic::Xchg => "xchg",
+            Mnemonic::Xadd => "xadd",
+            Mnemonic::Cmpxchg => "cmpxchg",
+            Mnemonic::Mfence => "mfence",
+            Mnemonic::Cpuid => "cpuid",
+            Mnemonic::Rdtsc => "rdtsc",
+            Mnemonic::Syscall => "syscall",
+            Mnemonic::Nop => "nop",
+            Mnemonic::Movsx => "movsx",
+            Mnemonic::Movsxd => "movsxd",
         })
     }
 }
@@ -1950,6 +3040,7 @@ macro_rules! IsCheckerOps0 {
 
 IsCheckerOps0!(is_add, Mnemonic::Add);
 IsCheckerOps0!(is_adc, Mnemonic::Adc);
+IsCheckerOps0!(is_sbb, Mnemonic::Sbb);
 IsCheckerOps0!(is_and, Mnemonic::And);
 IsCheckerOps0!(is_or, Mnemonic::Or);
 IsCheckerOps0!(is_xor, Mnemonic::Xor);
@@ -1960,6 +3051,7 @@ IsCheckerOps0!(is_lea, Mnemonic::Lea);
 IsCheckerOps0!(is_mov, Mnemonic::Mov);
 IsCheckerOps0!(is_movzx, Mnemonic::Movzx);
 IsCheckerOps0!(is_movsx, Mnemonic::Movsx);
+IsCheckerOps0!(is_movsxd, Mnemonic::Movsxd);
 IsCheckerOps0!(is_push, Mnemonic::Push);
 IsCheckerOps0!(is_pop, Mnemonic::Pop);
 IsCheckerOps0!(is_ret, Mnemonic::Ret);
@@ -1975,6 +3067,12 @@ IsCheckerOps0!(is_jg, Mnemonic::Jg);
 IsCheckerOps0!(is_jl, Mnemonic::Jl);
 IsCheckerOps0!(is_jge, Mnemonic::Jge);
 IsCheckerOps0!(is_jle, Mnemonic::Jle);
+IsCheckerOps0!(is_ja, Mnemonic::Ja);
+IsCheckerOps0!(is_jae, Mnemonic::Jae);
+IsCheckerOps0!(is_jb, Mnemonic::Jb);
+IsCheckerOps0!(is_jbe, Mnemonic::Jbe);
+IsCheckerOps0!(is_jp, Mnemonic::Jp);
+IsCheckerOps0!(is_jnp, Mnemonic::Jnp);
 IsCheckerOps0!(is_endbr64, Mnemonic::Endbr64);
 IsCheckerOps0!(is_sete, Mnemonic::Sete);
 IsCheckerOps0!(is_setne, Mnemonic::Setne);
@@ -1982,16 +3080,35 @@ IsCheckerOps0!(is_setg, Mnemonic::Setg);
 IsCheckerOps0!(is_setl, Mnemonic::Setl);
 IsCheckerOps0!(is_setge, Mnemonic::Setge);
 IsCheckerOps0!(is_setle, Mnemonic::Setle);
+IsCheckerOps0!(is_seta, Mnemonic::Seta);
+IsCheckerOps0!(is_setae, Mnemonic::Setae);
+IsCheckerOps0!(is_setb, Mnemonic::Setb);
+IsCheckerOps0!(is_setbe, Mnemonic::Setbe);
+IsCheckerOps0!(is_setp, Mnemonic::Setp);
+IsCheckerOps0!(is_setnp, Mnemonic::Setnp);
+IsCheckerOps0!(is_seto, Mnemonic::Seto);
 IsCheckerOps0!(is_cmove, Mnemonic::Cmove);
 IsCheckerOps0!(is_cmovne, Mnemonic::Cmovne);
+IsCheckerOps0!(is_cmovp, Mnemonic::Cmovp);
 IsCheckerOps0!(is_sal, Mnemonic::Sal);
 IsCheckerOps0!(is_shr, Mnemonic::Shr);
 IsCheckerOps0!(is_sar, Mnemonic::Sar);
+IsCheckerOps0!(is_rol, Mnemonic::Rol);
+IsCheckerOps0!(is_ror, Mnemonic::Ror);
+IsCheckerOps0!(is_popcnt, Mnemonic::Popcnt);
+IsCheckerOps0!(is_bsr, Mnemonic::Bsr);
+IsCheckerOps0!(is_bsf, Mnemonic::Bsf);
+IsCheckerOps0!(is_lzcnt, Mnemonic::Lzcnt);
+IsCheckerOps0!(is_tzcnt, Mnemonic::Tzcnt);
+IsCheckerOps0!(is_bswap, Mnemonic::Bswap);
+IsCheckerOps0!(is_shld, Mnemonic::Shld);
+IsCheckerOps0!(is_shrd, Mnemonic::Shrd);
 IsCheckerOps0!(is_movq, Mnemonic::Movq);
 IsCheckerOps0!(is_movd, Mnemonic::Movd);
 IsCheckerOps0!(is_movss, Mnemonic::Movss);
 IsCheckerOps0!(is_movsd, Mnemonic::Movsd);
 IsCheckerOps0!(is_movups, Mnemonic::Movups);
+IsCheckerOps0!(is_movaps, Mnemonic::Movaps);
 IsCheckerOps0!(is_movupd, Mnemonic::Movupd);
 IsCheckerOps0!(is_addss, Mnemonic::Addss);
 IsCheckerOps0!(is_addsd, Mnemonic::Addsd);
@@ -2001,10 +3118,44 @@ IsCheckerOps0!(is_mulss, Mnemonic::Mulss);
 IsCheckerOps0!(is_mulsd, Mnemonic::Mulsd);
 IsCheckerOps0!(is_subss, Mnemonic::Subss);
 IsCheckerOps0!(is_subsd, Mnemonic::Subsd);
+IsCheckerOps0!(is_addps, Mnemonic::Addps);
+IsCheckerOps0!(is_addpd, Mnemonic::Addpd);
+IsCheckerOps0!(is_subps, Mnemonic::Subps);
+IsCheckerOps0!(is_subpd, Mnemonic::Subpd);
+IsCheckerOps0!(is_mulps, Mnemonic::Mulps);
+IsCheckerOps0!(is_mulpd, Mnemonic::Mulpd);
+IsCheckerOps0!(is_divps, Mnemonic::Divps);
+IsCheckerOps0!(is_divpd, Mnemonic::Divpd);
+IsCheckerOps0!(is_paddb, Mnemonic::Paddb);
+IsCheckerOps0!(is_paddw, Mnemonic::Paddw);
+IsCheckerOps0!(is_paddd, Mnemonic::Paddd);
+IsCheckerOps0!(is_paddq, Mnemonic::Paddq);
+IsCheckerOps0!(is_psubb, Mnemonic::Psubb);
+IsCheckerOps0!(is_psubw, Mnemonic::Psubw);
+IsCheckerOps0!(is_psubd, Mnemonic::Psubd);
+IsCheckerOps0!(is_psubq, Mnemonic::Psubq);
+IsCheckerOps0!(is_pmullw, Mnemonic::Pmullw);
+IsCheckerOps0!(is_pmulld, Mnemonic::Pmulld);
+IsCheckerOps0!(is_pand, Mnemonic::Pand);
+IsCheckerOps0!(is_por, Mnemonic::Por);
+IsCheckerOps0!(is_pxor, Mnemonic::Pxor);
 IsCheckerOps0!(is_ucomiss, Mnemonic::Ucomiss);
 IsCheckerOps0!(is_ucomisd, Mnemonic::Ucomisd);
+IsCheckerOps0!(is_sqrtss, Mnemonic::Sqrtss);
+IsCheckerOps0!(is_sqrtsd, Mnemonic::Sqrtsd);
+IsCheckerOps0!(is_roundss, Mnemonic::Roundss);
+IsCheckerOps0!(is_roundsd, Mnemonic::Roundsd);
+IsCheckerOps0!(is_minss, Mnemonic::Minss);
+IsCheckerOps0!(is_minsd, Mnemonic::Minsd);
+IsCheckerOps0!(is_maxss, Mnemonic::Maxss);
+IsCheckerOps0!(is_maxsd, Mnemonic::Maxsd);
+IsCheckerOps0!(is_andpd, Mnemonic::Andpd);
+IsCheckerOps0!(is_andnpd, Mnemonic::Andnpd);
+IsCheckerOps0!(is_orpd, Mnemonic::Orpd);
 IsCheckerOps0!(is_cvtss2si, Mnemonic::Cvtss2si);
 IsCheckerOps0!(is_cvtsd2si, Mnemonic::Cvtsd2si);
+IsCheckerOps0!(is_cvttss2si, Mnemonic::Cvttss2si);
+IsCheckerOps0!(is_cvttsd2si, Mnemonic::Cvttsd2si);
 IsCheckerOps0!(is_cvtss2sd, Mnemonic::Cvtss2sd);
 IsCheckerOps0!(is_cvtsd2ss, Mnemonic::Cvtsd2ss);
 IsCheckerOps0!(is_cvtsi2ss, Mnemonic::Cvtsi2ss);
@@ -2023,6 +3174,7 @@ macro_rules! IsCheckerOps1 {
 
 IsCheckerOps1!(is_add1, Mnemonic::Add);
 IsCheckerOps1!(is_adc1, Mnemonic::Adc);
+IsCheckerOps1!(is_sbb1, Mnemonic::Sbb);
 IsCheckerOps1!(is_and1, Mnemonic::And);
 IsCheckerOps1!(is_or1, Mnemonic::Or);
 IsCheckerOps1!(is_xor1, Mnemonic::Xor);
@@ -2033,6 +3185,7 @@ IsCheckerOps1!(is_lea1, Mnemonic::Lea);
 IsCheckerOps1!(is_mov1, Mnemonic::Mov);
 IsCheckerOps1!(is_movzx1, Mnemonic::Movzx);
 IsCheckerOps1!(is_movsx1, Mnemonic::Movsx);
+IsCheckerOps1!(is_movsxd1, Mnemonic::Movsxd);
 IsCheckerOps1!(is_push1, Mnemonic::Push);
 IsCheckerOps1!(is_pop1, Mnemonic::Pop);
 IsCheckerOps1!(is_imul1, Mnemonic::Imul);
@@ -2044,8 +3197,16 @@ IsCheckerOps1!(is_setg1, Mnemonic::Setg);
 IsCheckerOps1!(is_setl1, Mnemonic::Setl);
 IsCheckerOps1!(is_setge1, Mnemonic::Setge);
 IsCheckerOps1!(is_setle1, Mnemonic::Setle);
+IsCheckerOps1!(is_seta1, Mnemonic::Seta);
+IsCheckerOps1!(is_setae1, Mnemonic::Setae);
+IsCheckerOps1!(is_setb1, Mnemonic::Setb);
+IsCheckerOps1!(is_setbe1, Mnemonic::Setbe);
+IsCheckerOps1!(is_setp1, Mnemonic::Setp);
+IsCheckerOps1!(is_setnp1, Mnemonic::Setnp);
+IsCheckerOps1!(is_seto1, Mnemonic::Seto);
 IsCheckerOps1!(is_cmove1, Mnemonic::Cmove);
 IsCheckerOps1!(is_cmovne1, Mnemonic::Cmovne);
+IsCheckerOps1!(is_cmovp1, Mnemonic::Cmovp);
 IsCheckerOps1!(is_sal1, Mnemonic::Sal);
 IsCheckerOps1!(is_shr1, Mnemonic::Shr);
 IsCheckerOps1!(is_movq1, Mnemonic::Movq);
@@ -2053,6 +3214,7 @@ IsCheckerOps1!(is_movd1, Mnemonic::Movd);
 IsCheckerOps1!(is_movss1, Mnemonic::Movd);
 IsCheckerOps1!(is_movsd1, Mnemonic::Movd);
 IsCheckerOps1!(is_movups1, Mnemonic::Movups);
+IsCheckerOps1!(is_movaps1, Mnemonic::Movaps);
 IsCheckerOps1!(is_movupd1, Mnemonic::Movupd);
 IsCheckerOps1!(is_addss1, Mnemonic::Addss);
 IsCheckerOps1!(is_addsd1, Mnemonic::Addsd);
@@ -2062,10 +3224,44 @@ IsCheckerOps1!(is_mulss1, Mnemonic::Mulss);
 IsCheckerOps1!(is_mulsd1, Mnemonic::Mulsd);
 IsCheckerOps1!(is_subss1, Mnemonic::Subss);
 IsCheckerOps1!(is_subsd1, Mnemonic::Subsd);
+IsCheckerOps1!(is_addps1, Mnemonic::Addps);
+IsCheckerOps1!(is_addpd1, Mnemonic::Addpd);
+IsCheckerOps1!(is_subps1, Mnemonic::Subps);
+IsCheckerOps1!(is_subpd1, Mnemonic::Subpd);
+IsCheckerOps1!(is_mulps1, Mnemonic::Mulps);
+IsCheckerOps1!(is_mulpd1, Mnemonic::Mulpd);
+IsCheckerOps1!(is_divps1, Mnemonic::Divps);
+IsCheckerOps1!(is_divpd1, Mnemonic::Divpd);
+IsCheckerOps1!(is_paddb1, Mnemonic::Paddb);
+IsCheckerOps1!(is_paddw1, Mnemonic::Paddw);
+IsCheckerOps1!(is_paddd1, Mnemonic::Paddd);
+IsCheckerOps1!(is_paddq1, Mnemonic::Paddq);
+IsCheckerOps1!(is_psubb1, Mnemonic::Psubb);
+IsCheckerOps1!(is_psubw1, Mnemonic::Psubw);
+IsCheckerOps1!(is_psubd1, Mnemonic::Psubd);
+IsCheckerOps1!(is_psubq1, Mnemonic::Psubq);
+IsCheckerOps1!(is_pmullw1, Mnemonic::Pmullw);
+IsCheckerOps1!(is_pmulld1, Mnemonic::Pmulld);
+IsCheckerOps1!(is_pand1, Mnemonic::Pand);
+IsCheckerOps1!(is_por1, Mnemonic::Por);
+IsCheckerOps1!(is_pxor1, Mnemonic::Pxor);
 IsCheckerOps1!(is_ucomiss1, Mnemonic::Ucomiss);
 IsCheckerOps1!(is_ucomisd1, Mnemonic::Ucomisd);
+IsCheckerOps1!(is_sqrtss1, Mnemonic::Sqrtss);
+IsCheckerOps1!(is_sqrtsd1, Mnemonic::Sqrtsd);
+IsCheckerOps1!(is_roundss1, Mnemonic::Roundss);
+IsCheckerOps1!(is_roundsd1, Mnemonic::Roundsd);
+IsCheckerOps1!(is_minss1, Mnemonic::Minss);
+IsCheckerOps1!(is_minsd1, Mnemonic::Minsd);
+IsCheckerOps1!(is_maxss1, Mnemonic::Maxss);
+IsCheckerOps1!(is_maxsd1, Mnemonic::Maxsd);
+IsCheckerOps1!(is_andpd1, Mnemonic::Andpd);
+IsCheckerOps1!(is_andnpd1, Mnemonic::Andnpd);
+IsCheckerOps1!(is_orpd1, Mnemonic::Orpd);
 IsCheckerOps1!(is_cvtss2si1, Mnemonic::Cvtss2si);
 IsCheckerOps1!(is_cvtsd2si1, Mnemonic::Cvtsd2si);
+IsCheckerOps1!(is_cvttss2si1, Mnemonic::Cvttss2si);
+IsCheckerOps1!(is_cvttsd2si1, Mnemonic::Cvttsd2si);
 IsCheckerOps1!(is_cvtss2sd1, Mnemonic::Cvtss2sd);
 IsCheckerOps1!(is_cvtsd2ss1, Mnemonic::Cvtsd2ss);
 IsCheckerOps1!(is_cvtsi2ss1, Mnemonic::Cvtsi2ss);
@@ -2084,6 +3280,7 @@ macro_rules! IsCheckerOps2 {
 
 IsCheckerOps2!(is_add2, Mnemonic::Add);
 IsCheckerOps2!(is_adc2, Mnemonic::Adc);
+IsCheckerOps2!(is_sbb2, Mnemonic::Sbb);
 IsCheckerOps2!(is_and2, Mnemonic::And);
 IsCheckerOps2!(is_or2, Mnemonic::Or);
 IsCheckerOps2!(is_xor2, Mnemonic::Xor);
@@ -2093,8 +3290,10 @@ IsCheckerOps2!(is_lea2, Mnemonic::Lea);
 IsCheckerOps2!(is_mov2, Mnemonic::Mov);
 IsCheckerOps2!(is_movzx2, Mnemonic::Movzx);
 IsCheckerOps2!(is_movsx2, Mnemonic::Movsx);
+IsCheckerOps2!(is_movsxd2, Mnemonic::Movsxd);
 IsCheckerOps2!(is_cmove2, Mnemonic::Cmove);
 IsCheckerOps2!(is_cmovne2, Mnemonic::Cmovne);
+IsCheckerOps2!(is_cmovp2, Mnemonic::Cmovp);
 IsCheckerOps2!(is_sal2, Mnemonic::Sal);
 IsCheckerOps2!(is_shr2, Mnemonic::Shr);
 IsCheckerOps2!(is_movq2, Mnemonic::Movq);
@@ -2102,6 +3301,7 @@ IsCheckerOps2!(is_movd2, Mnemonic::Movd);
 IsCheckerOps2!(is_movss2, Mnemonic::Movd);
 IsCheckerOps2!(is_movsd2, Mnemonic::Movd);
 IsCheckerOps2!(is_movups2, Mnemonic::Movups);
+IsCheckerOps2!(is_movaps2, Mnemonic::Movaps);
 IsCheckerOps2!(is_movupd2, Mnemonic::Movupd);
 IsCheckerOps2!(is_addss2, Mnemonic::Addss);
 IsCheckerOps2!(is_addsd2, Mnemonic::Addsd);
@@ -2111,10 +3311,44 @@ IsCheckerOps2!(is_mulss2, Mnemonic::Mulss);
 IsCheckerOps2!(is_mulsd2, Mnemonic::Mulsd);
 IsCheckerOps2!(is_subss2, Mnemonic::Subss);
 IsCheckerOps2!(is_subsd2, Mnemonic::Subsd);
+IsCheckerOps2!(is_addps2, Mnemonic::Addps);
+IsCheckerOps2!(is_addpd2, Mnemonic::Addpd);
+IsCheckerOps2!(is_subps2, Mnemonic::Subps);
+IsCheckerOps2!(is_subpd2, Mnemonic::Subpd);
+IsCheckerOps2!(is_mulps2, Mnemonic::Mulps);
+IsCheckerOps2!(is_mulpd2, Mnemonic::Mulpd);
+IsCheckerOps2!(is_divps2, Mnemonic::Divps);
+IsCheckerOps2!(is_divpd2, Mnemonic::Divpd);
+IsCheckerOps2!(is_paddb2, Mnemonic::Paddb);
+IsCheckerOps2!(is_paddw2, Mnemonic::Paddw);
+IsCheckerOps2!(is_paddd2, Mnemonic::Paddd);
+IsCheckerOps2!(is_paddq2, Mnemonic::Paddq);
+IsCheckerOps2!(is_psubb2, Mnemonic::Psubb);
+IsCheckerOps2!(is_psubw2, Mnemonic::Psubw);
+IsCheckerOps2!(is_psubd2, Mnemonic::Psubd);
+IsCheckerOps2!(is_psubq2, Mnemonic::Psubq);
+IsCheckerOps2!(is_pmullw2, Mnemonic::Pmullw);
+IsCheckerOps2!(is_pmulld2, Mnemonic::Pmulld);
+IsCheckerOps2!(is_pand2, Mnemonic::Pand);
+IsCheckerOps2!(is_por2, Mnemonic::Por);
+IsCheckerOps2!(is_pxor2, Mnemonic::Pxor);
 IsCheckerOps2!(is_ucomiss2, Mnemonic::Ucomiss);
 IsCheckerOps2!(is_ucomisd2, Mnemonic::Ucomisd);
+IsCheckerOps2!(is_sqrtss2, Mnemonic::Sqrtss);
+IsCheckerOps2!(is_sqrtsd2, Mnemonic::Sqrtsd);
+IsCheckerOps2!(is_roundss2, Mnemonic::Roundss);
+IsCheckerOps2!(is_roundsd2, Mnemonic::Roundsd);
+IsCheckerOps2!(is_minss2, Mnemonic::Minss);
+IsCheckerOps2!(is_minsd2, Mnemonic::Minsd);
+IsCheckerOps2!(is_maxss2, Mnemonic::Maxss);
+IsCheckerOps2!(is_maxsd2, Mnemonic::Maxsd);
+IsCheckerOps2!(is_andpd2, Mnemonic::Andpd);
+IsCheckerOps2!(is_andnpd2, Mnemonic::Andnpd);
+IsCheckerOps2!(is_orpd2, Mnemonic::Orpd);
 IsCheckerOps2!(is_cvtss2si2, Mnemonic::Cvtss2si);
 IsCheckerOps2!(is_cvtsd2si2, Mnemonic::Cvtsd2si);
+IsCheckerOps2!(is_cvttss2si2, Mnemonic::Cvttss2si);
+IsCheckerOps2!(is_cvttsd2si2, Mnemonic::Cvttsd2si);
 IsCheckerOps2!(is_cvtss2sd2, Mnemonic::Cvtss2sd);
 IsCheckerOps2!(is_cvtsd2ss2, Mnemonic::Cvtsd2ss);
 IsCheckerOps2!(is_cvtsi2ss2, Mnemonic::Cvtsi2ss);
@@ -31,13 +31,28 @@ impl x64Parser {
 
         self.tokens.pop_front(); // advance
 
-        let string = instr.to_ascii_lowercase();
+        let mut string = instr.to_ascii_lowercase();
+
+        let lock = string == "lock";
+
+        if lock {
+            string = if let Some(Token::Ident(instr)) = self.tokens.pop_front() {
+                instr.to_ascii_lowercase()
+            } else {
+                Err(ParsingError::FirstTokenNeedsToBeIdent)?
+            };
+        }
+
         let parsed = Mnemonic::from_str(&string);
         match parsed {
             Ok(mne) => self.parse_instr(mne)?,
             Err(_) => Err(ParsingError::UnknownInstruction(string))?
         }
 
+        if lock {
+            self.out = self.out.as_ref().map(|instr| instr.make_lock());
+        }
+
         Ok(())
     }
 
@@ -48,6 +63,7 @@ impl x64Parser {
             op2: None,
             op3: None,
             far: false,
+            lock: false,
         };
 
         let mut first_op = false;
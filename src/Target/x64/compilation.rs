@@ -1,5 +1,5 @@
-use std::collections::HashMap;
-use crate::CodeGen::{calling_convention::MachineCallingConvention, compilation::CompilationHelper, Allocator, ConstImmRules, Reg};
+use std::collections::{HashMap, HashSet};
+use crate::CodeGen::{calling_convention::MachineCallingConvention, compilation::CompilationHelper, Allocator, ConstImmRules, FrameInfo, Reg};
 use crate::Target::{Arch, CallConv};
 
 use super::X64Reg;
@@ -59,6 +59,12 @@ pub(crate) fn construct_compilation_helper(call_conv: CallConv) -> CompilationHe
         call: calling_convention,
 
         callee_save_registers: Vec::new(), // will be set in the allocation
+        va: None,
+        arg_stores: Vec::new(),
+        arg_loads: Vec::new(),
+        arg_copies: Vec::new(),
+        ret_ptr: None,
+        features: HashSet::new(),
     };
 
     alloc.fregs.reverse();
@@ -72,6 +78,10 @@ pub(crate) fn construct_compilation_helper(call_conv: CallConv) -> CompilationHe
     );
 
     helper.lower = Some(super::lower::x64_lower);
+    helper.frame = Some(FrameInfo {
+        cfi: super::lower::x64_prolog_cfi,
+        size: super::lower::x64_frame_size,
+    });
 
     helper.fp_imm = ConstImmRules::CreateConst;
 
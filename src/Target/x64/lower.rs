@@ -1,4 +1,4 @@
-use crate::CodeGen::{MCInstr, MachineCallingConvention, MachineInstr, MachineMnemonic, MachineOperand};
+use crate::CodeGen::{MCInstr, MachineInstr, MachineMnemonic, MachineOperand};
 use crate::Target::CallConv;
 
 mod adr;
@@ -15,14 +15,26 @@ mod stack;
 mod switch;
 mod zext;
 mod cmov;
+mod wide;
+mod vector;
+mod mem;
+mod va;
+mod asm;
+mod atomic;
 
 mod fmath;
 mod fcmp;
 mod fmove;
 mod fcast;
+mod bitcast;
+mod bit;
+mod overflow;
+mod float;
+
+pub(crate) use prolog::{x64_frame_size, x64_prolog_cfi};
 
 use super::optimizer::X64AsmOpt;
-use super::{instr::{Mnemonic, Operand, X64MCInstr}, X64Reg};
+use super::{instr::{MemOp, Mnemonic, Operand, X64MCInstr}, X64Reg};
 
 //pub(crate) static mut USE_SP_FOR_STACK: bool = false;
 //pub(crate) static mut SP_OFF: i32 = -4;
@@ -31,7 +43,8 @@ macro_rules! x64_stack {
     ($off:expr) => {
         //unsafe {
             //if !USE_SP_FOR_STACK {
-                Operand::Mem(X64Reg::Rbp - $off as u32)
+                // arguments which are passed over the stack have negative offsets (they are above rbp)
+                Operand::Mem(MemOp { base: Some(X64Reg::Rbp), index: None, scale: 1, displ: -($off as isize), rip: false })
             /*} else {
                 Operand::Mem(X64Reg::Rsp + ($off + SP_OFF) as u32)
             }*/
@@ -41,23 +54,8 @@ macro_rules! x64_stack {
 
 pub(crate) fn x64_lower_instr(conv: CallConv, sink: &mut Vec<X64MCInstr>, instr: MachineInstr) {
     match &instr.mnemonic {        
-    MachineMnemonic::CallStackPrepare => {
-        sink.push(X64MCInstr::with2(
-            Mnemonic::Sub, Operand::Reg(X64Reg::Rsp), 
-            Operand::Imm(
-                MachineCallingConvention {
-                call_conv: conv
-            }.shadow(crate::Target::Arch::X86_64) - 8
-        )));
-    },MachineMnemonic::CallStackRedo => {
-        sink.push(X64MCInstr::with2(
-            Mnemonic::Add, Operand::Reg(X64Reg::Rsp), 
-            Operand::Imm(
-                MachineCallingConvention {
-                call_conv: conv
-            }.shadow(crate::Target::Arch::X86_64) - 8
-        )));
-    },
+        MachineMnemonic::CallStackPrepare =>                             push::x64_lower_call_stack(sink, &instr, Mnemonic::Sub),
+        MachineMnemonic::CallStackRedo =>                                push::x64_lower_call_stack(sink, &instr, Mnemonic::Add),
         MachineMnemonic::Move =>                                         mov::x64_lower_move(sink, &instr),
         MachineMnemonic::Add =>                                          math::x64_lower_add(sink, &instr),
         MachineMnemonic::And =>                                          math::x64_lower_and(sink, &instr),
@@ -69,21 +67,47 @@ pub(crate) fn x64_lower_instr(conv: CallConv, sink: &mut Vec<X64MCInstr>, instr:
         MachineMnemonic::Xor =>                                          math::x64_lower_xor(sink, &instr),
         MachineMnemonic::Shl =>                                          math::x64_lower_shl(sink, &instr),
         MachineMnemonic::Shr =>                                          math::x64_lower_shr(sink, &instr),
-        MachineMnemonic::Zext(_) =>                                      zext::x64_lower_zext(sink, &instr),
+        MachineMnemonic::Rotl =>                                         bit::x64_lower_rotate(sink, &instr, true),
+        MachineMnemonic::Rotr =>                                         bit::x64_lower_rotate(sink, &instr, false),
+        MachineMnemonic::Popcnt | MachineMnemonic::Clz | 
+        MachineMnemonic::Ctz | MachineMnemonic::Bswap |
+        MachineMnemonic::BitOpFallback(_) =>                             bit::x64_lower_bitop(sink, &instr),
+        MachineMnemonic::Overflow(kind) =>                               overflow::x64_lower_overflow(sink, &instr, kind),
+        MachineMnemonic::FloatOp(kind) =>                                float::x64_lower_floatop(sink, &instr, kind),
+        MachineMnemonic::Zext(from) =>                                   zext::x64_lower_zext(sink, &instr, *from, false),
+        MachineMnemonic::Sext(from) =>                                   zext::x64_lower_zext(sink, &instr, *from, true),
+        MachineMnemonic::Bitcast(from) =>                                bitcast::x64_lower_bitcast(sink, &instr, *from),
         MachineMnemonic::Downcast(_) =>                                  downcast::x64_lower_downcast(sink, &instr),
         MachineMnemonic::Call(to) =>                            call::x64_lower_call(conv, sink, &instr, to),
+        MachineMnemonic::CallIndirect(_) =>                              call::x64_lower_call_indirect(conv, sink, &instr),
+        MachineMnemonic::TailCall(to) =>                                 call::x64_lower_tail_call(sink, to),
         MachineMnemonic::Return =>                                       ret::x64_lower_return(sink, &instr),
+        MachineMnemonic::Unreachable =>                                  {},
+        MachineMnemonic::Trap =>                                         ret::x64_lower_trap(sink, &instr),
+        MachineMnemonic::MemCpy =>                                       mem::x64_lower_memcpy(sink, &instr),
+        MachineMnemonic::MemMove =>                                      mem::x64_lower_memmove(sink, &instr),
+        MachineMnemonic::MemSet =>                                       mem::x64_lower_memset(sink, &instr),
+        MachineMnemonic::AtomicLoad(_) =>                                atomic::x64_lower_atomic_load(sink, &instr),
+        MachineMnemonic::AtomicStore(order) =>                 atomic::x64_lower_atomic_store(sink, &instr, *order),
+        MachineMnemonic::AtomicRmw(op, _, label) =>               atomic::x64_lower_atomic_rmw(sink, &instr, *op, label),
+        MachineMnemonic::CmpXchg(_) =>                                   atomic::x64_lower_cmpxchg(sink, &instr),
+        MachineMnemonic::Fence(order) =>                       atomic::x64_lower_fence(sink, *order),
+        MachineMnemonic::VaSave =>                                       va::x64_lower_va_save(conv, sink, &instr),
+        MachineMnemonic::VaStart =>                                      va::x64_lower_va_start(conv, sink, &instr),
+        MachineMnemonic::VaArg(stack, load) =>                           va::x64_lower_va_arg(conv, sink, &instr, stack, load),
+        MachineMnemonic::VaCopy =>                                       va::x64_lower_va_copy(conv, sink, &instr),
+        MachineMnemonic::InlineAsm(asm) =>                               asm::x64_lower_asm(sink, &instr, asm),
         MachineMnemonic::AdressLoad(to) =>                      adr::x64_lower_adr_load(sink, &instr, to),
         MachineMnemonic::Br(to) =>                              br::x64_lower_br(sink, &instr, to),
+        MachineMnemonic::Label(label) =>                        sink.push(X64MCInstr::with1(Mnemonic::Label, Operand::Debug(label.to_owned()))),
         MachineMnemonic::BrCond(iftrue, iffalse) =>    br::x64_lower_cond_br(sink, &instr, iftrue, iffalse),
         MachineMnemonic::Compare(mode) =>                      cmp::x64_lower_cmp(sink, &instr, mode),
         MachineMnemonic::Prolog =>                                       prolog::x64_lower_prolog(sink, &instr),
         MachineMnemonic::Epilog =>                                       prolog::x64_lower_epilog(sink, &instr),
         MachineMnemonic::StackAlloc =>                                   stack::x64_lower_salloc(sink, &instr),
-        MachineMnemonic::Store =>                                        stack::x64_lower_store(sink, &instr),
-        MachineMnemonic::Load =>                                         stack::x64_lower_load(sink, &instr),
+        MachineMnemonic::Store(align) =>                                 stack::x64_lower_store(sink, &instr, *align),
+        MachineMnemonic::Load(align) =>                                  stack::x64_lower_load(sink, &instr, *align),
         MachineMnemonic::Push =>                                         push::x64_lower_push(sink, &instr),
-        MachineMnemonic::PushCleanup =>                                  push::x64_lower_push_cleanup(sink, &instr),
         MachineMnemonic::AdrMove =>                                      adr::x64_lower_adrm(sink, &instr),
         MachineMnemonic::Switch(cases) =>         switch::x64_lower_switch(sink, &instr, cases),
         MachineMnemonic::Neg =>                                          math::x64_lower_neg(sink, &instr),
@@ -95,7 +119,7 @@ pub(crate) fn x64_lower_instr(conv: CallConv, sink: &mut Vec<X64MCInstr>, instr:
         MachineMnemonic::FMul =>                                         fmath::x64_lower_fmul(sink, &instr),
         MachineMnemonic::FSub =>                                         fmath::x64_lower_fsub(sink, &instr),
         MachineMnemonic::FCompare(mode) =>                     fcmp::x64_lower_fcmp(sink, &instr, mode),
-        MachineMnemonic::FCast(input_type) =>             fcast::X64_lower_fcast(sink, &instr, *input_type),
+        MachineMnemonic::FCast(kind, from) =>             fcast::X64_lower_fcast(sink, &instr, *kind, *from),
         _ => todo!("TDOD: {}", instr.mnemonic),
     }
 }
@@ -127,6 +151,7 @@ impl From<MachineOperand> for Operand {
         match value {
             MachineOperand::Stack(stack, _) => x64_stack!(stack as i32),
             MachineOperand::Imm(imm) => Operand::Imm(imm as i64),
+            MachineOperand::Int(int) => Operand::Imm(int as i64),
             MachineOperand::Reg(reg) => match reg {
                 crate::CodeGen::Reg::x64(x64_reg) => Operand::Reg(x64_reg),
                 _ => panic!("the x64 backend expects the register to be x64 registers and not any other")
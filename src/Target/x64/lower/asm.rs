@@ -0,0 +1,72 @@
+use crate::CodeGen::{MachineInstr, MachineOperand, Reg};
+use crate::Target::x64::{x64Lexer, x64Parser, Token};
+use crate::Target::x64::asm::instr::*;
+use crate::Target::Lexer;
+
+/// Replaces the `$0`, `$1`, ... in the assembly with the operands
+fn x64_asm_substitute(asm: &str, operands: &[String]) -> String {
+    let mut out = String::new();
+    let mut chars = asm.chars().peekable();
+
+    while let Some(chr) = chars.next() {
+        if chr != '$' {
+            out.push(chr);
+            continue;
+        }
+
+        let mut index = String::new();
+
+        while let Some(digit) = chars.peek().filter(|chr| chr.is_ascii_digit()) {
+            index.push(*digit);
+            chars.next();
+        }
+
+        let operand = index.parse::<usize>().ok().and_then(|index| operands.get(index));
+
+        out.push_str(operand.unwrap_or_else(|| panic!("invalid operand in inline assembly: ${}", index)));
+    }
+
+    out
+}
+
+pub(crate) fn x64_lower_asm(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, asm: &str) {
+    let mut operands = Vec::new();
+
+    for operand in &instr.operands {
+        operands.push(match operand {
+            MachineOperand::Reg(Reg::x64(reg)) => reg.to_string(),
+            MachineOperand::Imm(imm) => (*imm as i64).to_string(),
+            _ => unreachable!("the operands of inline assembly are registers or immediates"),
+        });
+    }
+
+    // the optimizer isn't allowed to change the assembly
+    sink.push(X64MCInstr::with0(Mnemonic::EndOptimization));
+
+    for line in asm.split(['\n', ';']) {
+        let line = x64_asm_substitute(line, &operands);
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let tokens = match (x64Lexer {}).lex(line.to_owned()) {
+            Ok(tokens) => tokens,
+            Err(err) => panic!("invalid inline assembly '{}': {}", line.trim(), err),
+        };
+
+        let tokens = tokens.into_iter()
+            .map(|token| *token.downcast::<Token>().expect("the x64 lexer returns x64 tokens"))
+            .collect();
+
+        let mut parser = x64Parser::new(tokens);
+
+        if let Err(err) = parser.parse() {
+            panic!("invalid inline assembly '{}': {}", line.trim(), err);
+        }
+
+        sink.push(parser.out.expect("the parser sets the instruction"));
+    }
+
+    sink.push(X64MCInstr::with0(Mnemonic::StartOptimization));
+}
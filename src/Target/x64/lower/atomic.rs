@@ -0,0 +1,142 @@
+use crate::CodeGen::MachineInstr;
+use crate::Target::x64::X64Reg;
+use crate::Target::x64::asm::instr::*;
+use crate::IR::ir::{AtomicOrdering, AtomicRmwOp};
+
+use super::mem::{x64_mem_at, x64_mem_base};
+
+// x64 has a strong memory model: plain loads already have acquire and plain stores
+// release semantics. So only seq_cst stores and fences need extra instructions.
+// The atomics use rax for the data and r11 for pointers which are stored on the stack
+
+/// Borrows a register which isn't used by the operands (it is saved in xmm15)
+fn x64_atomic_borrow(sink: &mut Vec<X64MCInstr>, used: &[&Operand]) -> X64Reg {
+    let is_used = |reg: X64Reg| used.iter().any(|op| match op {
+        Operand::Reg(used) => used.sub64() == reg,
+        Operand::Mem(mem) => mem.base.map(|base| base.sub64()) == Some(reg) || mem.index.map(|index| index.sub64()) == Some(reg),
+        _ => false,
+    });
+
+    let reg = [X64Reg::Rcx, X64Reg::Rdx, X64Reg::Rsi, X64Reg::Rdi].into_iter()
+        .find(|reg| !is_used(*reg))
+        .expect("expected a free register");
+
+    sink.push(X64MCInstr::with2(Mnemonic::Movq, Operand::Reg(X64Reg::Xmm15), Operand::Reg(reg)));
+
+    reg
+}
+
+/// Restores the register borrowed by `x64_atomic_borrow`
+fn x64_atomic_restore(sink: &mut Vec<X64MCInstr>, reg: X64Reg) {
+    sink.push(X64MCInstr::with2(Mnemonic::Movq, Operand::Reg(reg), Operand::Reg(X64Reg::Xmm15)));
+}
+
+pub(crate) fn x64_lower_atomic_load(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+    let ptr: Operand = (*instr.operands.get(0).expect("atomic loads expect a pointer")).into();
+    let out: Operand = instr.out.expect("atomic loads need an output").into();
+
+    let rax = Operand::Reg(X64Reg::Rax.sub_ty(instr.meta));
+
+    let base = x64_mem_base(sink, ptr);
+
+    sink.extend_from_slice(&[
+        X64MCInstr::with2(Mnemonic::Mov, rax.clone(), x64_mem_at(base, 0)),
+        X64MCInstr::with2(Mnemonic::Mov, out, rax),
+    ]);
+}
+
+pub(crate) fn x64_lower_atomic_store(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, order: AtomicOrdering) {
+    let ptr: Operand = (*instr.operands.get(0).expect("atomic stores expect a pointer")).into();
+    let val: Operand = (*instr.operands.get(1).expect("atomic stores expect a value")).into();
+
+    let rax = Operand::Reg(X64Reg::Rax.sub_ty(instr.meta));
+
+    let base = x64_mem_base(sink, ptr);
+
+    sink.push(X64MCInstr::with2(Mnemonic::Mov, rax.clone(), val));
+
+    // xchg has an implicit lock prefix which makes the store sequentially consistent
+    sink.push(X64MCInstr::with2(if order == AtomicOrdering::SeqCst { Mnemonic::Xchg } else { Mnemonic::Mov }, x64_mem_at(base, 0), rax));
+}
+
+pub(crate) fn x64_lower_atomic_rmw(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, op: AtomicRmwOp, label: &str) {
+    let ptr: Operand = (*instr.operands.get(0).expect("atomicrmw expects a pointer")).into();
+    let val: Operand = (*instr.operands.get(1).expect("atomicrmw expects a value")).into();
+    let out: Operand = instr.out.expect("atomicrmw needs an output").into();
+
+    let rax = Operand::Reg(X64Reg::Rax.sub_ty(instr.meta));
+
+    let base = x64_mem_base(sink, ptr);
+    let mem = x64_mem_at(base, 0);
+
+    match op {
+        AtomicRmwOp::Add => sink.extend_from_slice(&[
+            X64MCInstr::with2(Mnemonic::Mov, rax.clone(), val),
+            X64MCInstr::with2(Mnemonic::Xadd, mem, rax.clone()).make_lock(),
+        ]),
+        AtomicRmwOp::Xchg => sink.extend_from_slice(&[
+            X64MCInstr::with2(Mnemonic::Mov, rax.clone(), val),
+            X64MCInstr::with2(Mnemonic::Xchg, mem, rax.clone()),
+        ]),
+        AtomicRmwOp::And | AtomicRmwOp::Or => {
+            // x64 has no instruction which returns the old value, so it's done in a cmpxchg loop:
+            //      mov rax, [ptr]
+            // loop:
+            //      mov tmp, rax
+            //      and tmp, val
+            //      lock cmpxchg [ptr], tmp
+            //      jne loop
+            let tmp = x64_atomic_borrow(sink, &[&Operand::Reg(base), &val]);
+            let tmp_op = Operand::Reg(tmp.sub_ty(instr.meta));
+
+            sink.extend_from_slice(&[
+                X64MCInstr::with2(Mnemonic::Mov, rax.clone(), mem.clone()),
+                X64MCInstr::with1(Mnemonic::Label, Operand::Debug(label.to_owned())),
+                X64MCInstr::with2(Mnemonic::Mov, tmp_op.clone(), rax.clone()),
+                X64MCInstr::with2(if op == AtomicRmwOp::And { Mnemonic::And } else { Mnemonic::Or }, tmp_op.clone(), val),
+                X64MCInstr::with2(Mnemonic::Cmpxchg, mem, tmp_op).make_lock(),
+                X64MCInstr::with1(Mnemonic::Jne, Operand::BlockLinkDestination(label.to_owned(), -4)),
+            ]);
+
+            x64_atomic_restore(sink, tmp);
+        },
+    }
+
+    sink.push(X64MCInstr::with2(Mnemonic::Mov, out, rax));
+}
+
+pub(crate) fn x64_lower_cmpxchg(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+    let ptr: Operand = (*instr.operands.get(0).expect("cmpxchg expects a pointer")).into();
+    let cmp: Operand = (*instr.operands.get(1).expect("cmpxchg expects a value to compare with")).into();
+    let new: Operand = (*instr.operands.get(2).expect("cmpxchg expects a new value")).into();
+    let out: Operand = instr.out.expect("cmpxchg needs an output").into();
+
+    let rax = Operand::Reg(X64Reg::Rax.sub_ty(instr.meta));
+
+    let base = x64_mem_base(sink, ptr);
+
+    // cmpxchg compares with rax and loads the old value into it
+    sink.push(X64MCInstr::with2(Mnemonic::Mov, rax.clone(), cmp));
+
+    if let Operand::Reg(_) = new {
+        sink.push(X64MCInstr::with2(Mnemonic::Cmpxchg, x64_mem_at(base, 0), new).make_lock());
+    } else {
+        let tmp = x64_atomic_borrow(sink, &[&Operand::Reg(base), &new]);
+
+        sink.extend_from_slice(&[
+            X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(tmp.sub_ty(instr.meta)), new),
+            X64MCInstr::with2(Mnemonic::Cmpxchg, x64_mem_at(base, 0), Operand::Reg(tmp.sub_ty(instr.meta))).make_lock(),
+        ]);
+
+        x64_atomic_restore(sink, tmp);
+    }
+
+    sink.push(X64MCInstr::with2(Mnemonic::Mov, out, rax));
+}
+
+pub(crate) fn x64_lower_fence(sink: &mut Vec<X64MCInstr>, order: AtomicOrdering) {
+    // acquire and release fences are already guaranteed by the memory model
+    if order == AtomicOrdering::SeqCst {
+        sink.push(X64MCInstr::with0(Mnemonic::Mfence));
+    }
+}
@@ -0,0 +1,161 @@
+use crate::CodeGen::{MachineInstr, MachineMnemonic};
+use crate::IR::ir::BitOpKind;
+use crate::Target::x64::X64Reg;
+use crate::Target::x64::asm::instr::*;
+
+use super::zext::x64_extend_rax;
+
+// The value is zero extended into eax (or rax for 64 bit values) and manipulated there.
+// popcnt, lzcnt and tzcnt are only used if the target has their extension (popcnt, lzcnt, bmi),
+// else clz/ctz use bsr/bsf (and select the bit size through r11 if the value is zero)
+// and popcnt is computed in software
+
+/// Lowers popcnt, clz, ctz and bswap
+pub(crate) fn x64_lower_bitop(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+    let op = instr.operands.get(0).expect("expected an operand for the bit manipulation");
+    let out: Operand = instr.out.expect("expected an output for the bit manipulation").into();
+
+    let bits = instr.meta.bitSize() as i64;
+    let wide = bits > 32;
+
+    let rax = x64_extend_rax(sink, op, instr.meta, false, wide);
+    let r11 = if wide { X64Reg::R11 } else { X64Reg::R11d };
+
+    // the size of the register in which the value is manipulated
+    let size = if wide { 64 } else { 32 };
+
+    match &instr.mnemonic {
+        MachineMnemonic::Popcnt => sink.push(X64MCInstr::with2(Mnemonic::Popcnt, Operand::Reg(rax), Operand::Reg(rax))),
+        MachineMnemonic::Clz => {
+            // lzcnt returns the size for zero
+            sink.push(X64MCInstr::with2(Mnemonic::Lzcnt, Operand::Reg(rax), Operand::Reg(rax)));
+
+            // the value was zero extended
+            if bits < size {
+                sink.push(X64MCInstr::with2(Mnemonic::Sub, Operand::Reg(rax), Operand::Imm(size - bits)));
+            }
+        },
+        MachineMnemonic::Ctz => {
+            // the value was zero extended, so the bit above it is set which makes tzcnt return the bit size for zero
+            if bits < size {
+                sink.push(X64MCInstr::with2(Mnemonic::Or, Operand::Reg(rax), Operand::Imm(1 << bits)));
+            }
+
+            sink.push(X64MCInstr::with2(Mnemonic::Tzcnt, Operand::Reg(rax), Operand::Reg(rax)));
+        },
+        MachineMnemonic::BitOpFallback(BitOpKind::Popcnt) => x64_soft_popcnt(sink, wide),
+        MachineMnemonic::BitOpFallback(BitOpKind::Clz) => {
+            // bsr returns the index of the highest set bit, so: clz = (size - 1) ^ index 
+            // for zero 2 * size - 1 is used which gives size
+            sink.extend_from_slice(&[
+                X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(r11), Operand::Imm(2 * size - 1)),
+                X64MCInstr::with2(Mnemonic::Bsr, Operand::Reg(rax), Operand::Reg(rax)),
+                X64MCInstr::with2(Mnemonic::Cmove, Operand::Reg(rax), Operand::Reg(r11)),
+                X64MCInstr::with2(Mnemonic::Xor, Operand::Reg(rax), Operand::Imm(size - 1)),
+            ]);
+
+            // the value was zero extended
+            if bits < size {
+                sink.push(X64MCInstr::with2(Mnemonic::Sub, Operand::Reg(rax), Operand::Imm(size - bits)));
+            }
+        },
+        MachineMnemonic::BitOpFallback(BitOpKind::Ctz) => sink.extend_from_slice(&[
+            X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(r11), Operand::Imm(bits)),
+            X64MCInstr::with2(Mnemonic::Bsf, Operand::Reg(rax), Operand::Reg(rax)),
+            X64MCInstr::with2(Mnemonic::Cmove, Operand::Reg(rax), Operand::Reg(r11)),
+        ]),
+        MachineMnemonic::Bswap => sink.push(if bits == 16 {
+            X64MCInstr::with2(Mnemonic::Rol, Operand::Reg(X64Reg::Ax), Operand::Imm(8))
+        } else {
+            X64MCInstr::with1(Mnemonic::Bswap, Operand::Reg(rax))
+        }),
+        mnemonic => panic!("{} isn't a bit manipulation", mnemonic),
+    }
+
+    sink.push(X64MCInstr::with2(Mnemonic::Mov, out, Operand::Reg(X64Reg::Rax.sub_ty(instr.meta))));
+}
+
+/// Counts the set bits of eax (or rax) without popcnt by summing up the bits in
+/// groups of 2, 4 and 8 bits (rcx is saved onto the stack and holds the masks)
+fn x64_soft_popcnt(sink: &mut Vec<X64MCInstr>, wide: bool) {
+    let (rax, r11, rcx) = if wide { 
+        (X64Reg::Rax, X64Reg::R11, X64Reg::Rcx) 
+    } else { 
+        (X64Reg::Eax, X64Reg::R11d, X64Reg::Ecx) 
+    };
+
+    // repeats the byte over the whole register
+    let mask = |byte: u8| if wide { u64::from_ne_bytes([byte; 8]) as i64 } else { u32::from_ne_bytes([byte; 4]) as i64 };
+
+    let size = if wide { 64 } else { 32 };
+
+    sink.extend_from_slice(&[
+        X64MCInstr::with1(Mnemonic::Push, Operand::Reg(X64Reg::Rcx)),
+
+        // x - ((x >> 1) & 0x55..)
+        X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(r11), Operand::Reg(rax)),
+        X64MCInstr::with2(Mnemonic::Shr, Operand::Reg(r11), Operand::Imm(1)),
+        X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(rcx), Operand::Imm(mask(0x55))),
+        X64MCInstr::with2(Mnemonic::And, Operand::Reg(r11), Operand::Reg(rcx)),
+        X64MCInstr::with2(Mnemonic::Sub, Operand::Reg(rax), Operand::Reg(r11)),
+
+        // (x & 0x33..) + ((x >> 2) & 0x33..)
+        X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(rcx), Operand::Imm(mask(0x33))),
+        X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(r11), Operand::Reg(rax)),
+        X64MCInstr::with2(Mnemonic::Shr, Operand::Reg(r11), Operand::Imm(2)),
+        X64MCInstr::with2(Mnemonic::And, Operand::Reg(r11), Operand::Reg(rcx)),
+        X64MCInstr::with2(Mnemonic::And, Operand::Reg(rax), Operand::Reg(rcx)),
+        X64MCInstr::with2(Mnemonic::Add, Operand::Reg(rax), Operand::Reg(r11)),
+
+        // (x + (x >> 4)) & 0x0f..
+        X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(r11), Operand::Reg(rax)),
+        X64MCInstr::with2(Mnemonic::Shr, Operand::Reg(r11), Operand::Imm(4)),
+        X64MCInstr::with2(Mnemonic::Add, Operand::Reg(rax), Operand::Reg(r11)),
+        X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(rcx), Operand::Imm(mask(0x0f))),
+        X64MCInstr::with2(Mnemonic::And, Operand::Reg(rax), Operand::Reg(rcx)),
+
+        // the multiplication sums up all bytes in the highest one
+        X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(rcx), Operand::Imm(mask(0x01))),
+        X64MCInstr::with2(Mnemonic::Imul, Operand::Reg(rax), Operand::Reg(rcx)),
+        X64MCInstr::with2(Mnemonic::Shr, Operand::Reg(rax), Operand::Imm(size - 8)),
+
+        X64MCInstr::with1(Mnemonic::Pop, Operand::Reg(X64Reg::Rcx)),
+    ]);
+}
+
+/// Lowers rotl and rotr
+pub(crate) fn x64_lower_rotate(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, left: bool) {
+    let out: Operand = instr.out.expect("expected output for valid rotate instruction").into();
+    let op1: Operand = (*instr.operands.get(0).expect("expected 2 operands for valid rotate instruction")).into();
+    let op2: Operand = (*instr.operands.get(1).expect("expected 2 operands for valid rotate instruction")).into();
+
+    let mne = if left { Mnemonic::Rol } else { Mnemonic::Ror };
+
+    let rax = Operand::Reg(X64Reg::Rax.sub_ty(instr.meta));
+
+    sink.push(X64MCInstr::with2(Mnemonic::Mov, rax.clone(), op1));
+
+    if let Operand::Imm(amount) = op2 {
+        sink.push(X64MCInstr::with2(mne, rax.clone(), Operand::Imm(amount % instr.meta.bitSize() as i64)));
+    } else {
+        // the amount needs to be in cl, so rcx is saved (if it isn't the output)
+        let save_rcx = out != Operand::Reg(X64Reg::Rcx.sub_ty(instr.meta));
+
+        sink.push(X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::R11.sub_ty(instr.meta)), op2));
+
+        if save_rcx {
+            sink.push(X64MCInstr::with1(Mnemonic::Push, Operand::Reg(X64Reg::Rcx)));
+        }
+
+        sink.extend_from_slice(&[
+            X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::Rcx), Operand::Reg(X64Reg::R11)),
+            X64MCInstr::with2(mne, rax.clone(), Operand::Reg(X64Reg::Cl)),
+        ]);
+
+        if save_rcx {
+            sink.push(X64MCInstr::with1(Mnemonic::Pop, Operand::Reg(X64Reg::Rcx)));
+        }
+    }
+
+    sink.push(X64MCInstr::with2(Mnemonic::Mov, out, rax));
+}
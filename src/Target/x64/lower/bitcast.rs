@@ -0,0 +1,51 @@
+use crate::CodeGen::{MachineInstr, MachineOperand};
+use crate::IR::TypeMetadata;
+use crate::Target::x64::X64Reg;
+use crate::Target::x64::asm::instr::*;
+
+// The bits are moved through rax (or xmm15 for vectors which always live on the stack)
+
+pub(crate) fn x64_lower_bitcast(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, from: TypeMetadata) {
+    let op = instr.operands.get(0).expect("bitcast expects an operand");
+    let out: Operand = instr.out.expect("bitcast expects an output").into();
+
+    if instr.meta.vector() {
+        sink.extend_from_slice(&[
+            X64MCInstr::with2(Mnemonic::Movups, Operand::Reg(X64Reg::Xmm15), (*op).into()),
+            X64MCInstr::with2(Mnemonic::Movups, out, Operand::Reg(X64Reg::Xmm15)),
+        ]);
+        return;
+    }
+
+    let bits = match instr.meta.byteSize() {
+        8 => TypeMetadata::i64,
+        4 => TypeMetadata::i32,
+        2 => TypeMetadata::i16,
+        _ => TypeMetadata::i8,
+    };
+
+    let rax = Operand::Reg(X64Reg::Rax.sub_ty(bits));
+
+    let movx = if bits == TypeMetadata::i64 { Mnemonic::Movq } else { Mnemonic::Movd };
+
+    match *op {
+        MachineOperand::Imm(imm) => {
+            let imm = match from {
+                TypeMetadata::f32 => (imm as f32).to_bits() as i64,
+                TypeMetadata::f64 => imm.to_bits() as i64,
+                _ => imm as i64,
+            };
+
+            sink.push(X64MCInstr::with2(Mnemonic::Mov, rax.clone(), Operand::Imm(imm)));
+        },
+        op => match op.into() {
+            Operand::Reg(reg) if reg.is_xmm() => sink.push(X64MCInstr::with2(movx, Operand::Reg(X64Reg::Rax.sub_ty(bits)), Operand::Reg(reg))),
+            op => sink.push(X64MCInstr::with2(Mnemonic::Mov, rax.clone(), op)),
+        },
+    }
+
+    match out {
+        Operand::Reg(reg) if reg.is_xmm() => sink.push(X64MCInstr::with2(movx, Operand::Reg(reg), rax)),
+        out => sink.push(X64MCInstr::with2(Mnemonic::Mov, out, rax)),
+    }
+}
@@ -1,13 +1,22 @@
 use crate::CodeGen::MachineInstr;
-use crate::Target::{x64::X64Reg, CallConv};
+use crate::Target::CallConv;
 use crate::Target::x64::asm::instr::*;
 
-pub(crate) fn x64_lower_call(conv: CallConv, sink: &mut Vec<X64MCInstr>, _: &MachineInstr, target: &String) {   
-    let func = target;
+// the vector register count for variadic system v calls (in al) is set while compiling the call
 
-    if conv == CallConv::SystemV{
-        sink.push( X64MCInstr::with2(Mnemonic::Xor, Operand::Reg(X64Reg::Eax), Operand::Reg(X64Reg::Eax)) );
-    }
+pub(crate) fn x64_lower_call(_: CallConv, sink: &mut Vec<X64MCInstr>, _: &MachineInstr, target: &String) {   
+    let func = target;
 
     sink.push( X64MCInstr::with1(Mnemonic::Call, Operand::LinkDestination(func.to_string(), -4)) );
 }
+
+pub(crate) fn x64_lower_tail_call(sink: &mut Vec<X64MCInstr>, target: &String) {
+    // the epilog was already emitted, so the called function returns to our caller
+    sink.push( X64MCInstr::with1(Mnemonic::Jmp, Operand::LinkDestination(target.to_string(), -4)) );
+}
+
+pub(crate) fn x64_lower_call_indirect(_: CallConv, sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+    let target = instr.operands.get(0).expect("expected the adress of the called function");
+
+    sink.push( X64MCInstr::with1(Mnemonic::Call, (*target).into()) );
+}
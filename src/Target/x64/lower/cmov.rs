@@ -1,4 +1,4 @@
-use crate::CodeGen::MachineInstr; 
+use crate::CodeGen::{MachineInstr, MachineOperand};
 use crate::Target::x64::instr::{Mnemonic, Operand, X64MCInstr};
 use crate::Target::x64::X64Reg;
 use crate::IR::TypeMetadata;
@@ -9,6 +9,7 @@ pub(crate) fn x64_lower_cmov_zero(sink: &mut Vec<X64MCInstr>, instr: &MachineIns
     }
 
     let cond = instr.operands.get(0).expect("expected condition for valid cmov");
+    let cond_ty = x64_cond_ty(cond);
     let cond = (*cond).into();
 
     let value = instr.operands.get(1).expect("expected value for valid cmov");
@@ -23,8 +24,8 @@ pub(crate) fn x64_lower_cmov_zero(sink: &mut Vec<X64MCInstr>, instr: &MachineIns
 
      if let Operand::Mem(_) = cond {
         sink.extend_from_slice(&[
-            X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::R11), Operand::Imm(1)),
-            X64MCInstr::with2(Mnemonic::Cmp, cond, Operand::Reg(X64Reg::R11))
+            X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::R11.sub_ty(cond_ty)), Operand::Imm(1)),
+            X64MCInstr::with2(Mnemonic::Cmp, cond, Operand::Reg(X64Reg::R11.sub_ty(cond_ty)))
             ]
         );
     } else {
@@ -59,6 +60,7 @@ pub(crate) fn x64_lower_cmov_not_zero(sink: &mut Vec<X64MCInstr>, instr: &Machin
     }
 
     let cond = instr.operands.get(0).expect("expected condition for valid cmov");
+    let cond_ty = x64_cond_ty(cond);
     let cond = (*cond).into();
 
     let value = instr.operands.get(1).expect("expected value for valid cmov");
@@ -73,8 +75,8 @@ pub(crate) fn x64_lower_cmov_not_zero(sink: &mut Vec<X64MCInstr>, instr: &Machin
 
      if let Operand::Mem(_) = cond {
         sink.extend_from_slice(&[
-            X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::R11), Operand::Imm(1)),
-            X64MCInstr::with2(Mnemonic::Cmp, cond, Operand::Reg(X64Reg::R11))
+            X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::R11.sub_ty(cond_ty)), Operand::Imm(1)),
+            X64MCInstr::with2(Mnemonic::Cmp, cond, Operand::Reg(X64Reg::R11.sub_ty(cond_ty)))
             ]
         );
     } else {
@@ -105,6 +107,7 @@ pub(crate) fn x64_lower_cmov_not_zero(sink: &mut Vec<X64MCInstr>, instr: &Machin
 
 pub(crate) fn x64_lower_fcmov0(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
     let cond = instr.operands.get(0).expect("expected condition for valid cmov");
+    let cond_ty = x64_cond_ty(cond);
     let cond: Operand = (*cond).into();
 
     let value = instr.operands.get(1).expect("expected value for valid cmov");
@@ -123,8 +126,8 @@ pub(crate) fn x64_lower_fcmov0(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr)
     };
 
     sink.extend_from_slice(&if let Operand::Mem(_) = cond {
-        vec![X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::R11), Operand::Imm(1)),
-             X64MCInstr::with2(Mnemonic::Cmp, cond, Operand::Reg(X64Reg::R11))]
+        vec![X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::R11.sub_ty(cond_ty)), Operand::Imm(1)),
+             X64MCInstr::with2(Mnemonic::Cmp, cond, Operand::Reg(X64Reg::R11.sub_ty(cond_ty)))]
     } else {
         vec![X64MCInstr::with2(Mnemonic::Cmp, cond, Operand::Imm(1))]
     });
@@ -148,6 +151,7 @@ pub(crate) fn x64_lower_fcmov0(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr)
 
 pub(crate) fn x64_lower_fcmovne0(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
     let cond = instr.operands.get(0).expect("expected condition for valid cmov");
+    let cond_ty = x64_cond_ty(cond);
     let cond: Operand = (*cond).into();
 
     let value = instr.operands.get(1).expect("expected value for valid cmov");
@@ -166,8 +170,8 @@ pub(crate) fn x64_lower_fcmovne0(sink: &mut Vec<X64MCInstr>, instr: &MachineInst
     };
 
     sink.extend_from_slice(&if let Operand::Mem(_) = cond {
-        vec![X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::R11), Operand::Imm(1)),
-             X64MCInstr::with2(Mnemonic::Cmp, cond, Operand::Reg(X64Reg::R11))]
+        vec![X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::R11.sub_ty(cond_ty)), Operand::Imm(1)),
+             X64MCInstr::with2(Mnemonic::Cmp, cond, Operand::Reg(X64Reg::R11.sub_ty(cond_ty)))]
     } else {
         vec![X64MCInstr::with2(Mnemonic::Cmp, cond, Operand::Imm(1))]
     });
@@ -187,4 +191,13 @@ pub(crate) fn x64_lower_fcmovne0(sink: &mut Vec<X64MCInstr>, instr: &MachineInst
             X64MCInstr::with2(Mnemonic::Mov, out, Operand::Reg(tmp)),
         ]);
     }
-} 
+}
+
+/// Returns the type of the condition 
+/// (conditions on the stack need to be compared with the right size, cuz booleans only write one byte)
+fn x64_cond_ty(cond: &MachineOperand) -> TypeMetadata {
+    match cond {
+        MachineOperand::Stack(_, ty) => *ty,
+        _ => TypeMetadata::i64,
+    }
+}
@@ -1,24 +1,35 @@
 use crate::prelude::CmpMode;
-use crate::CodeGen::MachineInstr;
+use crate::CodeGen::{MachineInstr, MachineOperand, Reg};
 use crate::Target::x64::X64Reg;
 use crate::Target::x64::asm::instr::*;
 
+use super::wide::x64_lower_wide_cmp;
+
 pub(crate) 
 fn x64_lower_cmp(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, mode: &CmpMode) {
+    if instr.meta.wide() {
+        return x64_lower_wide_cmp(sink, instr, mode.resolve(instr.meta));
+    }
+
     let ls = instr.operands.get(0).expect("expected valid src operand at 1. place");
     let rs = instr.operands.get(1).expect("expected valid value to compare at 2. place");
 
     let out = instr.out.expect("expected output");
+    let byte_out = x64_is_byte_operand(&out);
     let out = out.into();
 
     let mut ls = (*ls).into();
 
     let mut rs = (*rs).into();
 
+    let mut mode = mode.resolve(instr.meta);
+
     if let Operand::Imm(_) = ls {
         let tmp = ls;
         ls = rs;
         rs = tmp;
+
+        mode = mode.swapped();
     }
 
     if let Operand::Mem(_) = ls {
@@ -34,7 +45,9 @@ fn x64_lower_cmp(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, mode: &CmpMod
             sink.push(X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::Rax.sub_ty(instr.meta)), ls));
             sink.push(X64MCInstr::with2(Mnemonic::Cmp, Operand::Reg(X64Reg::Rax.sub_ty(instr.meta)), Operand::Reg(X64Reg::R11.sub_ty(instr.meta))));
         } else {
-            if let Operand::Reg(_) = out {
+            if byte_out {
+                // setcc already writes the entire output
+            } else if let Operand::Reg(_) = out {
                 sink.push(X64MCInstr::with2(Mnemonic::Mov, out.clone(), Operand::Imm(0)));
             } else {
                 sink.push(X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::Rax.sub_ty(instr.meta)), Operand::Imm(0)));
@@ -53,7 +66,9 @@ fn x64_lower_cmp(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, mode: &CmpMod
             sink.push(X64MCInstr::with2(Mnemonic::Mov, rs, Operand::Imm(0)));
             sink.push(X64MCInstr::with2(Mnemonic::Cmp, ls, Operand::Reg(X64Reg::Rax.sub_ty(instr.meta))));
         } else {
-            if let Operand::Reg(_) = out {
+            if byte_out {
+                // setcc already writes the entire output
+            } else if let Operand::Reg(_) = out {
                 sink.push(X64MCInstr::with2(Mnemonic::Mov, out.clone(), Operand::Imm(0)));
             } else {
                 sink.push(X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::Rax.sub_ty(instr.meta)), Operand::Imm(0)));
@@ -71,11 +86,25 @@ fn x64_lower_cmp(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, mode: &CmpMod
     let mne = match mode {
         CmpMode::Eqal => Mnemonic::Sete,
         CmpMode::NotEqal => Mnemonic::Setne,
-        CmpMode::GreaterThan => Mnemonic::Setg,
-        CmpMode::LessThan => Mnemonic::Setl,
-        CmpMode::GreaterThanOrEqual => Mnemonic::Setge,
-        CmpMode::LessThanOrEqual => Mnemonic::Setle,
+        CmpMode::SignedGreaterThan => Mnemonic::Setg,
+        CmpMode::SignedLessThan => Mnemonic::Setl,
+        CmpMode::SignedGreaterThanOrEqual => Mnemonic::Setge,
+        CmpMode::SignedLessThanOrEqual => Mnemonic::Setle,
+        CmpMode::UnsignedGreaterThan => Mnemonic::Seta,
+        CmpMode::UnsignedLessThan => Mnemonic::Setb,
+        CmpMode::UnsignedGreaterThanOrEqual => Mnemonic::Setae,
+        CmpMode::UnsignedLessThanOrEqual => Mnemonic::Setbe,
+        mode => panic!("the compare mode {} can't compare integers", mode),
     };
 
     sink.push( X64MCInstr::with1(mne, out) );
 }
+
+/// Returns if the operand is only one byte wide (like an `i1` produced by a compare)
+pub(crate) fn x64_is_byte_operand(op: &MachineOperand) -> bool {
+    match op {
+        MachineOperand::Reg(Reg::x64(reg)) => reg.is_gr8(),
+        MachineOperand::Stack(_, ty) => ty.byteSize() == 1,
+        _ => false,
+    }
+}
@@ -1,53 +1,142 @@
-use crate::{CodeGen::MachineInstr, Target::{x64::instr::*, x64::X64Reg}, IR::TypeMetadata};
+use crate::{CodeGen::{MachineInstr, MachineOperand}, Target::{x64::instr::*, x64::X64Reg}, IR::{ir::CastKind, TypeMetadata}};
 
-pub(crate) fn X64_lower_fcast(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, input_type: TypeMetadata) {
+use super::zext::x64_extend_rax;
+
+// Integers are converted through rax (and r11), float constants and
+// results which are written to the stack through xmm15 (and xmm14)
+
+/// Returns the float input as a sse operand (constants are loaded into xmm15)
+fn x64_fcast_input(sink: &mut Vec<X64MCInstr>, op: &MachineOperand, from: TypeMetadata) -> Operand {
+    if let MachineOperand::Imm(imm) = op {
+        let bits = if from == TypeMetadata::f32 { (*imm as f32).to_bits() as i64 } else { imm.to_bits() as i64 };
+
+        sink.extend_from_slice(&[
+            X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::Rax), Operand::Imm(bits)),
+            X64MCInstr::with2(Mnemonic::Movq, Operand::Reg(X64Reg::Xmm15), Operand::Reg(X64Reg::Rax)),
+        ]);
+
+        return Operand::Reg(X64Reg::Xmm15);
+    }
+
+    (*op).into()
+}
+
+/// Loads the float constant `value` of the type `ty` into `reg` (using r11)
+fn x64_fcast_const(sink: &mut Vec<X64MCInstr>, reg: X64Reg, value: f64, ty: TypeMetadata) {
+    let bits = if ty == TypeMetadata::f32 { (value as f32).to_bits() as i64 } else { value.to_bits() as i64 };
+
+    sink.extend_from_slice(&[
+        X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::R11), Operand::Imm(bits)),
+        X64MCInstr::with2(Mnemonic::Movq, Operand::Reg(reg), Operand::Reg(X64Reg::R11)),
+    ]);
+}
+
+pub(crate) fn X64_lower_fcast(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, kind: CastKind, from: TypeMetadata) {
     let out = instr.out.expect("fcast expects output");
-    let out = out.into();
+    let out: Operand = out.into();
 
     let input = instr.operands.get(0).expect("fcast expects input operand");
-    let input = (*input).into();
 
-    let mut output_reg = X64Reg::Rax.sub_ty(instr.meta);
+    let f32_in = from == TypeMetadata::f32;
+    let f32_out = instr.meta == TypeMetadata::f32;
 
-    if let Operand::Reg(reg) = &out {
-        output_reg = *reg;
-    }
+    let output_reg = match &out {
+        Operand::Reg(reg) if instr.meta.float() => *reg,
+        _ => X64Reg::Xmm15,
+    };
+
+    match kind {
+        CastKind::FpExt => {
+            let input = x64_fcast_input(sink, input, from);
+            sink.push(X64MCInstr::with2(Mnemonic::Cvtss2sd, Operand::Reg(output_reg), input));
+        },
+        CastKind::FpTrunc => {
+            let input = x64_fcast_input(sink, input, from);
+            sink.push(X64MCInstr::with2(Mnemonic::Cvtsd2ss, Operand::Reg(output_reg), input));
+        },
+        CastKind::FpToSi | CastKind::FpToUi => {
+            let input = x64_fcast_input(sink, input, from);
+
+            let cvtt = if f32_in { Mnemonic::Cvttss2si } else { Mnemonic::Cvttsd2si };
+
+            if kind == CastKind::FpToUi && instr.meta.bitSize() == 64 {
+                // cvtt only converts into signed integers, so values >= 2^63 are converted
+                // after subtracting 2^63 and the sign bit of the first conversion selects:
+                //      cvtt rax, x
+                //      cvtt r11, x - 2^63
+                //      rax |= r11 & (rax >> 63)
+                let (mov, sub) = if f32_in { (Mnemonic::Movss, Mnemonic::Subss) } else { (Mnemonic::Movsd, Mnemonic::Subsd) };
 
-    if input_type == TypeMetadata::f32 {
-        match instr.meta {
-            TypeMetadata::i32 => sink.push(X64MCInstr::with2(Mnemonic::Cvtss2si, Operand::Reg(output_reg), input)),
-            TypeMetadata::i64 => sink.push(X64MCInstr::with2(Mnemonic::Cvtss2si, Operand::Reg(output_reg), input)),
-            
-            TypeMetadata::f32 => sink.push(X64MCInstr::with2(Mnemonic::Movss, Operand::Reg(output_reg), input)),
-            TypeMetadata::f64 => sink.push(X64MCInstr::with2(Mnemonic::Cvtss2sd, Operand::Reg(output_reg), input)),
-            _ => panic!("fcast can only cast from f32 to i32/i64/f32/f64")
-        }
-    } else if input_type == TypeMetadata::f64 {
-        match instr.meta {
-            TypeMetadata::i32 => sink.push(X64MCInstr::with2(Mnemonic::Cvtsd2si, Operand::Reg(output_reg), input)),
-            TypeMetadata::i64 => sink.push(X64MCInstr::with2(Mnemonic::Cvtsd2si, Operand::Reg(output_reg), input)),
-            
-            TypeMetadata::f32 => sink.push(X64MCInstr::with2(Mnemonic::Cvtsd2ss, Operand::Reg(output_reg), input)),
-            TypeMetadata::f64 => sink.push(X64MCInstr::with2(Mnemonic::Cvtsd2ss, Operand::Reg(output_reg), input)),
-            _ => panic!("fcast can only cast from f64 to i32/i64/f32/f64")
-        }
-    } else if input_type == TypeMetadata::i32 || input_type ==  TypeMetadata::i64 {
-        match instr.meta {
-            TypeMetadata::f32 => sink.push(X64MCInstr::with2(Mnemonic::Cvtsi2ss, Operand::Reg(output_reg), input)),
-            TypeMetadata::f64 => sink.push(X64MCInstr::with2(Mnemonic::Cvtsi2sd, Operand::Reg(output_reg), input)),
-            _ => panic!("fcast can only cast from i32 to f32/f64")
-        }
-    } else {
-        panic!("fcast expects the input type to be either f32/f64/i32/i64")
+                sink.extend_from_slice(&[
+                    X64MCInstr::with2(cvtt, Operand::Reg(X64Reg::Rax), input.clone()),
+                    X64MCInstr::with2(mov, Operand::Reg(X64Reg::Xmm14), input),
+                ]);
+
+                x64_fcast_const(sink, X64Reg::Xmm15, 9223372036854775808.0, from);
+
+                sink.extend_from_slice(&[
+                    X64MCInstr::with2(sub, Operand::Reg(X64Reg::Xmm14), Operand::Reg(X64Reg::Xmm15)),
+                    X64MCInstr::with2(cvtt, Operand::Reg(X64Reg::R11), Operand::Reg(X64Reg::Xmm14)),
+                    X64MCInstr::with2(Mnemonic::Movq, Operand::Reg(X64Reg::Xmm15), Operand::Reg(X64Reg::Rax)),
+                    X64MCInstr::with2(Mnemonic::Sar, Operand::Reg(X64Reg::Rax), Operand::Imm(63)),
+                    X64MCInstr::with2(Mnemonic::And, Operand::Reg(X64Reg::R11), Operand::Reg(X64Reg::Rax)),
+                    X64MCInstr::with2(Mnemonic::Movq, Operand::Reg(X64Reg::Rax), Operand::Reg(X64Reg::Xmm15)),
+                    X64MCInstr::with2(Mnemonic::Or, Operand::Reg(X64Reg::Rax), Operand::Reg(X64Reg::R11)),
+                ]);
+            } else {
+                // unsigned 32 bit values don't fit into a signed 32 bit integer
+                let dst = if instr.meta.bitSize() > 32 || (kind == CastKind::FpToUi && instr.meta.bitSize() == 32) { X64Reg::Rax } else { X64Reg::Eax };
+
+                sink.push(X64MCInstr::with2(cvtt, Operand::Reg(dst), input));
+            }
+
+            sink.push(X64MCInstr::with2(Mnemonic::Mov, out, Operand::Reg(X64Reg::Rax.sub_ty(instr.meta))));
+            return;
+        },
+        CastKind::SiToFp | CastKind::UiToFp => {
+            let cvt = if f32_out { Mnemonic::Cvtsi2ss } else { Mnemonic::Cvtsi2sd };
+
+            if kind == CastKind::UiToFp && from.bitSize() == 64 {
+                // the unsigned value is split into two halves which are converted exactly:
+                //      (double)(x >> 32) * 2^32 + (double)(x & 0xffffffff)
+                x64_extend_rax(sink, input, from, false, true);
+
+                // the constant is loaded first, so the asm optimizer doesn't merge
+                // the mov into rax with the following mov from rax
+                x64_fcast_const(sink, X64Reg::Xmm14, 4294967296.0, TypeMetadata::f64);
+
+                sink.extend_from_slice(&[
+                    X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::R11), Operand::Reg(X64Reg::Rax)),
+                    X64MCInstr::with2(Mnemonic::Shr, Operand::Reg(X64Reg::R11), Operand::Imm(32)),
+                    X64MCInstr::with2(Mnemonic::Cvtsi2sd, Operand::Reg(X64Reg::Xmm15), Operand::Reg(X64Reg::R11)),
+                    X64MCInstr::with2(Mnemonic::Mulsd, Operand::Reg(X64Reg::Xmm15), Operand::Reg(X64Reg::Xmm14)),
+                    X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::R11d), Operand::Reg(X64Reg::Eax)),
+                    X64MCInstr::with2(Mnemonic::Cvtsi2sd, Operand::Reg(X64Reg::Xmm14), Operand::Reg(X64Reg::R11)),
+                    X64MCInstr::with2(Mnemonic::Addsd, Operand::Reg(X64Reg::Xmm15), Operand::Reg(X64Reg::Xmm14)),
+                ]);
+
+                sink.push(if f32_out {
+                    X64MCInstr::with2(Mnemonic::Cvtsd2ss, Operand::Reg(output_reg), Operand::Reg(X64Reg::Xmm15))
+                } else {
+                    X64MCInstr::with2(Mnemonic::Movsd, Operand::Reg(output_reg), Operand::Reg(X64Reg::Xmm15))
+                });
+            } else {
+                // unsigned 32 bit values are converted as signed 64 bit ones
+                let wide = from.bitSize() > 32 || (kind == CastKind::UiToFp && from.bitSize() == 32);
+
+                let src = x64_extend_rax(sink, input, from, kind == CastKind::SiToFp, wide);
+
+                sink.push(X64MCInstr::with2(cvt, Operand::Reg(output_reg), Operand::Reg(src)));
+            }
+        },
+        _ => panic!("fcast can't lower the non float cast {}", kind),
     }
 
     if let Operand::Mem(out) = &out {
-        sink.push(if instr.meta == TypeMetadata::f32 {
+        sink.push(if f32_out {
             X64MCInstr::with2(Mnemonic::Movd, Operand::Mem(out.to_owned()), Operand::Reg(output_reg))
-        } else if instr.meta == TypeMetadata::f64 {
-            X64MCInstr::with2(Mnemonic::Movq, Operand::Mem(out.to_owned()), Operand::Reg(output_reg))
         } else {
-            X64MCInstr::with2(Mnemonic::Mov, Operand::Mem(out.to_owned()), Operand::Reg(output_reg))
+            X64MCInstr::with2(Mnemonic::Movq, Operand::Mem(out.to_owned()), Operand::Reg(output_reg))
         });
     }
-}
\ No newline at end of file
+}
@@ -1,36 +1,82 @@
 use crate::prelude::CmpMode;
 use crate::CodeGen::MachineInstr;
-use crate::Target::x64::instr::*;
+use crate::Target::x64::{instr::*, X64Reg};
 use crate::IR::TypeMetadata;
 
+// ucomis sets the flags like an unsigned compare (and all of zf, pf, cf if one operand is NaN), so:
+//      ogt/oge => seta/setae
+//      ult/ule => setb/setbe
+// the other relations swap the operands
+
 pub(crate) fn x64_lower_fcmp(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, mode: &CmpMode) {
     let ls = instr.operands.get(0).expect("expected valid src operand at 1. place");
     let rs = instr.operands.get(1).expect("expected valid value to compare at 2. place");
     let out = instr.out.expect("expected output");
+    let byte_out = super::cmp::x64_is_byte_operand(&out);
     let out: Operand = out.into();
-    let ls: Operand = (*ls).into();
-    let rs: Operand = (*rs).into();
+    let mut ls: Operand = (*ls).into();
+    let mut rs: Operand = (*rs).into();
+
+    let mode = mode.resolve(instr.meta);
 
-    sink.push(X64MCInstr::with2(Mnemonic::Mov, out.to_owned(), Operand::Imm(0)));
+    if !byte_out { // setcc already writes the entire output of booleans
+        sink.push(X64MCInstr::with2(Mnemonic::Mov, out.to_owned(), Operand::Imm(0)));
+    }
 
-    let mnemonic = if TypeMetadata::f32 == instr.meta {
-        Mnemonic::Ucomiss 
+    let (mnemonic, mov) = if TypeMetadata::f32 == instr.meta {
+        (Mnemonic::Ucomiss, Mnemonic::Movss)
     } else if TypeMetadata::f64 == instr.meta {
-        Mnemonic::Ucomisd
+        (Mnemonic::Ucomisd, Mnemonic::Movsd)
     } else {
         panic!("fcmp expects fp args");
     };
 
+    let swap = matches!(mode, 
+        CmpMode::OrderedLessThan | CmpMode::OrderedLessThanOrEqual |
+        CmpMode::UnorderedGreaterThan | CmpMode::UnorderedGreaterThanOrEqual
+    );
+
+    if swap {
+        std::mem::swap(&mut ls, &mut rs);
+    }
+
+    // ucomis needs the first operand in a register
+    if !matches!(ls, Operand::Reg(_)) {
+        sink.push(X64MCInstr::with2(mov, Operand::Reg(X64Reg::Xmm15), ls));
+        ls = Operand::Reg(X64Reg::Xmm15);
+    }
+
     sink.push(X64MCInstr::with2(mnemonic, ls, rs));
 
+    let out = match out {
+        Operand::Reg(reg) => Operand::Reg(reg.sub8()),
+        _ => out,
+    };
+
     let mne = match mode {
-        CmpMode::Eqal => Mnemonic::Sete,
-        CmpMode::NotEqal => Mnemonic::Setne,
-        CmpMode::GreaterThan => Mnemonic::Setg,
-        CmpMode::LessThan => Mnemonic::Setl,
-        CmpMode::GreaterThanOrEqual => Mnemonic::Setge,
-        CmpMode::LessThanOrEqual => Mnemonic::Setle,
+        CmpMode::OrderedEqal | CmpMode::UnorderedEqal => Mnemonic::Sete,
+        CmpMode::OrderedNotEqal | CmpMode::UnorderedNotEqal => Mnemonic::Setne,
+        CmpMode::OrderedGreaterThan | CmpMode::OrderedLessThan => Mnemonic::Seta,
+        CmpMode::OrderedGreaterThanOrEqual | CmpMode::OrderedLessThanOrEqual => Mnemonic::Setae,
+        CmpMode::UnorderedGreaterThan | CmpMode::UnorderedLessThan => Mnemonic::Setb,
+        CmpMode::UnorderedGreaterThanOrEqual | CmpMode::UnorderedLessThanOrEqual => Mnemonic::Setbe,
+        CmpMode::Ordered => Mnemonic::Setnp,
+        CmpMode::Unordered => Mnemonic::Setp,
+        mode => panic!("the compare mode {} can't compare floats", mode),
     };
 
-    sink.push( X64MCInstr::with1(mne, out) );
-}
\ No newline at end of file
+    sink.push( X64MCInstr::with1(mne, out.to_owned()) );
+
+    // zf is also set for NaNs, so the parity flag needs to be checked
+    match mode {
+        CmpMode::OrderedEqal => sink.extend_from_slice(&[
+            X64MCInstr::with1(Mnemonic::Setnp, Operand::Reg(X64Reg::R11b)),
+            X64MCInstr::with2(Mnemonic::And, out, Operand::Reg(X64Reg::R11b)),
+        ]),
+        CmpMode::UnorderedNotEqal => sink.extend_from_slice(&[
+            X64MCInstr::with1(Mnemonic::Setp, Operand::Reg(X64Reg::R11b)),
+            X64MCInstr::with2(Mnemonic::Or, out, Operand::Reg(X64Reg::R11b)),
+        ]),
+        _ => {},
+    }
+}
@@ -0,0 +1,141 @@
+use crate::prelude::FloatOpKind;
+use crate::CodeGen::MachineInstr;
+use crate::IR::TypeMetadata;
+use crate::Target::x64::X64Reg;
+use crate::Target::x64::asm::instr::*;
+
+// The result is computed in xmm15 (xmm14 holds the masks and second operands),
+// the bits of the values are moved through rax and r11.
+// floor, ceil, trunc and round only get here with SSE4.1 (else they are libm calls)
+
+/// Loads the `bits` into the xmm register (through r11)
+fn x64_float_mask(sink: &mut Vec<X64MCInstr>, reg: X64Reg, bits: u64, f32: bool) {
+    if f32 {
+        sink.extend_from_slice(&[
+            X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::R11d), Operand::Imm(bits as i64)),
+            X64MCInstr::with2(Mnemonic::Movd, Operand::Reg(reg), Operand::Reg(X64Reg::R11d)),
+        ]);
+    } else {
+        sink.extend_from_slice(&[
+            X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::R11), Operand::Imm(bits as i64)),
+            X64MCInstr::with2(Mnemonic::Movq, Operand::Reg(reg), Operand::Reg(X64Reg::R11)),
+        ]);
+    }
+}
+
+/// Lowers the float math functions
+pub(crate) fn x64_lower_floatop(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, kind: &FloatOpKind) {
+    let out: Operand = instr.out.expect("expected an output for the float operation").into();
+
+    let arg = |index: usize| -> Operand { (*instr.operands.get(index).expect("expected an operand for the float operation")).into() };
+
+    let f32 = instr.meta == TypeMetadata::f32;
+
+    let mov = if f32 { Mnemonic::Movss } else { Mnemonic::Movsd };
+    let movx = if f32 { Mnemonic::Movd } else { Mnemonic::Movq };
+
+    let sign_mask = if f32 { 1 << 31 } else { 1 << 63 };
+
+    let xmm15 = || Operand::Reg(X64Reg::Xmm15);
+    let xmm14 = || Operand::Reg(X64Reg::Xmm14);
+    let rax = || Operand::Reg(if f32 { X64Reg::Eax } else { X64Reg::Rax });
+    let r11 = || Operand::Reg(if f32 { X64Reg::R11d } else { X64Reg::R11 });
+
+    let result = match kind {
+        FloatOpKind::Sqrt => {
+            let sqrt = if f32 { Mnemonic::Sqrtss } else { Mnemonic::Sqrtsd };
+            sink.push(X64MCInstr::with2(sqrt, xmm15(), arg(0)));
+
+            xmm15()
+        },
+        FloatOpKind::Fabs => {
+            x64_float_mask(sink, X64Reg::Xmm14, !sign_mask & if f32 { u32::MAX as u64 } else { u64::MAX }, f32);
+
+            sink.extend_from_slice(&[
+                X64MCInstr::with2(mov, xmm15(), arg(0)),
+                X64MCInstr::with2(Mnemonic::Andpd, xmm15(), xmm14()),
+            ]);
+
+            xmm15()
+        },
+        FloatOpKind::Copysign => {
+            // (ls & !sign) | (rs & sign)
+            x64_float_mask(sink, X64Reg::Xmm14, sign_mask, f32);
+
+            sink.extend_from_slice(&[
+                X64MCInstr::with2(mov, xmm15(), arg(1)),
+                X64MCInstr::with2(Mnemonic::Andpd, xmm15(), xmm14()),
+                X64MCInstr::with2(movx, rax(), xmm15()),
+                X64MCInstr::with2(mov, xmm15(), arg(0)),
+                X64MCInstr::with2(Mnemonic::Andnpd, xmm14(), xmm15()),
+                X64MCInstr::with2(movx, xmm15(), rax()),
+                X64MCInstr::with2(Mnemonic::Orpd, xmm14(), xmm15()),
+            ]);
+
+            xmm14()
+        },
+        FloatOpKind::Floor | FloatOpKind::Ceil | FloatOpKind::Trunc => {
+            let round = if f32 { Mnemonic::Roundss } else { Mnemonic::Roundsd };
+
+            // the rounding mode (with the precision exception suppressed)
+            let mode = match kind {
+                FloatOpKind::Floor => 0b1001,
+                FloatOpKind::Ceil => 0b1010,
+                _ => 0b1011,
+            };
+
+            sink.push(X64MCInstr::with3(round, xmm15(), arg(0), Operand::Imm(mode)));
+
+            xmm15()
+        },
+        FloatOpKind::Round => {
+            // trunc(x + copysign(0.49999..., x)): the biggest value below 0.5 is used,
+            // so that the addition can't round up to the next intenger
+            let (add, round) = if f32 { (Mnemonic::Addss, Mnemonic::Roundss) } else { (Mnemonic::Addsd, Mnemonic::Roundsd) };
+            let half = if f32 { 0.49999997f32.to_bits() as i64 } else { 0.49999999999999994f64.to_bits() as i64 };
+            let sign_shift = if f32 { 31 } else { 63 };
+
+            sink.extend_from_slice(&[
+                X64MCInstr::with2(mov, xmm15(), arg(0)),
+                X64MCInstr::with2(movx, rax(), xmm15()),
+                X64MCInstr::with2(Mnemonic::Shr, rax(), Operand::Imm(sign_shift)),
+                X64MCInstr::with2(Mnemonic::Sal, rax(), Operand::Imm(sign_shift)),
+                X64MCInstr::with2(Mnemonic::Mov, r11(), Operand::Imm(half)),
+                X64MCInstr::with2(Mnemonic::Or, rax(), r11()),
+                X64MCInstr::with2(movx, xmm14(), rax()),
+                X64MCInstr::with2(add, xmm14(), xmm15()),
+                X64MCInstr::with3(round, xmm15(), xmm14(), Operand::Imm(0b1011)),
+            ]);
+
+            xmm15()
+        },
+        FloatOpKind::Minnum | FloatOpKind::Maxnum => {
+            // minsd/maxsd return the second operand if one is NaN, so a NaN
+            // left operand is replaced by the right one:
+            //      ls = isnan(ls) ? rs : ls
+            //      rs = min(rs, ls)
+            let (ucomi, math) = match (f32, kind) {
+                (true, FloatOpKind::Minnum) => (Mnemonic::Ucomiss, Mnemonic::Minss),
+                (true, _) => (Mnemonic::Ucomiss, Mnemonic::Maxss),
+                (false, FloatOpKind::Minnum) => (Mnemonic::Ucomisd, Mnemonic::Minsd),
+                (false, _) => (Mnemonic::Ucomisd, Mnemonic::Maxsd),
+            };
+
+            sink.extend_from_slice(&[
+                X64MCInstr::with2(mov, xmm15(), arg(0)),
+                X64MCInstr::with2(movx, rax(), xmm15()),
+                X64MCInstr::with2(mov, xmm14(), arg(1)),
+                X64MCInstr::with2(movx, r11(), xmm14()),
+                X64MCInstr::with2(ucomi, xmm15(), xmm15()),
+                X64MCInstr::with2(Mnemonic::Cmovp, rax(), r11()),
+                X64MCInstr::with2(movx, xmm15(), rax()),
+                X64MCInstr::with2(math, xmm14(), xmm15()),
+            ]);
+
+            xmm14()
+        },
+        FloatOpKind::Fma => panic!("fma is compiled into a call to libm"),
+    };
+
+    sink.push(X64MCInstr::with2(mov, out, result));
+}
@@ -30,6 +30,17 @@ pub(crate) fn x64_lower_fmove(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr)
     let op1: Operand = (*op1).into();
     let out: Operand = out.into();
 
+    if let (Operand::Mem(_), Operand::Mem(_)) = (&op1, &out) {
+        // there is no mov [mem], [mem] so we move it over xmm15
+        let mne = if TypeMetadata::f32 == instr.meta { Mnemonic::Movss } else { Mnemonic::Movsd };
+
+        sink.extend_from_slice(&[
+            X64MCInstr::with2(mne, Operand::Reg(X64Reg::Xmm15), op1),
+            X64MCInstr::with2(mne, out, Operand::Reg(X64Reg::Xmm15)),
+        ]);
+        return;
+    }
+
     let mut variant = FInstrVariant::Normal;
 
     if let Operand::Reg(op1) = op1 {
@@ -3,9 +3,20 @@ use crate::Target::x64::X64Reg;
 use crate::Target::x64::asm::instr::*;
 use crate::IR::TypeMetadata;
 
+use super::wide::{x64_lower_wide_math, x64_lower_wide_shl, x64_lower_wide_shr};
+use super::vector::x64_lower_vector_math;
+
 macro_rules! LowerSimpleMath {
-    ($func:ident, $mnemonic:expr) => {
+    ($func:ident, $mnemonic:expr, $wide_hi:expr) => {
         pub(crate) fn $func(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {       
+            if instr.meta.wide() {
+                return x64_lower_wide_math(sink, instr, $mnemonic, $wide_hi);
+            }
+
+            if instr.meta.vector() {
+                return x64_lower_vector_math(sink, instr);
+            }
+
             let op1 = instr.operands.get(0).expect("expected a first operand");
             let op2 = instr.operands.get(1).expect("expected a second operand");
             let out = instr.out.expect("expected a output operand");
@@ -23,13 +34,17 @@ macro_rules! LowerSimpleMath {
     };
 }
 
-LowerSimpleMath!(x64_lower_add, Mnemonic::Add);
-LowerSimpleMath!(x64_lower_and, Mnemonic::And);
-LowerSimpleMath!(x64_lower_or, Mnemonic::Or);
-LowerSimpleMath!(x64_lower_sub, Mnemonic::Sub);
-LowerSimpleMath!(x64_lower_xor, Mnemonic::Xor);
+LowerSimpleMath!(x64_lower_add, Mnemonic::Add, Mnemonic::Adc);
+LowerSimpleMath!(x64_lower_and, Mnemonic::And, Mnemonic::And);
+LowerSimpleMath!(x64_lower_or, Mnemonic::Or, Mnemonic::Or);
+LowerSimpleMath!(x64_lower_sub, Mnemonic::Sub, Mnemonic::Sbb);
+LowerSimpleMath!(x64_lower_xor, Mnemonic::Xor, Mnemonic::Xor);
 
 pub(crate) fn x64_lower_mul(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+    if instr.meta.vector() {
+        return x64_lower_vector_math(sink, instr);
+    }
+
     let op1 = instr.operands.get(0).expect("expected a first operand");
     let op2 = instr.operands.get(1).expect("expected a second operand");
     let out = instr.out.expect("expected a output operand");
@@ -110,6 +125,10 @@ pub(crate) fn x64_lower_neg(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
 }
 
 pub(crate) fn x64_lower_div(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+    if instr.meta.vector() {
+        return x64_lower_vector_math(sink, instr);
+    }
+
     // core logic:
 
     // save rdx
@@ -257,6 +276,10 @@ pub(crate) fn x64_lower_rem(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
 }
 
 pub(crate) fn x64_lower_shl(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+    if instr.meta.wide() {
+        return x64_lower_wide_shl(sink, instr);
+    }
+
     let out = instr.out.expect("expected output for valid shl instruction");
     let op1 = instr.operands.get(0).expect("expected 2 operands for valid shl instruction");
     let op2 = instr.operands.get(1).expect("expected 2 operands for valid shl instruction");
@@ -298,6 +321,10 @@ pub(crate) fn x64_lower_shl(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
 }
 
 pub(crate) fn x64_lower_shr(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+    if instr.meta.wide() {
+        return x64_lower_wide_shr(sink, instr);
+    }
+
     let out = instr.out.expect("expected output for valid shl instruction");
     let op1 = instr.operands.get(0).expect("expected 2 operands for valid shl instruction");
     let op2 = instr.operands.get(1).expect("expected 2 operands for valid shl instruction");
@@ -0,0 +1,113 @@
+use crate::CodeGen::{MachineInstr, MachineOperand};
+use crate::Target::x64::X64Reg;
+use crate::Target::x64::asm::instr::*;
+use crate::IR::TypeMetadata;
+
+// the memory intrinsics only reach the lowering with constant sizes
+// (the others are compiled into libc calls). They use rax for the data
+// and r11 for pointers which are stored on the stack
+
+/// Splits `size` bytes into the offsets and types of the moves which copy them
+fn x64_mem_chunks(size: usize) -> Vec<(usize, TypeMetadata)> {
+    let mut chunks = Vec::new();
+    let mut off = 0;
+
+    while off < size {
+        let ty = match size - off {
+            8.. => TypeMetadata::i64,
+            4..=7 => TypeMetadata::i32,
+            2..=3 => TypeMetadata::i16,
+            _ => TypeMetadata::i8,
+        };
+
+        chunks.push((off, ty));
+
+        off += ty.byteSize();
+    }
+
+    chunks
+}
+
+/// Returns the register which holds the pointer (pointers on the stack are loaded into r11)
+pub(crate) fn x64_mem_base(sink: &mut Vec<X64MCInstr>, ptr: Operand) -> X64Reg {
+    match ptr {
+        Operand::Reg(reg) => reg,
+        _ => {
+            sink.push(X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::R11), ptr));
+            X64Reg::R11
+        }
+    }
+}
+
+pub(crate) fn x64_mem_at(base: X64Reg, off: usize) -> Operand {
+    Operand::Mem(MemOp {
+        base: Some(base),
+        index: None,
+        scale: 1,
+        displ: off as isize,
+        rip: false,
+    })
+}
+
+fn x64_mem_size(instr: &MachineInstr) -> usize {
+    match instr.operands.get(2) {
+        Some(MachineOperand::Imm(size)) => *size as usize,
+        _ => panic!("memory intrinsics need a constant size in order to be expanded inline"),
+    }
+}
+
+pub(crate) fn x64_lower_memcpy(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+    let dst: Operand = (*instr.operands.get(0).expect("memcpy expects a destination")).into();
+    let src: Operand = (*instr.operands.get(1).expect("memcpy expects a source")).into();
+
+    let size = x64_mem_size(instr);
+
+    // if only one pointer is on the stack it can stay in r11 for all moves
+    let (dst, src) = match (&dst, &src) {
+        (Operand::Reg(_), Operand::Reg(_)) => (dst, src),
+        (Operand::Reg(_), _) => (dst, Operand::Reg(x64_mem_base(sink, src))),
+        (_, Operand::Reg(_)) => (Operand::Reg(x64_mem_base(sink, dst)), src),
+        _ => (dst, src),
+    };
+
+    for (off, ty) in x64_mem_chunks(size) {
+        let from = x64_mem_base(sink, src.clone());
+        sink.push(X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::Rax.sub_ty(ty)), x64_mem_at(from, off)));
+
+        let to = x64_mem_base(sink, dst.clone());
+        sink.push(X64MCInstr::with2(Mnemonic::Mov, x64_mem_at(to, off), Operand::Reg(X64Reg::Rax.sub_ty(ty))));
+    }
+}
+
+/// memmove only gets expanded inline if it is done with a single load and store
+/// which is overlap safe, so it's lowered like memcpy
+pub(crate) fn x64_lower_memmove(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+    x64_lower_memcpy(sink, instr);
+}
+
+pub(crate) fn x64_lower_memset(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+    let dst: Operand = (*instr.operands.get(0).expect("memset expects a destination")).into();
+    let val = *instr.operands.get(1).expect("memset expects a value");
+
+    let size = x64_mem_size(instr);
+
+    // fill rax with the byte
+    if let MachineOperand::Imm(val) = val {
+        let pattern = i64::from_ne_bytes([val as u8; 8]);
+
+        sink.push(X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::Rax), Operand::Imm(pattern)));
+    } else {
+        sink.extend_from_slice(&[
+            X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::Al), val.into()),
+            X64MCInstr::with2(Mnemonic::Movzx, Operand::Reg(X64Reg::Eax), Operand::Reg(X64Reg::Al)),
+            X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::R11), Operand::Imm(0x0101010101010101)),
+            X64MCInstr::with2(Mnemonic::Imul, Operand::Reg(X64Reg::Rax), Operand::Reg(X64Reg::R11)),
+        ]);
+    }
+
+    let to = x64_mem_base(sink, dst);
+
+    for (off, ty) in x64_mem_chunks(size) {
+        sink.push(X64MCInstr::with2(Mnemonic::Mov, x64_mem_at(to, off), Operand::Reg(X64Reg::Rax.sub_ty(ty))));
+    }
+}
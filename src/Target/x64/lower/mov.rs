@@ -2,7 +2,18 @@ use crate::CodeGen::MachineInstr;
 use crate::Target::x64::X64Reg;
 use crate::Target::x64::asm::instr::*;
 
+use super::wide::x64_lower_wide_move;
+use super::vector::x64_lower_vector_move;
+
 pub(crate) fn x64_lower_move(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+    if instr.meta.wide() {
+        return x64_lower_wide_move(sink, instr);
+    }
+
+    if instr.meta.vector() {
+        return x64_lower_vector_move(sink, instr);
+    }
+
     let op1 = instr.operands.get(0).expect("expected a first operand");
     let out = instr.out.expect("expected a output operand");
 
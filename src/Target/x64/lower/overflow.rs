@@ -0,0 +1,64 @@
+use crate::prelude::OverflowKind;
+use crate::CodeGen::MachineInstr;
+use crate::Target::x64::X64Reg;
+use crate::Target::x64::asm::instr::*;
+
+// The arithmetic is done in rax (multiplications get their right operand through r11)
+// and the overflow flag is read out of the flags register with seto (signed) or setb (unsigned)
+
+/// Lowers the overflow checked add, sub and mul
+pub(crate) fn x64_lower_overflow(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, kind: &OverflowKind) {
+    if instr.meta.wide() {
+        todo!("overflow checked arithmetic on 128bit values")
+    }
+
+    let ls = instr.operands.get(0).expect("expected a left operand for the overflow checked arithmetic");
+    let rs = instr.operands.get(1).expect("expected a right operand for the overflow checked arithmetic");
+    let overflow = instr.operands.get(2).expect("expected an overflow flag for the overflow checked arithmetic");
+    let out: Operand = instr.out.expect("expected an output for the overflow checked arithmetic").into();
+
+    let overflow = match (*overflow).into() {
+        Operand::Reg(reg) => Operand::Reg(reg.sub8()),
+        overflow => overflow,
+    };
+
+    let rax = || Operand::Reg(X64Reg::Rax.sub_ty(instr.meta));
+    let r11 = || Operand::Reg(X64Reg::R11.sub_ty(instr.meta));
+
+    sink.push(X64MCInstr::with2(Mnemonic::Mov, rax(), (*ls).into()));
+
+    match kind {
+        OverflowKind::SAdd | OverflowKind::UAdd => sink.push(X64MCInstr::with2(Mnemonic::Add, rax(), (*rs).into())),
+        OverflowKind::SSub | OverflowKind::USub => sink.push(X64MCInstr::with2(Mnemonic::Sub, rax(), (*rs).into())),
+        OverflowKind::SMul | OverflowKind::UMul => {
+            sink.push(X64MCInstr::with2(Mnemonic::Mov, r11(), (*rs).into()));
+
+            if *kind == OverflowKind::SMul && instr.meta.bitSize() > 8 {
+                sink.push(X64MCInstr::with2(Mnemonic::Imul, rax(), r11()));
+            } else {
+                // the one operand forms write the upper half into rdx (ah for bytes)
+                // push and pop don't change the flags
+                let save_rdx = instr.meta.bitSize() > 8;
+
+                if save_rdx {
+                    sink.push(X64MCInstr::with1(Mnemonic::Push, Operand::Reg(X64Reg::Rdx)));
+                }
+
+                let mnemonic = if kind.signed() { Mnemonic::Imul } else { Mnemonic::Mul };
+                sink.push(X64MCInstr::with1(mnemonic, r11()));
+
+                if save_rdx {
+                    sink.push(X64MCInstr::with1(Mnemonic::Pop, Operand::Reg(X64Reg::Rdx)));
+                }
+            }
+        },
+    }
+
+    let setcc = if kind.signed() { Mnemonic::Seto } else { Mnemonic::Setb };
+
+    sink.extend_from_slice(&[
+        X64MCInstr::with1(setcc, Operand::Reg(X64Reg::R11b)),
+        X64MCInstr::with2(Mnemonic::Mov, out, rax()),
+        X64MCInstr::with2(Mnemonic::Mov, overflow, Operand::Reg(X64Reg::R11b)),
+    ]);
+}
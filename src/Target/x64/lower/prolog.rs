@@ -1,4 +1,8 @@
-use crate::CodeGen::{MachineInstr, MachineOperand};
+use std::any::Any;
+
+use gimli::write::CallFrameInstruction;
+
+use crate::CodeGen::{MCInstr, MachineInstr, MachineOperand};
 use crate::Target::x64::X64Reg;
 use crate::Target::x64::asm::instr::*;
 
@@ -8,10 +12,11 @@ pub(crate) fn x64_lower_prolog(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr)
     for op in instr.operands.iter() { // we remove the stack_off
         let MachineOperand::Reg(crate::CodeGen::Reg::x64(callee_save)) = op else { continue; }; 
         
+        // callee saved xmm registers need to keep all 128 bits
         if callee_save.is_xmm() {
             sink.extend_from_slice(&[
-                X64MCInstr::with2(Mnemonic::Movq, Operand::Mem(MemOp { base: Some(X64Reg::Rsp), index: None, scale: 1, displ: 0, rip: false }), Operand::Reg(*callee_save)),
-                X64MCInstr::with2(Mnemonic::Sub, Operand::Reg(X64Reg::Rsp), Operand::Imm(8)),
+                X64MCInstr::with2(Mnemonic::Sub, Operand::Reg(X64Reg::Rsp), Operand::Imm(16)),
+                X64MCInstr::with2(Mnemonic::Movups, Operand::Mem(MemOp { base: Some(X64Reg::Rsp), index: None, scale: 1, displ: 0, rip: false }), Operand::Reg(*callee_save)),
             ]);
         } else {
             sink.push(X64MCInstr::with1(Mnemonic::Push, Operand::Reg(*callee_save)));
@@ -21,27 +26,95 @@ pub(crate) fn x64_lower_prolog(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr)
     let Some(MachineOperand::Imm(stack_off)) = instr.operands.get(0) else { panic!("expected valid stack_off for prolog")};
     let stack_off = *stack_off as i64;
 
-    if stack_off > 0 {
-        sink.push( X64MCInstr::with1(Mnemonic::Push, Operand::Reg(X64Reg::Rbp) ) );
-        sink.push( X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::Rbp), Operand::Reg(X64Reg::Rsp)  ) );
-        sink.push( X64MCInstr::with2(Mnemonic::Sub, Operand::Reg(X64Reg::Rbp),  Operand::Imm(stack_off)) );
+    sink.push( X64MCInstr::with1(Mnemonic::Push, Operand::Reg(X64Reg::Rbp) ) );
+    sink.push( X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::Rbp), Operand::Reg(X64Reg::Rsp)  ) );
+
+    // the stack slots live below rbp (the first one is at rbp - 8), so rsp is moved below them
+    // in order for calls to not overwrite them. rsp also needs to stay 16 byte aligned for calls:
+    // the return adress and each saved register moved it by 8 bytes (xmm registers by 16)
+    let saves = instr.operands.iter().filter(|op| matches!(op, MachineOperand::Reg(crate::CodeGen::Reg::x64(reg)) if !reg.is_xmm())).count() as i64;
+
+    let mut frame = (stack_off - 8 + 15) / 16 * 16;
+
+    if saves % 2 == 1 {
+        frame += 8;
     }
 
+    if frame > 0 {
+        sink.push( X64MCInstr::with2(Mnemonic::Sub, Operand::Reg(X64Reg::Rsp),  Operand::Imm(frame)) );
+    }
 }
 
 pub(crate) fn x64_lower_epilog(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+    sink.push( X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::Rsp), Operand::Reg(X64Reg::Rbp)  ) );
+    sink.push( X64MCInstr::with1(Mnemonic::Pop, Operand::Reg(X64Reg::Rbp) ) );
+
     for op in instr.operands.iter().rev() { // we remove the stack_off
         let MachineOperand::Reg(crate::CodeGen::Reg::x64(callee_save)) = op else { continue; }; 
         
         if callee_save.is_xmm() {
             sink.extend_from_slice(&[
-                X64MCInstr::with2(Mnemonic::Movq, Operand::Reg(*callee_save), Operand::Mem(MemOp { base: Some(X64Reg::Rsp), index: None, scale: 1, displ: 0, rip: false })),
-                X64MCInstr::with2(Mnemonic::Add, Operand::Reg(X64Reg::Rsp), Operand::Imm(8)),
+                X64MCInstr::with2(Mnemonic::Movups, Operand::Reg(*callee_save), Operand::Mem(MemOp { base: Some(X64Reg::Rsp), index: None, scale: 1, displ: 0, rip: false })),
+                X64MCInstr::with2(Mnemonic::Add, Operand::Reg(X64Reg::Rsp), Operand::Imm(16)),
             ]);
         } else {
             sink.push(X64MCInstr::with1(Mnemonic::Pop, Operand::Reg(*callee_save)));
         }
     }
+}
 
-    sink.push( X64MCInstr::with1(Mnemonic::Pop, Operand::Reg(X64Reg::Rbp) ) );
+/// Describes how the prolog changes the frame (for the `.eh_frame` of the function)
+/// 
+/// Returns the instructions with the code offset after which they apply.
+/// The epilogs don't need to be described, since nothing can unwind through them
+pub(crate) fn x64_prolog_cfi(prolog: &[Box<dyn MCInstr>]) -> Vec<(u32, CallFrameInstruction)> {
+    let mut cfi = Vec::new();
+
+    let mut offset = 0;
+    // the call pushed the return adress
+    let mut cfa = 8;
+    // if the cfa is still calculated relative to rsp
+    let mut rsp_based = true;
+
+    for instr in prolog {
+        offset += instr.encode().map(|(encoded, _)| encoded.len() as u32).unwrap_or(0);
+
+        let Some(instr) = (instr.as_ref() as &dyn Any).downcast_ref::<X64MCInstr>() else { continue };
+
+        match (instr.mnemonic, &instr.op1, &instr.op2) {
+            (Mnemonic::Push, Some(Operand::Reg(reg)), _) if rsp_based => {
+                cfa += 8;
+                cfi.push((offset, CallFrameInstruction::CfaOffset(cfa)));
+                cfi.push((offset, CallFrameInstruction::Offset(reg.dwarf(), -cfa)));
+            },
+            (Mnemonic::Sub, Some(Operand::Reg(X64Reg::Rsp)), Some(Operand::Imm(imm))) if rsp_based => {
+                cfa += *imm as i32;
+                cfi.push((offset, CallFrameInstruction::CfaOffset(cfa)));
+            },
+            (Mnemonic::Mov, Some(Operand::Reg(X64Reg::Rbp)), Some(Operand::Reg(X64Reg::Rsp))) => {
+                cfi.push((offset, CallFrameInstruction::CfaRegister(gimli::X86_64::RBP)));
+                rsp_based = false;
+            },
+            _ => {},
+        }
+    }
+
+    cfi
+}
+
+/// Returns how far the prolog moves rsp (the size of the frame without the return adress)
+pub(crate) fn x64_frame_size(prolog: &[Box<dyn MCInstr>]) -> u64 {
+    let mut size = 0;
+
+    for instr in prolog {
+        let Some(instr) = (instr.as_ref() as &dyn Any).downcast_ref::<X64MCInstr>() else { continue };
+
+        match (instr.mnemonic, &instr.op1, &instr.op2) {
+            (Mnemonic::Push, Some(Operand::Reg(_)), _) => size += 8,
+            (Mnemonic::Sub, Some(Operand::Reg(X64Reg::Rsp)), Some(Operand::Imm(imm))) => size += *imm as u64,
+            _ => {},
+        }
+    }
+
+    size
 }
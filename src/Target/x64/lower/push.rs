@@ -1,18 +1,55 @@
-use crate::CodeGen::MachineInstr;
+use crate::CodeGen::{MachineInstr, MachineOperand};
 use crate::Target::x64::X64Reg;
 use crate::Target::x64::asm::instr::*;
 
 pub(crate) fn x64_lower_push(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
     let input = instr.operands.get(0).expect("push needs an operand");
 
-    sink.push(
-        X64MCInstr::with1(Mnemonic::Push, (*input).into())
-    );
+    // every stack argument takes a quadword (the upper bytes of smaller ones are undefined)
+    match *input {
+        MachineOperand::Reg(_) => {
+            let Operand::Reg(reg) = (*input).into() else { unreachable!() };
 
-    sink.push(X64MCInstr::with2(Mnemonic::Sub, Operand::Reg(X64Reg::Rsp), Operand::Imm(8))); // for 16 byte alignment
+            if reg.is_xmm() {
+                sink.extend_from_slice(&[
+                    X64MCInstr::with2(Mnemonic::Sub, Operand::Reg(X64Reg::Rsp), Operand::Imm(8)),
+                    X64MCInstr::with2(Mnemonic::Movq, Operand::Mem(MemOp { base: Some(X64Reg::Rsp), index: None, scale: 1, displ: 0, rip: false }), Operand::Reg(reg)),
+                ]);
+            } else {
+                sink.push( X64MCInstr::with1(Mnemonic::Push, Operand::Reg(reg.sub64())) );
+            }
+        },
+        MachineOperand::Stack(..) => sink.push( X64MCInstr::with1(Mnemonic::Push, (*input).into()) ),
+        MachineOperand::Imm(imm) => {
+            let imm = if instr.meta == crate::IR::TypeMetadata::f32 {
+                (imm as f32).to_bits() as i64
+            } else if instr.meta.float() {
+                imm.to_bits() as i64
+            } else {
+                imm as i64
+            };
+
+            x64_push_imm(sink, imm);
+        },
+        MachineOperand::Int(int) => x64_push_imm(sink, int as i64),
+    }
+}
+
+fn x64_push_imm(sink: &mut Vec<X64MCInstr>, imm: i64) {
+    if imm == imm as i32 as i64 {
+        sink.push( X64MCInstr::with1(Mnemonic::Push, Operand::Imm(imm)) );
+    } else {
+        // push only sign extends an imm32
+        sink.extend_from_slice(&[
+            X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::Rax), Operand::Imm(imm)),
+            X64MCInstr::with1(Mnemonic::Push, Operand::Reg(X64Reg::Rax)),
+        ]);
+    }
 }
 
-pub(crate) fn x64_lower_push_cleanup(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
-    sink.push( X64MCInstr::with1(Mnemonic::Pop, Operand::Reg(X64Reg::Rax.sub_ty(instr.meta))));
-    sink.push(X64MCInstr::with2(Mnemonic::Add, Operand::Reg(X64Reg::Rsp), Operand::Imm(8))); // for 16 byte alignment
+/// Moves rsp by the size of the stack space of a call (`sub` reserves it and `add` frees it)
+pub(crate) fn x64_lower_call_stack(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, mnemonic: Mnemonic) {
+    let Some(MachineOperand::Imm(size)) = instr.operands.get(0) else { panic!("expected the size of the stack space") };
+
+    sink.push( X64MCInstr::with2(mnemonic, Operand::Reg(X64Reg::Rsp), Operand::Imm(*size as i64)) );
 }
@@ -4,10 +4,23 @@ use crate::Target::x64::X64Reg;
 use crate::IR::TypeMetadata;
 
 use super::fmove::x64_lower_fmove;
+use super::wide::x64_lower_wide_return;
 
 pub(crate) fn x64_lower_return(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
     let op = instr.operands.get(0).expect("return expectes operand");
 
+    if instr.meta.wide() {
+        return x64_lower_wide_return(sink, instr);
+    }
+
+    if let MachineOperand::Reg(Reg::x64(reg)) = op {
+        if reg.sub64() == X64Reg::Rax || *reg == X64Reg::Xmm0 {
+            // the value already is in the return register
+            sink.push( X64MCInstr::with0(Mnemonic::Ret).into() );
+            return;
+        }
+    }
+
     if instr.meta.float() {
         let mut instr = MachineInstr::new(MachineMnemonic::FMove);
         instr.add_operand(*op);
@@ -17,12 +30,18 @@ pub(crate) fn x64_lower_return(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr)
     } else {
         let mut mne = Mnemonic::Mov;
 
-        if instr.meta == TypeMetadata::ptr {
+        let op: Operand = (*op).into();
+
+        if instr.meta == TypeMetadata::ptr && matches!(op, Operand::Mem(_)) {
             mne = Mnemonic::Lea;
         }
 
-        sink.push( X64MCInstr::with2(mne, Operand::Reg(X64Reg::Rax.sub_ty(instr.meta)), (*op).into()));
+        sink.push( X64MCInstr::with2(mne, Operand::Reg(X64Reg::Rax.sub_ty(instr.meta)), op));
     }
 
     sink.push( X64MCInstr::with0(Mnemonic::Ret).into() );
 }
+
+pub(crate) fn x64_lower_trap(sink: &mut Vec<X64MCInstr>, _: &MachineInstr) {
+    sink.push( X64MCInstr::with0(Mnemonic::Ud2).into() );
+}
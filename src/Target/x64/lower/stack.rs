@@ -28,13 +28,27 @@ pub(crate) fn x64_lower_salloc(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr)
     }
 }
 
-pub(crate) fn x64_lower_store(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+pub(crate) fn x64_lower_store(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, align: Option<usize>) {
     let ptr = instr.out.expect("stack stores need a output");
     let value = instr.operands.get(0).expect("stack stores need one operand");
 
     let ptr = ptr.into();
     let value = (*value).into();
 
+    if instr.meta.vector() && instr.meta.byteSize() == 16 {
+        // vectors live on the stack, which isn't 16 byte aligned
+        sink.extend_from_slice(&[
+            X64MCInstr::with2(Mnemonic::Movups, Operand::Reg(X64Reg::Xmm15), Operand::Mem(x64_aggregate_adr(value))),
+            X64MCInstr::with2(x64_sse_mov(&ptr, align), Operand::Mem(x64_aggregate_adr(ptr)), Operand::Reg(X64Reg::Xmm15)),
+        ]);
+        return;
+    }
+
+    if instr.meta.aggregate() || instr.meta.wide() || instr.meta.vector() {
+        x64_lower_aggregate_copy(sink, x64_aggregate_adr(ptr), x64_aggregate_adr(value), instr.meta.byteSize());
+        return;
+    }
+
     if let Operand::Reg(ptr) = ptr {
         let ptr = Operand::Mem(MemOp {
             base: Some(ptr),
@@ -115,7 +129,7 @@ pub(crate) fn x64_lower_store(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr)
 
 }
 
-pub(crate) fn x64_lower_load(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+pub(crate) fn x64_lower_load(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, align: Option<usize>) {
     let out = instr.out.expect("stack stores need a output");
     let ptr = instr.operands.get(0).expect("stack stores need one operand");
 
@@ -123,6 +137,19 @@ pub(crate) fn x64_lower_load(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
     
     let out = out.into();
 
+    if instr.meta.vector() && instr.meta.byteSize() == 16 {
+        sink.extend_from_slice(&[
+            X64MCInstr::with2(x64_sse_mov(&ptr, align), Operand::Reg(X64Reg::Xmm15), Operand::Mem(x64_aggregate_adr(ptr))),
+            X64MCInstr::with2(Mnemonic::Movups, Operand::Mem(x64_aggregate_adr(out)), Operand::Reg(X64Reg::Xmm15)),
+        ]);
+        return;
+    }
+
+    if instr.meta.aggregate() || instr.meta.wide() || instr.meta.vector() {
+        x64_lower_aggregate_copy(sink, x64_aggregate_adr(out), x64_aggregate_adr(ptr), instr.meta.byteSize());
+        return;
+    }
+
     if let Operand::Reg(out_reg) = out {
         if !out_reg.is_xmm() {
             if let Operand::Reg(ptr) = ptr {
@@ -205,3 +232,57 @@ pub(crate) fn x64_lower_load(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
     }
 
 }
+
+/// Returns the sse move for 16 byte vectors (`movaps` faults on unaligned memory, so it's only
+/// used if the pointer is known to be aligned)
+/// 
+/// Stack slots are only 8 byte aligned, so they always use `movups` (whatever alignment the node claims)
+fn x64_sse_mov(ptr: &Operand, align: Option<usize>) -> Mnemonic {
+    match (ptr, align) {
+        (Operand::Reg(_), Some(16..)) => Mnemonic::Movaps,
+        _ => Mnemonic::Movups,
+    }
+}
+
+/// Returns the memory which is described by the operand
+/// (registers hold the adress, stack operands are the memory itself)
+fn x64_aggregate_adr(op: Operand) -> MemOp {
+    match op {
+        Operand::Reg(reg) => MemOp {
+            base: Some(reg),
+            index: None,
+            scale: 1,
+            displ: 0,
+            rip: false,
+        },
+        Operand::Mem(mem) => mem,
+        _ => panic!("aggregates can only be adressed over registers or the stack (got: {})", op),
+    }
+}
+
+/// Copies `size` bytes from `src` to `dst` (uses rax as the temporary)
+fn x64_lower_aggregate_copy(sink: &mut Vec<X64MCInstr>, dst: MemOp, src: MemOp, size: usize) {
+    let mut off = 0;
+
+    while off < size {
+        let ty = match size - off {
+            8.. => TypeMetadata::i64,
+            4..=7 => TypeMetadata::i32,
+            2..=3 => TypeMetadata::i16,
+            _ => TypeMetadata::i8,
+        };
+
+        let mut from = src.clone();
+        from.displ += off as isize;
+
+        let mut to = dst.clone();
+        to.displ += off as isize;
+
+        sink.extend_from_slice(&[
+            X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::Rax.sub_ty(ty)), Operand::Mem(from)),
+            X64MCInstr::with2(Mnemonic::Mov, Operand::Mem(to), Operand::Reg(X64Reg::Rax.sub_ty(ty))),
+        ]);
+
+        off += ty.byteSize();
+    }
+}
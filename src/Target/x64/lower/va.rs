@@ -0,0 +1,218 @@
+use crate::CodeGen::{MachineInstr, MachineOperand};
+use crate::Target::x64::X64Reg;
+use crate::Target::x64::asm::instr::*;
+use crate::Target::CallConv;
+use crate::IR::TypeMetadata;
+
+// On system v the va_list is a struct:
+//      u32 gp_offset           the offset of the next intenger argument in the register save area
+//      u32 fp_offset           the offset of the next float argument in the register save area
+//      ptr overflow_arg_area   the next argument which was passed over the stack
+//      ptr reg_save_area
+// The register save area holds rdi, rsi, rdx, rcx, r8, r9 and xmm0 - xmm7 (16 bytes each)
+// followed by the adress of the stack arguments.
+//
+// On windows the va_list is only a pointer to the next argument: every argument takes
+// 8 bytes and the argument registers are spilled into the home space (which is right in
+// front of the stack arguments). The save area only holds the adress of the home space.
+//
+// The va nodes use rax for the va_list pointer and r11 for everything else
+
+const X64_SYSV_ARG_GRS: [X64Reg; 6] = [X64Reg::Rdi, X64Reg::Rsi, X64Reg::Rdx, X64Reg::Rcx, X64Reg::R8, X64Reg::R9];
+const X64_SYSV_ARG_FPS: [X64Reg; 8] = [X64Reg::Xmm0, X64Reg::Xmm1, X64Reg::Xmm2, X64Reg::Xmm3, X64Reg::Xmm4, X64Reg::Xmm5, X64Reg::Xmm6, X64Reg::Xmm7];
+const X64_WIN_ARG_GRS: [X64Reg; 4] = [X64Reg::Rcx, X64Reg::Rdx, X64Reg::R8, X64Reg::R9];
+
+/// The offset of the saved float registers in the register save area
+const X64_SYSV_FPS_START: i64 = 48;
+/// The offset of the adress of the stack arguments in the register save area
+const X64_SYSV_FPS_END: i64 = 176;
+
+fn x64_va_at(base: X64Reg, displ: i64) -> Operand {
+    Operand::Mem(MemOp {
+        base: Some(base),
+        index: None,
+        scale: 1,
+        displ: displ as isize,
+        rip: false,
+    })
+}
+
+/// Returns the memory operand `off` bytes into the register save area
+fn x64_va_slot(area: &Operand, off: i64) -> Operand {
+    let Operand::Mem(area) = area else { panic!("expected the register save area to be on the stack") };
+
+    Operand::Mem(MemOp {
+        base: area.base,
+        index: area.index,
+        scale: area.scale,
+        displ: area.displ + off as isize,
+        rip: area.rip,
+    })
+}
+
+fn x64_va_imm(instr: &MachineInstr, index: usize) -> i64 {
+    match instr.operands.get(index) {
+        Some(MachineOperand::Imm(imm)) => *imm as i64,
+        _ => panic!("expected a constant operand for {}", instr.mnemonic),
+    }
+}
+
+pub(crate) fn x64_lower_va_save(conv: CallConv, sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+    let area: Operand = (*instr.operands.get(0).expect("va_save expects the register save area")).into();
+    let stack_args = x64_va_imm(instr, 1);
+
+    if conv == CallConv::WindowsFastCall {
+        for (index, reg) in X64_WIN_ARG_GRS.iter().enumerate() {
+            sink.push(X64MCInstr::with2(Mnemonic::Mov, x64_va_at(X64Reg::Rbp, stack_args + 8 * index as i64), Operand::Reg(*reg)));
+        }
+
+        sink.extend_from_slice(&[
+            X64MCInstr::with2(Mnemonic::Lea, Operand::Reg(X64Reg::R11), x64_va_at(X64Reg::Rbp, stack_args)),
+            X64MCInstr::with2(Mnemonic::Mov, area, Operand::Reg(X64Reg::R11)),
+        ]);
+        return;
+    }
+
+    for (index, reg) in X64_SYSV_ARG_GRS.iter().enumerate() {
+        sink.push(X64MCInstr::with2(Mnemonic::Mov, x64_va_slot(&area, 8 * index as i64), Operand::Reg(*reg)));
+    }
+
+    for (index, reg) in X64_SYSV_ARG_FPS.iter().enumerate() {
+        sink.push(X64MCInstr::with2(Mnemonic::Movsd, x64_va_slot(&area, X64_SYSV_FPS_START + 16 * index as i64), Operand::Reg(*reg)));
+    }
+
+    sink.extend_from_slice(&[
+        X64MCInstr::with2(Mnemonic::Lea, Operand::Reg(X64Reg::R11), x64_va_at(X64Reg::Rbp, stack_args)),
+        X64MCInstr::with2(Mnemonic::Mov, x64_va_slot(&area, X64_SYSV_FPS_END), Operand::Reg(X64Reg::R11)),
+    ]);
+}
+
+pub(crate) fn x64_lower_va_start(conv: CallConv, sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+    let list: Operand = (*instr.operands.get(0).expect("va_start expects a va_list")).into();
+    let area: Operand = (*instr.operands.get(1).expect("va_start expects the register save area")).into();
+
+    let named_grs = x64_va_imm(instr, 2);
+    let named_fps = x64_va_imm(instr, 3);
+
+    let rax = || Operand::Reg(X64Reg::Rax);
+    let r11 = || Operand::Reg(X64Reg::R11);
+
+    sink.push(X64MCInstr::with2(Mnemonic::Mov, rax(), list));
+
+    if conv == CallConv::WindowsFastCall {
+        // the first variadic argument comes right after the named ones
+        sink.extend_from_slice(&[
+            X64MCInstr::with2(Mnemonic::Mov, r11(), area),
+            X64MCInstr::with2(Mnemonic::Add, r11(), Operand::Imm(8 * named_grs)),
+            X64MCInstr::with2(Mnemonic::Mov, x64_va_at(X64Reg::Rax, 0), r11()),
+        ]);
+        return;
+    }
+
+    let gp_offset = (8 * named_grs).min(X64_SYSV_FPS_START);
+    let fp_offset = (X64_SYSV_FPS_START + 16 * named_fps).min(X64_SYSV_FPS_END);
+
+    sink.extend_from_slice(&[
+        X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::R11d), Operand::Imm(gp_offset)),
+        X64MCInstr::with2(Mnemonic::Mov, x64_va_at(X64Reg::Rax, 0), Operand::Reg(X64Reg::R11d)),
+        X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::R11d), Operand::Imm(fp_offset)),
+        X64MCInstr::with2(Mnemonic::Mov, x64_va_at(X64Reg::Rax, 4), Operand::Reg(X64Reg::R11d)),
+        X64MCInstr::with2(Mnemonic::Mov, r11(), x64_va_slot(&area, X64_SYSV_FPS_END)),
+        X64MCInstr::with2(Mnemonic::Mov, x64_va_at(X64Reg::Rax, 8), r11()),
+        X64MCInstr::with2(Mnemonic::Lea, r11(), area),
+        X64MCInstr::with2(Mnemonic::Mov, x64_va_at(X64Reg::Rax, 16), r11()),
+    ]);
+}
+
+pub(crate) fn x64_lower_va_arg(conv: CallConv, sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, stack: &str, load: &str) {
+    let list: Operand = (*instr.operands.get(0).expect("va_arg expects a va_list")).into();
+    let out: Operand = instr.out.expect("va_arg needs an output").into();
+
+    let ty = instr.meta;
+
+    let rax = || Operand::Reg(X64Reg::Rax);
+    let r11 = || Operand::Reg(X64Reg::R11);
+    let r11d = || Operand::Reg(X64Reg::R11d);
+
+    sink.push(X64MCInstr::with2(Mnemonic::Mov, rax(), list));
+
+    // r11 = the adress of the argument
+    if conv == CallConv::WindowsFastCall {
+        sink.extend_from_slice(&[
+            X64MCInstr::with2(Mnemonic::Mov, r11(), x64_va_at(X64Reg::Rax, 0)),
+            X64MCInstr::with2(Mnemonic::Add, r11(), Operand::Imm(8)),
+            X64MCInstr::with2(Mnemonic::Mov, x64_va_at(X64Reg::Rax, 0), r11()),
+            X64MCInstr::with2(Mnemonic::Sub, r11(), Operand::Imm(8)),
+        ]);
+    } else {
+        // the argument is in the register save area as long as there are registers left:
+        //      mov r11d, [rax + offset]
+        //      cmp r11d, limit
+        //      jae stack
+        //      (advance the offset and add the reg_save_area to it)
+        //      jmp load
+        // stack:
+        //      (advance the overflow_arg_area)
+        // load:
+        let (field, limit, step) = if ty.float() {
+            (4, X64_SYSV_FPS_END, 16)
+        } else {
+            (0, X64_SYSV_FPS_START, 8)
+        };
+
+        sink.extend_from_slice(&[
+            X64MCInstr::with2(Mnemonic::Mov, r11d(), x64_va_at(X64Reg::Rax, field)),
+            X64MCInstr::with2(Mnemonic::Cmp, r11d(), Operand::Imm(limit)),
+            X64MCInstr::with1(Mnemonic::Jae, Operand::BlockLinkDestination(stack.to_owned(), -4)),
+
+            X64MCInstr::with2(Mnemonic::Add, r11d(), Operand::Imm(step)),
+            X64MCInstr::with2(Mnemonic::Mov, x64_va_at(X64Reg::Rax, field), r11d()),
+            X64MCInstr::with2(Mnemonic::Add, r11(), x64_va_at(X64Reg::Rax, 16)),
+            X64MCInstr::with2(Mnemonic::Sub, r11(), Operand::Imm(step)),
+            X64MCInstr::with1(Mnemonic::Jmp, Operand::BlockLinkDestination(load.to_owned(), -4)),
+
+            X64MCInstr::with1(Mnemonic::Label, Operand::Debug(stack.to_owned())),
+            X64MCInstr::with2(Mnemonic::Mov, r11(), x64_va_at(X64Reg::Rax, 8)),
+            X64MCInstr::with2(Mnemonic::Add, r11(), Operand::Imm(8)),
+            X64MCInstr::with2(Mnemonic::Mov, x64_va_at(X64Reg::Rax, 8), r11()),
+            X64MCInstr::with2(Mnemonic::Sub, r11(), Operand::Imm(8)),
+
+            X64MCInstr::with1(Mnemonic::Label, Operand::Debug(load.to_owned())),
+        ]);
+    }
+
+    if ty.float() {
+        let mov = if ty == TypeMetadata::f32 { Mnemonic::Movss } else { Mnemonic::Movsd };
+
+        sink.extend_from_slice(&[
+            X64MCInstr::with2(mov, Operand::Reg(X64Reg::Xmm15), x64_va_at(X64Reg::R11, 0)),
+            X64MCInstr::with2(mov, out, Operand::Reg(X64Reg::Xmm15)),
+        ]);
+    } else {
+        let rax = Operand::Reg(X64Reg::Rax.sub_ty(ty));
+
+        sink.extend_from_slice(&[
+            X64MCInstr::with2(Mnemonic::Mov, rax.clone(), x64_va_at(X64Reg::R11, 0)),
+            X64MCInstr::with2(Mnemonic::Mov, out, rax),
+        ]);
+    }
+}
+
+pub(crate) fn x64_lower_va_copy(conv: CallConv, sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+    let dst: Operand = (*instr.operands.get(0).expect("va_copy expects a destination va_list")).into();
+    let src: Operand = (*instr.operands.get(1).expect("va_copy expects a source va_list")).into();
+
+    let size = if conv == CallConv::WindowsFastCall { 8 } else { 24 };
+
+    sink.extend_from_slice(&[
+        X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::Rax), src),
+        X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::R11), dst),
+    ]);
+
+    for off in (0..size).step_by(8) {
+        sink.extend_from_slice(&[
+            X64MCInstr::with2(Mnemonic::Movq, Operand::Reg(X64Reg::Xmm15), x64_va_at(X64Reg::Rax, off)),
+            X64MCInstr::with2(Mnemonic::Movq, x64_va_at(X64Reg::R11, off), Operand::Reg(X64Reg::Xmm15)),
+        ]);
+    }
+}
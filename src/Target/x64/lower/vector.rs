@@ -0,0 +1,86 @@
+use crate::CodeGen::{MachineInstr, MachineMnemonic};
+use crate::Target::x64::X64Reg;
+use crate::Target::x64::asm::instr::*;
+use crate::IR::TypeMetadata;
+
+// vectors always live on the stack (see `x64_alloc_rv`)
+// so they are loaded into xmm15 (and xmm14 for the second operand),
+// computed and then written back
+
+/// Returns the mnemonic which moves a vector of the type between memory and a xmm register
+fn x64_vector_mov(ty: TypeMetadata) -> Mnemonic {
+    match ty.byteSize() {
+        16 => Mnemonic::Movups,
+        8 => Mnemonic::Movq,
+        4 => Mnemonic::Movd,
+        _ => todo!("the vector {} doesn't fit into a single xmm register", ty),
+    }
+}
+
+/// Returns the packed sse instruction for the elementwise operation
+fn x64_vector_op(mnemonic: &MachineMnemonic, ty: TypeMetadata) -> Mnemonic {
+    let (elem, _) = ty.vectorElems().expect("expected a vector type");
+
+    match (mnemonic, elem) {
+        (MachineMnemonic::And, _) => Mnemonic::Pand,
+        (MachineMnemonic::Or, _) => Mnemonic::Por,
+        (MachineMnemonic::Xor, _) => Mnemonic::Pxor,
+
+        (MachineMnemonic::Add, TypeMetadata::f32) => Mnemonic::Addps,
+        (MachineMnemonic::Add, TypeMetadata::f64) => Mnemonic::Addpd,
+        (MachineMnemonic::Sub, TypeMetadata::f32) => Mnemonic::Subps,
+        (MachineMnemonic::Sub, TypeMetadata::f64) => Mnemonic::Subpd,
+        (MachineMnemonic::Mul, TypeMetadata::f32) => Mnemonic::Mulps,
+        (MachineMnemonic::Mul, TypeMetadata::f64) => Mnemonic::Mulpd,
+        (MachineMnemonic::Div, TypeMetadata::f32) => Mnemonic::Divps,
+        (MachineMnemonic::Div, TypeMetadata::f64) => Mnemonic::Divpd,
+
+        (MachineMnemonic::Add, elem) if !elem.float() => match elem.byteSize() {
+            1 => Mnemonic::Paddb,
+            2 => Mnemonic::Paddw,
+            4 => Mnemonic::Paddd,
+            _ => Mnemonic::Paddq,
+        },
+        (MachineMnemonic::Sub, elem) if !elem.float() => match elem.byteSize() {
+            1 => Mnemonic::Psubb,
+            2 => Mnemonic::Psubw,
+            4 => Mnemonic::Psubd,
+            _ => Mnemonic::Psubq,
+        },
+        (MachineMnemonic::Mul, TypeMetadata::u16 | TypeMetadata::i16) => Mnemonic::Pmullw,
+        (MachineMnemonic::Mul, TypeMetadata::u32 | TypeMetadata::i32) => Mnemonic::Pmulld,
+
+        _ => todo!("{} isn't supported for the vector {} yet (scalarise it)", mnemonic, ty),
+    }
+}
+
+pub(crate) fn x64_lower_vector_move(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+    let op = instr.operands.get(0).expect("expected a first operand");
+    let out = instr.out.expect("expected a output operand");
+
+    let mov = x64_vector_mov(instr.meta);
+
+    sink.extend_from_slice(&[
+        X64MCInstr::with2(mov, Operand::Reg(X64Reg::Xmm15), (*op).into()),
+        X64MCInstr::with2(mov, out.into(), Operand::Reg(X64Reg::Xmm15)),
+    ]);
+}
+
+/// Lowers the elementwise math instructions (add, sub, mul, div, and, or, xor) for vectors
+pub(crate) fn x64_lower_vector_math(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+    let op1 = instr.operands.get(0).expect("expected a first operand");
+    let op2 = instr.operands.get(1).expect("expected a second operand");
+    let out = instr.out.expect("expected a output operand");
+
+    let mov = x64_vector_mov(instr.meta);
+    let op = x64_vector_op(&instr.mnemonic, instr.meta);
+
+    // the legacy sse instructions require aligned memory operands
+    // but the stack slots aren't aligned to 16 bytes, so both operands are loaded
+    sink.extend_from_slice(&[
+        X64MCInstr::with2(mov, Operand::Reg(X64Reg::Xmm15), (*op1).into()),
+        X64MCInstr::with2(mov, Operand::Reg(X64Reg::Xmm14), (*op2).into()),
+        X64MCInstr::with2(op, Operand::Reg(X64Reg::Xmm15), Operand::Reg(X64Reg::Xmm14)),
+        X64MCInstr::with2(mov, out.into(), Operand::Reg(X64Reg::Xmm15)),
+    ]);
+}
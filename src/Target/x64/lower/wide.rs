@@ -0,0 +1,290 @@
+use crate::prelude::CmpMode;
+use crate::CodeGen::{MachineInstr, MachineOperand};
+use crate::Target::x64::X64Reg;
+use crate::Target::x64::asm::instr::*;
+use crate::IR::TypeMetadata;
+
+// 128bit intengers always live on the stack (see `x64_alloc_rv`)
+// so they are loaded into the register pair r11:rax (high:low),
+// computed and then written back
+
+fn x64_wide_halves(op: &MachineOperand, ty: TypeMetadata) -> (Operand, Operand) {
+    let (lo, hi) = op.halves(ty);
+
+    (lo.into(), hi.into())
+}
+
+fn x64_wide_load(sink: &mut Vec<X64MCInstr>, op: &MachineOperand, ty: TypeMetadata) {
+    let (lo, hi) = x64_wide_halves(op, ty);
+
+    sink.extend_from_slice(&[
+        X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::Rax), lo),
+        X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::R11), hi),
+    ]);
+}
+
+fn x64_wide_write(sink: &mut Vec<X64MCInstr>, out: &MachineOperand, ty: TypeMetadata) {
+    let (lo, hi) = x64_wide_halves(out, ty);
+
+    sink.extend_from_slice(&[
+        X64MCInstr::with2(Mnemonic::Mov, lo, Operand::Reg(X64Reg::Rax)),
+        X64MCInstr::with2(Mnemonic::Mov, hi, Operand::Reg(X64Reg::R11)),
+    ]);
+}
+
+pub(crate) fn x64_lower_wide_move(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+    let op = instr.operands.get(0).expect("expected a first operand");
+    let out = instr.out.expect("expected a output operand");
+
+    x64_wide_load(sink, op, instr.meta);
+    x64_wide_write(sink, &out, instr.meta);
+}
+
+/// Lowers add, sub, and, or and xor. The low quadwords are combined using `lo`,
+/// the high ones using `hi` (which consumes the carry of `lo` for add/sub)
+pub(crate) fn x64_lower_wide_math(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, lo: Mnemonic, hi: Mnemonic) {
+    let op1 = instr.operands.get(0).expect("expected a first operand");
+    let op2 = instr.operands.get(1).expect("expected a second operand");
+    let out = instr.out.expect("expected a output operand");
+
+    let mut op2 = *op2;
+
+    if let MachineOperand::Imm(_) | MachineOperand::Int(_) = op2 {
+        // the halves of the immediate may not fit into an imm32,
+        // so we place it into the output first
+        x64_wide_load(sink, &op2, instr.meta);
+        x64_wide_write(sink, &out, instr.meta);
+        op2 = out;
+    }
+
+    let (op2_lo, op2_hi) = x64_wide_halves(&op2, instr.meta);
+
+    x64_wide_load(sink, op1, instr.meta);
+
+    sink.extend_from_slice(&[
+        X64MCInstr::with2(lo, Operand::Reg(X64Reg::Rax), op2_lo),
+        X64MCInstr::with2(hi, Operand::Reg(X64Reg::R11), op2_hi),
+    ]);
+
+    x64_wide_write(sink, &out, instr.meta);
+}
+
+pub(crate) fn x64_lower_wide_shl(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+    x64_lower_wide_shift(sink, instr, true);
+}
+
+pub(crate) fn x64_lower_wide_shr(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+    x64_lower_wide_shift(sink, instr, false);
+}
+
+fn x64_lower_wide_shift(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, left: bool) {
+    // shl:                     shr:
+    // shld r11, rax, cl        shrd rax, r11, cl
+    // sal rax, cl              shr/sar r11, cl
+    // rdx = 0                  rdx = 0 / r11 >> 63
+    //
+    // if (cl & 64) != 0:
+    //  r11 = rax               rax = r11
+    //  rax = rdx               r11 = rdx
+    let op1 = instr.operands.get(0).expect("expected a first operand");
+    let op2 = instr.operands.get(1).expect("expected a second operand");
+    let out = instr.out.expect("expected a output operand");
+
+    let (amount, _) = x64_wide_halves(op2, instr.meta);
+
+    let rax = || Operand::Reg(X64Reg::Rax);
+    let r11 = || Operand::Reg(X64Reg::R11);
+    let rcx = || Operand::Reg(X64Reg::Rcx);
+    let rdx = || Operand::Reg(X64Reg::Rdx);
+
+    sink.extend_from_slice(&[
+        X64MCInstr::with1(Mnemonic::Push, rcx()),
+        X64MCInstr::with1(Mnemonic::Push, rdx()),
+    ]);
+
+    x64_wide_load(sink, op1, instr.meta);
+
+    sink.push( X64MCInstr::with2(Mnemonic::Mov, rcx(), amount) );
+
+    let (low, high) = if left {
+        sink.extend_from_slice(&[
+            X64MCInstr::with3(Mnemonic::Shld, r11(), rax(), Operand::Reg(X64Reg::Cl)),
+            X64MCInstr::with2(Mnemonic::Sal, rax(), Operand::Reg(X64Reg::Cl)),
+            X64MCInstr::with2(Mnemonic::Mov, rdx(), Operand::Imm(0)),
+        ]);
+
+        (r11(), rax())
+    } else {
+        sink.push( X64MCInstr::with3(Mnemonic::Shrd, rax(), r11(), Operand::Reg(X64Reg::Cl)) );
+
+        if instr.meta.signed() {
+            sink.extend_from_slice(&[
+                X64MCInstr::with2(Mnemonic::Sar, r11(), Operand::Reg(X64Reg::Cl)),
+                X64MCInstr::with2(Mnemonic::Mov, rdx(), r11()),
+                X64MCInstr::with2(Mnemonic::Sar, rdx(), Operand::Imm(63)),
+            ]);
+        } else {
+            sink.extend_from_slice(&[
+                X64MCInstr::with2(Mnemonic::Shr, r11(), Operand::Reg(X64Reg::Cl)),
+                X64MCInstr::with2(Mnemonic::Mov, rdx(), Operand::Imm(0)),
+            ]);
+        }
+
+        (rax(), r11())
+    };
+
+    // the shift instructions only use the lower 6 bits of cl
+    sink.extend_from_slice(&[
+        X64MCInstr::with2(Mnemonic::And, rcx(), Operand::Imm(64)),
+        X64MCInstr::with2(Mnemonic::Cmovne, low, high.clone()),
+        X64MCInstr::with2(Mnemonic::Cmovne, high, rdx()),
+    ]);
+
+    x64_wide_write(sink, &out, instr.meta);
+
+    sink.extend_from_slice(&[
+        X64MCInstr::with1(Mnemonic::Pop, rdx()),
+        X64MCInstr::with1(Mnemonic::Pop, rcx()),
+    ]);
+}
+
+pub(crate) fn x64_lower_wide_zext(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, from: TypeMetadata, signed: bool) {
+    let op = instr.operands.get(0).expect("expected a first operand");
+    let out = instr.out.expect("expected a output operand");
+
+    if let MachineOperand::Imm(imm) = op {
+        let value = if signed { *imm as i64 as i128 } else { *imm as u64 as i128 };
+
+        x64_wide_load(sink, &MachineOperand::Int(value), instr.meta);
+        x64_wide_write(sink, &out, instr.meta);
+        return;
+    }
+
+    let op: Operand = (*op).into();
+
+    let bits = from.bitSize();
+
+    if bits >= 64 {
+        sink.push( X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::Rax), op.clone()) );
+
+        if signed {
+            sink.push( X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::R11), op) );
+        }
+    } else {
+        // mov eax, ... zeros the upper half of rax, mov ax/al doesn't
+        sink.extend_from_slice(&[
+            X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::Rax), Operand::Imm(0)),
+            X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::Rax.sub_ty(from)), op),
+        ]);
+
+        if signed {
+            sink.extend_from_slice(&[
+                X64MCInstr::with2(Mnemonic::Sal, Operand::Reg(X64Reg::Rax), Operand::Imm(64 - bits as i64)),
+                X64MCInstr::with2(Mnemonic::Sar, Operand::Reg(X64Reg::Rax), Operand::Imm(64 - bits as i64)),
+            ]);
+        }
+    }
+
+    if signed {
+        if bits < 64 {
+            sink.push( X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::R11), Operand::Reg(X64Reg::Rax)) );
+        }
+
+        sink.push( X64MCInstr::with2(Mnemonic::Sar, Operand::Reg(X64Reg::R11), Operand::Imm(63)) );
+    } else {
+        sink.push( X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::R11), Operand::Imm(0)) );
+    }
+
+    x64_wide_write(sink, &out, instr.meta);
+}
+
+pub(crate) fn x64_lower_wide_return(sink: &mut Vec<X64MCInstr>, _: &MachineInstr) {
+    // the value was already moved into rdx:rax (see `compile_ret`)
+    sink.push( X64MCInstr::with0(Mnemonic::Ret) );
+}
+
+pub(crate) fn x64_lower_wide_cmp(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, mode: CmpMode) {
+    // eq/ne:                   lt/ge (gt/le swap the operands):
+    // xor rax, rcx             cmp rax, rcx
+    // xor r11, rdx             sbb r11, rdx
+    // or rax, r11
+    //
+    // the result is set into al first, since the output may be rcx or rdx
+    let ls = instr.operands.get(0).expect("expected a first operand");
+    let rs = instr.operands.get(1).expect("expected a second operand");
+    let out = instr.out.expect("expected a output operand");
+
+    let rax = || Operand::Reg(X64Reg::Rax);
+    let r11 = || Operand::Reg(X64Reg::R11);
+    let rcx = || Operand::Reg(X64Reg::Rcx);
+    let rdx = || Operand::Reg(X64Reg::Rdx);
+
+    let (rs_lo, rs_hi) = x64_wide_halves(rs, instr.meta);
+
+    sink.extend_from_slice(&[
+        X64MCInstr::with1(Mnemonic::Push, rcx()),
+        X64MCInstr::with1(Mnemonic::Push, rdx()),
+        X64MCInstr::with2(Mnemonic::Mov, rcx(), rs_lo),
+        X64MCInstr::with2(Mnemonic::Mov, rdx(), rs_hi),
+    ]);
+
+    x64_wide_load(sink, ls, instr.meta);
+
+    let set = match mode {
+        CmpMode::Eqal | CmpMode::NotEqal => {
+            sink.extend_from_slice(&[
+                X64MCInstr::with2(Mnemonic::Xor, rax(), rcx()),
+                X64MCInstr::with2(Mnemonic::Xor, r11(), rdx()),
+                X64MCInstr::with2(Mnemonic::Or, rax(), r11()),
+            ]);
+
+            if mode == CmpMode::Eqal { Mnemonic::Sete } else { Mnemonic::Setne }
+        },
+        _ => {
+            let swap = matches!(mode, 
+                CmpMode::SignedGreaterThan | CmpMode::SignedLessThanOrEqual | 
+                CmpMode::UnsignedGreaterThan | CmpMode::UnsignedLessThanOrEqual
+            );
+
+            let (ls_lo, ls_hi, rs_lo, rs_hi) = if swap {
+                (rcx(), rdx(), rax(), r11())
+            } else {
+                (rax(), r11(), rcx(), rdx())
+            };
+
+            sink.extend_from_slice(&[
+                X64MCInstr::with2(Mnemonic::Cmp, ls_lo, rs_lo),
+                X64MCInstr::with2(Mnemonic::Sbb, ls_hi, rs_hi),
+            ]);
+
+            match mode {
+                CmpMode::SignedLessThan | CmpMode::SignedGreaterThan => Mnemonic::Setl,
+                CmpMode::SignedGreaterThanOrEqual | CmpMode::SignedLessThanOrEqual => Mnemonic::Setge,
+                CmpMode::UnsignedLessThan | CmpMode::UnsignedGreaterThan => Mnemonic::Setb,
+                CmpMode::UnsignedGreaterThanOrEqual | CmpMode::UnsignedLessThanOrEqual => Mnemonic::Setae,
+                mode => panic!("the compare mode {} can't compare integers", mode),
+            }
+        },
+    };
+
+    // mov doesn't change the flags
+    sink.extend_from_slice(&[
+        X64MCInstr::with2(Mnemonic::Mov, rax(), Operand::Imm(0)),
+        X64MCInstr::with1(set, Operand::Reg(X64Reg::Al)),
+        X64MCInstr::with1(Mnemonic::Pop, rdx()),
+        X64MCInstr::with1(Mnemonic::Pop, rcx()),
+    ]);
+
+    let src = match out {
+        MachineOperand::Reg(_) => rax(),
+        MachineOperand::Stack(_, ty) => Operand::Reg(X64Reg::Rax.sub_ty(ty)),
+        _ => panic!("expected a register or stack slot as the output of a compare"),
+    };
+
+    let out = match out.into() {
+        Operand::Reg(reg) => Operand::Reg(reg.sub64()),
+        out => out,
+    };
+
+    sink.push( X64MCInstr::with2(Mnemonic::Mov, out, src) );
+}
@@ -1,37 +1,81 @@
-use crate::CodeGen::MachineInstr;
+use crate::CodeGen::{MachineInstr, MachineOperand};
+use crate::IR::TypeMetadata;
 use crate::Target::x64::X64Reg;
 use crate::Target::x64::asm::instr::*;
 
-pub(crate) fn x64_lower_zext(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
-    let op1 = instr.operands.get(0).expect("expected a first operand");
-    let out = instr.out.expect("expected a output operand");
+use super::wide::x64_lower_wide_zext;
 
-    let mut movxz = false;
+/// Sign or zero extends the integer `op` (of the type `from`) into `eax` (or `rax` if `wide` is set)
+/// and returns the register
+pub(super) fn x64_extend_rax(sink: &mut Vec<X64MCInstr>, op: &MachineOperand, from: TypeMetadata, signed: bool, wide: bool) -> X64Reg {
+    let dst = if wide { X64Reg::Rax } else { X64Reg::Eax };
 
-    let op1 = (*op1).into();
-    
-    let out = out.into();
+    let bits = from.bitSize() as u32;
 
-    if let Operand::Reg(op1) = op1 {
-        if let Operand::Reg(out) = out {
-            if (op1.is_gr16() | op1.is_gr8()) && (out.is_gr32() | out.is_gr64()) { // movxz allowes a gr8/16 zext into gr32/64
-                movxz = true;
-            }
+    if let MachineOperand::Imm(imm) = op {
+        let mut value = *imm as i64;
+
+        if bits < 64 {
+            value = if signed {
+                (value << (64 - bits)) >> (64 - bits)
+            } else {
+                value & ((1 << bits) - 1)
+            };
         }
+
+        if !wide {
+            value = value as i32 as i64;
+        }
+
+        sink.push(X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(dst), Operand::Imm(value)));
+        return dst;
     }
 
-    if movxz {
-        let mnemonic = if instr.meta.signed() { Mnemonic::Movsx } else { Mnemonic::Movzx };
+    let op: Operand = match *op {
+        MachineOperand::Reg(crate::CodeGen::Reg::x64(reg)) => Operand::Reg(reg.sub_ty(from)),
+        op => op.into(),
+    };
+
+    match bits {
+        64 => sink.push(X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::Rax), op)),
+        32 if signed && wide => sink.push(X64MCInstr::with2(Mnemonic::Movsxd, Operand::Reg(X64Reg::Rax), op)),
+        // mov eax, ... zeros the upper half of rax
+        32 => sink.push(X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(X64Reg::Eax), op)),
+        _ => {
+            let src = Operand::Reg(X64Reg::Rax.sub_ty(from));
+
+            sink.push(X64MCInstr::with2(Mnemonic::Mov, src.clone(), op));
 
-        let tmp = Operand::Reg(X64Reg::Rax.sub_ty(instr.meta).sub_ty(instr.meta));
+            if bits == 1 {
+                // i1 only has its lowest bit defined: 1 sign extends to -1
+                sink.extend_from_slice(&[
+                    X64MCInstr::with2(Mnemonic::Movzx, Operand::Reg(dst), src),
+                    X64MCInstr::with2(Mnemonic::And, Operand::Reg(dst), Operand::Imm(1)),
+                ]);
 
-        sink.push(X64MCInstr::with2(mnemonic, tmp.clone(), op1));
-        sink.push(X64MCInstr::with2(Mnemonic::Mov, out, tmp));
-    } else {
-        let tmp = Operand::Reg(X64Reg::Rax.sub_ty(instr.meta).sub_ty(instr.meta));
+                if signed {
+                    sink.push(X64MCInstr::with1(Mnemonic::Neg, Operand::Reg(dst)));
+                }
+            } else {
+                let mnemonic = if signed { Mnemonic::Movsx } else { Mnemonic::Movzx };
 
-        sink.push(X64MCInstr::with2(Mnemonic::Mov, tmp.clone(), op1));
-        sink.push(X64MCInstr::with2(Mnemonic::Mov, out, tmp.clone()));
+                sink.push(X64MCInstr::with2(mnemonic, Operand::Reg(dst), src));
+            }
+        },
+    }
+
+    dst
+}
+
+pub(crate) fn x64_lower_zext(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, from: TypeMetadata, signed: bool) {
+    if instr.meta.wide() {
+        return x64_lower_wide_zext(sink, instr, from, signed);
     }
 
+    let op1 = instr.operands.get(0).expect("expected a first operand");
+    let out = instr.out.expect("expected a output operand");
+
+    x64_extend_rax(sink, op1, from, signed, instr.meta.bitSize() > 32);
+
+    sink.push(X64MCInstr::with2(Mnemonic::Mov, out.into(), Operand::Reg(X64Reg::Rax.sub_ty(instr.meta))));
 }
@@ -26,7 +26,12 @@ pub(crate) fn X64AsmOpt(instrs: &mut Vec<X64MCInstr>) {
     let mut index = 0;
 
     while index < instrs.len() {
-        if let Some(mut opt_instr) = X64MergeInstrs(&instrs[index..], 3) {
+        if instrs[index].mnemonic == Mnemonic::EndOptimization {
+            // skip everything (e.g: inline assembly) till the optimization is started again
+            while index < instrs.len() && instrs[index].mnemonic != Mnemonic::StartOptimization {
+                index += 1;
+            }
+        } else if let Some(mut opt_instr) = X64MergeInstrs(&instrs[index..], 3) {
             instrs.drain(index..index + 3);
 
             opt_instr.reverse();
@@ -97,12 +102,18 @@ fn X64MergeMove(instr0: &X64MCInstr, instr1: &X64MCInstr) -> Option<Vec<X64MCIns
         return None;
     }
 
+    // mov eax, edi + mov rcx, rax can't be merged into mov rcx, edi
+    if instr0.op1 != instr1.op2 {
+        return None;
+    }
+
     Some(vec![X64MCInstr {
         mnemonic: Mnemonic::Mov,
         op1: instr1.op1.clone(),
         op2: instr0.op2.clone(),
         op3: None,
         far: false,
+        lock: false,
     }])
 }
 
@@ -158,10 +169,11 @@ fn X64MergeAdd(instr0: &X64MCInstr, instr1: &X64MCInstr, instr2: &X64MCInstr) ->
     let ls = instr0.op2.clone();
     let rs = instr1.op2.clone();
 
-    if let Some(Operand::Reg(reg)) = out {
-        if !(reg.is_gr32() || reg.is_gr64()) {
-            return None;
-        }
+    // lea can only write into a register
+    let Some(Operand::Reg(reg)) = out else { return None };
+
+    if !(reg.is_gr32() || reg.is_gr64()) {
+        return None;
     }
 
     if let Some(Operand::Reg(reg)) = ls {
@@ -189,6 +201,7 @@ fn X64MergeAdd(instr0: &X64MCInstr, instr1: &X64MCInstr, instr2: &X64MCInstr) ->
             })),
             op3: None,
             far: false,
+            lock: false,
         }])
     } else if instr0.is_op2_imm() && instr1.is_op2_reg() {
         let imm = if let Some(Operand::Imm(imm)) = instr0.op1 { imm } else { unreachable!() };
@@ -205,6 +218,7 @@ fn X64MergeAdd(instr0: &X64MCInstr, instr1: &X64MCInstr, instr2: &X64MCInstr) ->
             })),
             op3: None,
             far: false,
+            lock: false,
         }])
     } else if instr0.is_op2_reg() && instr1.is_op2_imm() {
         let imm = if let Some(Operand::Imm(imm)) = instr1.op2 { imm } else { unreachable!() };
@@ -221,6 +235,7 @@ fn X64MergeAdd(instr0: &X64MCInstr, instr1: &X64MCInstr, instr2: &X64MCInstr) ->
             })),
             op3: None,
             far: false,
+            lock: false,
         }])
     } else { None }
 }
@@ -238,11 +253,17 @@ fn X64MergeBrCond(instr0: &X64MCInstr, instr1: &X64MCInstr, instr2: &X64MCInstr)
          instr0.is_setg()  || 
          instr0.is_setl()  || 
          instr0.is_setge() || 
-         instr0.is_setle()) {
+         instr0.is_setle() ||
+         instr0.is_seta()  ||
+         instr0.is_setae() ||
+         instr0.is_setb()  ||
+         instr0.is_setbe() ||
+         instr0.is_setp()  ||
+         instr0.is_setnp()) {
         return None;
     }
 
-    if !instr1.is_cmp() {
+    if !instr1.is_cmp() || instr1.op2 != Some(Operand::Imm(0)) {
         return None;
     }
 
@@ -257,6 +278,12 @@ fn X64MergeBrCond(instr0: &X64MCInstr, instr1: &X64MCInstr, instr2: &X64MCInstr)
         Mnemonic::Setl => Mnemonic::Jl,
         Mnemonic::Setge => Mnemonic::Jge,
         Mnemonic::Setle => Mnemonic::Jle,
+        Mnemonic::Seta => Mnemonic::Ja,
+        Mnemonic::Setae => Mnemonic::Jae,
+        Mnemonic::Setb => Mnemonic::Jb,
+        Mnemonic::Setbe => Mnemonic::Jbe,
+        Mnemonic::Setp => Mnemonic::Jp,
+        Mnemonic::Setnp => Mnemonic::Jnp,
         _ => unreachable!()
     };
 
@@ -266,5 +293,6 @@ fn X64MergeBrCond(instr0: &X64MCInstr, instr1: &X64MCInstr, instr2: &X64MCInstr)
         op2: None,
         op3: None,
         far: false,
+        lock: false,
     }])
 }
\ No newline at end of file
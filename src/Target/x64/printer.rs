@@ -29,6 +29,9 @@ impl AsmPrinter for X64AsmPrinter {
 
             let mut index = 0;
 
+            // where the prolog gets inserted
+            let mut prolog_at = None;
+
             for block in &func.blocks {
                 if index != 0 {
                     lines.push( format!(" {}:\n", block.name) );
@@ -37,19 +40,7 @@ impl AsmPrinter for X64AsmPrinter {
                 let asm_lines = registry.buildAsmForTarget(Arch::X86_64, &block, &func, module)?;
                 
                 if registry.requires_prolog(&func) && index == 0 {
-                    let mut helper = registry.getBackendForFuncOrFork(Arch::X86_64, &func).helper.expect("expected valid helper");
-
-                    let mut prolog = vec![];
-
-                    helper.compile_prolog(&mut prolog);
-
-                    let mc_instrs = helper.lower.unwrap()(call_conv, prolog);
-
-                    for instr in mc_instrs {
-                        for line in instr.dump()?  {
-                            lines.push( format!("\t{}\n", line));
-                        }
-                    }
+                    prolog_at = Some(lines.len());
                 }
 
                 for line in asm_lines {
@@ -58,6 +49,28 @@ impl AsmPrinter for X64AsmPrinter {
             
                 index += 1;
             }
+
+            // the prolog is compiled after all blocks, so the frame also
+            // contains the stack slots which are allocated in later blocks
+            if let Some(prolog_at) = prolog_at {
+                let mut helper = registry.getBackendForFuncOrFork(Arch::X86_64, &func).helper.expect("expected valid helper");
+
+                let mut prolog = vec![];
+
+                helper.compile_prolog(&mut prolog);
+
+                let mc_instrs = helper.lower.unwrap()(func.ty.call_conv.unwrap_or(call_conv), prolog);
+
+                let mut prolog_lines = vec![];
+
+                for instr in mc_instrs {
+                    for line in instr.dump()?  {
+                        prolog_lines.push( format!("\t{}\n", line));
+                    }
+                }
+
+                lines.splice(prolog_at..prolog_at, prolog_lines);
+            }
         }
 
         lines.push("section .rodata\n\n".to_string());
@@ -80,6 +93,32 @@ impl AsmPrinter for X64AsmPrinter {
             lines.push(format!("{}: \n{}", consta.name, data));
         }
 
+        lines.push("section .data\n\n".to_string());
+
+        for (_, global) in &module.globals {
+            if global.isZeroInit() || global.linkage == Linkage::Extern {
+                continue;
+            }
+
+            let mut data = String::new();
+
+            for byte in global.data(&registry.triple) {
+                data.push_str(&format!("\t.byte {:02x?}\n", byte));
+            }
+
+            lines.push(format!("{}: \n{}", global.name, data));
+        }
+
+        lines.push("section .bss\n\n".to_string());
+
+        for (_, global) in &module.globals {
+            if !global.isZeroInit() || global.linkage == Linkage::Extern {
+                continue;
+            }
+
+            lines.push(format!("{}: \n\t.zero {}\n", global.name, global.data(&registry.triple).len()));
+        }
+
         let mut out = String::new();
 
         for line in lines {
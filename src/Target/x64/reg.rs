@@ -37,7 +37,7 @@ impl X64Reg {
         use X64Reg::*;
         match string.to_ascii_lowercase().as_str() {
             "rax" => Some(Rax), "eax" => Some(Eax), "ax" => Some(Ax), "al" => Some(Al),
-            "rbx" => Some(R11), "ebx" => Some(Ebx), "bx" => Some(Bx), "bl" => Some(Bl),
+            "rbx" => Some(Rbx), "ebx" => Some(Ebx), "bx" => Some(Bx), "bl" => Some(Bl),
             "rcx" => Some(Rcx), "ecx" => Some(Ecx), "cx" => Some(Cx), "cl" => Some(Cl),
             "rdx" => Some(Rdx), "edx" => Some(Edx), "dx" => Some(Dx), "dl" => Some(Dl),
             "rsi" => Some(Rsi), "esi" => Some(Esi), "si" => Some(Si), "sil" => Some(Sil),
@@ -90,7 +90,7 @@ impl X64Reg {
         use X64Reg::*;
         match self {
             Rax | Eax | Ax | Al => Rax,
-            Rbx | Ebx | Bx | Bl => R11,
+            Rbx | Ebx | Bx | Bl => Rbx,
             Rcx | Ecx | Cx | Cl => Rcx,
             Rdx | Edx | Dx | Dl => Rdx,
             Rsi | Esi | Si | Sil => Rsi,
@@ -291,6 +291,31 @@ impl X64Reg {
         }
     }
     
+    /// Returns the dwarf register number of the general purpose register
+    pub(crate) fn dwarf(&self) -> gimli::Register {
+        use gimli::X86_64;
+
+        match self.sub64() {
+            X64Reg::Rax => X86_64::RAX,
+            X64Reg::Rbx => X86_64::RBX,
+            X64Reg::Rcx => X86_64::RCX,
+            X64Reg::Rdx => X86_64::RDX,
+            X64Reg::Rsi => X86_64::RSI,
+            X64Reg::Rdi => X86_64::RDI,
+            X64Reg::Rsp => X86_64::RSP,
+            X64Reg::Rbp => X86_64::RBP,
+            X64Reg::R8 => X86_64::R8,
+            X64Reg::R9 => X86_64::R9,
+            X64Reg::R10 => X86_64::R10,
+            X64Reg::R11 => X86_64::R11,
+            X64Reg::R12 => X86_64::R12,
+            X64Reg::R13 => X86_64::R13,
+            X64Reg::R14 => X86_64::R14,
+            X64Reg::R15 => X86_64::R15,
+            reg => panic!("{} has no general purpose dwarf register", reg),
+        }
+    }
+
     #[doc(hidden)]
     pub fn as_any(&self) -> &dyn std::any::Any {
         self
@@ -313,6 +338,8 @@ impl X64Reg {
 
         match self.sub64() {
             Rbx | Rbp | R12 | R13 | R14 | R15 | Rsp => true,
+            // rsi and rdi are only callee saved in the windows abi
+            Rsi | Rdi => call == CallConv::WindowsFastCall,
             _ => false,
         }
     }
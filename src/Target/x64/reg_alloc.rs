@@ -1,23 +1,101 @@
-use crate::CodeGen::{Allocator, MachineCallingConvention, Reg, VarLocation};
-use crate::prelude::{Alloca, Function, Ir, Phi, TypeMetadata};
-use crate::Target::Arch;
+use crate::CodeGen::{Allocator, ArgClass, MachineCallingConvention, Reg, VaLayout, VarLocation};
+use crate::prelude::{Alloca, AsmConstraint, FloatOp, FloatOpKind, Function, InlineAsm, Ir, Phi, TypeMetadata};
+use crate::Target::{Arch, CallConv, TargetFeature};
 use crate::IR::Var;
 
+use super::X64Reg;
+
 fn arg_prep(alloc: &mut Allocator, func: &Function, call: MachineCallingConvention) {
-    let mut index = 0;
+    let mut reg_args = 0;
+    let mut fp_reg_args = 0;
+
+    let arg_regs = call.args(Arch::X86_64, TypeMetadata::i64);
+    let fp_args = call.args(Arch::X86_64, TypeMetadata::f64);
+
+    // the offset of the next argument which is passed over the stack,
+    // in the windows abi the stack arguments follow the shadow space of the register arguments
+    let mut stack_args = if call.call_conv == CallConv::WindowsFastCall { 32 } else { 0 };
+
+    if call.return_in_memory(Arch::X86_64, func.ty.ret).expect("the calling convention is verified before compiling") {
+        // the adress of the returned aggregate is passed as a hidden first argument
+        let slot = x64_alloc_stack(alloc, TypeMetadata::ptr);
+
+        alloc.arg_stores.push((arg_regs[0], slot));
+        alloc.ret_ptr = Some(slot);
+
+        reg_args += 1;
+
+        if call.call_conv == CallConv::WindowsFastCall {
+            fp_reg_args += 1;
+        }
+    }
 
     for (name, ty) in &func.ty.args {
-        if let Some(reg) = call.arg(Arch::X86_64, *ty, index) {
+        if ty.aggregate() {
+            let slot = x64_alloc_stack(alloc, *ty);
+
+            agg_arg_prep(alloc, call, *ty, slot, (&mut reg_args, &mut fp_reg_args, &mut stack_args));
+
+            alloc.vars.insert(name.to_owned(), slot);
+            alloc.var_types.insert(name.to_owned(), *ty);
+
+            continue;
+        }
+
+        if ty.wide() {
+            // sysv passes 128bit intengers in two general purpose registers (low, high),
+            // they are stored into the stack slot of the argument after the prolog
+            if call.call_conv != CallConv::SystemV {
+                panic!("128bit intengers can only be passed as arguments in the system v calling convention");
+            }
+
+            let VarLocation::Mem(slot, _) = x64_alloc_stack(alloc, *ty) else { unreachable!() };
+
+            if let (Some(lo), Some(hi)) = (arg_regs.get(reg_args), arg_regs.get(reg_args + 1)) {
+                alloc.arg_stores.push((*lo, VarLocation::Mem(slot, TypeMetadata::i64)));
+                alloc.arg_stores.push((*hi, VarLocation::Mem(slot - 8, TypeMetadata::i64)));
+
+                reg_args += 2;
+            } else {
+                // if the pair doesn't fit into the remaining registers, it is passed 16 byte aligned over the stack
+                stack_args = (stack_args + 15) / 16 * 16;
+
+                alloc.arg_loads.push((stack_args, VarLocation::Mem(slot, TypeMetadata::i64)));
+                alloc.arg_loads.push((stack_args + 8, VarLocation::Mem(slot - 8, TypeMetadata::i64)));
+
+                stack_args += 16;
+            }
+
+            alloc.vars.insert(name.to_owned(), VarLocation::Mem(slot, *ty));
+            alloc.var_types.insert(name.to_owned(), *ty);
+
+            continue;
+        }
+
+        if ty.vector() {
+            unreachable!("vector arguments are rejected during verification");
+        }
+
+        if ty.tuple() {
+            todo!("tuples can only be returned and not passed as arguments");
+        }
+
+        let reg = if ty.float() { fp_args.get(fp_reg_args) } else { arg_regs.get(reg_args) };
+
+        if let Some(Reg::x64(reg)) = reg {
             // argument in an register
+            let reg = Reg::x64(reg.sub_ty(*ty));
 
             alloc.vars.insert(name.to_owned(), VarLocation::Reg(reg));
             alloc.var_types.insert(name.to_owned(), *ty);
 
+            let free = if ty.float() { &mut alloc.ffpregs } else { &mut alloc.fregs };
+
             let mut removeal_index = 0;
 
-            for freg in alloc.fregs.clone() {
+            for freg in free.clone() {
                 if freg.is(&reg) {
-                    alloc.fregs.remove(removeal_index);
+                    free.remove(removeal_index);
                     break;
                 }
 
@@ -25,15 +103,128 @@ fn arg_prep(alloc: &mut Allocator, func: &Function, call: MachineCallingConventi
             }
         } else {
             // argument on stack
-            todo!("x64 currently doesn't support arguments which are passed over the stack");
+            let slot = x64_alloc_stack(alloc, *ty);
+
+            alloc.arg_loads.push((stack_args, slot));
+
+            alloc.vars.insert(name.to_owned(), slot);
+            alloc.var_types.insert(name.to_owned(), *ty);
+
+            stack_args += 8;
         }
 
-        index += 1;
+        if call.call_conv == CallConv::WindowsFastCall {
+            // in the windows abi the position of the argument selects its register (no matter its type)
+            fp_reg_args += 1;
+            reg_args += 1;
+        } else if ty.float() {
+            fp_reg_args += 1;
+        } else {
+            reg_args += 1;
+        }
     }
 }
 
+/// Stores the aggregate argument (`ty`) into its `slot` after the prolog (the counters are the used
+/// general purpose and sse registers and the offset of the next stack argument)
+fn agg_arg_prep(alloc: &mut Allocator, call: MachineCallingConvention, ty: TypeMetadata, slot: VarLocation, counters: (&mut usize, &mut usize, &mut i64)) {
+    let (reg_args, fp_reg_args, stack_args) = counters;
+
+    let VarLocation::Mem(slot_off, _) = slot else { unreachable!() };
+
+    let classes = call.classify(Arch::X86_64, ty).expect("the calling convention is verified before compiling");
+
+    let arg_regs = call.args(Arch::X86_64, TypeMetadata::i64);
+    let fp_args = call.args(Arch::X86_64, TypeMetadata::f64);
+
+    if call.call_conv == CallConv::WindowsFastCall {
+        // the aggregate (or the pointer to its copy) is passed like an intenger
+        let value = if classes[0] == ArgClass::Memory { x64_alloc_stack(alloc, TypeMetadata::ptr) } else { VarLocation::Mem(slot_off, TypeMetadata::i64) };
+
+        if let Some(reg) = arg_regs.get(*reg_args) {
+            alloc.arg_stores.push((*reg, value));
+        } else {
+            alloc.arg_loads.push((*stack_args, value));
+            *stack_args += 8;
+        }
+
+        if classes[0] == ArgClass::Memory {
+            alloc.arg_copies.push((value, slot));
+        }
+
+        *reg_args += 1;
+        *fp_reg_args += 1;
+        return;
+    }
+
+    let ints = classes.iter().filter(|class| **class == ArgClass::Integer).count();
+    let sses = classes.iter().filter(|class| **class == ArgClass::Sse).count();
+
+    if !classes.contains(&ArgClass::Memory) && *reg_args + ints <= arg_regs.len() && *fp_reg_args + sses <= fp_args.len() {
+        // every eightbyte is passed in the next register of its class
+        for (index, class) in classes.iter().enumerate() {
+            let reg = if *class == ArgClass::Sse {
+                *fp_reg_args += 1;
+                fp_args[*fp_reg_args - 1]
+            } else {
+                *reg_args += 1;
+                arg_regs[*reg_args - 1]
+            };
+
+            alloc.arg_stores.push((reg, VarLocation::Mem(slot_off - 8 * index as i64, class.ty())));
+        }
+
+        return;
+    }
+
+    // if it doesn't fit into the remaining registers, it is copied onto the stack
+    if ty.align(&call.layout_triple(Arch::X86_64)) > 8 {
+        *stack_args = (*stack_args + 15) / 16 * 16;
+    }
+
+    for index in 0..ty.byteSize().div_ceil(8) as i64 {
+        alloc.arg_loads.push((*stack_args, VarLocation::Mem(slot_off - 8 * index, TypeMetadata::i64)));
+        *stack_args += 8;
+    }
+}
+
+/// Reserves the stack slot which the arguments of a variadic function are saved in
+fn va_prep(alloc: &mut Allocator, func: &Function, call: MachineCallingConvention) {
+    if !func.ty.any_args {
+        return;
+    }
+
+    let (size, named_grs, named_fps) = if call.call_conv == CallConv::WindowsFastCall {
+        // the argument registers are spilled into the home space,
+        // so only its adress needs to be saved
+        (8, func.ty.args.len(), func.ty.args.len())
+    } else {
+        // rdi, rsi, rdx, rcx, r8, r9, xmm0 - xmm7 and the adress of the stack arguments
+        let fps = func.ty.args.iter().filter(|(_, ty)| ty.float()).count();
+
+        (6 * 8 + 8 * 16 + 8, func.ty.args.len() - fps, fps)
+    };
+
+    alloc.epilog = true;
+
+    let save_area = alloc.stack_off + size - 8;
+    alloc.stack_off += size;
+
+    alloc.va = Some(VaLayout {
+        save_area: save_area,
+        named_grs: named_grs,
+        named_fps: named_fps,
+    });
+}
+
 pub(crate) fn x64_alloc(alloc: &mut Allocator, func: &Function) {
-    arg_prep(alloc, func, alloc.call);
+    let call = match func.ty.call_conv {
+        Some(call_conv) => MachineCallingConvention { call_conv: call_conv },
+        None => alloc.call,
+    };
+
+    arg_prep(alloc, func, call);
+    va_prep(alloc, func, call);
 
     // run phis
     for block in &func.blocks {
@@ -69,6 +260,11 @@ fn node_prep(alloc: &mut Allocator, node: &Box<dyn Ir>) {
 
     alloc.scopes.insert(node.dump(), scopes);
 
+    if may_call(alloc, node) {
+        // the prolog keeps rsp 16 byte aligned for the call
+        alloc.epilog = true;
+    }
+
     // handle specific nodes here (like alloca)
     if let Some(alloca) = node.as_any().downcast_ref::<Alloca>() {
         let location = x64_alloc_stack(alloc, alloca.inner2);
@@ -84,7 +280,11 @@ fn node_prep(alloc: &mut Allocator, node: &Box<dyn Ir>) {
         return;
     }
 
-    if let Some(out) = node.output() {
+    if let Some(asm) = node.as_any().downcast_ref::<InlineAsm>() {
+        asm_prep(alloc, asm);
+    }
+
+    for out in node.outputs() {
         let location = x64_alloc_rv(alloc, out.ty);
 
         alloc.vars.insert(out.name.to_owned(), location);
@@ -92,13 +292,56 @@ fn node_prep(alloc: &mut Allocator, node: &Box<dyn Ir>) {
     }
 }
 
+/// Returns if the node is (or could be) lowered into a call
+fn may_call(alloc: &Allocator, node: &Box<dyn Ir>) -> bool {
+    if node.is_call() || node.is_callindirect() || node.is_invoke() || node.is_statepoint() || node.is_memcpy() || node.is_memmove() || node.is_memset() {
+        return true;
+    }
+
+    // floor, ceil, trunc and round are calls into libm if sse4.1 isn't enabled (fma always is)
+    if let Some(op) = node.as_any().downcast_ref::<FloatOp>() {
+        return match op.kind {
+            FloatOpKind::Floor | FloatOpKind::Ceil | FloatOpKind::Trunc | FloatOpKind::Round => !alloc.features.contains(&TargetFeature::Sse4_1),
+            FloatOpKind::Fma => true,
+            _ => false,
+        };
+    }
+
+    // 128bit multiplications and divisions are runtime calls
+    (node.is_mul() || node.is_div() || node.is_rem()) && node.outputs().iter().any(|out| out.ty.wide())
+}
+
+/// Callee saved registers which are overwritten by inline assembly need to be saved in the prolog
+fn asm_prep(alloc: &mut Allocator, asm: &InlineAsm) {
+    for constraint in &asm.constraints {
+        let (AsmConstraint::OutFixed(reg) | AsmConstraint::InFixed(reg) | AsmConstraint::Clobber(reg)) = constraint else { continue };
+
+        let Some(reg) = X64Reg::parse(reg.to_owned()) else { continue };
+
+        if !reg.callee_saved(alloc.call.call_conv) || matches!(reg.sub64(), X64Reg::Rsp | X64Reg::Rbp) {
+            continue;
+        }
+
+        let reg = Reg::x64(reg.sub64());
+
+        if !Reg::contains_reg(reg, &alloc.callee_save_registers) {
+            alloc.callee_save_registers.push(reg);
+            alloc.epilog = true;
+        }
+    }
+}
+
 pub(crate) fn x64_alloc_rv(alloc: &mut Allocator, ty: TypeMetadata) -> VarLocation {
+    if ty.aggregate() || ty.wide() || ty.vector() || ty.tuple() { // aggregates, 128bit intengers, vectors and tuples never fit into a single register
+        return x64_alloc_stack(alloc, ty);
+    }
+
     let vec = if ty.float() { &mut alloc.ffpregs } else { &mut alloc.fregs }; // select free registers vec
 
     if let Some(reg) = vec.pop() {
         let Reg::x64(x64) = reg else { panic!("x64 reg alloc expects x64 regs") };
         
-        if x64.callee_saved(alloc.call.call_conv) {
+        if x64.callee_saved(alloc.call.call_conv) && !Reg::contains_reg(reg, &alloc.callee_save_registers) {
             alloc.callee_save_registers.push(Reg::x64(x64));
             alloc.epilog = true;
         }
@@ -115,8 +358,12 @@ pub(crate) fn x64_alloc_rv(alloc: &mut Allocator, ty: TypeMetadata) -> VarLocati
 pub(crate) fn x64_alloc_stack(alloc: &mut Allocator, ty: TypeMetadata) -> VarLocation {
     alloc.epilog = true;
 
-    let ret = VarLocation::Mem(alloc.stack_off, ty);    
-    alloc.stack_off += 8; // alignment
+    // the location points to the lowest adress of the allocated region
+    let size = (ty.byteSize() as i64 + 7) / 8 * 8;
+    let size = size.max(8); // alignment
+
+    let ret = VarLocation::Mem(alloc.stack_off + size - 8, ty);    
+    alloc.stack_off += size;
     
     ret
 }
@@ -24,4 +24,903 @@ pub fn ir_optimization() {
     func.BuildRet( val );
 
     //assert_eq!(module.dump(), "define i32 @add(i32 %0, i32 %1) {\n entry:\n\t%2 = call i32 cfunc i32 %0 i32 %1 \n\tadd = %3 i32 %2, %0\n\tret i32 %3\n\n}\ndeclare i32 @cfunc(i32 %0, i32 %1)\n\n".to_string());
-}
\ No newline at end of file
+}
+#[test]
+pub fn ir_aggregate_layout() {
+    let x64 = Triple::parse("x86_64-unknown-linux").unwrap();
+    let x86 = Triple::parse("x86-unknown-linux").unwrap();
+
+    let ty = TypeMetadata::newStruct(&[TypeMetadata::i32, TypeMetadata::i64, TypeMetadata::newArray(TypeMetadata::u8, 3)]);
+
+    assert_eq!(ty.size(&x64), 24);
+    assert_eq!(ty.align(&x64), 8);
+    assert_eq!(ty.fieldOffset(1, &x64), Some(8));
+    assert_eq!(ty.fieldOffset(2, &x64), Some(16));
+
+    assert_eq!(ty.size(&x86), 16);
+    assert_eq!(ty.align(&x86), 4);
+    assert_eq!(ty.fieldOffset(1, &x86), Some(4));
+
+    assert_eq!(ty, TypeMetadata::newStruct(&[TypeMetadata::i32, TypeMetadata::i64, TypeMetadata::newArray(TypeMetadata::u8, 3)]));
+    assert_eq!(ty.to_string(), "{i32, i64, [3 x u8]}");
+}
+#[test]
+pub fn ir_vector_types() {
+    let x64 = Triple::parse("x86_64-unknown-linux").unwrap();
+
+    let ty = TypeMetadata::newVector(TypeMetadata::f32, 4);
+
+    assert_eq!(ty.size(&x64), 16);
+    assert_eq!(ty.align(&x64), 16);
+    assert_eq!(ty.vectorElems(), Some((TypeMetadata::f32, 4)));
+    assert_eq!(ty.to_string(), "<4 x f32>");
+
+    let mut module = Module();
+    let fnty = FnTy(vec![TypeMetadata::ptr, TypeMetadata::ptr], TypeMetadata::f32);
+    let builder = module.add("vec", &fnty);
+    builder.addBlock("entry");
+
+    let ls = builder.BuildLoad(fnty.arg(0), ty);
+    let rs = builder.BuildLoad(fnty.arg(1), ty);
+
+    let shuffled = builder.BuildShuffleVector(ls, rs, &[0, 5, 2, 7]);
+    let elem = builder.BuildExtractElement(shuffled, 1);
+    builder.BuildRet(elem);
+
+    assert!(module.verify().is_ok());
+
+    let mut module = Module();
+    let fnty = FnTy(vec![ty], ty);
+    let builder = module.add("id", &fnty);
+    builder.addBlock("entry");
+    builder.BuildRet(fnty.arg(0));
+
+    assert!(matches!(module.verify(), Err(VerifyError::VectorArgUnsupported(vec)) if vec == ty));
+}
+#[test]
+pub fn ir_indirect_call() {
+    let mut module = Module();
+
+    let ty = FnTy(vec![TypeMetadata::i32, TypeMetadata::i32], TypeMetadata::i32);
+
+    let callee = module.add("callee", &ty);
+    callee.import();
+    let callee = callee.id();
+
+    let func = module.add("caller", &FnTy(vec![TypeMetadata::i32], TypeMetadata::i32));
+    func.addBlock("entry");
+
+    let arg = func.ty.arg(0);
+
+    let ptr = func.BuildAssign(&callee);
+    let val = func.BuildCallIndirect(ptr, &ty, vec![IROperand::Var(arg.clone()), IROperand::Var(arg)]);
+    func.BuildRet(val);
+
+    assert!(module.verify().is_ok());
+
+    let dump = module.dump();
+
+    assert!(dump.contains("= ptr callee"));
+    assert!(dump.contains("= call i32 %1 i32 %0 i32 %0"));
+}
+
+#[test]
+pub fn ir_globals() {
+    let mut module = Module();
+
+    let counter = module.addGlobal("counter", TypeMetadata::i32, Some(vec![1, 0, 0, 0]), ygen::Obj::Linkage::External).to_owned();
+    module.addGlobal("zeroed", TypeMetadata::i64, None, ygen::Obj::Linkage::Internal);
+
+    let func = module.add("bump", &FnTy(vec![], TypeMetadata::i32));
+    func.addBlock("entry");
+
+    let ptr = func.BuildAssign(&counter);
+    let val = func.BuildLoad(ptr.clone(), TypeMetadata::i32);
+    let val = func.BuildAdd(val, Type::i32(1));
+    func.BuildStore(ptr, val.clone());
+    func.BuildRet(val);
+
+    assert!(module.verify().is_ok());
+
+    let dump = module.dump();
+
+    assert!(dump.contains("global extern i32 counter = [1, 0, 0, 0]"));
+    assert!(dump.contains("global intern i64 zeroed\n"));
+    assert!(dump.contains("%0 = ptr counter"));
+}
+
+#[test]
+pub fn ir_unreachable() {
+    let mut module = Module();
+
+    let abort = module.add("abort", &FnTy(vec![], TypeMetadata::Void));
+    abort.import();
+    let abort = abort.id();
+
+    let func = module.add("fail", &FnTy(vec![], TypeMetadata::i32));
+    func.addBlock("entry");
+    func.BuildCall(&abort, vec![]);
+    func.BuildUnreachable();
+
+    let func = module.add("check", &FnTy(vec![], TypeMetadata::i32));
+    func.addBlock("entry");
+    func.BuildTrap();
+
+    assert!(module.verify().is_ok());
+
+    let dump = module.dump();
+
+    assert!(dump.contains("call void abort \n\tunreachable"));
+    assert!(dump.contains("entry:\n\ttrap"));
+
+    let func = module.add("missing", &FnTy(vec![], TypeMetadata::i32));
+    func.addBlock("entry");
+    func.BuildAdd(Type::i32(1), Type::i32(2));
+
+    assert_eq!(module.verify(), Err(VerifyError::MissingTerminator));
+}
+
+#[test]
+pub fn ir_conditions() {
+    let mut module = Module();
+
+    let func = module.add("select", &FnTy(vec![TypeMetadata::i32], TypeMetadata::i32));
+    func.addBlock("entry");
+    let cond = func.BuildAssign(Type::i1(true));
+    let out = func.BuildSelect(cond, Type::i32(1), Type::i32(2));
+    func.BuildRet(out);
+
+    assert!(module.verify().is_ok());
+
+    let func = module.add("bad_select", &FnTy(vec![TypeMetadata::i32], TypeMetadata::i32));
+    func.addBlock("entry");
+    let cond = func.BuildAssign(Type::i32(1));
+    let out = func.BuildSelect(cond, Type::i32(1), Type::i32(2));
+    func.BuildRet(out);
+
+    assert!(module.verify().is_err());
+
+    let mut module = Module();
+
+    let func = module.add("bad_br", &FnTy(vec![], TypeMetadata::i32));
+    let yes = func.addBlock("yes");
+    func.BuildRet(Type::i32(1));
+    let no = func.addBlock("no");
+    func.BuildRet(Type::i32(0));
+    func.addBlock("entry");
+    let cond = func.BuildAssign(Type::i32(1));
+    func.BuildBrCond(cond, &yes, &no);
+
+    assert!(module.verify().is_err());
+}
+
+#[test]
+pub fn ir_mem_intrinsics() {
+    let mut module = Module();
+
+    let ty = FnTy(vec![TypeMetadata::ptr, TypeMetadata::ptr, TypeMetadata::i64], TypeMetadata::Void);
+
+    let func = module.add("copy", &ty);
+    func.addBlock("entry");
+
+    func.BuildMemCpy(ty.arg(0), ty.arg(1), IROperand::Var(ty.arg(2)));
+    func.BuildMemMove(ty.arg(1), ty.arg(0), IROperand::Type(Type::i64(8)));
+    func.BuildMemSet(ty.arg(0), IROperand::Type(Type::u8(0)), IROperand::Var(ty.arg(2)));
+    func.BuildRet(Type::Void);
+
+    assert!(module.verify().is_ok());
+
+    let dump = module.dump();
+
+    assert!(dump.contains("memcpy %0, %1, i64 %2"));
+    assert!(dump.contains("memmove %1, %0, i64 8"));
+    assert!(dump.contains("memset %0, u8 0, i64 %2"));
+
+    let func = module.add("invalid", &ty);
+    func.addBlock("entry");
+
+    func.BuildMemSet(ty.arg(0), IROperand::Type(Type::i32(0)), IROperand::Var(ty.arg(2)));
+    func.BuildRet(Type::Void);
+
+    assert!(module.verify().is_err());
+}
+
+#[test]
+pub fn ir_atomics() {
+    let mut module = Module();
+
+    let ty = FnTy(vec![TypeMetadata::ptr, TypeMetadata::i32], TypeMetadata::i32);
+
+    let func = module.add("atomics", &ty);
+    func.addBlock("entry");
+
+    func.BuildAtomicStore(ty.arg(0), IROperand::Var(ty.arg(1)), AtomicOrdering::Release);
+    func.BuildAtomicRmw(AtomicRmwOp::Add, ty.arg(0), IROperand::Type(Type::i32(1)), AtomicOrdering::SeqCst);
+    func.BuildCmpXchg(ty.arg(0), IROperand::Type(Type::i32(2)), IROperand::Var(ty.arg(1)), AtomicOrdering::AcqRel);
+    func.BuildFence(AtomicOrdering::SeqCst);
+    let out = func.BuildAtomicLoad(ty.arg(0), TypeMetadata::i32, AtomicOrdering::Acquire);
+    func.BuildRet(out);
+
+    assert!(module.verify().is_ok());
+
+    let dump = module.dump();
+
+    assert!(dump.contains("store atomic i32 %1, %0 release"));
+    assert!(dump.contains("atomicrmw add %0, i32 1 seq_cst"));
+    assert!(dump.contains("cmpxchg %0, i32 2, i32 %1 acq_rel"));
+    assert!(dump.contains("fence seq_cst"));
+    assert!(dump.contains("load atomic i32 %0 acquire"));
+
+    let func = module.add("invalid", &ty);
+    func.addBlock("entry");
+
+    let out = func.BuildAtomicLoad(ty.arg(0), TypeMetadata::i32, AtomicOrdering::Release);
+    func.BuildRet(out);
+
+    assert!(module.verify().is_err());
+}
+
+#[test]
+pub fn ir_volatile_and_align() {
+    let mut module = Module();
+
+    let ty = FnTy(vec![TypeMetadata::ptr, TypeMetadata::i32], TypeMetadata::i32);
+
+    let func = module.add("mmio", &ty);
+    func.addBlock("entry");
+
+    func.BuildStoreWithFlags(ty.arg(0), IROperand::Var(ty.arg(1)), true, Some(4));
+    let out = func.BuildLoadWithFlags(ty.arg(0), TypeMetadata::i32, true, None);
+    func.BuildRet(out);
+
+    assert!(module.verify().is_ok());
+
+    let dump = module.dump();
+
+    assert!(dump.contains("store volatile i32 %1, %0, align 4"));
+    assert!(dump.contains("= load volatile i32 %0\n"));
+
+    let func = module.add("invalid", &ty);
+    func.addBlock("entry");
+
+    let out = func.BuildLoadWithFlags(ty.arg(0), TypeMetadata::i32, false, Some(3));
+    func.BuildRet(out);
+
+    assert!(module.verify().is_err());
+}
+
+#[test]
+pub fn ir_cast_kinds() {
+    let mut module = Module();
+
+    let ty = FnTy(vec![TypeMetadata::i32], TypeMetadata::i64);
+
+    let func = module.add("widen", &ty);
+    func.addBlock("entry");
+
+    let sext = func.BuildCast(ty.arg(0), TypeMetadata::i64);
+    let zext = func.BuildCastOp(CastKind::Zext, ty.arg(0), TypeMetadata::i64);
+    let out = func.BuildAdd(sext, zext);
+    func.BuildRet(out);
+
+    assert!(module.verify().is_ok());
+
+    let dump = module.dump();
+
+    assert!(dump.contains("sext i32 %0 to i64"));
+    assert!(dump.contains("zext i32 %0 to i64"));
+
+    let func = module.add("invalid", &ty);
+    func.addBlock("entry");
+
+    let out = func.BuildCastOp(CastKind::Trunc, ty.arg(0), TypeMetadata::i64);
+    func.BuildRet(out);
+
+    assert!(module.verify().is_err());
+}
+
+#[test]
+pub fn ir_cmp_modes() {
+    let mut module = Module();
+
+    let ty = FnTy(vec![TypeMetadata::f64, TypeMetadata::f64], TypeMetadata::i1);
+
+    let func = module.add("unordered", &ty);
+    func.addBlock("entry");
+
+    let out = func.BuildCmp(CmpMode::UnorderedLessThan, ty.arg(0), ty.arg(1));
+    func.BuildRet(out);
+
+    assert!(module.verify().is_ok());
+    assert!(module.dump().contains("cmp ult f64 %0, %1"));
+
+    let func = module.add("invalid", &ty);
+    func.addBlock("entry");
+
+    let out = func.BuildCmp(CmpMode::SignedLessThan, ty.arg(0), ty.arg(1));
+    func.BuildRet(out);
+
+    assert!(module.verify().is_err());
+}
+
+#[test]
+pub fn ir_bit_ops() {
+    let mut module = Module();
+
+    let ty = FnTy(vec![TypeMetadata::u32, TypeMetadata::u32], TypeMetadata::u32);
+
+    let func = module.add("bits", &ty);
+    func.addBlock("entry");
+
+    let count = func.BuildBitOp(BitOpKind::Popcnt, ty.arg(0));
+    let rotated = func.BuildRotl(count, ty.arg(1));
+    func.BuildRet(rotated);
+
+    assert!(module.verify().is_ok());
+
+    let dump = module.dump();
+
+    assert!(dump.contains("popcnt u32 %0"));
+    assert!(dump.contains("rotl u32 %2, %1"));
+
+    let func = module.add("invalid", &FnTy(vec![TypeMetadata::u8], TypeMetadata::u8));
+    func.addBlock("entry");
+
+    let out = func.BuildBitOp(BitOpKind::Bswap, Type::u8(1));
+    func.BuildRet(out);
+
+    assert!(module.verify().is_err());
+
+    // 128bit intengers can't be manipulated or rotated
+    let mut module = Module();
+
+    let ty = FnTy(vec![TypeMetadata::u128], TypeMetadata::u128);
+
+    let func = module.add("wide", &ty);
+    func.addBlock("entry");
+
+    let out = func.BuildBitOp(BitOpKind::Ctz, ty.arg(0));
+    func.BuildRet(out);
+
+    assert!(matches!(module.verify(), Err(VerifyError::InvalidBitOp(BitOpKind::Ctz, TypeMetadata::u128))));
+
+    let mut module = Module();
+
+    let func = module.add("wide", &ty);
+    func.addBlock("entry");
+
+    let out = func.BuildRotr(ty.arg(0), Type::u128(3));
+    func.BuildRet(out);
+
+    assert!(matches!(module.verify(), Err(VerifyError::InvalidRotate(TypeMetadata::u128))));
+}
+
+#[test]
+pub fn ir_overflow_ops() {
+    let mut module = Module();
+
+    let ty = FnTy(vec![TypeMetadata::i32, TypeMetadata::i32], TypeMetadata::i32);
+
+    let func = module.add("checked", &ty);
+    func.addBlock("entry");
+
+    let (sum, overflow) = func.BuildOverflowOp(OverflowKind::SAdd, ty.arg(0), ty.arg(1));
+    let flag = func.BuildCastOp(CastKind::Zext, overflow, TypeMetadata::i32);
+    let out = func.BuildAdd(sum, flag);
+    func.BuildRet(out);
+
+    assert!(module.verify().is_ok());
+
+    let dump = module.dump();
+
+    assert!(dump.contains("%2, %3 = sadd_overflow i32 %0, %1"));
+
+    let func = module.add("invalid", &FnTy(vec![TypeMetadata::f32], TypeMetadata::f32));
+    func.addBlock("entry");
+
+    let (out, _) = func.BuildOverflowOp(OverflowKind::UMul, Type::f32(1.0), Type::f32(2.0));
+    func.BuildRet(out);
+
+    assert!(module.verify().is_err());
+}
+
+#[test]
+pub fn ir_float_ops() {
+    let mut module = Module();
+
+    let ty = FnTy(vec![TypeMetadata::f64, TypeMetadata::f64], TypeMetadata::f64);
+
+    let func = module.add("math", &ty);
+    func.addBlock("entry");
+
+    let root = func.BuildFloatOp(FloatOpKind::Sqrt, vec![ty.arg(0)]);
+    let out = func.BuildFloatOp(FloatOpKind::Fma, vec![root, ty.arg(0), ty.arg(1)]);
+    func.BuildRet(out);
+
+    assert!(module.verify().is_ok());
+
+    let dump = module.dump();
+
+    assert!(dump.contains("%2 = sqrt f64 %0"));
+    assert!(dump.contains("%3 = fma f64 %2, %0, %1"));
+
+    let func = module.add("invalid", &FnTy(vec![TypeMetadata::f32], TypeMetadata::f32));
+    func.addBlock("entry");
+
+    let out = func.BuildFloatOp(FloatOpKind::Copysign, vec![IROperand::Type(Type::f32(1.0))]);
+    func.BuildRet(out);
+
+    assert!(module.verify().is_err());
+}
+
+#[test]
+pub fn ir_fn_attrs() {
+    let mut module = Module();
+
+    let abort = module.add("abort", &FnTy(vec![], TypeMetadata::Void));
+    abort.import();
+    abort.add_attr(FnAttr::NoReturn);
+    abort.add_attr(FnAttr::Cold);
+    abort.add_attr(FnAttr::NoReturn);
+
+    assert!(abort.has_attr(FnAttr::NoReturn));
+    assert!(abort.has_attr(FnAttr::Cold));
+    assert!(!abort.has_attr(FnAttr::Naked));
+    assert_eq!(abort.attrs().to_string(), "noreturn cold");
+
+    let dump = abort.dump();
+
+    assert!(dump.contains("declare void @abort() noreturn cold"));
+
+    let func = module.add("main", &FnTy(vec![], TypeMetadata::i32));
+    func.add_attr(FnAttr::NoInline);
+
+    assert!(func.has_attr(FnAttr::NoInline));
+    assert!(!func.has_attr(FnAttr::AlwaysInline));
+    assert!(func.dump().contains("@main() noinline {"));
+
+    for attr in [FnAttr::NoInline, FnAttr::AlwaysInline, FnAttr::NoReturn, FnAttr::Cold, FnAttr::Naked, FnAttr::ReadNone] {
+        assert_eq!(FnAttr::parse(&attr.to_string()), Some(attr));
+    }
+}
+
+#[test]
+pub fn ir_readnone_callee() {
+    let mut module = Module();
+
+    let ty = FnTy(vec![TypeMetadata::i32], TypeMetadata::i32);
+
+    let abs = module.add("abs", &ty);
+    abs.import();
+    abs.add_attr(FnAttr::ReadNone);
+    let abs = abs.id();
+
+    let func = module.add("main", &FnTy(vec![], TypeMetadata::i32));
+    func.addBlock("entry");
+    func.BuildCall(&abs, vec![IROperand::Type(Type::i32(-5))]); // unused (so it gets removed)
+    let out = func.BuildCall(&abs, vec![IROperand::Type(Type::i32(-7))]);
+    func.BuildRet(out);
+
+    // the call site isn't readnone, but the called function is declared readnone
+    let mut mngr = PassManager::new();
+    mngr.add(ygen::Optimizations::Passes::DeadNodeElimination());
+    module.runPassMngr(mngr);
+
+    let dump = module.dump();
+
+    assert!(!dump.contains("call i32 abs -5"));
+    assert!(dump.contains("call i32 abs -7"));
+}
+
+#[test]
+pub fn ir_naked_verify() {
+    let mut module = Module();
+
+    let ty = FnTy(vec![TypeMetadata::i32], TypeMetadata::i32);
+
+    let other = module.add("other", &ty);
+    other.import();
+    let other = other.id();
+
+    let func = module.add("alloca", &FnTy(vec![], TypeMetadata::i32));
+    func.add_attr(FnAttr::Naked);
+    func.addBlock("entry");
+    func.BuildAlloca(TypeMetadata::i32);
+    func.BuildRet(Type::i32(0));
+
+    assert!(matches!(module.verify(), Err(VerifyError::NakedFuncWithAlloca)));
+
+    let mut module = Module();
+
+    let func = module.add("call", &ty);
+    func.add_attr(FnAttr::Naked);
+    func.addBlock("entry");
+    let val = func.BuildCall(&other, vec![IROperand::Var(ty.arg(0))]);
+    let val = func.BuildAdd(val, ty.arg(0));
+    func.BuildRet(val);
+
+    assert!(matches!(module.verify(), Err(VerifyError::NakedFuncWithCall)));
+
+    let mut module = Module();
+
+    let func = module.add("tail", &ty);
+    func.add_attr(FnAttr::Naked);
+    func.addBlock("entry");
+    let val = func.BuildTailCall(&other, vec![IROperand::Var(ty.arg(0))]);
+    func.BuildRet(val);
+
+    assert!(module.verify().is_ok());
+}
+
+#[test]
+pub fn ir_call_conv() {
+    let mut module = Module();
+
+    let mut ty = FnTy(vec![TypeMetadata::i32], TypeMetadata::i32);
+    ty.set_call_conv(ygen::Target::CallConv::WindowsFastCall);
+
+    let callback = module.add("callback", &ty);
+    callback.addBlock("entry");
+    callback.BuildRet(ty.arg(0));
+
+    let callback = callback.id();
+
+    let func = module.add("main", &FnTy(vec![], TypeMetadata::i32));
+    func.addBlock("entry");
+    let out = func.BuildCall(&callback, vec![IROperand::Type(Type::i32(5))]);
+    func.BuildRet(out);
+
+    assert!(module.verify().is_ok());
+
+    let dump = module.dump();
+
+    assert!(dump.contains("win64 i32 @callback(i32 %0)"));
+    assert!(dump.contains("= call win64 i32 callback"));
+
+    // the windows calling convention can't pass 128bit intengers yet
+    let mut module = Module();
+
+    let mut ty = FnTy(vec![TypeMetadata::i128], TypeMetadata::Void);
+    ty.set_call_conv(ygen::Target::CallConv::WindowsFastCall);
+    module.add("wide", &ty).import();
+
+    assert!(module.verify().is_err());
+
+    // functions without a calling convention use the one of the target
+    let windows = Triple::parse("x86_64-pc-windows-msvc").unwrap();
+    let linux = Triple::parse("x86_64-unknown-linux").unwrap();
+
+    let ty = FnTy(vec![TypeMetadata::i128], TypeMetadata::Void);
+
+    let mut module = Module();
+    let func = module.add("wide", &ty);
+    func.addBlock("entry");
+    func.BuildRet(Type::Void);
+
+    assert!(module.verify().is_ok());
+    assert!(module.emitAsm(windows, &mut ygen::Target::initializeAllTargets(windows).unwrap()).is_err());
+    assert!(module.emitAsm(linux, &mut ygen::Target::initializeAllTargets(linux).unwrap()).is_ok());
+
+    // which is also the case for the called functions
+    let mut module = Module();
+    let wide = module.add("wide", &ty);
+    wide.import();
+    let wide = wide.id();
+
+    let func = module.add("main", &FnTy(vec![], TypeMetadata::Void));
+    func.addBlock("entry");
+    func.BuildCall(&wide, vec![IROperand::Type(Type::i128(1))]);
+    func.BuildRet(Type::Void);
+
+    assert!(module.verify().is_ok());
+    assert!(module.emitAsm(windows, &mut ygen::Target::initializeAllTargets(windows).unwrap()).is_err());
+
+    // aggregates can only be passed by value in the x64 calling conventions
+    let pair = TypeMetadata::newStruct(&[TypeMetadata::i32, TypeMetadata::i32]);
+    let mut ty = FnTy(vec![pair], TypeMetadata::Void);
+
+    let mut module = Module();
+    let func = module.add("pair", &ty);
+    func.addBlock("entry");
+    func.BuildRet(Type::Void);
+
+    assert!(module.verify().is_ok());
+    assert!(module.emitAsm(linux, &mut ygen::Target::initializeAllTargets(linux).unwrap()).is_ok());
+
+    ty.set_call_conv(ygen::Target::CallConv::WasmBasicCAbi);
+
+    let mut module = Module();
+    let func = module.add("pair", &ty);
+    func.addBlock("entry");
+    func.BuildRet(Type::Void);
+
+    assert!(module.verify().is_ok());
+    assert!(module.emitAsm(linux, &mut ygen::Target::initializeAllTargets(linux).unwrap()).is_err());
+}
+
+#[test]
+pub fn ir_va() {
+    let mut module = Module();
+
+    let mut ty = FnTy(vec![TypeMetadata::i32], TypeMetadata::i32);
+    ty.activate_dynamic_arguments();
+
+    let func = module.add("sum", &ty);
+    func.addBlock("entry");
+
+    let list = func.BuildAlloca(TypeMetadata::i64);
+    let copy = func.BuildAlloca(TypeMetadata::i64);
+    func.BuildVaStart(list.to_owned());
+    let val = func.BuildVaArg(list.to_owned(), TypeMetadata::i32);
+    func.BuildVaCopy(copy.to_owned(), list.to_owned());
+    func.BuildVaEnd(list);
+    func.BuildVaEnd(copy);
+    func.BuildRet(val);
+
+    assert!(module.verify().is_ok());
+
+    let dump = module.dump();
+
+    assert!(dump.contains("i32 @sum(i32 %0, ...)"));
+    assert!(dump.contains("va_start %1"));
+    assert!(dump.contains("%3 = va_arg i32 %1"));
+    assert!(dump.contains("va_copy %2, %1"));
+    assert!(dump.contains("va_end %2"));
+
+    let func = module.add("invalid", &FnTy(vec![], TypeMetadata::Void));
+    func.addBlock("entry");
+
+    let list = func.BuildAlloca(TypeMetadata::i64);
+    func.BuildVaStart(list);
+    func.BuildRet(Type::Void);
+
+    assert!(module.verify().is_err());
+}
+
+#[test]
+pub fn ir_inline_asm() {
+    let mut module = Module();
+
+    let ty = FnTy(vec![TypeMetadata::i32], TypeMetadata::i32);
+
+    let func = module.add("main", &ty);
+    func.addBlock("entry");
+
+    let out = func.BuildAsm(TypeMetadata::i32, "mov $0, $1\nadd $0, $2", "=r,r,i,~{rdx}", vec![IROperand::Var(ty.arg(0)), IROperand::Type(Type::i32(2))]);
+    func.BuildAsm(TypeMetadata::Void, "cpuid", "{eax},~{ebx},~{ecx},~{edx}", vec![IROperand::Type(Type::i32(0))]);
+    func.BuildRet(out.unwrap());
+
+    assert!(module.verify().is_ok());
+
+    let dump = module.dump();
+
+    assert!(dump.contains(r#"%1 = asm i32 "mov $0, $1\nadd $0, $2" "=r,r,i,~{rdx}" i32 %0 i32 2"#));
+    assert!(dump.contains(r#"asm "cpuid" "{eax},~{ebx},~{ecx},~{edx}" i32 0"#));
+
+    let func = module.add("invalid", &ty);
+    func.addBlock("entry");
+
+    // immediates need to be constants
+    let out = func.BuildAsm(TypeMetadata::i32, "mov $0, $1", "=r,i", vec![IROperand::Var(ty.arg(0))]);
+    func.BuildRet(out.unwrap());
+
+    assert!(module.verify().is_err());
+}
+
+#[test]
+pub fn ir_tail_call() {
+    let mut module = Module();
+
+    let ty = FnTy(vec![TypeMetadata::i32, TypeMetadata::i32], TypeMetadata::i32);
+
+    let func = module.add("sum", &ty);
+    let id = func.id();
+    func.addBlock("entry");
+
+    let next = func.BuildSub(ty.arg(0), Type::i32(1));
+    let acc = func.BuildAdd(ty.arg(1), ty.arg(0));
+    let out = func.BuildTailCall(&id, vec![IROperand::Var(next), IROperand::Var(acc)]);
+    func.BuildRet(out);
+
+    assert!(module.verify().is_ok());
+    assert!(module.dump().contains("%4 = tail call i32 sum"));
+
+    let mut mngr = PassManager::new();
+    mngr.add(ygen::Optimizations::Passes::TailRecursionElimination());
+    module.runPassMngr(mngr);
+
+    let dump = module.dump();
+
+    assert!(module.verify().is_ok());
+    assert!(dump.contains("entry_preheader:"));
+    assert!(dump.contains("br entry"));
+    assert!(!dump.contains("tail call"));
+
+    let func = module.add("invalid", &ty);
+    func.addBlock("entry");
+
+    // the result of the tail call needs to be returned directly
+    let out = func.BuildTailCall(&id, vec![IROperand::Var(ty.arg(0)), IROperand::Var(ty.arg(1))]);
+    let out = func.BuildAdd(out, Type::i32(1));
+    func.BuildRet(out);
+
+    assert!(module.verify().is_err());
+}
+
+#[test]
+pub fn ir_invoke() {
+    let mut module = Module();
+
+    let thrower = module.add("thrower", &FnTy(vec![TypeMetadata::i32], TypeMetadata::Void));
+    thrower.import();
+    let thrower = thrower.id();
+
+    let ty = FnTy(vec![TypeMetadata::i32], TypeMetadata::i32);
+
+    let func = module.add("guarded", &ty);
+    func.set_personality("__gxx_personality_v0");
+
+    let ok = func.addBlock("ok");
+    func.BuildRet(Type::i32(1));
+
+    let lpad = func.addBlock("lpad");
+    let (_, selector) = func.BuildLandingPadWithSelector(false, vec![Some("_ZTIi"), None]);
+    func.BuildRet(selector);
+
+    func.addBlock("entry");
+    func.BuildInvoke(&thrower, vec![IROperand::Var(ty.arg(0))], &ok, &lpad);
+
+    assert!(module.verify().is_ok());
+
+    let dump = module.dump();
+
+    assert!(dump.contains("personality @__gxx_personality_v0"));
+    assert!(dump.contains("= landingpad ptr catch @_ZTIi catch null"));
+    assert!(dump.contains("= invoke void thrower i32 %0 to ok unwind lpad"));
+
+    // the personality function is missing
+    let func = module.add("invalid", &ty);
+
+    let ok = func.addBlock("ok");
+    func.BuildRet(Type::i32(1));
+
+    let lpad = func.addBlock("lpad");
+    func.BuildLandingPad(true, vec![]);
+    func.BuildRet(Type::i32(0));
+
+    func.addBlock("entry");
+    func.BuildInvoke(&thrower, vec![IROperand::Var(ty.arg(0))], &ok, &lpad);
+
+    assert!(module.verify().is_err());
+
+    // the unwind block doesn't start with a landing pad
+    let func = module.add("invalid", &ty);
+    func.set_personality("__gxx_personality_v0");
+
+    let ok = func.addBlock("ok");
+    func.BuildRet(Type::i32(1));
+
+    func.addBlock("entry");
+    func.BuildInvoke(&thrower, vec![IROperand::Var(ty.arg(0))], &ok, &ok);
+
+    assert!(module.verify().is_err());
+}
+
+#[test]
+pub fn ir_statepoint() {
+    let mut module = Module();
+
+    let collect = module.add("collect", &FnTy(vec![], TypeMetadata::Void));
+    collect.import();
+    let collect = collect.id();
+
+    let ty = FnTy(vec![TypeMetadata::ptr, TypeMetadata::ptr], TypeMetadata::ptr);
+
+    let func = module.add("keep", &ty);
+
+    func.addBlock("entry");
+    func.BuildStatepoint(7, &collect, vec![], vec![ty.arg(0), ty.arg(1)]);
+    func.BuildRet(ty.arg(0));
+
+    assert!(module.verify().is_ok());
+
+    assert!(module.dump().contains("= statepoint 7 void collect gc [%0, %1]"));
+
+    // the gc roots need to be pointers
+    let ty = FnTy(vec![TypeMetadata::i64], TypeMetadata::i64);
+
+    let func = module.add("invalid", &ty);
+
+    func.addBlock("entry");
+    func.BuildStatepoint(7, &collect, vec![], vec![ty.arg(0)]);
+    func.BuildRet(ty.arg(0));
+
+    assert!(module.verify().is_err());
+}
+
+#[test]
+pub fn ir_tuple() {
+    let mut module = Module();
+
+    let pair = TypeMetadata::newTuple(&[TypeMetadata::i64, TypeMetadata::f64]);
+
+    let ty = FnTy(vec![TypeMetadata::i64, TypeMetadata::f64], pair);
+
+    let func = module.add("pair", &ty);
+
+    func.addBlock("entry");
+    let tuple = func.BuildTuple(vec![IROperand::Var(ty.arg(0)), IROperand::Var(ty.arg(1))]);
+    func.BuildExtractValue(tuple.to_owned(), 1);
+    func.BuildRet(tuple);
+
+    assert!(module.verify().is_ok());
+
+    assert!(module.dump().contains("= tuple i64 %0, f64 %1"));
+    assert!(module.dump().contains("= extractvalue (i64, f64) "));
+
+    // the values of a tuple need to fit into a register
+    let ty = FnTy(vec![TypeMetadata::i128], TypeMetadata::i128);
+
+    let func = module.add("invalid", &ty);
+
+    func.addBlock("entry");
+    func.BuildTuple(vec![IROperand::Var(ty.arg(0))]);
+    func.BuildRet(ty.arg(0));
+
+    assert!(module.verify().is_err());
+
+    // only two intengers and two floats can be returned
+    let mut module = Module();
+
+    let triple = TypeMetadata::newTuple(&[TypeMetadata::i64, TypeMetadata::i64, TypeMetadata::i64]);
+    module.add("triple", &FnTy(vec![], triple)).import();
+
+    assert!(module.verify().is_err());
+
+    // the windows calling convention can't return tuples
+    let mut module = Module();
+
+    let mut ty = FnTy(vec![], pair);
+    ty.set_call_conv(ygen::Target::CallConv::WindowsFastCall);
+    module.add("win_pair", &ty).import();
+
+    assert!(module.verify().is_err());
+}
+
+#[test]
+pub fn ir_metadata() {
+    let mut module = Module();
+
+    module.add_metadata(MetadataNode::new("producer", vec![MetadataValue::Str("ygen tests".into())]));
+
+    let ty = FnTy(vec![TypeMetadata::i32], TypeMetadata::i32);
+
+    let func = module.add("weights", &ty);
+    func.add_metadata(MetadataNode::new("span", vec![MetadataValue::Str("main.c".into()), MetadataValue::Int(12)]));
+
+    func.addBlock("entry");
+    func.BuildAdd(ty.arg(0), Type::i32(1)); // unused (so it gets removed)
+    let out = func.BuildAdd(ty.arg(0), Type::i32(2));
+    func.attach(MetadataNode::new("hint", vec![MetadataValue::Int(-1)]));
+    func.BuildRet(out);
+    func.attach(MetadataNode::new("scope", vec![MetadataValue::Ref("producer".into())]));
+
+    assert!(module.verify().is_ok());
+
+    let dump = module.dump();
+
+    assert!(dump.contains("!producer = [\"ygen tests\"]\n"));
+    assert!(dump.contains("@weights(i32 %0) !span [\"main.c\", 12] {"));
+    assert!(dump.contains("%2 = add i32 %0, 2 !hint [-1]\n"));
+    assert!(dump.contains("ret i32 %2 !scope [!producer]\n"));
+
+    // the metadata stays attached to its node when the nodes before it are removed
+    let mut mngr = PassManager::new();
+    mngr.add(ygen::Optimizations::Passes::DeadNodeElimination());
+    module.runPassMngr(mngr);
+
+    let dump = module.dump();
+
+    assert!(!dump.contains("= add i32 %0, 1"));
+    assert!(dump.contains("%2 = add i32 %0, 2 !hint [-1]\n\tret i32 %2 !scope [!producer]\n"));
+}
@@ -69,6 +69,45 @@ pub fn call() -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
+#[test]
+pub fn const_adrs() -> Result<(), Box<dyn Error>> {
+    let mut module = Module();
+
+    module.addConst("value").set(vec![42, 43, 44, 45]);
+
+    let table = module.addConst("table");
+    table.addBytes(&[1, 2, 3, 4, 5, 6, 7, 8]);
+    table.addAdr("value", 2);
+    let table = table.to_owned();
+
+    let ty = FnTy(vec![], TypeMetadata::i32);
+
+    let func = module.add("get", &ty);
+    func.extrn();
+    func.addBlock("entry");
+
+    let table = func.BuildAssign(&table);
+    let entry = func.BuildAdd(table, Type::ptr(8));
+    let adr = func.BuildLoad(entry, TypeMetadata::ptr);
+    let value = func.BuildLoad(adr, TypeMetadata::u8);
+    let value = func.BuildCast(value, TypeMetadata::i32);
+    func.BuildRet(value);
+
+    module.verify()?;
+
+    let mut funcs = module.jitMap(&mut initializeAllTargets(Triple::host())? )?;
+
+    let mut get: JitFunction<unsafe extern "C" fn() -> i32> = funcs.get_function("get").expect("hmm shouldn't happen");
+
+    unsafe {
+        let ret = get.call();
+
+        assert_eq!(ret, 44);
+    }
+
+    Ok(())
+}
+
 /*
 
 // NOT YET SUPPORTED
@@ -7,7 +7,7 @@ use std::error::Error;
 use ygen::prelude::{DebugNode, PassManager};
 use ygen::Optimizations::Passes;
 use ygen::Support::{ColorProfile, Colorize};
-use ygen::Target::initializeAllTargets;
+use ygen::Target::{initializeAllTargets, TargetFeature, TargetRegistry};
 use ygen::{Support::Cli, Target::Triple};
 use ygen::IR::parser::{gen::IrGen, lexer::IrLexer, parser::IrParser, semnatic::IrSemnatic};
 use ygen::IR::Module;
@@ -21,6 +21,7 @@ fn main() -> Result<(), Box<dyn Error>> {
     cli.add_opt("v", "version", "Displays the version");
 
     cli.add_arg("triple", "triple", "The target triple", /*required*/ false);
+    cli.add_arg("features", "target-features", "The instruction set extensions to use (e.g: sse4.1)", /*required*/ false);
 
     cli.add_opt("asm", "emit-assembly", "Instead of emitting generated machine code into the file, it will put the generated assembly there");
     cli.add_opt("asm-clr", "print-colored-assembly", "Prints out the generated assembly to stderr");
@@ -55,6 +56,26 @@ fn main() -> Result<(), Box<dyn Error>> {
         }
     };
 
+    let mut features = Vec::new();
+
+    if let Some(list) = cli.arg_val("features") {
+        for feature in list.split(',') {
+            match TargetFeature::parse(feature) {
+                Some(feature) => features.push(feature),
+                None => eprintln!("unknown target feature: {}", feature),
+            }
+        }
+    }
+
+    let registry = || -> Result<TargetRegistry, Box<dyn Error>> {
+        let mut registry = initializeAllTargets(triple)?;
+
+        for feature in &features {
+            registry.enableFeatureForTarget(triple.arch, *feature);
+        }
+
+        Ok(registry)
+    };
     
     let infile = cli.arg_val("in").expect("we said it was required");
     let outfile;
@@ -136,7 +157,7 @@ fn main() -> Result<(), Box<dyn Error>> {
 
     for stmt in &parser.out {
         match stmt {
-            ygen::IR::parser::parser::IrStmt::Func { name: _, ret: _, args: _, body, scope: _, location: _ } => {
+            ygen::IR::parser::parser::IrStmt::Func { name: _, ret: _, args: _, body, scope: _, attrs: _, call_conv: _, location: _, personality: _, metadata: _ } => {
                 for (_, block) in body {
                     for node in &block.body {
                         if let Some(dbg) = node.inst.as_any().downcast_ref::<DebugNode>() {
@@ -172,6 +193,7 @@ fn main() -> Result<(), Box<dyn Error>> {
                 "dne" | "dead_node" | "dead_node_elim" | "dead-node" | "dead-node-elimination" =>   Some( Passes::DeadNodeElimination() ),
                 "dbe" | "dead_block" | "dead_block_elim" | "dead-block" | "dead-block-elimination" =>   Some( Passes::DeadBlockElimination() ),
                 "instcombine" | "instrcombine" =>   Some( Passes::InstrCombine() ),
+                "tre" | "tail_rec" | "tail-rec" | "tail-recursion-elimination" =>   Some( Passes::TailRecursionElimination() ),
                 _ => {eprintln!("unkown pass: {}", pass); None },
             };
 
@@ -184,6 +206,7 @@ fn main() -> Result<(), Box<dyn Error>> {
     } else if cli.opt("O") {
         let mut opts = PassManager::new();
 
+        opts.add( Passes::TailRecursionElimination() );
         opts.add( Passes::InstrCombine() );
         opts.add( Passes::ConstantEvaluation() );
         opts.add( Passes::DeadBlockElimination() );
@@ -201,7 +224,7 @@ fn main() -> Result<(), Box<dyn Error>> {
     }
 
     if cli.opt("asm-clr") {
-        let asm = module.emitAsm(triple, &mut initializeAllTargets(triple)?)?;
+        let asm = module.emitAsm(triple, &mut registry()?)?;
 
         println!("{}", asm);
     }
@@ -209,13 +232,13 @@ fn main() -> Result<(), Box<dyn Error>> {
     let debug = cli.opt("g");
 
     if cli.opt("asm") {
-        let asm = module.emitAsm(triple, &mut initializeAllTargets(triple)?)?;
+        let asm = module.emitAsm(triple, &mut registry()?)?;
 
         outfile.write_all(asm.as_bytes())?
     } else {
         let (mut object, debug_registry) = module.emitMachineCode(
             triple, 
-            &mut initializeAllTargets(triple)?,
+            &mut registry()?,
             debug
         )?;
         object.debug = debug;